@@ -0,0 +1,173 @@
+//! `lintal bench`: time linting a corpus, to track performance as rules are
+//! added and to compare against checkstyle.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use lintal_linter::{AuditListener, Linter};
+
+/// How to run checkstyle for a baseline.
+pub(crate) struct Checkstyle<'a> {
+    /// The checkstyle `-all` jar.
+    pub(crate) jar: &'a Path,
+    pub(crate) config: &'a Path,
+    pub(crate) config_loc: Option<&'a Path>,
+}
+
+/// Discards every event; only the time taken matters.
+struct Quiet;
+
+impl AuditListener for Quiet {}
+
+/// Lint `files` `runs` times and print wall time, time per rule and peak
+/// memory, then the same wall times for checkstyle if given.
+pub(crate) fn run(
+    linter: &Linter,
+    files: &[PathBuf],
+    runs: usize,
+    checkstyle: Option<&Checkstyle<'_>>,
+) -> Result<()> {
+    anyhow::ensure!(runs > 0, "--runs must be at least 1");
+
+    let mut violations = 0;
+    let lintal = time_runs(runs, || {
+        violations = linter.audit(files, &mut Quiet).violations;
+        Ok(())
+    })?;
+    println!(
+        "lintal: {} file(s), {} rule(s), {violations} violation(s)",
+        files.len(),
+        linter.rules().len()
+    );
+    print_times(&lintal);
+    match peak_rss() {
+        Some(bytes) => println!("  peak RSS: {} MiB", bytes.div_ceil(1024 * 1024)),
+        None => println!("  peak RSS: unavailable on this platform"),
+    }
+
+    // Rules run in parallel over files, so these add up to more than the
+    // wall time on a multi-core machine
+    let mut timings = linter.rule_timings();
+    let total: Duration = timings.iter().map(|(_, elapsed)| *elapsed).sum();
+    timings.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    println!("\nTime per rule, summed over threads, per run:");
+    for (rule, elapsed) in timings {
+        let share = if total.is_zero() {
+            0.0
+        } else {
+            elapsed.as_secs_f64() / total.as_secs_f64() * 100.0
+        };
+        println!(
+            "  {:<32} {:>10} {share:>5.1}%",
+            rule,
+            format_duration(elapsed / u32::try_from(runs).unwrap_or(u32::MAX))
+        );
+    }
+
+    if let Some(checkstyle) = checkstyle {
+        let times = time_checkstyle(checkstyle, files, runs)?;
+        println!("\ncheckstyle: {}", checkstyle.jar.display());
+        print_times(&times);
+        let speedup = mean(&times).as_secs_f64() / mean(&lintal).as_secs_f64();
+        println!("  checkstyle / lintal mean wall time: {speedup:.1}x");
+    }
+
+    Ok(())
+}
+
+/// Wall time of each of `runs` calls to `f`.
+fn time_runs(runs: usize, mut f: impl FnMut() -> Result<()>) -> Result<Vec<Duration>> {
+    (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            f()?;
+            Ok(start.elapsed())
+        })
+        .collect()
+}
+
+/// Run checkstyle over `files` as `scripts/benchmark.py` does: one JVM per
+/// run, with the files passed in an argument file.
+fn time_checkstyle(
+    checkstyle: &Checkstyle<'_>,
+    files: &[PathBuf],
+    runs: usize,
+) -> Result<Vec<Duration>> {
+    let file_list = std::env::temp_dir().join(format!("lintal-bench-{}.txt", std::process::id()));
+    let list: Vec<String> = files
+        .iter()
+        .map(|file| file.display().to_string())
+        .collect();
+    std::fs::write(&file_list, list.join("\n"))
+        .with_context(|| format!("Failed to write {}", file_list.display()))?;
+
+    let mut command = Command::new("java");
+    if let Some(config_loc) = checkstyle.config_loc {
+        command.arg(format!("-Dconfig_loc={}", config_loc.display()));
+    }
+    command
+        .arg("-jar")
+        .arg(checkstyle.jar)
+        .arg("-c")
+        .arg(checkstyle.config)
+        .arg(format!("@{}", file_list.display()))
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+
+    // Checkstyle exits non-zero when it finds violations, so only failing to
+    // start it is an error
+    let times = time_runs(runs, || {
+        command
+            .status()
+            .map(drop)
+            .context("Failed to run checkstyle; is `java` on the PATH?")
+    });
+    let _ = std::fs::remove_file(&file_list);
+    times
+}
+
+fn print_times(times: &[Duration]) {
+    let min = times.iter().min().copied().unwrap_or_default();
+    let max = times.iter().max().copied().unwrap_or_default();
+    println!(
+        "  wall time: mean {}, min {}, max {} over {} run(s)",
+        format_duration(mean(times)),
+        format_duration(min),
+        format_duration(max),
+        times.len()
+    );
+}
+
+fn mean(times: &[Duration]) -> Duration {
+    times.iter().sum::<Duration>() / u32::try_from(times.len()).unwrap_or(u32::MAX)
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
+
+/// Peak resident set size of this process, in bytes.
+#[cfg(target_os = "linux")]
+fn peak_rss() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kib: u64 = line
+        .trim_start_matches("VmHWM:")
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn peak_rss() -> Option<u64> {
+    None
+}
@@ -0,0 +1,423 @@
+//! `lintal daemon`: keep the configured linter resident and lint on request,
+//! so a build that runs lintal for every action pays for loading the
+//! configuration once and only re-lints the files that changed. Their parse
+//! trees are kept too, so a changed file is parsed again incrementally.
+//!
+//! Clients connect to a Unix domain socket and send one JSON request per
+//! line; each gets one JSON line back:
+//!
+//! ```text
+//! {"method": "check", "paths": ["/work/src/main/java"]}
+//! {"violations": [...], "errors": [], "linted": 3, "cached": 120}
+//! {"method": "shutdown"}
+//! {"ok": true}
+//! ```
+//!
+//! Violations look as `lintal check --output-format json` prints them.
+//! Relative paths are resolved against the daemon's working directory.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use lintal_java_parser::ParseResult;
+use lintal_linter::{AuditEvent, AuditListener, FixPolicy, Linter, SourceError};
+use serde::{Deserialize, Serialize};
+
+use crate::{JsonViolation, collect_java_files, with_project_index};
+
+#[derive(Deserialize)]
+#[serde(tag = "method", rename_all = "lowercase")]
+enum Request {
+    /// Lint the Java files under `paths`.
+    Check { paths: Vec<PathBuf> },
+    /// Answer this request, then stop listening.
+    Shutdown,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Response<'a> {
+    Check {
+        violations: Vec<&'a JsonViolation>,
+        errors: Vec<FileError>,
+        /// Files linted for this request.
+        linted: usize,
+        /// Files whose violations were reused from an earlier request.
+        cached: usize,
+    },
+    Shutdown {
+        ok: bool,
+    },
+    Error {
+        error: String,
+    },
+}
+
+/// A file that could not be read, parsed or was over a limit.
+#[derive(Serialize)]
+struct FileError {
+    path: PathBuf,
+    message: String,
+}
+
+/// What a file's metadata said when it was linted. A file is linted again
+/// once this changes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Stamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl Stamp {
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified().ok()?,
+            len: metadata.len(),
+        })
+    }
+}
+
+struct Cached {
+    stamp: Stamp,
+    violations: Vec<JsonViolation>,
+}
+
+struct Daemon {
+    linter: Linter,
+    cache: HashMap<PathBuf, Cached>,
+    /// Parse trees of the files linted so far, by path.
+    trees: HashMap<PathBuf, ParseResult>,
+}
+
+/// Answer requests on `socket` until a client asks to shut down.
+///
+/// Clients are served one at a time; each request is linted in parallel as
+/// `lintal check` would.
+pub(crate) fn run(linter: Linter, socket: &Path) -> Result<()> {
+    let listener = bind(socket)?;
+    eprintln!("Listening on {}", socket.display());
+
+    let mut daemon = Daemon::new(linter);
+    for stream in listener.incoming() {
+        let stream = stream.context("Failed to accept a connection")?;
+        match daemon.serve(stream) {
+            Ok(true) => break,
+            Ok(false) => {}
+            // One client going away mid-request doesn't stop the daemon
+            Err(err) => tracing::warn!("connection failed: {err:#}"),
+        }
+    }
+
+    let _ = std::fs::remove_file(socket);
+    Ok(())
+}
+
+/// Listen on `socket`, replacing a socket file left behind by a daemon that
+/// is no longer running.
+fn bind(socket: &Path) -> Result<UnixListener> {
+    match UnixListener::bind(socket) {
+        Err(err) if err.kind() == ErrorKind::AddrInUse => {
+            anyhow::ensure!(
+                UnixStream::connect(socket).is_err(),
+                "A lintal daemon is already listening on {}",
+                socket.display()
+            );
+            std::fs::remove_file(socket)
+                .with_context(|| format!("Failed to remove stale {}", socket.display()))?;
+            UnixListener::bind(socket)
+        }
+        result => result,
+    }
+    .with_context(|| format!("Failed to listen on {}", socket.display()))
+}
+
+impl Daemon {
+    fn new(linter: Linter) -> Self {
+        Self {
+            linter,
+            cache: HashMap::new(),
+            trees: HashMap::new(),
+        }
+    }
+
+    /// Answer the requests of one client until it hangs up. Returns whether
+    /// it asked the daemon to shut down.
+    fn serve(&mut self, stream: UnixStream) -> Result<bool> {
+        let mut writer = stream.try_clone()?;
+        for line in BufReader::new(stream).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let request = serde_json::from_str::<Request>(&line);
+            let shutdown = matches!(request, Ok(Request::Shutdown));
+            let response = match request {
+                Ok(Request::Check { paths }) => self.check(&paths),
+                Ok(Request::Shutdown) => serde_json::to_string(&Response::Shutdown { ok: true }),
+                Err(err) => serde_json::to_string(&Response::Error {
+                    error: format!("invalid request: {err}"),
+                }),
+            }
+            .expect("responses serialize to JSON");
+            writeln!(writer, "{response}")?;
+            if shutdown {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Lint the files under `paths` that changed since they were last
+    /// linted and return the violations of all of them as a JSON response.
+    fn check(&mut self, paths: &[PathBuf]) -> serde_json::Result<String> {
+        let files = collect_java_files(paths);
+
+        if self.linter.needs_project_index() {
+            // A file's violations depend on every other file, so none are
+            // reused and the index is rebuilt over the files asked for
+            self.cache.clear();
+            let linter = std::mem::replace(&mut self.linter, Linter::new(Vec::new()));
+            self.linter = with_project_index(linter, &files);
+        }
+
+        // Stamped before linting, so a file changed while it is linted is
+        // linted again next time
+        let stamps: Vec<Option<Stamp>> = files.iter().map(|path| Stamp::of(path)).collect();
+        let is_stale: Vec<bool> = files
+            .iter()
+            .zip(&stamps)
+            .map(|(path, stamp)| match (stamp, self.cache.get(path)) {
+                (Some(stamp), Some(cached)) => cached.stamp != *stamp,
+                _ => true,
+            })
+            .collect();
+        let stale: Vec<PathBuf> = files
+            .iter()
+            .zip(&is_stale)
+            .filter(|(_, stale)| **stale)
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        let mut collector = Collector {
+            fix_policy: self.linter.fix_policy(),
+            results: HashMap::new(),
+        };
+        self.linter
+            .audit_incremental(&stale, &mut self.trees, &mut collector);
+        let mut results = collector.results;
+
+        let mut errors = Vec::new();
+        // Violations of files without a stamp, which can't be cached
+        let mut uncached = HashMap::new();
+        for ((path, stamp), _) in files
+            .iter()
+            .zip(&stamps)
+            .zip(&is_stale)
+            .filter(|(_, stale)| **stale)
+        {
+            // Files turned off by file suppressions are never reported
+            match (results.remove(path).unwrap_or(Ok(Vec::new())), stamp) {
+                (Ok(violations), Some(stamp)) => {
+                    let stamp = *stamp;
+                    self.cache
+                        .insert(path.clone(), Cached { stamp, violations });
+                }
+                (Ok(violations), None) => {
+                    uncached.insert(path, violations);
+                }
+                (Err(message), _) => {
+                    self.cache.remove(path);
+                    errors.push(FileError {
+                        path: path.clone(),
+                        message,
+                    });
+                }
+            }
+        }
+
+        let violations = files
+            .iter()
+            .filter_map(|path| {
+                self.cache
+                    .get(path)
+                    .map(|cached| &cached.violations)
+                    .or_else(|| uncached.get(path))
+            })
+            .flatten()
+            .collect();
+        serde_json::to_string(&Response::Check {
+            violations,
+            errors,
+            linted: stale.len(),
+            cached: files.len() - stale.len(),
+        })
+    }
+}
+
+/// Collects the violations of each file linted, or why it couldn't be.
+struct Collector<'a> {
+    fix_policy: &'a FixPolicy,
+    results: HashMap<PathBuf, Result<Vec<JsonViolation>, String>>,
+}
+
+impl AuditListener for Collector<'_> {
+    fn file_started(&mut self, path: &Path) {
+        self.results.insert(path.to_path_buf(), Ok(Vec::new()));
+    }
+
+    fn violation(&mut self, event: &AuditEvent<'_>) {
+        if let Some(Ok(violations)) = self.results.get_mut(event.path) {
+            violations.push(JsonViolation::new(event, self.fix_policy));
+        }
+    }
+
+    fn file_error(&mut self, path: &Path, error: &SourceError) {
+        self.results
+            .insert(path.to_path_buf(), Err(error.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::net::Shutdown;
+
+    use lintal_linter::rules::UpperEll;
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    fn daemon() -> Daemon {
+        Daemon::new(Linter::new(vec![Box::new(UpperEll)]))
+    }
+
+    /// A fresh directory for the test `name`.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("lintal-daemon-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn check(daemon: &mut Daemon, dir: &Path) -> Value {
+        serde_json::from_str(&daemon.check(&[dir.to_path_buf()]).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_check_lints_changed_files() {
+        let dir = test_dir("cache");
+        let a = dir.join("A.java");
+        std::fs::write(&a, "class A {\n    long x = 1l;\n}\n").unwrap();
+        std::fs::write(dir.join("B.java"), "class B {\n}\n").unwrap();
+        let mut daemon = daemon();
+
+        let first = check(&mut daemon, &dir);
+        let unchanged = check(&mut daemon, &dir);
+        std::fs::write(&a, "class A {\n    long x = 1L;\n    long y = 2l;\n}\n").unwrap();
+        let changed = check(&mut daemon, &dir);
+        let trees = daemon.trees.len();
+        std::fs::remove_file(dir.join("B.java")).unwrap();
+        let removed = check(&mut daemon, &dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!((&first["linted"], &first["cached"]), (&json!(2), &json!(0)));
+        assert_eq!(first["violations"][0]["rule"], "UpperEll");
+        assert_eq!(first["violations"][0]["line"], 2);
+        assert_eq!(first["errors"], json!([]));
+
+        assert_eq!(
+            (&unchanged["linted"], &unchanged["cached"]),
+            (&json!(0), &json!(2))
+        );
+        assert_eq!(unchanged["violations"], first["violations"]);
+
+        assert_eq!(
+            (&changed["linted"], &changed["cached"]),
+            (&json!(1), &json!(1))
+        );
+        assert_eq!(changed["violations"].as_array().unwrap().len(), 1);
+        assert_eq!(changed["violations"][0]["line"], 3);
+        // Kept to parse each file incrementally next time
+        assert_eq!(trees, 2);
+
+        assert_eq!(
+            (&removed["linted"], &removed["cached"]),
+            (&json!(0), &json!(1))
+        );
+        assert_eq!(removed["violations"], changed["violations"]);
+    }
+
+    #[test]
+    fn test_serve() {
+        let dir = test_dir("serve");
+        std::fs::write(dir.join("A.java"), "class A {\n    long x = 1l;\n}\n").unwrap();
+        let (mut client, server) = UnixStream::pair().unwrap();
+        let check = json!({ "method": "check", "paths": [dir] });
+        writeln!(
+            client,
+            "{check}\n\nnot json\n{{\"method\": \"shutdown\"}}\n{check}"
+        )
+        .unwrap();
+        client.shutdown(Shutdown::Write).unwrap();
+
+        let shut_down = daemon().serve(server).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        let mut responses = String::new();
+        client.read_to_string(&mut responses).unwrap();
+
+        assert!(shut_down);
+        let responses: Vec<Value> = responses
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        // Nothing is answered after the shutdown
+        assert_eq!(responses.len(), 3);
+        assert_eq!(responses[0]["linted"], 1);
+        assert_eq!(responses[0]["violations"][0]["code"], "ST001");
+        assert!(
+            responses[1]["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("invalid request: "),
+            "{}",
+            responses[1]
+        );
+        assert_eq!(responses[2], json!({ "ok": true }));
+    }
+
+    #[test]
+    fn test_serve_until_hang_up() {
+        let (mut client, server) = UnixStream::pair().unwrap();
+        writeln!(client, "{{\"method\": \"check\", \"paths\": []}}").unwrap();
+        client.shutdown(Shutdown::Write).unwrap();
+
+        assert!(!daemon().serve(server).unwrap());
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&response).unwrap(),
+            json!({ "violations": [], "errors": [], "linted": 0, "cached": 0 })
+        );
+    }
+
+    #[test]
+    fn test_bind_replaces_stale_socket() {
+        let dir = test_dir("bind");
+        let socket = dir.join("lintal.sock");
+
+        let listener = bind(&socket).unwrap();
+        let err = bind(&socket).unwrap_err();
+        assert!(err.to_string().contains("already listening"), "{err:#}");
+        // The socket file outlives the listener
+        drop(listener);
+        assert!(socket.exists());
+        let rebound = bind(&socket);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(rebound.is_ok());
+    }
+}
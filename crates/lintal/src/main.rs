@@ -1,120 +1,145 @@
 //! lintal - A fast Java linter with auto-fix support.
 
+mod bench;
+#[cfg(unix)]
+mod daemon;
+mod sarif;
+mod server;
+mod watch;
+mod worker;
+
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use colored::Colorize;
-use lintal_checkstyle::{CheckstyleConfig, ConfiguredRule, LintalConfig, MergedConfig};
-use lintal_diagnostics::{Applicability, Diagnostic, Edit};
-use lintal_java_cst::{CstNode, TreeWalker};
-use lintal_java_parser::{JavaParser, java_kind_id_map, java_language};
+use lintal_checkstyle::{
+    CheckstyleConfig, ConfiguredRule, LintalConfig, MergedConfig, Preset, SeverityLevel,
+};
+use lintal_diagnostics::{
+    Applicability, Locale, MessageBundles, RenderOptions, Severity, render_diagnostic,
+};
+use lintal_java_semantic::ProjectIndex;
+use lintal_linter::diff::unified_diff;
+use lintal_linter::organize_imports::ImportLayout;
 use lintal_linter::{
-    CheckContext, FileSuppressionsConfig, PlainTextCommentFilterConfig, Rule, RuleRegistry,
-    SuppressionContext,
+    AuditEvent, AuditListener, AuditSummary, CommentFilterConfig, FileSuppressionsConfig,
+    FixPolicy, Linter, PlainTextCommentFilterConfig, PluginLibrary, Rule, RuleRegistry, SkipReason,
+    SuppressWarningsAliases, SuppressedBy, SuppressionPolicy,
 };
+use lintal_linter::{archive, codes, docs};
+use lintal_source_file::{Charset, LineIndex, PositionEncoding, SourceCode};
 use lintal_text_size::Ranged;
 use rayon::prelude::*;
-use std::cell::RefCell;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
 use walkdir::WalkDir;
 
-// Thread-local parser to avoid repeated initialization overhead
-thread_local! {
-    static PARSER: RefCell<JavaParser> = RefCell::new(JavaParser::new());
+/// Result of fixing a single file.
+struct FileFixResult {
+    fixed: usize,
+    unfixable: usize,
+    skipped: Vec<SkipReason>,
+    changed: bool,
+    messages: Vec<String>,
+    /// The diagnostics whose fixes were skipped, for `--verbose`.
+    skipped_messages: Vec<String>,
 }
 
-struct DispatchTable {
-    per_kind: Vec<Vec<usize>>,
-    catch_all: Vec<usize>,
-    /// Bitmap of which node kinds have any rules (including catch_all)
-    has_rules: Vec<bool>,
+/// Where `fix` writes the diff of its fixes instead of applying them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum DiffOutput {
+    Stdout,
+    /// A patch file, never colored.
+    File(PathBuf),
 }
 
-impl DispatchTable {
-    fn new(rules: &[Box<dyn Rule>]) -> Self {
-        let language = java_language();
-        let kind_count = language.node_kind_count();
-        let mut per_kind: Vec<Vec<usize>> = vec![Vec::new(); kind_count];
-        let mut catch_all = Vec::new();
-        let kind_map = java_kind_id_map();
-        let mut unknown_kinds: Vec<(&'static str, &'static str)> = Vec::new();
-
-        for (idx, rule) in rules.iter().enumerate() {
-            let kinds = rule.relevant_kinds();
-            if kinds.is_empty() {
-                catch_all.push(idx);
-                continue;
-            }
+/// How `check` reports violations.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// One line per violation
+    #[default]
+    Concise,
+    /// Violations with the offending source lines and fix previews
+    Full,
+    /// A JSON array of violations with the edits of their fixes, for tools
+    /// that apply fixes themselves
+    Json,
+    /// Markdown tables of violations per rule and per file, for a pull
+    /// request comment or `$GITHUB_STEP_SUMMARY`
+    Markdown,
+    /// TeamCity service messages, so violations show up as inspections of
+    /// the build
+    Teamcity,
+    /// SonarQube's generic external issue JSON, for
+    /// `sonar.externalIssuesReportPaths`
+    Sonar,
+    /// A SARIF 2.1.0 log with rule metadata and fixes, for GitHub Code
+    /// Scanning
+    Sarif,
+}
 
-            for &kind in kinds {
-                if let Some(ids) = kind_map.get(kind) {
-                    for id in ids {
-                        let slot = &mut per_kind[*id as usize];
-                        if !slot.contains(&idx) {
-                            slot.push(idx);
-                        }
-                    }
-                } else {
-                    unknown_kinds.push((rule.name(), kind));
-                }
-            }
-        }
+/// How `lintal metrics` writes its results.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+enum MetricsFormat {
+    /// Comma-separated values with a header row
+    #[default]
+    Csv,
+    /// A JSON array of files
+    Json,
+}
 
-        #[cfg(debug_assertions)]
-        if !unknown_kinds.is_empty() {
-            let mut seen: std::collections::HashSet<(&'static str, &'static str)> =
-                std::collections::HashSet::new();
-            for (rule, kind) in unknown_kinds {
-                if seen.insert((rule, kind)) {
-                    eprintln!(
-                        "Debug: rule '{}' references unknown node kind '{}'",
-                        rule, kind
-                    );
-                }
-            }
-        }
+/// What a row of `lintal metrics` output describes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+enum MetricsLevel {
+    File,
+    #[default]
+    Method,
+}
 
-        // Pre-compute which kinds have any rules
-        let has_catch_all = !catch_all.is_empty();
-        let has_rules: Vec<bool> = per_kind
-            .iter()
-            .map(|rules| has_catch_all || !rules.is_empty())
-            .collect();
+/// The least severe violations that make `check` fail.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+enum FailOn {
+    Warning,
+    #[default]
+    Error,
+}
 
-        Self {
-            per_kind,
-            catch_all,
-            has_rules,
+impl FailOn {
+    fn severity(self) -> Severity {
+        match self {
+            FailOn::Warning => Severity::Warning,
+            FailOn::Error => Severity::Error,
         }
     }
-
-    /// Quick check if this node kind has any rules to run
-    #[inline]
-    fn has_rules_for_kind(&self, kind_id: u16) -> bool {
-        self.has_rules[kind_id as usize]
-    }
-
-    fn rule_indices_for_kind(&self, kind_id: u16) -> impl Iterator<Item = usize> + '_ {
-        self.per_kind[kind_id as usize]
-            .iter()
-            .copied()
-            .chain(self.catch_all.iter().copied())
-    }
 }
 
-/// Result of checking a single file.
-struct FileCheckResult {
-    violations: Vec<String>,
-    violation_count: usize,
-    fixable_count: usize,
+/// How much `lintal` logs to stderr.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
 }
 
-/// Result of fixing a single file.
-struct FileFixResult {
-    fixed: usize,
-    unfixable: usize,
-    changed: bool,
-    messages: Vec<String>,
+impl LogLevel {
+    fn filter(self) -> LevelFilter {
+        match self {
+            LogLevel::Off => LevelFilter::OFF,
+            LogLevel::Error => LevelFilter::ERROR,
+            LogLevel::Warn => LevelFilter::WARN,
+            LogLevel::Info => LevelFilter::INFO,
+            LogLevel::Debug => LevelFilter::DEBUG,
+            LogLevel::Trace => LevelFilter::TRACE,
+        }
+    }
 }
 
 #[derive(Parser)]
@@ -123,6 +148,18 @@ struct FileFixResult {
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Log timings and progress to stderr (overrides RUST_LOG)
+    #[arg(long, global = true, value_enum)]
+    log_level: Option<LogLevel>,
+
+    /// Never color output, e.g. to pipe `fix --diff` into `git apply`
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Lint this many files at once (defaults to the number of CPUs)
+    #[arg(long, global = true, value_name = "N")]
+    threads: Option<usize>,
 }
 
 #[derive(Subcommand)]
@@ -141,6 +178,33 @@ enum Commands {
         /// (defaults to the directory containing checkstyle.xml)
         #[arg(long)]
         config_loc: Option<PathBuf>,
+
+        /// Use a bundled configuration, google or sun, instead of checkstyle.xml
+        #[arg(long, conflicts_with = "config")]
+        preset: Option<Preset>,
+
+        /// Output format for violations
+        #[arg(long, value_enum, default_value_t)]
+        output_format: OutputFormat,
+
+        /// Only run these rules, by name, code or code prefix (e.g. WS001,UpperEll,NM)
+        #[arg(long, value_delimiter = ',')]
+        select: Vec<String>,
+
+        /// Also list the violations suppressions hide, with what hid each
+        /// and counts per rule (concise and full output only)
+        #[arg(long)]
+        explain_suppressed: bool,
+
+        /// Exit with an error on violations of this severity or worse; info
+        /// violations never fail
+        #[arg(long, value_enum, default_value_t)]
+        fail_on: FailOn,
+
+        /// Keep running and check files again whenever they change, until
+        /// interrupted
+        #[arg(long)]
+        watch: bool,
     },
     /// Fix violations in files
     Fix {
@@ -157,59 +221,375 @@ enum Commands {
         #[arg(long)]
         config_loc: Option<PathBuf>,
 
+        /// Use a bundled configuration, google or sun, instead of checkstyle.xml
+        #[arg(long, conflicts_with = "config")]
+        preset: Option<Preset>,
+
         /// Show diff without applying fixes
         #[arg(long)]
         diff: bool,
 
+        /// Write the diff to this file as a patch for `git apply` or
+        /// `patch -p1`, without applying fixes
+        #[arg(long, value_name = "FILE")]
+        patch: Option<PathBuf>,
+
         /// Apply unsafe fixes
         #[arg(long)]
         r#unsafe: bool,
+
+        /// List the violations whose fixes were skipped, and why
+        #[arg(short, long)]
+        verbose: bool,
+
+        /// Only run these rules, by name, code or code prefix (e.g. WS001,UpperEll,NM)
+        #[arg(long, value_delimiter = ',')]
+        select: Vec<String>,
+    },
+    /// Rewrite the imports of files: drop unused ones, expand or collapse
+    /// star imports, and sort them into the configured groups
+    OrganizeImports {
+        /// Paths to organize
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Path to checkstyle.xml config, for its ImportOrder,
+        /// CustomImportOrder and AvoidStarImport modules
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Directory for resolving ${config_loc} in suppressions.xml paths
+        /// (defaults to the directory containing checkstyle.xml)
+        #[arg(long)]
+        config_loc: Option<PathBuf>,
+
+        /// Use a bundled configuration, google or sun, instead of checkstyle.xml
+        #[arg(long, conflicts_with = "config")]
+        preset: Option<Preset>,
+
+        /// Show diff without writing files
+        #[arg(long)]
+        diff: bool,
+    },
+    /// Delete suppression comments and annotations that suppress nothing
+    RemoveUnusedSuppressions {
+        /// Paths to clean up
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Path to checkstyle.xml config
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Directory for resolving ${config_loc} in suppressions.xml paths
+        /// (defaults to the directory containing checkstyle.xml)
+        #[arg(long)]
+        config_loc: Option<PathBuf>,
+
+        /// Use a bundled configuration, google or sun, instead of checkstyle.xml
+        #[arg(long, conflicts_with = "config")]
+        preset: Option<Preset>,
+
+        /// Show diff without writing files
+        #[arg(long)]
+        diff: bool,
+    },
+    /// Time linting a corpus, per rule, optionally against checkstyle
+    Bench {
+        /// Paths to lint
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Path to checkstyle.xml config
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Directory for resolving ${config_loc} in suppressions.xml paths
+        /// (defaults to the directory containing checkstyle.xml)
+        #[arg(long)]
+        config_loc: Option<PathBuf>,
+
+        /// Use a bundled configuration, google or sun, instead of checkstyle.xml
+        #[arg(long, conflicts_with = "config")]
+        preset: Option<Preset>,
+
+        /// Only run these rules, by name, code or code prefix (e.g. WS001,UpperEll,NM)
+        #[arg(long, value_delimiter = ',')]
+        select: Vec<String>,
+
+        /// How many times to lint the corpus
+        #[arg(long, default_value_t = 5)]
+        runs: usize,
+
+        /// Also time checkstyle with this checkstyle-all jar (needs --config)
+        #[arg(long, requires = "config")]
+        checkstyle: Option<PathBuf>,
+    },
+    /// Export size and complexity metrics of files and methods, without
+    /// checking any rules
+    Metrics {
+        /// Paths to measure
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Path to checkstyle.xml config, for the charset and file limits
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Directory for resolving ${config_loc} in suppressions.xml paths
+        /// (defaults to the directory containing checkstyle.xml)
+        #[arg(long)]
+        config_loc: Option<PathBuf>,
+
+        /// Use a bundled configuration, google or sun, instead of checkstyle.xml
+        #[arg(long, conflicts_with = "config")]
+        preset: Option<Preset>,
+
+        /// Output format for the metrics
+        #[arg(long, value_enum, default_value_t)]
+        format: MetricsFormat,
+
+        /// One row per method, or per file with its method count and the
+        /// highest complexity and nesting of its methods
+        #[arg(long, value_enum, default_value_t)]
+        level: MetricsLevel,
+    },
+    /// Keep the configuration loaded and lint files on request over a Unix
+    /// socket, reusing results for files that haven't changed
+    Daemon {
+        /// Socket to listen on
+        #[arg(long, default_value = ".lintal.sock")]
+        socket: PathBuf,
+
+        /// Path to checkstyle.xml config
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Directory for resolving ${config_loc} in suppressions.xml paths
+        /// (defaults to the directory containing checkstyle.xml)
+        #[arg(long)]
+        config_loc: Option<PathBuf>,
+
+        /// Use a bundled configuration, google or sun, instead of checkstyle.xml
+        #[arg(long, conflicts_with = "config")]
+        preset: Option<Preset>,
+
+        /// Only run these rules, by name, code or code prefix (e.g. WS001,UpperEll,NM)
+        #[arg(long, value_delimiter = ',')]
+        select: Vec<String>,
+    },
+    /// Serve the Language Server Protocol on stdin and stdout, so editors
+    /// show violations as files are edited
+    Server {
+        /// Path to checkstyle.xml config
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Directory for resolving ${config_loc} in suppressions.xml paths
+        /// (defaults to the directory containing checkstyle.xml)
+        #[arg(long)]
+        config_loc: Option<PathBuf>,
+
+        /// Use a bundled configuration, google or sun, instead of checkstyle.xml
+        #[arg(long, conflicts_with = "config")]
+        preset: Option<Preset>,
+
+        /// Only run these rules, by name, code or code prefix (e.g. WS001,UpperEll,NM)
+        #[arg(long, value_delimiter = ',')]
+        select: Vec<String>,
     },
 }
 
 fn main() -> Result<()> {
+    // Bazel starts workers with its own flag rather than a subcommand
+    let args: Vec<String> = std::env::args_os()
+        .skip(1)
+        .filter_map(|arg| arg.into_string().ok())
+        .collect();
+    if let Some(protocol) = worker::startup_protocol(&args) {
+        return worker::run(protocol);
+    }
+
     let cli = Cli::parse();
+    init_logging(cli.log_level);
+    if cli.no_color {
+        colored::control::set_override(false);
+    }
+    if let Some(threads) = cli.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .context("Failed to start the worker threads")?;
+    }
 
     match cli.command {
         Commands::Check {
             paths,
             config,
             config_loc,
-        } => run_check(&paths, config.as_deref(), config_loc.as_deref()),
+            preset,
+            output_format,
+            select,
+            explain_suppressed,
+            fail_on,
+            watch,
+        } => run_check(
+            &paths,
+            config.as_deref(),
+            config_loc.as_deref(),
+            preset,
+            output_format,
+            &select,
+            explain_suppressed,
+            fail_on,
+            watch,
+        ),
         Commands::Fix {
             paths,
             config,
             config_loc,
+            preset,
             diff,
+            patch,
             r#unsafe: allow_unsafe,
+            verbose,
+            select,
         } => run_fix(
             &paths,
             config.as_deref(),
             config_loc.as_deref(),
-            diff,
+            preset,
+            match patch {
+                Some(path) => Some(DiffOutput::File(path)),
+                None => diff.then_some(DiffOutput::Stdout),
+            },
             allow_unsafe,
+            verbose,
+            &select,
+        ),
+        Commands::OrganizeImports {
+            paths,
+            config,
+            config_loc,
+            preset,
+            diff,
+        } => run_organize_imports(
+            &paths,
+            config.as_deref(),
+            config_loc.as_deref(),
+            preset,
+            diff,
+        ),
+        Commands::RemoveUnusedSuppressions {
+            paths,
+            config,
+            config_loc,
+            preset,
+            diff,
+        } => run_remove_unused_suppressions(
+            &paths,
+            config.as_deref(),
+            config_loc.as_deref(),
+            preset,
+            diff,
+        ),
+        Commands::Bench {
+            paths,
+            config,
+            config_loc,
+            preset,
+            select,
+            runs,
+            checkstyle,
+        } => run_bench(
+            &paths,
+            config.as_deref(),
+            config_loc.as_deref(),
+            preset,
+            &select,
+            runs,
+            checkstyle.as_deref(),
+        ),
+        Commands::Daemon {
+            socket,
+            config,
+            config_loc,
+            preset,
+            select,
+        } => run_daemon(
+            &socket,
+            config.as_deref(),
+            config_loc.as_deref(),
+            preset,
+            &select,
+        ),
+        Commands::Server {
+            config,
+            config_loc,
+            preset,
+            select,
+        } => run_server(config.as_deref(), config_loc.as_deref(), preset, &select),
+        Commands::Metrics {
+            paths,
+            config,
+            config_loc,
+            preset,
+            format,
+            level,
+        } => run_metrics(
+            &paths,
+            config.as_deref(),
+            config_loc.as_deref(),
+            preset,
+            format,
+            level,
         ),
     }
 }
 
+/// Log to stderr at `level`, or as `RUST_LOG` says; nothing by default.
+///
+/// Spans are logged when they close, with the time spent in them, so slow
+/// files and phases stand out.
+fn init_logging(level: Option<LogLevel>) {
+    let filter = match level {
+        Some(level) => EnvFilter::default().add_directive(level.filter().into()),
+        None => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("off")),
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(std::io::stderr)
+        .init();
+}
+
 /// Run the check command.
+#[allow(clippy::too_many_arguments)]
 fn run_check(
     paths: &[PathBuf],
     config_path: Option<&Path>,
     config_loc: Option<&Path>,
+    preset: Option<Preset>,
+    output_format: OutputFormat,
+    select: &[String],
+    explain_suppressed: bool,
+    fail_on: FailOn,
+    watch: bool,
 ) -> Result<()> {
     // Load configuration
-    let (rules, merged_config, suppression_filters, file_suppressions) =
-        load_rules(config_path, config_loc, paths)?;
-    let dispatch = DispatchTable::new(&rules);
+    let (mut linter, merged_config) = load_linter(config_path, config_loc, preset, paths, select)?;
+    if explain_suppressed {
+        linter = linter.with_suppressed_diagnostics();
+    }
 
-    if rules.is_empty() {
+    if linter.rules().is_empty() {
         eprintln!("{}", "Warning: No rules configured".yellow());
     } else {
         let rule_names: Vec<_> = merged_config
             .as_ref()
+            .filter(|_| select.is_empty())
             .map(|c| c.enabled_rules().map(|r| r.name.as_str()).collect())
-            .unwrap_or_else(|| rules.iter().map(|r| r.name()).collect());
+            .unwrap_or_else(|| linter.rules().iter().map(|r| r.name()).collect());
         eprintln!(
             "Checking with {} rule(s): {}",
             rule_names.len(),
@@ -217,82 +597,618 @@ fn run_check(
         );
     }
 
-    let files = collect_java_files(paths);
-    let file_count = files.len();
-    let files_processed = AtomicUsize::new(0);
+    let mut files = collect_java_files(paths);
+    let linter = with_project_index(linter, &files);
+    // Documents are linted along with the sources, but aren't indexed
+    let docs_enabled = merged_config.as_ref().is_some_and(|c| c.docs.enabled);
+    files.extend(collect_doc_files(paths, docs_enabled));
+    files.sort();
 
-    // Process files in parallel
-    let results: Vec<FileCheckResult> = files
-        .par_iter()
-        .filter_map(|path| {
-            // Skip files that are fully suppressed by file-based suppressions
-            let path_str = path.to_string_lossy();
-            if file_suppressions.is_file_fully_suppressed(&path_str) {
-                files_processed.fetch_add(1, Ordering::Relaxed);
-                return None;
+    if watch {
+        return watch::run(linter, paths, files, docs_enabled, output_format);
+    }
+
+    let summary = tracing::info_span!("audit", files = files.len())
+        .in_scope(|| linter.audit(&files, &mut *reporter(&linter, output_format, files.len())));
+
+    let unused = report_unused_file_suppressions(&linter);
+    if summary.violations_at_least(fail_on.severity()) > 0 || unused > 0 {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// The listener printing violations in `output_format`, for an audit of
+/// `file_count` files.
+fn reporter<'a>(
+    linter: &'a Linter,
+    output_format: OutputFormat,
+    file_count: usize,
+) -> Box<dyn AuditListener + 'a> {
+    match output_format {
+        OutputFormat::Json => Box::new(JsonReporter {
+            fix_policy: linter.fix_policy(),
+            violations: Vec::new(),
+        }),
+        OutputFormat::Markdown => Box::new(MarkdownReporter::default()),
+        OutputFormat::Teamcity => Box::new(TeamCityReporter::default()),
+        OutputFormat::Sonar => Box::new(SonarReporter::default()),
+        OutputFormat::Sarif => Box::new(sarif::SarifReporter::new(linter)),
+        OutputFormat::Concise | OutputFormat::Full => Box::new(TextReporter {
+            output_format,
+            file_count,
+            suppressions_file: linter.file_suppressions().path().map(Path::to_path_buf),
+            suppressed_by_rule: BTreeMap::new(),
+        }),
+    }
+}
+
+/// Warn about the rows of suppressions.xml that suppressed nothing, and
+/// return how many there are.
+fn report_unused_file_suppressions(linter: &Linter) -> usize {
+    let unused = linter.unused_file_suppressions();
+    let path = linter.file_suppressions().path().map_or_else(
+        || "suppressions.xml".to_string(),
+        |path| path.display().to_string(),
+    );
+    for suppression in &unused {
+        let location = match suppression.line {
+            Some(line) => format!("{path}:{line}"),
+            None => path.clone(),
+        };
+        eprintln!(
+            "{}",
+            format!(
+                "Warning: {location}: Unused suppression of checks \"{}\" in files \"{}\"",
+                suppression.checks_pattern, suppression.files_pattern
+            )
+            .yellow()
+        );
+    }
+    unused.len()
+}
+
+/// Prints violations as `lintal check` reports them.
+struct TextReporter {
+    output_format: OutputFormat,
+    /// Files collected, including those skipped by file suppressions.
+    file_count: usize,
+    /// The suppressions.xml file suppressions were read from.
+    suppressions_file: Option<PathBuf>,
+    /// Violations hidden by suppressions, by rule.
+    suppressed_by_rule: BTreeMap<&'static str, usize>,
+}
+
+impl AuditListener for TextReporter {
+    fn violation(&mut self, event: &AuditEvent<'_>) {
+        match self.output_format {
+            OutputFormat::Concise => println!(
+                "{}:{}:{}: {} {}",
+                event.path.display(),
+                event.location.line,
+                event.location.column,
+                format!("[{}]", event.rule).blue(),
+                event.diagnostic.kind.body
+            ),
+            OutputFormat::Full => println!(
+                "{}",
+                render_diagnostic(
+                    event.diagnostic,
+                    event.rule,
+                    &event.path.display().to_string(),
+                    event.source,
+                    RenderOptions::default(),
+                )
+            ),
+            OutputFormat::Json
+            | OutputFormat::Markdown
+            | OutputFormat::Teamcity
+            | OutputFormat::Sonar
+            | OutputFormat::Sarif => {
+                unreachable!("structured formats have reporters of their own")
             }
+        }
+    }
+
+    fn suppressed(&mut self, event: &AuditEvent<'_>, by: &SuppressedBy) {
+        *self.suppressed_by_rule.entry(event.rule).or_default() += 1;
+        let by = match by {
+            SuppressedBy::Directive { line, text } => format!("line {line}: {text}"),
+            SuppressedBy::File {
+                line,
+                checks,
+                files,
+            } => {
+                let file = self
+                    .suppressions_file
+                    .as_deref()
+                    .map_or(Cow::Borrowed("suppressions.xml"), Path::to_string_lossy);
+                let location = match line {
+                    Some(line) => format!("{file}:{line}"),
+                    None => file.into_owned(),
+                };
+                format!("{location}: checks \"{checks}\" in files \"{files}\"")
+            }
+        };
+        println!(
+            "{}:{}:{}: {} {} {}",
+            event.path.display(),
+            event.location.line,
+            event.location.column,
+            format!("[{}]", event.rule).dimmed(),
+            event.diagnostic.kind.body.dimmed(),
+            format!("(suppressed by {by})").dimmed()
+        );
+    }
+
+    fn file_error(&mut self, path: &Path, error: &lintal_linter::SourceError) {
+        println!("{}: {error}", path.display());
+    }
+
+    fn file_skipped(&mut self, path: &Path, error: &lintal_linter::SourceError) {
+        eprintln!(
+            "{}",
+            format!("Warning: {}: {error}", path.display()).yellow()
+        );
+    }
 
-            let result = check_file(
-                path,
-                &rules,
-                &dispatch,
-                &suppression_filters,
-                &file_suppressions,
+    fn audit_finished(&mut self, summary: &AuditSummary) {
+        eprintln!("Checked {} files", self.file_count);
+        if summary.skipped > 0 {
+            eprintln!(
+                "{}",
+                format!(
+                    "Skipped {} file(s) over the limits in lintal.toml",
+                    summary.skipped
+                )
+                .yellow()
             );
-            files_processed.fetch_add(1, Ordering::Relaxed);
-            result.ok()
-        })
-        .collect();
+        }
 
-    // Aggregate and output results
-    let mut total_violations = 0;
-    let mut total_fixable = 0;
+        if summary.violations > 0 {
+            println!(
+                "\nFound {} violations ({} fixable)",
+                summary.violations.to_string().red(),
+                summary.fixable.to_string().yellow()
+            );
+        } else {
+            println!("{}", "No violations found".green());
+        }
 
-    for result in results {
-        for violation in &result.violations {
-            println!("{violation}");
+        if summary.suppressed > 0 {
+            println!(
+                "\nSuppressed {} violations:",
+                summary.suppressed.to_string().yellow()
+            );
+            let mut counts: Vec<_> = self.suppressed_by_rule.iter().collect();
+            // Most hidden first
+            counts.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+            for (rule, count) in counts {
+                println!("  {rule}: {count}");
+            }
+        }
+    }
+}
+
+/// Collects violations and prints them as a JSON array once the audit is
+/// done.
+struct JsonReporter<'a> {
+    fix_policy: &'a FixPolicy,
+    violations: Vec<JsonViolation>,
+}
+
+/// A violation as `--output-format json` prints it.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonViolation {
+    path: PathBuf,
+    rule: &'static str,
+    code: String,
+    /// `error`, `warning` or `info`, from the rule's `severity`.
+    severity: &'static str,
+    message: String,
+    /// One-based line and column, counted the way checkstyle counts them.
+    line: usize,
+    column: usize,
+    /// Where the violation ends, exclusive, counted the same way.
+    end_line: usize,
+    end_column: usize,
+    /// Whether `fix` is present.
+    fixable: bool,
+    /// The proposed fix, left unapplied. Absent if there is none or fixes of
+    /// the rule are disabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fix: Option<JsonFix>,
+}
+
+#[derive(Serialize)]
+struct JsonFix {
+    /// `safe`, `unsafe` or `display-only`, after `[fixes]` overrides; only
+    /// safe fixes are applied by `lintal fix` without `--unsafe`.
+    applicability: &'static str,
+    edits: Vec<JsonEdit>,
+}
+
+/// Replace the bytes from `start` to `end` of the file's UTF-8 text with
+/// `content`.
+#[derive(Serialize)]
+struct JsonEdit {
+    start: usize,
+    end: usize,
+    content: String,
+}
+
+impl JsonViolation {
+    /// Describe `event`, with its fix as `fix_policy` would apply it.
+    fn new(event: &AuditEvent<'_>, fix_policy: &FixPolicy) -> Self {
+        let diagnostic = event.diagnostic;
+        let fix = diagnostic.fix.as_ref().and_then(|fix| {
+            let applicability = match fix_policy.applicability(event.rule, fix)? {
+                Applicability::Safe => "safe",
+                Applicability::Unsafe => "unsafe",
+                Applicability::DisplayOnly => "display-only",
+            };
+            let edits = fix
+                .edits()
+                .iter()
+                .map(|edit| JsonEdit {
+                    start: edit.start().to_usize(),
+                    end: edit.end().to_usize(),
+                    content: edit.content().unwrap_or_default().to_string(),
+                })
+                .collect();
+            Some(JsonFix {
+                applicability,
+                edits,
+            })
+        });
+        Self {
+            path: event.path.to_path_buf(),
+            rule: event.rule,
+            code: diagnostic.kind.code.clone(),
+            severity: diagnostic.severity.as_str(),
+            message: diagnostic.kind.body.clone(),
+            line: event.location.line.get(),
+            column: event.location.column.get(),
+            end_line: event.end_location.line.get(),
+            end_column: event.end_location.column.get(),
+            fixable: fix.is_some(),
+            fix,
         }
-        total_violations += result.violation_count;
-        total_fixable += result.fixable_count;
+    }
+}
+
+impl AuditListener for JsonReporter<'_> {
+    fn violation(&mut self, event: &AuditEvent<'_>) {
+        self.violations
+            .push(JsonViolation::new(event, self.fix_policy));
     }
 
-    eprintln!("Checked {} files", file_count);
+    fn file_error(&mut self, path: &Path, error: &lintal_linter::SourceError) {
+        eprintln!("{}: {error}", path.display());
+    }
+
+    fn audit_finished(&mut self, _summary: &AuditSummary) {
+        let json =
+            serde_json::to_string_pretty(&self.violations).expect("violations serialize to JSON");
+        println!("{json}");
+    }
+}
+
+/// Collects violations and prints them as Markdown once the audit is done.
+#[derive(Default)]
+struct MarkdownReporter {
+    violations: Vec<MarkdownViolation>,
+    errors: Vec<String>,
+}
+
+struct MarkdownViolation {
+    path: PathBuf,
+    rule: &'static str,
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl AuditListener for MarkdownReporter {
+    fn violation(&mut self, event: &AuditEvent<'_>) {
+        self.violations.push(MarkdownViolation {
+            path: event.path.to_path_buf(),
+            rule: event.rule,
+            line: event.location.line.get(),
+            column: event.location.column.get(),
+            message: event.diagnostic.kind.body.clone(),
+        });
+    }
 
-    if total_violations > 0 {
+    fn file_error(&mut self, path: &Path, error: &lintal_linter::SourceError) {
+        self.errors.push(format!("{}: {error}", path.display()));
+    }
+
+    fn audit_finished(&mut self, summary: &AuditSummary) {
+        print!("{}", self.render(summary));
+    }
+}
+
+impl MarkdownReporter {
+    /// A count of violations per rule, then a table of the violations of
+    /// each file.
+    fn render(&self, summary: &AuditSummary) -> String {
+        use std::fmt::Write;
+
+        let mut out = String::from("## lintal\n\n");
+        if self.violations.is_empty() {
+            writeln!(out, "No violations found in {} file(s).", summary.files).unwrap();
+        } else {
+            // Violations arrive file by file
+            let files: Vec<&[MarkdownViolation]> =
+                self.violations.chunk_by(|a, b| a.path == b.path).collect();
+            writeln!(
+                out,
+                "Found **{}** violation(s) ({} fixable) in {} of {} file(s).\n",
+                summary.violations,
+                summary.fixable,
+                files.len(),
+                summary.files
+            )
+            .unwrap();
+
+            let mut per_rule: BTreeMap<&str, usize> = BTreeMap::new();
+            for violation in &self.violations {
+                *per_rule.entry(violation.rule).or_default() += 1;
+            }
+            out.push_str("| Rule | Violations |\n| --- | ---: |\n");
+            for (rule, count) in per_rule {
+                writeln!(out, "| {rule} | {count} |").unwrap();
+            }
+
+            for file in files {
+                writeln!(out, "\n### `{}` ({})\n", file[0].path.display(), file.len()).unwrap();
+                out.push_str("| Line | Column | Rule | Message |\n| ---: | ---: | --- | --- |\n");
+                for violation in file {
+                    writeln!(
+                        out,
+                        "| {} | {} | {} | {} |",
+                        violation.line,
+                        violation.column,
+                        violation.rule,
+                        markdown_cell(&violation.message)
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        if !self.errors.is_empty() {
+            out.push_str("\n### Files that could not be checked\n\n");
+            for error in &self.errors {
+                writeln!(out, "- {}", markdown_cell(error)).unwrap();
+            }
+        }
+        out
+    }
+}
+
+/// `text` escaped to stay inside one table cell and out of HTML, as in
+/// `List<String>`.
+fn markdown_cell(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace('<', "&lt;")
+        .replace('\n', " ")
+}
+
+/// Prints violations as TeamCity service messages, declaring each rule as
+/// an inspection type before its first violation.
+#[derive(Default)]
+struct TeamCityReporter {
+    declared: HashSet<&'static str>,
+}
+
+impl AuditListener for TeamCityReporter {
+    fn violation(&mut self, event: &AuditEvent<'_>) {
+        let code = &event.diagnostic.kind.code;
+        if self.declared.insert(event.rule) {
+            let description = if code.is_empty() {
+                format!("Checkstyle {} check", event.rule)
+            } else {
+                format!("Checkstyle {} check ({code})", event.rule)
+            };
+            println!(
+                "##teamcity[inspectionType id='{}' name='{}' description='{}' category='{}']",
+                teamcity_escape(event.rule),
+                teamcity_escape(event.rule),
+                teamcity_escape(&description),
+                teamcity_escape(codes::category(code).unwrap_or("Checkstyle"))
+            );
+        }
+        let severity = match event.diagnostic.severity {
+            Severity::Info => "INFO",
+            Severity::Warning => "WARNING",
+            Severity::Error => "ERROR",
+        };
         println!(
-            "\nFound {} violations ({} fixable)",
-            total_violations.to_string().red(),
-            total_fixable.to_string().yellow()
+            "##teamcity[inspection typeId='{}' message='{}' file='{}' line='{}' SEVERITY='{severity}']",
+            teamcity_escape(event.rule),
+            teamcity_escape(&event.diagnostic.kind.body),
+            teamcity_escape(&event.path.display().to_string()),
+            event.location.line
         );
-        std::process::exit(1);
-    } else {
-        println!("{}", "No violations found".green());
     }
 
-    Ok(())
+    fn file_error(&mut self, path: &Path, error: &lintal_linter::SourceError) {
+        println!(
+            "##teamcity[message text='{}' status='ERROR']",
+            teamcity_escape(&format!("{}: {error}", path.display()))
+        );
+    }
+
+    fn file_skipped(&mut self, path: &Path, error: &lintal_linter::SourceError) {
+        println!(
+            "##teamcity[message text='{}' status='WARNING']",
+            teamcity_escape(&format!("{}: {error}", path.display()))
+        );
+    }
+
+    fn audit_finished(&mut self, summary: &AuditSummary) {
+        println!(
+            "##teamcity[buildStatisticValue key='lintalViolations' value='{}']",
+            summary.violations
+        );
+    }
+}
+
+/// `value` escaped for a TeamCity service message attribute.
+fn teamcity_escape(value: &str) -> String {
+    use std::fmt::Write;
+
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '|' => escaped.push_str("||"),
+            '\'' => escaped.push_str("|'"),
+            '\n' => escaped.push_str("|n"),
+            '\r' => escaped.push_str("|r"),
+            '[' => escaped.push_str("|["),
+            ']' => escaped.push_str("|]"),
+            c if c.is_ascii() => escaped.push(c),
+            c => write!(escaped, "|0x{:04x}", u32::from(c)).unwrap(),
+        }
+    }
+    escaped
+}
+
+/// Collects violations and prints them as a SonarQube external issues
+/// report once the audit is done.
+#[derive(Default)]
+struct SonarReporter {
+    /// The rules violated, declared by the report alongside their issues.
+    rules: BTreeMap<&'static str, SonarRule>,
+    issues: Vec<SonarIssue>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SonarReport<'a> {
+    rules: Vec<&'a SonarRule>,
+    issues: &'a [SonarIssue],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SonarRule {
+    id: &'static str,
+    name: &'static str,
+    description: String,
+    engine_id: &'static str,
+    clean_code_attribute: &'static str,
+    impacts: [SonarImpact; 1],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SonarImpact {
+    software_quality: &'static str,
+    severity: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SonarIssue {
+    rule_id: &'static str,
+    primary_location: SonarLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SonarLocation {
+    message: String,
+    file_path: PathBuf,
+    /// Only the line: SonarQube counts columns in characters from zero,
+    /// not as checkstyle does, and rejects a report with a column past the
+    /// end of its line.
+    text_range: SonarTextRange,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SonarTextRange {
+    start_line: usize,
+}
+
+impl AuditListener for SonarReporter {
+    fn violation(&mut self, event: &AuditEvent<'_>) {
+        let code = &event.diagnostic.kind.code;
+        self.rules.entry(event.rule).or_insert_with(|| SonarRule {
+            id: event.rule,
+            name: event.rule,
+            description: if code.is_empty() {
+                format!("Checkstyle {} check", event.rule)
+            } else {
+                format!("Checkstyle {} check ({code})", event.rule)
+            },
+            engine_id: "lintal",
+            clean_code_attribute: "CONVENTIONAL",
+            impacts: [SonarImpact {
+                software_quality: "MAINTAINABILITY",
+                severity: "LOW",
+            }],
+        });
+        self.issues.push(SonarIssue {
+            rule_id: event.rule,
+            primary_location: SonarLocation {
+                message: event.diagnostic.kind.body.clone(),
+                file_path: event.path.to_path_buf(),
+                text_range: SonarTextRange {
+                    start_line: event.location.line.get(),
+                },
+            },
+        });
+    }
+
+    fn file_error(&mut self, path: &Path, error: &lintal_linter::SourceError) {
+        eprintln!("{}: {error}", path.display());
+    }
+
+    fn audit_finished(&mut self, _summary: &AuditSummary) {
+        let report = SonarReport {
+            rules: self.rules.values().collect(),
+            issues: &self.issues,
+        };
+        let json = serde_json::to_string_pretty(&report).expect("issues serialize to JSON");
+        println!("{json}");
+    }
 }
 
 /// Run the fix command.
+#[allow(clippy::too_many_arguments)]
 fn run_fix(
     paths: &[PathBuf],
     config_path: Option<&Path>,
     config_loc: Option<&Path>,
-    diff_only: bool,
+    preset: Option<Preset>,
+    diff: Option<DiffOutput>,
     allow_unsafe: bool,
+    verbose: bool,
+    select: &[String],
 ) -> Result<()> {
-    let (rules, merged_config, suppression_filters, file_suppressions) =
-        load_rules(config_path, config_loc, paths)?;
-    let dispatch = DispatchTable::new(&rules);
+    let (linter, merged_config) = load_linter(config_path, config_loc, preset, paths, select)?;
+    let diff_only = diff.is_some();
+    if matches!(diff, Some(DiffOutput::File(_))) {
+        colored::control::set_override(false);
+    }
 
-    if rules.is_empty() {
+    if linter.rules().is_empty() {
         eprintln!("{}", "Warning: No rules configured".yellow());
         return Ok(());
     }
 
     let rule_names: Vec<_> = merged_config
         .as_ref()
+        .filter(|_| select.is_empty())
         .map(|c| c.enabled_rules().map(|r| r.name.as_str()).collect())
-        .unwrap_or_else(|| rules.iter().map(|r| r.name()).collect());
+        .unwrap_or_else(|| linter.rules().iter().map(|r| r.name()).collect());
     eprintln!(
         "Fixing with {} rule(s): {}",
         rule_names.len(),
@@ -305,355 +1221,726 @@ fn run_fix(
         Applicability::Safe
     };
 
+    let mut files = collect_java_files(paths);
+    if !diff_only {
+        let collected = files.len();
+        files.retain(|path| archive::split_entry_path(path).is_none());
+        if files.len() < collected {
+            eprintln!(
+                "{}: skipping {} file(s) inside archives, which can't be written; use --diff to see their fixes",
+                "Warning".yellow(),
+                collected - files.len()
+            );
+        }
+    }
+
+    // The index describes the files as they are before fixing
+    let linter = with_project_index(linter, &files);
+
+    // Process files in parallel
+    let results: Vec<FileFixResult> =
+        tracing::info_span!("fix", files = files.len()).in_scope(|| {
+            files
+                .par_iter()
+                .filter_map(|path| {
+                    // Skip files that are fully suppressed
+                    if linter.is_file_suppressed(path) {
+                        return None;
+                    }
+                    Some(fix_file(path, &linter, applicability, diff_only))
+                })
+                .collect()
+        });
+
+    // Aggregate and output results
+    let mut total_fixed = 0;
+    let mut total_unfixable = 0;
+    let mut skipped = Vec::new();
+    let mut files_changed = 0;
+
+    // Only diffs go to stdout with --diff, so it can be applied as a patch
+    let mut patch = String::new();
+    for result in results {
+        for msg in &result.messages {
+            if !diff_only {
+                print!("{msg}");
+            } else if result.changed {
+                patch.push_str(msg);
+            } else {
+                eprint!("{msg}");
+            }
+        }
+        if verbose {
+            for msg in &result.skipped_messages {
+                eprint!("{msg}");
+            }
+        }
+        total_fixed += result.fixed;
+        total_unfixable += result.unfixable;
+        skipped.extend(result.skipped);
+        if result.changed {
+            files_changed += 1;
+        }
+    }
+
+    match &diff {
+        Some(DiffOutput::Stdout) => print!("{patch}"),
+        Some(DiffOutput::File(path)) => std::fs::write(path, &patch)
+            .with_context(|| format!("Failed to write {}", path.display()))?,
+        None => {}
+    }
+
+    if diff_only {
+        eprintln!(
+            "\n{} fix(es) available in {} file(s)",
+            total_fixed.to_string().green(),
+            files_changed
+        );
+    } else if total_fixed > 0 {
+        println!(
+            "\n{} fix(es) applied in {} file(s)",
+            total_fixed.to_string().green(),
+            files_changed
+        );
+    } else {
+        println!("{}", "No fixes to apply".green());
+    }
+
+    if !skipped.is_empty() {
+        eprintln!("{}", skipped_summary(&skipped, verbose));
+    }
+    if total_unfixable > 0 {
+        eprintln!(
+            "{} violation(s) could not be fixed automatically",
+            total_unfixable.to_string().yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// `N fix(es) skipped: M overlapping, K unsafe (re-run with --unsafe)`.
+fn skipped_summary(skipped: &[SkipReason], verbose: bool) -> String {
+    let count = |reason| skipped.iter().filter(|&&skipped| skipped == reason).count();
+    let reasons: Vec<String> = [
+        (SkipReason::Overlapping, "overlapping"),
+        (SkipReason::Unsafe, "unsafe (re-run with --unsafe)"),
+        (SkipReason::Suppressed, "suppressed"),
+        (SkipReason::Disabled, "disabled in lintal.toml"),
+        (SkipReason::Invalid, "invalid (a bug in the rule)"),
+    ]
+    .into_iter()
+    .filter_map(|(reason, label)| match count(reason) {
+        0 => None,
+        n => Some(format!("{n} {label}")),
+    })
+    .collect();
+    let mut summary = format!(
+        "{} fix(es) skipped: {}",
+        skipped.len().to_string().yellow(),
+        reasons.join(", ")
+    );
+    if !verbose {
+        summary.push_str("; use --verbose to list them");
+    }
+    summary
+}
+
+fn skip_reason_label(reason: SkipReason) -> &'static str {
+    match reason {
+        SkipReason::Overlapping => "overlaps another fix",
+        SkipReason::Unsafe => "unsafe",
+        SkipReason::Suppressed => "suppressed",
+        SkipReason::Disabled => "disabled in lintal.toml",
+        SkipReason::Invalid => "would split a character",
+    }
+}
+
+/// Run the bench command.
+fn run_bench(
+    paths: &[PathBuf],
+    config_path: Option<&Path>,
+    config_loc: Option<&Path>,
+    preset: Option<Preset>,
+    select: &[String],
+    runs: usize,
+    checkstyle_jar: Option<&Path>,
+) -> Result<()> {
+    let (linter, _) = load_linter(config_path, config_loc, preset, paths, select)?;
+    let files = collect_java_files(paths);
+    let linter = with_project_index(linter.with_rule_timings(), &files);
+
+    let checkstyle = checkstyle_jar
+        .zip(config_path)
+        .map(|(jar, config)| bench::Checkstyle {
+            jar,
+            config,
+            // Checkstyle resolves ${config_loc} only if it is set
+            config_loc: config_loc.or_else(|| config.parent()),
+        });
+    bench::run(&linter, &files, runs, checkstyle.as_ref())
+}
+
+/// A file's metrics as `lintal metrics --format json` prints them.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonFileMetrics {
+    path: PathBuf,
+    ncss: usize,
+    method_count: usize,
+    max_complexity: usize,
+    max_nesting: usize,
+    /// Absent with `--level file`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    methods: Option<Vec<JsonMethodMetrics>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonMethodMetrics {
+    name: String,
+    line: usize,
+    ncss: usize,
+    complexity: usize,
+    max_nesting: usize,
+}
+
+/// Run the organize-imports command.
+fn run_organize_imports(
+    paths: &[PathBuf],
+    config_path: Option<&Path>,
+    config_loc: Option<&Path>,
+    preset: Option<Preset>,
+    diff_only: bool,
+) -> Result<()> {
+    // No rules are run; the import modules of the configuration give the layout
+    let (linter, merged_config) = load_linter(config_path, config_loc, preset, paths, &[])?;
+    let layout = merged_config
+        .as_ref()
+        .map_or_else(ImportLayout::default, ImportLayout::from_config);
+    let files = collect_java_files(paths);
+    let linter = with_project_index(linter.with_import_layout(layout), &files);
+
+    let results: Vec<_> = files
+        .par_iter()
+        .filter(|path| !linter.is_file_suppressed(path))
+        .map(
+            |path| -> Result<Option<String>, lintal_linter::SourceError> {
+                let file = linter.organize_imports_file(path)?;
+                if !file.changed() {
+                    return Ok(None);
+                }
+                if diff_only {
+                    return Ok(Some(format_diff(
+                        path,
+                        &file.original.text,
+                        &file.fixed.code,
+                    )));
+                }
+                file.write(path)?;
+                Ok(Some(format!("{}: imports organized\n", path.display())))
+            },
+        )
+        .collect();
+
+    let mut changed = 0;
+    for result in results {
+        match result {
+            Ok(Some(message)) => {
+                print!("{message}");
+                changed += 1;
+            }
+            Ok(None) => {}
+            Err(err) => eprintln!("{}", format!("Warning: {err}").yellow()),
+        }
+    }
+    if diff_only {
+        eprintln!(
+            "\nImports to organize in {} file(s)",
+            changed.to_string().green()
+        );
+    } else {
+        println!(
+            "\nImports organized in {} file(s)",
+            changed.to_string().green()
+        );
+    }
+    Ok(())
+}
+
+/// Run the remove-unused-suppressions command.
+fn run_remove_unused_suppressions(
+    paths: &[PathBuf],
+    config_path: Option<&Path>,
+    config_loc: Option<&Path>,
+    preset: Option<Preset>,
+    diff_only: bool,
+) -> Result<()> {
+    // Every configured rule runs, to see which suppressions it needs
+    let (linter, _) = load_linter(config_path, config_loc, preset, paths, &[])?;
     let files = collect_java_files(paths);
+    let linter = with_project_index(linter.with_unused_suppressions(), &files);
 
-    // Process files in parallel
-    let results: Vec<FileFixResult> = files
+    let results: Vec<_> = files
         .par_iter()
-        .filter_map(|path| {
-            // Skip files that are fully suppressed
-            let path_str = path.to_string_lossy();
-            if file_suppressions.is_file_fully_suppressed(&path_str) {
-                return None;
-            }
-
-            fix_file(
-                path,
-                &rules,
-                &dispatch,
-                &suppression_filters,
-                &file_suppressions,
-                applicability,
-                diff_only,
-            )
-            .ok()
-        })
+        .map(
+            |path| -> Result<Option<String>, lintal_linter::SourceError> {
+                let file = linter.remove_unused_suppressions_file(path)?;
+                if !file.changed() {
+                    return Ok(None);
+                }
+                if diff_only {
+                    return Ok(Some(format_diff(
+                        path,
+                        &file.original.text,
+                        &file.fixed.code,
+                    )));
+                }
+                file.write(path)?;
+                Ok(Some(format!(
+                    "{}: removed {} unused suppression(s)\n",
+                    path.display(),
+                    file.fixed.applied
+                )))
+            },
+        )
         .collect();
 
-    // Aggregate and output results
-    let mut total_fixed = 0;
-    let mut total_unfixable = 0;
-    let mut files_changed = 0;
-
+    let mut changed = 0;
     for result in results {
-        for msg in &result.messages {
-            print!("{msg}");
-        }
-        total_fixed += result.fixed;
-        total_unfixable += result.unfixable;
-        if result.changed {
-            files_changed += 1;
+        match result {
+            Ok(Some(message)) => {
+                print!("{message}");
+                changed += 1;
+            }
+            Ok(None) => {}
+            Err(err) => eprintln!("{}", format!("Warning: {err}").yellow()),
         }
     }
-
+    // Rows of suppressions.xml are left for the user to delete
+    report_unused_file_suppressions(&linter);
     if diff_only {
-        println!(
-            "\n{} fix(es) available in {} file(s)",
-            total_fixed.to_string().green(),
-            files_changed
-        );
-    } else if total_fixed > 0 {
-        println!(
-            "\n{} fix(es) applied in {} file(s)",
-            total_fixed.to_string().green(),
-            files_changed
+        eprintln!(
+            "\nUnused suppressions to remove in {} file(s)",
+            changed.to_string().green()
         );
     } else {
-        println!("{}", "No fixes to apply".green());
-    }
-
-    if total_unfixable > 0 {
-        eprintln!(
-            "{} violation(s) could not be fixed automatically",
-            total_unfixable.to_string().yellow()
+        println!(
+            "\nUnused suppressions removed in {} file(s)",
+            changed.to_string().green()
         );
     }
-
     Ok(())
 }
 
-/// Fix violations in a single file.
-fn fix_file(
-    path: &PathBuf,
-    rules: &[Box<dyn Rule>],
-    dispatch: &DispatchTable,
-    suppression_filters: &[PlainTextCommentFilterConfig],
-    file_suppressions: &FileSuppressionsConfig,
-    applicability: Applicability,
-    diff_only: bool,
-) -> Result<FileFixResult> {
-    let source = std::fs::read_to_string(path)
-        .with_context(|| format!("Failed to read {}", path.display()))?;
-
-    // Use thread-local parser to avoid repeated initialization
-    let parse_result = PARSER.with(|parser| parser.borrow_mut().parse(&source));
-    let Some(result) = parse_result else {
-        return Ok(FileFixResult {
-            fixed: 0,
-            unfixable: 0,
-            changed: false,
-            messages: vec![format!("{}: Failed to parse\n", path.display())],
-        });
-    };
-
-    let ctx = CheckContext::new(&source);
-    let mut suppression_ctx = SuppressionContext::from_source(&source, suppression_filters);
-
-    // Parse @SuppressWarnings annotations for additional suppressions
-    let root = CstNode::new(result.tree.root_node(), &source);
-    suppression_ctx.parse_suppress_warnings(&source, &root);
+/// Run the metrics command.
+fn run_metrics(
+    paths: &[PathBuf],
+    config_path: Option<&Path>,
+    config_loc: Option<&Path>,
+    preset: Option<Preset>,
+    format: MetricsFormat,
+    level: MetricsLevel,
+) -> Result<()> {
+    use std::fmt::Write;
 
-    let path_str = path.to_string_lossy();
+    // No rules are run; only the charset and limits of the configuration apply
+    let (linter, _) = load_linter(config_path, config_loc, preset, paths, &[])?;
+    let files = collect_java_files(paths);
+    let results: Vec<_> = files
+        .par_iter()
+        .map(|path| (path, linter.measure_file(path)))
+        .collect();
 
-    // Cache which rules are suppressed for this file (check once, not per-node)
-    let suppressed_rules: Option<Vec<bool>> = if file_suppressions.is_empty() {
-        None
-    } else {
-        Some(
-            rules
-                .iter()
-                .map(|rule| file_suppressions.is_suppressed(&path_str, rule.name()))
-                .collect(),
-        )
-    };
+    let mut measured = Vec::with_capacity(results.len());
+    for (path, result) in results {
+        match result {
+            Ok(metrics) => measured.push((path, metrics)),
+            Err(err) => eprintln!("{}", format!("Warning: {err}").yellow()),
+        }
+    }
 
-    // Collect all diagnostics, filtering out suppressed ones
-    let mut diagnostics: Vec<Diagnostic> = Vec::new();
-    let has_suppressions = suppression_ctx.has_suppressions();
-    for node in TreeWalker::new(root.inner(), &source) {
-        // Quick skip for nodes with no rules
-        let kind_id = node.kind_id();
-        if !dispatch.has_rules_for_kind(kind_id) {
-            continue;
+    let mut out = String::new();
+    match format {
+        MetricsFormat::Json => {
+            let files: Vec<_> = measured
+                .into_iter()
+                .map(|(path, metrics)| JsonFileMetrics {
+                    path: path.clone(),
+                    ncss: metrics.ncss,
+                    method_count: metrics.methods.len(),
+                    max_complexity: metrics.max_complexity(),
+                    max_nesting: metrics.max_nesting(),
+                    methods: (level == MetricsLevel::Method).then(|| {
+                        metrics
+                            .methods
+                            .into_iter()
+                            .map(|method| JsonMethodMetrics {
+                                name: method.name,
+                                line: method.line,
+                                ncss: method.ncss,
+                                complexity: method.complexity,
+                                max_nesting: method.max_nesting,
+                            })
+                            .collect()
+                    }),
+                })
+                .collect();
+            out = serde_json::to_string_pretty(&files)?;
+            out.push('\n');
         }
-        for rule_idx in dispatch.rule_indices_for_kind(kind_id) {
-            if suppressed_rules.as_ref().is_some_and(|mask| mask[rule_idx]) {
-                continue;
+        MetricsFormat::Csv if level == MetricsLevel::File => {
+            out.push_str("path,ncss,methods,max_complexity,max_nesting\n");
+            for (path, metrics) in &measured {
+                writeln!(
+                    out,
+                    "{},{},{},{},{}",
+                    csv_field(&path.display().to_string()),
+                    metrics.ncss,
+                    metrics.methods.len(),
+                    metrics.max_complexity(),
+                    metrics.max_nesting()
+                )?;
             }
-            let rule = &rules[rule_idx];
-            for diagnostic in rule.check(&ctx, &node) {
-                if has_suppressions
-                    && suppression_ctx.is_suppressed(rule.name(), diagnostic.range.start())
-                {
-                    continue;
+        }
+        MetricsFormat::Csv => {
+            out.push_str("path,method,line,ncss,complexity,max_nesting\n");
+            for (path, metrics) in &measured {
+                let path = csv_field(&path.display().to_string());
+                for method in &metrics.methods {
+                    writeln!(
+                        out,
+                        "{path},{},{},{},{},{}",
+                        csv_field(&method.name),
+                        method.line,
+                        method.ncss,
+                        method.complexity,
+                        method.max_nesting
+                    )?;
                 }
-                diagnostics.push(diagnostic);
             }
         }
     }
+    print!("{out}");
+    Ok(())
+}
 
-    if diagnostics.is_empty() {
-        return Ok(FileFixResult {
-            fixed: 0,
-            unfixable: 0,
-            changed: false,
-            messages: vec![],
-        });
+/// `value` quoted for CSV if it has a comma, quote or line break.
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
     }
+}
 
-    // Collect applicable fixes
-    let mut edits: Vec<Edit> = Vec::new();
-    let mut fixed = 0;
-    let mut unfixable = 0;
+/// Run the daemon command.
+#[cfg(unix)]
+fn run_daemon(
+    socket: &Path,
+    config_path: Option<&Path>,
+    config_loc: Option<&Path>,
+    preset: Option<Preset>,
+    select: &[String],
+) -> Result<()> {
+    // The configuration is read once; restart the daemon to pick up changes
+    let (linter, _) = load_linter(config_path, config_loc, preset, &[], select)?;
+    daemon::run(linter, socket)
+}
 
-    for diagnostic in &diagnostics {
-        if let Some(fix) = &diagnostic.fix {
-            if fix.applies(applicability) {
-                edits.extend(fix.edits().iter().cloned());
-                fixed += 1;
-            } else {
-                unfixable += 1;
-            }
-        } else {
-            unfixable += 1;
-        }
+#[cfg(not(unix))]
+fn run_daemon(
+    _socket: &Path,
+    _config_path: Option<&Path>,
+    _config_loc: Option<&Path>,
+    _preset: Option<Preset>,
+    _select: &[String],
+) -> Result<()> {
+    anyhow::bail!("`lintal daemon` listens on a Unix domain socket, which this platform lacks")
+}
+
+/// Run the language server command.
+fn run_server(
+    config_path: Option<&Path>,
+    config_loc: Option<&Path>,
+    preset: Option<Preset>,
+    select: &[String],
+) -> Result<()> {
+    let config_path = config_path.map(Path::to_path_buf);
+    let config_loc = config_loc.map(Path::to_path_buf);
+    let select = select.to_vec();
+    // Unlike the daemon, the server loads the configuration again as it changes
+    let mut config_files = vec!["checkstyle.xml".to_string(), "lintal.toml".to_string()];
+    if let Some(name) = config_path
+        .as_deref()
+        .and_then(Path::file_name)
+        .and_then(|name| name.to_str())
+        && !config_files.iter().any(|config| config == name)
+    {
+        config_files.push(name.to_string());
     }
+    server::run(
+        move || {
+            load_linter(
+                config_path.as_deref(),
+                config_loc.as_deref(),
+                preset,
+                &[],
+                &select,
+            )
+            .map(|(linter, _)| linter)
+        },
+        config_files,
+    )
+}
+
+/// Fix violations in a single file, writing it back unless `diff_only`.
+fn fix_file(
+    path: &Path,
+    linter: &Linter,
+    applicability: Applicability,
+    diff_only: bool,
+) -> FileFixResult {
+    let error = |err: lintal_linter::SourceError| FileFixResult {
+        fixed: 0,
+        unfixable: 0,
+        skipped: vec![],
+        changed: false,
+        messages: vec![format!("{}: {err}\n", path.display())],
+        skipped_messages: vec![],
+    };
+
+    let file = match linter.fix_file(path, applicability) {
+        Ok(file) => file,
+        Err(err) => return error(err),
+    };
+    let skipped: Vec<SkipReason> = file
+        .fixed
+        .skipped
+        .iter()
+        .map(|skipped| skipped.reason)
+        .collect();
+    // Suppressed violations aren't among those remaining
+    let unfixable = file.fixed.remaining.len()
+        - skipped
+            .iter()
+            .filter(|&&reason| reason != SkipReason::Suppressed)
+            .count();
+
+    // Skipped diagnostics are located in the fixed source
+    let line_index = LineIndex::from_source_text(&file.fixed.code);
+    let source = SourceCode::new(&file.fixed.code, &line_index);
+    let skipped_messages = file
+        .fixed
+        .skipped
+        .iter()
+        .map(|skipped| {
+            let found = &skipped.diagnostic;
+            let encoding = linter
+                .rule(found.rule)
+                .map_or(PositionEncoding::Utf32, |rule| rule.column_encoding());
+            let location = source.checkstyle_column(
+                found.diagnostic.range.start(),
+                linter.tab_width(),
+                encoding,
+            );
+            format!(
+                "{}:{}:{}: {} {} (fix skipped: {})\n",
+                path.display(),
+                location.line,
+                location.column,
+                format!("[{}]", found.rule).blue(),
+                found.diagnostic.kind.body,
+                skip_reason_label(skipped.reason)
+            )
+        })
+        .collect();
 
-    if edits.is_empty() {
-        return Ok(FileFixResult {
+    if !file.changed() {
+        return FileFixResult {
             fixed: 0,
             unfixable,
+            skipped,
             changed: false,
             messages: vec![],
-        });
+            skipped_messages,
+        };
     }
 
-    // Sort edits by position (descending) to apply from end to start
-    edits.sort_by_key(|e| std::cmp::Reverse(e.start()));
-
-    // Remove overlapping edits (keep first one, which is the one with highest start)
-    let edits = remove_overlapping_edits(edits);
-
-    // Apply edits to source
-    let fixed_source = apply_edits(&source, &edits);
-
-    let mut messages = Vec::new();
-
-    if diff_only {
+    let fixed = file.fixed.applied;
+    let message = if diff_only {
         // Buffer diff output
-        messages.push(format_diff(path, &source, &fixed_source));
+        format_diff(path, &file.original.text, &file.fixed.code)
     } else {
-        // Write fixed source
-        std::fs::write(path, &fixed_source)
-            .with_context(|| format!("Failed to write {}", path.display()))?;
-        messages.push(format!("{}: {} fix(es) applied\n", path.display(), fixed));
-    }
+        // Write fixed source back in the file's original charset
+        if let Err(err) = file.write(path) {
+            return error(err);
+        }
+        if fixed == 0 {
+            format!("{}: imports organized\n", path.display())
+        } else {
+            format!("{}: {} fix(es) applied\n", path.display(), fixed)
+        }
+    };
 
-    Ok(FileFixResult {
+    FileFixResult {
         fixed,
         unfixable,
+        skipped,
         changed: true,
-        messages,
-    })
-}
-
-/// Remove overlapping edits, keeping the first one (highest start position).
-fn remove_overlapping_edits(edits: Vec<Edit>) -> Vec<Edit> {
-    let mut result: Vec<Edit> = Vec::new();
-
-    for edit in edits {
-        // Check if this edit overlaps with any already accepted edit
-        let overlaps = result.iter().any(|existing| {
-            // Since edits are sorted descending by start, existing edits have higher starts
-            // An overlap occurs if edit.end > existing.start
-            edit.end() > existing.start()
-        });
-
-        if !overlaps {
-            result.push(edit);
-        }
+        messages: vec![message],
+        skipped_messages,
     }
-
-    result
 }
 
-/// Apply edits to source text.
-fn apply_edits(source: &str, edits: &[Edit]) -> String {
-    let mut result = source.to_string();
-
-    // Edits are sorted descending by start position, so we can apply them in order
-    for edit in edits {
-        let start = usize::from(edit.start());
-        let end = usize::from(edit.end());
-        let content = edit.content().unwrap_or("");
-
-        result.replace_range(start..end, content);
-    }
-
-    result
+/// A unified diff between original and fixed source, with `a/` and `b/`
+/// paths as `git apply` expects, colored unless colors are off.
+fn format_diff(path: &Path, original: &str, fixed: &str) -> String {
+    let path = path.strip_prefix(".").unwrap_or(path).display();
+    let diff = unified_diff(&format!("a/{path}"), &format!("b/{path}"), original, fixed);
+    diff.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let line = if i < 2 {
+                line.bold()
+            } else if line.starts_with("@@") {
+                line.cyan()
+            } else if line.starts_with('-') {
+                line.red()
+            } else if line.starts_with('+') {
+                line.green()
+            } else {
+                line.normal()
+            };
+            format!("{line}\n")
+        })
+        .collect()
 }
 
-/// Print a unified diff between original and fixed source.
-fn format_diff(path: &Path, original: &str, fixed: &str) -> String {
-    use std::fmt::Write;
+/// Load a sandboxed WebAssembly plugin and register its rules.
+#[cfg(feature = "wasm-plugins")]
+fn load_wasm_plugin(registry: &mut RuleRegistry, path: &Path) -> Result<()> {
+    let plugin = lintal_linter::WasmPlugin::load(path)?;
+    eprintln!(
+        "Loaded WebAssembly plugin '{}' from: {} ({} rule(s))",
+        plugin.name(),
+        path.display(),
+        plugin.rule_names().count()
+    );
+    registry.register_wasm_plugin(&plugin);
+    Ok(())
+}
 
-    let mut output = String::new();
-    writeln!(output, "--- a/{}", path.display()).unwrap();
-    writeln!(output, "+++ b/{}", path.display()).unwrap();
-
-    let original_lines: Vec<&str> = original.lines().collect();
-    let fixed_lines: Vec<&str> = fixed.lines().collect();
-
-    // Simple line-by-line diff
-    let mut i = 0;
-    let mut j = 0;
-    let mut in_hunk = false;
-    let mut hunk_start_orig = 0;
-    let mut hunk_start_fixed = 0;
-    let mut hunk_lines: Vec<String> = Vec::new();
-
-    while i < original_lines.len() || j < fixed_lines.len() {
-        let orig_line = original_lines.get(i);
-        let fixed_line = fixed_lines.get(j);
-
-        match (orig_line, fixed_line) {
-            (Some(o), Some(f)) if o == f => {
-                // Lines match
-                if in_hunk {
-                    hunk_lines.push(format!(" {}", o));
-                }
-                i += 1;
-                j += 1;
-            }
-            (Some(o), Some(f)) => {
-                // Lines differ
-                if !in_hunk {
-                    in_hunk = true;
-                    hunk_start_orig = i + 1;
-                    hunk_start_fixed = j + 1;
-                }
-                hunk_lines.push(format!("{}{}", "-".red(), o));
-                hunk_lines.push(format!("{}{}", "+".green(), f));
-                i += 1;
-                j += 1;
-            }
-            (Some(o), None) => {
-                // Original has extra line
-                if !in_hunk {
-                    in_hunk = true;
-                    hunk_start_orig = i + 1;
-                    hunk_start_fixed = j + 1;
-                }
-                hunk_lines.push(format!("{}{}", "-".red(), o));
-                i += 1;
-            }
-            (None, Some(f)) => {
-                // Fixed has extra line
-                if !in_hunk {
-                    in_hunk = true;
-                    hunk_start_orig = i + 1;
-                    hunk_start_fixed = j + 1;
-                }
-                hunk_lines.push(format!("{}{}", "+".green(), f));
-                j += 1;
-            }
-            (None, None) => break,
-        }
+#[cfg(not(feature = "wasm-plugins"))]
+fn load_wasm_plugin(_registry: &mut RuleRegistry, path: &Path) -> Result<()> {
+    anyhow::bail!(
+        "Cannot load {}: lintal was built without the `wasm-plugins` feature",
+        path.display()
+    )
+}
 
-        // Flush hunk if we have context after changes
-        if in_hunk && hunk_lines.len() > 6 {
-            let context_count = hunk_lines
+/// Load the configured rules and settings, or the defaults, keeping only
+/// the rules `select` picks if it isn't empty.
+fn load_linter(
+    config_path: Option<&Path>,
+    config_loc: Option<&Path>,
+    preset: Option<Preset>,
+    base_paths: &[PathBuf],
+    select: &[String],
+) -> Result<(Linter, Option<MergedConfig>)> {
+    let _span = tracing::info_span!("load_config").entered();
+    let (
+        mut rules,
+        merged_config,
+        suppression_filters,
+        comment_filters,
+        aliases,
+        file_suppressions,
+    ) = load_rules(config_path, config_loc, preset, base_paths)?;
+    if !select.is_empty() {
+        rules.retain(|rule| {
+            select
                 .iter()
-                .rev()
-                .take_while(|l| l.starts_with(' '))
-                .count();
-            if context_count >= 3 {
-                // Flush the hunk
-                writeln!(
-                    output,
-                    "@@ -{},{} +{},{} @@",
-                    hunk_start_orig,
-                    hunk_lines.iter().filter(|l| !l.starts_with('+')).count(),
-                    hunk_start_fixed,
-                    hunk_lines.iter().filter(|l| !l.starts_with('-')).count()
-                )
-                .unwrap();
-                for line in &hunk_lines {
-                    writeln!(output, "{}", line).unwrap();
-                }
-                hunk_lines.clear();
-                in_hunk = false;
-            }
-        }
+                .any(|selector| codes::selects(selector, rule.as_ref()))
+        });
     }
-
-    // Flush remaining hunk
-    if !hunk_lines.is_empty() {
-        writeln!(
-            output,
-            "@@ -{},{} +{},{} @@",
-            hunk_start_orig,
-            hunk_lines.iter().filter(|l| !l.starts_with('+')).count(),
-            hunk_start_fixed,
-            hunk_lines.iter().filter(|l| !l.starts_with('-')).count()
-        )
-        .unwrap();
-        for line in &hunk_lines {
-            writeln!(output, "{}", line).unwrap();
+    let charset = source_charset(merged_config.as_ref())?;
+    let tab_width = merged_config
+        .as_ref()
+        .map_or(MergedConfig::DEFAULT_TAB_WIDTH, |c| c.tab_width);
+
+    let mut linter = Linter::new(rules)
+        .with_suppression_filters(suppression_filters)
+        .with_comment_filters(comment_filters)
+        .with_suppress_warnings_aliases(aliases)
+        .with_file_suppressions(file_suppressions)
+        .with_charset(charset)
+        .with_tab_width(tab_width);
+    if let Some(messages) = message_bundles(merged_config.as_ref())? {
+        linter = linter.with_messages(messages);
+    }
+    if let Some(config) = &merged_config {
+        linter = linter.with_fix_policy(FixPolicy::new(config.fix_overrides.clone()));
+        linter = linter.with_severities(|rule| {
+            config
+                .get_rule(rule)
+                .map_or(Severity::Error, |rule| match rule.severity {
+                    // Ignored rules aren't enabled, so never run
+                    SeverityLevel::Ignore | SeverityLevel::Info => Severity::Info,
+                    SeverityLevel::Warning => Severity::Warning,
+                    SeverityLevel::Error => Severity::Error,
+                })
+        });
+        if let Some(bytes) = config.limits.max_file_size {
+            linter = linter.with_max_file_size(bytes);
+        }
+        if let Some(millis) = config.limits.max_parse_time_ms {
+            linter = linter.with_max_parse_time(Duration::from_millis(millis));
+        }
+        if config.organize_imports {
+            linter = linter.with_import_layout(ImportLayout::from_config(config));
+        }
+        let policy = config.suppression_policy;
+        if policy.require_reason || policy.check_expiry {
+            linter = linter.with_suppression_policy(SuppressionPolicy {
+                require_reason: policy.require_reason,
+                today: policy.check_expiry.then(today),
+            });
+        }
+        if policy.report_unused {
+            linter = linter.with_unused_suppressions();
         }
     }
+    let docs = merged_config
+        .as_ref()
+        .map(|config| config.docs.clone())
+        .unwrap_or_default();
+    linter = linter.with_doc_rules(|rule| docs.runs(rule));
+    Ok((linter, merged_config))
+}
 
-    output
+/// Today's date in UTC as `YYYY-MM-DD`.
+fn today() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    // Days since 1970-01-01 to a civil date, after Howard Hinnant's
+    // `civil_from_days`
+    let days = i64::try_from(secs / 86_400).unwrap_or(0) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
 }
 
 /// Load rules from configuration or use defaults.
@@ -661,18 +1948,37 @@ fn format_diff(path: &Path, original: &str, fixed: &str) -> String {
 fn load_rules(
     config_path: Option<&Path>,
     config_loc: Option<&Path>,
+    preset: Option<Preset>,
     base_paths: &[PathBuf],
 ) -> Result<(
     Vec<Box<dyn Rule>>,
     Option<MergedConfig>,
     Vec<PlainTextCommentFilterConfig>,
+    Vec<CommentFilterConfig>,
+    SuppressWarningsAliases,
     FileSuppressionsConfig,
 )> {
-    let registry = RuleRegistry::builtin();
+    let mut registry = RuleRegistry::builtin();
 
     // Try to load configuration
-    let (merged_config, suppression_filters, file_suppressions) =
-        load_config(config_path, config_loc, base_paths)?;
+    let (merged_config, suppression_filters, comment_filters, aliases, file_suppressions) =
+        load_config(config_path, config_loc, preset, base_paths)?;
+
+    // Plugin rules are registered alongside the builtins before any rule is created
+    for path in merged_config.iter().flat_map(|c| &c.plugins) {
+        if path.extension().is_some_and(|ext| ext == "wasm") {
+            load_wasm_plugin(&mut registry, path)?;
+            continue;
+        }
+        let library = PluginLibrary::load(path)?;
+        eprintln!(
+            "Loaded plugin '{}' from: {} ({} rule(s))",
+            library.name(),
+            path.display(),
+            library.rule_names().count()
+        );
+        registry.register_plugin(&library);
+    }
 
     let rules: Vec<Box<dyn Rule>> = match &merged_config {
         Some(config) => {
@@ -692,78 +1998,105 @@ fn load_rules(
         }
     };
 
-    Ok((rules, merged_config, suppression_filters, file_suppressions))
+    tracing::info!(rules = rules.len(), "configured rules");
+    Ok((
+        rules,
+        merged_config,
+        suppression_filters,
+        comment_filters,
+        aliases,
+        file_suppressions,
+    ))
 }
 
-/// Load merged configuration from files.
+/// Load merged configuration from files, or a preset.
 fn load_config(
     config_path: Option<&Path>,
     config_loc: Option<&Path>,
+    preset: Option<Preset>,
     base_paths: &[PathBuf],
 ) -> Result<(
     Option<MergedConfig>,
     Vec<PlainTextCommentFilterConfig>,
+    Vec<CommentFilterConfig>,
+    SuppressWarningsAliases,
     FileSuppressionsConfig,
 )> {
     // Load lintal.toml if it exists
     let lintal = find_lintal_config();
 
-    // Determine checkstyle.xml path
-    let checkstyle_path = config_path
-        .map(PathBuf::from)
-        .or_else(|| {
+    // A preset on the command line overrides lintal.toml, and one in
+    // lintal.toml is used unless it names a checkstyle.xml
+    let preset = match config_path {
+        Some(_) => None,
+        None => preset.or_else(|| {
             lintal
                 .as_ref()
-                .and_then(|l| l.checkstyle.config.clone().map(PathBuf::from))
-        })
-        .or_else(|| find_checkstyle_config(base_paths));
-
-    let Some(checkstyle_path) = checkstyle_path else {
-        return Ok((None, vec![], FileSuppressionsConfig::new()));
+                .filter(|l| l.checkstyle.config.is_none())
+                .and_then(|l| l.preset)
+        }),
     };
 
-    if !checkstyle_path.exists() {
-        anyhow::bail!("Checkstyle config not found: {}", checkstyle_path.display());
-    }
+    let (checkstyle, checkstyle_path) = if let Some(preset) = preset {
+        let checkstyle = preset
+            .config()
+            .with_context(|| format!("Failed to parse the {preset} preset"))?;
+        eprintln!("Using the {preset} preset");
+        (checkstyle, None)
+    } else {
+        // Determine checkstyle.xml path
+        let checkstyle_path = config_path
+            .map(PathBuf::from)
+            .or_else(|| {
+                lintal
+                    .as_ref()
+                    .and_then(|l| l.checkstyle.config.clone().map(PathBuf::from))
+            })
+            .or_else(|| find_checkstyle_config(base_paths));
+
+        let Some(checkstyle_path) = checkstyle_path else {
+            return Ok((
+                None,
+                vec![],
+                vec![],
+                SuppressWarningsAliases::new(),
+                FileSuppressionsConfig::new(),
+            ));
+        };
+
+        if !checkstyle_path.exists() {
+            anyhow::bail!("Checkstyle config not found: {}", checkstyle_path.display());
+        }
 
-    let checkstyle = CheckstyleConfig::from_file(&checkstyle_path)
-        .with_context(|| format!("Failed to parse {}", checkstyle_path.display()))?;
+        let checkstyle = CheckstyleConfig::from_file(&checkstyle_path)
+            .with_context(|| format!("Failed to parse {}", checkstyle_path.display()))?;
 
-    eprintln!("Loaded config from: {}", checkstyle_path.display());
+        eprintln!("Loaded config from: {}", checkstyle_path.display());
+        (checkstyle, Some(checkstyle_path))
+    };
 
     // Extract suppression filters from config
-    let suppression_filters = extract_suppression_filters(&checkstyle);
+    let suppression_filters = PlainTextCommentFilterConfig::from_checkstyle(&checkstyle);
+    let comment_filters = CommentFilterConfig::from_checkstyle(&checkstyle);
+    let aliases = SuppressWarningsAliases::from_checkstyle(&checkstyle);
 
     // Extract file-based suppressions
     // Use config_loc if provided, otherwise use the directory containing checkstyle.xml
-    let file_suppressions = extract_file_suppressions(&checkstyle, &checkstyle_path, config_loc);
+    let file_suppressions = match &checkstyle_path {
+        Some(path) => extract_file_suppressions(&checkstyle, path, config_loc),
+        // Presets have no SuppressionFilter
+        None => FileSuppressionsConfig::new(),
+    };
 
     Ok((
         Some(MergedConfig::new(&checkstyle, lintal.as_ref())),
         suppression_filters,
+        comment_filters,
+        aliases,
         file_suppressions,
     ))
 }
 
-/// Extract suppression filters from checkstyle config.
-fn extract_suppression_filters(config: &CheckstyleConfig) -> Vec<PlainTextCommentFilterConfig> {
-    let mut filters = vec![];
-
-    // Always add the default checkstyle suppression filter
-    filters.push(PlainTextCommentFilterConfig::checkstyle_default());
-
-    // Look for SuppressWithPlainTextCommentFilter modules
-    for module in &config.modules {
-        if module.name == "SuppressWithPlainTextCommentFilter"
-            && let Some(filter) = create_filter_from_module(module)
-        {
-            filters.push(filter);
-        }
-    }
-
-    filters
-}
-
 /// Extract file-based suppressions from checkstyle config.
 /// Looks for SuppressionFilter module and loads the referenced suppressions.xml file.
 fn extract_file_suppressions(
@@ -771,60 +2104,39 @@ fn extract_file_suppressions(
     checkstyle_path: &Path,
     config_loc: Option<&Path>,
 ) -> FileSuppressionsConfig {
-    // Look for SuppressionFilter module
-    for module in &config.modules {
-        if module.name == "SuppressionFilter"
-            && let Some(file_prop) = module.property("file")
+    // Resolve ${config_loc}:
+    // - If --config-loc was provided, use that directory
+    // - Otherwise, use the directory containing checkstyle.xml
+    let config_dir = config_loc
+        .or_else(|| checkstyle_path.parent())
+        .unwrap_or(Path::new("."));
+
+    for suppressions_path in FileSuppressionsConfig::files_from_checkstyle(config, config_dir) {
+        if suppressions_path.exists()
+            && let Ok(xml) = std::fs::read_to_string(&suppressions_path)
         {
-            // Resolve ${config_loc}:
-            // - If --config-loc was provided, use that directory
-            // - Otherwise, use the directory containing checkstyle.xml
-            let config_dir = config_loc
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_else(|| {
-                    checkstyle_path
-                        .parent()
-                        .map(|p| p.to_string_lossy().to_string())
-                        .unwrap_or_else(|| ".".to_string())
-                });
-
-            let resolved_path = file_prop.replace("${config_loc}", &config_dir);
-            let suppressions_path = Path::new(&resolved_path);
-
-            if suppressions_path.exists()
-                && let Ok(xml) = std::fs::read_to_string(suppressions_path)
-            {
-                let config = FileSuppressionsConfig::from_xml(&xml);
-                if !config.is_empty() {
-                    eprintln!(
-                        "Loaded {} file suppression(s) from: {}",
-                        config.len(),
-                        suppressions_path.display()
-                    );
-                }
-                return config;
+            let config =
+                FileSuppressionsConfig::from_xml(&xml).with_path(suppressions_path.clone());
+            if !config.is_empty() {
+                eprintln!(
+                    "Loaded {} file suppression(s) from: {}",
+                    config.len(),
+                    suppressions_path.display()
+                );
             }
+            return config;
         }
     }
 
     FileSuppressionsConfig::new()
 }
 
-/// Create a filter config from a checkstyle module.
-fn create_filter_from_module(
-    module: &lintal_checkstyle::Module,
-) -> Option<PlainTextCommentFilterConfig> {
-    let off_format = module.property("offCommentFormat")?;
-    let on_format = module.property("onCommentFormat")?;
-    let check_format = module.property("checkFormat");
-
-    PlainTextCommentFilterConfig::new(off_format, on_format, check_format)
-}
+/// Where lintal.toml is looked for, relative to the working directory.
+const LINTAL_CONFIG_FILES: [&str; 3] = ["lintal.toml", ".lintal.toml", "config/lintal.toml"];
 
 /// Find lintal.toml in common locations.
 fn find_lintal_config() -> Option<LintalConfig> {
-    let candidates = ["lintal.toml", ".lintal.toml", "config/lintal.toml"];
-    for candidate in candidates {
+    for candidate in LINTAL_CONFIG_FILES {
         let path = Path::new(candidate);
         if path.exists()
             && let Ok(config) = LintalConfig::from_file(path)
@@ -882,23 +2194,27 @@ fn create_rule_from_config(
 ) -> Option<Box<dyn Rule>> {
     let props = configured_rule.properties_ref();
 
-    if let Some(rule) = registry.create_rule(&configured_rule.name, &props) {
-        Some(rule)
-    } else {
-        eprintln!(
-            "{}: Unknown rule '{}', skipping",
-            "Warning".yellow(),
-            configured_rule.name
-        );
-        None
+    match registry.try_create_rule(&configured_rule.name, &props) {
+        Ok(rule) => Some(rule),
+        Err(err) => {
+            eprintln!("{}: {err}, skipping", "Warning".yellow());
+            None
+        }
     }
 }
 
 fn collect_java_files(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let _span = tracing::info_span!("collect_files").entered();
     let mut files = Vec::new();
     for path in paths {
         if path.is_file() && path.extension().is_some_and(|e| e == "java") {
             files.push(path.clone());
+        } else if path.is_file() && archive::is_archive(path) {
+            // Archives are only linted when named, not found in directories
+            match archive::java_entries(path) {
+                Ok(entries) => files.extend(entries),
+                Err(err) => eprintln!("{}: {err}", "Warning".yellow()),
+            }
         } else if path.is_dir() {
             for entry in WalkDir::new(path)
                 .into_iter()
@@ -909,96 +2225,80 @@ fn collect_java_files(paths: &[PathBuf]) -> Vec<PathBuf> {
             }
         }
     }
+    // Report files in the same order on every run, once each even if the
+    // given paths overlap
+    files.sort();
+    files.dedup();
+    tracing::info!(files = files.len(), "collected Java files");
     files
 }
 
-fn check_file(
-    path: &PathBuf,
-    rules: &[Box<dyn Rule>],
-    dispatch: &DispatchTable,
-    suppression_filters: &[PlainTextCommentFilterConfig],
-    file_suppressions: &FileSuppressionsConfig,
-) -> Result<FileCheckResult> {
-    let source = std::fs::read_to_string(path)
-        .with_context(|| format!("Failed to read {}", path.display()))?;
-
-    // Use thread-local parser to avoid repeated initialization
-    let parse_result = PARSER.with(|parser| parser.borrow_mut().parse(&source));
-    let Some(result) = parse_result else {
-        return Ok(FileCheckResult {
-            violations: vec![format!("{}: Failed to parse", path.display())],
-            violation_count: 0,
-            fixable_count: 0,
-        });
-    };
-
-    let ctx = CheckContext::new(&source);
-    let mut suppression_ctx = SuppressionContext::from_source(&source, suppression_filters);
+/// Collect the Markdown and AsciiDoc documents named in `paths`, and those
+/// in the directories among them if `in_directories`.
+fn collect_doc_files(paths: &[PathBuf], in_directories: bool) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_file() && docs::is_doc(path) {
+            files.push(path.clone());
+        } else if path.is_dir() && in_directories {
+            for entry in WalkDir::new(path)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file() && docs::is_doc(e.path()))
+            {
+                files.push(entry.path().to_path_buf());
+            }
+        }
+    }
+    files.sort();
+    files.dedup();
+    tracing::info!(files = files.len(), "collected documents");
+    files
+}
 
-    // Parse @SuppressWarnings annotations for additional suppressions
-    let root = CstNode::new(result.tree.root_node(), &source);
-    suppression_ctx.parse_suppress_warnings(&source, &root);
+/// Build the project-wide index over `files` if any rule needs it.
+///
+/// This is the first of two passes: every file is parsed and summarized here,
+/// then parsed again when it is checked.
+fn with_project_index(linter: Linter, files: &[PathBuf]) -> Linter {
+    if !linter.needs_project_index() {
+        return linter;
+    }
+    let _span = tracing::info_span!("project_index", files = files.len()).entered();
 
-    let mut violation_messages = Vec::new();
-    let mut violation_count = 0;
-    let mut fixable_count = 0;
+    let summaries: Vec<_> = files
+        .par_iter()
+        .filter_map(|path| linter.summarize_file(path))
+        .collect();
+    linter.with_project(ProjectIndex::new(summaries))
+}
 
-    let path_str = path.to_string_lossy();
+/// Resolve the charset source files are read and written in.
+fn source_charset(merged_config: Option<&MergedConfig>) -> Result<Charset> {
+    match merged_config.and_then(|config| config.charset.as_deref()) {
+        None => Ok(Charset::default()),
+        Some(name) => Charset::from_name(name)
+            .with_context(|| format!("Unsupported charset '{name}' in Checker configuration")),
+    }
+}
 
-    // Cache which rules are suppressed for this file (check once, not per-node)
-    let suppressed_rules: Option<Vec<bool>> = if file_suppressions.is_empty() {
-        None
-    } else {
-        Some(
-            rules
-                .iter()
-                .map(|rule| file_suppressions.is_suppressed(&path_str, rule.name()))
-                .collect(),
-        )
+/// Load checkstyle's message bundles for the configured locale, or the one
+/// `LANG` selects.
+fn message_bundles(merged_config: Option<&MergedConfig>) -> Result<Option<MessageBundles>> {
+    let Some(config) = merged_config else {
+        return Ok(None);
     };
-
-    let has_suppressions = suppression_ctx.has_suppressions();
-    for node in TreeWalker::new(root.inner(), &source) {
-        // Quick skip for nodes with no rules
-        let kind_id = node.kind_id();
-        if !dispatch.has_rules_for_kind(kind_id) {
-            continue;
-        }
-        for rule_idx in dispatch.rule_indices_for_kind(kind_id) {
-            if suppressed_rules.as_ref().is_some_and(|mask| mask[rule_idx]) {
-                continue;
-            }
-            let rule = &rules[rule_idx];
-            for diagnostic in rule.check(&ctx, &node) {
-                // Skip suppressed diagnostics (comment-based and @SuppressWarnings)
-                if has_suppressions
-                    && suppression_ctx.is_suppressed(rule.name(), diagnostic.range.start())
-                {
-                    continue;
-                }
-
-                violation_count += 1;
-                if diagnostic.fix.is_some() {
-                    fixable_count += 1;
-                }
-
-                let source_code = ctx.source_code();
-                let loc = source_code.line_column(diagnostic.range.start());
-                violation_messages.push(format!(
-                    "{}:{}:{}: {} {}",
-                    path.display(),
-                    loc.line.get(),
-                    loc.column.get(),
-                    format!("[{}]", rule.name()).blue(),
-                    diagnostic.kind.body
-                ));
-            }
-        }
+    let Some(dir) = &config.messages_dir else {
+        return Ok(None);
+    };
+    let locale = match &config.locale {
+        Some(locale) => Locale::parse(locale),
+        None => Locale::from_env(),
+    };
+    let messages = MessageBundles::load(dir, locale.as_ref())
+        .with_context(|| format!("Failed to load messages from {}", dir.display()))?;
+    if messages.is_empty() {
+        anyhow::bail!("No checkstyle messages found in {}", dir.display());
     }
-
-    Ok(FileCheckResult {
-        violations: violation_messages,
-        violation_count,
-        fixable_count,
-    })
+    Ok(Some(messages))
 }
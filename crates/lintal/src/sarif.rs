@@ -0,0 +1,497 @@
+//! `lintal check --output-format sarif`: a SARIF 2.1.0 log of the
+//! violations, for GitHub Code Scanning and other SARIF viewers.
+//!
+//! Each violated rule is described once in the tool's `rules`, with its
+//! description and a link to the checkstyle check it implements; results
+//! refer to it by index. Regions are counted in UTF-16 code units, SARIF's
+//! default column kind, rather than the way checkstyle counts columns.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Component, Path};
+
+use lintal_diagnostics::{Applicability, Severity};
+use lintal_linter::{AuditEvent, AuditListener, AuditSummary, Linter, SourceError};
+use lintal_source_file::{PositionEncoding, SourceCode};
+use lintal_text_size::{Ranged, TextRange};
+use serde::Serialize;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Collects violations and prints them as a SARIF log once the audit is
+/// done.
+pub(crate) struct SarifReporter<'a> {
+    linter: &'a Linter,
+    rules: Vec<SarifRule>,
+    /// Index in `rules` of each violated rule, by module name.
+    rule_indices: HashMap<&'static str, usize>,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLog<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: [SarifRun<'a>; 1],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRun<'a> {
+    tool: SarifTool<'a>,
+    results: &'a [SarifResult],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifTool<'a> {
+    driver: SarifDriver<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver<'a> {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: &'a [SarifRule],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+    /// The rule's code, or its name if it has none.
+    id: &'static str,
+    name: &'static str,
+    short_description: SarifMessage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_uri: Option<String>,
+    default_configuration: SarifConfiguration,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifConfiguration {
+    level: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: &'static str,
+    rule_index: usize,
+    level: &'static str,
+    message: SarifMessage,
+    locations: [SarifLocation; 1],
+    /// The violation's fix, left unapplied, if it has one and fixes of the
+    /// rule are enabled.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<SarifFix>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    region: SarifRegion,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+/// One-based lines and columns; the end column is exclusive.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifFix {
+    description: SarifMessage,
+    artifact_changes: [SarifArtifactChange; 1],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifArtifactChange {
+    artifact_location: SarifArtifactLocation,
+    replacements: Vec<SarifReplacement>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifReplacement {
+    deleted_region: SarifRegion,
+    inserted_content: SarifMessage,
+}
+
+impl<'a> SarifReporter<'a> {
+    pub(crate) fn new(linter: &'a Linter) -> Self {
+        Self {
+            linter,
+            rules: Vec::new(),
+            rule_indices: HashMap::new(),
+            results: Vec::new(),
+        }
+    }
+
+    /// The index of `rule` in `rules`, describing it on first use with the
+    /// `severity` of its violations.
+    fn rule_index(&mut self, rule: &'static str, severity: Severity) -> usize {
+        if let Some(&index) = self.rule_indices.get(rule) {
+            return index;
+        }
+        let configured = self.linter.rule(rule);
+        let id = configured.and_then(|r| r.code()).unwrap_or(rule);
+        let description = configured
+            .and_then(|r| r.description())
+            .map_or_else(|| format!("Checkstyle {rule} check"), str::to_string);
+        self.rules.push(SarifRule {
+            id,
+            name: rule,
+            short_description: SarifMessage { text: description },
+            help_uri: configured.and_then(|r| r.help_uri()),
+            default_configuration: SarifConfiguration {
+                level: level(severity),
+            },
+        });
+        let index = self.rules.len() - 1;
+        self.rule_indices.insert(rule, index);
+        index
+    }
+}
+
+impl AuditListener for SarifReporter<'_> {
+    fn violation(&mut self, event: &AuditEvent<'_>) {
+        let diagnostic = event.diagnostic;
+        let rule_index = self.rule_index(event.rule, diagnostic.severity);
+        let artifact_location = SarifArtifactLocation {
+            uri: artifact_uri(event.path),
+        };
+
+        let fixes = diagnostic
+            .fix
+            .as_ref()
+            .and_then(|fix| {
+                let description = match self.linter.fix_policy().applicability(event.rule, fix)? {
+                    Applicability::Safe => "Safe fix, applied by `lintal fix`",
+                    Applicability::Unsafe => "Unsafe fix, applied by `lintal fix --unsafe`",
+                    Applicability::DisplayOnly => "Suggested fix, not applied by `lintal fix`",
+                };
+                let replacements = fix
+                    .edits()
+                    .iter()
+                    .map(|edit| SarifReplacement {
+                        deleted_region: region(event.source, edit.range()),
+                        inserted_content: SarifMessage {
+                            text: edit.content().unwrap_or_default().to_string(),
+                        },
+                    })
+                    .collect();
+                Some(SarifFix {
+                    description: SarifMessage {
+                        text: description.to_string(),
+                    },
+                    artifact_changes: [SarifArtifactChange {
+                        artifact_location: artifact_location.clone(),
+                        replacements,
+                    }],
+                })
+            })
+            .into_iter()
+            .collect();
+
+        self.results.push(SarifResult {
+            rule_id: self.rules[rule_index].id,
+            rule_index,
+            level: level(diagnostic.severity),
+            message: SarifMessage {
+                text: diagnostic.kind.body.clone(),
+            },
+            locations: [SarifLocation {
+                physical_location: SarifPhysicalLocation {
+                    artifact_location,
+                    region: region(event.source, diagnostic.range),
+                },
+            }],
+            fixes,
+        });
+    }
+
+    fn file_error(&mut self, path: &Path, error: &SourceError) {
+        eprintln!("{}: {error}", path.display());
+    }
+
+    fn audit_finished(&mut self, _summary: &AuditSummary) {
+        println!("{}", self.log_json());
+    }
+}
+
+impl SarifReporter<'_> {
+    /// The SARIF log of the violations reported so far.
+    fn log_json(&self) -> String {
+        let log = SarifLog {
+            schema: SCHEMA,
+            version: "2.1.0",
+            runs: [SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "lintal",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: "https://github.com/eleventy7/lintal",
+                        rules: &self.rules,
+                    },
+                },
+                results: &self.results,
+            }],
+        };
+        serde_json::to_string_pretty(&log).expect("SARIF log serializes to JSON")
+    }
+}
+
+/// The SARIF level of violations of `severity`.
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "note",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    }
+}
+
+/// The region of `range` in `source`.
+fn region(source: &SourceCode<'_, '_>, range: TextRange) -> SarifRegion {
+    let start = source.source_location(range.start(), PositionEncoding::Utf16);
+    let end = source.source_location(range.end(), PositionEncoding::Utf16);
+    SarifRegion {
+        start_line: start.line.get(),
+        start_column: start.character_offset.get(),
+        end_line: end.line.get(),
+        end_column: end.character_offset.get(),
+    }
+}
+
+/// `path` as a URI reference: relative paths stay relative, to be resolved
+/// against the repository root by Code Scanning, and absolute ones become
+/// `file` URIs.
+fn artifact_uri(path: &Path) -> String {
+    let mut uri = String::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => continue,
+            Component::Prefix(prefix) => {
+                uri.push_str("file:///");
+                uri.push_str(&prefix.as_os_str().to_string_lossy());
+                continue;
+            }
+            Component::RootDir => {
+                if uri.is_empty() {
+                    uri.push_str("file://");
+                }
+            }
+            Component::ParentDir => uri.push_str(".."),
+            Component::Normal(name) => percent_encode(&name.to_string_lossy(), &mut uri),
+        }
+        uri.push('/');
+    }
+    uri.pop();
+    uri
+}
+
+/// Append `segment` to `uri`, escaping what isn't allowed in a path
+/// segment.
+fn percent_encode(segment: &str, uri: &mut String) {
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@".contains(&byte) {
+            uri.push(char::from(byte));
+        } else {
+            let _ = write!(uri, "%{byte:02X}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use lintal_linter::rules::UpperEll;
+    use lintal_linter::{AuditEvent, AuditListener, Linter};
+    use lintal_source_file::{LineIndex, SourceCode};
+    use lintal_text_size::TextRange;
+    use serde_json::{Value, json};
+
+    use super::{SarifReporter, artifact_uri, region};
+
+    /// The SARIF log of linting `source`, read from `path`, with UpperEll.
+    fn sarif_log(source: &str, path: &Path) -> Value {
+        let linter = Linter::new(vec![Box::new(UpperEll)]);
+        let diagnostics = linter.lint_source(source, Some(path)).unwrap();
+        let index = LineIndex::from_source_text(source);
+        let code = SourceCode::new(source, &index);
+        let mut reporter = SarifReporter::new(&linter);
+        for found in &diagnostics {
+            reporter.violation(&AuditEvent {
+                path,
+                rule: found.rule,
+                diagnostic: &found.diagnostic,
+                location: code.line_column(found.diagnostic.range.start()),
+                end_location: code.line_column(found.diagnostic.range.end()),
+                source: &code,
+            });
+        }
+        serde_json::from_str(&reporter.log_json()).unwrap()
+    }
+
+    #[test]
+    fn test_log() {
+        let source =
+            "class A {\n    String s = \"\u{1d11e}\"; long x = 10l;\n    long y = 2l;\n}\n";
+        let uri = "src/My%20Caf%C3%A9.java";
+        let result = |line: usize, start: usize, end: usize| {
+            json!({
+                "ruleId": "ST001",
+                "ruleIndex": 0,
+                "level": "error",
+                "message": { "text": "Should use uppercase 'L'." },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": uri },
+                        "region": {
+                            "startLine": line,
+                            "startColumn": start,
+                            "endLine": line,
+                            "endColumn": end,
+                        },
+                    },
+                }],
+                "fixes": [{
+                    "description": { "text": "Safe fix, applied by `lintal fix`" },
+                    "artifactChanges": [{
+                        "artifactLocation": { "uri": uri },
+                        "replacements": [{
+                            "deletedRegion": {
+                                "startLine": line,
+                                "startColumn": end - 1,
+                                "endLine": line,
+                                "endColumn": end,
+                            },
+                            "insertedContent": { "text": "L" },
+                        }],
+                    }],
+                }],
+            })
+        };
+
+        assert_eq!(
+            sarif_log(source, Path::new("src/My Café.java")),
+            json!({
+                "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+                "version": "2.1.0",
+                "runs": [{
+                    "tool": {
+                        "driver": {
+                            "name": "lintal",
+                            "version": env!("CARGO_PKG_VERSION"),
+                            "informationUri": "https://github.com/eleventy7/lintal",
+                            "rules": [{
+                                "id": "ST001",
+                                "name": "UpperEll",
+                                "shortDescription": {
+                                    "text": "Checks that long constants are defined with an upper ell.",
+                                },
+                                "helpUri": "https://checkstyle.org/checks/misc/upperell.html",
+                                "defaultConfiguration": { "level": "error" },
+                            }],
+                        },
+                    },
+                    // The clef before `10l` is two UTF-16 code units
+                    "results": [result(2, 31, 34), result(3, 14, 16)],
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_log_without_violations() {
+        let log = sarif_log("class A {}\n", Path::new("A.java"));
+        assert_eq!(log["runs"][0]["tool"]["driver"]["rules"], json!([]));
+        assert_eq!(log["runs"][0]["results"], json!([]));
+    }
+
+    #[test]
+    fn test_region() {
+        let source = "int a;\nString é = \"\u{1d11e}\";\n";
+        let index = LineIndex::from_source_text(source);
+        let code = SourceCode::new(source, &index);
+        let string = source.find('"').unwrap();
+        let range = TextRange::new(
+            u32::try_from(string).unwrap().into(),
+            u32::try_from(source.len() - 2).unwrap().into(),
+        );
+        let region = region(&code, range);
+        assert_eq!(
+            (region.start_line, region.start_column),
+            (2, 12),
+            "`é` is one UTF-16 code unit"
+        );
+        assert_eq!(
+            (region.end_line, region.end_column),
+            (2, 16),
+            "the clef is two UTF-16 code units"
+        );
+    }
+
+    #[test]
+    fn test_artifact_uri() {
+        assert_eq!(artifact_uri(Path::new("src/A.java")), "src/A.java");
+        assert_eq!(artifact_uri(Path::new("./src/A.java")), "src/A.java");
+        assert_eq!(artifact_uri(Path::new("../A.java")), "../A.java");
+        assert_eq!(
+            artifact_uri(Path::new("/work/A.java")),
+            "file:///work/A.java"
+        );
+        assert_eq!(
+            artifact_uri(Path::new("my src/A#1 100%.java")),
+            "my%20src/A%231%20100%25.java"
+        );
+        assert_eq!(
+            artifact_uri(Path::new("src/Größe.java")),
+            "src/Gr%C3%B6%C3%9Fe.java"
+        );
+        assert_eq!(
+            artifact_uri(Path::new("src/A(1);v=2.java")),
+            "src/A(1);v=2.java"
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_artifact_uri_windows() {
+        assert_eq!(
+            artifact_uri(Path::new(r"C:\work\My Project\A.java")),
+            "file:///C:/work/My%20Project/A.java"
+        );
+        assert_eq!(artifact_uri(Path::new(r"src\A.java")), "src/A.java");
+    }
+}
@@ -0,0 +1,1383 @@
+//! `lintal server`: a Language Server Protocol server on stdin and stdout,
+//! so editors show violations as files are edited.
+//!
+//! Open documents are parsed incrementally as the editor sends changes, and
+//! their violations are published after every change. Violations with a fix
+//! are offered as quick fixes, alongside source actions that apply every
+//! safe fix in the document, or those of one rule. Formatting a range, or
+//! the line a `}` or `;` was just typed on, applies the safe fixes of the
+//! whitespace rules inside it. Only the parts of the protocol lintal needs
+//! are decoded, so there is no LSP dependency. Positions are counted in
+//! UTF-16 code units, the protocol's default.
+//!
+//! The configuration is loaded again when the client reports a change to
+//! its settings or to a checkstyle.xml or lintal.toml, which the server asks
+//! clients able to watch files to watch, and open documents are linted again
+//! with it.
+//!
+//! Documents are linted on their own: rules that need an index of the whole
+//! project see none.
+
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use lintal_diagnostics::{Applicability, Severity};
+use lintal_java_parser::{JavaParser, ParseResult};
+use lintal_linter::{Linter, RuleDiagnostic, codes};
+use lintal_source_file::{LineIndex, OneIndexed, PositionEncoding, SourceLocation};
+use lintal_text_size::{Ranged, TextRange, TextSize};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// JSON-RPC error codes.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// LSP's `TextDocumentSyncKind.Incremental`.
+const INCREMENTAL_SYNC: u8 = 2;
+
+/// The kind of the source action applying every safe fix, as `lintal fix`
+/// does. Editors run `source.fixAll` actions on save if asked to.
+const FIX_ALL_KIND: &str = "source.fixAll.lintal";
+
+/// The kind of the source actions applying the safe fixes of one rule. Not
+/// under `source.fixAll`, so fixing all on save doesn't run them as well.
+const FIX_RULE_KIND: &str = "source.lintal.fixRule";
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TextDocumentIdentifier {
+    uri: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TextDocumentItem {
+    uri: String,
+    version: i32,
+    text: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VersionedTextDocumentIdentifier {
+    uri: String,
+    version: i32,
+}
+
+/// A zero-based line and UTF-16 column.
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Position {
+    line: u32,
+    character: u32,
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Range {
+    start: Position,
+    end: Position,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DidOpenParams {
+    text_document: TextDocumentItem,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DidChangeParams {
+    text_document: VersionedTextDocumentIdentifier,
+    content_changes: Vec<ContentChange>,
+}
+
+/// The text of `range`, or of the whole document without one, replaced
+/// with `text`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContentChange {
+    range: Option<Range>,
+    text: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DidCloseParams {
+    text_document: TextDocumentIdentifier,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DidChangeWatchedFilesParams {
+    changes: Vec<FileEvent>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileEvent {
+    uri: String,
+}
+
+/// The parameters of `textDocument/codeAction` and
+/// `textDocument/rangeFormatting` that lintal reads.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RangeParams {
+    text_document: TextDocumentIdentifier,
+    range: Range,
+    /// Only for code actions
+    context: Option<CodeActionContext>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CodeActionContext {
+    /// The kinds of action the client asks for, or all without it.
+    only: Option<Vec<String>>,
+}
+
+/// The parameters of `textDocument/onTypeFormatting` that lintal reads.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OnTypeFormattingParams {
+    text_document: TextDocumentIdentifier,
+    /// Just after the character typed.
+    position: Position,
+}
+
+impl RangeParams {
+    /// Whether the client asks for code actions of `kind`, directly or
+    /// through a more general kind.
+    fn wants(&self, kind: &str) -> bool {
+        let only = self
+            .context
+            .as_ref()
+            .and_then(|context| context.only.as_ref());
+        only.is_none_or(|only| {
+            only.iter().any(|wanted| {
+                kind.strip_prefix(wanted.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+            })
+        })
+    }
+}
+
+/// An open document and its violations as of its latest change.
+struct Document {
+    /// The file it was opened from, for file suppressions.
+    path: Option<PathBuf>,
+    version: i32,
+    text: String,
+    line_index: LineIndex,
+    /// The tree of `text`, to parse the next change incrementally. `None`
+    /// if it couldn't be parsed.
+    parsed: Option<ParseResult>,
+    diagnostics: Vec<RuleDiagnostic>,
+}
+
+struct Server {
+    linter: Linter,
+    /// Loads the linter again from the configuration.
+    load: Box<dyn Fn() -> Result<Linter>>,
+    /// Names of the configuration files whose changes reload the linter.
+    config_files: Vec<String>,
+    /// Whether the client can be asked to watch the configuration files.
+    watch_config: bool,
+    parser: JavaParser,
+    documents: HashMap<String, Document>,
+    shut_down: bool,
+}
+
+/// Serve the client on stdin and stdout until it exits, with the linter
+/// `load` returns, loading it again as the configuration files named
+/// `config_files` change.
+pub(crate) fn run(
+    load: impl Fn() -> Result<Linter> + 'static,
+    config_files: Vec<String>,
+) -> Result<()> {
+    let mut server = Server::new(load, config_files)?;
+    let mut stdin = std::io::stdin().lock();
+    let mut stdout = std::io::stdout().lock();
+    while let Some(body) = read_message(&mut stdin)? {
+        let message: Value = match serde_json::from_slice(&body) {
+            Ok(message) => message,
+            Err(err) => {
+                let error = rpc_error(PARSE_ERROR, format!("invalid message: {err}"));
+                write_message(&mut stdout, &response(&Value::Null, Err(error)))?;
+                continue;
+            }
+        };
+        // Responses to requests of ours; lintal sends none
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            continue;
+        };
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        if method == "exit" {
+            anyhow::ensure!(server.shut_down, "The client exited without shutting down");
+            return Ok(());
+        }
+        match message.get("id") {
+            Some(id) => {
+                let result = server.request(method, params);
+                write_message(&mut stdout, &response(id, result))?;
+            }
+            None => {
+                for notification in server.notify(method, params) {
+                    write_message(&mut stdout, &notification)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+impl Server {
+    fn new(load: impl Fn() -> Result<Linter> + 'static, config_files: Vec<String>) -> Result<Self> {
+        Ok(Self {
+            linter: load()?,
+            load: Box::new(load),
+            config_files,
+            watch_config: false,
+            parser: JavaParser::new(),
+            documents: HashMap::new(),
+            shut_down: false,
+        })
+    }
+
+    /// Answer a request with its result or an error.
+    fn request(&mut self, method: &str, params: Value) -> Result<Value, Value> {
+        if self.shut_down {
+            return Err(rpc_error(INVALID_REQUEST, "the server is shutting down"));
+        }
+        match method {
+            "initialize" => {
+                self.watch_config = params
+                    .pointer("/capabilities/workspace/didChangeWatchedFiles/dynamicRegistration")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+                Ok(json!({
+                    "capabilities": {
+                        "textDocumentSync": { "openClose": true, "change": INCREMENTAL_SYNC },
+                        "codeActionProvider": {
+                            "codeActionKinds": ["quickfix", FIX_ALL_KIND, FIX_RULE_KIND],
+                        },
+                        "documentRangeFormattingProvider": true,
+                        "documentOnTypeFormattingProvider": {
+                            "firstTriggerCharacter": "}",
+                            "moreTriggerCharacter": [";"],
+                        },
+                    },
+                    "serverInfo": { "name": "lintal", "version": env!("CARGO_PKG_VERSION") },
+                }))
+            }
+            "shutdown" => {
+                self.shut_down = true;
+                Ok(Value::Null)
+            }
+            "textDocument/codeAction" => Ok(self.code_actions(&parse_params(params)?)),
+            "textDocument/rangeFormatting" => Ok(self.format_range(&parse_params(params)?)),
+            "textDocument/onTypeFormatting" => Ok(self.format_on_type(&parse_params(params)?)),
+            _ => Err(rpc_error(
+                METHOD_NOT_FOUND,
+                format!("unsupported method {method}"),
+            )),
+        }
+    }
+
+    /// Handle a notification, returning the messages to send back.
+    fn notify(&mut self, method: &str, params: Value) -> Vec<Value> {
+        match method {
+            "initialized" if self.watch_config => return vec![self.watch_config_files()],
+            "workspace/didChangeConfiguration" => return self.reload(),
+            "workspace/didChangeWatchedFiles" => {
+                let changed = serde_json::from_value(params).is_ok_and(
+                    |params: DidChangeWatchedFilesParams| {
+                        params
+                            .changes
+                            .iter()
+                            .any(|change| self.is_config_file(&change.uri))
+                    },
+                );
+                return if changed { self.reload() } else { Vec::new() };
+            }
+            _ => {}
+        }
+        let published = match method {
+            "textDocument/didOpen" => serde_json::from_value(params)
+                .ok()
+                .map(|params: DidOpenParams| self.open(params.text_document)),
+            "textDocument/didChange" => serde_json::from_value(params)
+                .ok()
+                .and_then(|params: DidChangeParams| self.change(params)),
+            "textDocument/didClose" => {
+                serde_json::from_value(params)
+                    .ok()
+                    .map(|params: DidCloseParams| {
+                        let uri = params.text_document.uri;
+                        self.documents.remove(&uri);
+                        publish(&uri, None, &[])
+                    })
+            }
+            // `$/cancelRequest`, `workspace/didChangeWorkspaceFolders`, ...
+            _ => None,
+        };
+        published.into_iter().collect()
+    }
+
+    /// The request asking the client to report changes to the configuration
+    /// files. Its response is ignored: without it, changes to settings still
+    /// reload the configuration.
+    fn watch_config_files(&self) -> Value {
+        let watchers: Vec<Value> = self
+            .config_files
+            .iter()
+            .map(|name| json!({ "globPattern": format!("**/{name}") }))
+            .collect();
+        json!({
+            "jsonrpc": "2.0",
+            "id": "lintal/watchConfig",
+            "method": "client/registerCapability",
+            "params": { "registrations": [{
+                "id": "lintal/watchConfig",
+                "method": "workspace/didChangeWatchedFiles",
+                "registerOptions": { "watchers": watchers },
+            }] },
+        })
+    }
+
+    fn is_config_file(&self, uri: &str) -> bool {
+        uri_path(uri)
+            .as_deref()
+            .and_then(|path| path.file_name())
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| self.config_files.iter().any(|config| config == name))
+    }
+
+    /// Load the configuration again and lint the open documents with it,
+    /// returning their violations. A configuration that fails to load is
+    /// shown to the user, and the one before kept.
+    fn reload(&mut self) -> Vec<Value> {
+        match (self.load)() {
+            Ok(linter) => self.linter = linter,
+            Err(err) => {
+                return vec![json!({
+                    "jsonrpc": "2.0",
+                    "method": "window/showMessage",
+                    "params": {
+                        "type": 1,
+                        "message": format!("lintal: failed to reload the configuration: {err:#}"),
+                    },
+                })];
+            }
+        }
+        let mut documents = std::mem::take(&mut self.documents);
+        let published = documents
+            .iter_mut()
+            .map(|(uri, document)| self.lint(uri, document))
+            .collect();
+        self.documents = documents;
+        published
+    }
+
+    fn open(&mut self, item: TextDocumentItem) -> Value {
+        let parsed = self.parser.parse(&item.text);
+        let mut document = Document {
+            path: uri_path(&item.uri),
+            version: item.version,
+            line_index: LineIndex::from_source_text(&item.text),
+            text: item.text,
+            parsed,
+            diagnostics: Vec::new(),
+        };
+        let published = self.lint(&item.uri, &mut document);
+        self.documents.insert(item.uri, document);
+        published
+    }
+
+    /// Apply the changes to a document, parsing it again with its previous
+    /// tree, and lint it.
+    fn change(&mut self, params: DidChangeParams) -> Option<Value> {
+        let uri = params.text_document.uri;
+        let mut document = self.documents.remove(&uri)?;
+        for change in params.content_changes {
+            document.apply(change);
+        }
+        document.version = params.text_document.version;
+        document.parsed = match &document.parsed {
+            Some(old) => self.parser.parse_with_old_tree(&document.text, &old.tree),
+            None => self.parser.parse(&document.text),
+        };
+        let published = self.lint(&uri, &mut document);
+        self.documents.insert(uri, document);
+        Some(published)
+    }
+
+    /// Lint `document` and return the notification publishing its
+    /// violations.
+    fn lint(&self, uri: &str, document: &mut Document) -> Value {
+        let suppressed = document
+            .path
+            .as_deref()
+            .is_some_and(|path| self.linter.is_file_suppressed(path));
+        document.diagnostics = match &document.parsed {
+            Some(parsed) if !suppressed => {
+                self.linter.lint_parsed(parsed, document.path.as_deref())
+            }
+            _ => Vec::new(),
+        };
+        let diagnostics: Vec<Value> = document
+            .diagnostics
+            .iter()
+            .map(|found| self.diagnostic(document, found))
+            .collect();
+        publish(uri, Some(document.version), &diagnostics)
+    }
+
+    /// A violation as an LSP `Diagnostic`.
+    fn diagnostic(&self, document: &Document, found: &RuleDiagnostic) -> Value {
+        let diagnostic = &found.diagnostic;
+        let code = if diagnostic.kind.code.is_empty() {
+            found.rule
+        } else {
+            diagnostic.kind.code.as_str()
+        };
+        let mut value = json!({
+            "range": document.range(diagnostic.range),
+            "severity": match diagnostic.severity {
+                Severity::Error => 1,
+                Severity::Warning => 2,
+                Severity::Info => 3,
+            },
+            "code": code,
+            "source": "lintal",
+            "message": format!("{} [{}]", diagnostic.kind.body, found.rule),
+        });
+        if let Some(href) = self
+            .linter
+            .rule(found.rule)
+            .and_then(|rule| rule.help_uri())
+        {
+            value["codeDescription"] = json!({ "href": href });
+        }
+        value
+    }
+
+    /// Quick fixes for the violations with a fix that touch the requested
+    /// range, then the source actions fixing the whole document. Safe quick
+    /// fixes are preferred; fixes `lintal fix` only applies with `--unsafe`
+    /// or never are offered too, marked as such.
+    fn code_actions(&self, params: &RangeParams) -> Value {
+        let uri = &params.text_document.uri;
+        let Some(document) = self.documents.get(uri) else {
+            return json!([]);
+        };
+        let mut actions = Vec::new();
+        if params.wants("quickfix") {
+            let range = document.text_range(params.range);
+            actions.extend(
+                document
+                    .diagnostics
+                    .iter()
+                    .filter(|found| {
+                        let found = found.diagnostic.range;
+                        found.start() <= range.end() && range.start() <= found.end()
+                    })
+                    .filter_map(|found| self.quick_fix(uri, document, found)),
+            );
+        }
+        if params.wants(FIX_ALL_KIND)
+            && let Some(fixed) = self.linter.fix_source(
+                &document.text,
+                document.path.as_deref(),
+                Applicability::Safe,
+            )
+            && fixed.applied > 0
+        {
+            let whole = TextRange::up_to(TextSize::of(document.text.as_str()));
+            actions.push(json!({
+                "title": "Fix all auto-fixable problems",
+                "kind": FIX_ALL_KIND,
+                "edit": { "changes": { uri: [{
+                    "range": document.range(whole),
+                    "newText": fixed.code,
+                }] } },
+            }));
+        }
+        if params.wants(FIX_RULE_KIND) {
+            let mut rules: Vec<&'static str> = document
+                .diagnostics
+                .iter()
+                .filter(|found| self.is_safe_fix(found))
+                .map(|found| found.rule)
+                .collect();
+            rules.sort_unstable();
+            rules.dedup();
+            for rule in rules {
+                let edits = self.safe_edits(document, |found| found.rule == rule);
+                actions.push(json!({
+                    "title": format!("Fix all {rule} problems in file"),
+                    "kind": FIX_RULE_KIND,
+                    "edit": { "changes": { uri: edits } },
+                }));
+            }
+        }
+        Value::Array(actions)
+    }
+
+    /// The quick fix for one violation, if it has a fix.
+    fn quick_fix(&self, uri: &str, document: &Document, found: &RuleDiagnostic) -> Option<Value> {
+        let fix = found.diagnostic.fix.as_ref()?;
+        let applicability = self.linter.fix_policy().applicability(found.rule, fix)?;
+        let title = format!("Fix {}: {}", found.rule, found.diagnostic.kind.body);
+        let title = match applicability {
+            Applicability::Safe => title,
+            Applicability::Unsafe => format!("{title} (unsafe)"),
+            Applicability::DisplayOnly => format!("{title} (suggestion)"),
+        };
+        Some(json!({
+            "title": title,
+            "kind": "quickfix",
+            "diagnostics": [self.diagnostic(document, found)],
+            "isPreferred": applicability == Applicability::Safe,
+            "edit": { "changes": { uri: document.text_edits(fix.edits()) } },
+        }))
+    }
+
+    /// Whether a violation has a fix `lintal fix` applies without `--unsafe`.
+    fn is_safe_fix(&self, found: &RuleDiagnostic) -> bool {
+        found.diagnostic.fix.as_ref().is_some_and(|fix| {
+            self.linter.fix_policy().applicability(found.rule, fix) == Some(Applicability::Safe)
+        })
+    }
+
+    /// The edits of the safe fixes of the violations `keep` takes. A fix
+    /// overlapping one taken earlier is left out, as `lintal fix` leaves it
+    /// for its next pass.
+    fn safe_edits(
+        &self,
+        document: &Document,
+        keep: impl Fn(&RuleDiagnostic) -> bool,
+    ) -> Vec<Value> {
+        let mut taken: Vec<TextRange> = Vec::new();
+        let mut edits = Vec::new();
+        for found in &document.diagnostics {
+            let Some(fix) = &found.diagnostic.fix else {
+                continue;
+            };
+            if !self.is_safe_fix(found) || !keep(found) {
+                continue;
+            }
+            let overlaps = fix.edits().iter().any(|edit| {
+                taken
+                    .iter()
+                    .any(|other| edit.start() < other.end() && other.start() < edit.end())
+            });
+            if overlaps {
+                continue;
+            }
+            taken.extend(fix.edits().iter().map(Ranged::range));
+            edits.extend(document.text_edits(fix.edits()));
+        }
+        edits
+    }
+
+    /// The edits formatting the requested range.
+    fn format_range(&self, params: &RangeParams) -> Value {
+        let Some(document) = self.documents.get(&params.text_document.uri) else {
+            return json!([]);
+        };
+        Value::Array(self.format(document, document.text_range(params.range)))
+    }
+
+    /// The edits formatting the line a `}` or `;` was just typed on, e.g.
+    /// spacing out the statement it ends.
+    fn format_on_type(&self, params: &OnTypeFormattingParams) -> Value {
+        let Some(document) = self.documents.get(&params.text_document.uri) else {
+            return json!([]);
+        };
+        let line = params.position.line;
+        let range = document.text_range(Range {
+            start: Position { line, character: 0 },
+            end: Position {
+                line: line.saturating_add(1),
+                character: 0,
+            },
+        });
+        Value::Array(self.format(document, range))
+    }
+
+    /// The edits of the safe fixes of the whitespace rules inside `range`.
+    fn format(&self, document: &Document, range: TextRange) -> Vec<Value> {
+        self.safe_edits(document, |found| {
+            codes::category(&found.diagnostic.kind.code) == Some("Whitespace")
+                && range.contains_range(found.diagnostic.range)
+                && found.diagnostic.fix.as_ref().is_some_and(|fix| {
+                    fix.edits()
+                        .iter()
+                        .all(|edit| range.contains_range(edit.range()))
+                })
+        })
+    }
+}
+
+impl Document {
+    fn apply(&mut self, change: ContentChange) {
+        match change.range {
+            Some(range) => {
+                let range = self.text_range(range);
+                let (start, end) = (usize::from(range.start()), usize::from(range.end()));
+                if let Some(parsed) = &mut self.parsed {
+                    parsed.edit(&self.text, start, end, &change.text);
+                }
+                self.text.replace_range(start..end, &change.text);
+            }
+            None => {
+                self.text = change.text;
+                self.parsed = None;
+            }
+        }
+        // Each change's range is against the text the one before left
+        self.line_index = LineIndex::from_source_text(&self.text);
+    }
+
+    /// The offset of `position`, clamped to the text.
+    fn offset(&self, position: Position) -> TextSize {
+        let line = position.line as usize;
+        if line >= self.line_index.line_count() {
+            return TextSize::of(self.text.as_str());
+        }
+        let location = SourceLocation {
+            line: OneIndexed::from_zero_indexed(line),
+            character_offset: OneIndexed::from_zero_indexed(position.character as usize),
+        };
+        self.line_index
+            .offset(location, &self.text, PositionEncoding::Utf16)
+    }
+
+    fn text_range(&self, range: Range) -> TextRange {
+        let start = self.offset(range.start);
+        TextRange::new(start, self.offset(range.end).max(start))
+    }
+
+    fn position(&self, offset: TextSize) -> Position {
+        let location = self
+            .line_index
+            .source_location(offset, &self.text, PositionEncoding::Utf16);
+        Position {
+            line: u32::try_from(location.line.to_zero_indexed()).unwrap_or(u32::MAX),
+            character: u32::try_from(location.character_offset.to_zero_indexed())
+                .unwrap_or(u32::MAX),
+        }
+    }
+
+    fn range(&self, range: TextRange) -> Range {
+        Range {
+            start: self.position(range.start()),
+            end: self.position(range.end()),
+        }
+    }
+
+    /// `edits` as LSP `TextEdit`s.
+    fn text_edits(&self, edits: &[lintal_diagnostics::Edit]) -> Vec<Value> {
+        edits
+            .iter()
+            .map(|edit| {
+                json!({
+                    "range": self.range(edit.range()),
+                    "newText": edit.content().unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// The `textDocument/publishDiagnostics` notification of `diagnostics`.
+fn publish(uri: &str, version: Option<i32>, diagnostics: &[Value]) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "version": version, "diagnostics": diagnostics },
+    })
+}
+
+fn response(id: &Value, result: Result<Value, Value>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    }
+}
+
+fn rpc_error(code: i64, message: impl Into<String>) -> Value {
+    json!({ "code": code, "message": message.into() })
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, Value> {
+    serde_json::from_value(params)
+        .map_err(|err| rpc_error(INVALID_PARAMS, format!("invalid params: {err}")))
+}
+
+/// Read the body of the next message, or `None` once the client closes
+/// stdin.
+fn read_message(input: &mut impl BufRead) -> Result<Option<Vec<u8>>> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("Content-Length")
+        {
+            length = Some(
+                value
+                    .trim()
+                    .parse::<usize>()
+                    .with_context(|| format!("Invalid Content-Length: {value}"))?,
+            );
+        }
+    }
+    let length = length.context("Message without a Content-Length header")?;
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    Ok(Some(body))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> Result<()> {
+    let body = serde_json::to_string(message)?;
+    write!(output, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    output.flush()?;
+    Ok(())
+}
+
+/// The path of a `file` URI, e.g. `file:///work/src/A.java` or
+/// `file:///c%3A/work/A.java`.
+fn uri_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    let path = path.strip_prefix("localhost").unwrap_or(path);
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut rest = path.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = (byte == b'%')
+            .then(|| tail.get(..2))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(decoded) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            None => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    let path = String::from_utf8(bytes).ok()?;
+    // `/c:/work` on Windows
+    let path = match path.as_bytes() {
+        [b'/', drive, b':', ..] if cfg!(windows) && drive.is_ascii_alphabetic() => &path[1..],
+        _ => path.as_str(),
+    };
+    Some(PathBuf::from(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use lintal_linter::Rule;
+    use lintal_linter::rules::{ArrayTypeStyle, UpperEll, WhitespaceAround};
+
+    use super::*;
+
+    const URI: &str = "file:///work/src/A.java";
+
+    const SOURCE: &str = "class A {\n    long x = 1l;\n}\n";
+
+    fn server() -> Server {
+        server_with(|| vec![Box::new(UpperEll)])
+    }
+
+    fn server_with(rules: impl Fn() -> Vec<Box<dyn Rule>> + 'static) -> Server {
+        Server::new(move || Ok(Linter::new(rules())), Vec::new()).unwrap()
+    }
+
+    /// The code actions at the start of `URI` of the kinds `only`.
+    fn source_actions(server: &mut Server, only: &[&str]) -> Value {
+        server
+            .request(
+                "textDocument/codeAction",
+                json!({
+                    "textDocument": { "uri": URI },
+                    "range": range((0, 0), (0, 0)),
+                    "context": { "diagnostics": [], "only": only },
+                }),
+            )
+            .unwrap()
+    }
+
+    fn range(start: (u32, u32), end: (u32, u32)) -> Value {
+        json!({
+            "start": { "line": start.0, "character": start.1 },
+            "end": { "line": end.0, "character": end.1 },
+        })
+    }
+
+    /// Open `text` as the document at `URI` and return what was published.
+    fn open(server: &mut Server, text: &str) -> Value {
+        let params = json!({
+            "textDocument": { "uri": URI, "languageId": "java", "version": 1, "text": text },
+        });
+        let [published] = <[Value; 1]>::try_from(server.notify("textDocument/didOpen", params))
+            .expect("one notification");
+        published
+    }
+
+    fn document(text: &str) -> Document {
+        Document {
+            path: None,
+            version: 1,
+            text: text.to_string(),
+            line_index: LineIndex::from_source_text(text),
+            parsed: JavaParser::new().parse(text),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn messages(input: &str) -> Result<Vec<String>> {
+        let mut input = input.as_bytes();
+        let mut messages = Vec::new();
+        while let Some(body) = read_message(&mut input)? {
+            messages.push(String::from_utf8(body)?);
+        }
+        Ok(messages)
+    }
+
+    #[test]
+    fn test_read_message() {
+        let input = "Content-Length: 2\r\n\r\n{}\
+                     content-length:  7\r\n\
+                     Content-Type: application/vscode-jsonrpc; charset=utf-8\r\n\
+                     \r\n\
+                     [1,2,3]";
+        assert_eq!(messages(input).unwrap(), ["{}", "[1,2,3]"]);
+
+        let mut output = Vec::new();
+        let message = json!({ "jsonrpc": "2.0", "method": "window/logMessage", "params": "é" });
+        write_message(&mut output, &message).unwrap();
+        write_message(&mut output, &json!(null)).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        // The length counts bytes, not characters
+        assert!(
+            output.starts_with("Content-Length: 60\r\n\r\n{"),
+            "{output}"
+        );
+        assert_eq!(
+            messages(&output).unwrap(),
+            [message.to_string(), "null".to_string()]
+        );
+
+        assert!(messages("").unwrap().is_empty());
+        assert!(messages("Content-Type: text/plain\r\n\r\n{}").is_err());
+        assert!(messages("Content-Length: two\r\n\r\n{}").is_err());
+        assert!(messages("Content-Length: 10\r\n\r\n{}").is_err());
+    }
+
+    #[test]
+    fn test_uri_path() {
+        let path = |uri| uri_path(uri).map(|path| path.display().to_string());
+        assert_eq!(
+            path("file:///work/src/A.java").as_deref(),
+            Some("/work/src/A.java")
+        );
+        assert_eq!(
+            path("file:///work/My%20Project/Caf%C3%A9.java").as_deref(),
+            Some("/work/My Project/Café.java")
+        );
+        assert_eq!(
+            path("file://localhost/work/A.java").as_deref(),
+            Some("/work/A.java")
+        );
+        // A `%` that escapes nothing is kept
+        assert_eq!(
+            path("file:///work/100%.java").as_deref(),
+            Some("/work/100%.java")
+        );
+        assert_eq!(path("file:///work/%FF.java"), None);
+        assert_eq!(path("untitled:Untitled-1"), None);
+        if cfg!(windows) {
+            assert_eq!(
+                path("file:///c%3A/work/A.java").as_deref(),
+                Some(r"c:/work/A.java")
+            );
+        }
+    }
+
+    #[test]
+    fn test_document_apply() {
+        let mut document = document("class A {\n    String s = \"\u{1d11e}\"; long x = 1l;\n}\n");
+        let change = |range: Option<Value>, text: &str| ContentChange {
+            range: range.map(|range| serde_json::from_value(range).unwrap()),
+            text: text.to_string(),
+        };
+        // The clef is two UTF-16 code units, so the `l` is at 31
+        document.apply(change(Some(range((1, 31), (1, 32))), "L"));
+        // Against the text the change before left
+        document.apply(change(Some(range((2, 0), (2, 0))), "    int y;\n"));
+        assert_eq!(
+            document.text,
+            "class A {\n    String s = \"\u{1d11e}\"; long x = 1L;\n    int y;\n}\n"
+        );
+        assert!(document.parsed.is_some());
+        let int = document.text.find("int").unwrap();
+        assert_eq!(
+            usize::from(document.offset(Position {
+                line: 2,
+                character: 4
+            })),
+            int
+        );
+        assert_eq!(
+            document.offset(Position {
+                line: 10,
+                character: 0
+            }),
+            TextSize::of(document.text.as_str())
+        );
+
+        document.apply(change(None, "class B {}\n"));
+        assert_eq!(document.text, "class B {}\n");
+        assert!(document.parsed.is_none());
+        assert_eq!(document.line_index.line_count(), 2);
+    }
+
+    #[test]
+    fn test_publish_diagnostics() {
+        let mut server = server();
+        assert_eq!(
+            open(&mut server, SOURCE),
+            json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/publishDiagnostics",
+                "params": {
+                    "uri": URI,
+                    "version": 1,
+                    "diagnostics": [{
+                        "range": range((1, 13), (1, 15)),
+                        "severity": 1,
+                        "code": "ST001",
+                        "source": "lintal",
+                        "message": "Should use uppercase 'L'. [UpperEll]",
+                        "codeDescription": {
+                            "href": "https://checkstyle.org/checks/misc/upperell.html",
+                        },
+                    }],
+                },
+            })
+        );
+
+        let published = server.notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": URI, "version": 2 },
+                "contentChanges": [
+                    { "range": range((1, 14), (1, 15)), "text": "L" },
+                    { "range": range((1, 15), (1, 15)), "text": " + 2l" },
+                ],
+            }),
+        );
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0]["params"]["version"], 2);
+        let diagnostics = &published[0]["params"]["diagnostics"];
+        assert_eq!(diagnostics.as_array().unwrap().len(), 1);
+        assert_eq!(diagnostics[0]["range"], range((1, 18), (1, 20)));
+        assert_eq!(
+            server.documents[URI].text,
+            "class A {\n    long x = 1L + 2l;\n}\n"
+        );
+
+        let published = server.notify(
+            "textDocument/didClose",
+            json!({ "textDocument": { "uri": URI } }),
+        );
+        assert_eq!(published[0]["params"]["version"], Value::Null);
+        assert_eq!(published[0]["params"]["diagnostics"], json!([]));
+        assert!(server.documents.is_empty());
+
+        // Changes to documents that aren't open are ignored
+        let published = server.notify(
+            "textDocument/didChange",
+            json!({ "textDocument": { "uri": URI, "version": 3 }, "contentChanges": [] }),
+        );
+        assert!(published.is_empty());
+    }
+
+    #[test]
+    fn test_quick_fix() {
+        let mut server = server();
+        open(&mut server, SOURCE);
+        let code_actions = |server: &mut Server, at: (u32, u32)| {
+            server
+                .request(
+                    "textDocument/codeAction",
+                    json!({
+                        "textDocument": { "uri": URI },
+                        "range": range(at, at),
+                        "context": { "diagnostics": [], "only": ["quickfix"] },
+                    }),
+                )
+                .unwrap()
+        };
+
+        let actions = code_actions(&mut server, (1, 14));
+        assert_eq!(actions.as_array().unwrap().len(), 1);
+        assert_eq!(
+            actions[0]["title"],
+            "Fix UpperEll: Should use uppercase 'L'."
+        );
+        assert_eq!(actions[0]["kind"], "quickfix");
+        assert_eq!(actions[0]["isPreferred"], true);
+        assert_eq!(actions[0]["diagnostics"][0]["code"], "ST001");
+        assert_eq!(
+            actions[0]["edit"]["changes"][URI],
+            json!([{ "range": range((1, 14), (1, 15)), "newText": "L" }])
+        );
+
+        assert_eq!(code_actions(&mut server, (0, 0)), json!([]));
+    }
+
+    #[test]
+    fn test_requests() {
+        let mut server = server();
+        let initialized = server.request("initialize", json!({ "capabilities": {} }));
+        let capabilities = &initialized.unwrap()["capabilities"];
+        assert_eq!(capabilities["textDocumentSync"]["change"], INCREMENTAL_SYNC);
+        assert_eq!(capabilities["documentRangeFormattingProvider"], true);
+
+        let error = server.request("textDocument/hover", json!({})).unwrap_err();
+        assert_eq!(error["code"], METHOD_NOT_FOUND);
+        let error = server
+            .request("textDocument/codeAction", json!({ "range": 1 }))
+            .unwrap_err();
+        assert_eq!(error["code"], INVALID_PARAMS);
+        // No document is open
+        let actions = server.request(
+            "textDocument/codeAction",
+            json!({ "textDocument": { "uri": URI }, "range": range((0, 0), (0, 0)) }),
+        );
+        assert_eq!(actions, Ok(json!([])));
+
+        assert_eq!(server.request("shutdown", Value::Null), Ok(Value::Null));
+        let error = server.request("initialize", json!({})).unwrap_err();
+        assert_eq!(error["code"], INVALID_REQUEST);
+
+        assert_eq!(
+            response(&json!(7), Err(rpc_error(PARSE_ERROR, "bad"))),
+            json!({ "jsonrpc": "2.0", "id": 7, "error": { "code": PARSE_ERROR, "message": "bad" } })
+        );
+    }
+
+    #[test]
+    fn test_wants() {
+        let params = |only: Value| -> RangeParams {
+            serde_json::from_value(json!({
+                "textDocument": { "uri": URI },
+                "range": range((0, 0), (0, 0)),
+                "context": { "diagnostics": [], "only": only },
+            }))
+            .unwrap()
+        };
+        let everything = params(Value::Null);
+        assert!(everything.wants("quickfix"));
+        assert!(everything.wants(FIX_ALL_KIND));
+        assert!(everything.wants(FIX_RULE_KIND));
+
+        let source = params(json!(["source"]));
+        assert!(!source.wants("quickfix"));
+        assert!(source.wants(FIX_ALL_KIND));
+        assert!(source.wants(FIX_RULE_KIND));
+
+        let fix_all = params(json!(["source.fixAll"]));
+        assert!(fix_all.wants(FIX_ALL_KIND));
+        assert!(!fix_all.wants(FIX_RULE_KIND));
+
+        // Kinds are matched by whole components
+        assert!(!params(json!(["source.fix"])).wants(FIX_ALL_KIND));
+        assert!(!params(json!([])).wants("quickfix"));
+    }
+
+    #[test]
+    fn test_fix_all_action() {
+        let mut server = server();
+        open(
+            &mut server,
+            "class A {\n    long x = 1l;\n    long y = 2l;\n}\n",
+        );
+        assert_eq!(
+            source_actions(&mut server, &["source.fixAll"]),
+            json!([{
+                "title": "Fix all auto-fixable problems",
+                "kind": FIX_ALL_KIND,
+                "edit": { "changes": { URI: [{
+                    "range": range((0, 0), (4, 0)),
+                    "newText": "class A {\n    long x = 1L;\n    long y = 2L;\n}\n",
+                }] } },
+            }])
+        );
+
+        // Nothing to fix
+        open(&mut server, "class A {}\n");
+        assert_eq!(source_actions(&mut server, &["source"]), json!([]));
+    }
+
+    #[test]
+    fn test_fix_rule_actions() {
+        let mut server =
+            server_with(|| vec![Box::new(UpperEll), Box::new(ArrayTypeStyle::default())]);
+        open(
+            &mut server,
+            "class A {\n    long x = 1l;\n    int a[];\n    long y = 2l;\n}\n",
+        );
+
+        let actions = source_actions(&mut server, &[FIX_RULE_KIND]);
+        let actions = actions.as_array().unwrap();
+        let titles: Vec<&str> = actions
+            .iter()
+            .map(|action| action["title"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            titles,
+            [
+                "Fix all ArrayTypeStyle problems in file",
+                "Fix all UpperEll problems in file"
+            ]
+        );
+        assert!(actions.iter().all(|action| action["kind"] == FIX_RULE_KIND));
+        assert_eq!(
+            actions[1]["edit"]["changes"][URI],
+            json!([
+                { "range": range((1, 14), (1, 15)), "newText": "L" },
+                { "range": range((3, 14), (3, 15)), "newText": "L" },
+            ])
+        );
+
+        // Asked for everything, quick fixes come first and fixing all last
+        let kinds: Vec<Value> = server
+            .request(
+                "textDocument/codeAction",
+                json!({
+                    "textDocument": { "uri": URI },
+                    "range": range((1, 14), (1, 14)),
+                    "context": { "diagnostics": [] },
+                }),
+            )
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|action| action["kind"].clone())
+            .collect();
+        assert_eq!(
+            kinds,
+            ["quickfix", FIX_ALL_KIND, FIX_RULE_KIND, FIX_RULE_KIND]
+        );
+    }
+
+    /// A server watching checkstyle.xml and lintal.toml whose configuration
+    /// has UpperEll, nothing, or fails to load, as `config` says.
+    fn reloading_server(config: &Rc<Cell<Option<bool>>>) -> Server {
+        let config = Rc::clone(config);
+        let load = move || match config.get() {
+            Some(true) => Ok(Linter::new(vec![Box::new(UpperEll)])),
+            Some(false) => Ok(Linter::new(Vec::new())),
+            None => anyhow::bail!("Failed to parse checkstyle.xml"),
+        };
+        let config_files = vec!["checkstyle.xml".to_string(), "lintal.toml".to_string()];
+        Server::new(load, config_files).unwrap()
+    }
+
+    fn watched_files_changed(server: &mut Server, uri: &str) -> Vec<Value> {
+        server.notify(
+            "workspace/didChangeWatchedFiles",
+            json!({ "changes": [{ "uri": uri, "type": 2 }] }),
+        )
+    }
+
+    #[test]
+    fn test_watch_config_files() {
+        let config = Rc::new(Cell::new(Some(true)));
+        let mut server = reloading_server(&config);
+        server
+            .request("initialize", json!({ "capabilities": {} }))
+            .unwrap();
+        assert!(server.notify("initialized", json!({})).is_empty());
+
+        let mut server = reloading_server(&config);
+        let capabilities = json!({
+            "workspace": { "didChangeWatchedFiles": { "dynamicRegistration": true } },
+        });
+        server
+            .request("initialize", json!({ "capabilities": capabilities }))
+            .unwrap();
+        let [register] = <[Value; 1]>::try_from(server.notify("initialized", json!({}))).unwrap();
+        assert_eq!(register["method"], "client/registerCapability");
+        assert_eq!(
+            register["params"]["registrations"][0]["registerOptions"]["watchers"],
+            json!([{ "globPattern": "**/checkstyle.xml" }, { "globPattern": "**/lintal.toml" }])
+        );
+    }
+
+    #[test]
+    fn test_reload_changed_config() {
+        let config = Rc::new(Cell::new(Some(true)));
+        let mut server = reloading_server(&config);
+        open(&mut server, SOURCE);
+
+        config.set(Some(false));
+        assert!(watched_files_changed(&mut server, "file:///work/src/B.java").is_empty());
+        assert_eq!(server.documents[URI].diagnostics.len(), 1);
+
+        let [published] = <[Value; 1]>::try_from(watched_files_changed(
+            &mut server,
+            "file:///work/lintal.toml",
+        ))
+        .unwrap();
+        assert_eq!(published["method"], "textDocument/publishDiagnostics");
+        assert_eq!(published["params"]["uri"], URI);
+        assert_eq!(published["params"]["version"], 1);
+        assert_eq!(published["params"]["diagnostics"], json!([]));
+
+        config.set(Some(true));
+        let published = server.notify(
+            "workspace/didChangeConfiguration",
+            json!({ "settings": {} }),
+        );
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0]["params"]["diagnostics"][0]["code"], "ST001");
+    }
+
+    #[test]
+    fn test_reload_failure_keeps_config() {
+        let config = Rc::new(Cell::new(Some(true)));
+        let mut server = reloading_server(&config);
+        open(&mut server, SOURCE);
+
+        config.set(None);
+        let [message] = <[Value; 1]>::try_from(watched_files_changed(
+            &mut server,
+            "file:///work/checkstyle.xml",
+        ))
+        .unwrap();
+        assert_eq!(message["method"], "window/showMessage");
+        assert_eq!(message["params"]["type"], 1);
+        assert_eq!(
+            message["params"]["message"],
+            "lintal: failed to reload the configuration: Failed to parse checkstyle.xml"
+        );
+
+        // Linted with the configuration from before
+        let published = server.notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": URI, "version": 2 },
+                "contentChanges": [{ "range": range((1, 4), (1, 4)), "text": "final " }],
+            }),
+        );
+        assert_eq!(
+            published[0]["params"]["diagnostics"][0]["range"],
+            range((1, 19), (1, 21))
+        );
+    }
+
+    const UNFORMATTED: &str = "class A {\n    int a=1;\n    int b=2;\n    long c = 3l;\n    \
+                               void f() { int d=4; }\n}\n";
+
+    fn formatting_server() -> Server {
+        let mut server =
+            server_with(|| vec![Box::new(WhitespaceAround::default()), Box::new(UpperEll)]);
+        open(&mut server, UNFORMATTED);
+        server
+    }
+
+    /// Edits inserting a space either side of the `=` at `line`,
+    /// `character`.
+    fn spaced(line: u32, character: u32) -> Value {
+        json!([
+            { "range": range((line, character), (line, character)), "newText": " " },
+            { "range": range((line, character + 1), (line, character + 1)), "newText": " " },
+        ])
+    }
+
+    #[test]
+    fn test_range_formatting() {
+        let mut server = formatting_server();
+        let mut format = |start, end| {
+            server
+                .request(
+                    "textDocument/rangeFormatting",
+                    json!({
+                        "textDocument": { "uri": URI },
+                        "range": range(start, end),
+                        "options": { "tabSize": 4, "insertSpaces": true },
+                    }),
+                )
+                .unwrap()
+        };
+
+        // Whitespace fixes only: UpperEll's is left alone
+        let mut everything = spaced(1, 9);
+        for (line, character) in [(2, 9), (4, 20)] {
+            let edits = spaced(line, character);
+            everything
+                .as_array_mut()
+                .unwrap()
+                .extend(edits.as_array().unwrap().iter().cloned());
+        }
+        assert_eq!(format((0, 0), (6, 0)), everything);
+        assert_eq!(format((1, 0), (2, 0)), spaced(1, 9));
+        // The `=` is outside the range
+        assert_eq!(format((1, 0), (1, 9)), json!([]));
+    }
+
+    #[test]
+    fn test_on_type_formatting() {
+        let mut server = formatting_server();
+        let capabilities = server.request("initialize", json!({ "capabilities": {} }));
+        assert_eq!(
+            capabilities.unwrap()["capabilities"]["documentOnTypeFormattingProvider"],
+            json!({ "firstTriggerCharacter": "}", "moreTriggerCharacter": [";"] })
+        );
+
+        let mut typed = |ch: &str, at: (u32, u32)| {
+            server
+                .request(
+                    "textDocument/onTypeFormatting",
+                    json!({
+                        "textDocument": { "uri": URI },
+                        "position": { "line": at.0, "character": at.1 },
+                        "ch": ch,
+                        "options": { "tabSize": 4, "insertSpaces": true },
+                    }),
+                )
+                .unwrap()
+        };
+        // Only the line typed on is formatted
+        assert_eq!(typed(";", (2, 12)), spaced(2, 9));
+        assert_eq!(typed("}", (4, 25)), spaced(4, 20));
+        assert_eq!(typed(";", (3, 16)), json!([]));
+        // Past the end of the document
+        assert_eq!(typed("}", (9, 1)), json!([]));
+    }
+}
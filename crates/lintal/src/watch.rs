@@ -0,0 +1,134 @@
+//! `lintal check --watch`: check the files, then check them again as they
+//! change, until interrupted.
+//!
+//! Changes are noticed through the platform's file system notifications.
+//! The parse tree of each file is kept between checks, so a changed file is
+//! parsed incrementally, and only the files that changed are checked and
+//! reported again. If a rule needs an index of the whole project, the index
+//! is rebuilt and every file checked again on each change, as a change to
+//! one file can affect the violations of any other.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use lintal_linter::{Linter, docs};
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+use crate::{OutputFormat, collect_doc_files, collect_java_files, reporter, with_project_index};
+
+/// How long to wait for more changes after one, so that saving several
+/// files at once, or one file in several writes, is checked once.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Check `files`, those collected from `paths`, then check them again as
+/// they change. Only returns if watching fails.
+pub(crate) fn run(
+    mut linter: Linter,
+    paths: &[PathBuf],
+    mut files: Vec<PathBuf>,
+    docs_enabled: bool,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let (sender, events) = mpsc::channel();
+    let mut watcher =
+        notify::recommended_watcher(sender).context("Failed to watch for file changes")?;
+    let mut roots = Vec::new();
+    for path in paths {
+        watcher
+            .watch(path, RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {}", path.display()))?;
+        if let Ok(canonical) = path.canonicalize() {
+            roots.push((path.clone(), canonical));
+        }
+    }
+
+    // Parse trees of the files checked so far, by path
+    let mut trees = HashMap::new();
+    loop {
+        if !files.is_empty() {
+            let mut listener = reporter(&linter, output_format, files.len());
+            linter.audit_incremental(&files, &mut trees, &mut *listener);
+        }
+        eprintln!("{}", "Watching for changes...".dimmed());
+
+        let changed: Vec<PathBuf> = wait_for_changes(&events)?
+            .into_iter()
+            .map(|path| as_given(&path, &roots))
+            .collect();
+        for path in changed.iter().filter(|path| !path.exists()) {
+            trees.remove(path);
+        }
+        if linter.needs_project_index() {
+            files = collect_java_files(paths);
+            linter = with_project_index(linter, &files);
+            files.extend(collect_doc_files(paths, docs_enabled));
+            files.sort();
+        } else {
+            let changed: Vec<PathBuf> = changed
+                .into_iter()
+                .filter(|path| is_checked(path, paths, docs_enabled))
+                .collect();
+            files = collect_java_files(&changed);
+            files.extend(collect_doc_files(&changed, false));
+            files.sort();
+        }
+    }
+}
+
+/// Block until files change, then return the files that changed.
+fn wait_for_changes(events: &Receiver<notify::Result<Event>>) -> Result<BTreeSet<PathBuf>> {
+    let mut changed = BTreeSet::new();
+    loop {
+        let event = if changed.is_empty() {
+            events.recv().ok()
+        } else {
+            match events.recv_timeout(DEBOUNCE) {
+                Ok(event) => Some(event),
+                Err(RecvTimeoutError::Timeout) => return Ok(changed),
+                Err(RecvTimeoutError::Disconnected) => None,
+            }
+        };
+        let event = event
+            .context("Stopped watching for file changes")?
+            .context("Failed to watch for file changes")?;
+        let changes = matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+        );
+        // Reading a file can update its access time
+        if changes && !matches!(event.kind, EventKind::Modify(ModifyKind::Metadata(_))) {
+            changed.extend(event.paths);
+        }
+    }
+}
+
+/// `path`, as notified, under the path given on the command line that it
+/// was found under, so that it is reported and its tree kept under the same
+/// path as when it was collected.
+fn as_given(path: &Path, roots: &[(PathBuf, PathBuf)]) -> PathBuf {
+    roots
+        .iter()
+        .find_map(|(given, canonical)| {
+            let relative = path.strip_prefix(canonical).ok()?;
+            Some(if relative.as_os_str().is_empty() {
+                given.clone()
+            } else {
+                given.join(relative)
+            })
+        })
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Whether `lintal check` checks a changed file: Java files under `paths`,
+/// archives and documents named among them, and documents under them if
+/// `docs_enabled`.
+fn is_checked(path: &Path, paths: &[PathBuf], docs_enabled: bool) -> bool {
+    path.extension().is_some_and(|ext| ext == "java")
+        || paths.iter().any(|given| given == path)
+        || (docs_enabled && docs::is_doc(path))
+}
@@ -0,0 +1,678 @@
+//! Bazel persistent worker: `lintal --persistent_worker` answers `check`
+//! work requests from a long-lived process, keeping the linter of each
+//! configuration loaded between actions until its checkstyle.xml or
+//! lintal.toml changes.
+//!
+//! Bazel sends length-delimited `WorkRequest` protocol buffers on stdin and
+//! reads `WorkResponse`s from stdout; with `--worker-protocol=json` (for
+//! actions with `requires-worker-protocol: json`) each is a line of JSON
+//! instead. Only the fields lintal needs are decoded, so there is no
+//! protobuf dependency. Requests are answered one at a time, in order.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::{BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use lintal_checkstyle::Preset;
+use lintal_linter::{AuditEvent, AuditListener, AuditSummary, Linter, SourceError};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Cli, Commands, LINTAL_CONFIG_FILES, collect_java_files, load_linter, with_project_index,
+};
+
+/// How work requests and responses are encoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Protocol {
+    Proto,
+    Json,
+}
+
+/// The protocol to serve if lintal was started as a persistent worker.
+pub(crate) fn startup_protocol(args: &[String]) -> Option<Protocol> {
+    if !args.iter().any(|arg| arg == "--persistent_worker") {
+        return None;
+    }
+    let json = args.iter().any(|arg| arg == "--worker-protocol=json");
+    Some(if json {
+        Protocol::Json
+    } else {
+        Protocol::Proto
+    })
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkRequest {
+    #[serde(default)]
+    arguments: Vec<String>,
+    #[serde(default)]
+    request_id: i32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkResponse {
+    exit_code: i32,
+    output: String,
+    request_id: i32,
+}
+
+/// Serve work requests on stdin until Bazel closes it.
+pub(crate) fn run(protocol: Protocol) -> Result<()> {
+    let mut worker = Worker::default();
+    let mut stdin = std::io::stdin().lock();
+    let mut stdout = std::io::stdout().lock();
+    loop {
+        let request = match protocol {
+            Protocol::Proto => read_proto_request(&mut stdin)?,
+            Protocol::Json => read_json_request(&mut stdin)?,
+        };
+        let Some(request) = request else {
+            return Ok(());
+        };
+        let (exit_code, output) = worker.handle(&request.arguments);
+        let response = WorkResponse {
+            exit_code,
+            output,
+            request_id: request.request_id,
+        };
+        match protocol {
+            Protocol::Proto => stdout.write_all(&encode_proto_response(&response))?,
+            Protocol::Json => {
+                serde_json::to_writer(&mut stdout, &response)?;
+                stdout.write_all(b"\n")?;
+            }
+        }
+        stdout.flush()?;
+    }
+}
+
+/// Linters by the configuration options of the requests they were loaded
+/// for, with the stamp of the configuration files they were loaded from.
+#[derive(Default)]
+struct Worker {
+    linters: HashMap<LinterKey, (ConfigStamp, Linter)>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct LinterKey {
+    config: Option<PathBuf>,
+    preset: Option<Preset>,
+    config_loc: Option<PathBuf>,
+    select: Vec<String>,
+}
+
+/// The modification time and length of checkstyle.xml and of each
+/// lintal.toml, `None` for those that don't exist. A cached linter is
+/// loaded again once this changes.
+#[derive(PartialEq, Eq)]
+struct ConfigStamp(Vec<Option<(SystemTime, u64)>>);
+
+impl ConfigStamp {
+    fn of(config: Option<&Path>) -> Self {
+        let files = config
+            .into_iter()
+            .chain(LINTAL_CONFIG_FILES.iter().map(Path::new));
+        Self(
+            files
+                .map(|path| {
+                    let metadata = std::fs::metadata(path).ok()?;
+                    Some((metadata.modified().ok()?, metadata.len()))
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Worker {
+    /// Run the `lintal` command line `arguments` and return its exit code
+    /// and output.
+    fn handle(&mut self, arguments: &[String]) -> (i32, String) {
+        let arguments = match expand_param_files(arguments) {
+            Ok(arguments) => arguments,
+            Err(err) => return (2, format!("{err:#}\n")),
+        };
+        let cli = match Cli::try_parse_from(std::iter::once("lintal".to_string()).chain(arguments))
+        {
+            Ok(cli) => cli,
+            Err(err) => return (2, err.render().to_string()),
+        };
+        let Commands::Check {
+            paths,
+            config,
+            config_loc,
+            preset,
+            select,
+            fail_on,
+            ..
+        } = cli.command
+        else {
+            return (
+                2,
+                "Only `lintal check` runs as a persistent worker\n".to_string(),
+            );
+        };
+
+        let files = collect_java_files(&paths);
+        let mut reporter = WorkerReporter::default();
+        // Without --config or --preset the configuration is looked up next
+        // to the paths, so the linter can only be kept for an explicit one
+        let key = (config.is_some() || preset.is_some()).then(|| LinterKey {
+            config: config.clone(),
+            preset,
+            config_loc: config_loc.clone(),
+            select: select.clone(),
+        });
+        // Stamped before loading, so a change while loading is seen next time
+        let stamp = ConfigStamp::of(config.as_deref());
+        let cached = key
+            .as_ref()
+            .and_then(|key| self.linters.get(key))
+            .filter(|(loaded, _)| *loaded == stamp);
+        let summary = match cached {
+            Some((_, linter)) => linter.audit(&files, &mut reporter),
+            None => {
+                let linter = match load_linter(
+                    config.as_deref(),
+                    config_loc.as_deref(),
+                    preset,
+                    &paths,
+                    &select,
+                ) {
+                    Ok((linter, _)) => linter,
+                    Err(err) => return (2, format!("{err:#}\n")),
+                };
+                if linter.needs_project_index() {
+                    // The index is of the files of one request
+                    with_project_index(linter, &files).audit(&files, &mut reporter)
+                } else {
+                    let summary = linter.audit(&files, &mut reporter);
+                    if let Some(key) = key {
+                        self.linters.insert(key, (stamp, linter));
+                    }
+                    summary
+                }
+            }
+        };
+        (
+            i32::from(summary.violations_at_least(fail_on.severity()) > 0),
+            reporter.output,
+        )
+    }
+}
+
+/// Replace each `@file` argument with the arguments in the file, one per
+/// line, as Bazel writes parameter files for workers.
+fn expand_param_files(arguments: &[String]) -> Result<Vec<String>> {
+    let mut expanded = Vec::with_capacity(arguments.len());
+    for argument in arguments {
+        match argument.strip_prefix('@') {
+            Some(path) => {
+                let contents = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read parameter file {path}"))?;
+                expanded.extend(contents.lines().map(str::to_string));
+            }
+            None => expanded.push(argument.clone()),
+        }
+    }
+    Ok(expanded)
+}
+
+/// Collects violations as `lintal check` prints them, without colour.
+#[derive(Default)]
+struct WorkerReporter {
+    output: String,
+}
+
+impl AuditListener for WorkerReporter {
+    fn violation(&mut self, event: &AuditEvent<'_>) {
+        writeln!(
+            self.output,
+            "{}:{}:{}: [{}] {}",
+            event.path.display(),
+            event.location.line,
+            event.location.column,
+            event.rule,
+            event.diagnostic.kind.body
+        )
+        .unwrap();
+    }
+
+    fn file_error(&mut self, path: &Path, error: &SourceError) {
+        writeln!(self.output, "{}: {error}", path.display()).unwrap();
+    }
+
+    fn audit_finished(&mut self, summary: &AuditSummary) {
+        if summary.violations > 0 {
+            writeln!(
+                self.output,
+                "Found {} violations ({} fixable)",
+                summary.violations, summary.fixable
+            )
+            .unwrap();
+        }
+    }
+}
+
+fn read_json_request(input: &mut impl BufRead) -> Result<Option<WorkRequest>> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        if !line.trim().is_empty() {
+            return serde_json::from_str(&line)
+                .map(Some)
+                .context("Invalid JSON work request");
+        }
+    }
+}
+
+/// Read one length-delimited `WorkRequest`, or `None` at the end of input.
+fn read_proto_request(input: &mut impl Read) -> Result<Option<WorkRequest>> {
+    let mut first = [0u8];
+    if input.read(&mut first)? == 0 {
+        return Ok(None);
+    }
+    let mut len = u64::from(first[0] & 0x7f);
+    let mut shift = 7;
+    let mut byte = first[0];
+    while byte & 0x80 != 0 {
+        let mut next = [0u8];
+        input.read_exact(&mut next)?;
+        byte = next[0];
+        anyhow::ensure!(shift < 64, "Invalid work request length");
+        len |= u64::from(byte & 0x7f) << shift;
+        shift += 7;
+    }
+    // Read rather than allocate up front, so a corrupt length allocates no
+    // more than the input holds
+    let mut message = Vec::new();
+    input.take(len).read_to_end(&mut message)?;
+    anyhow::ensure!(message.len() as u64 == len, "Truncated work request");
+    decode_proto_request(&message).map(Some)
+}
+
+/// Decode the `arguments` (1) and `request_id` (3) fields of a
+/// `WorkRequest`, skipping the others.
+fn decode_proto_request(mut message: &[u8]) -> Result<WorkRequest> {
+    let mut request = WorkRequest::default();
+    while !message.is_empty() {
+        let key = read_varint(&mut message)?;
+        match (key >> 3, key & 7) {
+            (1, 2) => {
+                let bytes = read_length_delimited(&mut message)?;
+                request
+                    .arguments
+                    .push(String::from_utf8(bytes.to_vec()).context("Argument is not UTF-8")?);
+            }
+            // int32 is sign-extended to 64 bits, so truncating restores it
+            #[expect(clippy::cast_possible_truncation)]
+            (3, 0) => request.request_id = read_varint(&mut message)? as i32,
+            (_, 0) => {
+                read_varint(&mut message)?;
+            }
+            (_, 1) => skip(&mut message, 8)?,
+            (_, 2) => {
+                read_length_delimited(&mut message)?;
+            }
+            (_, 5) => skip(&mut message, 4)?,
+            (field, wire_type) => {
+                anyhow::bail!("Unsupported wire type {wire_type} of field {field}")
+            }
+        }
+    }
+    Ok(request)
+}
+
+fn read_varint(input: &mut &[u8]) -> Result<u64> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = input.split_first().context("Truncated work request")?;
+        *input = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    anyhow::bail!("Invalid varint in work request")
+}
+
+fn read_length_delimited<'a>(input: &mut &'a [u8]) -> Result<&'a [u8]> {
+    let len = usize::try_from(read_varint(input)?)?;
+    anyhow::ensure!(len <= input.len(), "Truncated work request");
+    let (bytes, rest) = input.split_at(len);
+    *input = rest;
+    Ok(bytes)
+}
+
+fn skip(input: &mut &[u8], len: usize) -> Result<()> {
+    anyhow::ensure!(len <= input.len(), "Truncated work request");
+    *input = &input[len..];
+    Ok(())
+}
+
+/// Encode a `WorkResponse`, prefixed with its length.
+fn encode_proto_response(response: &WorkResponse) -> Vec<u8> {
+    let mut message = Vec::new();
+    if response.exit_code != 0 {
+        message.push(1 << 3);
+        write_varint(&mut message, i64::from(response.exit_code).cast_unsigned());
+    }
+    if !response.output.is_empty() {
+        message.push((2 << 3) | 2);
+        write_varint(&mut message, response.output.len() as u64);
+        message.extend_from_slice(response.output.as_bytes());
+    }
+    if response.request_id != 0 {
+        message.push(3 << 3);
+        write_varint(&mut message, i64::from(response.request_id).cast_unsigned());
+    }
+
+    let mut delimited = Vec::with_capacity(message.len() + 5);
+    write_varint(&mut delimited, message.len() as u64);
+    delimited.extend(message);
+    delimited
+}
+
+#[expect(clippy::cast_possible_truncation)]
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `message` prefixed with its length, as Bazel delimits messages.
+    fn delimited(message: &[u8]) -> Vec<u8> {
+        let mut delimited = Vec::new();
+        write_varint(&mut delimited, message.len() as u64);
+        delimited.extend_from_slice(message);
+        delimited
+    }
+
+    fn encode_string(out: &mut Vec<u8>, field: u8, value: &str) {
+        out.push((field << 3) | 2);
+        write_varint(out, value.len() as u64);
+        out.extend_from_slice(value.as_bytes());
+    }
+
+    /// A `WorkRequest` as Bazel encodes it.
+    fn encode_request(arguments: &[&str], request_id: i32) -> Vec<u8> {
+        let mut message = Vec::new();
+        for argument in arguments {
+            encode_string(&mut message, 1, argument);
+        }
+        if request_id != 0 {
+            message.push(3 << 3);
+            write_varint(&mut message, i64::from(request_id).cast_unsigned());
+        }
+        delimited(&message)
+    }
+
+    /// The exit code, output and request id of a length-delimited
+    /// `WorkResponse`.
+    #[expect(clippy::cast_possible_truncation)]
+    fn decode_response(mut input: &[u8]) -> (i32, String, i32) {
+        let mut message = read_length_delimited(&mut input).unwrap();
+        assert!(input.is_empty());
+        let mut response = (0, String::new(), 0);
+        while !message.is_empty() {
+            match read_varint(&mut message).unwrap() {
+                0x08 => response.0 = read_varint(&mut message).unwrap() as i32,
+                0x12 => {
+                    let output = read_length_delimited(&mut message).unwrap();
+                    response.1 = String::from_utf8(output.to_vec()).unwrap();
+                }
+                0x18 => response.2 = read_varint(&mut message).unwrap() as i32,
+                key => panic!("unexpected key {key:#x}"),
+            }
+        }
+        response
+    }
+
+    fn read_requests(input: &[u8]) -> Vec<(Vec<String>, i32)> {
+        let mut input = input;
+        let mut requests = Vec::new();
+        while let Some(request) = read_proto_request(&mut input).unwrap() {
+            requests.push((request.arguments, request.request_id));
+        }
+        requests
+    }
+
+    #[test]
+    fn test_read_proto_requests() {
+        let mut input = encode_request(&["check", "--config", "my config.xml", "src"], 7);
+        input.extend(encode_request(&["check", "é.java"], 0));
+        input.extend(encode_request(&[], 8));
+
+        assert_eq!(
+            read_requests(&input),
+            [
+                (
+                    vec![
+                        "check".to_string(),
+                        "--config".to_string(),
+                        "my config.xml".to_string(),
+                        "src".to_string()
+                    ],
+                    7
+                ),
+                (vec!["check".to_string(), "é.java".to_string()], 0),
+                (vec![], 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_negative_request_id() {
+        // Negative int32s are sign-extended to ten-byte varints
+        let input = encode_request(&["check"], -1);
+        assert_eq!(input[0], 18);
+        assert_eq!(read_requests(&input), [(vec!["check".to_string()], -1)]);
+
+        let input = encode_request(&[], i32::MIN);
+        assert_eq!(read_requests(&input), [(vec![], i32::MIN)]);
+    }
+
+    #[test]
+    fn test_multi_byte_lengths() {
+        let argument = "a".repeat(300);
+        let input = encode_request(&[&argument], 1);
+        // 305 bytes of message, in a two-byte length
+        assert_eq!(input[..2], [0xb1, 0x02]);
+        assert_eq!(read_requests(&input), [(vec![argument], 1)]);
+    }
+
+    #[test]
+    fn test_skip_unknown_fields() {
+        let mut message = Vec::new();
+        // inputs: a nested Input with a path and digest
+        let mut input = Vec::new();
+        encode_string(&mut input, 1, "src/A.java");
+        encode_string(&mut input, 2, "digest");
+        message.push((2 << 3) | 2);
+        write_varint(&mut message, input.len() as u64);
+        message.extend(input);
+        encode_string(&mut message, 1, "check");
+        // verbosity
+        message.extend([4 << 3, 10]);
+        // sandbox_dir
+        encode_string(&mut message, 6, "/sandbox/1");
+        encode_string(&mut message, 1, "src");
+        // A fixed64 and a fixed32 field of a newer protocol
+        message.push((9 << 3) | 1);
+        message.extend([0xff; 8]);
+        message.push((10 << 3) | 5);
+        message.extend([0xff; 4]);
+        message.extend([3 << 3, 42]);
+
+        let request = decode_proto_request(&message).unwrap();
+        assert_eq!(request.arguments, ["check", "src"]);
+        assert_eq!(request.request_id, 42);
+    }
+
+    #[test]
+    fn test_truncated_requests() {
+        let truncated: [&[u8]; 4] = [
+            // The length's varint is cut off
+            &[0x80],
+            // Fewer bytes than the length
+            &[0x05, 0x0a, 0x01],
+            // An argument longer than the message
+            &[0x03, 0x0a, 0x05, b'a'],
+            // A request id without its value
+            &[0x02, 0x18, 0x80],
+        ];
+        for input in truncated {
+            let mut input = input;
+            assert!(read_proto_request(&mut input).is_err(), "{input:?}");
+        }
+
+        // A corrupt length larger than any request
+        let mut input = Vec::new();
+        write_varint(&mut input, 1 << 40);
+        input.extend(encode_request(&["check"], 1));
+        assert!(read_proto_request(&mut input.as_slice()).is_err());
+
+        assert!(decode_proto_request(&[(9 << 3) | 1, 1, 2, 3]).is_err());
+        assert!(decode_proto_request(&[(10 << 3) | 5, 1]).is_err());
+        // Groups are not supported
+        assert!(decode_proto_request(&[(4 << 3) | 3]).is_err());
+    }
+
+    #[test]
+    fn test_varint() {
+        for (value, len) in [
+            (0, 1),
+            (1, 1),
+            (127, 1),
+            (128, 2),
+            (300, 2),
+            (u64::from(u32::MAX), 5),
+            (u64::MAX, 10),
+        ] {
+            let mut out = Vec::new();
+            write_varint(&mut out, value);
+            assert_eq!(out.len(), len, "{value}");
+            let mut input = out.as_slice();
+            assert_eq!(read_varint(&mut input).unwrap(), value);
+            assert!(input.is_empty());
+        }
+
+        let mut input: &[u8] = &[0xff; 11];
+        assert!(read_varint(&mut input).is_err());
+    }
+
+    #[test]
+    fn test_encode_proto_response() {
+        let response = WorkResponse {
+            exit_code: 1,
+            output: format!(
+                "{}\n",
+                "A.java:1:2: [UpperEll] Should use uppercase 'L'.".repeat(3)
+            ),
+            request_id: -3,
+        };
+        let encoded = encode_proto_response(&response);
+        assert_ne!(encoded[0] & 0x80, 0, "two-byte length");
+        assert_eq!(
+            decode_response(&encoded),
+            (response.exit_code, response.output, response.request_id)
+        );
+
+        // Default values are left out
+        let response = WorkResponse {
+            exit_code: 0,
+            output: String::new(),
+            request_id: 0,
+        };
+        assert_eq!(encode_proto_response(&response), [0]);
+    }
+
+    #[test]
+    fn test_json_protocol() {
+        let mut input =
+            "\n{\"arguments\": [\"check\", \"src\"], \"requestId\": 4}\n\n{}\n".as_bytes();
+        let request = read_json_request(&mut input).unwrap().unwrap();
+        assert_eq!(request.arguments, ["check", "src"]);
+        assert_eq!(request.request_id, 4);
+        let request = read_json_request(&mut input).unwrap().unwrap();
+        assert!(request.arguments.is_empty());
+        assert!(read_json_request(&mut input).unwrap().is_none());
+
+        assert!(read_json_request(&mut "{\"arguments\": \n".as_bytes()).is_err());
+
+        let response = WorkResponse {
+            exit_code: 2,
+            output: "error\n".to_string(),
+            request_id: 4,
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"exitCode":2,"output":"error\n","requestId":4}"#
+        );
+    }
+
+    #[test]
+    fn test_reload_changed_config() {
+        let dir = std::env::temp_dir().join(format!("lintal-worker-{}", std::process::id()));
+        let src = dir.join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("A.java"), "class A {\n    long x = 1l;\n}\n").unwrap();
+        let config = dir.join("checkstyle.xml");
+        let write_config = |modules: &str| {
+            std::fs::write(
+                &config,
+                format!(
+                    "<module name=\"Checker\"><module name=\"TreeWalker\">{modules}\
+                     </module></module>"
+                ),
+            )
+            .unwrap();
+        };
+        let arguments = [
+            "check".to_string(),
+            "--config".to_string(),
+            config.display().to_string(),
+            src.display().to_string(),
+        ];
+
+        let mut worker = Worker::default();
+        write_config("<module name=\"UpperEll\"/>");
+        let first = worker.handle(&arguments);
+        let cached = worker.handle(&arguments);
+        write_config("");
+        let reloaded = worker.handle(&arguments);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first.0, 1, "{}", first.1);
+        assert!(first.1.contains("[UpperEll]"), "{}", first.1);
+        assert_eq!(cached, first);
+        assert_eq!(reloaded, (0, String::new()));
+        assert_eq!(worker.linters.len(), 1);
+    }
+
+    #[test]
+    fn test_linter_key_has_preset() {
+        let key = |preset| LinterKey {
+            config: None,
+            preset,
+            config_loc: None,
+            select: Vec::new(),
+        };
+        assert!(key(Some(Preset::Google)) != key(Some(Preset::Sun)));
+        assert!(key(None) != key(Some(Preset::Sun)));
+    }
+}
@@ -0,0 +1,123 @@
+/*
+ * C API for lintal.
+ *
+ * Link against liblintal_capi (built with `cargo build --release -p
+ * lintal_capi`). Sources are passed as UTF-8 buffers with an explicit length
+ * and need not be NUL-terminated.
+ *
+ *     LintalLinter *linter;
+ *     if (lintal_linter_new("config/checkstyle/checkstyle.xml", &linter) != LINTAL_OK) {
+ *         fprintf(stderr, "%s\n", lintal_last_error());
+ *         return 1;
+ *     }
+ *     LintalDiagnostics *diagnostics;
+ *     if (lintal_lint(linter, source, source_len, &diagnostics) == LINTAL_OK) {
+ *         for (size_t i = 0; i < lintal_diagnostics_len(diagnostics); i++) {
+ *             const LintalDiagnostic *d = lintal_diagnostics_get(diagnostics, i);
+ *             printf("%zu:%zu: [%s] %s\n", d->line, d->column, d->rule, d->message);
+ *         }
+ *         lintal_diagnostics_free(diagnostics);
+ *     }
+ *     lintal_linter_free(linter);
+ *
+ * Objects are owned by the caller and released with the matching _free
+ * function. A LintalLinter may be used from several threads at once.
+ *
+ * New functions may be added in later versions; existing declarations and
+ * struct layouts only change along with LINTAL_CAPI_VERSION.
+ */
+
+#ifndef LINTAL_H
+#define LINTAL_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define LINTAL_CAPI_VERSION 1
+
+typedef enum LintalStatus {
+    LINTAL_OK = 0,
+    /* A null pointer, or a source that isn't valid UTF-8. */
+    LINTAL_INVALID_ARGUMENT = 1,
+    /* The configuration couldn't be loaded. */
+    LINTAL_CONFIG_ERROR = 2,
+    /* The source couldn't be parsed as Java. */
+    LINTAL_PARSE_ERROR = 3,
+    /* lintal hit an internal error. */
+    LINTAL_INTERNAL_ERROR = 4,
+} LintalStatus;
+
+/* Configured rules. */
+typedef struct LintalLinter LintalLinter;
+
+/* The diagnostics of one source. */
+typedef struct LintalDiagnostics LintalDiagnostics;
+
+/* A fixed source. */
+typedef struct LintalFixed LintalFixed;
+
+typedef struct LintalDiagnostic {
+    /* Checkstyle module name of the rule, e.g. "LineLength". */
+    const char *rule;
+    const char *message;
+    /* Byte offsets of the violation in the source. */
+    size_t start;
+    size_t end;
+    /* One-based line and column, as the lintal CLI reports them. */
+    size_t line;
+    size_t column;
+    size_t end_line;
+    size_t end_column;
+    /* Whether lintal_fix fixes the violation without unsafe_fixes. */
+    bool fixable;
+} LintalDiagnostic;
+
+/* The LINTAL_CAPI_VERSION the library was built with. */
+unsigned int lintal_capi_version(void);
+
+/*
+ * Why the last call on this thread failed. Valid until the next failing call
+ * on this thread; never null.
+ */
+const char *lintal_last_error(void);
+
+/*
+ * Load the rules of a checkstyle.xml, or the rules the CLI uses when it finds
+ * none if config_path is null.
+ */
+LintalStatus lintal_linter_new(const char *config_path, LintalLinter **out);
+
+void lintal_linter_free(LintalLinter *linter);
+
+LintalStatus lintal_lint(const LintalLinter *linter, const char *source, size_t len,
+                         LintalDiagnostics **out);
+
+size_t lintal_diagnostics_len(const LintalDiagnostics *diagnostics);
+
+/* The diagnostic at index, or null if index is out of range. */
+const LintalDiagnostic *lintal_diagnostics_get(const LintalDiagnostics *diagnostics,
+                                               size_t index);
+
+void lintal_diagnostics_free(LintalDiagnostics *diagnostics);
+
+/* Apply fixes to a source; with unsafe_fixes, unsafe fixes are applied too. */
+LintalStatus lintal_fix(const LintalLinter *linter, const char *source, size_t len,
+                        bool unsafe_fixes, LintalFixed **out);
+
+/* The fixed source, with its length in *len. Not NUL-terminated. */
+const char *lintal_fixed_source(const LintalFixed *fixed, size_t *len);
+
+/* How many fixes were applied. */
+size_t lintal_fixed_applied(const LintalFixed *fixed);
+
+void lintal_fixed_free(LintalFixed *fixed);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* LINTAL_H */
@@ -0,0 +1,464 @@
+//! C API for lintal, declared in `include/lintal.h`.
+//!
+//! Every function catches panics and reports failures as a [`LintalStatus`],
+//! with the message available from [`lintal_last_error`] until the next
+//! failure on the same thread.
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char, c_uint};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::path::Path;
+
+use lintal_checkstyle::{CheckstyleConfig, MergedConfig};
+use lintal_diagnostics::Applicability;
+use lintal_linter::{FixedSource, Rule, RuleDiagnostic, RuleRegistry};
+use lintal_source_file::{LineIndex, PositionEncoding};
+
+/// Bumped whenever a declaration in `lintal.h` changes incompatibly.
+pub const LINTAL_CAPI_VERSION: c_uint = 1;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintalStatus {
+    Ok = 0,
+    InvalidArgument = 1,
+    ConfigError = 2,
+    ParseError = 3,
+    InternalError = 4,
+}
+
+/// Configured rules.
+pub struct LintalLinter {
+    rules: Vec<Box<dyn Rule>>,
+    tab_width: usize,
+}
+
+/// The diagnostics of one source, and the strings they point into.
+pub struct LintalDiagnostics {
+    items: Vec<LintalDiagnostic>,
+    _strings: Vec<CString>,
+}
+
+/// A fixed source.
+pub struct LintalFixed {
+    fixed: FixedSource,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct LintalDiagnostic {
+    pub rule: *const c_char,
+    pub message: *const c_char,
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    pub fixable: bool,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// A failed call: the status to return and the message for
+/// [`lintal_last_error`].
+struct Failure(LintalStatus, String);
+
+impl Failure {
+    fn null(name: &str) -> Self {
+        Self(LintalStatus::InvalidArgument, format!("{name} is null"))
+    }
+}
+
+/// Run `f`, storing its result in `out` and recording any failure, including
+/// a panic, which must not unwind into the caller.
+fn call<T>(out: *mut *mut T, f: impl FnOnce() -> Result<T, Failure>) -> LintalStatus {
+    if out.is_null() {
+        return fail(Failure::null("out"));
+    }
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => {
+            // SAFETY: `out` is non-null and, per the header, writable
+            unsafe { *out = Box::into_raw(Box::new(value)) };
+            LintalStatus::Ok
+        }
+        Ok(Err(failure)) => fail(failure),
+        Err(_) => fail(Failure(
+            LintalStatus::InternalError,
+            "lintal panicked".to_string(),
+        )),
+    }
+}
+
+fn fail(Failure(status, message): Failure) -> LintalStatus {
+    // Messages don't contain NUL, but don't lose the error if one does
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with_borrow_mut(|last| *last = message);
+    status
+}
+
+/// Borrow the UTF-8 source buffer `source[..len]`.
+///
+/// # Safety
+///
+/// `source` must be null or valid for reads of `len` bytes.
+unsafe fn source_str<'a>(source: *const c_char, len: usize) -> Result<&'a str, Failure> {
+    if source.is_null() {
+        return Err(Failure::null("source"));
+    }
+    // SAFETY: guaranteed by the caller
+    let bytes = unsafe { std::slice::from_raw_parts(source.cast::<u8>(), len) };
+    std::str::from_utf8(bytes)
+        .map_err(|err| Failure(LintalStatus::InvalidArgument, format!("source: {err}")))
+}
+
+impl LintalLinter {
+    fn load(config: Option<&Path>) -> Result<Self, Failure> {
+        let Some(path) = config else {
+            let defaults: Vec<Box<dyn Rule>> =
+                vec![Box::new(lintal_linter::rules::WhitespaceAround::default())];
+            return Ok(Self {
+                rules: defaults,
+                tab_width: MergedConfig::DEFAULT_TAB_WIDTH,
+            });
+        };
+
+        let config_error = |message: String| Failure(LintalStatus::ConfigError, message);
+        let checkstyle = CheckstyleConfig::from_file(path)
+            .map_err(|err| config_error(format!("{}: {err}", path.display())))?;
+        let config = MergedConfig::new(&checkstyle, None);
+        let rules = RuleRegistry::builtin()
+            .create_rules(&config)
+            .map_err(|err| config_error(err.to_string()))?;
+        Ok(Self {
+            rules,
+            tab_width: config.tab_width,
+        })
+    }
+
+    fn diagnostics(&self, source: &str) -> Result<LintalDiagnostics, Failure> {
+        let diagnostics = lintal_linter::lint_source(source, &self.rules).ok_or_else(|| {
+            Failure(
+                LintalStatus::ParseError,
+                "Failed to parse source".to_string(),
+            )
+        })?;
+        let index = LineIndex::from_source_text(source);
+        let mut strings = Vec::with_capacity(diagnostics.len() * 2);
+        let items = diagnostics
+            .into_iter()
+            .map(|diagnostic| self.to_c(source, &index, diagnostic, &mut strings))
+            .collect();
+        Ok(LintalDiagnostics {
+            items,
+            _strings: strings,
+        })
+    }
+
+    fn to_c(
+        &self,
+        source: &str,
+        index: &LineIndex,
+        rule_diagnostic: RuleDiagnostic,
+        strings: &mut Vec<CString>,
+    ) -> LintalDiagnostic {
+        let RuleDiagnostic { rule, diagnostic } = rule_diagnostic;
+        let encoding = self
+            .rules
+            .iter()
+            .find(|r| r.name() == rule)
+            .map_or(PositionEncoding::Utf32, |r| r.column_encoding());
+        let start =
+            index.checkstyle_column(diagnostic.range.start(), source, self.tab_width, encoding);
+        let end = index.checkstyle_column(diagnostic.range.end(), source, self.tab_width, encoding);
+
+        let mut c_string = |text: &str| {
+            let string = CString::new(text.replace('\0', " ")).unwrap_or_default();
+            // The heap buffer doesn't move when the CString does
+            let ptr = string.as_ptr();
+            strings.push(string);
+            ptr
+        };
+        LintalDiagnostic {
+            rule: c_string(rule),
+            message: c_string(&diagnostic.kind.body),
+            start: diagnostic.range.start().to_usize(),
+            end: diagnostic.range.end().to_usize(),
+            line: start.line.get(),
+            column: start.column.get(),
+            end_line: end.line.get(),
+            end_column: end.column.get(),
+            fixable: diagnostic
+                .fix
+                .as_ref()
+                .is_some_and(|fix| fix.applies(Applicability::Safe)),
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn lintal_capi_version() -> c_uint {
+    LINTAL_CAPI_VERSION
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn lintal_last_error() -> *const c_char {
+    LAST_ERROR.with_borrow(|last| last.as_ptr())
+}
+
+/// # Safety
+///
+/// `config_path` must be null or a NUL-terminated string, and `out` writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lintal_linter_new(
+    config_path: *const c_char,
+    out: *mut *mut LintalLinter,
+) -> LintalStatus {
+    call(out, || {
+        if config_path.is_null() {
+            return LintalLinter::load(None);
+        }
+        // SAFETY: guaranteed by the caller
+        let path = unsafe { CStr::from_ptr(config_path) }
+            .to_str()
+            .map_err(|err| Failure(LintalStatus::InvalidArgument, format!("config_path: {err}")))?;
+        LintalLinter::load(Some(Path::new(path)))
+    })
+}
+
+/// # Safety
+///
+/// `linter` must be null or from [`lintal_linter_new`], and not used again.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lintal_linter_free(linter: *mut LintalLinter) {
+    if !linter.is_null() {
+        // SAFETY: guaranteed by the caller
+        drop(unsafe { Box::from_raw(linter) });
+    }
+}
+
+/// # Safety
+///
+/// `linter` must be from [`lintal_linter_new`], `source` valid for reads of
+/// `len` bytes, and `out` writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lintal_lint(
+    linter: *const LintalLinter,
+    source: *const c_char,
+    len: usize,
+    out: *mut *mut LintalDiagnostics,
+) -> LintalStatus {
+    call(out, || {
+        // SAFETY: guaranteed by the caller
+        let linter = unsafe { linter.as_ref() }.ok_or_else(|| Failure::null("linter"))?;
+        // SAFETY: guaranteed by the caller
+        let source = unsafe { source_str(source, len) }?;
+        linter.diagnostics(source)
+    })
+}
+
+/// # Safety
+///
+/// `diagnostics` must be null or from [`lintal_lint`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lintal_diagnostics_len(diagnostics: *const LintalDiagnostics) -> usize {
+    // SAFETY: guaranteed by the caller
+    unsafe { diagnostics.as_ref() }.map_or(0, |diagnostics| diagnostics.items.len())
+}
+
+/// # Safety
+///
+/// `diagnostics` must be null or from [`lintal_lint`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lintal_diagnostics_get(
+    diagnostics: *const LintalDiagnostics,
+    index: usize,
+) -> *const LintalDiagnostic {
+    // SAFETY: guaranteed by the caller
+    unsafe { diagnostics.as_ref() }
+        .and_then(|diagnostics| diagnostics.items.get(index))
+        .map_or(std::ptr::null(), std::ptr::from_ref)
+}
+
+/// # Safety
+///
+/// `diagnostics` must be null or from [`lintal_lint`], and not used again.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lintal_diagnostics_free(diagnostics: *mut LintalDiagnostics) {
+    if !diagnostics.is_null() {
+        // SAFETY: guaranteed by the caller
+        drop(unsafe { Box::from_raw(diagnostics) });
+    }
+}
+
+/// # Safety
+///
+/// As for [`lintal_lint`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lintal_fix(
+    linter: *const LintalLinter,
+    source: *const c_char,
+    len: usize,
+    unsafe_fixes: bool,
+    out: *mut *mut LintalFixed,
+) -> LintalStatus {
+    call(out, || {
+        // SAFETY: guaranteed by the caller
+        let linter = unsafe { linter.as_ref() }.ok_or_else(|| Failure::null("linter"))?;
+        // SAFETY: guaranteed by the caller
+        let source = unsafe { source_str(source, len) }?;
+        let applicability = if unsafe_fixes {
+            Applicability::Unsafe
+        } else {
+            Applicability::Safe
+        };
+        let fixed =
+            lintal_linter::fix_source(source, &linter.rules, applicability).ok_or_else(|| {
+                Failure(
+                    LintalStatus::ParseError,
+                    "Failed to parse source".to_string(),
+                )
+            })?;
+        Ok(LintalFixed { fixed })
+    })
+}
+
+/// # Safety
+///
+/// `fixed` must be from [`lintal_fix`], and `len` null or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lintal_fixed_source(
+    fixed: *const LintalFixed,
+    len: *mut usize,
+) -> *const c_char {
+    // SAFETY: guaranteed by the caller
+    let code = unsafe { fixed.as_ref() }.map_or("", |fixed| fixed.fixed.code.as_str());
+    if !len.is_null() {
+        // SAFETY: guaranteed by the caller
+        unsafe { *len = code.len() };
+    }
+    code.as_ptr().cast()
+}
+
+/// # Safety
+///
+/// `fixed` must be null or from [`lintal_fix`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lintal_fixed_applied(fixed: *const LintalFixed) -> usize {
+    // SAFETY: guaranteed by the caller
+    unsafe { fixed.as_ref() }.map_or(0, |fixed| fixed.fixed.applied)
+}
+
+/// # Safety
+///
+/// `fixed` must be null or from [`lintal_fix`], and not used again.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lintal_fixed_free(fixed: *mut LintalFixed) {
+    if !fixed.is_null() {
+        // SAFETY: guaranteed by the caller
+        drop(unsafe { Box::from_raw(fixed) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_linter() -> *mut LintalLinter {
+        let mut linter = std::ptr::null_mut();
+        assert_eq!(
+            unsafe { lintal_linter_new(std::ptr::null(), &raw mut linter) },
+            LintalStatus::Ok
+        );
+        linter
+    }
+
+    fn c_str(ptr: *const c_char) -> &'static str {
+        unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()
+    }
+
+    #[test]
+    fn test_lint() {
+        let linter = default_linter();
+        let source = "class A { int x=1; }";
+        let mut diagnostics = std::ptr::null_mut();
+        let status = unsafe {
+            lintal_lint(
+                linter,
+                source.as_ptr().cast(),
+                source.len(),
+                &raw mut diagnostics,
+            )
+        };
+        assert_eq!(status, LintalStatus::Ok);
+
+        let len = unsafe { lintal_diagnostics_len(diagnostics) };
+        assert!(len > 0);
+        let first = unsafe { &*lintal_diagnostics_get(diagnostics, 0) };
+        assert_eq!(c_str(first.rule), "WhitespaceAround");
+        assert_eq!(&source[first.start..first.end], "=");
+        assert_eq!(first.line, 1);
+        assert!(unsafe { lintal_diagnostics_get(diagnostics, len) }.is_null());
+
+        unsafe {
+            lintal_diagnostics_free(diagnostics);
+            lintal_linter_free(linter);
+        }
+    }
+
+    #[test]
+    fn test_fix() {
+        let linter = default_linter();
+        let source = "class A { int x=1; }";
+        let mut fixed = std::ptr::null_mut();
+        let status = unsafe {
+            lintal_fix(
+                linter,
+                source.as_ptr().cast(),
+                source.len(),
+                false,
+                &raw mut fixed,
+            )
+        };
+        assert_eq!(status, LintalStatus::Ok);
+
+        let mut len = 0;
+        let code = unsafe { lintal_fixed_source(fixed, &raw mut len) };
+        let code = unsafe { std::slice::from_raw_parts(code.cast::<u8>(), len) };
+        assert_eq!(code, b"class A { int x = 1; }");
+        assert!(unsafe { lintal_fixed_applied(fixed) } > 0);
+
+        unsafe {
+            lintal_fixed_free(fixed);
+            lintal_linter_free(linter);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let linter = default_linter();
+        let source = [b'c', 0xff];
+        let mut diagnostics = std::ptr::null_mut();
+        let status = unsafe {
+            lintal_lint(
+                linter,
+                source.as_ptr().cast(),
+                source.len(),
+                &raw mut diagnostics,
+            )
+        };
+        assert_eq!(status, LintalStatus::InvalidArgument);
+        assert!(c_str(lintal_last_error()).starts_with("source:"));
+        assert!(diagnostics.is_null());
+
+        let path = c"/nonexistent/checkstyle.xml";
+        let mut missing = std::ptr::null_mut();
+        let status = unsafe { lintal_linter_new(path.as_ptr(), &raw mut missing) };
+        assert_eq!(status, LintalStatus::ConfigError);
+        assert!(c_str(lintal_last_error()).contains("/nonexistent/checkstyle.xml"));
+
+        unsafe { lintal_linter_free(linter) };
+    }
+}
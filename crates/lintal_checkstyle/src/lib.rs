@@ -4,14 +4,21 @@
 //! - checkstyle.xml (the source of truth for rules)
 //! - lintal.toml (optional overlay for fix behavior)
 //!
+//! and bundles [Preset] configurations to use in place of checkstyle.xml.
+//!
 //! The [MergedConfig] combines both sources, with checkstyle.xml defining
 //! *what* rules run and lintal.toml defining *how* violations are handled.
 
 mod lintal_config;
 mod merged_config;
+mod preset;
 
-pub use lintal_config::{FixConfig, LintalConfig, LintalConfigError, RuleMode};
-pub use merged_config::{ConfigError, ConfigLoader, ConfiguredRule, MergedConfig};
+pub use lintal_config::{
+    DocsConfig, FixConfig, FixOverride, ImportsConfig, LimitsConfig, LintalConfig,
+    LintalConfigError, MessagesConfig, RuleMode, SuppressionPolicyConfig,
+};
+pub use merged_config::{ConfigError, ConfigLoader, ConfiguredRule, MergedConfig, SeverityLevel};
+pub use preset::Preset;
 
 use quick_xml::de::from_str;
 use serde::Deserialize;
@@ -89,6 +96,14 @@ impl CheckstyleConfig {
         Ok(from_str(content)?)
     }
 
+    /// Get a Checker-level property value by name (e.g. `charset`).
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| p.value.as_str())
+    }
+
     /// Find the TreeWalker module.
     pub fn tree_walker(&self) -> Option<&Module> {
         self.modules.iter().find(|m| m.name == "TreeWalker")
@@ -162,4 +177,17 @@ mod tests {
         assert_eq!(file_modules[0].name, "FileTabCharacter");
         assert_eq!(file_modules[1].name, "LineLength");
     }
+
+    #[test]
+    fn test_parse_checker_properties() {
+        let xml = r#"<?xml version="1.0"?>
+<module name="Checker">
+    <property name="charset" value="windows-1252"/>
+    <module name="TreeWalker"/>
+</module>"#;
+
+        let config = CheckstyleConfig::parse(xml).unwrap();
+        assert_eq!(config.property("charset"), Some("windows-1252"));
+        assert_eq!(config.property("severity"), None);
+    }
 }
@@ -1,11 +1,16 @@
 //! Parser for lintal.toml configuration files.
 //!
 //! lintal.toml is an optional overlay configuration that controls fix behavior
-//! and points to the checkstyle.xml file. Example:
+//! and points to the checkstyle.xml file, or names a bundled preset to use
+//! instead. Example:
 //!
 //! ```toml
+//! preset = "google"
+//! plugins = ["rules/libacme_rules.so", "rules/vendor_rules.wasm"]
+//!
 //! [fix]
 //! unsafe = false
+//! organize_imports = true
 //!
 //! [fix.rules]
 //! WhitespaceAround = "fix"
@@ -13,15 +18,41 @@
 //! UnusedImports = "suggest"
 //! MethodLength = "disabled"
 //!
+//! [fixes]
+//! SimplifyBooleanReturn = "unsafe"
+//! RedundantModifier = "disabled"
+//!
 //! [checkstyle]
 //! config = "config/checkstyle/checkstyle.xml"
+//!
+//! [messages]
+//! locale = "de"
+//! directory = "config/checkstyle/messages"
+//!
+//! [limits]
+//! max_file_size = 5_000_000
+//! max_parse_time_ms = 10_000
+//!
+//! [imports]
+//! star_threshold = 5
+//!
+//! [suppressions]
+//! require_reason = true
+//! check_expiry = true
+//! report_unused = true
+//!
+//! [docs]
+//! enabled = true
+//! exclude = ["MagicNumber"]
 //! ```
 
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+use crate::Preset;
+
 #[derive(Error, Debug)]
 pub enum LintalConfigError {
     #[error("Failed to read config file: {0}")]
@@ -63,6 +94,37 @@ impl<'de> Deserialize<'de> for RuleMode {
     }
 }
 
+/// How safe `lintal fix` treats a rule's fixes, in place of what the rule
+/// itself says.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixOverride {
+    /// Apply the rule's fixes by default, unless the rule marks them display
+    /// only.
+    Safe,
+    /// Only apply the rule's fixes with `--unsafe`.
+    Unsafe,
+    /// Never apply the rule's fixes.
+    Disabled,
+}
+
+impl<'de> Deserialize<'de> for FixOverride {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        match s.to_lowercase().as_str() {
+            "safe" => Ok(FixOverride::Safe),
+            "unsafe" => Ok(FixOverride::Unsafe),
+            "disabled" | "disable" | "off" => Ok(FixOverride::Disabled),
+            _ => Err(serde::de::Error::custom(format!(
+                "Invalid fix applicability: {}. Expected safe, unsafe, or disabled",
+                s
+            ))),
+        }
+    }
+}
+
 /// Fix-related configuration.
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct FixConfig {
@@ -73,6 +135,11 @@ pub struct FixConfig {
     /// Per-rule fix mode overrides.
     #[serde(default)]
     pub rules: HashMap<String, RuleMode>,
+
+    /// Whether to organize the imports of each file being fixed, as
+    /// `lintal organize-imports` does.
+    #[serde(default)]
+    pub organize_imports: bool,
 }
 
 /// Checkstyle-related configuration.
@@ -82,6 +149,18 @@ pub struct CheckstyleReference {
     pub config: Option<String>,
 }
 
+/// Where violation messages come from.
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct MessagesConfig {
+    /// Locale of the messages, e.g. `de` or `pt_BR`. Overrides the Checker
+    /// `localeLanguage`/`localeCountry` properties and `LANG`.
+    pub locale: Option<String>,
+
+    /// Checkstyle's message bundles, e.g. the unpacked checkstyle jar. Without
+    /// them lintal reports its own English messages.
+    pub directory: Option<PathBuf>,
+}
+
 /// Root lintal.toml configuration.
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct LintalConfig {
@@ -92,6 +171,38 @@ pub struct LintalConfig {
     /// Reference to checkstyle.xml.
     #[serde(default)]
     pub checkstyle: CheckstyleReference,
+
+    /// Bundled configuration to use when no checkstyle.xml is given.
+    pub preset: Option<Preset>,
+
+    /// Plugins providing additional rules: native libraries, or sandboxed
+    /// `.wasm` modules.
+    #[serde(default)]
+    pub plugins: Vec<PathBuf>,
+
+    /// Localized messages.
+    #[serde(default)]
+    pub messages: MessagesConfig,
+
+    /// Per-rule overrides of how safe the rule's fixes are.
+    #[serde(default)]
+    pub fixes: HashMap<String, FixOverride>,
+
+    /// Limits beyond which files are skipped.
+    #[serde(default)]
+    pub limits: LimitsConfig,
+
+    /// How imports are organized.
+    #[serde(default)]
+    pub imports: ImportsConfig,
+
+    /// What is required of suppression comments and annotations.
+    #[serde(default)]
+    pub suppressions: SuppressionPolicyConfig,
+
+    /// How the Java blocks of documentation are linted.
+    #[serde(default)]
+    pub docs: DocsConfig,
 }
 
 impl LintalConfig {
@@ -127,6 +238,86 @@ impl LintalConfig {
     }
 }
 
+/// Limits beyond which a file is skipped with a warning rather than linted,
+/// e.g. for huge generated sources. No limits are set by default.
+#[derive(Debug, Clone, Copy, Deserialize, Default, PartialEq, Eq)]
+pub struct LimitsConfig {
+    /// Largest file to lint, in bytes.
+    pub max_file_size: Option<u64>,
+
+    /// Longest a file may take to parse, in milliseconds.
+    pub max_parse_time_ms: Option<u64>,
+}
+
+/// How imports are organized, beyond what the checkstyle import modules
+/// configure.
+#[derive(Debug, Clone, Copy, Deserialize, Default, PartialEq, Eq)]
+pub struct ImportsConfig {
+    /// Replace this many or more single-type imports of a package with a
+    /// star import, unless AvoidStarImport is enabled.
+    pub star_threshold: Option<usize>,
+}
+
+/// What is required of suppression comments and annotations. Nothing is
+/// by default.
+#[derive(Debug, Clone, Copy, Deserialize, Default, PartialEq, Eq)]
+pub struct SuppressionPolicyConfig {
+    /// Report suppressions without a reason written after them.
+    #[serde(default)]
+    pub require_reason: bool,
+
+    /// Report suppressions whose `until=YYYY-MM-DD` date has passed.
+    #[serde(default)]
+    pub check_expiry: bool,
+
+    /// Report suppressions that suppress nothing.
+    #[serde(default)]
+    pub report_unused: bool,
+}
+
+/// How the Java code blocks of Markdown and AsciiDoc files are linted.
+/// Documents named on the command line are always linted.
+#[derive(Debug, Clone, Deserialize, Default, PartialEq, Eq)]
+pub struct DocsConfig {
+    /// Also lint the documents found in directories.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// The only rules to run on code blocks. By default every configured
+    /// rule runs but those in [`DocsConfig::DEFAULT_EXCLUDED`].
+    pub rules: Option<Vec<String>>,
+
+    /// Rules not to run on code blocks, besides the default ones.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl DocsConfig {
+    /// Rules that don't run on code blocks unless named in `rules`: a
+    /// sample is a fragment of a file, not a whole one.
+    pub const DEFAULT_EXCLUDED: &[&str] = &[
+        "PackageDeclaration",
+        "UnusedImports",
+        "HideUtilityClassConstructor",
+        "FinalClass",
+        "FinalParameters",
+        "FinalLocalVariable",
+        "StrictDuplicateCode",
+        "JavadocPackage",
+        "NewlineAtEndOfFile",
+    ];
+
+    /// Whether `rule` runs on code blocks.
+    pub fn runs(&self, rule: &str) -> bool {
+        match &self.rules {
+            Some(rules) => rules.iter().any(|r| r == rule),
+            None => {
+                !Self::DEFAULT_EXCLUDED.contains(&rule) && !self.exclude.iter().any(|r| r == rule)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,6 +328,139 @@ mod tests {
         assert!(!config.fix.unsafe_fixes);
         assert!(config.fix.rules.is_empty());
         assert!(config.checkstyle.config.is_none());
+        assert!(config.preset.is_none());
+        assert!(config.plugins.is_empty());
+        assert!(config.messages.locale.is_none());
+        assert!(config.messages.directory.is_none());
+        assert!(config.fixes.is_empty());
+        assert_eq!(config.limits, LimitsConfig::default());
+        assert!(!config.fix.organize_imports);
+        assert_eq!(config.imports, ImportsConfig::default());
+        assert_eq!(config.suppressions, SuppressionPolicyConfig::default());
+        assert_eq!(config.docs, DocsConfig::default());
+    }
+
+    #[test]
+    fn test_parse_docs() {
+        let config =
+            LintalConfig::parse("[docs]\nenabled = true\nexclude = [\"MagicNumber\"]\n").unwrap();
+        assert!(config.docs.enabled);
+        assert!(config.docs.runs("WhitespaceAround"));
+        assert!(!config.docs.runs("MagicNumber"));
+        assert!(!config.docs.runs("UnusedImports"));
+
+        let config = LintalConfig::parse("[docs]\nrules = [\"UnusedImports\"]\n").unwrap();
+        assert!(!config.docs.enabled);
+        assert!(config.docs.runs("UnusedImports"));
+        assert!(!config.docs.runs("WhitespaceAround"));
+    }
+
+    #[test]
+    fn test_parse_suppressions() {
+        let toml = r#"
+[suppressions]
+require_reason = true
+report_unused = true
+"#;
+
+        let config = LintalConfig::parse(toml).unwrap();
+        assert!(config.suppressions.require_reason);
+        assert!(!config.suppressions.check_expiry);
+        assert!(config.suppressions.report_unused);
+    }
+
+    #[test]
+    fn test_parse_imports() {
+        let toml = r#"
+[fix]
+organize_imports = true
+
+[imports]
+star_threshold = 5
+"#;
+
+        let config = LintalConfig::parse(toml).unwrap();
+        assert!(config.fix.organize_imports);
+        assert_eq!(config.imports.star_threshold, Some(5));
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        let toml = r#"
+[limits]
+max_file_size = 5_000_000
+max_parse_time_ms = 2000
+"#;
+
+        let config = LintalConfig::parse(toml).unwrap();
+        assert_eq!(config.limits.max_file_size, Some(5_000_000));
+        assert_eq!(config.limits.max_parse_time_ms, Some(2000));
+    }
+
+    #[test]
+    fn test_parse_fixes() {
+        let toml = r#"
+[fixes]
+SimplifyBooleanReturn = "unsafe"
+UpperEll = "Safe"
+RedundantModifier = "off"
+"#;
+
+        let config = LintalConfig::parse(toml).unwrap();
+        assert_eq!(
+            config.fixes.get("SimplifyBooleanReturn"),
+            Some(&FixOverride::Unsafe)
+        );
+        assert_eq!(config.fixes.get("UpperEll"), Some(&FixOverride::Safe));
+        assert_eq!(
+            config.fixes.get("RedundantModifier"),
+            Some(&FixOverride::Disabled)
+        );
+
+        assert!(LintalConfig::parse("[fixes]\nUpperEll = \"sometimes\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_messages() {
+        let toml = r#"
+[messages]
+locale = "de_CH"
+directory = "config/checkstyle/messages"
+"#;
+
+        let config = LintalConfig::parse(toml).unwrap();
+        assert_eq!(config.messages.locale.as_deref(), Some("de_CH"));
+        assert_eq!(
+            config.messages.directory,
+            Some(PathBuf::from("config/checkstyle/messages"))
+        );
+    }
+
+    #[test]
+    fn test_parse_preset() {
+        let config = LintalConfig::parse("preset = \"Google\"\n").unwrap();
+        assert_eq!(config.preset, Some(Preset::Google));
+
+        assert!(LintalConfig::parse("preset = \"android\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_plugins() {
+        let toml = r#"
+plugins = ["rules/libacme_rules.so", "/opt/lintal/libextra.so"]
+
+[fix]
+unsafe_fixes = true
+"#;
+
+        let config = LintalConfig::parse(toml).unwrap();
+        assert_eq!(
+            config.plugins,
+            [
+                PathBuf::from("rules/libacme_rules.so"),
+                PathBuf::from("/opt/lintal/libextra.so")
+            ]
+        );
     }
 
     #[test]
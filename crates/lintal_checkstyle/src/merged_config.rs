@@ -4,9 +4,12 @@
 //! lintal.toml defines *how* violations are handled.
 
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use crate::{CheckstyleConfig, CheckstyleError, LintalConfig, LintalConfigError, RuleMode};
+use crate::{
+    CheckstyleConfig, CheckstyleError, DocsConfig, FixOverride, ImportsConfig, LimitsConfig,
+    LintalConfig, LintalConfigError, Preset, RuleMode, SuppressionPolicyConfig,
+};
 
 /// Error during config loading.
 #[derive(Debug)]
@@ -43,6 +46,31 @@ impl From<LintalConfigError> for ConfigError {
     }
 }
 
+/// The `severity` of a checkstyle module, or of the Checker for modules
+/// without one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SeverityLevel {
+    /// Violations aren't reported; the rule doesn't run.
+    Ignore,
+    Info,
+    Warning,
+    #[default]
+    Error,
+}
+
+impl SeverityLevel {
+    /// Parse a `severity` value, in any case as checkstyle does.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "ignore" => Some(Self::Ignore),
+            "info" => Some(Self::Info),
+            "warning" => Some(Self::Warning),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
 /// A configured rule with its properties and mode.
 #[derive(Debug, Clone)]
 pub struct ConfiguredRule {
@@ -52,6 +80,8 @@ pub struct ConfiguredRule {
     pub properties: HashMap<String, String>,
     /// How to handle violations (from lintal.toml).
     pub mode: RuleMode,
+    /// How serious violations are (from checkstyle.xml).
+    pub severity: SeverityLevel,
 }
 
 impl ConfiguredRule {
@@ -68,9 +98,10 @@ impl ConfiguredRule {
             .collect()
     }
 
-    /// Check if this rule is enabled.
+    /// Check if this rule is enabled: not disabled in lintal.toml, nor
+    /// with `severity` `ignore`.
     pub fn is_enabled(&self) -> bool {
-        self.mode != RuleMode::Disabled
+        self.mode != RuleMode::Disabled && self.severity != SeverityLevel::Ignore
     }
 
     /// Check if this rule should auto-fix.
@@ -86,10 +117,36 @@ pub struct MergedConfig {
     pub rules: Vec<ConfiguredRule>,
     /// Whether to apply unsafe fixes.
     pub unsafe_fixes: bool,
+    /// Charset of source files (the Checker `charset` property), if set.
+    pub charset: Option<String>,
+    /// Width of a tab when computing columns (the Checker `tabWidth` property).
+    pub tab_width: usize,
+    /// Plugin libraries to load rules from (from lintal.toml).
+    pub plugins: Vec<PathBuf>,
+    /// Locale of the messages: from lintal.toml, or the Checker
+    /// `localeLanguage` and `localeCountry` properties.
+    pub locale: Option<String>,
+    /// Checkstyle's message bundles (from lintal.toml).
+    pub messages_dir: Option<PathBuf>,
+    /// How safe `lintal fix` treats each rule's fixes (from lintal.toml).
+    pub fix_overrides: HashMap<String, FixOverride>,
+    /// Limits beyond which files are skipped (from lintal.toml).
+    pub limits: LimitsConfig,
+    /// Whether `lintal fix` organizes imports (from lintal.toml).
+    pub organize_imports: bool,
+    /// How imports are organized (from lintal.toml).
+    pub imports: ImportsConfig,
+    /// What is required of suppressions (from lintal.toml).
+    pub suppression_policy: SuppressionPolicyConfig,
+    /// How the Java blocks of documentation are linted (from lintal.toml).
+    pub docs: DocsConfig,
 }
 
 impl MergedConfig {
-    /// Checker-level modules that are not rules (filters, etc.)
+    /// Checkstyle's default `tabWidth`.
+    pub const DEFAULT_TAB_WIDTH: usize = 8;
+
+    /// Modules that are not rules (filters, etc.)
     const NON_RULE_MODULES: &[&str] = &[
         "SuppressionFilter",
         "SuppressWarningsFilter",
@@ -97,13 +154,26 @@ impl MergedConfig {
         "SuppressWithPlainTextCommentFilter",
         "SuppressWarningsHolder",
         "BeforeExecutionExclusionFileFilter",
+        "SuppressionCommentFilter",
+        "SuppressWithNearbyCommentFilter",
     ];
 
     /// Create a merged config from checkstyle.xml and optional lintal.toml.
     pub fn new(checkstyle: &CheckstyleConfig, lintal: Option<&LintalConfig>) -> Self {
         let lintal = lintal.cloned().unwrap_or_default();
 
-        let make_rule = |module: &&crate::Module| ConfiguredRule {
+        // Modules without a severity of their own take their parent's: the
+        // TreeWalker passes its own down to its checks, else the Checker's
+        let checker_severity = checkstyle
+            .property("severity")
+            .and_then(SeverityLevel::parse)
+            .unwrap_or_default();
+        let tree_walker_severity = checkstyle
+            .tree_walker()
+            .and_then(|tree_walker| tree_walker.property("severity"))
+            .and_then(SeverityLevel::parse)
+            .unwrap_or(checker_severity);
+        let make_rule = |module: &crate::Module, default_severity: SeverityLevel| ConfiguredRule {
             name: module.name.clone(),
             properties: module
                 .properties_map()
@@ -111,22 +181,53 @@ impl MergedConfig {
                 .map(|(k, v)| (k.to_string(), v.to_string()))
                 .collect(),
             mode: lintal.rule_mode(&module.name),
+            severity: module
+                .property("severity")
+                .and_then(SeverityLevel::parse)
+                .unwrap_or(default_severity),
         };
 
-        let mut rules: Vec<ConfiguredRule> = checkstyle.rules().iter().map(make_rule).collect();
+        let mut rules: Vec<ConfiguredRule> = checkstyle
+            .rules()
+            .iter()
+            .filter(|m| !Self::NON_RULE_MODULES.contains(&m.name.as_str()))
+            .map(|m| make_rule(m, tree_walker_severity))
+            .collect();
 
         // Also include Checker-level (file) modules that are actual rules
         let file_rules = checkstyle
             .file_modules()
             .iter()
             .filter(|m| !Self::NON_RULE_MODULES.contains(&m.name.as_str()))
-            .map(make_rule)
+            .map(|m| make_rule(m, checker_severity))
             .collect::<Vec<_>>();
         rules.extend(file_rules);
 
+        let checker_locale = checkstyle
+            .property("localeLanguage")
+            .filter(|language| !language.is_empty())
+            .map(|language| match checkstyle.property("localeCountry") {
+                Some(country) if !country.is_empty() => format!("{language}_{country}"),
+                _ => language.to_string(),
+            });
+
         Self {
             rules,
             unsafe_fixes: lintal.fix.unsafe_fixes,
+            charset: checkstyle.property("charset").map(str::to_string),
+            tab_width: checkstyle
+                .property("tabWidth")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(Self::DEFAULT_TAB_WIDTH),
+            plugins: lintal.plugins,
+            locale: lintal.messages.locale.or(checker_locale),
+            messages_dir: lintal.messages.directory,
+            fix_overrides: lintal.fixes,
+            limits: lintal.limits,
+            organize_imports: lintal.fix.organize_imports,
+            imports: lintal.imports,
+            suppression_policy: lintal.suppressions,
+            docs: lintal.docs,
         }
     }
 
@@ -150,6 +251,7 @@ impl MergedConfig {
 pub struct ConfigLoader {
     checkstyle_path: Option<std::path::PathBuf>,
     lintal_path: Option<std::path::PathBuf>,
+    preset: Option<Preset>,
 }
 
 impl ConfigLoader {
@@ -158,6 +260,7 @@ impl ConfigLoader {
         Self {
             checkstyle_path: None,
             lintal_path: None,
+            preset: None,
         }
     }
 
@@ -173,6 +276,13 @@ impl ConfigLoader {
         self
     }
 
+    /// Use a bundled preset when no checkstyle.xml path is set, in place of
+    /// the one lintal.toml names.
+    pub fn preset(mut self, preset: Preset) -> Self {
+        self.preset = Some(preset);
+        self
+    }
+
     /// Try to find lintal.toml in common locations.
     pub fn find_lintal(mut self) -> Self {
         let candidates = ["lintal.toml", ".lintal.toml", "config/lintal.toml"];
@@ -227,10 +337,15 @@ impl ConfigLoader {
                 .and_then(|l| l.checkstyle.config.as_ref().map(std::path::PathBuf::from))
         });
 
+        // Without one, fall back to a preset
+        let preset = self
+            .preset
+            .or_else(|| lintal.as_ref().and_then(|l| l.preset));
+
         // Load checkstyle.xml
-        let checkstyle = match checkstyle_path {
-            Some(path) if path.exists() => CheckstyleConfig::from_file(&path)?,
-            Some(path) => {
+        let checkstyle = match (checkstyle_path, preset) {
+            (Some(path), _) if path.exists() => CheckstyleConfig::from_file(&path)?,
+            (Some(path), _) => {
                 return Err(ConfigError::Checkstyle(CheckstyleError::Io(
                     std::io::Error::new(
                         std::io::ErrorKind::NotFound,
@@ -238,7 +353,8 @@ impl ConfigLoader {
                     ),
                 )));
             }
-            None => return Err(ConfigError::NoConfig),
+            (None, Some(preset)) => preset.config()?,
+            (None, None) => return Err(ConfigError::NoConfig),
         };
 
         Ok(MergedConfig::new(&checkstyle, lintal.as_ref()))
@@ -278,6 +394,10 @@ mod tests {
 
         assert_eq!(merged.rules.len(), 3);
         assert!(!merged.unsafe_fixes);
+        assert_eq!(merged.charset, None);
+        assert_eq!(merged.tab_width, MergedConfig::DEFAULT_TAB_WIDTH);
+        assert!(merged.plugins.is_empty());
+        assert_eq!(merged.locale, None);
 
         // All rules default to Fix mode
         for rule in &merged.rules {
@@ -290,6 +410,86 @@ mod tests {
         assert_eq!(ws.property("allowEmptyMethods"), Some("true"));
     }
 
+    #[test]
+    fn test_merged_config_skips_tree_walker_filters() {
+        let checkstyle = CheckstyleConfig::parse(
+            r#"<?xml version="1.0"?>
+<module name="Checker">
+    <module name="TreeWalker">
+        <module name="SuppressionCommentFilter"/>
+        <module name="SuppressWithNearbyCommentFilter"/>
+        <module name="NeedBraces"/>
+    </module>
+</module>"#,
+        )
+        .unwrap();
+        let merged = MergedConfig::new(&checkstyle, None);
+
+        let names: Vec<_> = merged.rules.iter().map(|rule| rule.name.as_str()).collect();
+        assert_eq!(names, ["NeedBraces"]);
+    }
+
+    #[test]
+    fn test_merged_config_severity() {
+        let checkstyle = CheckstyleConfig::parse(
+            r#"<?xml version="1.0"?>
+<module name="Checker">
+    <property name="severity" value="warning"/>
+    <module name="TreeWalker">
+        <module name="WhitespaceAround"/>
+        <module name="LeftCurly">
+            <property name="severity" value="ERROR"/>
+        </module>
+        <module name="NeedBraces">
+            <property name="severity" value="ignore"/>
+        </module>
+    </module>
+</module>"#,
+        )
+        .unwrap();
+        let merged = MergedConfig::new(&checkstyle, None);
+
+        let ws = merged.get_rule("WhitespaceAround").unwrap();
+        assert_eq!(ws.severity, SeverityLevel::Warning);
+        let lc = merged.get_rule("LeftCurly").unwrap();
+        assert_eq!(lc.severity, SeverityLevel::Error);
+        // Ignored rules don't run
+        let nb = merged.get_rule("NeedBraces").unwrap();
+        assert_eq!(nb.severity, SeverityLevel::Ignore);
+        assert!(!nb.is_enabled());
+        assert_eq!(merged.enabled_rules().count(), 2);
+
+        // The TreeWalker's severity comes before the Checker's for its checks
+        let checkstyle = CheckstyleConfig::parse(
+            r#"<?xml version="1.0"?>
+<module name="Checker">
+    <property name="severity" value="error"/>
+    <module name="NewlineAtEndOfFile"/>
+    <module name="TreeWalker">
+        <property name="severity" value="warning"/>
+        <module name="WhitespaceAround"/>
+        <module name="LeftCurly">
+            <property name="severity" value="info"/>
+        </module>
+    </module>
+</module>"#,
+        )
+        .unwrap();
+        let merged = MergedConfig::new(&checkstyle, None);
+        let severity = |name| merged.get_rule(name).unwrap().severity;
+        assert_eq!(severity("WhitespaceAround"), SeverityLevel::Warning);
+        assert_eq!(severity("LeftCurly"), SeverityLevel::Info);
+        assert_eq!(severity("NewlineAtEndOfFile"), SeverityLevel::Error);
+
+        let plain = MergedConfig::new(&sample_checkstyle(), None);
+        assert!(
+            plain
+                .rules
+                .iter()
+                .all(|rule| rule.severity == SeverityLevel::Error)
+        );
+    }
+
     #[test]
     fn test_merged_config_with_lintal() {
         let checkstyle = sample_checkstyle();
@@ -329,4 +529,34 @@ NeedBraces = "disabled"
         let enabled: Vec<_> = merged.enabled_rules().collect();
         assert_eq!(enabled.len(), 2);
     }
+
+    #[test]
+    fn test_merged_config_locale() {
+        let checkstyle = CheckstyleConfig::parse(
+            r#"<?xml version="1.0"?>
+<module name="Checker">
+    <property name="localeLanguage" value="de"/>
+    <property name="localeCountry" value="CH"/>
+    <module name="TreeWalker"/>
+</module>"#,
+        )
+        .unwrap();
+        let merged = MergedConfig::new(&checkstyle, None);
+        assert_eq!(merged.locale.as_deref(), Some("de_CH"));
+
+        let lintal = LintalConfig::parse("[messages]\nlocale = \"fr\"").unwrap();
+        let merged = MergedConfig::new(&checkstyle, Some(&lintal));
+        assert_eq!(merged.locale.as_deref(), Some("fr"));
+    }
+
+    #[test]
+    fn test_loader_preset() {
+        let merged = ConfigLoader::new().preset(Preset::Sun).load().unwrap();
+        assert!(merged.is_rule_enabled("MagicNumber"));
+
+        assert!(matches!(
+            ConfigLoader::new().load(),
+            Err(ConfigError::NoConfig)
+        ));
+    }
 }
@@ -0,0 +1,143 @@
+//! Bundled checkstyle configurations.
+//!
+//! A preset stands in for a checkstyle.xml, so a project following a
+//! well-known style needs no XML file of its own:
+//!
+//! ```toml
+//! preset = "google"
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::{CheckstyleConfig, CheckstyleError};
+
+/// A bundled configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Preset {
+    /// The Google Java Style Guide, as checkstyle's `google_checks.xml`.
+    Google,
+    /// The Sun Code Conventions, as checkstyle's `sun_checks.xml`.
+    Sun,
+}
+
+impl Preset {
+    /// All presets, in the order they are listed in messages.
+    pub const ALL: &[Preset] = &[Preset::Google, Preset::Sun];
+
+    /// The name the preset is selected by.
+    pub fn name(self) -> &'static str {
+        match self {
+            Preset::Google => "google",
+            Preset::Sun => "sun",
+        }
+    }
+
+    /// The checkstyle XML of the preset.
+    pub fn xml(self) -> &'static str {
+        match self {
+            Preset::Google => include_str!("../presets/google_checks.xml"),
+            Preset::Sun => include_str!("../presets/sun_checks.xml"),
+        }
+    }
+
+    /// Parse the checkstyle configuration of the preset.
+    pub fn config(self) -> Result<CheckstyleConfig, CheckstyleError> {
+        CheckstyleConfig::parse(self.xml())
+    }
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Preset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|preset| preset.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(|preset| preset.name()).collect();
+                format!("Invalid preset: {s}. Expected {}", names.join(" or "))
+            })
+    }
+}
+
+impl<'de> Deserialize<'de> for Preset {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MergedConfig;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("google".parse(), Ok(Preset::Google));
+        assert_eq!("Sun".parse(), Ok(Preset::Sun));
+        assert_eq!(
+            "android".parse::<Preset>(),
+            Err("Invalid preset: android. Expected google or sun".to_string())
+        );
+    }
+
+    #[test]
+    fn test_google() {
+        let config = MergedConfig::new(&Preset::Google.config().unwrap(), None);
+        assert_eq!(config.charset.as_deref(), Some("UTF-8"));
+
+        let indentation = config.get_rule("Indentation").unwrap();
+        assert_eq!(indentation.property("basicOffset"), Some("2"));
+        let line_length = config.get_rule("LineLength").unwrap();
+        assert_eq!(line_length.property("max"), Some("100"));
+        assert!(
+            line_length
+                .property("ignorePattern")
+                .unwrap()
+                .contains(r#"href\s*=\s*"[^"]*""#)
+        );
+        assert!(config.get_rule("SuppressWarningsHolder").is_none());
+        assert!(config.is_rule_enabled("OuterTypeFilename"));
+        assert!(config.is_rule_enabled("OneTopLevelClass"));
+
+        let separator_wraps = config
+            .enabled_rules()
+            .filter(|rule| rule.name == "SeparatorWrap")
+            .count();
+        assert_eq!(separator_wraps, 5);
+    }
+
+    #[test]
+    fn test_sun() {
+        let config = MergedConfig::new(&Preset::Sun.config().unwrap(), None);
+        assert!(config.is_rule_enabled("FinalParameters"));
+        assert!(config.is_rule_enabled("MagicNumber"));
+        assert!(config.is_rule_enabled("GenericWhitespace"));
+        assert!(config.is_rule_enabled("NewlineAtEndOfFile"));
+        assert!(config.is_rule_enabled("FileLength"));
+        assert!(config.is_rule_enabled("ParameterNumber"));
+        assert!(config.is_rule_enabled("VisibilityModifier"));
+        assert!(config.is_rule_enabled("DesignForExtension"));
+        let regexp = config.get_rule("RegexpSingleline").unwrap();
+        assert_eq!(regexp.property("format"), Some(r"\s+$"));
+        assert_eq!(
+            regexp.property("message"),
+            Some("Line has trailing spaces.")
+        );
+        assert_eq!(config.get_rule("LineLength").unwrap().property("max"), None);
+    }
+}
@@ -16,6 +16,52 @@ pub enum FixAvailability {
     None,
 }
 
+/// How serious a violation is, as checkstyle's `severity` property sets it.
+/// Ordered from least to most serious.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    /// Checkstyle's default.
+    #[default]
+    Error,
+}
+
+impl Severity {
+    /// The name checkstyle writes the severity as, e.g. `"warning"`.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A checkstyle message key, under which checkstyle's message bundles hold
+/// the translations of a violation's message.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct MessageKey {
+    /// Package of the check below `com.puppycrawl.tools.checkstyle.checks`
+    /// whose `messages.properties` holds the key, e.g. `"whitespace"`; empty
+    /// for checks in `checks` itself.
+    pub bundle: &'static str,
+    /// The key, e.g. `"ws.notPreceded"`.
+    pub key: &'static str,
+}
+
+impl MessageKey {
+    pub const fn new(bundle: &'static str, key: &'static str) -> Self {
+        Self { bundle, key }
+    }
+}
+
 /// A trait for violations that can be reported as diagnostics.
 pub trait Violation: std::fmt::Debug + Clone + Send + Sync {
     /// The availability of a fix for this violation.
@@ -28,15 +74,32 @@ pub trait Violation: std::fmt::Debug + Clone + Send + Sync {
     fn fix_title(&self) -> Option<String> {
         None
     }
+
+    /// Returns the checkstyle message key of the violation, if checkstyle
+    /// reports it too.
+    fn message_key(&self) -> Option<MessageKey> {
+        None
+    }
+
+    /// Returns the arguments of the message under [`Violation::message_key`],
+    /// in the order checkstyle passes them.
+    fn message_args(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 /// The kind of diagnostic (rule code and message).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DiagnosticKind {
-    /// The rule code (e.g., "WS001").
+    /// The rule code (e.g., "WS001"), or the name of the violation type for
+    /// rules without one.
     pub code: String,
     /// The message body.
     pub body: String,
+    /// The checkstyle message key, for localizing the message.
+    pub message_key: Option<MessageKey>,
+    /// Arguments of the message under `message_key`.
+    pub message_args: Vec<String>,
 }
 
 /// A diagnostic representing a violation found in source code.
@@ -48,6 +111,8 @@ pub struct Diagnostic {
     pub range: TextRange,
     /// The optional fix for the violation.
     pub fix: Option<Fix>,
+    /// The severity of the rule that reported it.
+    pub severity: Severity,
 }
 
 impl Diagnostic {
@@ -62,9 +127,12 @@ impl Diagnostic {
                     .unwrap_or("Unknown")
                     .to_string(),
                 body: violation.message(),
+                message_key: violation.message_key(),
+                message_args: violation.message_args(),
             },
             range,
             fix: None,
+            severity: Severity::default(),
         }
     }
 
@@ -75,6 +143,13 @@ impl Diagnostic {
         self
     }
 
+    /// Set the severity of this diagnostic.
+    #[must_use]
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
     /// Set the fix for this diagnostic.
     pub fn set_fix(&mut self, fix: Fix) {
         self.fix = Some(fix);
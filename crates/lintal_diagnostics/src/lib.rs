@@ -3,12 +3,18 @@
 //! This crate is derived from [ruff_diagnostics](https://github.com/astral-sh/ruff)
 //! by Astral Software Inc., licensed under MIT.
 
-pub use diagnostic::{Diagnostic, DiagnosticKind, FixAvailability, Violation};
+pub use diagnostic::{
+    Diagnostic, DiagnosticKind, FixAvailability, MessageKey, Severity, Violation,
+};
 pub use edit::Edit;
 pub use fix::{Applicability, Fix, IsolationLevel};
+pub use messages::{Locale, MessageBundles, format_message};
+pub use render::{RenderOptions, render_code_frame, render_diagnostic};
 pub use source_map::{SourceMap, SourceMarker};
 
 mod diagnostic;
 mod edit;
 mod fix;
+mod messages;
+mod render;
 mod source_map;
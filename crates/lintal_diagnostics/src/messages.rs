@@ -0,0 +1,401 @@
+//! Localized violation messages from checkstyle's message bundles.
+//!
+//! Checkstyle keeps the messages of each check package in
+//! `messages.properties` (English) and `messages_<locale>.properties`
+//! translations next to it, e.g.
+//! `com/puppycrawl/tools/checkstyle/checks/whitespace/messages_de.properties`.
+//! [`MessageBundles`] loads those files for a [`Locale`] and formats the
+//! messages of diagnostics that carry a [`MessageKey`], falling back from
+//! `de_CH` to `de` to English the way Java's `ResourceBundle` does.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::{DiagnosticKind, MessageKey};
+
+/// Where the check packages live inside the checkstyle jar and source tree.
+const CHECKS_PACKAGE_PATH: &str = "com/puppycrawl/tools/checkstyle/checks";
+
+/// A language with an optional country, as in `de` or `de_CH`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Locale {
+    language: String,
+    country: Option<String>,
+}
+
+impl Locale {
+    pub fn new(language: &str, country: Option<&str>) -> Self {
+        Self {
+            language: language.to_ascii_lowercase(),
+            country: country
+                .filter(|c| !c.is_empty())
+                .map(str::to_ascii_uppercase),
+        }
+    }
+
+    /// Parse a locale such as `de`, `de_CH`, `de-CH` or a POSIX locale like
+    /// `de_CH.UTF-8@euro`. `None` for the `C` and `POSIX` locales.
+    pub fn parse(locale: &str) -> Option<Self> {
+        let locale = locale.split(['.', '@']).next().unwrap_or_default();
+        if locale.is_empty() || locale == "C" || locale == "POSIX" {
+            return None;
+        }
+        let mut parts = locale.split(['_', '-']);
+        let language = parts.next()?;
+        if !language.chars().all(|c| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        Some(Self::new(language, parts.next()))
+    }
+
+    /// The locale for messages from the environment: the first of `LC_ALL`,
+    /// `LC_MESSAGES` and `LANG` that is set.
+    pub fn from_env() -> Option<Self> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::parse(&value))
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn country(&self) -> Option<&str> {
+        self.country.as_deref()
+    }
+
+    /// File name suffixes of the bundles for this locale, most specific
+    /// first, ending with the English base bundle.
+    fn bundle_suffixes(&self) -> Vec<String> {
+        let mut suffixes = Vec::new();
+        if let Some(country) = &self.country {
+            suffixes.push(format!("_{}_{country}", self.language));
+        }
+        suffixes.push(format!("_{}", self.language));
+        suffixes.push(String::new());
+        suffixes
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.country {
+            Some(country) => write!(f, "{}_{country}", self.language),
+            None => f.write_str(&self.language),
+        }
+    }
+}
+
+/// Checkstyle's messages for one locale.
+#[derive(Debug, Clone, Default)]
+pub struct MessageBundles {
+    /// Message patterns by bundle and key.
+    bundles: HashMap<String, HashMap<String, String>>,
+}
+
+impl MessageBundles {
+    /// Load the bundles for `locale` (English if `None`) from `dir`.
+    ///
+    /// `dir` is the `checks` package directory of checkstyle's resources, or
+    /// a directory containing `com/puppycrawl/tools/checkstyle/checks`, such
+    /// as the unpacked checkstyle jar.
+    pub fn load(dir: &Path, locale: Option<&Locale>) -> std::io::Result<Self> {
+        let nested = dir.join(CHECKS_PACKAGE_PATH);
+        let checks_dir = if nested.is_dir() { nested } else { dir.into() };
+
+        let mut packages: Vec<(String, PathBuf)> = vec![(String::new(), checks_dir.clone())];
+        for entry in std::fs::read_dir(&checks_dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                packages.push((
+                    entry.file_name().to_string_lossy().into_owned(),
+                    entry.path(),
+                ));
+            }
+        }
+
+        let suffixes = locale.map_or_else(|| vec![String::new()], Locale::bundle_suffixes);
+        let mut bundles = HashMap::new();
+        for (package, package_dir) in packages {
+            let mut messages: HashMap<String, String> = HashMap::new();
+            // Less specific bundles first, so that translations win
+            for suffix in suffixes.iter().rev() {
+                let path = package_dir.join(format!("messages{suffix}.properties"));
+                match std::fs::read(&path) {
+                    Ok(bytes) => messages.extend(parse_properties(&properties_text(&bytes))),
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            if !messages.is_empty() {
+                bundles.insert(package, messages);
+            }
+        }
+        Ok(Self { bundles })
+    }
+
+    /// Add the messages of a `.properties` file to `bundle`, replacing any
+    /// with the same keys.
+    pub fn insert_properties(&mut self, bundle: &str, properties: &str) {
+        self.bundles
+            .entry(bundle.to_string())
+            .or_default()
+            .extend(parse_properties(properties));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bundles.is_empty()
+    }
+
+    /// The message under `key`, formatted with `args`.
+    pub fn message(&self, key: MessageKey, args: &[String]) -> Option<String> {
+        let pattern = self.bundles.get(key.bundle)?.get(key.key)?;
+        Some(format_message(pattern, args))
+    }
+
+    /// The localized message of a diagnostic, if it has a message key the
+    /// bundles hold.
+    pub fn localize(&self, kind: &DiagnosticKind) -> Option<String> {
+        self.message(kind.message_key?, &kind.message_args)
+    }
+}
+
+/// Decode a `.properties` file: UTF-8, falling back to ISO-8859-1 for files
+/// that predate Java 9.
+fn properties_text(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|&b| char::from(b)).collect(),
+    }
+}
+
+/// Parse the entries of a Java `.properties` file.
+fn parse_properties(text: &str) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+    let mut lines = text.lines();
+
+    while let Some(line) = lines.next() {
+        let line = line.trim_start();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+            continue;
+        }
+
+        // Join lines ending in an odd number of backslashes
+        let mut logical = line.to_string();
+        while ends_with_continuation(&logical) {
+            logical.pop();
+            match lines.next() {
+                Some(next) => logical.push_str(next.trim_start()),
+                None => break,
+            }
+        }
+
+        let (key, value) = split_entry(&logical);
+        entries.push((unescape(key), unescape(value)));
+    }
+    entries
+}
+
+fn ends_with_continuation(line: &str) -> bool {
+    line.chars().rev().take_while(|&c| c == '\\').count() % 2 == 1
+}
+
+/// Split a logical line at the first unescaped `=`, `:` or whitespace.
+fn split_entry(line: &str) -> (&str, &str) {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '=' | ':' => return (&line[..i], line[i + 1..].trim_start()),
+            c if c.is_whitespace() => {
+                let rest = line[i..].trim_start();
+                let rest = rest.strip_prefix(['=', ':']).map_or(rest, str::trim_start);
+                return (&line[..i], rest);
+            }
+            _ => {}
+        }
+    }
+    (line, "")
+}
+
+/// Resolve the backslash escapes of a `.properties` key or value.
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => result.push('\t'),
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('f') => result.push('\u{c}'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                let decoded = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+                result.push(decoded.unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
+            Some(other) => result.push(other),
+            None => {}
+        }
+    }
+    result
+}
+
+/// Format a `java.text.MessageFormat` pattern: `{0}` is replaced with the
+/// first argument, `''` is a quote and text between single quotes is
+/// literal. Format types such as `{0,number,integer}` insert the argument
+/// as is.
+pub fn format_message(pattern: &str, args: &[String]) -> String {
+    let mut result = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars().peekable();
+    let mut quoted = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                result.push('\'');
+            }
+            '\'' => quoted = !quoted,
+            '{' if !quoted => {
+                let mut element = String::new();
+                let mut depth = 1;
+                for c in chars.by_ref() {
+                    match c {
+                        '{' => depth += 1,
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                    if depth == 0 {
+                        break;
+                    }
+                    element.push(c);
+                }
+                let index = element.split(',').next().unwrap_or_default().trim();
+                match index.parse::<usize>().ok().and_then(|i| args.get(i)) {
+                    Some(arg) => result.push_str(arg),
+                    // MessageFormat leaves arguments it wasn't given in place
+                    None => {
+                        result.push('{');
+                        result.push_str(&element);
+                        result.push('}');
+                    }
+                }
+            }
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_format_message() {
+        assert_eq!(
+            format_message("''{0}'' is not preceded with whitespace.", &args(&["="])),
+            "'=' is not preceded with whitespace."
+        );
+        assert_eq!(
+            format_message(
+                "Line is longer than {0,number,integer} characters (found {1,number,integer}).",
+                &args(&["100", "112"])
+            ),
+            "Line is longer than 100 characters (found 112)."
+        );
+        assert_eq!(
+            format_message("'{literal}' {1}", &args(&["a"])),
+            "{literal} {1}"
+        );
+    }
+
+    #[test]
+    fn test_parse_properties() {
+        let text = "# comment\n\
+                    ws.notPreceded=''{0}'' ist nicht durch Leerraum angef\\u00fchrt.\n\
+                    key.with\\ space : value \\\n    continued\n\
+                    ! another comment\n\
+                    empty\n";
+        let entries = parse_properties(text);
+        assert_eq!(
+            entries,
+            vec![
+                (
+                    "ws.notPreceded".to_string(),
+                    "''{0}'' ist nicht durch Leerraum angeführt.".to_string()
+                ),
+                ("key.with space".to_string(), "value continued".to_string()),
+                ("empty".to_string(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_locale() {
+        assert_eq!(
+            Locale::parse("de_CH.UTF-8@euro"),
+            Some(Locale::new("de", Some("CH")))
+        );
+        assert_eq!(Locale::parse("pt-br"), Some(Locale::new("pt", Some("BR"))));
+        assert_eq!(Locale::parse("fr"), Some(Locale::new("fr", None)));
+        assert_eq!(Locale::parse("C.UTF-8"), None);
+        assert_eq!(Locale::parse("POSIX"), None);
+        assert_eq!(
+            Locale::new("de", Some("CH")).bundle_suffixes(),
+            ["_de_CH", "_de", ""]
+        );
+    }
+
+    #[test]
+    fn test_load_falls_back_by_locale() {
+        let dir = std::env::temp_dir().join(format!("lintal-messages-{}", std::process::id()));
+        let whitespace = dir.join(CHECKS_PACKAGE_PATH).join("whitespace");
+        std::fs::create_dir_all(&whitespace).unwrap();
+        std::fs::write(
+            whitespace.join("messages.properties"),
+            "ws.notPreceded=''{0}'' is not preceded with whitespace.\n\
+             ws.notFollowed=''{0}'' is not followed by whitespace.\n",
+        )
+        .unwrap();
+        std::fs::write(
+            whitespace.join("messages_de.properties"),
+            "ws.notPreceded=''{0}'' ist nicht durch Leerraum angef\\u00fchrt.\n",
+        )
+        .unwrap();
+
+        let bundles = MessageBundles::load(&dir, Locale::parse("de_CH").as_ref());
+        std::fs::remove_dir_all(&dir).unwrap();
+        let bundles = bundles.unwrap();
+
+        let not_preceded = MessageKey::new("whitespace", "ws.notPreceded");
+        let not_followed = MessageKey::new("whitespace", "ws.notFollowed");
+        assert_eq!(
+            bundles.message(not_preceded, &args(&["="])).as_deref(),
+            Some("'=' ist nicht durch Leerraum angeführt.")
+        );
+        assert_eq!(
+            bundles.message(not_followed, &args(&[","])).as_deref(),
+            Some("',' is not followed by whitespace.")
+        );
+        assert_eq!(
+            bundles.message(MessageKey::new("blocks", "needBraces"), &[]),
+            None
+        );
+    }
+}
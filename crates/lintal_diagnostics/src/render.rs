@@ -0,0 +1,340 @@
+//! Rendering of diagnostics as annotated code frames.
+//!
+//! A rendered diagnostic looks like:
+//!
+//! ```text
+//! [WhitespaceAround] '=' is not preceded with whitespace.
+//!  --> src/Foo.java:3:10
+//!   |
+//! 3 |     int x=1;
+//!   |          ^
+//!   |
+//!   = safe fix:
+//!   -     int x=1;
+//!   +     int x = 1;
+//! ```
+
+use std::fmt::Write;
+
+use lintal_source_file::{OneIndexed, SourceCode};
+use lintal_text_size::{Ranged, TextRange, TextSize};
+
+use crate::{Applicability, Diagnostic, Fix};
+
+/// Spans covering more lines than this are elided in the middle.
+const MAX_SPAN_LINES: usize = 4;
+
+/// Options controlling how a diagnostic is rendered.
+#[derive(Debug, Clone, Copy)]
+pub struct RenderOptions {
+    /// Number of unannotated lines to show before and after the span.
+    pub context_lines: usize,
+    /// Whether to append a preview of the fix, if the diagnostic has one.
+    pub show_fix: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            context_lines: 0,
+            show_fix: true,
+        }
+    }
+}
+
+/// Render a diagnostic with a header, the annotated source lines, and an
+/// optional fix preview.
+pub fn render_diagnostic(
+    diagnostic: &Diagnostic,
+    rule_name: &str,
+    file_name: &str,
+    source: &SourceCode,
+    options: RenderOptions,
+) -> String {
+    let location = source.line_column(diagnostic.range.start());
+    let gutter = frame_last_line(source, diagnostic.range, options.context_lines)
+        .digits()
+        .get();
+
+    let mut output = String::new();
+    writeln!(output, "[{rule_name}] {}", diagnostic.kind.body).unwrap();
+    writeln!(output, "{:gutter$}--> {file_name}:{location}", "").unwrap();
+    output.push_str(&render_code_frame_with_gutter(
+        source,
+        diagnostic.range,
+        None,
+        options.context_lines,
+        gutter,
+    ));
+
+    if options.show_fix
+        && let Some(fix) = &diagnostic.fix
+    {
+        writeln!(output, "{:gutter$} |", "").unwrap();
+        output.push_str(&render_fix_preview(fix, source, gutter));
+    }
+
+    output
+}
+
+/// Render the source lines covered by `range` with carets underlining the
+/// span and an optional label after the last caret.
+///
+/// This has no header, so it can be embedded in other messages such as
+/// editor hover text.
+pub fn render_code_frame(
+    source: &SourceCode,
+    range: TextRange,
+    label: Option<&str>,
+    context_lines: usize,
+) -> String {
+    let gutter = frame_last_line(source, range, context_lines).digits().get();
+    render_code_frame_with_gutter(source, range, label, context_lines, gutter)
+}
+
+fn render_code_frame_with_gutter(
+    source: &SourceCode,
+    range: TextRange,
+    label: Option<&str>,
+    context_lines: usize,
+    gutter: usize,
+) -> String {
+    let start_line = source.line_index(range.start());
+    let end_line = span_end_line(source, range);
+    let first_line = start_line.saturating_sub(context_lines);
+    let last_line = frame_last_line(source, range, context_lines);
+
+    let span_lines = end_line.get() - start_line.get() + 1;
+    let elide = span_lines > MAX_SPAN_LINES;
+
+    let mut output = String::new();
+    writeln!(output, "{:gutter$} |", "").unwrap();
+
+    let mut line = first_line;
+    while line <= last_line {
+        // Keep the first two and the last line of long spans
+        if elide && line.get() == start_line.get() + 2 {
+            writeln!(output, "{:gutter$} ...", "").unwrap();
+            line = end_line;
+            continue;
+        }
+
+        let text = line_text(source, line);
+        writeln!(output, "{:>gutter$} | {text}", line.get()).unwrap();
+
+        if line >= start_line && line <= end_line {
+            let line_start = source.line_start(line);
+            let start_col = if line == start_line {
+                (range.start() - line_start).to_usize().min(text.len())
+            } else {
+                text.len() - text.trim_start().len()
+            };
+            let end_col = if line == end_line {
+                (range.end() - line_start).to_usize().min(text.len())
+            } else {
+                text.len()
+            }
+            .max(start_col);
+
+            let prefix: String = text[..start_col]
+                .chars()
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            let width = text[start_col..end_col].chars().count().max(1);
+            write!(output, "{:gutter$} | {prefix}{}", "", "^".repeat(width)).unwrap();
+            if line == end_line
+                && let Some(label) = label
+            {
+                write!(output, " {label}").unwrap();
+            }
+            output.push('\n');
+        }
+
+        line = line.saturating_add(1);
+    }
+
+    output
+}
+
+/// Render a before/after preview of the lines touched by `fix`.
+fn render_fix_preview(fix: &Fix, source: &SourceCode, gutter: usize) -> String {
+    let mut output = String::new();
+
+    let Some(start) = fix.min_start() else {
+        return output;
+    };
+    let end = fix.edits().iter().map(Ranged::end).max().unwrap_or(start);
+
+    let first_line = source.line_index(start);
+    let last_line = span_end_line(source, TextRange::new(start, end));
+    let snippet_start = source.line_start(first_line);
+    let snippet_end = source.line_end(last_line);
+
+    let mut fixed = String::new();
+    let mut last_pos = snippet_start;
+    for edit in fix.edits() {
+        fixed.push_str(source.slice(TextRange::new(last_pos, edit.start())));
+        fixed.push_str(edit.content().unwrap_or_default());
+        last_pos = edit.end();
+    }
+    fixed.push_str(source.slice(TextRange::new(last_pos, snippet_end)));
+
+    let applicability = match fix.applicability() {
+        Applicability::Safe => "safe",
+        Applicability::Unsafe => "unsafe",
+        Applicability::DisplayOnly => "suggested",
+    };
+    writeln!(output, "{:gutter$} = {applicability} fix:", "").unwrap();
+
+    let original = source.slice(TextRange::new(snippet_start, snippet_end));
+    for line in original.lines() {
+        writeln!(output, "{:gutter$} - {line}", "").unwrap();
+    }
+    for line in fixed.lines() {
+        writeln!(output, "{:gutter$} + {line}", "").unwrap();
+    }
+
+    output
+}
+
+/// The last line covered by `range`. A non-empty range ending right after a
+/// newline belongs to the line that newline terminates.
+fn span_end_line(source: &SourceCode, range: TextRange) -> OneIndexed {
+    let end_line = source.line_index(range.end());
+    if !range.is_empty() && range.end() == source.line_start(end_line) && end_line > OneIndexed::MIN
+    {
+        OneIndexed::from_zero_indexed(end_line.to_zero_indexed() - 1)
+    } else {
+        end_line
+    }
+}
+
+/// Text of a line without its terminator.
+fn line_text<'a>(source: &SourceCode<'a, '_>, line: OneIndexed) -> &'a str {
+    source.line_text(line).trim_end_matches(['\n', '\r'])
+}
+
+/// The last line shown in a frame for `range` with `context_lines` of context.
+fn frame_last_line(source: &SourceCode, range: TextRange, context_lines: usize) -> OneIndexed {
+    let last_line = OneIndexed::new(source.line_count()).unwrap_or(OneIndexed::MIN);
+    span_end_line(source, range)
+        .saturating_add(context_lines)
+        .min(last_line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Edit, Violation};
+    use lintal_source_file::LineIndex;
+
+    #[derive(Debug, Clone)]
+    struct TestViolation;
+
+    impl Violation for TestViolation {
+        fn message(&self) -> String {
+            "'=' is not preceded with whitespace.".to_string()
+        }
+    }
+
+    fn at(offset: usize) -> TextSize {
+        TextSize::try_from(offset).unwrap()
+    }
+
+    #[test]
+    fn test_render_single_line() {
+        let source = "class Foo {\n    int x=1;\n}\n";
+        let index = LineIndex::from_source_text(source);
+        let code = SourceCode::new(source, &index);
+        let eq = source.find('=').unwrap();
+        let diagnostic = Diagnostic::new(TestViolation, TextRange::at(at(eq), at(1)));
+
+        let rendered = render_diagnostic(
+            &diagnostic,
+            "WhitespaceAround",
+            "Foo.java",
+            &code,
+            RenderOptions::default(),
+        );
+
+        assert_eq!(
+            rendered,
+            "[WhitespaceAround] '=' is not preceded with whitespace.\n\
+             \x20--> Foo.java:2:10\n\
+             \x20 |\n\
+             2 |     int x=1;\n\
+             \x20 |          ^\n"
+        );
+    }
+
+    #[test]
+    fn test_render_fix_preview() {
+        let source = "class Foo {\n    int x=1;\n}\n";
+        let index = LineIndex::from_source_text(source);
+        let code = SourceCode::new(source, &index);
+        let eq = source.find('=').unwrap();
+        let diagnostic = Diagnostic::new(TestViolation, TextRange::at(at(eq), at(1)))
+            .with_fix(Fix::safe_edit(Edit::insertion(" ".to_string(), at(eq))));
+
+        let rendered = render_diagnostic(
+            &diagnostic,
+            "WhitespaceAround",
+            "Foo.java",
+            &code,
+            RenderOptions::default(),
+        );
+
+        assert!(rendered.ends_with(
+            "  = safe fix:\n\
+             \x20 -     int x=1;\n\
+             \x20 +     int x =1;\n"
+        ));
+    }
+
+    #[test]
+    fn test_render_code_frame_with_label_and_context() {
+        let source = "a\nbb cc\nd\n";
+        let index = LineIndex::from_source_text(source);
+        let code = SourceCode::new(source, &index);
+        let cc = source.find("cc").unwrap();
+
+        let rendered = render_code_frame(&code, TextRange::at(at(cc), at(2)), Some("here"), 1);
+
+        assert_eq!(
+            rendered,
+            "  |\n\
+             1 | a\n\
+             2 | bb cc\n\
+             \x20 |    ^^ here\n\
+             3 | d\n"
+        );
+    }
+
+    #[test]
+    fn test_render_multiline_span_is_elided() {
+        let source = "void m() {\n  a();\n  b();\n  c();\n  d();\n}\n";
+        let index = LineIndex::from_source_text(source);
+        let code = SourceCode::new(source, &index);
+        let range = TextRange::new(at(0), at(source.len() - 1));
+
+        let rendered = render_code_frame(&code, range, None, 0);
+
+        assert!(rendered.contains("1 | void m() {"));
+        assert!(rendered.contains("2 |   a();"));
+        assert!(rendered.contains("..."));
+        assert!(!rendered.contains("b();"));
+        assert!(rendered.contains("6 | }"));
+    }
+
+    #[test]
+    fn test_render_preserves_tabs_in_caret_line() {
+        let source = "\tint x=1;\n";
+        let index = LineIndex::from_source_text(source);
+        let code = SourceCode::new(source, &index);
+        let eq = source.find('=').unwrap();
+
+        let rendered = render_code_frame(&code, TextRange::at(at(eq), at(1)), None, 0);
+
+        assert!(rendered.contains("  | \t     ^\n"));
+    }
+}
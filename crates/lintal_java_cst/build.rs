@@ -0,0 +1,279 @@
+//! Generates the typed node wrappers in `ast` from tree-sitter-java's
+//! node-types.json, so they follow the grammar when it is updated.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct NodeInfo {
+    #[serde(rename = "type")]
+    kind: String,
+    named: bool,
+    #[serde(default)]
+    fields: BTreeMap<String, FieldInfo>,
+    #[serde(default)]
+    subtypes: Option<Vec<TypeRef>>,
+}
+
+#[derive(Deserialize)]
+struct FieldInfo {
+    multiple: bool,
+    types: Vec<TypeRef>,
+}
+
+#[derive(Deserialize)]
+struct TypeRef {
+    #[serde(rename = "type")]
+    kind: String,
+    named: bool,
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let nodes: Vec<NodeInfo> = serde_json::from_str(tree_sitter_java_orchard::NODE_TYPES)
+        .expect("tree-sitter-java node-types.json should be valid");
+    let out_dir = PathBuf::from(std::env::var_os("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    std::fs::write(out_dir.join("nodes.rs"), generate(&nodes)).expect("failed to write nodes.rs");
+}
+
+/// Generate a wrapper struct for every concrete named node kind, and an enum
+/// for every supertype.
+fn generate(nodes: &[NodeInfo]) -> String {
+    let wrapped: BTreeSet<&str> = nodes
+        .iter()
+        .filter(|node| node.named && node.subtypes.is_none() && !node.kind.starts_with('_'))
+        .map(|node| node.kind.as_str())
+        .collect();
+
+    let mut out = String::new();
+    let mut seen = BTreeSet::new();
+    for node in nodes {
+        if !wrapped.contains(node.kind.as_str()) || !seen.insert(node.kind.as_str()) {
+            continue;
+        }
+        let kind = &node.kind;
+        let name = type_name(kind);
+
+        writeln!(out, "/// `{kind}` node.").unwrap();
+        writeln!(out, "#[derive(Debug, Clone, Copy)]").unwrap();
+        writeln!(out, "pub struct {name}<'a>(CstNode<'a>);").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "impl<'a> AstNode<'a> for {name}<'a> {{").unwrap();
+        writeln!(out, "    const KIND: &'static str = {kind:?};").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "    fn cast(node: CstNode<'a>) -> Option<Self> {{").unwrap();
+        writeln!(
+            out,
+            "        (node.kind() == Self::KIND && node.inner().is_named()).then_some(Self(node))"
+        )
+        .unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "    fn node(&self) -> CstNode<'a> {{").unwrap();
+        writeln!(out, "        self.0").unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out, "}}").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "impl<'a> std::ops::Deref for {name}<'a> {{").unwrap();
+        writeln!(out, "    type Target = CstNode<'a>;").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "    fn deref(&self) -> &CstNode<'a> {{").unwrap();
+        writeln!(out, "        &self.0").unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out, "}}").unwrap();
+        writeln!(out).unwrap();
+
+        if node.fields.is_empty() {
+            continue;
+        }
+        writeln!(out, "impl<'a> {name}<'a> {{").unwrap();
+        let mut first = true;
+        for (field, info) in &node.fields {
+            // Fields that can only hold one concrete kind are typed, the rest
+            // (supertypes like `expression`, tokens, alternatives) are plain
+            let typed = match info.types.as_slice() {
+                [only] if only.named && wrapped.contains(only.kind.as_str()) => {
+                    Some(type_name(&only.kind))
+                }
+                _ => None,
+            };
+            let method = method_name(field);
+
+            if !first {
+                writeln!(out).unwrap();
+            }
+            first = false;
+            writeln!(out, "    /// The `{field}` field.").unwrap();
+            match (info.multiple, typed) {
+                (false, Some(ty)) => {
+                    writeln!(out, "    pub fn {method}(&self) -> Option<{ty}<'a>> {{").unwrap();
+                    writeln!(
+                        out,
+                        "        self.0.child_by_field_name({field:?}).and_then({ty}::cast)"
+                    )
+                    .unwrap();
+                }
+                (false, None) => {
+                    writeln!(out, "    pub fn {method}(&self) -> Option<CstNode<'a>> {{").unwrap();
+                    writeln!(out, "        self.0.child_by_field_name({field:?})").unwrap();
+                }
+                (true, Some(ty)) => {
+                    writeln!(
+                        out,
+                        "    pub fn {method}(&self) -> impl Iterator<Item = {ty}<'a>> + 'a {{"
+                    )
+                    .unwrap();
+                    writeln!(
+                        out,
+                        "        self.0.children_by_field_name({field:?}).filter_map({ty}::cast)"
+                    )
+                    .unwrap();
+                }
+                (true, None) => {
+                    writeln!(
+                        out,
+                        "    pub fn {method}(&self) -> impl Iterator<Item = CstNode<'a>> + 'a {{"
+                    )
+                    .unwrap();
+                    writeln!(out, "        self.0.children_by_field_name({field:?})").unwrap();
+                }
+            }
+            writeln!(out, "    }}").unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+        writeln!(out).unwrap();
+    }
+
+    let supertypes: BTreeMap<&str, &[TypeRef]> = nodes
+        .iter()
+        .filter_map(|node| Some((node.kind.as_str(), node.subtypes.as_deref()?)))
+        .filter(|(kind, _)| !wrapped.iter().any(|w| type_name(w) == type_name(kind)))
+        .collect();
+    for (kind, subtypes) in &supertypes {
+        generate_supertype(&mut out, kind, subtypes, &wrapped, &supertypes);
+    }
+    out
+}
+
+/// Generate an enum for a supertype like `statement`, with a variant for each
+/// named subtype. Subtypes that are supertypes themselves nest their enum;
+/// anonymous ones, like the `;` statement, don't cast.
+fn generate_supertype(
+    out: &mut String,
+    kind: &str,
+    subtypes: &[TypeRef],
+    wrapped: &BTreeSet<&str>,
+    supertypes: &BTreeMap<&str, &[TypeRef]>,
+) {
+    let name = type_name(kind);
+    let concrete: Vec<&str> = subtypes
+        .iter()
+        .filter(|subtype| subtype.named && wrapped.contains(subtype.kind.as_str()))
+        .map(|subtype| subtype.kind.as_str())
+        .collect();
+    let nested: Vec<&str> = subtypes
+        .iter()
+        .filter(|subtype| subtype.named && supertypes.contains_key(subtype.kind.as_str()))
+        .map(|subtype| subtype.kind.as_str())
+        .collect();
+    let variants: Vec<String> = concrete
+        .iter()
+        .chain(&nested)
+        .map(|k| type_name(k))
+        .collect();
+
+    writeln!(out, "/// A `{kind}` node: one of its named subtypes.").unwrap();
+    writeln!(out, "#[derive(Debug, Clone, Copy)]").unwrap();
+    writeln!(out, "pub enum {name}<'a> {{").unwrap();
+    for variant in &variants {
+        writeln!(out, "    {variant}({variant}<'a>),").unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+    writeln!(out).unwrap();
+
+    writeln!(out, "impl<'a> AstNode<'a> for {name}<'a> {{").unwrap();
+    writeln!(out, "    const KIND: &'static str = {kind:?};").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "    fn cast(node: CstNode<'a>) -> Option<Self> {{").unwrap();
+    writeln!(out, "        if !node.inner().is_named() {{").unwrap();
+    writeln!(out, "            return None;").unwrap();
+    writeln!(out, "        }}").unwrap();
+    writeln!(out, "        Some(match node.kind() {{").unwrap();
+    for subtype in &concrete {
+        let variant = type_name(subtype);
+        writeln!(
+            out,
+            "            {subtype:?} => Self::{variant}({variant}(node)),"
+        )
+        .unwrap();
+    }
+    let mut fallback = String::from("None");
+    for (i, subtype) in nested.iter().enumerate() {
+        let variant = type_name(subtype);
+        let cast = format!("{variant}::cast(node).map(Self::{variant})");
+        fallback = if i == 0 {
+            cast
+        } else {
+            format!("{fallback}.or_else(|| {cast})")
+        };
+    }
+    writeln!(out, "            _ => return {fallback},").unwrap();
+    writeln!(out, "        }})").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "    fn node(&self) -> CstNode<'a> {{").unwrap();
+    writeln!(out, "        match self {{").unwrap();
+    for variant in &variants {
+        writeln!(out, "            Self::{variant}(node) => node.node(),").unwrap();
+    }
+    writeln!(out, "        }}").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "}}").unwrap();
+    writeln!(out).unwrap();
+
+    writeln!(out, "impl<'a> std::ops::Deref for {name}<'a> {{").unwrap();
+    writeln!(out, "    type Target = CstNode<'a>;").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "    fn deref(&self) -> &CstNode<'a> {{").unwrap();
+    writeln!(out, "        match self {{").unwrap();
+    for variant in &variants {
+        writeln!(out, "            Self::{variant}(node) => node,").unwrap();
+    }
+    writeln!(out, "        }}").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "}}").unwrap();
+    writeln!(out).unwrap();
+}
+
+/// `class_declaration` -> `ClassDeclaration`.
+fn type_name(kind: &str) -> String {
+    kind.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        })
+        .collect()
+}
+
+/// Field names that are Rust keywords become raw identifiers.
+fn method_name(field: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in",
+        "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+        "return", "static", "struct", "trait", "true", "try", "type", "typeof", "union", "unsafe",
+        "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+    match field {
+        "self" | "Self" | "super" | "crate" => format!("{field}_"),
+        _ if KEYWORDS.contains(&field) => format!("r#{field}"),
+        _ => field.to_string(),
+    }
+}
@@ -0,0 +1,35 @@
+//! Typed wrappers for the node kinds of the Java grammar.
+//!
+//! `build.rs` generates one struct per named node kind from tree-sitter-java's
+//! node-types.json, e.g. [`IfStatement`] for `if_statement`, with an accessor
+//! for each of its fields. A field that can only hold one kind of node returns
+//! that kind's wrapper; others return a plain [`CstNode`]. Wrappers deref to
+//! their [`CstNode`], so everything else works as on an untyped node.
+//!
+//! Supertypes like `statement` and `expression` get an enum instead, e.g.
+//! [`Statement`], with a variant for each named subtype. Anonymous subtypes,
+//! like the `;` empty statement, don't cast.
+//!
+//! ```ignore
+//! if let Some(stmt) = node.cast::<IfStatement>()
+//!     && let Some(condition) = stmt.condition()
+//! {
+//!     // `condition` is a `ParenthesizedExpression`
+//! }
+//! ```
+
+use crate::CstNode;
+
+/// A typed view of a [`CstNode`] of one kind.
+pub trait AstNode<'a>: Copy {
+    /// The tree-sitter kind of the node, or the supertype for enums.
+    const KIND: &'static str;
+
+    /// Wrap `node` if it has this kind.
+    fn cast(node: CstNode<'a>) -> Option<Self>;
+
+    /// The untyped node.
+    fn node(&self) -> CstNode<'a>;
+}
+
+include!(concat!(env!("OUT_DIR"), "/nodes.rs"));
@@ -0,0 +1,203 @@
+//! Comments of a file and the nodes they belong to.
+//!
+//! Every comment is attached to a sibling node, the way formatters treat
+//! trivia: a comment on the same line after a node trails that node, any other
+//! comment leads the node that follows it. A comment with no sibling to attach
+//! to, e.g. in an otherwise empty file, dangles from its parent.
+//!
+//! ```text
+//! /** Javadoc */          <- leads the method
+//! void m() {
+//!     call(); // note     <- trails `call();`
+//!     // before brace     <- leads `}`
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use lintal_java_parser::java_kind_ids;
+use lintal_text_size::TextRange;
+
+use crate::{CstNode, TreeWalker};
+
+/// What kind of comment a [`Comment`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentKind {
+    /// `// ...`
+    Line,
+    /// `/* ... */`
+    Block,
+    /// `/** ... */`
+    Javadoc,
+}
+
+/// A comment in the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Comment {
+    pub range: TextRange,
+    pub kind: CommentKind,
+}
+
+impl Comment {
+    /// Text of the comment, including its delimiters.
+    pub fn text<'a>(&self, source: &'a str) -> &'a str {
+        &source[self.range]
+    }
+}
+
+/// All comments of a file, with the nodes they are attached to.
+#[derive(Debug, Default)]
+pub struct CommentMap {
+    /// In source order, so the comments attached to a node are contiguous.
+    comments: Vec<Comment>,
+    leading: HashMap<usize, Range<usize>>,
+    trailing: HashMap<usize, Range<usize>>,
+    dangling: HashMap<usize, Range<usize>>,
+}
+
+impl CommentMap {
+    /// Collect and attach the comments in the tree under `root`.
+    pub fn build(root: &CstNode) -> Self {
+        let comment_kinds = java_kind_ids(["line_comment", "block_comment"]);
+
+        let mut map = Self::default();
+        for node in TreeWalker::new(root.inner(), root.source).only_kinds(comment_kinds) {
+            let index = map.comments.len();
+            map.comments.push(Comment {
+                range: node.range(),
+                kind: comment_kind(node.text()),
+            });
+
+            let (attachments, target) = map.attachment(&node);
+            attachments.entry(target).or_insert(index..index).end = index + 1;
+        }
+        map
+    }
+
+    /// Decide which node `comment` belongs to.
+    fn attachment(&mut self, comment: &CstNode) -> (&mut HashMap<usize, Range<usize>>, usize) {
+        let inner = comment.inner();
+        let previous = std::iter::successors(inner.prev_sibling(), |n| n.prev_sibling())
+            .find(|n| !is_comment(n));
+        let next = std::iter::successors(inner.next_sibling(), |n| n.next_sibling())
+            .find(|n| !is_comment(n));
+
+        if let Some(previous) = previous {
+            let between = &comment.source[previous.end_byte()..inner.start_byte()];
+            if !between.contains('\n') || next.is_none() {
+                return (&mut self.trailing, previous.id());
+            }
+        }
+        match (next, inner.parent()) {
+            (Some(next), _) => (&mut self.leading, next.id()),
+            (None, Some(parent)) => (&mut self.dangling, parent.id()),
+            (None, None) => (&mut self.dangling, inner.id()),
+        }
+    }
+
+    /// All comments, in source order.
+    pub fn comments(&self) -> &[Comment] {
+        &self.comments
+    }
+
+    /// Comments before `node`, on their own lines.
+    pub fn leading(&self, node: &CstNode) -> &[Comment] {
+        self.attached(&self.leading, node)
+    }
+
+    /// Comments after `node` on the line it ends.
+    pub fn trailing(&self, node: &CstNode) -> &[Comment] {
+        self.attached(&self.trailing, node)
+    }
+
+    /// Comments inside `node` that have no sibling to attach to.
+    pub fn dangling(&self, node: &CstNode) -> &[Comment] {
+        self.attached(&self.dangling, node)
+    }
+
+    fn attached(&self, attachments: &HashMap<usize, Range<usize>>, node: &CstNode) -> &[Comment] {
+        attachments
+            .get(&node.inner().id())
+            .map_or(&[], |range| &self.comments[range.clone()])
+    }
+
+    /// The comment covering `range`, if any.
+    pub fn comment_at(&self, range: TextRange) -> Option<&Comment> {
+        let index = self
+            .comments
+            .partition_point(|comment| comment.range.end() <= range.start());
+        self.comments
+            .get(index)
+            .filter(|comment| comment.range.contains_range(range))
+    }
+}
+
+fn is_comment(node: &tree_sitter::Node) -> bool {
+    node.is_extra() && matches!(node.kind(), "line_comment" | "block_comment")
+}
+
+fn comment_kind(text: &str) -> CommentKind {
+    if text.starts_with("//") {
+        CommentKind::Line
+    } else if text.starts_with("/**") && text != "/**/" {
+        CommentKind::Javadoc
+    } else {
+        CommentKind::Block
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_parser::JavaParser;
+
+    #[test]
+    fn test_comment_attachment() {
+        let source = "\
+class Foo {
+    /** Javadoc */
+    void m() {
+        call(); // note
+        // before brace
+    }
+}
+";
+        let result = JavaParser::new().parse(source).unwrap();
+        let root = CstNode::new(result.tree.root_node(), source);
+        let map = CommentMap::build(&root);
+        let find = |kind: &str| {
+            TreeWalker::new(result.tree.root_node(), source)
+                .find(|node| node.kind() == kind)
+                .unwrap()
+        };
+        let texts = |comments: &[Comment]| -> Vec<&'static str> {
+            comments
+                .iter()
+                .map(|comment| comment.text(source))
+                .collect()
+        };
+
+        assert_eq!(map.comments().len(), 3);
+        assert_eq!(map.comments()[0].kind, CommentKind::Javadoc);
+        assert_eq!(
+            texts(map.leading(&find("method_declaration"))),
+            ["/** Javadoc */"]
+        );
+        assert_eq!(
+            texts(map.trailing(&find("expression_statement"))),
+            ["// note"]
+        );
+        let closing_brace = find("block").children().last().unwrap();
+        assert_eq!(texts(map.leading(&closing_brace)), ["// before brace"]);
+        assert!(map.trailing(&find("method_declaration")).is_empty());
+    }
+
+    #[test]
+    fn test_comment_kind() {
+        assert_eq!(comment_kind("// x"), CommentKind::Line);
+        assert_eq!(comment_kind("/* x */"), CommentKind::Block);
+        assert_eq!(comment_kind("/**/"), CommentKind::Block);
+        assert_eq!(comment_kind("/** x */"), CommentKind::Javadoc);
+    }
+}
@@ -3,6 +3,16 @@
 //! Provides strongly-typed access to tree-sitter nodes while preserving
 //! source positions needed for fixes.
 
+pub mod ast;
+pub mod comments;
+pub mod text_blocks;
+pub mod tokens;
+
+pub use ast::AstNode;
+pub use comments::{Comment, CommentKind, CommentMap};
+pub use text_blocks::{TextBlock, TextBlocks};
+pub use tokens::{SourceToken, TokenStream};
+
 use lintal_text_size::{TextRange, TextSize};
 use tree_sitter::Node;
 
@@ -86,6 +96,20 @@ impl<'a> CstNode<'a> {
             .map(|n| CstNode::new(n, self.source))
     }
 
+    /// Children in the field `name`, for fields that can hold several nodes.
+    pub fn children_by_field_name(&self, name: &'a str) -> impl Iterator<Item = CstNode<'a>> + 'a {
+        let node = self.node;
+        self.children()
+            .enumerate()
+            .filter(move |(i, _)| node.field_name_for_child(*i as u32) == Some(name))
+            .map(|(_, child)| child)
+    }
+
+    /// View this node as a typed [`AstNode`], if it has that kind.
+    pub fn cast<T: AstNode<'a>>(self) -> Option<T> {
+        T::cast(self)
+    }
+
     pub fn named_children(&self) -> impl Iterator<Item = CstNode<'a>> + 'a {
         self.children().filter(|c| c.node.is_named())
     }
@@ -103,10 +127,24 @@ impl<'a> CstNode<'a> {
 }
 
 /// Iterator for walking all nodes in a tree (pre-order traversal).
+///
+/// The walk can be pruned: [`TreeWalker::skip_subtree`] skips the children of
+/// the node just returned, [`TreeWalker::skip_kinds`] never descends into
+/// nodes of the given kinds, and [`TreeWalker::only_kinds`] and
+/// [`TreeWalker::named_only`] limit which nodes are returned without changing
+/// which are visited.
 pub struct TreeWalker<'a> {
     cursor: tree_sitter::TreeCursor<'a>,
     source: &'a str,
+    started: bool,
     done: bool,
+    /// Don't descend into the node the cursor is on.
+    skip_children: bool,
+    /// Kinds to return, indexed by kind id; all kinds if `None`.
+    only_kinds: Option<Vec<bool>>,
+    /// Kinds whose children are never visited, indexed by kind id.
+    skip_kinds: Vec<bool>,
+    named_only: bool,
 }
 
 impl<'a> TreeWalker<'a> {
@@ -114,39 +152,107 @@ impl<'a> TreeWalker<'a> {
         Self {
             cursor: root.walk(),
             source,
+            started: false,
             done: false,
+            skip_children: false,
+            only_kinds: None,
+            skip_kinds: Vec::new(),
+            named_only: false,
         }
     }
-}
 
-impl<'a> Iterator for TreeWalker<'a> {
-    type Item = CstNode<'a>;
+    /// Only return nodes with one of these kind ids. Other nodes are still
+    /// descended into.
+    pub fn only_kinds(mut self, kind_ids: impl IntoIterator<Item = u16>) -> Self {
+        self.only_kinds = Some(kind_set(kind_ids));
+        self
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.done {
-            return None;
-        }
+    /// Return nodes with these kind ids, but not their descendants, e.g. to
+    /// stay out of string literals.
+    pub fn skip_kinds(mut self, kind_ids: impl IntoIterator<Item = u16>) -> Self {
+        self.skip_kinds = kind_set(kind_ids);
+        self
+    }
 
-        let node = CstNode::new(self.cursor.node(), self.source);
+    /// Only return named nodes, not anonymous tokens such as punctuation.
+    pub fn named_only(mut self) -> Self {
+        self.named_only = true;
+        self
+    }
 
-        // Try to go to first child
-        if self.cursor.goto_first_child() {
-            return Some(node);
+    /// Don't visit the descendants of the node last returned by `next`.
+    pub fn skip_subtree(&mut self) {
+        if self.started {
+            self.skip_children = true;
         }
+    }
 
-        // Try to go to next sibling
-        if self.cursor.goto_next_sibling() {
-            return Some(node);
+    /// Move the cursor to the next node in pre-order, returning `false` once
+    /// the walk is finished.
+    fn advance(&mut self) -> bool {
+        let skip_children = std::mem::take(&mut self.skip_children)
+            || contains(&self.skip_kinds, self.cursor.node().kind_id());
+        if !skip_children && self.cursor.goto_first_child() {
+            return true;
         }
 
         // Go up until we can go to next sibling or reach root
         loop {
+            if self.cursor.goto_next_sibling() {
+                return true;
+            }
             if !self.cursor.goto_parent() {
-                self.done = true;
-                return Some(node);
+                return false;
             }
-            if self.cursor.goto_next_sibling() {
-                return Some(node);
+        }
+    }
+
+    fn wanted(&self, node: Node<'_>) -> bool {
+        (!self.named_only || node.is_named())
+            && self
+                .only_kinds
+                .as_ref()
+                .is_none_or(|kinds| contains(kinds, node.kind_id()))
+    }
+}
+
+fn kind_set(kind_ids: impl IntoIterator<Item = u16>) -> Vec<bool> {
+    let mut set = Vec::new();
+    for id in kind_ids {
+        let id = id as usize;
+        if set.len() <= id {
+            set.resize(id + 1, false);
+        }
+        set[id] = true;
+    }
+    set
+}
+
+fn contains(set: &[bool], kind_id: u16) -> bool {
+    set.get(kind_id as usize).copied().unwrap_or(false)
+}
+
+impl<'a> Iterator for TreeWalker<'a> {
+    type Item = CstNode<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.done {
+                return None;
+            }
+            if self.started {
+                if !self.advance() {
+                    self.done = true;
+                    return None;
+                }
+            } else {
+                self.started = true;
+            }
+
+            let node = self.cursor.node();
+            if self.wanted(node) {
+                return Some(CstNode::new(node, self.source));
             }
         }
     }
@@ -185,4 +291,121 @@ mod tests {
         assert!(!nodes.is_empty());
         assert_eq!(nodes[0].kind(), "program");
     }
+
+    fn walker_test_source() -> (lintal_java_parser::ParseResult, &'static str) {
+        let source = r#"class Foo { String s = "a"; void m() { int x = 1; } }"#;
+        (JavaParser::new().parse(source).unwrap(), source)
+    }
+
+    fn kind_ids(kind: &str) -> Vec<u16> {
+        lintal_java_parser::java_kind_ids([kind])
+    }
+
+    #[test]
+    fn test_tree_walker_skip_subtree() {
+        let (result, source) = walker_test_source();
+        let mut walker = TreeWalker::new(result.tree.root_node(), source);
+        let mut kinds = Vec::new();
+        while let Some(node) = walker.next() {
+            if node.kind() == "method_declaration" {
+                walker.skip_subtree();
+            }
+            kinds.push(node.kind());
+        }
+
+        assert!(kinds.contains(&"method_declaration"));
+        assert!(kinds.contains(&"string_literal"));
+        assert!(!kinds.contains(&"local_variable_declaration"));
+        assert_eq!(kinds.last(), Some(&"}"));
+    }
+
+    #[test]
+    fn test_tree_walker_skip_kinds() {
+        let (result, source) = walker_test_source();
+        let kinds: Vec<_> = TreeWalker::new(result.tree.root_node(), source)
+            .skip_kinds(kind_ids("block"))
+            .map(|node| node.kind())
+            .collect();
+
+        assert!(kinds.contains(&"block"));
+        assert!(kinds.contains(&"field_declaration"));
+        assert!(!kinds.contains(&"local_variable_declaration"));
+    }
+
+    #[test]
+    fn test_tree_walker_only_kinds() {
+        let (result, source) = walker_test_source();
+        let kinds: Vec<_> = TreeWalker::new(result.tree.root_node(), source)
+            .only_kinds(kind_ids("variable_declarator"))
+            .map(|node| node.kind())
+            .collect();
+
+        assert_eq!(kinds, ["variable_declarator", "variable_declarator"]);
+    }
+
+    #[test]
+    fn test_tree_walker_named_only() {
+        let (result, source) = walker_test_source();
+        let walker = TreeWalker::new(result.tree.root_node(), source).named_only();
+
+        assert!(walker.map(|node| node.inner()).all(|node| node.is_named()));
+    }
+
+    #[test]
+    fn test_typed_nodes() {
+        use ast::{ClassDeclaration, IfStatement};
+
+        let (result, source) = walker_test_source();
+        let root = CstNode::new(result.tree.root_node(), source);
+        let class = root
+            .named_children()
+            .find_map(CstNode::cast::<ClassDeclaration>)
+            .unwrap();
+        assert_eq!(class.name().unwrap().text(), "Foo");
+        assert_eq!(class.body().unwrap().kind(), "class_body");
+        assert!(root.cast::<IfStatement>().is_none());
+
+        let source = "class Foo { void m() { if (ok) return; } }";
+        let result = JavaParser::new().parse(source).unwrap();
+        let statement = TreeWalker::new(result.tree.root_node(), source)
+            .find_map(CstNode::cast::<IfStatement>)
+            .unwrap();
+        assert_eq!(statement.condition().unwrap().text(), "(ok)");
+        assert_eq!(statement.consequence().unwrap().kind(), "return_statement");
+        assert!(statement.alternative().is_none());
+    }
+
+    #[test]
+    fn test_supertype_enums() {
+        use ast::{Expression, Statement};
+
+        let source = "class Foo { void m() { if (a > b) return; else ; } }";
+        let result = JavaParser::new().parse(source).unwrap();
+        let statement = TreeWalker::new(result.tree.root_node(), source)
+            .find_map(CstNode::cast::<ast::IfStatement>)
+            .unwrap();
+
+        let consequence = statement.consequence().unwrap().cast::<Statement>();
+        assert!(matches!(consequence, Some(Statement::ReturnStatement(_))));
+        // The empty statement is an anonymous `;`
+        assert!(
+            statement
+                .alternative()
+                .unwrap()
+                .cast::<Statement>()
+                .is_none()
+        );
+
+        let condition = statement
+            .condition()
+            .unwrap()
+            .named_children()
+            .next()
+            .unwrap();
+        let Some(Expression::BinaryExpression(comparison)) = condition.cast::<Expression>() else {
+            panic!("expected a binary expression");
+        };
+        assert_eq!(comparison.text(), "a > b");
+        assert!(statement.cast::<Expression>().is_none());
+    }
 }
@@ -0,0 +1,122 @@
+//! Where the text blocks of a file are.
+//!
+//! The content of a text block is string data: its blank lines, its leading
+//! whitespace and anything in it that looks like code mean nothing to style
+//! rules. Its indentation relative to the closing delimiter is part of the
+//! string's value, so a fix must not touch it either. [`TextBlocks`] lists
+//! each text block of a file with its content so both can stay out of it.
+
+use lintal_java_parser::java_kind_ids;
+use lintal_text_size::{TextRange, TextSize};
+
+use crate::{CstNode, TreeWalker};
+
+/// A text block: the whole literal and its content, which runs from after the
+/// opening `"""` up to the closing one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextBlock {
+    pub range: TextRange,
+    pub content: TextRange,
+}
+
+/// The text blocks of a file in source order.
+#[derive(Debug, Default)]
+pub struct TextBlocks {
+    blocks: Vec<TextBlock>,
+}
+
+impl TextBlocks {
+    /// Collect the text blocks of the tree under `root`.
+    pub fn build(root: &CstNode) -> Self {
+        let literals = java_kind_ids(["string_literal", "text_block"]);
+
+        let blocks = TreeWalker::new(root.inner(), root.source)
+            .only_kinds(literals.iter().copied())
+            .skip_kinds(literals.iter().copied())
+            .filter_map(|literal| {
+                let mut delimiters = literal.children().filter(|child| child.kind() == "\"\"\"");
+                let open = delimiters.next()?;
+                // Error recovery can leave a text block without its closing
+                // delimiter; its content then runs to the end of the literal
+                let content_end = delimiters
+                    .last()
+                    .map_or(literal.range().end(), |close| close.range().start());
+                Some(TextBlock {
+                    range: literal.range(),
+                    content: TextRange::new(open.range().end(), content_end),
+                })
+            })
+            .collect();
+        Self { blocks }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    pub fn blocks(&self) -> &[TextBlock] {
+        &self.blocks
+    }
+
+    /// The text block whose content contains `offset`, its edges included.
+    pub fn containing(&self, offset: TextSize) -> Option<&TextBlock> {
+        let index = self
+            .blocks
+            .partition_point(|block| block.content.end() < offset);
+        self.blocks
+            .get(index)
+            .filter(|block| block.content.contains_inclusive(offset))
+    }
+
+    /// Whether replacing `range` would change the content of a text block
+    /// without replacing the whole literal.
+    pub fn changes_content(&self, range: TextRange) -> bool {
+        let index = self
+            .blocks
+            .partition_point(|block| block.content.end() < range.start());
+        self.blocks[index..]
+            .iter()
+            .take_while(|block| block.content.start() <= range.end())
+            .any(|block| !range.contains_range(block.range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_parser::JavaParser;
+
+    #[test]
+    fn test_text_blocks() {
+        let source = "class Foo {\n    String s = \"\"\"\n        a\n\n        b\"\"\";\n    String t = \"\";\n}\n";
+        let result = JavaParser::new().parse(source).unwrap();
+        let root = CstNode::new(result.tree.root_node(), source);
+        let text_blocks = TextBlocks::build(&root);
+
+        let [block] = text_blocks.blocks() else {
+            panic!("expected one text block: {text_blocks:?}");
+        };
+        let open = source.find("\"\"\"").unwrap();
+        let close = source.rfind("\"\"\"").unwrap();
+        assert_eq!(&source[block.range], "\"\"\"\n        a\n\n        b\"\"\"");
+        assert_eq!(&source[block.content], "\n        a\n\n        b");
+
+        let offset = |offset: usize| TextSize::new(offset as u32);
+        let range = |start: usize, end: usize| TextRange::new(offset(start), offset(end));
+        assert!(text_blocks.containing(offset(open)).is_none());
+        assert_eq!(text_blocks.containing(offset(open + 3)), Some(block));
+        assert_eq!(text_blocks.containing(offset(close)), Some(block));
+        assert!(text_blocks.containing(offset(close + 3)).is_none());
+
+        // Re-indenting the content or the closing delimiter changes the string
+        let line = source.find("        a").unwrap();
+        assert!(text_blocks.changes_content(range(line, line + 4)));
+        assert!(text_blocks.changes_content(range(close, close)));
+        assert!(text_blocks.changes_content(range(open + 3, open + 3)));
+        // Edits next to it or replacing all of it don't
+        assert!(!text_blocks.changes_content(range(open - 1, open)));
+        assert!(!text_blocks.changes_content(range(close + 3, close + 4)));
+        assert!(!text_blocks.changes_content(range(open, close + 3)));
+        assert!(!text_blocks.changes_content(range(0, 5)));
+    }
+}
@@ -0,0 +1,166 @@
+//! A flat, indexed view of the tokens of a file.
+//!
+//! Whitespace rules mostly care about what is next to a token and how much
+//! space separates them, not about tree structure. [`TokenStream`] lists the
+//! leaves of the tree in source order, comments included, so the neighbours
+//! of a token and the whitespace between them are an index lookup away.
+//! String, character and text block literals are single tokens.
+
+use lintal_java_parser::java_kind_ids;
+use lintal_text_size::{TextRange, TextSize};
+
+use crate::{CstNode, TreeWalker};
+
+/// A token of a [`TokenStream`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceToken {
+    pub range: TextRange,
+    pub kind: &'static str,
+    pub kind_id: u16,
+}
+
+impl SourceToken {
+    pub fn is_comment(&self) -> bool {
+        matches!(self.kind, "line_comment" | "block_comment")
+    }
+
+    pub fn text<'a>(&self, source: &'a str) -> &'a str {
+        &source[self.range]
+    }
+}
+
+/// The tokens of a file in source order.
+#[derive(Debug, Default)]
+pub struct TokenStream {
+    tokens: Vec<SourceToken>,
+}
+
+impl TokenStream {
+    /// Collect the tokens of the tree under `root`.
+    pub fn build(root: &CstNode) -> Self {
+        let literals = java_kind_ids(["string_literal", "character_literal", "text_block"]);
+
+        let tokens = TreeWalker::new(root.inner(), root.source)
+            .skip_kinds(literals.iter().copied())
+            .filter(|node| node.inner().child_count() == 0 || literals.contains(&node.kind_id()))
+            // Missing nodes inserted by error recovery take up no space
+            .filter(|node| !node.range().is_empty())
+            .map(|node| SourceToken {
+                range: node.range(),
+                kind: node.kind(),
+                kind_id: node.kind_id(),
+            })
+            .collect();
+        Self { tokens }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    pub fn tokens(&self) -> &[SourceToken] {
+        &self.tokens
+    }
+
+    pub fn get(&self, index: usize) -> Option<&SourceToken> {
+        self.tokens.get(index)
+    }
+
+    /// Index of the token starting at `offset`.
+    pub fn index_at(&self, offset: TextSize) -> Option<usize> {
+        self.tokens
+            .binary_search_by_key(&offset, |token| token.range.start())
+            .ok()
+    }
+
+    /// Index of the first token of `node`.
+    pub fn first_index(&self, node: &CstNode) -> Option<usize> {
+        self.index_at(node.range().start())
+    }
+
+    /// Index of the last token of `node`.
+    pub fn last_index(&self, node: &CstNode) -> Option<usize> {
+        let end = node.range().end();
+        let index = self.tokens.partition_point(|token| token.range.end() < end);
+        self.tokens
+            .get(index)
+            .is_some_and(|token| token.range.end() == end)
+            .then_some(index)
+    }
+
+    /// The token before `index`.
+    pub fn previous(&self, index: usize) -> Option<&SourceToken> {
+        index
+            .checked_sub(1)
+            .and_then(|index| self.tokens.get(index))
+    }
+
+    /// The token after `index`.
+    pub fn next(&self, index: usize) -> Option<&SourceToken> {
+        self.tokens.get(index + 1)
+    }
+
+    /// The whitespace between the token at `index` and the one before it, or
+    /// the start of the file. Empty if they touch.
+    pub fn whitespace_before(&self, index: usize) -> TextRange {
+        let start = self
+            .previous(index)
+            .map_or(TextSize::new(0), |token| token.range.end());
+        TextRange::new(start, self.tokens[index].range.start())
+    }
+
+    /// The whitespace between the token at `index` and the one after it. Empty
+    /// if they touch; for the last token, up to its end.
+    pub fn whitespace_after(&self, index: usize) -> TextRange {
+        let end = self.tokens[index].range.end();
+        TextRange::new(
+            end,
+            self.next(index).map_or(end, |token| token.range.start()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_parser::JavaParser;
+
+    #[test]
+    fn test_token_stream() {
+        let source = "class Foo { String s = ( \"a  b\" ); // c\n}";
+        let result = JavaParser::new().parse(source).unwrap();
+        let root = CstNode::new(result.tree.root_node(), source);
+        let tokens = TokenStream::build(&root);
+
+        let texts: Vec<&str> = tokens
+            .tokens()
+            .iter()
+            .map(|token| token.text(source))
+            .collect();
+        assert_eq!(
+            texts,
+            [
+                "class", "Foo", "{", "String", "s", "=", "(", "\"a  b\"", ")", ";", "// c", "}"
+            ]
+        );
+
+        let lparen = tokens.index_at(TextSize::new(23)).unwrap();
+        assert_eq!(tokens.get(lparen).unwrap().kind, "(");
+        assert_eq!(tokens.next(lparen).unwrap().kind, "string_literal");
+        assert_eq!(tokens.previous(lparen).unwrap().kind, "=");
+        assert_eq!(&source[tokens.whitespace_after(lparen)], " ");
+        assert!(tokens.whitespace_after(lparen + 2).is_empty());
+        assert_eq!(&source[tokens.whitespace_before(tokens.len() - 1)], "\n");
+        assert!(tokens.tokens()[10].is_comment());
+
+        let field = TreeWalker::new(result.tree.root_node(), source)
+            .find(|node| node.kind() == "field_declaration")
+            .unwrap();
+        assert_eq!(tokens.first_index(&field), Some(3));
+        assert_eq!(tokens.last_index(&field), Some(9));
+    }
+}
@@ -0,0 +1,123 @@
+//! The grammars lintal parses, behind one interface.
+//!
+//! Parsing and resolving node kinds go through a [`Language`], so another
+//! tree-sitter grammar can be added in a crate of its own that implements
+//! it, next to [`Java`] rather than inside its code paths. The rules and
+//! the CST helpers are still written against the Java grammar.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A tree-sitter grammar and what lintal needs to know about it.
+pub trait Language: Send + Sync {
+    /// Lowercase name, e.g. `java`.
+    fn name(&self) -> &'static str;
+
+    /// Extensions of its source files, without the dot.
+    fn extensions(&self) -> &'static [&'static str];
+
+    fn grammar(&self) -> tree_sitter::Language;
+
+    /// The kind ids of its nodes, built once.
+    fn kind_ids(&self) -> &KindIds;
+
+    /// Whether `path` is one of its source files, by extension.
+    fn matches(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.extensions().contains(&ext))
+    }
+}
+
+/// The kind ids of a grammar by kind name. A name can have several ids,
+/// e.g. a keyword that is also the name of a node.
+#[derive(Debug)]
+pub struct KindIds {
+    map: HashMap<&'static str, Vec<u16>>,
+    count: usize,
+}
+
+impl KindIds {
+    pub fn new(grammar: &tree_sitter::Language) -> Self {
+        let count = grammar.node_kind_count();
+        let mut map: HashMap<&'static str, Vec<u16>> = HashMap::new();
+        for id in 0..count {
+            let id = id as u16;
+            if let Some(kind) = grammar.node_kind_for_id(id) {
+                map.entry(kind).or_default().push(id);
+            }
+        }
+        Self { map, count }
+    }
+
+    /// Number of kinds, one more than the largest id.
+    pub fn kind_count(&self) -> usize {
+        self.count
+    }
+
+    pub fn map(&self) -> &HashMap<&'static str, Vec<u16>> {
+        &self.map
+    }
+
+    pub fn contains(&self, kind: &str) -> bool {
+        self.map.contains_key(kind)
+    }
+
+    /// Resolve node kinds to their kind ids, sorted, skipping kinds the
+    /// grammar doesn't have.
+    pub fn resolve<'a>(&self, kinds: impl IntoIterator<Item = &'a str>) -> Vec<u16> {
+        let mut ids: Vec<u16> = kinds
+            .into_iter()
+            .filter_map(|kind| self.map.get(kind))
+            .flatten()
+            .copied()
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+}
+
+/// Java, the language of every built-in rule.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Java;
+
+impl Language for Java {
+    fn name(&self) -> &'static str {
+        "java"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["java"]
+    }
+
+    fn grammar(&self) -> tree_sitter::Language {
+        tree_sitter_java_orchard::LANGUAGE.into()
+    }
+
+    fn kind_ids(&self) -> &KindIds {
+        crate::java_kinds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_java() {
+        assert!(Java.matches(Path::new("src/A.java")));
+        assert!(!Java.matches(Path::new("build.gradle")));
+        assert!(!Java.matches(Path::new("java")));
+
+        let java = Java;
+        let kind_ids = java.kind_ids();
+        assert_eq!(kind_ids.kind_count(), Java.grammar().node_kind_count());
+        assert!(kind_ids.contains("class_declaration"));
+        assert!(!kind_ids.contains("no_such_kind"));
+        assert_eq!(
+            kind_ids.resolve(["program", "program"]),
+            kind_ids.map()["program"]
+        );
+    }
+}
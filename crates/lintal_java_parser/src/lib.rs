@@ -1,8 +1,15 @@
 //! Java parser for lintal, built on tree-sitter-java.
+//!
+//! Other grammars plug in through [`Language`]; see [`language`].
+
+pub mod language;
+
+pub use language::{Java, KindIds, Language};
 
 use std::collections::HashMap;
-use std::sync::Arc;
-use std::sync::OnceLock;
+use std::ops::ControlFlow;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
 
 /// Result of parsing a Java source file.
 pub struct ParseResult {
@@ -10,52 +17,191 @@ pub struct ParseResult {
     pub source: Arc<str>,
 }
 
-/// Java parser wrapping tree-sitter.
-pub struct JavaParser {
+impl ParseResult {
+    /// Tell the tree that the bytes of `source` from `start` to `old_end`
+    /// were replaced with `new_text`, so that
+    /// [`Parser::parse_with_old_tree`] reuses what the edit didn't touch.
+    ///
+    /// `source` is the text as it was before this edit: the parsed source,
+    /// or that source with the edits since applied.
+    pub fn edit(&mut self, source: &str, start: usize, old_end: usize, new_text: &str) {
+        let start_position = point_at(source, start);
+        let new_end_position = match new_text.rfind('\n') {
+            Some(last) => tree_sitter::Point {
+                row: start_position.row + new_text.matches('\n').count(),
+                column: new_text.len() - last - 1,
+            },
+            None => tree_sitter::Point {
+                row: start_position.row,
+                column: start_position.column + new_text.len(),
+            },
+        };
+        self.tree.edit(&tree_sitter::InputEdit {
+            start_byte: start,
+            old_end_byte: old_end,
+            new_end_byte: start + new_text.len(),
+            start_position,
+            old_end_position: point_at(source, old_end),
+            new_end_position,
+        });
+    }
+
+    /// Tell the tree that its source was replaced with `new_source`, as one
+    /// edit spanning the bytes that differ, e.g. for a file changed on disk.
+    pub fn edit_to(&mut self, new_source: &str) {
+        let source = Arc::clone(&self.source);
+        let (old, new) = (source.as_bytes(), new_source.as_bytes());
+        let mut start = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+        while !source.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut suffix = old[start..]
+            .iter()
+            .rev()
+            .zip(new[start..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        while !source.is_char_boundary(old.len() - suffix) {
+            suffix -= 1;
+        }
+        self.edit(
+            &source,
+            start,
+            old.len() - suffix,
+            &new_source[start..new.len() - suffix],
+        );
+    }
+}
+
+/// The row and byte column of `offset` in `source`, as tree-sitter counts
+/// them.
+fn point_at(source: &str, offset: usize) -> tree_sitter::Point {
+    let before = &source.as_bytes()[..offset];
+    match before.iter().rposition(|&b| b == b'\n') {
+        Some(last) => tree_sitter::Point {
+            row: before.iter().filter(|&&b| b == b'\n').count(),
+            column: offset - last - 1,
+        },
+        None => tree_sitter::Point {
+            row: 0,
+            column: offset,
+        },
+    }
+}
+
+/// A tree-sitter parser for one [`Language`], Java unless created with
+/// [`Parser::for_language`].
+pub struct Parser {
     parser: tree_sitter::Parser,
 }
 
+/// The parser every rule's trees come from.
+pub type JavaParser = Parser;
+
 /// Return the tree-sitter Java language.
 pub fn java_language() -> tree_sitter::Language {
-    tree_sitter_java_orchard::LANGUAGE.into()
+    Java.grammar()
 }
 
 /// Return a map from node kind string to one or more kind IDs.
 pub fn java_kind_id_map() -> &'static HashMap<&'static str, Vec<u16>> {
-    static KIND_ID_MAP: OnceLock<HashMap<&'static str, Vec<u16>>> = OnceLock::new();
-
-    KIND_ID_MAP.get_or_init(|| {
-        let language = java_language();
-        let mut map: HashMap<&'static str, Vec<u16>> = HashMap::new();
-        let kind_count = language.node_kind_count();
+    java_kinds().map()
+}
 
-        for id in 0..kind_count {
-            let id = id as u16;
-            if let Some(kind) = language.node_kind_for_id(id) {
-                map.entry(kind).or_default().push(id);
-            }
-        }
+/// Resolve node kinds to their kind IDs, skipping kinds the grammar doesn't
+/// have.
+pub fn java_kind_ids<'a>(kinds: impl IntoIterator<Item = &'a str>) -> Vec<u16> {
+    java_kinds().resolve(kinds)
+}
 
-        map
-    })
+pub(crate) fn java_kinds() -> &'static KindIds {
+    static KIND_IDS: OnceLock<KindIds> = OnceLock::new();
+    KIND_IDS.get_or_init(|| KindIds::new(&java_language()))
 }
 
-impl JavaParser {
+impl Parser {
     /// Create a new Java parser.
     pub fn new() -> Self {
+        Self::for_language(&Java)
+    }
+
+    /// Create a parser for `language`.
+    pub fn for_language(language: &dyn Language) -> Self {
         let mut parser = tree_sitter::Parser::new();
         parser
-            .set_language(&tree_sitter_java_orchard::LANGUAGE.into())
-            .expect("Failed to load Java grammar");
+            .set_language(&language.grammar())
+            .unwrap_or_else(|err| panic!("Failed to load the {} grammar: {err}", language.name()));
         Self { parser }
     }
 
     /// Parse Java source code into a syntax tree.
+    ///
+    /// The result holds a copy of `source`; use
+    /// [`parse_shared`](Self::parse_shared) to avoid that for large files.
     pub fn parse(&mut self, source: &str) -> Option<ParseResult> {
-        let tree = self.parser.parse(source, None)?;
+        self.parse_shared(&Arc::from(source))
+    }
+
+    /// Parse Java source code into a syntax tree that shares `source`
+    /// rather than copying it.
+    pub fn parse_shared(&mut self, source: &Arc<str>) -> Option<ParseResult> {
+        let tree = self.parser.parse(source.as_bytes(), None)?;
+        Some(ParseResult {
+            tree,
+            source: Arc::clone(source),
+        })
+    }
+
+    /// Like [`parse_shared`](Self::parse_shared), but give up and return
+    /// `None` if parsing takes longer than `timeout`.
+    pub fn parse_shared_within(
+        &mut self,
+        source: &Arc<str>,
+        timeout: Duration,
+    ) -> Option<ParseResult> {
+        self.parse_incrementally_within(source, None, timeout)
+    }
+
+    /// Like [`parse_shared_within`](Self::parse_shared_within), reusing
+    /// what `old_tree` says the edits since didn't touch; see
+    /// [`ParseResult::edit`].
+    pub fn reparse_shared_within(
+        &mut self,
+        source: &Arc<str>,
+        old_tree: &tree_sitter::Tree,
+        timeout: Duration,
+    ) -> Option<ParseResult> {
+        self.parse_incrementally_within(source, Some(old_tree), timeout)
+    }
+
+    fn parse_incrementally_within(
+        &mut self,
+        source: &Arc<str>,
+        old_tree: Option<&tree_sitter::Tree>,
+        timeout: Duration,
+    ) -> Option<ParseResult> {
+        let deadline = Instant::now() + timeout;
+        let mut progress = |_: &tree_sitter::ParseState| {
+            if Instant::now() < deadline {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        };
+        let bytes = source.as_bytes();
+        let tree = self.parser.parse_with_options(
+            &mut |offset, _| bytes.get(offset..).unwrap_or_default(),
+            old_tree,
+            Some(tree_sitter::ParseOptions::new().progress_callback(&mut progress)),
+        );
+        let Some(tree) = tree else {
+            // Otherwise the next parse would resume this one
+            self.parser.reset();
+            return None;
+        };
         Some(ParseResult {
             tree,
-            source: source.into(),
+            source: Arc::clone(source),
         })
     }
 
@@ -65,15 +211,25 @@ impl JavaParser {
         source: &str,
         old_tree: &tree_sitter::Tree,
     ) -> Option<ParseResult> {
-        let tree = self.parser.parse(source, Some(old_tree))?;
+        self.reparse_shared(&Arc::from(source), old_tree)
+    }
+
+    /// Like [`parse_with_old_tree`](Self::parse_with_old_tree), sharing
+    /// `source` rather than copying it.
+    pub fn reparse_shared(
+        &mut self,
+        source: &Arc<str>,
+        old_tree: &tree_sitter::Tree,
+    ) -> Option<ParseResult> {
+        let tree = self.parser.parse(source.as_bytes(), Some(old_tree))?;
         Some(ParseResult {
             tree,
-            source: source.into(),
+            source: Arc::clone(source),
         })
     }
 }
 
-impl Default for JavaParser {
+impl Default for Parser {
     fn default() -> Self {
         Self::new()
     }
@@ -97,6 +253,99 @@ public class Hello {
         assert_eq!(result.tree.root_node().kind(), "program");
     }
 
+    #[test]
+    fn test_java_kind_ids() {
+        let ids = java_kind_ids(["program", "identifier", "program", "no_such_kind"]);
+        let language = java_language();
+        let kinds: Vec<&str> = ids
+            .iter()
+            .filter_map(|&id| language.node_kind_for_id(id))
+            .collect();
+        assert!(kinds.contains(&"program"));
+        assert!(kinds.contains(&"identifier"));
+        assert!(
+            kinds
+                .iter()
+                .all(|kind| ["program", "identifier"].contains(kind))
+        );
+        assert!(java_kind_ids(["no_such_kind"]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_shared() {
+        let source: Arc<str> = Arc::from("class A {}");
+        let result = JavaParser::new().parse_shared(&source).unwrap();
+        assert!(Arc::ptr_eq(&result.source, &source));
+    }
+
+    #[test]
+    fn test_parse_shared_within() {
+        let source: Arc<str> =
+            format!("class A {{\n{}}}\n", "    int x = 1;\n".repeat(10_000)).into();
+        let mut parser = JavaParser::new();
+        assert!(
+            parser
+                .parse_shared_within(&source, Duration::ZERO)
+                .is_none()
+        );
+
+        // A cancelled parse doesn't leak into the next one
+        let small: Arc<str> = Arc::from("class B {}");
+        let result = parser
+            .parse_shared_within(&small, Duration::from_secs(60))
+            .unwrap();
+        assert_eq!(
+            result.tree.root_node().to_sexp(),
+            parser
+                .parse("class B {}")
+                .unwrap()
+                .tree
+                .root_node()
+                .to_sexp()
+        );
+    }
+
+    #[test]
+    fn test_parse_with_old_tree_after_edits() {
+        let mut parser = JavaParser::new();
+        let before = "class A {\n    int x;\n}\n";
+        let mut result = parser.parse(before).unwrap();
+
+        // Rename x to total, then add a method on a line of its own
+        let mut source = before.to_string();
+        let at = source.find('x').unwrap();
+        result.edit(&source, at, at + 1, "total");
+        source.replace_range(at..=at, "total");
+        let at = source.rfind('}').unwrap();
+        result.edit(&source, at, at, "    void run() {}\n");
+        source.insert_str(at, "    void run() {}\n");
+
+        let reparsed = parser.parse_with_old_tree(&source, &result.tree).unwrap();
+        assert_eq!(
+            reparsed.tree.root_node().to_sexp(),
+            parser.parse(&source).unwrap().tree.root_node().to_sexp()
+        );
+    }
+
+    #[test]
+    fn test_reparse_after_edit_to() {
+        let mut parser = JavaParser::new();
+        let mut result = parser
+            .parse("class A {\n    String s = \"é\";\n}\n")
+            .unwrap();
+
+        // Differs from the first byte of the é to the end of the field
+        let after: Arc<str> = "class A {\n    String s = \"è\";\n    int n;\n}\n".into();
+        result.edit_to(&after);
+        let reparsed = parser
+            .reparse_shared_within(&after, &result.tree, Duration::from_secs(10))
+            .unwrap();
+        assert_eq!(
+            reparsed.tree.root_node().to_sexp(),
+            parser.parse(&after).unwrap().tree.root_node().to_sexp()
+        );
+    }
+
     #[test]
     fn test_parse_record() {
         let mut parser = JavaParser::new();
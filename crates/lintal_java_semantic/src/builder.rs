@@ -0,0 +1,668 @@
+//! Builds a [`SemanticModel`] from a Java CST.
+
+use lintal_java_cst::CstNode;
+use lintal_text_size::TextRange;
+
+use crate::types::count_dimensions;
+use crate::{
+    Access, JavaType, Reference, ReferenceKind, Scope, ScopeId, ScopeKind, SemanticModel, Symbol,
+    SymbolId, SymbolKind,
+};
+
+/// Node kinds that declare a named type.
+pub(crate) const TYPE_DECLARATIONS: &[&str] = &[
+    "class_declaration",
+    "interface_declaration",
+    "enum_declaration",
+    "record_declaration",
+    "annotation_type_declaration",
+];
+
+/// Node kinds that never contain variable references (types, names, labels).
+const SKIPPED_KINDS: &[&str] = &[
+    "package_declaration",
+    "import_declaration",
+    "module_declaration",
+    "type_identifier",
+    "scoped_type_identifier",
+    "scoped_identifier",
+    "generic_type",
+    "type_parameters",
+    "type_arguments",
+    "annotation_type_element_declaration",
+    "break_statement",
+    "continue_statement",
+    "line_comment",
+    "block_comment",
+    "this",
+    "super",
+];
+
+pub(crate) struct SemanticBuilder<'a> {
+    source: &'a str,
+    model: SemanticModel,
+    current: ScopeId,
+    /// `var` declarations whose type is inferred once references are
+    /// resolved: from the initializer, or from the element type of the
+    /// iterated expression in an enhanced `for`.
+    inferred: Vec<(SymbolId, CstNode<'a>, bool)>,
+}
+
+impl<'a> SemanticBuilder<'a> {
+    pub(crate) fn new(source: &'a str, root: &CstNode<'a>) -> Self {
+        let mut model = SemanticModel::default();
+        model.scopes.push(Scope::new(
+            ScopeKind::CompilationUnit,
+            None,
+            root.range(),
+            true,
+        ));
+        Self {
+            source,
+            model,
+            current: ScopeId::ROOT,
+            inferred: Vec::new(),
+        }
+    }
+
+    pub(crate) fn build(mut self, root: &CstNode<'a>) -> SemanticModel {
+        for child in root.named_children() {
+            self.visit(&child);
+        }
+
+        let resolved: Vec<Option<SymbolId>> = self
+            .model
+            .references
+            .iter()
+            .map(|reference| self.model.resolve_reference(reference))
+            .collect();
+        for (reference, symbol) in self.model.references.iter_mut().zip(resolved) {
+            reference.resolved = symbol;
+        }
+        self.model.reference_index = self
+            .model
+            .references
+            .iter()
+            .enumerate()
+            .map(|(index, reference)| (reference.range, index))
+            .collect();
+
+        // In declaration order, so `var b = a;` sees the type of an earlier `var a`
+        for (id, value, is_element) in std::mem::take(&mut self.inferred) {
+            let ty = self.model.expression_type(&value).and_then(|ty| match ty {
+                JavaType::Array(element) if is_element => Some(*element),
+                _ if is_element => None,
+                JavaType::Null => None,
+                ty => Some(ty),
+            });
+            self.model.symbols[id.index()].ty = ty;
+        }
+
+        self.model
+    }
+
+    fn visit(&mut self, node: &CstNode<'a>) {
+        let kind = node.kind();
+        if SKIPPED_KINDS.contains(&kind) {
+            return;
+        }
+
+        match kind {
+            _ if TYPE_DECLARATIONS.contains(&kind) => self.visit_type_declaration(node),
+            // Named type bodies are handled by `visit_type_declaration`, so
+            // any class body reached here belongs to an anonymous class
+            "class_body" => {
+                self.push_scope(ScopeKind::Type, node.range(), false);
+                self.visit_type_body(node, false, None);
+                self.pop_scope();
+            }
+            "method_declaration"
+            | "constructor_declaration"
+            | "compact_constructor_declaration" => {
+                self.visit_method(node);
+            }
+            "lambda_expression" => self.visit_lambda(node),
+            "block"
+            | "constructor_body"
+            | "switch_block"
+            | "for_statement"
+            | "catch_clause"
+            | "try_with_resources_statement" => {
+                self.push_scope(ScopeKind::Block, node.range(), false);
+                self.visit_children(node);
+                self.pop_scope();
+            }
+            "static_initializer" => {
+                self.push_scope(ScopeKind::Block, node.range(), true);
+                self.visit_children(node);
+                self.pop_scope();
+            }
+            "enhanced_for_statement" => {
+                self.push_scope(ScopeKind::Block, node.range(), false);
+                if let Some(name) = node.child_by_field_name("name") {
+                    let ty = declared_type(node);
+                    let id = self.declare(
+                        &name,
+                        node,
+                        SymbolKind::LocalVariable,
+                        false,
+                        is_final(node),
+                        ty,
+                    );
+                    if is_var(node)
+                        && let Some(value) = node.child_by_field_name("value")
+                    {
+                        self.inferred.push((id, value, true));
+                    }
+                }
+                self.visit_field(node, "value");
+                self.visit_field(node, "body");
+                self.pop_scope();
+            }
+            "local_variable_declaration" => {
+                self.visit_variable_declaration(node, Some(SymbolKind::LocalVariable));
+            }
+            "field_declaration" | "constant_declaration" => {
+                self.visit_variable_declaration(node, None);
+            }
+            "catch_formal_parameter" => {
+                if let Some(name) = node.child_by_field_name("name") {
+                    // Multi-catch parameters have a union type
+                    let ty = node
+                        .named_children()
+                        .find(|child| child.kind() == "catch_type")
+                        .and_then(|catch_type| {
+                            let types: Vec<_> = catch_type.named_children().collect();
+                            match types.as_slice() {
+                                [ty] => JavaType::from_type_node(ty),
+                                _ => None,
+                            }
+                        });
+                    self.declare(
+                        &name,
+                        node,
+                        SymbolKind::Parameter,
+                        false,
+                        is_final(node),
+                        ty,
+                    );
+                }
+            }
+            "resource" => {
+                if let Some(name) = node.child_by_field_name("name") {
+                    // Resources are implicitly final
+                    let ty = declared_type(node);
+                    let id = self.declare(&name, node, SymbolKind::LocalVariable, false, true, ty);
+                    if is_var(node)
+                        && let Some(value) = node.child_by_field_name("value")
+                    {
+                        self.inferred.push((id, value, false));
+                    }
+                    self.visit_field(node, "value");
+                } else {
+                    self.visit_children(node);
+                }
+            }
+            "instanceof_expression" => {
+                self.visit_field(node, "left");
+                if let Some(name) = node.child_by_field_name("name") {
+                    let ty = node
+                        .child_by_field_name("right")
+                        .and_then(|ty| JavaType::from_type_node(&ty));
+                    self.declare(
+                        &name,
+                        node,
+                        SymbolKind::LocalVariable,
+                        false,
+                        is_final(node),
+                        ty,
+                    );
+                }
+                self.visit_field(node, "pattern");
+            }
+            "type_pattern" => {
+                if let Some(name) = node.named_children().last()
+                    && name.kind() == "identifier"
+                {
+                    let ty = node
+                        .named_children()
+                        .find(|child| child.kind() != "modifiers")
+                        .and_then(|ty| JavaType::from_type_node(&ty));
+                    self.declare(
+                        &name,
+                        node,
+                        SymbolKind::LocalVariable,
+                        false,
+                        is_final(node),
+                        ty,
+                    );
+                }
+            }
+            "enum_constant" => {
+                self.visit_field(node, "arguments");
+                self.visit_field(node, "body");
+            }
+            "assignment_expression" => {
+                // Compound operators (`+=`, `<<=`, ...) also read the target
+                let compound = node
+                    .children()
+                    .any(|child| child.kind() != "=" && child.kind().ends_with('='));
+                let access = if compound {
+                    Access::ReadWrite
+                } else {
+                    Access::Write
+                };
+                if let Some(left) = node.child_by_field_name("left") {
+                    self.visit_target(&left, access);
+                }
+                self.visit_field(node, "right");
+            }
+            "update_expression" => {
+                if let Some(operand) = node.named_children().next() {
+                    self.visit_target(&operand, Access::ReadWrite);
+                }
+            }
+            "method_invocation" => {
+                let name = node.child_by_field_name("name");
+                match node.child_by_field_name("object") {
+                    None => {
+                        if let Some(name) = name {
+                            self.reference(&name, ReferenceKind::Method, Access::Read, false);
+                        }
+                    }
+                    Some(object) if object.kind() == "this" => {
+                        if let Some(name) = name {
+                            self.reference(&name, ReferenceKind::Method, Access::Read, true);
+                        }
+                    }
+                    Some(object) => self.visit(&object),
+                }
+                self.visit_field(node, "arguments");
+            }
+            "field_access" => self.visit_target(node, Access::Read),
+            "method_reference" => {
+                // Only the qualifier can be a variable; the name after `::` is a method
+                if let Some(qualifier) = node.named_children().next() {
+                    self.visit(&qualifier);
+                }
+            }
+            "labeled_statement" => {
+                for child in node.named_children().filter(|c| c.kind() != "identifier") {
+                    self.visit(&child);
+                }
+            }
+            "annotation" | "marker_annotation" => self.visit_field(node, "arguments"),
+            "element_value_pair" => self.visit_field(node, "value"),
+            "identifier" => self.reference(node, ReferenceKind::Variable, Access::Read, false),
+            _ => self.visit_children(node),
+        }
+    }
+
+    fn visit_children(&mut self, node: &CstNode<'a>) {
+        for child in node.named_children() {
+            self.visit(&child);
+        }
+    }
+
+    fn visit_field(&mut self, node: &CstNode<'a>, field: &str) {
+        if let Some(child) = node.child_by_field_name(field) {
+            self.visit(&child);
+        }
+    }
+
+    /// Visit an expression that is written to (or read), recording the access.
+    fn visit_target(&mut self, node: &CstNode<'a>, access: Access) {
+        match node.kind() {
+            "identifier" => self.reference(node, ReferenceKind::Variable, access, false),
+            "parenthesized_expression" => {
+                if let Some(inner) = node.named_children().next() {
+                    self.visit_target(&inner, access);
+                }
+            }
+            "field_access" => match node.child_by_field_name("object") {
+                Some(object) if object.kind() == "this" => {
+                    if let Some(field) = node.child_by_field_name("field") {
+                        self.reference(&field, ReferenceKind::Variable, access, true);
+                    }
+                }
+                // `obj.field` only references `obj`
+                Some(object) => self.visit(&object),
+                None => {}
+            },
+            _ => self.visit(node),
+        }
+    }
+
+    fn visit_type_declaration(&mut self, node: &CstNode<'a>) {
+        let kind = node.kind();
+        let name = node.child_by_field_name("name");
+        let ty = name
+            .as_ref()
+            .map(|name| JavaType::Class(self.text(name.range()).to_string()));
+        if let Some(name) = &name {
+            self.declare(
+                name,
+                node,
+                SymbolKind::Type,
+                has_modifier(node, "static"),
+                is_final(node),
+                ty.clone(),
+            );
+        }
+
+        // Enums, records, interfaces and annotations are implicitly static
+        let is_static = kind != "class_declaration" || has_modifier(node, "static");
+        self.push_scope(ScopeKind::Type, node.range(), is_static);
+
+        if kind == "record_declaration"
+            && let Some(parameters) = node.child_by_field_name("parameters")
+        {
+            for component in parameters.named_children() {
+                if let Some(name) = component.child_by_field_name("name") {
+                    let ty = declared_type(&component);
+                    self.declare(
+                        &name,
+                        &component,
+                        SymbolKind::RecordComponent,
+                        false,
+                        true,
+                        ty,
+                    );
+                }
+            }
+        }
+
+        if let Some(body) = node.child_by_field_name("body") {
+            let is_interface = matches!(
+                kind,
+                "interface_declaration" | "annotation_type_declaration"
+            );
+            self.visit_type_body(&body, is_interface, ty.as_ref());
+        }
+
+        self.pop_scope();
+    }
+
+    /// Declare all members of a type body up front (members are visible
+    /// regardless of declaration order), then visit their contents.
+    ///
+    /// `owner` is the type being declared, which is the type of its enum constants.
+    fn visit_type_body(
+        &mut self,
+        body: &CstNode<'a>,
+        is_interface: bool,
+        owner: Option<&JavaType>,
+    ) {
+        self.declare_members(body, is_interface, owner);
+        self.visit_members(body);
+    }
+
+    fn declare_members(
+        &mut self,
+        body: &CstNode<'a>,
+        is_interface: bool,
+        owner: Option<&JavaType>,
+    ) {
+        for member in body.named_children() {
+            match member.kind() {
+                "field_declaration" | "constant_declaration" => {
+                    let is_static = is_interface || has_modifier(&member, "static");
+                    let is_final = is_interface || is_final(&member);
+                    let ty = declared_type(&member);
+                    for declarator in declarators(&member) {
+                        if let Some(name) = declarator.child_by_field_name("name") {
+                            let ty = declarator_type(ty.clone(), &declarator);
+                            self.declare(
+                                &name,
+                                &member,
+                                SymbolKind::Field,
+                                is_static,
+                                is_final,
+                                ty,
+                            );
+                        }
+                    }
+                }
+                "enum_constant" => {
+                    if let Some(name) = member.child_by_field_name("name") {
+                        self.declare(
+                            &name,
+                            &member,
+                            SymbolKind::EnumConstant,
+                            true,
+                            true,
+                            owner.cloned(),
+                        );
+                    }
+                }
+                "method_declaration" => {
+                    if let Some(name) = member.child_by_field_name("name") {
+                        let is_static = has_modifier(&member, "static");
+                        let ty = declared_type(&member);
+                        self.declare(&name, &member, SymbolKind::Method, is_static, false, ty);
+                    }
+                }
+                "enum_body_declarations" => self.declare_members(&member, is_interface, owner),
+                _ => {}
+            }
+        }
+    }
+
+    fn visit_members(&mut self, body: &CstNode<'a>) {
+        for member in body.named_children() {
+            if member.kind() == "enum_body_declarations" {
+                self.visit_members(&member);
+            } else {
+                self.visit(&member);
+            }
+        }
+    }
+
+    fn visit_method(&mut self, node: &CstNode<'a>) {
+        self.push_scope(
+            ScopeKind::Method,
+            node.range(),
+            has_modifier(node, "static"),
+        );
+
+        if let Some(parameters) = node.child_by_field_name("parameters") {
+            self.declare_parameters(&parameters);
+        }
+        self.visit_field(node, "body");
+
+        self.pop_scope();
+    }
+
+    fn visit_lambda(&mut self, node: &CstNode<'a>) {
+        self.push_scope(ScopeKind::Lambda, node.range(), false);
+
+        if let Some(parameters) = node.child_by_field_name("parameters") {
+            match parameters.kind() {
+                "identifier" => {
+                    self.declare(
+                        &parameters,
+                        &parameters,
+                        SymbolKind::Parameter,
+                        false,
+                        false,
+                        None,
+                    );
+                }
+                "inferred_parameters" => {
+                    for name in parameters.named_children() {
+                        self.declare(&name, &name, SymbolKind::Parameter, false, false, None);
+                    }
+                }
+                _ => self.declare_parameters(&parameters),
+            }
+        }
+        self.visit_field(node, "body");
+
+        self.pop_scope();
+    }
+
+    /// Declare the parameters in a `formal_parameters` node.
+    fn declare_parameters(&mut self, parameters: &CstNode<'a>) {
+        for parameter in parameters.named_children() {
+            let (name, ty) = match parameter.kind() {
+                "formal_parameter" => (
+                    parameter.child_by_field_name("name"),
+                    declarator_type(declared_type(&parameter), &parameter),
+                ),
+                // Varargs: `String... args` wraps the name in a declarator and
+                // has no `type` field
+                "spread_parameter" => (
+                    declarators(&parameter)
+                        .first()
+                        .and_then(|d| d.child_by_field_name("name")),
+                    parameter
+                        .named_children()
+                        .find_map(|child| JavaType::from_type_node(&child))
+                        .map(|ty| ty.array_of(1)),
+                ),
+                _ => (None, None),
+            };
+            if let Some(name) = name {
+                self.declare(
+                    &name,
+                    &parameter,
+                    SymbolKind::Parameter,
+                    false,
+                    is_final(&parameter),
+                    ty,
+                );
+            }
+        }
+    }
+
+    /// Visit a variable declaration, declaring its names as `kind` (fields
+    /// are already declared by `declare_members`) and visiting initializers.
+    fn visit_variable_declaration(&mut self, node: &CstNode<'a>, kind: Option<SymbolKind>) {
+        let is_final = is_final(node);
+        let ty = declared_type(node);
+        for declarator in declarators(node) {
+            if let Some(kind) = kind
+                && let Some(name) = declarator.child_by_field_name("name")
+            {
+                let ty = declarator_type(ty.clone(), &declarator);
+                let id = self.declare(&name, node, kind, false, is_final, ty);
+                if is_var(node)
+                    && let Some(value) = declarator.child_by_field_name("value")
+                {
+                    self.inferred.push((id, value, false));
+                }
+            }
+            self.visit_field(&declarator, "value");
+        }
+    }
+
+    fn declare(
+        &mut self,
+        name: &CstNode<'a>,
+        declaration: &CstNode<'a>,
+        kind: SymbolKind,
+        is_static: bool,
+        is_final: bool,
+        ty: Option<JavaType>,
+    ) -> SymbolId {
+        let id = SymbolId::new(self.model.symbols.len());
+        self.model.symbols.push(Symbol {
+            name: self.text(name.range()).to_string(),
+            kind,
+            range: name.range(),
+            declaration: declaration.range(),
+            scope: self.current,
+            is_static,
+            is_final,
+            ty,
+        });
+        self.model.scopes[self.current.index()].symbols.push(id);
+        self.model.declarations.insert(name.range(), id);
+        id
+    }
+
+    fn reference(
+        &mut self,
+        name: &CstNode<'a>,
+        kind: ReferenceKind,
+        access: Access,
+        qualified_with_this: bool,
+    ) {
+        self.model.references.push(Reference {
+            name: self.text(name.range()).to_string(),
+            kind,
+            range: name.range(),
+            scope: self.current,
+            access,
+            qualified_with_this,
+            resolved: None,
+        });
+    }
+
+    fn push_scope(&mut self, kind: ScopeKind, range: TextRange, is_static: bool) {
+        let id = ScopeId::new(self.model.scopes.len());
+        self.model
+            .scopes
+            .push(Scope::new(kind, Some(self.current), range, is_static));
+        self.current = id;
+    }
+
+    fn pop_scope(&mut self) {
+        self.current = self.model.scopes[self.current.index()]
+            .parent
+            .unwrap_or(ScopeId::ROOT);
+    }
+
+    fn text(&self, range: TextRange) -> &'a str {
+        &self.source[range]
+    }
+}
+
+pub(crate) fn declarators<'a>(node: &CstNode<'a>) -> Vec<CstNode<'a>> {
+    node.named_children()
+        .filter(|child| child.kind() == "variable_declarator")
+        .collect()
+}
+
+/// The type in the `type` field of a declaration, if it is written out.
+fn declared_type(node: &CstNode) -> Option<JavaType> {
+    node.child_by_field_name("type")
+        .and_then(|ty| JavaType::from_type_node(&ty))
+}
+
+/// Add the C-style array brackets after a declared name (`int a[]`).
+fn declarator_type(ty: Option<JavaType>, declarator: &CstNode) -> Option<JavaType> {
+    match declarator.child_by_field_name("dimensions") {
+        Some(dimensions) => ty.map(|ty| ty.array_of(count_dimensions(&dimensions))),
+        None => ty,
+    }
+}
+
+/// Whether a declaration uses `var` instead of a type.
+fn is_var(node: &CstNode) -> bool {
+    node.child_by_field_name("type")
+        .is_some_and(|ty| ty.kind() == "type_identifier" && ty.text() == "var")
+}
+
+pub(crate) fn has_modifier(node: &CstNode, modifier: &str) -> bool {
+    node.children().any(|child| match child.kind() {
+        "modifiers" => child.children().any(|m| modifier_kind(&m) == modifier),
+        _ => modifier_kind(&child) == modifier,
+    })
+}
+
+/// The keyword of a modifier, unwrapping the grammar's `visibility` and
+/// `modifier` wrapper nodes.
+fn modifier_kind<'a>(node: &CstNode<'a>) -> &'a str {
+    match node.kind() {
+        "visibility" | "modifier" => node
+            .children()
+            .next()
+            .map_or(node.kind(), |child| child.kind()),
+        other => other,
+    }
+}
+
+fn is_final(node: &CstNode) -> bool {
+    has_modifier(node, "final")
+}
@@ -0,0 +1,253 @@
+//! Duplicated code across the files of a project.
+//!
+//! Each file is reduced to a stream of token fingerprints, leaving out
+//! whitespace, comments and the package and import declarations. Runs of at
+//! least `min_tokens` tokens that appear more than once are found by hashing
+//! every window of that many tokens, then grown as far as the copies stay
+//! equal, so each clone is reported once at its full length.
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use lintal_java_cst::{CstNode, TokenStream};
+use lintal_text_size::TextRange;
+
+/// Windows shared by more places than this are not compared pairwise. They
+/// are runs of the same few tokens, such as the rows of a table of numbers,
+/// and comparing them all would be quadratic.
+const MAX_OCCURRENCES: usize = 64;
+
+/// A token of a file, as compared for duplicates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenFingerprint {
+    /// Hash of the token's kind and text.
+    pub hash: u64,
+    pub range: TextRange,
+    /// Zero-based lines the token starts and ends on.
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+/// The fingerprints of the tokens of the file under `root`.
+pub(crate) fn fingerprints(root: &CstNode) -> Vec<TokenFingerprint> {
+    let source = root.source;
+    // Files import much the same things; those aren't duplicated code
+    let header_end = root
+        .named_children()
+        .filter(|child| matches!(child.kind(), "package_declaration" | "import_declaration"))
+        .map(|child| child.range().end())
+        .max()
+        .unwrap_or_default();
+
+    let mut line = 0;
+    let mut counted_to = 0;
+    let mut count_lines_to = |offset: usize| {
+        line += source[counted_to..offset].matches('\n').count();
+        counted_to = offset;
+        line
+    };
+    TokenStream::build(root)
+        .tokens()
+        .iter()
+        .filter(|token| !token.is_comment() && token.range.start() >= header_end)
+        .map(|token| {
+            let mut hasher = DefaultHasher::new();
+            token.kind.hash(&mut hasher);
+            token.text(source).hash(&mut hasher);
+            TokenFingerprint {
+                hash: hasher.finish(),
+                range: token.range,
+                start_line: count_lines_to(token.range.start().to_usize()),
+                end_line: count_lines_to(token.range.end().to_usize()),
+            }
+        })
+        .collect()
+}
+
+/// Code in one file that is also in another file or elsewhere in the same
+/// one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Duplicate {
+    /// The duplicated code in this file, from its first token to its last.
+    pub range: TextRange,
+    /// How many lines the duplicated code spans in this file.
+    pub lines: usize,
+    pub tokens: usize,
+    /// Index of the file with the other copy in
+    /// [`ProjectIndex::files`](crate::ProjectIndex::files).
+    pub other: usize,
+    /// One-based line the other copy starts on.
+    pub other_line: usize,
+}
+
+/// Every clone of at least `min_tokens` tokens among `files`, listed under
+/// each file it is in, in source order.
+pub(crate) fn find(files: &[&[TokenFingerprint]], min_tokens: usize) -> Vec<Vec<Duplicate>> {
+    let mut found = vec![Vec::new(); files.len()];
+    let min_tokens = min_tokens.max(1);
+
+    let mut windows: HashMap<u64, Vec<(usize, usize)>> = HashMap::new();
+    for (file, tokens) in files.iter().enumerate() {
+        for (start, hash) in window_hashes(tokens, min_tokens) {
+            windows.entry(hash).or_default().push((file, start));
+        }
+    }
+
+    for places in windows.values() {
+        if places.len() < 2 || places.len() > MAX_OCCURRENCES {
+            continue;
+        }
+        for (i, &(a, a_start)) in places.iter().enumerate() {
+            for &(b, b_start) in &places[i + 1..] {
+                let (a_tokens, b_tokens) = (files[a], files[b]);
+                // Copies that go on before here were found from where they start
+                if a_start > 0
+                    && b_start > 0
+                    && a_tokens[a_start - 1].hash == b_tokens[b_start - 1].hash
+                {
+                    continue;
+                }
+                // Within a file a copy ends where the other begins
+                let limit = if a == b {
+                    b_start.abs_diff(a_start)
+                } else {
+                    usize::MAX
+                };
+                let len = a_tokens[a_start..]
+                    .iter()
+                    .zip(&b_tokens[b_start..])
+                    .take(limit)
+                    .take_while(|(a, b)| a.hash == b.hash)
+                    .count();
+                // Shorter means the hashes of the windows collided
+                if len < min_tokens {
+                    continue;
+                }
+                let a_copy = &a_tokens[a_start..a_start + len];
+                let b_copy = &b_tokens[b_start..b_start + len];
+                found[a].push(duplicate(a_copy, b, b_copy));
+                found[b].push(duplicate(b_copy, a, a_copy));
+            }
+        }
+    }
+
+    for duplicates in &mut found {
+        duplicates.sort_by_key(|duplicate| {
+            (
+                duplicate.range.start(),
+                duplicate.other,
+                duplicate.other_line,
+            )
+        });
+    }
+    found
+}
+
+fn duplicate(
+    copy: &[TokenFingerprint],
+    other: usize,
+    other_copy: &[TokenFingerprint],
+) -> Duplicate {
+    let (first, last) = (copy[0], copy[copy.len() - 1]);
+    Duplicate {
+        range: TextRange::new(first.range.start(), last.range.end()),
+        lines: last.end_line - first.start_line + 1,
+        tokens: copy.len(),
+        other,
+        other_line: other_copy[0].start_line + 1,
+    }
+}
+
+/// A polynomial rolling hash of each window of `len` tokens, with where it
+/// starts.
+fn window_hashes(tokens: &[TokenFingerprint], len: usize) -> Vec<(usize, u64)> {
+    const BASE: u64 = 0x100_0000_01b3;
+    if tokens.len() < len {
+        return Vec::new();
+    }
+    // BASE^(len - 1), to take the token leaving the window back out
+    let leading = (1..len).fold(1u64, |power, _| power.wrapping_mul(BASE));
+
+    let mut hash = tokens[..len].iter().fold(0u64, |hash, token| {
+        hash.wrapping_mul(BASE).wrapping_add(token.hash)
+    });
+    let mut hashes = Vec::with_capacity(tokens.len() - len + 1);
+    hashes.push((0, hash));
+    for start in 1..=tokens.len() - len {
+        hash = hash
+            .wrapping_sub(tokens[start - 1].hash.wrapping_mul(leading))
+            .wrapping_mul(BASE)
+            .wrapping_add(tokens[start + len - 1].hash);
+        hashes.push((start, hash));
+    }
+    hashes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FileSummary, ProjectIndex};
+    use lintal_java_parser::JavaParser;
+    use std::path::Path;
+
+    fn summarize(path: &str, source: &str) -> FileSummary {
+        let result = JavaParser::new().parse(source).unwrap();
+        FileSummary::build(path, &CstNode::new(result.tree.root_node(), source))
+    }
+
+    const BODY: &str = "
+        int total = 0;
+        for (int value : values) {
+            if (value > 0) {
+                total += value * 2;
+            }
+        }
+        return total;
+";
+
+    #[test]
+    fn test_finds_clones_across_files() {
+        let a = format!(
+            "package a;\n\nimport java.util.List;\n\nclass A {{\n    int sum(int[] values) {{{BODY}    }}\n}}\n"
+        );
+        let b = format!(
+            "package b;\n\nclass B {{\n    // Same as A\n    int add(int[] values) {{\n{BODY}    }}\n\n    void other() {{}}\n}}\n"
+        );
+        let index = ProjectIndex::new([summarize("A.java", &a), summarize("B.java", &b)]);
+
+        let in_a = index.duplicates_of(Path::new("A.java"), 20);
+        let [duplicate] = in_a.as_slice() else {
+            panic!("expected one duplicate: {in_a:?}");
+        };
+        // From after the method names to the end of the methods
+        assert!(a[duplicate.range].starts_with("(int[] values) {"));
+        assert!(a[duplicate.range].ends_with("return total;\n    }"));
+        assert_eq!(duplicate.lines, 9);
+        assert_eq!(duplicate.other, 1);
+        assert_eq!(duplicate.other_line, 5);
+
+        let in_b = index.duplicates_of(Path::new("B.java"), 20);
+        assert_eq!(in_b.len(), 1);
+        assert_eq!(in_b[0].other, 0);
+        assert_eq!(in_b[0].other_line, 6);
+        assert_eq!(in_b[0].tokens, duplicate.tokens);
+
+        // Nothing that long is duplicated
+        assert!(index.duplicates_of(Path::new("A.java"), 100).is_empty());
+    }
+
+    #[test]
+    fn test_finds_clones_within_a_file() {
+        let source = format!(
+            "class A {{\n    int one(int[] values) {{{BODY}    }}\n\n    int two(int[] values) {{{BODY}    }}\n}}\n"
+        );
+        let index = ProjectIndex::new([summarize("A.java", &source)]);
+        let found = index.duplicates_of(Path::new("A.java"), 20);
+        let lines: Vec<(usize, usize)> = found
+            .iter()
+            .map(|duplicate| (duplicate.lines, duplicate.other_line))
+            .collect();
+        // Each copy points at the other
+        assert_eq!(lines, [(9, 12), (9, 2)]);
+    }
+}
@@ -0,0 +1,555 @@
+//! Semantic model for Java source files.
+//!
+//! Builds the lexical scopes of a file (compilation unit, types, methods,
+//! lambdas and blocks), the symbols declared in each of them, and the
+//! references to simple names, resolved to their declarations where the
+//! declaration is in the same file.
+//!
+//! Resolution is purely lexical: members are visible throughout their type,
+//! locals only after their declaration, and names that are not declared in
+//! the file (inherited members, imports, other classes) stay unresolved.
+//!
+//! On top of that, [`SemanticModel::expression_type`] gives the static type of
+//! an expression where it can be worked out from this file alone.
+//!
+//! Across files, a [`ProjectIndex`] combines per-file [`FileSummary`]s into
+//! packages, types and members, for checks that need to see the whole project,
+//! and finds the code duplicated between them.
+
+mod builder;
+pub mod duplicates;
+mod project;
+mod scope;
+mod symbol;
+mod types;
+
+pub use duplicates::Duplicate;
+pub use project::{FileSummary, MemberSummary, ProjectIndex, TypeKind, TypeSummary, Visibility};
+pub use scope::{Scope, ScopeId, ScopeKind};
+pub use symbol::{Access, Reference, ReferenceKind, Symbol, SymbolId, SymbolKind};
+pub use types::{JavaType, Primitive};
+
+use std::collections::HashMap;
+
+use lintal_java_cst::CstNode;
+use lintal_text_size::{TextRange, TextSize};
+
+use crate::builder::SemanticBuilder;
+
+/// Scopes, symbols and resolved references for a single file.
+#[derive(Debug, Default)]
+pub struct SemanticModel {
+    scopes: Vec<Scope>,
+    symbols: Vec<Symbol>,
+    references: Vec<Reference>,
+    /// Symbols by the range of their declared name.
+    declarations: HashMap<TextRange, SymbolId>,
+    /// Indices into `references` by the range of the referencing name.
+    reference_index: HashMap<TextRange, usize>,
+}
+
+impl SemanticModel {
+    /// Build the semantic model for a parsed file.
+    ///
+    /// `root` should be the `program` node of `source`.
+    pub fn build(source: &str, root: &CstNode) -> Self {
+        SemanticBuilder::new(source, root).build(root)
+    }
+
+    /// All scopes, in pre-order (a scope comes after its parent).
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    pub fn scope(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id.index()]
+    }
+
+    /// All symbols, in declaration order.
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    pub fn symbol(&self, id: SymbolId) -> &Symbol {
+        &self.symbols[id.index()]
+    }
+
+    /// All references, in source order.
+    pub fn references(&self) -> &[Reference] {
+        &self.references
+    }
+
+    /// Iterate over the symbols declared directly in `scope`.
+    pub fn symbols_in(&self, scope: ScopeId) -> impl Iterator<Item = (SymbolId, &Symbol)> {
+        self.scope(scope)
+            .symbols
+            .iter()
+            .map(|&id| (id, self.symbol(id)))
+    }
+
+    /// The innermost scope containing `offset`.
+    pub fn scope_at(&self, offset: TextSize) -> ScopeId {
+        // Scopes are stored in pre-order and siblings don't overlap, so the
+        // last scope containing the offset is the innermost one
+        self.scopes
+            .iter()
+            .rposition(|scope| scope.range.contains(offset))
+            .map_or(ScopeId::ROOT, ScopeId::new)
+    }
+
+    /// The innermost scope containing the start of `node`.
+    pub fn scope_of(&self, node: &CstNode) -> ScopeId {
+        self.scope_at(node.range().start())
+    }
+
+    /// The symbol whose declared name is at `range` (e.g. a declarator's `name` node).
+    pub fn declared_symbol(&self, range: TextRange) -> Option<SymbolId> {
+        self.declarations.get(&range).copied()
+    }
+
+    /// The reference whose name is at `range` (an `identifier`, or the name
+    /// of a method invocation or `this.` field access).
+    pub fn reference_at(&self, range: TextRange) -> Option<&Reference> {
+        self.reference_index
+            .get(&range)
+            .map(|&index| &self.references[index])
+    }
+
+    /// What the identifier `name` refers to: the symbol it declares, or the
+    /// one it resolves to as a reference. `None` for names not declared in
+    /// this file.
+    pub fn binding(&self, name: &CstNode) -> Option<SymbolId> {
+        let range = name.range();
+        self.declared_symbol(range)
+            .or_else(|| self.reference_at(range)?.resolved)
+    }
+
+    /// Iterate over the resolved references to `symbol`.
+    pub fn references_to(&self, symbol: SymbolId) -> impl Iterator<Item = &Reference> {
+        self.references
+            .iter()
+            .filter(move |reference| reference.resolved == Some(symbol))
+    }
+
+    /// Whether `symbol` is assigned anywhere after its declaration.
+    pub fn is_reassigned(&self, symbol: SymbolId) -> bool {
+        self.references_to(symbol)
+            .any(|reference| reference.access.is_write())
+    }
+
+    /// Resolve `name` as a variable visible at `offset`.
+    pub fn resolve_variable(&self, name: &str, offset: TextSize) -> Option<SymbolId> {
+        self.lookup(name, ReferenceKind::Variable, self.scope_at(offset), offset)
+    }
+
+    /// The innermost type scope enclosing `scope` (or `scope` itself).
+    pub fn enclosing_type(&self, scope: ScopeId) -> Option<ScopeId> {
+        self.ancestors(scope)
+            .find(|&id| self.scope(id).kind == ScopeKind::Type)
+    }
+
+    /// Whether code in `scope` runs without a `this` instance, i.e. it is
+    /// inside a static method or static initializer of its enclosing type.
+    pub fn is_static_context(&self, scope: ScopeId) -> bool {
+        for id in self.ancestors(scope) {
+            let scope = self.scope(id);
+            match scope.kind {
+                ScopeKind::Type => return false,
+                _ if scope.is_static => return true,
+                _ => {}
+            }
+        }
+        true
+    }
+
+    /// Iterate over `scope` and its enclosing scopes, innermost first.
+    pub fn ancestors(&self, scope: ScopeId) -> impl Iterator<Item = ScopeId> + '_ {
+        std::iter::successors(Some(scope), |&id| self.scope(id).parent)
+    }
+
+    pub(crate) fn resolve_reference(&self, reference: &Reference) -> Option<SymbolId> {
+        if reference.qualified_with_this {
+            let ty = self.enclosing_type(reference.scope)?;
+            return self
+                .symbols_in(ty)
+                .find(|(_, symbol)| {
+                    symbol.name == reference.name && matches_kind(symbol.kind, reference.kind)
+                })
+                .map(|(id, _)| id);
+        }
+        self.lookup(
+            &reference.name,
+            reference.kind,
+            reference.scope,
+            reference.range.start(),
+        )
+    }
+
+    /// Find the declaration of `name` visible from `scope` at `offset`.
+    fn lookup(
+        &self,
+        name: &str,
+        kind: ReferenceKind,
+        scope: ScopeId,
+        offset: TextSize,
+    ) -> Option<SymbolId> {
+        self.ancestors(scope).find_map(|id| {
+            self.symbols_in(id)
+                .find(|(_, symbol)| {
+                    symbol.name == name
+                        && matches_kind(symbol.kind, kind)
+                        // Locals are only in scope after their declaration
+                        && (symbol.kind != SymbolKind::LocalVariable
+                            || symbol.range.end() <= offset)
+                })
+                .map(|(id, _)| id)
+        })
+    }
+}
+
+fn matches_kind(symbol: SymbolKind, reference: ReferenceKind) -> bool {
+    match reference {
+        ReferenceKind::Variable => symbol.is_variable(),
+        ReferenceKind::Method => symbol == SymbolKind::Method,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_parser::JavaParser;
+
+    fn with_model(source: &str, f: impl FnOnce(&SemanticModel)) {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let root = CstNode::new(result.tree.root_node(), source);
+        let model = SemanticModel::build(source, &root);
+        f(&model);
+    }
+
+    fn find_symbol<'m>(model: &'m SemanticModel, name: &str) -> (SymbolId, &'m Symbol) {
+        model
+            .symbols()
+            .iter()
+            .enumerate()
+            .find(|(_, symbol)| symbol.name == name)
+            .map(|(index, symbol)| (SymbolId::new(index), symbol))
+            .unwrap_or_else(|| panic!("no symbol named {name}"))
+    }
+
+    #[test]
+    fn test_declares_members_and_locals() {
+        let source = r#"
+class Foo {
+    private static final int MAX = 1;
+    private int count;
+
+    void bump(int by) {
+        int next = count + by;
+        count = next;
+    }
+}
+"#;
+        with_model(source, |model| {
+            let (_, max) = find_symbol(model, "MAX");
+            assert_eq!(max.kind, SymbolKind::Field);
+            assert!(max.is_static && max.is_final);
+
+            let (_, by) = find_symbol(model, "by");
+            assert_eq!(by.kind, SymbolKind::Parameter);
+            assert_eq!(model.scope(by.scope).kind, ScopeKind::Method);
+
+            let (_, next) = find_symbol(model, "next");
+            assert_eq!(next.kind, SymbolKind::LocalVariable);
+            assert_eq!(model.scope(next.scope).kind, ScopeKind::Block);
+
+            let (_, bump) = find_symbol(model, "bump");
+            assert_eq!(bump.kind, SymbolKind::Method);
+        });
+    }
+
+    #[test]
+    fn test_resolves_references_and_writes() {
+        let source = r#"
+class Foo {
+    private int count;
+
+    void bump(int by) {
+        int next = count + by;
+        count = next;
+        count += 1;
+    }
+}
+"#;
+        with_model(source, |model| {
+            let (count, _) = find_symbol(model, "count");
+            let accesses: Vec<Access> = model.references_to(count).map(|r| r.access).collect();
+            assert_eq!(accesses, [Access::Read, Access::Write, Access::ReadWrite]);
+            assert!(model.is_reassigned(count));
+
+            let (next, _) = find_symbol(model, "next");
+            assert_eq!(model.references_to(next).count(), 1);
+            assert!(!model.is_reassigned(next));
+        });
+    }
+
+    #[test]
+    fn test_local_shadows_field() {
+        let source = r#"
+class Foo {
+    int x;
+
+    void m() {
+        use(x);
+        int x = 1;
+        use(x);
+        use(this.x);
+    }
+}
+"#;
+        with_model(source, |model| {
+            let resolved: Vec<SymbolKind> = model
+                .references()
+                .iter()
+                .filter(|r| r.name == "x")
+                .map(|r| model.symbol(r.resolved.unwrap()).kind)
+                .collect();
+            // Before the local is declared, and through `this.`, `x` is the field
+            assert_eq!(
+                resolved,
+                [
+                    SymbolKind::Field,
+                    SymbolKind::LocalVariable,
+                    SymbolKind::Field
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn test_lambda_and_loop_scopes() {
+        let source = r#"
+class Foo {
+    void m(java.util.List<String> items) {
+        for (String item : items) {
+            items.forEach(s -> System.out.println(s + item));
+        }
+        for (int i = 0; i < 10; i++) {}
+    }
+}
+"#;
+        with_model(source, |model| {
+            let (_, s) = find_symbol(model, "s");
+            assert_eq!(model.scope(s.scope).kind, ScopeKind::Lambda);
+
+            let (item, _) = find_symbol(model, "item");
+            assert_eq!(model.references_to(item).count(), 1);
+
+            let (i, _) = find_symbol(model, "i");
+            assert!(model.is_reassigned(i));
+
+            // `System` is not declared in this file
+            assert!(
+                model
+                    .references()
+                    .iter()
+                    .any(|r| r.name == "System" && r.resolved.is_none())
+            );
+        });
+    }
+
+    #[test]
+    fn test_static_context() {
+        let source = r#"
+class Foo {
+    int field;
+
+    static void s() {
+        int a = 0;
+    }
+
+    void i() {
+        int b = 0;
+    }
+}
+"#;
+        with_model(source, |model| {
+            let (_, a) = find_symbol(model, "a");
+            assert!(model.is_static_context(a.scope));
+
+            let (_, b) = find_symbol(model, "b");
+            assert!(!model.is_static_context(b.scope));
+        });
+    }
+
+    #[test]
+    fn test_anonymous_class_and_enum_members() {
+        let source = r#"
+enum Color {
+    RED, GREEN;
+
+    static Color first() {
+        return RED;
+    }
+
+    Runnable printer() {
+        return new Runnable() {
+            int calls;
+            public void run() { calls++; }
+        };
+    }
+}
+"#;
+        with_model(source, |model| {
+            let (red, symbol) = find_symbol(model, "RED");
+            assert_eq!(symbol.kind, SymbolKind::EnumConstant);
+            assert_eq!(model.references_to(red).count(), 1);
+
+            let (calls, symbol) = find_symbol(model, "calls");
+            assert_eq!(model.scope(symbol.scope).kind, ScopeKind::Type);
+            assert!(model.is_reassigned(calls));
+        });
+    }
+
+    /// Find the first node (in pre-order) whose source text is `text`.
+    fn find_node<'a>(node: &CstNode<'a>, text: &str) -> Option<CstNode<'a>> {
+        if node.text() == text {
+            return Some(*node);
+        }
+        node.children().find_map(|child| find_node(&child, text))
+    }
+
+    fn expression_type(source: &str, expression: &str) -> Option<JavaType> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let root = CstNode::new(result.tree.root_node(), source);
+        let model = SemanticModel::build(source, &root);
+        let node =
+            find_node(&root, expression).unwrap_or_else(|| panic!("no node for {expression}"));
+        model.expression_type(&node)
+    }
+
+    #[test]
+    fn test_declared_types() {
+        let source = r#"
+enum Color {
+    RED;
+
+    private java.util.List<String> names;
+    int[] counts, matrix[];
+
+    static String label(final String... parts) {
+        for (var part : parts) {}
+        var copy = new java.util.ArrayList<String>();
+        var nothing = null;
+        try {} catch (IllegalStateException | IllegalArgumentException e) {}
+        return "";
+    }
+}
+"#;
+        let class = |name: &str| Some(JavaType::Class(name.to_string()));
+        let int = JavaType::Primitive(Primitive::Int);
+        with_model(source, |model| {
+            assert_eq!(find_symbol(model, "RED").1.ty, class("Color"));
+            assert_eq!(find_symbol(model, "names").1.ty, class("java.util.List"));
+            assert_eq!(
+                find_symbol(model, "counts").1.ty,
+                Some(int.clone().array_of(1))
+            );
+            assert_eq!(find_symbol(model, "matrix").1.ty, Some(int.array_of(2)));
+            assert_eq!(find_symbol(model, "label").1.ty, class("String"));
+            assert_eq!(
+                find_symbol(model, "parts").1.ty,
+                Some(JavaType::string().array_of(1))
+            );
+            assert_eq!(find_symbol(model, "part").1.ty, class("String"));
+            assert_eq!(
+                find_symbol(model, "copy").1.ty,
+                class("java.util.ArrayList")
+            );
+            assert_eq!(find_symbol(model, "nothing").1.ty, None);
+            assert_eq!(find_symbol(model, "e").1.ty, None);
+        });
+    }
+
+    #[test]
+    fn test_expression_types() {
+        let source = r#"
+class Foo {
+    private String name;
+    private long total;
+
+    String name() { return name; }
+
+    void m(int i, char c, Object o) {
+        use(1L, 2.5f, 'x', i + c, i * 2.0, "a" + i, i < 2, !true);
+        use(this.name, name(), o.toString(), (String) o, i << total);
+        use(new int[i][], o instanceof Foo, this, i > 0 ? null : "b");
+        use(other());
+    }
+}
+"#;
+        let ty = |expression| expression_type(source, expression);
+        let primitive = |p| Some(JavaType::Primitive(p));
+        assert_eq!(ty("1L"), primitive(Primitive::Long));
+        assert_eq!(ty("2.5f"), primitive(Primitive::Float));
+        assert_eq!(ty("'x'"), primitive(Primitive::Char));
+        assert_eq!(ty("i + c"), primitive(Primitive::Int));
+        assert_eq!(ty("i * 2.0"), primitive(Primitive::Double));
+        assert_eq!(ty("\"a\" + i"), Some(JavaType::string()));
+        assert_eq!(ty("i < 2"), Some(JavaType::boolean()));
+        assert_eq!(ty("!true"), Some(JavaType::boolean()));
+        assert_eq!(ty("this.name"), Some(JavaType::string()));
+        assert_eq!(ty("name()"), Some(JavaType::string()));
+        assert_eq!(ty("o.toString()"), Some(JavaType::string()));
+        assert_eq!(ty("(String) o"), Some(JavaType::string()));
+        assert_eq!(ty("i << total"), primitive(Primitive::Int));
+        assert_eq!(
+            ty("new int[i][]"),
+            Some(JavaType::Primitive(Primitive::Int).array_of(2))
+        );
+        assert_eq!(ty("o instanceof Foo"), Some(JavaType::boolean()));
+        assert_eq!(ty("this"), Some(JavaType::Class("Foo".to_string())));
+        assert_eq!(ty("i > 0 ? null : \"b\""), Some(JavaType::string()));
+        // Not declared in this file
+        assert_eq!(ty("other()"), None);
+    }
+
+    #[test]
+    fn test_binding() {
+        let source = "class Foo { int x; void m(int y) { x = y; other = y; } }";
+        let result = JavaParser::new().parse(source).unwrap();
+        let root = CstNode::new(result.tree.root_node(), source);
+        let model = SemanticModel::build(source, &root);
+        let binding = |nth: usize, name: &str| {
+            let identifier = lintal_java_cst::TreeWalker::new(result.tree.root_node(), source)
+                .filter(|node| node.kind() == "identifier" && node.text() == name)
+                .nth(nth)
+                .unwrap();
+            model.binding(&identifier).map(|id| model.symbol(id).kind)
+        };
+
+        // Declarations bind to themselves, references to their declaration
+        assert_eq!(binding(0, "x"), Some(SymbolKind::Field));
+        assert_eq!(binding(1, "x"), Some(SymbolKind::Field));
+        assert_eq!(binding(2, "y"), Some(SymbolKind::Parameter));
+        assert_eq!(binding(0, "other"), None);
+    }
+
+    #[test]
+    fn test_scope_at_and_declared_symbol() {
+        let source = "class Foo { void m() { int local = 0; } }";
+        with_model(source, |model| {
+            let offset = TextSize::try_from(source.find("local").unwrap()).unwrap();
+            let scope = model.scope_at(offset);
+            assert_eq!(model.scope(scope).kind, ScopeKind::Block);
+
+            let (id, symbol) = find_symbol(model, "local");
+            assert_eq!(model.declared_symbol(symbol.range), Some(id));
+            assert_eq!(
+                model.resolve_variable("local", TextSize::try_from(source.len() - 4).unwrap()),
+                Some(id)
+            );
+        });
+    }
+}
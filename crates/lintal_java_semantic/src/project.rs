@@ -0,0 +1,540 @@
+//! Project-wide index of the types declared across many files.
+//!
+//! Each file is summarized on its own (its package, the types it declares and
+//! their members, and the names it uses), so summaries can be built in
+//! parallel and then combined into a [`ProjectIndex`].
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use lintal_java_cst::CstNode;
+use lintal_text_size::TextRange;
+
+use crate::SymbolKind;
+use crate::builder::{TYPE_DECLARATIONS, declarators, has_modifier};
+use crate::duplicates::{self, Duplicate, TokenFingerprint};
+
+/// Declared accessibility of a type or member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    Public,
+    Protected,
+    /// No access modifier.
+    Package,
+    Private,
+}
+
+impl Visibility {
+    /// The visibility written on a declaration. Interface members without a
+    /// modifier are public, not package-private.
+    fn of(node: &CstNode, in_interface: bool) -> Self {
+        if has_modifier(node, "public") {
+            Visibility::Public
+        } else if has_modifier(node, "protected") {
+            Visibility::Protected
+        } else if has_modifier(node, "private") {
+            Visibility::Private
+        } else if in_interface {
+            Visibility::Public
+        } else {
+            Visibility::Package
+        }
+    }
+}
+
+/// The kind of a declared type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeKind {
+    Class,
+    Interface,
+    Enum,
+    Record,
+    Annotation,
+}
+
+impl TypeKind {
+    fn from_declaration(kind: &str) -> Option<Self> {
+        Some(match kind {
+            "class_declaration" => TypeKind::Class,
+            "interface_declaration" => TypeKind::Interface,
+            "enum_declaration" => TypeKind::Enum,
+            "record_declaration" => TypeKind::Record,
+            "annotation_type_declaration" => TypeKind::Annotation,
+            _ => return None,
+        })
+    }
+}
+
+/// A member (field, enum constant or method) of a [`TypeSummary`].
+#[derive(Debug, Clone)]
+pub struct MemberSummary {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub visibility: Visibility,
+    pub is_static: bool,
+    /// Range of the member's name in its file.
+    pub range: TextRange,
+}
+
+/// A type declared in a file, including nested types.
+#[derive(Debug, Clone)]
+pub struct TypeSummary {
+    pub name: String,
+    /// Fully qualified name (`com.example.Outer.Inner`).
+    pub qualified_name: String,
+    pub kind: TypeKind,
+    pub visibility: Visibility,
+    /// Qualified name of the enclosing type, `None` for top-level types.
+    pub outer: Option<String>,
+    /// Range of the type's name in its file.
+    pub range: TextRange,
+    pub members: Vec<MemberSummary>,
+}
+
+/// What the project index needs to know about one file.
+#[derive(Debug, Clone)]
+pub struct FileSummary {
+    pub path: PathBuf,
+    /// The declared package, empty for the default package.
+    pub package: String,
+    /// Types declared in the file, outer types before the types nested in them.
+    pub types: Vec<TypeSummary>,
+    /// Simple names used in the file outside of declarations.
+    pub used_names: HashSet<String>,
+    /// The file's code tokens, for finding duplicated code.
+    pub tokens: Vec<TokenFingerprint>,
+}
+
+impl FileSummary {
+    /// Summarize a parsed file. `root` should be the `program` node.
+    pub fn build(path: impl Into<PathBuf>, root: &CstNode) -> Self {
+        let mut summary = FileSummary {
+            path: path.into(),
+            package: String::new(),
+            types: Vec::new(),
+            used_names: HashSet::new(),
+            tokens: duplicates::fingerprints(root),
+        };
+
+        for child in root.named_children() {
+            if child.kind() == "package_declaration" {
+                if let Some(name) = child
+                    .named_children()
+                    .find(|c| matches!(c.kind(), "identifier" | "scoped_identifier"))
+                {
+                    summary.package = name.text().split_whitespace().collect();
+                }
+            } else if TYPE_DECLARATIONS.contains(&child.kind()) {
+                summary.add_type(&child, None, false);
+            }
+        }
+        collect_used_names(root, &mut summary.used_names);
+
+        summary
+    }
+
+    fn add_type(&mut self, node: &CstNode, outer: Option<&str>, in_interface: bool) {
+        let (Some(name), Some(kind)) = (
+            node.child_by_field_name("name"),
+            TypeKind::from_declaration(node.kind()),
+        ) else {
+            return;
+        };
+        let qualified_name = match (outer, self.package.as_str()) {
+            (Some(outer), _) => format!("{outer}.{}", name.text()),
+            (None, "") => name.text().to_string(),
+            (None, package) => format!("{package}.{}", name.text()),
+        };
+
+        let index = self.types.len();
+        self.types.push(TypeSummary {
+            name: name.text().to_string(),
+            qualified_name: qualified_name.clone(),
+            kind,
+            visibility: Visibility::of(node, in_interface),
+            outer: outer.map(str::to_string),
+            range: name.range(),
+            members: Vec::new(),
+        });
+
+        if let Some(body) = node.child_by_field_name("body") {
+            let is_interface = matches!(kind, TypeKind::Interface | TypeKind::Annotation);
+            let members = self.add_members(&body, &qualified_name, is_interface);
+            self.types[index].members = members;
+        }
+    }
+
+    fn add_members(
+        &mut self,
+        body: &CstNode,
+        qualified_name: &str,
+        is_interface: bool,
+    ) -> Vec<MemberSummary> {
+        let mut members = Vec::new();
+        for member in body.named_children() {
+            let visibility = Visibility::of(&member, is_interface);
+            let is_static = is_interface || has_modifier(&member, "static");
+            match member.kind() {
+                "field_declaration" | "constant_declaration" => {
+                    for declarator in declarators(&member) {
+                        if let Some(name) = declarator.child_by_field_name("name") {
+                            members.push(MemberSummary {
+                                name: name.text().to_string(),
+                                kind: SymbolKind::Field,
+                                visibility,
+                                is_static,
+                                range: name.range(),
+                            });
+                        }
+                    }
+                }
+                "enum_constant" | "method_declaration" => {
+                    if let Some(name) = member.child_by_field_name("name") {
+                        let enum_constant = member.kind() == "enum_constant";
+                        members.push(MemberSummary {
+                            name: name.text().to_string(),
+                            kind: if enum_constant {
+                                SymbolKind::EnumConstant
+                            } else {
+                                SymbolKind::Method
+                            },
+                            visibility: if enum_constant {
+                                Visibility::Public
+                            } else {
+                                visibility
+                            },
+                            is_static: enum_constant || has_modifier(&member, "static"),
+                            range: name.range(),
+                        });
+                    }
+                }
+                "enum_body_declarations" => {
+                    members.extend(self.add_members(&member, qualified_name, is_interface));
+                }
+                kind if TYPE_DECLARATIONS.contains(&kind) => {
+                    self.add_type(&member, Some(qualified_name), is_interface);
+                }
+                _ => {}
+            }
+        }
+        members
+    }
+}
+
+/// Collect the names used in `node` outside of declarations.
+fn collect_used_names(node: &CstNode, names: &mut HashSet<String>) {
+    for child in node.named_children() {
+        match child.kind() {
+            "package_declaration" | "line_comment" | "block_comment" => {}
+            "identifier" | "type_identifier" if !is_declared_name(&child) => {
+                names.insert(child.text().to_string());
+            }
+            _ => collect_used_names(&child, names),
+        }
+    }
+}
+
+/// Whether `name` is the name being declared by its parent.
+fn is_declared_name(name: &CstNode) -> bool {
+    name.parent().is_some_and(|parent| {
+        let declares = parent.kind().ends_with("_declaration")
+            || matches!(
+                parent.kind(),
+                "variable_declarator" | "formal_parameter" | "enum_constant"
+            );
+        declares
+            && parent
+                .child_by_field_name("name")
+                .is_some_and(|declared| declared.range() == name.range())
+    })
+}
+
+/// Location of a type declaration in the index: the file and the type's
+/// position in [`FileSummary::types`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TypeLocation {
+    file: usize,
+    ty: usize,
+}
+
+/// Packages, types and members declared across a set of files.
+#[derive(Debug, Default)]
+pub struct ProjectIndex {
+    files: Vec<FileSummary>,
+    /// Declarations of each qualified type name (more than one if duplicated).
+    types: HashMap<String, Vec<TypeLocation>>,
+    /// Files in each package, by index into `files`.
+    packages: BTreeMap<String, Vec<usize>>,
+    /// Duplicates of each file by index into `files`, found on first use
+    /// for each minimum clone length.
+    duplicates: Mutex<HashMap<usize, Arc<Vec<Vec<Duplicate>>>>>,
+}
+
+impl ProjectIndex {
+    pub fn new(files: impl IntoIterator<Item = FileSummary>) -> Self {
+        let mut index = ProjectIndex::default();
+        for summary in files {
+            let file = index.files.len();
+            for (ty, summary) in summary.types.iter().enumerate() {
+                index
+                    .types
+                    .entry(summary.qualified_name.clone())
+                    .or_default()
+                    .push(TypeLocation { file, ty });
+            }
+            index
+                .packages
+                .entry(summary.package.clone())
+                .or_default()
+                .push(file);
+            index.files.push(summary);
+        }
+        index
+    }
+
+    /// All indexed files.
+    pub fn files(&self) -> &[FileSummary] {
+        &self.files
+    }
+
+    /// The summary of the file at `path`.
+    pub fn file(&self, path: &Path) -> Option<&FileSummary> {
+        self.files.iter().find(|file| file.path == path)
+    }
+
+    /// Names of all packages, in order.
+    pub fn packages(&self) -> impl Iterator<Item = &str> {
+        self.packages.keys().map(String::as_str)
+    }
+
+    /// Iterate over the files declaring `package`.
+    pub fn files_in(&self, package: &str) -> impl Iterator<Item = &FileSummary> {
+        self.packages
+            .get(package)
+            .into_iter()
+            .flatten()
+            .map(|&file| &self.files[file])
+    }
+
+    /// Iterate over the top-level types of `package`.
+    pub fn types_in(&self, package: &str) -> impl Iterator<Item = &TypeSummary> {
+        self.files_in(package)
+            .flat_map(|file| &file.types)
+            .filter(|ty| ty.outer.is_none())
+    }
+
+    /// The type with the given qualified name, if exactly one file declares it.
+    pub fn find_type(&self, qualified_name: &str) -> Option<&TypeSummary> {
+        match self.types.get(qualified_name)?.as_slice() {
+            [location] => Some(self.type_at(*location).1),
+            _ => None,
+        }
+    }
+
+    /// Iterate over every declaration of `qualified_name` with its file.
+    pub fn declarations_of(
+        &self,
+        qualified_name: &str,
+    ) -> impl Iterator<Item = (&FileSummary, &TypeSummary)> {
+        self.types
+            .get(qualified_name)
+            .into_iter()
+            .flatten()
+            .map(|&location| self.type_at(location))
+    }
+
+    /// Qualified names of types declared in more than one file, sorted.
+    pub fn duplicate_types(&self) -> Vec<&str> {
+        let mut duplicates: Vec<&str> = self
+            .types
+            .iter()
+            .filter(|(_, locations)| {
+                locations
+                    .iter()
+                    .any(|location| location.file != locations[0].file)
+            })
+            .map(|(name, _)| name.as_str())
+            .collect();
+        duplicates.sort_unstable();
+        duplicates
+    }
+
+    /// The types a star import brings into scope: the top-level types of a
+    /// package (`import com.example.*`), or the member types of a type
+    /// (`import com.example.Outer.*`).
+    pub fn expand_star_import(&self, qualifier: &str) -> Vec<&TypeSummary> {
+        if self.packages.contains_key(qualifier) {
+            return self.types_in(qualifier).collect();
+        }
+        self.files
+            .iter()
+            .flat_map(|file| &file.types)
+            .filter(|ty| ty.outer.as_deref() == Some(qualifier))
+            .collect()
+    }
+
+    /// Whether `name` is used in any file of `package`, other than in its
+    /// declarations. Package-private members can only be used from there.
+    pub fn is_used_in_package(&self, package: &str, name: &str) -> bool {
+        self.files_in(package)
+            .any(|file| file.used_names.contains(name))
+    }
+
+    /// The code of the file at `path` that is duplicated elsewhere in the
+    /// project, in runs of at least `min_tokens` tokens; see
+    /// [`crate::duplicates`].
+    ///
+    /// The clones of all files are found the first time any file asks.
+    pub fn duplicates_of(&self, path: &Path, min_tokens: usize) -> Vec<Duplicate> {
+        let Some(file) = self.files.iter().position(|file| file.path == path) else {
+            return Vec::new();
+        };
+        let found = {
+            let mut cache = self
+                .duplicates
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            Arc::clone(cache.entry(min_tokens).or_insert_with(|| {
+                let tokens: Vec<&[TokenFingerprint]> = self
+                    .files
+                    .iter()
+                    .map(|file| file.tokens.as_slice())
+                    .collect();
+                Arc::new(duplicates::find(&tokens, min_tokens))
+            }))
+        };
+        found[file].clone()
+    }
+
+    fn type_at(&self, location: TypeLocation) -> (&FileSummary, &TypeSummary) {
+        let file = &self.files[location.file];
+        (file, &file.types[location.ty])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_parser::JavaParser;
+
+    fn summarize(path: &str, source: &str) -> FileSummary {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let root = CstNode::new(result.tree.root_node(), source);
+        FileSummary::build(path, &root)
+    }
+
+    fn project() -> ProjectIndex {
+        ProjectIndex::new([
+            summarize(
+                "a/Shapes.java",
+                r#"
+package com.example.shapes;
+
+public class Shapes {
+    static int created;
+    private int unused;
+
+    public interface Shape {
+        double area();
+    }
+
+    enum Kind { CIRCLE, SQUARE }
+}
+"#,
+            ),
+            summarize(
+                "a/Circle.java",
+                r#"
+package com.example.shapes;
+
+class Circle implements Shapes.Shape {
+    public double area() {
+        Shapes.created++;
+        return 0;
+    }
+}
+"#,
+            ),
+            summarize(
+                "b/Circle.java",
+                "package com.example.shapes;\n\nclass Circle {}\n",
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_summarizes_types_and_members() {
+        let index = project();
+        let shapes = index.find_type("com.example.shapes.Shapes").unwrap();
+        assert_eq!(shapes.kind, TypeKind::Class);
+        assert_eq!(shapes.visibility, Visibility::Public);
+        let members: Vec<(&str, Visibility)> = shapes
+            .members
+            .iter()
+            .map(|m| (m.name.as_str(), m.visibility))
+            .collect();
+        assert_eq!(
+            members,
+            [
+                ("created", Visibility::Package),
+                ("unused", Visibility::Private)
+            ]
+        );
+
+        let shape = index.find_type("com.example.shapes.Shapes.Shape").unwrap();
+        assert_eq!(shape.kind, TypeKind::Interface);
+        assert_eq!(shape.outer.as_deref(), Some("com.example.shapes.Shapes"));
+        // Interface methods are implicitly public
+        assert_eq!(shape.members[0].visibility, Visibility::Public);
+
+        let kind = index.find_type("com.example.shapes.Shapes.Kind").unwrap();
+        assert_eq!(kind.members.len(), 2);
+        assert!(
+            kind.members
+                .iter()
+                .all(|m| m.kind == SymbolKind::EnumConstant)
+        );
+    }
+
+    #[test]
+    fn test_duplicate_types() {
+        let index = project();
+        assert_eq!(index.duplicate_types(), ["com.example.shapes.Circle"]);
+        assert!(index.find_type("com.example.shapes.Circle").is_none());
+        let paths: Vec<&Path> = index
+            .declarations_of("com.example.shapes.Circle")
+            .map(|(file, _)| file.path.as_path())
+            .collect();
+        assert_eq!(
+            paths,
+            [Path::new("a/Circle.java"), Path::new("b/Circle.java")]
+        );
+    }
+
+    #[test]
+    fn test_expand_star_import() {
+        let index = project();
+        let names = |types: Vec<&TypeSummary>| -> Vec<String> {
+            types.iter().map(|ty| ty.name.clone()).collect()
+        };
+        assert_eq!(
+            names(index.expand_star_import("com.example.shapes")),
+            ["Shapes", "Circle", "Circle"]
+        );
+        assert_eq!(
+            names(index.expand_star_import("com.example.shapes.Shapes")),
+            ["Shape", "Kind"]
+        );
+        assert!(index.expand_star_import("java.util").is_empty());
+    }
+
+    #[test]
+    fn test_used_names() {
+        let index = project();
+        assert!(index.is_used_in_package("com.example.shapes", "created"));
+        assert!(!index.is_used_in_package("com.example.shapes", "unused"));
+        assert!(!index.is_used_in_package("other", "created"));
+    }
+}
@@ -0,0 +1,69 @@
+//! Lexical scopes.
+
+use lintal_text_size::TextRange;
+
+use crate::SymbolId;
+
+/// Index of a [`Scope`] in a [`SemanticModel`](crate::SemanticModel).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ScopeId(u32);
+
+impl ScopeId {
+    /// The compilation unit scope, which is always the first scope.
+    pub const ROOT: ScopeId = ScopeId(0);
+
+    pub(crate) fn new(index: usize) -> Self {
+        Self(index as u32)
+    }
+
+    pub(crate) fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// The syntactic construct that introduces a scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeKind {
+    /// The whole file; holds top-level type declarations.
+    CompilationUnit,
+    /// A class, interface, enum, record, annotation type or anonymous class body.
+    Type,
+    /// A method, constructor or compact constructor; holds its parameters.
+    Method,
+    /// A lambda expression; holds its parameters.
+    Lambda,
+    /// A block or block-like statement (`for`, `catch`, try-with-resources).
+    Block,
+}
+
+/// A lexical scope and the symbols declared directly in it.
+#[derive(Debug, Clone)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    /// The enclosing scope, `None` only for the compilation unit.
+    pub parent: Option<ScopeId>,
+    /// Source range of the node that introduces the scope.
+    pub range: TextRange,
+    /// Whether code in this scope has no `this` (static methods, static
+    /// initializers, static nested types).
+    pub is_static: bool,
+    /// Symbols declared in this scope, in declaration order.
+    pub symbols: Vec<SymbolId>,
+}
+
+impl Scope {
+    pub(crate) fn new(
+        kind: ScopeKind,
+        parent: Option<ScopeId>,
+        range: TextRange,
+        is_static: bool,
+    ) -> Self {
+        Self {
+            kind,
+            parent,
+            range,
+            is_static,
+            symbols: Vec::new(),
+        }
+    }
+}
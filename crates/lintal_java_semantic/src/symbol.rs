@@ -0,0 +1,113 @@
+//! Declared symbols and references to them.
+
+use lintal_text_size::TextRange;
+
+use crate::{JavaType, ScopeId};
+
+/// Index of a [`Symbol`] in a [`SemanticModel`](crate::SemanticModel).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SymbolId(u32);
+
+impl SymbolId {
+    pub(crate) fn new(index: usize) -> Self {
+        Self(index as u32)
+    }
+
+    pub(crate) fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// What a symbol declares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    /// A class, interface, enum, record or annotation type.
+    Type,
+    /// A field, including interface constants.
+    Field,
+    /// An enum constant.
+    EnumConstant,
+    /// A record component.
+    RecordComponent,
+    /// A method.
+    Method,
+    /// A method, constructor, lambda or catch parameter.
+    Parameter,
+    /// A local variable, including `for`, resource and pattern variables.
+    LocalVariable,
+}
+
+impl SymbolKind {
+    /// Whether the symbol names a value that can be referenced as a variable.
+    pub fn is_variable(self) -> bool {
+        !matches!(self, SymbolKind::Type | SymbolKind::Method)
+    }
+
+    /// Whether the symbol is a member of a type rather than a local.
+    pub fn is_member(self) -> bool {
+        matches!(
+            self,
+            SymbolKind::Field
+                | SymbolKind::EnumConstant
+                | SymbolKind::RecordComponent
+                | SymbolKind::Method
+        )
+    }
+}
+
+/// A declared name.
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Range of the declared name.
+    pub range: TextRange,
+    /// Range of the whole declaration (e.g. the `local_variable_declaration`).
+    pub declaration: TextRange,
+    /// The scope the symbol is declared in.
+    pub scope: ScopeId,
+    pub is_static: bool,
+    pub is_final: bool,
+    /// The declared (or, for `var`, inferred) type; the return type for
+    /// methods. `None` when it isn't known.
+    pub ty: Option<JavaType>,
+}
+
+/// Whether a reference reads or writes its target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    /// Plain assignment (`x = ...`).
+    Write,
+    /// Compound assignment or increment/decrement (`x += 1`, `x++`).
+    ReadWrite,
+}
+
+impl Access {
+    pub fn is_write(self) -> bool {
+        matches!(self, Access::Write | Access::ReadWrite)
+    }
+}
+
+/// Whether a reference names a variable or calls a method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceKind {
+    Variable,
+    Method,
+}
+
+/// A use of a simple name.
+#[derive(Debug, Clone)]
+pub struct Reference {
+    pub name: String,
+    pub kind: ReferenceKind,
+    /// Range of the name.
+    pub range: TextRange,
+    /// The innermost scope containing the reference.
+    pub scope: ScopeId,
+    pub access: Access,
+    /// Whether the name is qualified with `this.` (`this.x`, `this.m()`).
+    pub qualified_with_this: bool,
+    /// The symbol the name resolves to, if it is declared in this file.
+    pub resolved: Option<SymbolId>,
+}
@@ -0,0 +1,369 @@
+//! Best-effort static types of declarations and expressions.
+//!
+//! Types come from what is written in the file: declared types, `new`
+//! expressions, casts and literals. Nothing is known about other classes, so
+//! a type is a name as written (without type arguments) and anything that
+//! depends on outside information is `None`.
+
+use std::fmt::{Display, Formatter};
+
+use lintal_java_cst::CstNode;
+
+use crate::{SemanticModel, SymbolKind};
+
+/// A primitive type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Primitive {
+    Boolean,
+    Byte,
+    Short,
+    Char,
+    Int,
+    Long,
+    Float,
+    Double,
+}
+
+impl Primitive {
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        Some(match keyword {
+            "boolean" => Primitive::Boolean,
+            "byte" => Primitive::Byte,
+            "short" => Primitive::Short,
+            "char" => Primitive::Char,
+            "int" => Primitive::Int,
+            "long" => Primitive::Long,
+            "float" => Primitive::Float,
+            "double" => Primitive::Double,
+            _ => return None,
+        })
+    }
+
+    pub fn keyword(self) -> &'static str {
+        match self {
+            Primitive::Boolean => "boolean",
+            Primitive::Byte => "byte",
+            Primitive::Short => "short",
+            Primitive::Char => "char",
+            Primitive::Int => "int",
+            Primitive::Long => "long",
+            Primitive::Float => "float",
+            Primitive::Double => "double",
+        }
+    }
+
+    pub fn is_numeric(self) -> bool {
+        self != Primitive::Boolean
+    }
+}
+
+/// The static type of a declaration or expression.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum JavaType {
+    Primitive(Primitive),
+    /// A class or interface type, by its name as written without type
+    /// arguments (`String`, `java.util.List`, `Map.Entry`).
+    Class(String),
+    Array(Box<JavaType>),
+    /// The type of the `null` literal.
+    Null,
+}
+
+impl JavaType {
+    pub fn string() -> Self {
+        JavaType::Class("String".to_string())
+    }
+
+    pub fn boolean() -> Self {
+        JavaType::Primitive(Primitive::Boolean)
+    }
+
+    /// Whether this is `String` or `java.lang.String`.
+    pub fn is_string(&self) -> bool {
+        matches!(self, JavaType::Class(name) if name == "String" || name == "java.lang.String")
+    }
+
+    pub fn is_primitive(&self) -> bool {
+        matches!(self, JavaType::Primitive(_))
+    }
+
+    /// The unqualified name of a class type (`Entry` for `Map.Entry`).
+    pub fn simple_name(&self) -> Option<&str> {
+        match self {
+            JavaType::Class(name) => name.rsplit('.').next(),
+            _ => None,
+        }
+    }
+
+    /// The element type of an array type.
+    pub fn element(&self) -> Option<&JavaType> {
+        match self {
+            JavaType::Array(element) => Some(element),
+            _ => None,
+        }
+    }
+
+    /// Wrap `self` in `dimensions` levels of array.
+    pub fn array_of(self, dimensions: usize) -> Self {
+        (0..dimensions).fold(self, |ty, _| JavaType::Array(Box::new(ty)))
+    }
+
+    /// Parse a type node (`integral_type`, `generic_type`, `array_type`, ...).
+    ///
+    /// Returns `None` for `void`, `var` and types that can't be named, such as
+    /// union types in multi-catch.
+    pub fn from_type_node(node: &CstNode) -> Option<Self> {
+        match node.kind() {
+            "integral_type" | "floating_point_type" | "boolean_type" => {
+                Primitive::from_keyword(node.text()).map(JavaType::Primitive)
+            }
+            "type_identifier" if node.text() == "var" => None,
+            "type_identifier" | "scoped_type_identifier" => Some(JavaType::Class(
+                node.text().split_whitespace().collect::<String>(),
+            )),
+            // `List<String>` is `List`
+            "generic_type" => node
+                .named_children()
+                .next()
+                .and_then(|raw| JavaType::from_type_node(&raw)),
+            "array_type" => {
+                let element = node.child_by_field_name("element")?;
+                let dimensions = node
+                    .child_by_field_name("dimensions")
+                    .map_or(1, |d| count_dimensions(&d));
+                JavaType::from_type_node(&element).map(|ty| ty.array_of(dimensions))
+            }
+            // Annotations on a type don't change it
+            "annotated_type" => node
+                .named_children()
+                .filter(|child| !matches!(child.kind(), "annotation" | "marker_annotation"))
+                .last()
+                .and_then(|ty| JavaType::from_type_node(&ty)),
+            _ => None,
+        }
+    }
+
+    /// The type of a literal expression node.
+    pub fn from_literal(node: &CstNode) -> Option<Self> {
+        let text = node.text();
+        Some(match node.kind() {
+            "string_literal" | "text_block" => JavaType::string(),
+            "character_literal" => JavaType::Primitive(Primitive::Char),
+            "true" | "false" => JavaType::boolean(),
+            "null_literal" => JavaType::Null,
+            "decimal_integer_literal"
+            | "hex_integer_literal"
+            | "octal_integer_literal"
+            | "binary_integer_literal" => {
+                if text.ends_with(['l', 'L']) {
+                    JavaType::Primitive(Primitive::Long)
+                } else {
+                    JavaType::Primitive(Primitive::Int)
+                }
+            }
+            "decimal_floating_point_literal" | "hex_floating_point_literal" => {
+                if text.ends_with(['f', 'F']) {
+                    JavaType::Primitive(Primitive::Float)
+                } else {
+                    JavaType::Primitive(Primitive::Double)
+                }
+            }
+            _ => return None,
+        })
+    }
+}
+
+impl Display for JavaType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JavaType::Primitive(primitive) => f.write_str(primitive.keyword()),
+            JavaType::Class(name) => f.write_str(name),
+            JavaType::Array(element) => write!(f, "{element}[]"),
+            JavaType::Null => f.write_str("null"),
+        }
+    }
+}
+
+impl SemanticModel {
+    /// The static type of an expression, if it can be worked out from this
+    /// file: literals, `new`, casts, operators, and names and in-file methods
+    /// whose types are declared.
+    pub fn expression_type(&self, node: &CstNode) -> Option<JavaType> {
+        if let Some(ty) = JavaType::from_literal(node) {
+            return Some(ty);
+        }
+        match node.kind() {
+            "parenthesized_expression" => node
+                .named_children()
+                .next()
+                .and_then(|inner| self.expression_type(&inner)),
+            "object_creation_expression" => node
+                .child_by_field_name("type")
+                .and_then(|ty| JavaType::from_type_node(&ty)),
+            "array_creation_expression" => {
+                let element = node
+                    .child_by_field_name("type")
+                    .and_then(|ty| JavaType::from_type_node(&ty))?;
+                // `new int[n][]` has one `dimensions_expr` and one `dimensions`
+                let dimensions = node
+                    .named_children()
+                    .map(|child| match child.kind() {
+                        "dimensions_expr" => 1,
+                        "dimensions" => count_dimensions(&child),
+                        _ => 0,
+                    })
+                    .sum();
+                Some(element.array_of(dimensions))
+            }
+            "cast_expression" => node
+                .child_by_field_name("type")
+                .and_then(|ty| JavaType::from_type_node(&ty)),
+            "class_literal" => Some(JavaType::Class("Class".to_string())),
+            "instanceof_expression" => Some(JavaType::boolean()),
+            "binary_expression" => self.binary_type(node),
+            "unary_expression" => {
+                let operand = self.expression_type(&node.child_by_field_name("operand")?)?;
+                match node.child_by_field_name("operator")?.kind() {
+                    "!" => Some(JavaType::boolean()),
+                    _ => unary_promote(&operand),
+                }
+            }
+            "update_expression" => node
+                .named_children()
+                .next()
+                .and_then(|operand| self.expression_type(&operand)),
+            "assignment_expression" => node
+                .child_by_field_name("left")
+                .and_then(|left| self.expression_type(&left)),
+            "ternary_expression" => {
+                let consequence = self.expression_type(&node.child_by_field_name("consequence")?);
+                let alternative = self.expression_type(&node.child_by_field_name("alternative")?);
+                match (consequence?, alternative?) {
+                    (a, b) if a == b => Some(a),
+                    (JavaType::Null, other) | (other, JavaType::Null) if !other.is_primitive() => {
+                        Some(other)
+                    }
+                    (JavaType::Primitive(a), JavaType::Primitive(b)) => {
+                        promote(a, b).map(JavaType::Primitive)
+                    }
+                    _ => None,
+                }
+            }
+            "array_access" => node
+                .child_by_field_name("array")
+                .and_then(|array| self.expression_type(&array))
+                .and_then(|array| array.element().cloned()),
+            "identifier" => self.resolved_type(node),
+            "field_access" => match node.child_by_field_name("object") {
+                Some(object) if object.kind() == "this" => {
+                    self.resolved_type(&node.child_by_field_name("field")?)
+                }
+                // `array.length`
+                Some(object)
+                    if node
+                        .child_by_field_name("field")
+                        .is_some_and(|field| field.text() == "length")
+                        && matches!(self.expression_type(&object), Some(JavaType::Array(_))) =>
+                {
+                    Some(JavaType::Primitive(Primitive::Int))
+                }
+                _ => None,
+            },
+            "this" => {
+                let ty = self.enclosing_type(self.scope_of(node))?;
+                let range = self.scope(ty).range;
+                // Anonymous classes have no type symbol
+                self.symbols()
+                    .iter()
+                    .find(|symbol| symbol.kind == SymbolKind::Type && symbol.declaration == range)
+                    .and_then(|symbol| symbol.ty.clone())
+            }
+            "method_invocation" => self.invocation_type(node),
+            _ => None,
+        }
+    }
+
+    /// The declared type of the symbol a name resolves to.
+    fn resolved_type(&self, name: &CstNode) -> Option<JavaType> {
+        let symbol = self.reference_at(name.range())?.resolved?;
+        self.symbol(symbol).ty.clone()
+    }
+
+    fn binary_type(&self, node: &CstNode) -> Option<JavaType> {
+        let operator = node.child_by_field_name("operator")?;
+        match operator.kind() {
+            "==" | "!=" | "<" | ">" | "<=" | ">=" | "&&" | "||" => {
+                return Some(JavaType::boolean());
+            }
+            _ => {}
+        }
+
+        let left = self.expression_type(&node.child_by_field_name("left")?);
+        let right = self.expression_type(&node.child_by_field_name("right")?);
+        if operator.kind() == "+"
+            && (left.as_ref().is_some_and(JavaType::is_string)
+                || right.as_ref().is_some_and(JavaType::is_string))
+        {
+            return Some(JavaType::string());
+        }
+
+        match (left?, right?) {
+            // The type of a shift is the promoted type of its left operand
+            (left, _) if matches!(operator.kind(), "<<" | ">>" | ">>>") => unary_promote(&left),
+            (JavaType::Primitive(Primitive::Boolean), JavaType::Primitive(Primitive::Boolean))
+                if matches!(operator.kind(), "&" | "|" | "^") =>
+            {
+                Some(JavaType::boolean())
+            }
+            (JavaType::Primitive(left), JavaType::Primitive(right)) => {
+                promote(left, right).map(JavaType::Primitive)
+            }
+            _ => None,
+        }
+    }
+
+    /// The return type of an invocation of a method declared in this file,
+    /// when every overload with that name agrees on it.
+    fn invocation_type(&self, node: &CstNode) -> Option<JavaType> {
+        let name = node.child_by_field_name("name")?;
+        let arguments = node.child_by_field_name("arguments");
+        if name.text() == "toString" && arguments.is_some_and(|a| a.named_children().count() == 0) {
+            return Some(JavaType::string());
+        }
+
+        let method = self.symbol(self.reference_at(name.range())?.resolved?);
+        let ty = method.ty.clone()?;
+        self.symbols_in(method.scope)
+            .filter(|(_, symbol)| symbol.kind == SymbolKind::Method && symbol.name == method.name)
+            .all(|(_, symbol)| symbol.ty.as_ref() == Some(&ty))
+            .then_some(ty)
+    }
+}
+
+/// Unary numeric promotion (JLS 5.6.1).
+fn unary_promote(ty: &JavaType) -> Option<JavaType> {
+    match ty {
+        JavaType::Primitive(primitive) => {
+            promote(*primitive, Primitive::Int).map(JavaType::Primitive)
+        }
+        _ => None,
+    }
+}
+
+/// Count the `[]` pairs in a `dimensions` node.
+pub(crate) fn count_dimensions(dimensions: &CstNode) -> usize {
+    dimensions.children().filter(|c| c.kind() == "[").count()
+}
+
+/// Binary numeric promotion (JLS 5.6.2) of two primitive operand types.
+pub(crate) fn promote(left: Primitive, right: Primitive) -> Option<Primitive> {
+    if !left.is_numeric() || !right.is_numeric() {
+        return None;
+    }
+    Some(match left.max(right) {
+        Primitive::Double => Primitive::Double,
+        Primitive::Float => Primitive::Float,
+        Primitive::Long => Primitive::Long,
+        _ => Primitive::Int,
+    })
+}
@@ -0,0 +1,397 @@
+//! Parser for Javadoc comments.
+//!
+//! Turns a `/** ... */` comment into a tree: the main description, then the
+//! block tags such as `@param` and `@return`, each made of text, inline tags
+//! such as `{@code ...}`, and HTML elements. The leading `*` of each line is
+//! left out, and every node keeps its range in the file, so rules can report
+//! on the exact text they find fault with.
+//!
+//! The parser never fails: an inline tag without its `}` or an HTML element
+//! without its end tag is kept and marked as such, for the rules that look
+//! for them.
+
+mod parser;
+
+use lintal_text_size::{TextRange, TextSize};
+
+/// A parsed Javadoc comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Javadoc<'a> {
+    /// The comment, from `/**` to `*/`.
+    pub range: TextRange,
+    /// The main description, before the first block tag.
+    pub description: Vec<Node<'a>>,
+    /// The block tags, in source order.
+    pub block_tags: Vec<BlockTag<'a>>,
+}
+
+impl<'a> Javadoc<'a> {
+    /// Parse `comment`, the text of a comment starting at `offset` in its
+    /// file; `None` if it is not a Javadoc comment.
+    pub fn parse(comment: &'a str, offset: TextSize) -> Option<Self> {
+        if !comment.starts_with("/**") || comment == "/**/" {
+            return None;
+        }
+        Some(parser::Parser::new(comment, offset).parse())
+    }
+
+    /// Whether the comment has neither a description nor block tags.
+    pub fn is_empty(&self) -> bool {
+        is_blank(&self.description) && self.block_tags.is_empty()
+    }
+
+    /// The inline tags of the whole comment, in source order.
+    pub fn inline_tags(&self) -> Vec<&InlineTag<'a>> {
+        fn collect<'n, 'a>(nodes: &'n [Node<'a>], tags: &mut Vec<&'n InlineTag<'a>>) {
+            for node in nodes {
+                match node {
+                    Node::InlineTag(tag) => tags.push(tag),
+                    Node::Html(element) => collect(&element.children, tags),
+                    _ => {}
+                }
+            }
+        }
+
+        let mut tags = vec![];
+        collect(&self.description, &mut tags);
+        for block_tag in &self.block_tags {
+            collect(&block_tag.description, &mut tags);
+        }
+        tags
+    }
+}
+
+/// A block tag, such as `@param name the description`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockTag<'a> {
+    /// The tag name without `@`, e.g. `param`.
+    pub name: &'a str,
+    /// The tag name with its `@`.
+    pub name_range: TextRange,
+    /// The parameter or exception named by `@param`, `@throws` and
+    /// `@exception`.
+    pub argument: Option<Text<'a>>,
+    /// The text after the name and argument.
+    pub description: Vec<Node<'a>>,
+    /// The whole tag, from its `@` to the end of its description.
+    pub range: TextRange,
+}
+
+/// A part of a description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node<'a> {
+    /// Text within one line.
+    Text(Text<'a>),
+    /// A line break, with the leading `*` of the next line.
+    Newline(TextRange),
+    /// An inline tag, such as `{@code x}`.
+    InlineTag(InlineTag<'a>),
+    /// An HTML element, such as `<p>` or `<b>bold</b>`.
+    Html(HtmlElement<'a>),
+    /// An end tag closing no open element, such as a lone `</p>`.
+    HtmlEndTag(HtmlEndTag<'a>),
+}
+
+impl Node<'_> {
+    pub fn range(&self) -> TextRange {
+        match self {
+            Node::Text(text) => text.range,
+            Node::Newline(range) => *range,
+            Node::InlineTag(tag) => tag.range,
+            Node::Html(element) => element.range(),
+            Node::HtmlEndTag(tag) => tag.range,
+        }
+    }
+}
+
+/// Text and where it is in the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Text<'a> {
+    pub text: &'a str,
+    pub range: TextRange,
+}
+
+/// An inline tag, such as `{@link Foo#bar() bar}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineTag<'a> {
+    /// The tag name without `@`, e.g. `link`.
+    pub name: &'a str,
+    /// The tag name with its `@`.
+    pub name_range: TextRange,
+    /// The text between the name and the closing `}`, without the leading
+    /// `*` of the lines it continues on.
+    pub content: String,
+    /// From `{` to `}`, or to the end of the text an unclosed tag runs to.
+    pub range: TextRange,
+    /// Whether the tag has its closing `}`.
+    pub is_closed: bool,
+}
+
+/// Elements that have no content and no end tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// An HTML element with the nodes inside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlElement<'a> {
+    /// The element name as written, e.g. `p`.
+    pub name: &'a str,
+    /// The start tag, e.g. `<a href="x">`.
+    pub start_tag: TextRange,
+    /// The end tag, `None` for void and self-closing elements and elements
+    /// left open.
+    pub end_tag: Option<TextRange>,
+    /// Whether the start tag closes itself, as in `<br/>`.
+    pub is_self_closing: bool,
+    pub children: Vec<Node<'a>>,
+}
+
+impl HtmlElement<'_> {
+    /// Whether the element is one of those that never have an end tag, such
+    /// as `<br>`.
+    pub fn is_void(&self) -> bool {
+        VOID_ELEMENTS
+            .iter()
+            .any(|void| void.eq_ignore_ascii_case(self.name))
+    }
+
+    /// From the start tag to the end tag, or the last node inside an element
+    /// left open.
+    pub fn range(&self) -> TextRange {
+        match (self.end_tag, self.children.last()) {
+            (Some(end_tag), _) => self.start_tag.cover(end_tag),
+            (None, Some(last)) => self.start_tag.cover(last.range()),
+            (None, None) => self.start_tag,
+        }
+    }
+}
+
+/// An HTML end tag, such as `</p>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HtmlEndTag<'a> {
+    /// The element name as written, e.g. `p`.
+    pub name: &'a str,
+    pub range: TextRange,
+}
+
+/// The text of `nodes` roughly as Javadoc renders it: the text and the
+/// content of inline tags, with lines joined by `\n` and HTML tags left out.
+pub fn plain_text(nodes: &[Node]) -> String {
+    let mut text = String::new();
+    for node in nodes {
+        match node {
+            Node::Text(t) => text.push_str(t.text),
+            Node::Newline(_) => text.push('\n'),
+            Node::InlineTag(tag) => text.push_str(&tag.content),
+            Node::Html(element) => text.push_str(&plain_text(&element.children)),
+            Node::HtmlEndTag(_) => {}
+        }
+    }
+    text
+}
+
+/// Whether `nodes` hold nothing but whitespace and HTML tags.
+pub fn is_blank(nodes: &[Node]) -> bool {
+    nodes.iter().all(|node| match node {
+        Node::Text(text) => text.text.trim().is_empty(),
+        Node::Newline(_) | Node::HtmlEndTag(_) => true,
+        Node::InlineTag(_) => false,
+        Node::Html(element) => is_blank(&element.children),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(comment: &str) -> Javadoc<'_> {
+        Javadoc::parse(comment, TextSize::default()).unwrap()
+    }
+
+    /// The HTML structure of `nodes`, e.g. `p(b)`.
+    fn outline(nodes: &[Node]) -> Vec<String> {
+        nodes
+            .iter()
+            .filter_map(|node| match node {
+                Node::Html(element) => {
+                    let close = if element.end_tag.is_some() { "" } else { "!" };
+                    Some(format!(
+                        "{}{close}({})",
+                        element.name,
+                        outline(&element.children).join(" ")
+                    ))
+                }
+                Node::HtmlEndTag(tag) => Some(format!("/{}", tag.name)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_not_javadoc() {
+        assert!(Javadoc::parse("/* block */", TextSize::default()).is_none());
+        assert!(Javadoc::parse("/**/", TextSize::default()).is_none());
+        assert!(parse("/** */").is_empty());
+        assert!(parse("/**\n *\n */").is_empty());
+    }
+
+    #[test]
+    fn test_description_and_block_tags() {
+        let javadoc = parse(
+            "/**
+     * Returns the sum.
+     * Of two numbers.
+     *
+     * @param a the first
+     * @param b
+     * @return the sum
+     *     of both
+     * @author nobody
+     */",
+        );
+        assert_eq!(
+            plain_text(&javadoc.description).trim(),
+            "Returns the sum.\n Of two numbers."
+        );
+
+        let tags: Vec<_> = javadoc
+            .block_tags
+            .iter()
+            .map(|tag| {
+                (
+                    tag.name,
+                    tag.argument.map(|arg| arg.text),
+                    plain_text(&tag.description).trim().to_string(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            tags,
+            [
+                ("param", Some("a"), "the first".to_string()),
+                ("param", Some("b"), String::new()),
+                ("return", None, "the sum\n     of both".to_string()),
+                ("author", None, "nobody".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_argument() {
+        let javadoc = parse(
+            "/**\n * @param <T>\n *     the type\n * @throws\n * IOException if it fails\n */",
+        );
+        let arguments: Vec<_> = javadoc
+            .block_tags
+            .iter()
+            .map(|tag| tag.argument.map(|arg| arg.text))
+            .collect();
+        assert_eq!(arguments, [Some("<T>"), Some("IOException")]);
+        assert!(
+            javadoc
+                .block_tags
+                .iter()
+                .all(|tag| !is_blank(&tag.description))
+        );
+    }
+
+    #[test]
+    fn test_inline_tags() {
+        let javadoc = parse(
+            "/**
+     * Uses {@code Map<K, {V}>} and {@link Foo#bar(int) bar}.
+     * {@code first
+     *     second}
+     * {@literal unclosed
+     * @return {@inheritDoc}
+     */",
+        );
+        let tags: Vec<_> = javadoc
+            .inline_tags()
+            .iter()
+            .map(|tag| (tag.name, tag.content.as_str(), tag.is_closed))
+            .collect();
+        assert_eq!(
+            tags,
+            [
+                ("code", "Map<K, {V}>", true),
+                ("link", "Foo#bar(int) bar", true),
+                ("code", "first\n     second", true),
+                ("literal", "unclosed", false),
+                ("inheritDoc", "", true),
+            ]
+        );
+        assert!(outline(&javadoc.description).is_empty());
+        assert_eq!(javadoc.block_tags.len(), 1);
+    }
+
+    #[test]
+    fn test_inline_tag_across_block_tag_line() {
+        let javadoc = parse("/**\n * {@code\n * @Override\n * }\n */");
+        assert!(javadoc.block_tags.is_empty());
+        assert_eq!(javadoc.inline_tags()[0].content, "@Override\n ");
+    }
+
+    #[test]
+    fn test_html() {
+        let javadoc = parse(
+            "/**
+     * <p>First <b>bold</b>, <a href=\"x>y\">link</a>
+     * <ul><li>one<li>two</li></ul>
+     * </div><br><img src='x'/>
+     */",
+        );
+        assert_eq!(
+            outline(&javadoc.description),
+            ["p!(b() a() ul(li!(li())) /div br!() img!())"]
+        );
+        let Some(Node::Html(p)) = javadoc
+            .description
+            .iter()
+            .find(|n| matches!(n, Node::Html(_)))
+        else {
+            panic!("expected <p>");
+        };
+        let images: Vec<_> = p
+            .children
+            .iter()
+            .filter_map(|node| match node {
+                Node::Html(element) if element.end_tag.is_none() => {
+                    Some((element.name, element.is_void(), element.is_self_closing))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(images, [("br", true, false), ("img", true, true)]);
+    }
+
+    #[test]
+    fn test_not_html() {
+        let javadoc = parse("/** a < b, 1<2 and x<-y. */");
+        assert!(outline(&javadoc.description).is_empty());
+        assert_eq!(plain_text(&javadoc.description), " a < b, 1<2 and x<-y.");
+    }
+
+    #[test]
+    fn test_ranges() {
+        let source = "class A {\n    /**\n     * Hi {@code x}.\n     * @param y the <b>y</b>\n     */\n    void f(int y) {}\n}\n";
+        let start = source.find("/**").unwrap();
+        let end = source.find("*/").unwrap() + 2;
+        let javadoc =
+            Javadoc::parse(&source[start..end], TextSize::try_from(start).unwrap()).unwrap();
+
+        assert_eq!(&source[javadoc.range], &source[start..end]);
+        assert_eq!(&source[javadoc.inline_tags()[0].range], "{@code x}");
+        let tag = &javadoc.block_tags[0];
+        assert_eq!(&source[tag.name_range], "@param");
+        assert_eq!(tag.argument.map(|arg| &source[arg.range]), Some("y"));
+        assert_eq!(&source[tag.range], "@param y the <b>y</b>");
+        let newline = javadoc
+            .description
+            .iter()
+            .find(|node| matches!(node, Node::Newline(_)));
+        assert!(newline.is_none(), "no line break at the end of a section");
+    }
+}
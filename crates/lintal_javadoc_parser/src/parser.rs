@@ -0,0 +1,408 @@
+//! The Javadoc parser: splits the comment into lines without their leading
+//! `*`, then reads the lines as a stream of text, inline tags and HTML tags,
+//! starting a new section at each block tag.
+
+use lintal_text_size::{TextRange, TextSize};
+
+use crate::{BlockTag, HtmlElement, HtmlEndTag, InlineTag, Javadoc, Node, Text};
+
+/// Block tags whose first word names what they document.
+const TAGS_WITH_ARGUMENT: &[&str] = &["param", "throws", "exception"];
+
+pub(crate) struct Parser<'a> {
+    comment: &'a str,
+    offset: TextSize,
+    /// The content of each line, as byte offsets into `comment`: without
+    /// `/**` and `*/`, the leading `*` and the whitespace around the line.
+    lines: Vec<(usize, usize)>,
+}
+
+/// Where the nodes of the description or a block tag go, with the HTML
+/// elements still open.
+#[derive(Default)]
+struct Section<'a> {
+    nodes: Vec<Node<'a>>,
+    /// Open elements, innermost last.
+    open: Vec<HtmlElement<'a>>,
+    /// Line breaks waiting for the next node, dropped at the end of the
+    /// section.
+    line_breaks: Vec<TextRange>,
+    /// The end of the last node.
+    end: Option<TextSize>,
+}
+
+impl<'a> Section<'a> {
+    fn target(&mut self) -> &mut Vec<Node<'a>> {
+        match self.open.last_mut() {
+            Some(element) => &mut element.children,
+            None => &mut self.nodes,
+        }
+    }
+
+    fn line_break(&mut self, range: TextRange) {
+        if self.end.is_some() {
+            self.line_breaks.push(range);
+        }
+    }
+
+    fn flush_line_breaks(&mut self) {
+        let line_breaks = std::mem::take(&mut self.line_breaks);
+        self.target()
+            .extend(line_breaks.into_iter().map(Node::Newline));
+    }
+
+    fn push(&mut self, node: Node<'a>) {
+        self.flush_line_breaks();
+        self.end = Some(node.range().end());
+        self.target().push(node);
+    }
+
+    fn start_element(&mut self, element: HtmlElement<'a>) {
+        if element.is_self_closing || element.is_void() {
+            self.push(Node::Html(element));
+        } else {
+            self.flush_line_breaks();
+            self.end = Some(element.start_tag.end());
+            self.open.push(element);
+        }
+    }
+
+    fn end_element(&mut self, name: &'a str, range: TextRange) {
+        let Some(index) = self
+            .open
+            .iter()
+            .rposition(|element| element.name.eq_ignore_ascii_case(name))
+        else {
+            self.push(Node::HtmlEndTag(HtmlEndTag { name, range }));
+            return;
+        };
+
+        self.flush_line_breaks();
+        // Elements opened inside it are left unclosed
+        while self.open.len() > index + 1 {
+            self.close_innermost();
+        }
+        if let Some(element) = self.open.last_mut() {
+            element.end_tag = Some(range);
+        }
+        self.close_innermost();
+        self.end = Some(range.end());
+    }
+
+    fn close_innermost(&mut self) {
+        if let Some(element) = self.open.pop() {
+            self.target().push(Node::Html(element));
+        }
+    }
+
+    fn finish(mut self) -> (Vec<Node<'a>>, Option<TextSize>) {
+        while !self.open.is_empty() {
+            self.close_innermost();
+        }
+        (self.nodes, self.end)
+    }
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn new(comment: &'a str, offset: TextSize) -> Self {
+        let body_end = if comment.len() >= 5 && comment.ends_with("*/") {
+            comment.len() - 2
+        } else {
+            comment.len()
+        };
+
+        let mut lines = vec![];
+        let mut start = 3;
+        while start <= body_end {
+            let end = comment[start..body_end]
+                .find('\n')
+                .map_or(body_end, |i| start + i);
+            // Trailing whitespace, `\r` included, is not content
+            let line = comment[start..end].trim_end();
+            let content_start = if lines.is_empty() {
+                start
+            } else {
+                start + decoration_len(line)
+            };
+            lines.push((content_start, start + line.len()));
+            start = end + 1;
+        }
+
+        Self {
+            comment,
+            offset,
+            lines,
+        }
+    }
+
+    pub(crate) fn parse(self) -> Javadoc<'a> {
+        let bytes = self.comment.as_bytes();
+        let mut description = Section::default();
+        let mut tags: Vec<(BlockTag<'a>, Section<'a>)> = vec![];
+        let mut awaiting_argument = false;
+
+        let mut line = 0;
+        let mut pos = self.lines.first().map_or(0, |&(start, _)| start);
+        let mut line_start = true;
+        while line < self.lines.len() {
+            let (start, end) = self.lines[line];
+
+            if line_start {
+                line_start = false;
+                if let Some(name) = self.block_tag_name(line) {
+                    let at = name.range.start() - self.offset - TextSize::from(1);
+                    let at = usize::from(at);
+                    let name_range = self.range(at, at + 1 + name.text.len());
+                    tags.push((
+                        BlockTag {
+                            name: name.text,
+                            name_range,
+                            argument: None,
+                            description: vec![],
+                            range: name_range,
+                        },
+                        Section::default(),
+                    ));
+                    awaiting_argument = TAGS_WITH_ARGUMENT.contains(&name.text);
+                    pos = usize::from(name_range.end() - self.offset);
+                } else if line > 0 {
+                    let section = tags.last_mut().map_or(&mut description, |(_, s)| s);
+                    section.line_break(self.range(self.lines[line - 1].1, start));
+                }
+
+                if awaiting_argument
+                    && let Some((tag, _)) = tags.last_mut()
+                    && let Some(argument) = self.first_word(pos, end)
+                {
+                    tag.argument = Some(argument);
+                    tag.range = tag.range.cover(argument.range);
+                    pos = usize::from(argument.range.end() - self.offset);
+                    awaiting_argument = false;
+                }
+            }
+
+            let section = tags.last_mut().map_or(&mut description, |(_, s)| s);
+            let mut text_start = pos;
+            let mut next = None;
+            let mut i = pos;
+            while i < end {
+                if bytes[i] == b'{'
+                    && bytes.get(i + 1) == Some(&b'@')
+                    && bytes.get(i + 2).is_some_and(u8::is_ascii_alphabetic)
+                {
+                    self.push_text(section, text_start, i);
+                    let (tag, after) = self.inline_tag(line, i);
+                    section.push(Node::InlineTag(tag));
+                    next = Some(after);
+                    break;
+                }
+                if bytes[i] == b'<'
+                    && let Some((name, is_end, tag_end)) = self.html_tag(i, end)
+                {
+                    self.push_text(section, text_start, i);
+                    let range = self.range(i, tag_end);
+                    if is_end {
+                        section.end_element(name, range);
+                    } else {
+                        section.start_element(HtmlElement {
+                            name,
+                            start_tag: range,
+                            end_tag: None,
+                            is_self_closing: bytes[tag_end - 2] == b'/',
+                            children: vec![],
+                        });
+                    }
+                    i = tag_end;
+                    text_start = i;
+                    continue;
+                }
+                i += 1;
+            }
+
+            match next {
+                Some((after_line, after_pos)) => {
+                    line = after_line;
+                    pos = after_pos;
+                }
+                None => {
+                    self.push_text(section, text_start, end);
+                    line += 1;
+                    pos = self.lines.get(line).map_or(end, |&(start, _)| start);
+                    line_start = true;
+                }
+            }
+        }
+
+        let (description, _) = description.finish();
+        let block_tags = tags
+            .into_iter()
+            .map(|(mut tag, section)| {
+                let (nodes, end) = section.finish();
+                if let Some(end) = end {
+                    tag.range = TextRange::new(tag.range.start(), end);
+                }
+                tag.description = nodes;
+                tag
+            })
+            .collect();
+
+        Javadoc {
+            range: self.range(0, self.comment.len()),
+            description,
+            block_tags,
+        }
+    }
+
+    fn range(&self, start: usize, end: usize) -> TextRange {
+        let at = |offset: usize| self.offset + TextSize::from(offset as u32);
+        TextRange::new(at(start), at(end))
+    }
+
+    fn text(&self, start: usize, end: usize) -> Text<'a> {
+        Text {
+            text: &self.comment[start..end],
+            range: self.range(start, end),
+        }
+    }
+
+    fn push_text(&self, section: &mut Section<'a>, start: usize, end: usize) {
+        if start < end {
+            section.push(Node::Text(self.text(start, end)));
+        }
+    }
+
+    /// The name of the block tag starting `line`, if it starts one: an `@`
+    /// and a name as the first text on the line.
+    fn block_tag_name(&self, line: usize) -> Option<Text<'a>> {
+        let (start, end) = self.lines[line];
+        let content = &self.comment[start..end];
+        let at = start + content.len() - content.trim_start().len();
+        let name = self.comment[at..end].strip_prefix('@')?;
+        let len = name.bytes().take_while(u8::is_ascii_alphanumeric).count();
+        name.starts_with(|c: char| c.is_ascii_alphabetic())
+            .then(|| self.text(at + 1, at + 1 + len))
+    }
+
+    /// The first word between `start` and `end`.
+    fn first_word(&self, start: usize, end: usize) -> Option<Text<'a>> {
+        let content = &self.comment[start..end];
+        let word_start = start + content.len() - content.trim_start().len();
+        let word_end = self.comment[word_start..end]
+            .find(char::is_whitespace)
+            .map_or(end, |i| word_start + i);
+        (word_start < word_end).then(|| self.text(word_start, word_end))
+    }
+
+    /// The inline tag whose `{` is at `at` on `line`, and the line and
+    /// offset after it.
+    ///
+    /// The tag runs to the `}` matching its `{`, perhaps on a later line. An
+    /// unclosed tag runs to the end of the comment, or the line before the
+    /// next block tag.
+    fn inline_tag(&self, line: usize, at: usize) -> (InlineTag<'a>, (usize, usize)) {
+        let bytes = self.comment.as_bytes();
+        let name_start = at + 2;
+        let name_len = bytes[name_start..]
+            .iter()
+            .take_while(|b| b.is_ascii_alphanumeric())
+            .count();
+        let name = &self.comment[name_start..name_start + name_len];
+
+        let mut pieces: Vec<&str> = vec![];
+        let mut first_block_tag = None;
+        let mut depth = 1;
+        for (index, &(start, end)) in self.lines.iter().enumerate().skip(line) {
+            let start = if index == line {
+                name_start + name_len
+            } else {
+                if first_block_tag.is_none() && self.block_tag_name(index).is_some() {
+                    first_block_tag = Some((index, pieces.len()));
+                }
+                start
+            };
+            for i in start..end {
+                match bytes[i] {
+                    b'{' => depth += 1,
+                    b'}' => depth -= 1,
+                    _ => {}
+                }
+                if depth == 0 {
+                    pieces.push(&self.comment[start..i]);
+                    let tag = self.inline_tag_of(name, at, i + 1, &pieces, true);
+                    return (tag, (index, i + 1));
+                }
+            }
+            pieces.push(&self.comment[start..end]);
+        }
+
+        // Unclosed: stop short of the next block tag
+        let (last_line, last_piece) = match first_block_tag {
+            Some((index, piece)) => (index - 1, piece),
+            None => (self.lines.len() - 1, pieces.len()),
+        };
+        let end = self.lines[last_line].1.max(name_start + name_len);
+        let tag = self.inline_tag_of(name, at, end, &pieces[..last_piece], false);
+        (tag, (last_line, end))
+    }
+
+    fn inline_tag_of(
+        &self,
+        name: &'a str,
+        start: usize,
+        end: usize,
+        pieces: &[&str],
+        is_closed: bool,
+    ) -> InlineTag<'a> {
+        InlineTag {
+            name,
+            name_range: self.range(start + 1, start + 2 + name.len()),
+            content: pieces.join("\n").trim_start().to_string(),
+            range: self.range(start, end),
+            is_closed,
+        }
+    }
+
+    /// The HTML tag whose `<` is at `at`: its name, whether it is an end tag,
+    /// and where it ends. Only tags within the line count.
+    fn html_tag(&self, at: usize, end: usize) -> Option<(&'a str, bool, usize)> {
+        let bytes = self.comment.as_bytes();
+        let is_end = bytes.get(at + 1) == Some(&b'/');
+        let name_start = at + 1 + usize::from(is_end);
+        let name_end = name_start
+            + bytes[name_start..end]
+                .iter()
+                .take_while(|b| b.is_ascii_alphanumeric() || **b == b'-')
+                .count();
+        if name_start == name_end || !bytes[name_start].is_ascii_alphabetic() {
+            return None;
+        }
+
+        let mut quote = None;
+        for i in name_end..end {
+            match (quote, bytes[i]) {
+                (Some(q), b) if b == q => quote = None,
+                (Some(_), _) => {}
+                (None, b'"' | b'\'') => quote = Some(bytes[i]),
+                (None, b'>') => return Some((&self.comment[name_start..name_end], is_end, i + 1)),
+                (None, b'<') => return None,
+                (None, b) if i == name_end && !b.is_ascii_whitespace() && b != b'/' => {
+                    return None;
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+/// The length of the whitespace and `*`s starting a continuation line, or
+/// zero if the line has no `*` and its indentation is content.
+fn decoration_len(line: &str) -> usize {
+    let trimmed = line.trim_start();
+    let stars = trimmed.len() - trimmed.trim_start_matches('*').len();
+    if stars > 0 {
+        line.len() - trimmed.len() + stars
+    } else {
+        0
+    }
+}
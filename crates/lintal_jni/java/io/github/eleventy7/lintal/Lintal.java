@@ -0,0 +1,63 @@
+package io.github.eleventy7.lintal;
+
+/**
+ * Lints Java sources in-process through the {@code lintal_jni} native library.
+ *
+ * <p>Diagnostics are returned as a JSON array of objects with {@code path},
+ * {@code rule}, {@code code}, {@code message}, {@code line}, {@code column},
+ * {@code endLine}, {@code endColumn} and {@code fixable}.
+ *
+ * <p>An instance may be used from several threads at once, but must not be
+ * closed while a call is in progress.
+ */
+public final class Lintal implements AutoCloseable {
+    static {
+        System.loadLibrary("lintal_jni");
+    }
+
+    private volatile long handle;
+
+    /**
+     * Loads the rules of a checkstyle.xml, or the default rules if
+     * {@code configPath} is null.
+     *
+     * @throws IllegalArgumentException if the configuration can't be loaded
+     */
+    public Lintal(String configPath) {
+        handle = nativeOpen(configPath);
+    }
+
+    /**
+     * Lints Java source text.
+     *
+     * @throws IllegalArgumentException if the source can't be parsed
+     */
+    public String lintSource(String source) {
+        return nativeLintSource(handle, source);
+    }
+
+    /**
+     * Lints a Java file, decoded with the configured charset.
+     *
+     * @throws java.io.IOException if the file can't be read or decoded
+     * @throws IllegalArgumentException if the file can't be parsed
+     */
+    public String lintFile(String path) throws java.io.IOException {
+        return nativeLintFile(handle, path);
+    }
+
+    @Override
+    public synchronized void close() {
+        long current = handle;
+        handle = 0;
+        nativeClose(current);
+    }
+
+    private static native long nativeOpen(String configPath);
+
+    private static native String nativeLintSource(long handle, String source);
+
+    private static native String nativeLintFile(long handle, String path) throws java.io.IOException;
+
+    private static native void nativeClose(long handle);
+}
@@ -0,0 +1,366 @@
+//! JNI bindings for lintal.
+//!
+//! Lets a Gradle or Maven plugin lint from inside the build JVM rather than
+//! spawning `lintal` for every module. The Java side is
+//! `java/io/github/eleventy7/lintal/Lintal.java`:
+//!
+//! ```java
+//! try (Lintal lintal = new Lintal("config/checkstyle/checkstyle.xml")) {
+//!     String json = lintal.lintFile("src/main/java/com/example/Foo.java");
+//! }
+//! ```
+//!
+//! A `Lintal` holds the configured rules, so the configuration is loaded once
+//! rather than for every file. Diagnostics are returned as a JSON array of
+//! `{path, rule, message, line, column, endLine, endColumn, fixable}`, with
+//! lines and columns as the CLI reports them. Failures are thrown as
+//! `IllegalArgumentException` for a bad configuration or unparseable source,
+//! and `IOException` for a file that can't be read.
+
+use std::any::Any;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::path::{Path, PathBuf};
+
+use jni::JNIEnv;
+use jni::objects::{JClass, JString};
+use jni::sys::{jlong, jstring};
+use lintal_checkstyle::{CheckstyleConfig, CheckstyleError, MergedConfig};
+use lintal_diagnostics::Applicability;
+use lintal_linter::{Rule, RuleDiagnostic, RuleError, RuleRegistry};
+use lintal_source_file::{Charset, DecodeError, LineIndex, PositionEncoding};
+use serde::Serialize;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("{}: {source}", .path.display())]
+    Config {
+        path: PathBuf,
+        source: CheckstyleError,
+    },
+    #[error(transparent)]
+    Rule(#[from] RuleError),
+    #[error("Unsupported charset '{0}'")]
+    Charset(String),
+    #[error("{}: {source}", .path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("{}: {source}", .path.display())]
+    Decode { path: PathBuf, source: DecodeError },
+    #[error("{}: Failed to parse", .0.display())]
+    ParseFile(PathBuf),
+    #[error("Failed to parse source")]
+    ParseSource,
+    #[error("Lintal has been closed")]
+    Closed,
+    #[error(transparent)]
+    Jni(#[from] jni::errors::Error),
+}
+
+impl Error {
+    /// The Java exception thrown for this error.
+    fn exception_class(&self) -> &'static str {
+        match self {
+            Error::Io { .. } | Error::Decode { .. } => "java/io/IOException",
+            Error::Closed => "java/lang/IllegalStateException",
+            Error::Jni(_) => "java/lang/RuntimeException",
+            _ => "java/lang/IllegalArgumentException",
+        }
+    }
+}
+
+/// A diagnostic as serialized for Java.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonDiagnostic {
+    /// The file the violation is in, for diagnostics from [`Linter::lint_file`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    pub rule: String,
+    /// The rule's short code, e.g. `WS001`.
+    pub code: String,
+    pub message: String,
+    /// One-based line and column of the start of the violation.
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    /// Whether `lintal fix` can fix the violation.
+    pub fixable: bool,
+}
+
+/// Rules and settings loaded from a configuration; what a Java `Lintal`
+/// holds a handle to.
+pub struct Linter {
+    rules: Vec<Box<dyn Rule>>,
+    tab_width: usize,
+    charset: Charset,
+}
+
+impl Linter {
+    /// Load the rules of a checkstyle.xml, or the rules the CLI uses when it
+    /// finds none.
+    pub fn load(config: Option<&Path>) -> Result<Self, Error> {
+        let Some(path) = config else {
+            let defaults: Vec<Box<dyn Rule>> =
+                vec![Box::new(lintal_linter::rules::WhitespaceAround::default())];
+            return Ok(Self {
+                rules: defaults,
+                tab_width: MergedConfig::DEFAULT_TAB_WIDTH,
+                charset: Charset::default(),
+            });
+        };
+
+        let checkstyle = CheckstyleConfig::from_file(path).map_err(|source| Error::Config {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let config = MergedConfig::new(&checkstyle, None);
+        let rules = RuleRegistry::builtin().create_rules(&config)?;
+        let charset = match config.charset.as_deref() {
+            None => Charset::default(),
+            Some(name) => Charset::from_name(name).ok_or_else(|| Error::Charset(name.into()))?,
+        };
+        Ok(Self {
+            rules,
+            tab_width: config.tab_width,
+            charset,
+        })
+    }
+
+    /// Lint Java source text.
+    pub fn lint_source(&self, source: &str) -> Result<Vec<JsonDiagnostic>, Error> {
+        self.lint(source, None).ok_or(Error::ParseSource)
+    }
+
+    /// Lint a Java file, decoding it with the configured charset.
+    pub fn lint_file(&self, path: &Path) -> Result<Vec<JsonDiagnostic>, Error> {
+        let bytes = std::fs::read(path).map_err(|source| Error::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let decoded =
+            lintal_source_file::decode(&bytes, self.charset).map_err(|source| Error::Decode {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        self.lint(&decoded.text, Some(path))
+            .ok_or_else(|| Error::ParseFile(path.to_path_buf()))
+    }
+
+    fn lint(&self, source: &str, path: Option<&Path>) -> Option<Vec<JsonDiagnostic>> {
+        let diagnostics = match path {
+            Some(path) => lintal_linter::lint_source_at(source, path, &self.rules)?,
+            None => lintal_linter::lint_source(source, &self.rules)?,
+        };
+        let index = LineIndex::from_source_text(source);
+        Some(
+            diagnostics
+                .into_iter()
+                .map(|diagnostic| self.to_json(source, &index, path, diagnostic))
+                .collect(),
+        )
+    }
+
+    fn to_json(
+        &self,
+        source: &str,
+        index: &LineIndex,
+        path: Option<&Path>,
+        rule_diagnostic: RuleDiagnostic,
+    ) -> JsonDiagnostic {
+        let RuleDiagnostic { rule, diagnostic } = rule_diagnostic;
+        let encoding = self
+            .rules
+            .iter()
+            .find(|r| r.name() == rule)
+            .map_or(PositionEncoding::Utf32, |r| r.column_encoding());
+        let start =
+            index.checkstyle_column(diagnostic.range.start(), source, self.tab_width, encoding);
+        let end = index.checkstyle_column(diagnostic.range.end(), source, self.tab_width, encoding);
+        JsonDiagnostic {
+            path: path.map(Path::to_path_buf),
+            rule: rule.to_string(),
+            code: diagnostic.kind.code,
+            message: diagnostic.kind.body,
+            line: start.line.get(),
+            column: start.column.get(),
+            end_line: end.line.get(),
+            end_column: end.column.get(),
+            fixable: diagnostic
+                .fix
+                .as_ref()
+                .is_some_and(|fix| fix.applies(Applicability::Safe)),
+        }
+    }
+}
+
+/// `Lintal.nativeOpen(String configPath)`: load a configuration, returning a
+/// handle for the other calls. A null path loads the default rules.
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_io_github_eleventy7_lintal_Lintal_nativeOpen<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    config: JString<'local>,
+) -> jlong {
+    call(&mut env, 0, |env| {
+        let config = optional_string(env, &config)?;
+        let linter = Linter::load(config.as_deref().map(Path::new))?;
+        Ok(Box::into_raw(Box::new(linter)) as jlong)
+    })
+}
+
+/// `Lintal.nativeLintSource(long handle, String source)`: lint source text,
+/// returning its diagnostics as JSON.
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_io_github_eleventy7_lintal_Lintal_nativeLintSource<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+    source: JString<'local>,
+) -> jstring {
+    call(&mut env, std::ptr::null_mut(), |env| {
+        let linter = linter(handle)?;
+        let source: String = env.get_string(&source)?.into();
+        let diagnostics = linter.lint_source(&source)?;
+        to_java(env, &diagnostics)
+    })
+}
+
+/// `Lintal.nativeLintFile(long handle, String path)`: lint a file, returning
+/// its diagnostics as JSON.
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_io_github_eleventy7_lintal_Lintal_nativeLintFile<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+    path: JString<'local>,
+) -> jstring {
+    call(&mut env, std::ptr::null_mut(), |env| {
+        let linter = linter(handle)?;
+        let path: String = env.get_string(&path)?.into();
+        let diagnostics = linter.lint_file(Path::new(&path))?;
+        to_java(env, &diagnostics)
+    })
+}
+
+/// `Lintal.nativeClose(long handle)`: free a handle from `nativeOpen`.
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_io_github_eleventy7_lintal_Lintal_nativeClose<'local>(
+    _env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+) {
+    if handle != 0 {
+        // SAFETY: the Java class hands each handle from `nativeOpen` to
+        // `nativeClose` once, and never uses it afterwards
+        drop(unsafe { Box::from_raw(handle as *mut Linter) });
+    }
+}
+
+fn linter<'a>(handle: jlong) -> Result<&'a Linter, Error> {
+    if handle == 0 {
+        return Err(Error::Closed);
+    }
+    // SAFETY: non-zero handles come from `nativeOpen` and stay valid until
+    // `nativeClose`
+    Ok(unsafe { &*(handle as *const Linter) })
+}
+
+fn optional_string(env: &mut JNIEnv<'_>, string: &JString<'_>) -> Result<Option<String>, Error> {
+    if string.is_null() {
+        return Ok(None);
+    }
+    Ok(Some(env.get_string(string)?.into()))
+}
+
+fn to_java(env: &mut JNIEnv<'_>, diagnostics: &[JsonDiagnostic]) -> Result<jstring, Error> {
+    // Serializing plain structs can only fail on non-UTF-8 paths, which
+    // can't have come from a Java string
+    let json = serde_json::to_string(diagnostics).unwrap_or_default();
+    Ok(env.new_string(json)?.into_raw())
+}
+
+/// Run `f`, turning errors and panics into Java exceptions. Unwinding into the
+/// JVM is undefined behaviour, and aborting would take the build down with it.
+fn call<'local, T>(
+    env: &mut JNIEnv<'local>,
+    default: T,
+    f: impl FnOnce(&mut JNIEnv<'local>) -> Result<T, Error>,
+) -> T {
+    let (class, message) = match catch_unwind(AssertUnwindSafe(|| f(env))) {
+        Ok(Ok(value)) => return value,
+        Ok(Err(err)) => (err.exception_class(), err.to_string()),
+        Err(panic) => ("java/lang/RuntimeException", panic_message(&*panic)),
+    };
+    // A failed JNI call may already have left an exception pending
+    if !env.exception_check().unwrap_or(false) {
+        let _ = env.throw_new(class, message);
+    }
+    default
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    let message = panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic");
+    format!("lintal panicked: {message}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_source_defaults() {
+        let linter = Linter::load(None).unwrap();
+        let diagnostics = linter.lint_source("class A { int x=1; }").unwrap();
+        assert!(!diagnostics.is_empty());
+        assert!(diagnostics.iter().all(|d| d.rule == "WhitespaceAround"));
+
+        let json = serde_json::to_value(&diagnostics[0]).unwrap();
+        assert_eq!(json["line"], 1);
+        assert!(json.get("endColumn").is_some());
+        assert!(json.get("path").is_none());
+    }
+
+    #[test]
+    fn test_lint_file_with_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("checkstyle.xml");
+        std::fs::write(
+            &config,
+            r#"<module name="Checker"><module name="TreeWalker">
+                <module name="UpperEll"/>
+            </module></module>"#,
+        )
+        .unwrap();
+        let file = dir.path().join("A.java");
+        std::fs::write(&file, "class A {\n    long x = 1l;\n}\n").unwrap();
+
+        let linter = Linter::load(Some(&config)).unwrap();
+        let diagnostics = linter.lint_file(&file).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "UpperEll");
+        assert_eq!(diagnostics[0].code, "ST001");
+        assert_eq!(diagnostics[0].path.as_deref(), Some(file.as_path()));
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (2, 14));
+    }
+
+    #[test]
+    fn test_errors_map_to_java_exceptions() {
+        let missing = Linter::load(None)
+            .unwrap()
+            .lint_file(Path::new("/nonexistent/A.java"))
+            .unwrap_err();
+        assert_eq!(missing.exception_class(), "java/io/IOException");
+        assert_eq!(
+            Error::ParseSource.exception_class(),
+            "java/lang/IllegalArgumentException"
+        );
+    }
+}
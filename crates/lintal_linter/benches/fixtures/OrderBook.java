@@ -0,0 +1,212 @@
+/*
+ * A representative file for benchmarks: a mix of declarations, control flow,
+ * generics, lambdas, comments and deliberate style violations.
+ */
+package com.example.bench;
+
+import java.util.ArrayList;
+import java.util.Comparator;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+import java.util.Optional;
+import java.util.function.Predicate;
+
+public final class OrderBook
+{
+    public static final int MAX_LEVELS = 1024;
+    private static final long NULL_PRICE = -1l;
+    private static int instanceCount;
+
+    private final String symbol;
+    private final Map<Long, Level> bids = new HashMap<>();
+    private final Map<Long, Level> asks = new HashMap<>();
+    private long lastTradePrice = NULL_PRICE;
+    private int tradeCount;
+
+    public OrderBook(final String symbol)
+    {
+        this.symbol = symbol;
+        instanceCount++;
+    }
+
+    public String symbol()
+    {
+        return symbol;
+    }
+
+    /**
+     * Add an order, matching it against the opposite side first.
+     *
+     * @param side     buy or sell.
+     * @param price    limit price in ticks.
+     * @param quantity quantity to trade.
+     * @return the quantity left resting on the book.
+     */
+    public long add(final Side side, final long price, final long quantity)
+    {
+        if (quantity <= 0)
+        {
+            throw new IllegalArgumentException("quantity must be positive: " + quantity);
+        }
+
+        long remaining = quantity;
+        final Map<Long, Level> opposite = side == Side.BUY ? asks : bids;
+        final List<Long> prices = new ArrayList<>(opposite.keySet());
+        prices.sort(side == Side.BUY ? Comparator.naturalOrder() : Comparator.reverseOrder());
+
+        for (final Long levelPrice : prices)
+        {
+            if (remaining == 0 || !crosses(side, price, levelPrice))
+            {
+                break;
+            }
+
+            final Level level = opposite.get(levelPrice);
+            final long traded = Math.min(remaining, level.quantity);
+            level.quantity -= traded;
+            remaining -= traded;
+            lastTradePrice = levelPrice;
+            tradeCount++;
+
+            if (level.quantity == 0)
+            {
+                opposite.remove(levelPrice);
+            }
+        }
+
+        if (remaining > 0)
+        {
+            final Map<Long, Level> book = side == Side.BUY ? bids : asks;
+            if (book.size() >= MAX_LEVELS && !book.containsKey(price))
+            {
+                return remaining;
+            }
+            book.computeIfAbsent(price, Level::new).quantity += remaining;
+        }
+
+        return remaining;
+    }
+
+    public Optional<Long> bestBid()
+    {
+        return bids.keySet().stream().max(Long::compare);
+    }
+
+    public Optional<Long> bestAsk()
+    {
+        return asks.keySet().stream().min(Long::compare);
+    }
+
+    public long spread()
+    {
+        final Optional<Long> bid = bestBid();
+        final Optional<Long> ask = bestAsk();
+        if (bid.isPresent() && ask.isPresent())
+        {
+            return ask.get() - bid.get();
+        }
+        return NULL_PRICE;
+    }
+
+    public int levels(final Predicate<Level> filter)
+    {
+        int count = 0;
+        for (final Level level : bids.values()) { if (filter.test(level)) count++; }
+        for (final Level level : asks.values())
+        {
+            if (filter.test(level))
+            {
+                count++;
+            }
+        }
+        return count;
+    }
+
+    public String describe(int depth)
+    {
+        final StringBuilder sb = new StringBuilder(symbol).append(':');
+        switch (depth)
+        {
+            case 0:
+                sb.append(" empty");
+                break;
+            case 1:
+                sb.append(" top ").append(bestBid().orElse(NULL_PRICE)).append('/').append(bestAsk().orElse(NULL_PRICE));
+                break;
+            default:
+                sb.append(" bids=").append(bids.size()).append(" asks=").append(asks.size());
+        }
+        return sb.toString();
+    }
+
+    public long lastTradePrice()
+    {
+        return lastTradePrice;
+    }
+
+    public int tradeCount()
+    {
+        return tradeCount;
+    }
+
+    public static int instanceCount()
+    {
+        return instanceCount;
+    }
+
+    private static boolean crosses(final Side side, final long price, final long levelPrice)
+    {
+        return side == Side.BUY ? price >= levelPrice : price <= levelPrice;
+    }
+
+    public boolean equals(Object other)
+    {
+        if (this == other) return true;
+        if (!(other instanceof OrderBook))
+        {
+            return false;
+        }
+        final OrderBook that = (OrderBook)other;
+        return symbol.equals(that.symbol);
+    }
+
+    public enum Side
+    {
+        BUY, SELL
+    }
+
+    public static final class Level
+    {
+        final long price;
+        long quantity;
+
+        Level(final long price)
+        {
+            this.price = price;
+        }
+
+        public long price()
+        {
+            return price;
+        }
+
+        public long quantity()
+        {
+            return quantity;
+        }
+
+        @Override
+        public String toString()
+        {
+            try
+            {
+                return "Level{price=" + price + ", quantity=" + quantity + "}";
+            }
+            catch (final RuntimeException ex)
+            {
+            }
+            return "";
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! Benchmarks of parsing and linting, overall and per rule.
+//!
+//! Run with `cargo bench -p lintal_linter`. Files are linted from
+//! `benches/fixtures`, plus every Java file under `LINTAL_BENCH_CORPUS` if
+//! set, e.g. a checkout from `mise run clone-test-repos`. For timings of a
+//! whole project against checkstyle, see `lintal bench`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use lintal_java_parser::JavaParser;
+use lintal_linter::{Rule, RuleRegistry, lint_source};
+use walkdir::WalkDir;
+
+/// Every built-in rule, with default properties.
+fn builtin_rules() -> Vec<Box<dyn Rule>> {
+    let registry = RuleRegistry::builtin();
+    let mut names: Vec<&str> = registry.module_names().collect();
+    names.sort_unstable();
+    names
+        .into_iter()
+        .filter_map(|name| registry.create_rule(name, &HashMap::new()))
+        .collect()
+}
+
+/// The sources to lint, concatenated per directory so each is one benchmark
+/// input.
+fn corpus() -> Vec<(String, Vec<String>)> {
+    let mut dirs = vec![PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("benches/fixtures")];
+    dirs.extend(std::env::var_os("LINTAL_BENCH_CORPUS").map(PathBuf::from));
+    dirs.iter()
+        .map(|dir| (corpus_name(dir), read_java_files(dir)))
+        .collect()
+}
+
+fn corpus_name(dir: &Path) -> String {
+    dir.file_name().map_or_else(
+        || dir.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    )
+}
+
+fn read_java_files(dir: &Path) -> Vec<String> {
+    WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "java"))
+        .filter_map(|entry| std::fs::read_to_string(entry.path()).ok())
+        .collect()
+}
+
+fn bytes(sources: &[String]) -> u64 {
+    sources.iter().map(|source| source.len() as u64).sum()
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (name, sources) in corpus() {
+        group.throughput(Throughput::Bytes(bytes(&sources)));
+        group.bench_function(name, |b| {
+            let mut parser = JavaParser::new();
+            b.iter(|| {
+                for source in &sources {
+                    parser.parse(source);
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
+fn bench_lint(c: &mut Criterion) {
+    let rules = builtin_rules();
+    let mut group = c.benchmark_group("lint");
+    for (name, sources) in corpus() {
+        group.throughput(Throughput::Bytes(bytes(&sources)));
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for source in &sources {
+                    lint_source(source, &rules);
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
+/// Each rule on its own, so a slow rule shows up by name.
+fn bench_rules(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rule");
+    for (corpus, sources) in corpus() {
+        group.throughput(Throughput::Bytes(bytes(&sources)));
+        for rule in builtin_rules() {
+            let id = BenchmarkId::new(rule.name(), &corpus);
+            let rules = vec![rule];
+            group.bench_function(id, |b| {
+                b.iter(|| {
+                    for source in &sources {
+                        lint_source(source, &rules);
+                    }
+                });
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse, bench_lint, bench_rules);
+criterion_main!(benches);
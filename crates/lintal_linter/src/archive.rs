@@ -0,0 +1,200 @@
+//! Java sources inside `.jar` and `.zip` archives.
+//!
+//! An entry is named by the archive's path, `!/` and the entry's name, e.g.
+//! `lib/foo-sources.jar!/com/example/Foo.java`, as the JVM names resources
+//! inside jars. [`Linter::read_source`](crate::Linter::read_source) reads
+//! such paths from the archive, so entries are linted and audited like
+//! files. They can't be written back.
+
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use zip::ZipArchive;
+use zip::result::ZipError;
+
+use crate::SourceError;
+
+/// Separates the archive from the entry in an entry's path.
+const SEPARATOR: &str = "!/";
+
+thread_local! {
+    /// The archive last read on this thread, kept open so that reading its
+    /// entries one at a time doesn't read its index again for each.
+    static OPEN: RefCell<Option<OpenArchive>> = const { RefCell::new(None) };
+}
+
+struct OpenArchive {
+    path: PathBuf,
+    /// When the archive was modified when it was opened; it is opened again
+    /// if it has been replaced since.
+    modified: Option<SystemTime>,
+    zip: ZipArchive<File>,
+}
+
+/// Whether `path` has the extension of an archive entries can be read from.
+pub fn is_archive(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("jar") || ext.eq_ignore_ascii_case("zip"))
+}
+
+/// The path of the entry named `entry` inside `archive`.
+pub fn entry_path(archive: &Path, entry: &str) -> PathBuf {
+    let mut path = archive.as_os_str().to_owned();
+    path.push(SEPARATOR);
+    path.push(entry);
+    PathBuf::from(path)
+}
+
+/// Split the path of an archive entry into the archive and the entry name.
+/// `None` if `path` isn't inside an archive.
+pub fn split_entry_path(path: &Path) -> Option<(&Path, &str)> {
+    let (archive, entry) = path.to_str()?.split_once(SEPARATOR)?;
+    let archive = Path::new(archive);
+    is_archive(archive).then_some((archive, entry))
+}
+
+/// The paths of the `.java` entries of `archive`, sorted.
+pub fn java_entries(archive: &Path) -> Result<Vec<PathBuf>, SourceError> {
+    let file = File::open(archive).map_err(|source| SourceError::Read {
+        path: archive.to_path_buf(),
+        source,
+    })?;
+    let zip = ZipArchive::new(file).map_err(|err| zip_error(archive, err))?;
+    let mut entries: Vec<PathBuf> = zip
+        .file_names()
+        .filter(|name| name.ends_with(".java"))
+        .map(|name| entry_path(archive, name))
+        .collect();
+    entries.sort();
+    Ok(entries)
+}
+
+/// Read the bytes of the entry `entry` of `archive`, whose path is `path`.
+pub(crate) fn read_entry(
+    archive: &Path,
+    entry: &str,
+    path: &Path,
+    max_size: Option<u64>,
+) -> Result<Vec<u8>, SourceError> {
+    OPEN.with_borrow_mut(|open| {
+        let modified = std::fs::metadata(archive)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        let reuse = open
+            .as_ref()
+            .is_some_and(|open| open.path == archive && open.modified == modified);
+        if !reuse {
+            *open = None;
+            let file = File::open(archive).map_err(|source| SourceError::Read {
+                path: path.to_path_buf(),
+                source,
+            })?;
+            *open = Some(OpenArchive {
+                path: archive.to_path_buf(),
+                modified,
+                zip: ZipArchive::new(file).map_err(|err| zip_error(path, err))?,
+            });
+        }
+        let zip = &mut open.as_mut().expect("opened above").zip;
+
+        let mut file = zip.by_name(entry).map_err(|err| zip_error(path, err))?;
+        if let Some(max) = max_size
+            && file.size() > max
+        {
+            return Err(SourceError::TooLarge {
+                path: path.to_path_buf(),
+                size: file.size(),
+                max,
+            });
+        }
+        let mut bytes = Vec::with_capacity(usize::try_from(file.size()).unwrap_or_default());
+        file.read_to_end(&mut bytes)
+            .map_err(|source| SourceError::Read {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        Ok(bytes)
+    })
+}
+
+fn zip_error(path: &Path, err: ZipError) -> SourceError {
+    SourceError::Read {
+        path: path.to_path_buf(),
+        source: std::io::Error::other(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use zip::write::{SimpleFileOptions, ZipWriter};
+
+    use super::*;
+    use crate::Linter;
+    use crate::rules::UpperEll;
+
+    #[test]
+    fn test_entry_paths() {
+        let path = entry_path(Path::new("lib/a-sources.jar"), "com/example/A.java");
+        assert_eq!(path, Path::new("lib/a-sources.jar!/com/example/A.java"));
+        assert_eq!(
+            split_entry_path(&path),
+            Some((Path::new("lib/a-sources.jar"), "com/example/A.java"))
+        );
+        assert_eq!(split_entry_path(Path::new("src/A.java")), None);
+        assert_eq!(split_entry_path(Path::new("odd!/A.java")), None);
+        assert!(is_archive(Path::new("A.ZIP")));
+    }
+
+    #[test]
+    fn test_lint_archive_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("a-sources.jar");
+        let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+        for (name, source) in [
+            ("META-INF/MANIFEST.MF", "Manifest-Version: 1.0\n"),
+            ("com/example/B.java", "class B {}\n"),
+            ("com/example/A.java", "class A {\n    long x = 1l;\n}\n"),
+        ] {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(source.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let entries = java_entries(&archive).unwrap();
+        assert_eq!(
+            entries,
+            [
+                entry_path(&archive, "com/example/A.java"),
+                entry_path(&archive, "com/example/B.java")
+            ]
+        );
+
+        let linter = Linter::new(vec![Box::new(UpperEll::default())]);
+        let file = linter.lint_file(&entries[0]).unwrap();
+        assert_eq!(file.diagnostics.len(), 1);
+        assert!(
+            linter
+                .lint_file(&entries[1])
+                .unwrap()
+                .diagnostics
+                .is_empty()
+        );
+        assert!(matches!(
+            linter.lint_file(&entry_path(&archive, "Missing.java")),
+            Err(SourceError::Read { .. })
+        ));
+
+        let fixed = linter
+            .fix_file(&entries[0], lintal_diagnostics::Applicability::Safe)
+            .unwrap();
+        assert!(matches!(
+            fixed.write(&entries[0]),
+            Err(SourceError::InArchive { .. })
+        ));
+    }
+}
@@ -0,0 +1,470 @@
+//! Events of a lint run, for reporters and embedders.
+//!
+//! [`Linter::audit`] lints a set of files and tells an [`AuditListener`] what
+//! happens, like checkstyle's `AuditListener`: the run starts, each file
+//! starts, its violations are found, the file finishes, and the run finishes.
+//! `lintal check` prints its output from such a listener, so a custom
+//! reporter sees exactly what the CLI reports.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use lintal_diagnostics::{Diagnostic, Severity};
+use lintal_java_parser::ParseResult;
+use lintal_source_file::{LineColumn, LineIndex, PositionEncoding, SourceCode};
+
+use crate::{LintedFile, Linter, RuleDiagnostic, SourceError, SuppressedBy, SuppressedDiagnostic};
+
+/// Files linted in parallel before their events are delivered, bounding how
+/// many linted files are held at once.
+const AUDIT_BATCH_SIZE: usize = 256;
+
+/// What [`Linter::relint_file`] returns.
+type Relinted = Result<(LintedFile, Option<ParseResult>), SourceError>;
+
+/// Receives the events of [`Linter::audit`].
+///
+/// Events are delivered on the calling thread, in the order of the files
+/// passed to `audit`; those of one file are never interleaved with another's.
+pub trait AuditListener {
+    /// The run is about to start.
+    fn audit_started(&mut self) {}
+
+    /// A file is about to be reported.
+    fn file_started(&mut self, _path: &Path) {}
+
+    /// A violation was found in the current file.
+    fn violation(&mut self, _event: &AuditEvent<'_>) {}
+
+    /// A violation in the current file was hidden by a suppression. Only
+    /// delivered with [`Linter::with_suppressed_diagnostics`], after the
+    /// file's violations.
+    fn suppressed(&mut self, _event: &AuditEvent<'_>, _by: &SuppressedBy) {}
+
+    /// The current file could not be read or parsed, so it has no violations.
+    fn file_error(&mut self, _path: &Path, _error: &SourceError) {}
+
+    /// The current file was skipped for being over a size or parse time
+    /// limit; see [`SourceError::is_skipped`]. Reported as an error unless
+    /// overridden.
+    fn file_skipped(&mut self, path: &Path, error: &SourceError) {
+        self.file_error(path, error);
+    }
+
+    /// All events of the current file have been delivered.
+    fn file_finished(&mut self, _path: &Path) {}
+
+    /// Every file has been reported.
+    fn audit_finished(&mut self, _summary: &AuditSummary) {}
+}
+
+/// A violation found during [`Linter::audit`].
+pub struct AuditEvent<'a> {
+    pub path: &'a Path,
+    /// Module name of the rule.
+    pub rule: &'static str,
+    pub diagnostic: &'a Diagnostic,
+    /// Where the violation starts, with the column counted the way
+    /// checkstyle counts it.
+    pub location: LineColumn,
+    /// Where the violation ends, exclusive, counted the same way.
+    pub end_location: LineColumn,
+    /// Source of the file, e.g. for rendering code frames.
+    pub source: &'a SourceCode<'a, 'a>,
+}
+
+/// Totals of a [`Linter::audit`] run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AuditSummary {
+    /// Files reported, including those that couldn't be linted.
+    pub files: usize,
+    /// Files that couldn't be read or parsed.
+    pub errors: usize,
+    /// Files skipped for being over a size or parse time limit.
+    pub skipped: usize,
+    pub violations: usize,
+    /// Violations of rules with a `warning` severity.
+    pub warnings: usize,
+    /// Violations of rules with an `info` severity.
+    pub infos: usize,
+    /// Violations that have a fix.
+    pub fixable: usize,
+    /// Violations hidden by suppressions, if collected.
+    pub suppressed: usize,
+}
+
+impl AuditSummary {
+    /// Violations of `severity` or more serious, e.g. to decide whether a
+    /// build fails.
+    pub fn violations_at_least(&self, severity: Severity) -> usize {
+        match severity {
+            Severity::Info => self.violations,
+            Severity::Warning => self.violations - self.infos,
+            Severity::Error => self.violations - self.infos - self.warnings,
+        }
+    }
+}
+
+impl Linter {
+    /// Lint `files` and report them to `listener`. Files that file
+    /// suppressions turn off entirely are skipped.
+    pub fn audit(&self, files: &[PathBuf], listener: &mut dyn AuditListener) -> AuditSummary {
+        let files: Vec<&PathBuf> = files
+            .iter()
+            .filter(|path| !self.is_file_suppressed(path))
+            .collect();
+        let mut summary = AuditSummary::default();
+
+        listener.audit_started();
+        for batch in files.chunks(AUDIT_BATCH_SIZE) {
+            for (path, result) in batch.iter().zip(self.lint_batch(batch)) {
+                self.report_file(path, &result, listener, &mut summary);
+            }
+        }
+        listener.audit_finished(&summary);
+        summary
+    }
+
+    /// Like [`Linter::audit`], but parse each file incrementally from its
+    /// tree in `trees`, kept from when it was last linted, and keep its new
+    /// tree there; see [`Linter::relint_file`]. For watchers linting files
+    /// again as they change.
+    pub fn audit_incremental(
+        &self,
+        files: &[PathBuf],
+        trees: &mut HashMap<PathBuf, ParseResult>,
+        listener: &mut dyn AuditListener,
+    ) -> AuditSummary {
+        let files: Vec<&PathBuf> = files
+            .iter()
+            .filter(|path| !self.is_file_suppressed(path))
+            .collect();
+        let mut summary = AuditSummary::default();
+
+        listener.audit_started();
+        for batch in files.chunks(AUDIT_BATCH_SIZE) {
+            let previous = batch.iter().map(|path| trees.remove(*path)).collect();
+            for (path, result) in batch.iter().zip(self.relint_batch(batch, previous)) {
+                let result = result.map(|(file, tree)| {
+                    if let Some(tree) = tree {
+                        trees.insert(PathBuf::clone(path), tree);
+                    }
+                    file
+                });
+                self.report_file(path, &result, listener, &mut summary);
+            }
+        }
+        listener.audit_finished(&summary);
+        summary
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn lint_batch(&self, batch: &[&PathBuf]) -> Vec<Result<LintedFile, SourceError>> {
+        use rayon::prelude::*;
+
+        batch.par_iter().map(|path| self.lint_file(path)).collect()
+    }
+
+    #[cfg(target_family = "wasm")]
+    fn lint_batch(&self, batch: &[&PathBuf]) -> Vec<Result<LintedFile, SourceError>> {
+        batch.iter().map(|path| self.lint_file(path)).collect()
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn relint_batch(
+        &self,
+        batch: &[&PathBuf],
+        previous: Vec<Option<ParseResult>>,
+    ) -> Vec<Relinted> {
+        use rayon::prelude::*;
+
+        batch
+            .par_iter()
+            .zip(previous)
+            .map(|(path, previous)| self.relint_file(path, previous))
+            .collect()
+    }
+
+    #[cfg(target_family = "wasm")]
+    fn relint_batch(
+        &self,
+        batch: &[&PathBuf],
+        previous: Vec<Option<ParseResult>>,
+    ) -> Vec<Relinted> {
+        batch
+            .iter()
+            .zip(previous)
+            .map(|(path, previous)| self.relint_file(path, previous))
+            .collect()
+    }
+
+    fn report_file(
+        &self,
+        path: &Path,
+        result: &Result<LintedFile, SourceError>,
+        listener: &mut dyn AuditListener,
+        summary: &mut AuditSummary,
+    ) {
+        summary.files += 1;
+        listener.file_started(path);
+
+        match result {
+            Ok(file) => {
+                let line_index = LineIndex::from_source_text(&file.source);
+                let source = SourceCode::new(&file.source, &line_index);
+                for found in &file.diagnostics {
+                    summary.violations += 1;
+                    match found.diagnostic.severity {
+                        Severity::Info => summary.infos += 1,
+                        Severity::Warning => summary.warnings += 1,
+                        Severity::Error => {}
+                    }
+                    if found.diagnostic.fixable() {
+                        summary.fixable += 1;
+                    }
+                    listener.violation(&self.audit_event(path, found, &source));
+                }
+                for SuppressedDiagnostic { diagnostic, by } in &file.suppressed {
+                    summary.suppressed += 1;
+                    listener.suppressed(&self.audit_event(path, diagnostic, &source), by);
+                }
+            }
+            Err(error) if error.is_skipped() => {
+                summary.skipped += 1;
+                listener.file_skipped(path, error);
+            }
+            Err(error) => {
+                summary.errors += 1;
+                listener.file_error(path, error);
+            }
+        }
+
+        listener.file_finished(path);
+    }
+
+    fn audit_event<'a>(
+        &self,
+        path: &'a Path,
+        found: &'a RuleDiagnostic,
+        source: &'a SourceCode<'a, 'a>,
+    ) -> AuditEvent<'a> {
+        // Report columns the way checkstyle does so they line up with
+        // existing suppressions
+        let encoding = self
+            .rule(found.rule)
+            .map_or(PositionEncoding::Utf32, |rule| rule.column_encoding());
+        let range = found.diagnostic.range;
+        let location = source.checkstyle_column(range.start(), self.tab_width(), encoding);
+        let end_location = source.checkstyle_column(range.end(), self.tab_width(), encoding);
+        AuditEvent {
+            path,
+            rule: found.rule,
+            diagnostic: &found.diagnostic,
+            location,
+            end_location,
+            source,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::UpperEll;
+
+    /// Records events as strings.
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl AuditListener for Recorder {
+        fn audit_started(&mut self) {
+            self.0.push("audit started".to_string());
+        }
+
+        fn file_started(&mut self, path: &Path) {
+            self.0.push(format!("started {}", file_name(path)));
+        }
+
+        fn violation(&mut self, event: &AuditEvent<'_>) {
+            self.0.push(format!(
+                "{}:{}:{} {}",
+                file_name(event.path),
+                event.location.line,
+                event.location.column,
+                event.rule
+            ));
+        }
+
+        fn suppressed(&mut self, event: &AuditEvent<'_>, by: &SuppressedBy) {
+            let by = match by {
+                SuppressedBy::Directive { line, text } => format!("line {line}: {text}"),
+                SuppressedBy::File { line, checks, .. } => {
+                    format!("suppressions.xml:{} ({checks})", line.unwrap_or_default())
+                }
+            };
+            self.0.push(format!(
+                "{}:{}:{} {} suppressed by {by}",
+                file_name(event.path),
+                event.location.line,
+                event.location.column,
+                event.rule
+            ));
+        }
+
+        fn file_error(&mut self, path: &Path, _error: &SourceError) {
+            self.0.push(format!("error {}", file_name(path)));
+        }
+
+        fn file_finished(&mut self, path: &Path) {
+            self.0.push(format!("finished {}", file_name(path)));
+        }
+
+        fn audit_finished(&mut self, summary: &AuditSummary) {
+            self.0.push(format!(
+                "audit finished: {} files, {} violations",
+                summary.files, summary.violations
+            ));
+        }
+    }
+
+    #[test]
+    fn test_audit_skips_files_over_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        let small = dir.path().join("Small.java");
+        let large = dir.path().join("Large.java");
+        std::fs::write(&small, "class Small {}\n").unwrap();
+        std::fs::write(&large, format!("class Large {{}}\n{}", " ".repeat(100))).unwrap();
+
+        let linter = Linter::new(vec![Box::new(UpperEll::default())]).with_max_file_size(64);
+        let summary = linter.audit(&[small, large.clone()], &mut Recorder::default());
+        assert_eq!((summary.files, summary.skipped, summary.errors), (2, 1, 0));
+        assert!(matches!(
+            linter.lint_file(&large),
+            Err(SourceError::TooLarge { max: 64, .. })
+        ));
+    }
+
+    fn file_name(path: &Path) -> String {
+        path.file_name().unwrap().to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_audit_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("A.java");
+        let b = dir.path().join("B.java");
+        std::fs::write(&a, "class A {\n    long x = 1l;\n}\n").unwrap();
+        std::fs::write(&b, "class B {}\n").unwrap();
+        let missing = dir.path().join("C.java");
+
+        let linter = Linter::new(vec![Box::new(UpperEll::default())]);
+        let mut recorder = Recorder::default();
+        let summary = linter.audit(&[a, b, missing], &mut recorder);
+
+        assert_eq!(
+            recorder.0,
+            [
+                "audit started",
+                "started A.java",
+                "A.java:2:14 UpperEll",
+                "finished A.java",
+                "started B.java",
+                "finished B.java",
+                "started C.java",
+                "error C.java",
+                "finished C.java",
+                "audit finished: 3 files, 1 violations",
+            ]
+        );
+        assert_eq!(
+            summary,
+            AuditSummary {
+                files: 3,
+                errors: 1,
+                skipped: 0,
+                violations: 1,
+                warnings: 0,
+                infos: 0,
+                fixable: 1,
+                suppressed: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_audit_incremental() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("A.java");
+        std::fs::write(&a, "class A {\n    long x = 1l;\n}\n").unwrap();
+        let missing = dir.path().join("B.java");
+
+        let linter = Linter::new(vec![Box::new(UpperEll::default())]);
+        let mut trees = HashMap::new();
+        let files = [a.clone(), missing];
+        let summary = linter.audit_incremental(&files, &mut trees, &mut Recorder::default());
+        assert_eq!((summary.violations, summary.errors), (1, 1));
+        assert_eq!(trees.keys().collect::<Vec<_>>(), [&a]);
+
+        // Reparsed from the kept tree
+        std::fs::write(&a, "class A {\n    long x = 1L;\n    long y = 2l;\n}\n").unwrap();
+        let mut recorder = Recorder::default();
+        linter.audit_incremental(&files[..1], &mut trees, &mut recorder);
+        assert!(recorder.0.contains(&"A.java:3:14 UpperEll".to_string()));
+        assert_eq!(&*trees[&a].source, std::fs::read_to_string(&a).unwrap());
+    }
+
+    #[test]
+    fn test_audit_counts_severities() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("A.java");
+        std::fs::write(&a, "class A {\n    long x = 1l;\n    long y = 2l;\n}\n").unwrap();
+
+        let linter =
+            Linter::new(vec![Box::new(UpperEll::default())]).with_severities(|_| Severity::Warning);
+        let summary = linter.audit(&[a], &mut Recorder::default());
+
+        assert_eq!(
+            (summary.violations, summary.warnings, summary.infos),
+            (2, 2, 0)
+        );
+        assert_eq!(summary.violations_at_least(Severity::Warning), 2);
+        assert_eq!(summary.violations_at_least(Severity::Error), 0);
+    }
+
+    #[test]
+    fn test_audit_suppressed_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("A.java");
+        let b = dir.path().join("B.java");
+        std::fs::write(
+            &a,
+            "class A {\n    // lintal: disable-next-line UpperEll\n    long x = 1l;\n}\n",
+        )
+        .unwrap();
+        std::fs::write(&b, "class B {\n    long x = 1l;\n}\n").unwrap();
+        let suppressions = crate::FileSuppressionsConfig::from_xml(
+            r#"<suppress files="B\.java" checks="UpperEll"/>"#,
+        );
+
+        let linter = Linter::new(vec![Box::new(UpperEll::default())])
+            .with_file_suppressions(suppressions)
+            .with_suppressed_diagnostics();
+        let mut recorder = Recorder::default();
+        let summary = linter.audit(&[a, b], &mut recorder);
+
+        assert_eq!(
+            recorder.0,
+            [
+                "audit started",
+                "started A.java",
+                "A.java:3:14 UpperEll suppressed by line 2: // lintal: disable-next-line UpperEll",
+                "finished A.java",
+                "started B.java",
+                "B.java:2:14 UpperEll suppressed by suppressions.xml:1 (UpperEll)",
+                "finished B.java",
+                "audit finished: 2 files, 0 violations",
+            ]
+        );
+        assert_eq!((summary.violations, summary.suppressed), (0, 2));
+    }
+}
@@ -0,0 +1,643 @@
+//! Short, stable codes for the built-in rules.
+//!
+//! Every built-in rule has a code made of a category prefix and a number,
+//! e.g. `WS001` for WhitespaceAround. Codes are usable wherever a rule name
+//! is: in `--select`, in suppression comments and `@SuppressWarnings`, and
+//! they are reported on each diagnostic. A code is never reused or
+//! renumbered; new rules take the next number of their category.
+//!
+//! | Prefix | Category   |
+//! |--------|------------|
+//! | `AN`   | Annotation |
+//! | `BL`   | Blocks     |
+//! | `CD`   | Coding     |
+//! | `DS`   | Design     |
+//! | `DU`   | Duplicates |
+//! | `HD`   | Header     |
+//! | `IM`   | Imports    |
+//! | `JD`   | Javadoc    |
+//! | `MD`   | Modifiers  |
+//! | `MS`   | Misc       |
+//! | `MT`   | Metrics    |
+//! | `NM`   | Naming     |
+//! | `RX`   | Regexp     |
+//! | `ST`   | Style      |
+//! | `SZ`   | Sizes      |
+//! | `WS`   | Whitespace |
+//!
+//! Each built-in rule also has a one-line [`description`] and a [`help_uri`]
+//! linking to the documentation of the checkstyle check it implements, for
+//! reports that describe their rules, such as SARIF.
+
+use crate::Rule;
+
+/// Categories of the built-in rules, by code prefix.
+const CATEGORIES: &[(&str, &str)] = &[
+    ("AN", "Annotation"),
+    ("BL", "Blocks"),
+    ("CD", "Coding"),
+    ("DS", "Design"),
+    ("DU", "Duplicates"),
+    ("HD", "Header"),
+    ("IM", "Imports"),
+    ("JD", "Javadoc"),
+    ("MD", "Modifiers"),
+    ("MS", "Misc"),
+    ("MT", "Metrics"),
+    ("NM", "Naming"),
+    ("RX", "Regexp"),
+    ("ST", "Style"),
+    ("SZ", "Sizes"),
+    ("WS", "Whitespace"),
+];
+
+/// Codes of the built-in rules, by checkstyle module name.
+const RULE_CODES: &[(&str, &str)] = &[
+    // Whitespace rules
+    ("WhitespaceAround", "WS001"),
+    ("WhitespaceAfter", "WS002"),
+    ("NoWhitespaceAfter", "WS003"),
+    ("NoWhitespaceBefore", "WS004"),
+    ("ParenPad", "WS005"),
+    ("SingleSpaceSeparator", "WS006"),
+    ("MethodParamPad", "WS007"),
+    ("EmptyForInitializerPad", "WS008"),
+    ("TypecastParenPad", "WS009"),
+    ("FileTabCharacter", "WS010"),
+    ("OperatorWrap", "WS011"),
+    ("EmptyLineSeparator", "WS012"),
+    ("Indentation", "WS013"),
+    ("SeparatorWrap", "WS014"),
+    ("GenericWhitespace", "WS015"),
+    // Block rules
+    ("LeftCurly", "BL001"),
+    ("RightCurly", "BL002"),
+    ("NeedBraces", "BL003"),
+    ("EmptyBlock", "BL004"),
+    ("EmptyCatchBlock", "BL005"),
+    ("AvoidNestedBlocks", "BL006"),
+    // Modifier rules
+    ("ModifierOrder", "MD001"),
+    ("FinalParameters", "MD002"),
+    ("RedundantModifier", "MD003"),
+    ("FinalLocalVariable", "MD004"),
+    ("FinalClass", "MD005"),
+    // Style rules
+    ("UpperEll", "ST001"),
+    ("ArrayTypeStyle", "ST002"),
+    // Import rules
+    ("RedundantImport", "IM001"),
+    ("UnusedImports", "IM002"),
+    ("ImportOrder", "IM003"),
+    ("CustomImportOrder", "IM004"),
+    ("AvoidStarImport", "IM005"),
+    ("AvoidStaticImport", "IM006"),
+    // Coding rules
+    ("OneStatementPerLine", "CD001"),
+    ("MultipleVariableDeclarations", "CD002"),
+    ("SimplifyBooleanReturn", "CD003"),
+    ("SimplifyBooleanExpression", "CD004"),
+    ("EmptyStatement", "CD005"),
+    ("StringLiteralEquality", "CD006"),
+    ("DefaultComesLast", "CD007"),
+    ("NestedTryDepth", "CD008"),
+    ("PackageDeclaration", "CD009"),
+    ("MissingSwitchDefault", "CD010"),
+    ("InnerAssignment", "CD011"),
+    ("CovariantEquals", "CD012"),
+    ("EqualsHashCode", "CD013"),
+    ("DeclarationOrder", "CD014"),
+    ("HiddenField", "CD015"),
+    ("FallThrough", "CD016"),
+    ("IllegalType", "CD017"),
+    ("MagicNumber", "CD018"),
+    ("RequireThis", "CD019"),
+    ("EqualsAvoidNull", "CD020"),
+    ("ReturnCount", "CD021"),
+    ("NestedIfDepth", "CD022"),
+    ("NestedForDepth", "CD023"),
+    // Regexp rules
+    ("RegexpSinglelineJava", "RX001"),
+    ("RegexpSingleline", "RX002"),
+    ("RegexpMultiline", "RX003"),
+    // Misc rules
+    ("DescendantToken", "MS001"),
+    ("OuterTypeFilename", "MS002"),
+    ("NewlineAtEndOfFile", "MS003"),
+    // Design rules
+    ("HideUtilityClassConstructor", "DS001"),
+    ("MutableException", "DS002"),
+    ("VisibilityModifier", "DS003"),
+    ("DesignForExtension", "DS004"),
+    ("OneTopLevelClass", "DS005"),
+    // Header rules
+    ("Header", "HD001"),
+    ("RegexpHeader", "HD002"),
+    // Duplicate code rules
+    ("StrictDuplicateCode", "DU001"),
+    // Size rules
+    ("LineLength", "SZ001"),
+    ("MethodLength", "SZ002"),
+    ("FileLength", "SZ003"),
+    ("ParameterNumber", "SZ004"),
+    ("MethodCount", "SZ005"),
+    ("AnonInnerLength", "SZ006"),
+    ("ExecutableStatementCount", "SZ007"),
+    ("LambdaBodyLength", "SZ008"),
+    // Metrics rules
+    ("NPathComplexity", "MT001"),
+    ("BooleanExpressionComplexity", "MT002"),
+    // Javadoc rules
+    ("JavadocMethod", "JD001"),
+    ("MissingJavadocMethod", "JD002"),
+    // Annotation rules
+    ("MissingOverride", "AN001"),
+    ("AnnotationLocation", "AN002"),
+    ("AnnotationUseStyle", "AN003"),
+    // Naming rules
+    ("ConstantName", "NM001"),
+    ("LocalFinalVariableName", "NM002"),
+    ("LocalVariableName", "NM003"),
+    ("MemberName", "NM004"),
+    ("MethodName", "NM005"),
+    ("PackageName", "NM006"),
+    ("ParameterName", "NM007"),
+    ("StaticVariableName", "NM008"),
+    ("TypeName", "NM009"),
+    ("ClassTypeParameterName", "NM010"),
+    ("InterfaceTypeParameterName", "NM011"),
+    ("MethodTypeParameterName", "NM012"),
+];
+
+/// One-line descriptions of the built-in rules, by module name.
+const DESCRIPTIONS: &[(&str, &str)] = &[
+    (
+        "WhitespaceAround",
+        "Checks that a token is surrounded by whitespace.",
+    ),
+    (
+        "WhitespaceAfter",
+        "Checks that a token is followed by whitespace.",
+    ),
+    (
+        "NoWhitespaceAfter",
+        "Checks that there is no whitespace after a token.",
+    ),
+    (
+        "NoWhitespaceBefore",
+        "Checks that there is no whitespace before a token.",
+    ),
+    ("ParenPad", "Checks the padding of parentheses."),
+    (
+        "SingleSpaceSeparator",
+        "Checks that non-whitespace characters are separated by no more than one space.",
+    ),
+    (
+        "MethodParamPad",
+        "Checks the padding between a method or constructor name and its parameter list.",
+    ),
+    (
+        "EmptyForInitializerPad",
+        "Checks the padding of an empty for initializer.",
+    ),
+    (
+        "TypecastParenPad",
+        "Checks the padding of the parentheses of typecasts.",
+    ),
+    (
+        "FileTabCharacter",
+        "Checks that there are no tab characters in the source code.",
+    ),
+    (
+        "OperatorWrap",
+        "Checks the policy on how to wrap lines on operators.",
+    ),
+    (
+        "EmptyLineSeparator",
+        "Checks for empty line separators before package, imports, members and blocks.",
+    ),
+    ("Indentation", "Checks the indentation of Java code."),
+    ("SeparatorWrap", "Checks line wrapping with separators."),
+    (
+        "GenericWhitespace",
+        "Checks the whitespace around the generic tokens < and >.",
+    ),
+    ("LeftCurly", "Checks the placement of left curly braces."),
+    ("RightCurly", "Checks the placement of right curly braces."),
+    ("NeedBraces", "Checks for braces around code blocks."),
+    ("EmptyBlock", "Checks for empty blocks."),
+    ("EmptyCatchBlock", "Checks for empty catch blocks."),
+    ("AvoidNestedBlocks", "Finds nested blocks."),
+    (
+        "ModifierOrder",
+        "Checks that the order of modifiers conforms to the JLS.",
+    ),
+    (
+        "FinalParameters",
+        "Checks that method, constructor and catch parameters are final.",
+    ),
+    ("RedundantModifier", "Checks for redundant modifiers."),
+    (
+        "FinalLocalVariable",
+        "Checks that local variables that never have their values changed are declared final.",
+    ),
+    (
+        "FinalClass",
+        "Ensures that a class with only private constructors is declared final.",
+    ),
+    (
+        "UpperEll",
+        "Checks that long constants are defined with an upper ell.",
+    ),
+    (
+        "ArrayTypeStyle",
+        "Checks the style of array type definitions.",
+    ),
+    ("RedundantImport", "Checks for redundant import statements."),
+    ("UnusedImports", "Checks for unused import statements."),
+    (
+        "ImportOrder",
+        "Checks the grouping, ordering and separation of import statements.",
+    ),
+    (
+        "CustomImportOrder",
+        "Checks that import statements are in the configured groups and order.",
+    ),
+    ("AvoidStarImport", "Checks that there are no star imports."),
+    (
+        "AvoidStaticImport",
+        "Checks that there are no static imports.",
+    ),
+    (
+        "OneStatementPerLine",
+        "Checks that there is only one statement per line.",
+    ),
+    (
+        "MultipleVariableDeclarations",
+        "Checks that each variable declaration is in its own statement and on its own line.",
+    ),
+    (
+        "SimplifyBooleanReturn",
+        "Checks for over-complicated boolean return statements.",
+    ),
+    (
+        "SimplifyBooleanExpression",
+        "Checks for over-complicated boolean expressions.",
+    ),
+    ("EmptyStatement", "Detects empty statements."),
+    (
+        "StringLiteralEquality",
+        "Checks that string literals are not compared with == or !=.",
+    ),
+    (
+        "DefaultComesLast",
+        "Checks that the default is after all the cases of a switch.",
+    ),
+    (
+        "NestedTryDepth",
+        "Restricts the nesting depth of try statements.",
+    ),
+    (
+        "PackageDeclaration",
+        "Ensures that a class has a package declaration matching its directory.",
+    ),
+    (
+        "MissingSwitchDefault",
+        "Checks that switch statements have a default clause.",
+    ),
+    (
+        "InnerAssignment",
+        "Checks for assignments in subexpressions.",
+    ),
+    (
+        "CovariantEquals",
+        "Checks that classes which define a covariant equals method also override equals(Object).",
+    ),
+    (
+        "EqualsHashCode",
+        "Checks that classes that override equals() also override hashCode().",
+    ),
+    (
+        "DeclarationOrder",
+        "Checks that the parts of a class or interface declaration appear in the conventional order.",
+    ),
+    (
+        "HiddenField",
+        "Checks that a local variable or a parameter does not shadow a field.",
+    ),
+    (
+        "FallThrough",
+        "Checks for fall-through in switch statements.",
+    ),
+    (
+        "IllegalType",
+        "Checks that particular classes or interfaces are never used.",
+    ),
+    (
+        "MagicNumber",
+        "Checks that there are no magic numbers outside constant definitions.",
+    ),
+    (
+        "RequireThis",
+        "Checks that instance fields and methods are referenced through this.",
+    ),
+    (
+        "EqualsAvoidNull",
+        "Checks that string literals are on the left side of equals comparisons.",
+    ),
+    (
+        "ReturnCount",
+        "Restricts the number of return statements in methods, constructors and lambdas.",
+    ),
+    (
+        "NestedIfDepth",
+        "Restricts the nesting depth of if-else statements.",
+    ),
+    (
+        "NestedForDepth",
+        "Restricts the nesting depth of for loops.",
+    ),
+    (
+        "RegexpSinglelineJava",
+        "Checks that a specified pattern matches a single line in Java files.",
+    ),
+    (
+        "RegexpSingleline",
+        "Checks that a specified pattern matches a single line in any file type.",
+    ),
+    (
+        "RegexpMultiline",
+        "Checks that a specified pattern matches across multiple lines in any file.",
+    ),
+    (
+        "DescendantToken",
+        "Checks for restricted tokens beneath other tokens.",
+    ),
+    (
+        "HideUtilityClassConstructor",
+        "Makes sure that utility classes do not have a public or default constructor.",
+    ),
+    (
+        "MutableException",
+        "Ensures that exception classes are immutable.",
+    ),
+    ("VisibilityModifier", "Checks visibility of class members."),
+    (
+        "DesignForExtension",
+        "Checks that classes are designed for extension (subclass creation).",
+    ),
+    (
+        "OneTopLevelClass",
+        "Checks that each top-level class, interface, enum or annotation resides in a source file of its own.",
+    ),
+    (
+        "OuterTypeFilename",
+        "Checks that the outer type name and the file name match.",
+    ),
+    (
+        "NewlineAtEndOfFile",
+        "Checks whether files end with a line separator.",
+    ),
+    (
+        "Header",
+        "Checks that a source file begins with a specified header.",
+    ),
+    (
+        "RegexpHeader",
+        "Checks the header of a source file against a header that contains a regular expression for each line.",
+    ),
+    (
+        "StrictDuplicateCode",
+        "Performs a line-by-line comparison of all code lines and reports duplicate code.",
+    ),
+    ("LineLength", "Checks for long lines."),
+    ("MethodLength", "Checks for long methods and constructors."),
+    ("FileLength", "Checks for long source files."),
+    (
+        "ParameterNumber",
+        "Checks the number of parameters of a method or constructor.",
+    ),
+    (
+        "MethodCount",
+        "Checks the number of methods declared in each type.",
+    ),
+    (
+        "AnonInnerLength",
+        "Checks for long anonymous inner classes.",
+    ),
+    (
+        "ExecutableStatementCount",
+        "Restricts the number of executable statements to a specified limit.",
+    ),
+    ("LambdaBodyLength", "Checks for long lambda bodies."),
+    (
+        "NPathComplexity",
+        "Checks the number of possible execution paths through a method.",
+    ),
+    (
+        "BooleanExpressionComplexity",
+        "Restricts the number of boolean operators in an expression.",
+    ),
+    (
+        "JavadocMethod",
+        "Checks the Javadoc of a method or constructor against its signature.",
+    ),
+    (
+        "MissingJavadocMethod",
+        "Checks for missing Javadoc comments for a method or constructor.",
+    ),
+    (
+        "MissingOverride",
+        "Checks that methods documented with {@inheritDoc} are annotated with @Override.",
+    ),
+    (
+        "AnnotationLocation",
+        "Checks that annotations are on their own lines, indented like their declaration.",
+    ),
+    (
+        "AnnotationUseStyle",
+        "Checks the style of annotation element values, parentheses and trailing commas.",
+    ),
+    (
+        "ConstantName",
+        "Checks that constant names conform to a specified pattern.",
+    ),
+    (
+        "LocalFinalVariableName",
+        "Checks that local final variable names conform to a specified pattern.",
+    ),
+    (
+        "LocalVariableName",
+        "Checks that local, non-final variable names conform to a specified pattern.",
+    ),
+    (
+        "MemberName",
+        "Checks that instance variable names conform to a specified pattern.",
+    ),
+    (
+        "MethodName",
+        "Checks that method names conform to a specified pattern.",
+    ),
+    (
+        "PackageName",
+        "Checks that package names conform to a specified pattern.",
+    ),
+    (
+        "ParameterName",
+        "Checks that method parameter names conform to a specified pattern.",
+    ),
+    (
+        "StaticVariableName",
+        "Checks that static, non-final variable names conform to a specified pattern.",
+    ),
+    (
+        "TypeName",
+        "Checks that type names conform to a specified pattern.",
+    ),
+    (
+        "ClassTypeParameterName",
+        "Checks that class type parameter names conform to a specified pattern.",
+    ),
+    (
+        "InterfaceTypeParameterName",
+        "Checks that interface type parameter names conform to a specified pattern.",
+    ),
+    (
+        "MethodTypeParameterName",
+        "Checks that method type parameter names conform to a specified pattern.",
+    ),
+];
+
+/// The code of the built-in rule with module name `name`.
+pub fn rule_code(name: &str) -> Option<&'static str> {
+    RULE_CODES
+        .iter()
+        .find(|(rule, _)| *rule == name)
+        .map(|(_, code)| *code)
+}
+
+/// The module name of the built-in rule with code `code`, ignoring case.
+pub fn rule_name(code: &str) -> Option<&'static str> {
+    RULE_CODES
+        .iter()
+        .find(|(_, rule_code)| rule_code.eq_ignore_ascii_case(code))
+        .map(|(name, _)| *name)
+}
+
+/// The category of the rule with code `code`, e.g. `Whitespace` for
+/// `WS001`.
+pub fn category(code: &str) -> Option<&'static str> {
+    CATEGORIES
+        .iter()
+        .find(|(prefix, _)| code.get(..prefix.len()) == Some(*prefix))
+        .map(|(_, category)| *category)
+}
+
+/// A one-line description of the built-in rule with module name `name`.
+pub fn description(name: &str) -> Option<&'static str> {
+    DESCRIPTIONS
+        .iter()
+        .find(|(rule, _)| *rule == name)
+        .map(|(_, description)| *description)
+}
+
+/// The URL of checkstyle's documentation of the built-in rule with module
+/// name `name`. Checkstyle files its checks under sections that mostly, but
+/// not always, match our categories.
+pub fn help_uri(name: &str) -> Option<String> {
+    let section = match (name, category(rule_code(name)?)?) {
+        ("Indentation", _) | (_, "Style") => "misc".to_string(),
+        (_, "Modifiers") => "modifier".to_string(),
+        // Checkstyle dropped StrictDuplicateCode and its documentation
+        (_, "Duplicates") => return None,
+        (_, category) => category.to_lowercase(),
+    };
+    Some(format!(
+        "https://checkstyle.org/checks/{section}/{}.html",
+        name.to_lowercase()
+    ))
+}
+
+/// Whether `selector` picks `rule`: its module name, its code, or a prefix
+/// of its code such as `WS` or `WS00`.
+pub fn selects(selector: &str, rule: &dyn Rule) -> bool {
+    rule.name() == selector
+        || rule.code().is_some_and(|code| {
+            code.get(..selector.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(selector))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use super::*;
+    use crate::RuleRegistry;
+    use crate::rules::{UpperEll, WhitespaceAround};
+
+    #[test]
+    fn test_every_builtin_rule_has_a_unique_code() {
+        let registry = RuleRegistry::builtin();
+        for name in registry.module_names() {
+            assert!(rule_code(name).is_some(), "{name} has no code");
+        }
+
+        let codes: HashSet<&str> = RULE_CODES.iter().map(|(_, code)| *code).collect();
+        assert_eq!(codes.len(), RULE_CODES.len());
+        for (name, code) in RULE_CODES {
+            assert!(registry.has_rule(name), "{code} names unknown rule {name}");
+            assert!(category(code).is_some(), "{code} has no category");
+            assert!(description(name).is_some(), "{name} has no description");
+        }
+    }
+
+    #[test]
+    fn test_rule_codes() {
+        assert_eq!(rule_code("WhitespaceAround"), Some("WS001"));
+        assert_eq!(rule_name("ws001"), Some("WhitespaceAround"));
+        assert_eq!(rule_code("NoSuchRule"), None);
+        assert_eq!(category("WS001"), Some("Whitespace"));
+        assert_eq!(category("XX001"), None);
+
+        let rule = RuleRegistry::builtin()
+            .create_rule("UpperEll", &HashMap::new())
+            .unwrap();
+        assert_eq!(rule.code(), Some("ST001"));
+    }
+
+    #[test]
+    fn test_help_uri() {
+        assert_eq!(
+            help_uri("WhitespaceAround").as_deref(),
+            Some("https://checkstyle.org/checks/whitespace/whitespacearound.html")
+        );
+        assert_eq!(
+            help_uri("ModifierOrder").as_deref(),
+            Some("https://checkstyle.org/checks/modifier/modifierorder.html")
+        );
+        assert_eq!(
+            help_uri("UpperEll").as_deref(),
+            Some("https://checkstyle.org/checks/misc/upperell.html")
+        );
+        assert_eq!(
+            help_uri("Indentation").as_deref(),
+            Some("https://checkstyle.org/checks/misc/indentation.html")
+        );
+        assert_eq!(help_uri("StrictDuplicateCode"), None);
+        assert_eq!(help_uri("NoSuchRule"), None);
+    }
+
+    #[test]
+    fn test_selects() {
+        let rule = WhitespaceAround::default();
+        assert!(selects("WhitespaceAround", &rule));
+        assert!(selects("WS001", &rule));
+        assert!(selects("WS", &rule));
+        assert!(selects("ws0", &rule));
+        assert!(!selects("WS002", &rule));
+        assert!(!selects("WS0010", &rule));
+        assert!(!selects("WhitespaceAfter", &rule));
+        assert!(!selects("WS", &UpperEll::default()));
+    }
+}
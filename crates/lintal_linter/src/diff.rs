@@ -0,0 +1,344 @@
+//! Line diffs between a source and its fixed version, as shown by
+//! `lintal fix --diff`.
+//!
+//! Lines are aligned by Myers' algorithm and grouped into hunks with
+//! [`CONTEXT_LINES`] lines of context, as `diff -u` does, so that
+//! [`unified_diff`] can be applied with `git apply` or `patch`.
+
+use std::fmt::Write;
+
+/// Lines of context around the changes of a [`Hunk`].
+pub const CONTEXT_LINES: usize = 3;
+
+/// A line of a [`Hunk`], without its line break.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+    /// A line present in both versions.
+    Context(&'a str),
+    /// A line only in the original.
+    Removed(&'a str),
+    /// A line only in the fixed version.
+    Added(&'a str),
+}
+
+/// A run of changed lines, with the context around them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk<'a> {
+    /// One-based line in the original where the hunk starts.
+    pub original_start: usize,
+    /// One-based line in the fixed version where the hunk starts.
+    pub fixed_start: usize,
+    pub lines: Vec<DiffLine<'a>>,
+}
+
+impl Hunk<'_> {
+    /// Number of lines the hunk covers in the original.
+    pub fn original_len(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Added(_)))
+            .count()
+    }
+
+    /// Number of lines the hunk covers in the fixed version.
+    pub fn fixed_len(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Removed(_)))
+            .count()
+    }
+}
+
+/// How a line of the edit script turns the original into the fixed version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Compare `original` and `fixed` line by line.
+///
+/// Each hunk has up to [`CONTEXT_LINES`] lines of context before and after
+/// its changes; changes closer together than twice that share a hunk.
+/// Lines differing only in their line break, `\r\n` for `\n` or none at the
+/// end of the file, are changed lines too.
+pub fn diff_lines<'a>(original: &'a str, fixed: &'a str) -> Vec<Hunk<'a>> {
+    let original_lines: Vec<&str> = original.split_inclusive('\n').collect();
+    let fixed_lines: Vec<&str> = fixed.split_inclusive('\n').collect();
+    let ops = edit_script(&original_lines, &fixed_lines);
+
+    // Where in each version every op starts
+    let mut positions = Vec::with_capacity(ops.len());
+    let (mut i, mut j) = (0, 0);
+    for op in &ops {
+        positions.push((i, j));
+        match op {
+            Op::Equal => (i, j) = (i + 1, j + 1),
+            Op::Delete => i += 1,
+            Op::Insert => j += 1,
+        }
+    }
+
+    let line = |k: usize| {
+        let (i, j) = positions[k];
+        match ops[k] {
+            Op::Equal => DiffLine::Context(without_newline(original_lines[i])),
+            Op::Delete => DiffLine::Removed(without_newline(original_lines[i])),
+            Op::Insert => DiffLine::Added(without_newline(fixed_lines[j])),
+        }
+    };
+
+    let changes: Vec<usize> = (0..ops.len()).filter(|&k| ops[k] != Op::Equal).collect();
+    let mut hunks = Vec::new();
+    let mut rest = changes.as_slice();
+    while let Some(&first) = rest.first() {
+        let mut last = first;
+        let mut taken = 1;
+        while let Some(&next) = rest.get(taken) {
+            if next - last - 1 > 2 * CONTEXT_LINES {
+                break;
+            }
+            last = next;
+            taken += 1;
+        }
+        rest = &rest[taken..];
+
+        let start = first.saturating_sub(CONTEXT_LINES);
+        let end = (last + 1 + CONTEXT_LINES).min(ops.len());
+        let (i, j) = positions[start];
+        hunks.push(Hunk {
+            original_start: i + 1,
+            fixed_start: j + 1,
+            lines: (start..end).map(line).collect(),
+        });
+    }
+    hunks
+}
+
+/// A unified diff turning `original` into `fixed`, with the names given in
+/// its `---` and `+++` headers; empty if they're the same.
+pub fn unified_diff(original_name: &str, fixed_name: &str, original: &str, fixed: &str) -> String {
+    let hunks = diff_lines(original, fixed);
+    if hunks.is_empty() {
+        return String::new();
+    }
+
+    // The last line of a version that has no line break gets a marker
+    let unterminated = |text: &str| {
+        (!text.is_empty() && !text.ends_with('\n')).then(|| text.split_inclusive('\n').count())
+    };
+    let original_last = unterminated(original);
+    let fixed_last = unterminated(fixed);
+
+    let mut output = String::new();
+    writeln!(output, "--- {original_name}").unwrap();
+    writeln!(output, "+++ {fixed_name}").unwrap();
+    for hunk in &hunks {
+        writeln!(
+            output,
+            "@@ -{} +{} @@",
+            hunk_range(hunk.original_start, hunk.original_len()),
+            hunk_range(hunk.fixed_start, hunk.fixed_len())
+        )
+        .unwrap();
+        let (mut i, mut j) = (hunk.original_start, hunk.fixed_start);
+        for line in &hunk.lines {
+            let (marker, text, last) = match *line {
+                DiffLine::Context(text) => {
+                    (i, j) = (i + 1, j + 1);
+                    (' ', text, original_last == Some(i - 1))
+                }
+                DiffLine::Removed(text) => {
+                    i += 1;
+                    ('-', text, original_last == Some(i - 1))
+                }
+                DiffLine::Added(text) => {
+                    j += 1;
+                    ('+', text, fixed_last == Some(j - 1))
+                }
+            };
+            writeln!(output, "{marker}{text}").unwrap();
+            if last {
+                output.push_str("\\ No newline at end of file\n");
+            }
+        }
+    }
+    output
+}
+
+/// The `start,len` of a hunk header; an empty range is given by the line
+/// before it.
+fn hunk_range(start: usize, len: usize) -> String {
+    if len == 0 {
+        format!("{},0", start - 1)
+    } else {
+        format!("{start},{len}")
+    }
+}
+
+fn without_newline(line: &str) -> &str {
+    line.strip_suffix('\n').unwrap_or(line)
+}
+
+/// The shortest edit script turning the lines `a` into `b`.
+fn edit_script(a: &[&str], b: &[&str]) -> Vec<Op> {
+    // Fixes change few lines of a file, so its common ends are skipped
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+
+    let mut ops = vec![Op::Equal; prefix];
+    ops.extend(myers(
+        &a[prefix..a.len() - suffix],
+        &b[prefix..b.len() - suffix],
+    ));
+    ops.extend(std::iter::repeat_n(Op::Equal, suffix));
+    ops
+}
+
+/// Myers' O(ND) diff: for each number of edits `d`, how far along each
+/// diagonal `k = x - y` the furthest path reaches, then the path back.
+fn myers(a: &[&str], b: &[&str]) -> Vec<Op> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = n + m;
+    if max == 0 {
+        return Vec::new();
+    }
+
+    // Furthest x on each diagonal, indexed by k + max
+    let mut v = vec![0isize; 2 * max as usize + 2];
+    let at = |k: isize| (k + max) as usize;
+    // The reach of diagonals -d..=d after each d, indexed by k + d
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    'search: for d in 0..=max {
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[at(k - 1)] < v[at(k + 1)]) {
+                v[at(k + 1)]
+            } else {
+                v[at(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[at(k)] = x;
+            if x >= n && y >= m {
+                trace.push(v[at(-d)..=at(d)].to_vec());
+                break 'search;
+            }
+        }
+        trace.push(v[at(-d)..=at(d)].to_vec());
+    }
+
+    let mut ops = Vec::with_capacity(max as usize);
+    let (mut x, mut y) = (n, m);
+    for d in (1..trace.len() as isize).rev() {
+        let previous = &trace[d as usize - 1];
+        let reach = |k: isize| previous[(k + d - 1) as usize];
+        let k = x - y;
+        let previous_k = if k == -d || (k != d && reach(k - 1) < reach(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let previous_x = reach(previous_k);
+        let previous_y = previous_x - previous_k;
+        while x > previous_x && y > previous_y {
+            ops.push(Op::Equal);
+            x -= 1;
+            y -= 1;
+        }
+        ops.push(if previous_k == k + 1 {
+            Op::Insert
+        } else {
+            Op::Delete
+        });
+        (x, y) = (previous_x, previous_y);
+    }
+    ops.extend(std::iter::repeat_n(Op::Equal, x as usize));
+    ops.reverse();
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_line() {
+        let hunks = diff_lines("a\nint x=1;\nb\n", "a\nint x = 1;\nb\n");
+        assert_eq!(hunks.len(), 1);
+        assert_eq!((hunks[0].original_start, hunks[0].fixed_start), (1, 1));
+        assert_eq!(
+            hunks[0].lines,
+            vec![
+                DiffLine::Context("a"),
+                DiffLine::Removed("int x=1;"),
+                DiffLine::Added("int x = 1;"),
+                DiffLine::Context("b"),
+            ]
+        );
+        assert_eq!((hunks[0].original_len(), hunks[0].fixed_len()), (4, 4));
+    }
+
+    #[test]
+    fn test_identical() {
+        assert!(diff_lines("a\nb\n", "a\nb\n").is_empty());
+        assert!(unified_diff("a/F.java", "b/F.java", "a\nb\n", "a\nb\n").is_empty());
+    }
+
+    #[test]
+    fn test_inserted_lines_align() {
+        let original = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let fixed = "1\n2\nnew\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let hunks = diff_lines(original, fixed);
+        assert_eq!(hunks.len(), 1);
+        assert_eq!((hunks[0].original_start, hunks[0].fixed_start), (1, 1));
+        assert_eq!((hunks[0].original_len(), hunks[0].fixed_len()), (5, 6));
+        assert_eq!(hunks[0].lines[2], DiffLine::Added("new"));
+    }
+
+    #[test]
+    fn test_hunks_split_and_merge() {
+        let original: String = (1..=30).map(|i| format!("{i}\n")).collect();
+        let fixed = original
+            .replace("\n2\n", "\ntwo\n")
+            .replace("\n8\n", "\neight\n")
+            .replace("\n25\n", "\ntwenty-five\n");
+        let hunks = diff_lines(&original, &fixed);
+        assert_eq!(hunks.len(), 2);
+        // 2 and 8 are six lines apart, so their context joins up
+        assert_eq!((hunks[0].original_start, hunks[0].original_len()), (1, 11));
+        assert_eq!((hunks[1].original_start, hunks[1].original_len()), (22, 7));
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        let diff = unified_diff(
+            "a/F.java",
+            "b/F.java",
+            "class F {\nint x=1;\n}",
+            "class F {\nint x = 1;\n}\n",
+        );
+        assert_eq!(
+            diff,
+            "--- a/F.java\n+++ b/F.java\n@@ -1,3 +1,3 @@\n class F {\n-int x=1;\n-}\n\\ No newline at end of file\n+int x = 1;\n+}\n"
+        );
+
+        let removed = unified_diff("a/F.java", "b/F.java", "a\nb\n", "b\n");
+        assert_eq!(
+            removed,
+            "--- a/F.java\n+++ b/F.java\n@@ -1,2 +1,1 @@\n-a\n b\n"
+        );
+
+        let emptied = unified_diff("a/F.java", "b/F.java", "a\n", "");
+        assert_eq!(emptied, "--- a/F.java\n+++ b/F.java\n@@ -1,1 +0,0 @@\n-a\n");
+    }
+}
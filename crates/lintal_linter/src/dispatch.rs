@@ -0,0 +1,278 @@
+//! Dispatch of CST nodes to the rules interested in them.
+
+use lintal_java_parser::{Java, Language};
+use tree_sitter::Query;
+
+use crate::Rule;
+
+/// Which rules to run for each node kind, indexed by kind id.
+pub(crate) struct DispatchTable {
+    per_kind: Vec<Vec<usize>>,
+    catch_all: Vec<usize>,
+    /// Rules that check whole files instead of nodes
+    file_rules: Vec<usize>,
+    /// Rules that find their nodes with a query, with the query compiled
+    query_rules: Vec<(usize, Query)>,
+    /// Rules that keep per-file state; they are dispatched nodes as well
+    stateful_rules: Vec<usize>,
+    /// Kinds that have any rules, or `None` if some rule wants every node
+    dispatched_kinds: Option<Vec<u16>>,
+}
+
+impl DispatchTable {
+    pub(crate) fn new(rules: &[Box<dyn Rule>]) -> Self {
+        let language = Java;
+        let kind_ids = language.kind_ids();
+        let kind_count = kind_ids.kind_count();
+        let mut per_kind: Vec<Vec<usize>> = vec![Vec::new(); kind_count];
+        let mut catch_all = Vec::new();
+        let mut file_rules = Vec::new();
+        let mut query_rules = Vec::new();
+        let mut stateful_rules = Vec::new();
+        let mut unknown_kinds: Vec<(&'static str, &'static str)> = Vec::new();
+
+        for (idx, rule) in rules.iter().enumerate() {
+            if rule.as_file_rule().is_some() {
+                file_rules.push(idx);
+                continue;
+            }
+            if let Some(query_rule) = rule.as_query_rule() {
+                match Query::new(&language.grammar(), query_rule.query()) {
+                    Ok(query) => query_rules.push((idx, query)),
+                    Err(err) => {
+                        tracing::warn!(rule = rule.name(), %err, "skipping rule with invalid query")
+                    }
+                }
+                continue;
+            }
+            if rule.as_stateful_rule().is_some() {
+                stateful_rules.push(idx);
+            }
+            let Some(ids) = rule.relevant_kind_ids() else {
+                catch_all.push(idx);
+                continue;
+            };
+
+            for id in ids {
+                let slot = &mut per_kind[id as usize];
+                if !slot.contains(&idx) {
+                    slot.push(idx);
+                }
+            }
+            for &kind in rule.relevant_kinds() {
+                if !kind_ids.contains(kind) {
+                    unknown_kinds.push((rule.name(), kind));
+                }
+            }
+        }
+
+        #[cfg(debug_assertions)]
+        if !unknown_kinds.is_empty() {
+            let mut seen: std::collections::HashSet<(&'static str, &'static str)> =
+                std::collections::HashSet::new();
+            for (rule, kind) in unknown_kinds {
+                if seen.insert((rule, kind)) {
+                    eprintln!(
+                        "Debug: rule '{}' references unknown node kind '{}'",
+                        rule, kind
+                    );
+                }
+            }
+        }
+
+        // Pre-compute which kinds have any rules
+        let dispatched_kinds = catch_all.is_empty().then(|| {
+            (0..kind_count as u16)
+                .filter(|&id| !per_kind[id as usize].is_empty())
+                .collect()
+        });
+
+        Self {
+            per_kind,
+            catch_all,
+            file_rules,
+            query_rules,
+            stateful_rules,
+            dispatched_kinds,
+        }
+    }
+
+    /// Kind ids of the nodes any rule wants to see, or `None` if every node
+    /// has to be visited.
+    pub(crate) fn dispatched_kinds(&self) -> Option<&[u16]> {
+        self.dispatched_kinds.as_deref()
+    }
+
+    /// Indices of the rules to run once per file, outside the traversal.
+    pub(crate) fn file_rule_indices(&self) -> &[usize] {
+        &self.file_rules
+    }
+
+    /// Rules to run once per file over their query's matches, with the
+    /// compiled query.
+    pub(crate) fn query_rules(&self) -> &[(usize, Query)] {
+        &self.query_rules
+    }
+
+    /// Indices of the rules that begin and finish each file around the
+    /// traversal.
+    pub(crate) fn stateful_rule_indices(&self) -> &[usize] {
+        &self.stateful_rules
+    }
+
+    pub(crate) fn rule_indices_for_kind(&self, kind_id: u16) -> impl Iterator<Item = usize> + '_ {
+        self.per_kind[kind_id as usize]
+            .iter()
+            .copied()
+            .chain(self.catch_all.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_parser::java_kind_ids;
+
+    struct Kinds(&'static [&'static str]);
+
+    impl Rule for Kinds {
+        fn name(&self) -> &'static str {
+            "Kinds"
+        }
+
+        fn relevant_kinds(&self) -> &'static [&'static str] {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_dispatch_by_kind_id() {
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(Kinds(&["class_declaration"])),
+            Box::new(Kinds(&["class_declaration", "identifier"])),
+            // A misspelt kind matches nothing rather than everything
+            Box::new(Kinds(&["no_such_kind"])),
+        ];
+        let table = DispatchTable::new(&rules);
+
+        let class = java_kind_ids(["class_declaration"])[0];
+        let identifier = java_kind_ids(["identifier"])[0];
+        let program = java_kind_ids(["program"])[0];
+        let indices = |id| table.rule_indices_for_kind(id).collect::<Vec<_>>();
+        assert_eq!(indices(class), [0, 1]);
+        assert_eq!(indices(identifier), [1]);
+        assert!(indices(program).is_empty());
+        assert!(table.dispatched_kinds().is_some());
+    }
+
+    #[test]
+    fn test_catch_all_visits_every_node() {
+        let rules: Vec<Box<dyn Rule>> = vec![Box::new(Kinds(&[]))];
+        let table = DispatchTable::new(&rules);
+        assert!(table.dispatched_kinds().is_none());
+        let program = java_kind_ids(["program"])[0];
+        assert_eq!(
+            table.rule_indices_for_kind(program).collect::<Vec<_>>(),
+            [0]
+        );
+    }
+
+    #[test]
+    fn test_file_rules_stay_out_of_the_walk() {
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(crate::rules::FileLength { max: 2000 }),
+            Box::new(Kinds(&["class_declaration"])),
+        ];
+        let table = DispatchTable::new(&rules);
+
+        assert_eq!(table.file_rule_indices(), [0]);
+        let class = java_kind_ids(["class_declaration"])[0];
+        let program = java_kind_ids(["program"])[0];
+        assert_eq!(table.rule_indices_for_kind(class).collect::<Vec<_>>(), [1]);
+        assert!(table.rule_indices_for_kind(program).next().is_none());
+        assert_eq!(table.dispatched_kinds(), Some(&[class][..]));
+    }
+
+    struct Queries(&'static str);
+
+    impl Rule for Queries {
+        fn name(&self) -> &'static str {
+            "Queries"
+        }
+
+        fn as_query_rule(&self) -> Option<&dyn crate::QueryRule> {
+            Some(self)
+        }
+    }
+
+    impl crate::QueryRule for Queries {
+        fn query(&self) -> &str {
+            self.0
+        }
+
+        fn check_match(
+            &self,
+            _ctx: &crate::CheckContext,
+            _captures: &crate::QueryCaptures,
+        ) -> Vec<lintal_diagnostics::Diagnostic> {
+            vec![]
+        }
+    }
+
+    #[test]
+    fn test_query_rules_are_compiled_once() {
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(Queries("(class_declaration name: (identifier) @name)")),
+            // Doesn't compile, so the rule is skipped
+            Box::new(Queries("(no_such_kind)")),
+        ];
+        let table = DispatchTable::new(&rules);
+
+        let indices: Vec<usize> = table.query_rules().iter().map(|(idx, _)| *idx).collect();
+        assert_eq!(indices, [0]);
+        assert_eq!(table.query_rules()[0].1.capture_names(), ["name"]);
+        // Query rules aren't dispatched nodes
+        assert_eq!(table.dispatched_kinds(), Some(&[][..]));
+    }
+
+    struct Counter;
+
+    impl Rule for Counter {
+        fn name(&self) -> &'static str {
+            "Counter"
+        }
+
+        fn relevant_kinds(&self) -> &'static [&'static str] {
+            &["class_declaration"]
+        }
+
+        fn as_stateful_rule(&self) -> Option<&dyn crate::StatefulRule> {
+            Some(self)
+        }
+    }
+
+    impl crate::StatefulRule for Counter {
+        fn begin_file<'r>(&'r self, _ctx: &crate::CheckContext) -> Box<dyn crate::FileState + 'r> {
+            Box::new(Counter)
+        }
+    }
+
+    impl crate::FileState for Counter {
+        fn finish_file(
+            self: Box<Self>,
+            _ctx: &crate::CheckContext,
+        ) -> Vec<lintal_diagnostics::Diagnostic> {
+            vec![]
+        }
+    }
+
+    #[test]
+    fn test_stateful_rules_are_dispatched_nodes() {
+        let rules: Vec<Box<dyn Rule>> = vec![Box::new(Kinds(&["identifier"])), Box::new(Counter)];
+        let table = DispatchTable::new(&rules);
+
+        assert_eq!(table.stateful_rule_indices(), [1]);
+        let class = java_kind_ids(["class_declaration"])[0];
+        assert_eq!(table.rule_indices_for_kind(class).collect::<Vec<_>>(), [1]);
+    }
+}
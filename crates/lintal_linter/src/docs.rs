@@ -0,0 +1,272 @@
+//! Java code blocks embedded in Markdown and AsciiDoc documentation.
+//!
+//! The ```` ```java ```` (or `~~~java`) fences of Markdown and the
+//! `[source,java]` listings of AsciiDoc are each linted as a source of their
+//! own, by [`Linter::lint_doc_file`](crate::Linter::lint_doc_file). Their
+//! diagnostics are reported against the document with every line outside
+//! the blocks emptied, so they are on the lines of the document they were
+//! found on; columns are counted from the indentation of the block's fence.
+//! Documentation is only checked: the diagnostics have no fixes.
+
+use std::path::Path;
+
+/// A Java code block of a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaBlock {
+    /// Zero-based line of the document the code starts on.
+    pub line: usize,
+    /// The code, without the indentation of its fence, each line ending in
+    /// a line break.
+    pub code: String,
+}
+
+/// Whether `path` is a Markdown or AsciiDoc document.
+pub fn is_doc(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| {
+        ["md", "markdown", "adoc", "asciidoc"]
+            .iter()
+            .any(|doc| ext.eq_ignore_ascii_case(doc))
+    })
+}
+
+/// Whether `path` is an AsciiDoc document.
+pub fn is_asciidoc(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("adoc") || ext.eq_ignore_ascii_case("asciidoc"))
+}
+
+/// An open code block.
+struct Open {
+    /// The line closing the block: a fence of at least `len` of `marker`,
+    /// or AsciiDoc's exact delimiter.
+    marker: char,
+    len: usize,
+    exact: Option<String>,
+    indent: usize,
+    java: bool,
+    line: usize,
+    code: String,
+}
+
+/// The Java code blocks of `text`, a Markdown document, or an AsciiDoc one
+/// if `asciidoc`. Markdown fences are recognized in both, as Asciidoctor
+/// does.
+pub fn java_blocks(text: &str, asciidoc: bool) -> Vec<JavaBlock> {
+    let mut blocks = Vec::new();
+    let mut open: Option<Open> = None;
+    // An AsciiDoc `[source,java]` line applies to the listing that follows
+    let mut source_java = None;
+
+    for (number, line) in text.lines().enumerate() {
+        if let Some(block) = &mut open {
+            if closes(block, line) {
+                if block.java {
+                    blocks.push(JavaBlock {
+                        line: block.line,
+                        code: std::mem::take(&mut block.code),
+                    });
+                }
+                open = None;
+            } else {
+                block
+                    .code
+                    .push_str(&code_line(line, block.indent, block.exact.is_some()));
+                block.code.push('\n');
+            }
+            continue;
+        }
+
+        if asciidoc {
+            if let Some(java) = source_java.take()
+                && is_listing_delimiter(line)
+            {
+                open = Some(Open {
+                    marker: '-',
+                    len: line.len(),
+                    exact: Some(line.trim_end().to_string()),
+                    indent: 0,
+                    java,
+                    line: number + 1,
+                    code: String::new(),
+                });
+                continue;
+            }
+            source_java = source_language(line).map(|lang| lang.eq_ignore_ascii_case("java"));
+        }
+
+        if let Some((marker, len, indent, info)) = opening_fence(line) {
+            let lang = info
+                .split(|c: char| c.is_whitespace() || c == ',' || c == '{')
+                .next()
+                .unwrap_or_default();
+            open = Some(Open {
+                marker,
+                len,
+                exact: None,
+                indent,
+                java: lang.eq_ignore_ascii_case("java"),
+                line: number + 1,
+                code: String::new(),
+            });
+        }
+    }
+
+    // A block left open runs to the end of the document, as in CommonMark
+    if let Some(block) = open
+        && block.java
+    {
+        blocks.push(JavaBlock {
+            line: block.line,
+            code: block.code,
+        });
+    }
+    blocks
+}
+
+/// The code of `blocks` on their lines of the document, every other line
+/// empty, with the offset each block's code starts at.
+pub fn blocks_source(blocks: &[JavaBlock]) -> (String, Vec<usize>) {
+    let mut source = String::new();
+    let mut starts = Vec::with_capacity(blocks.len());
+    let mut line = 0;
+    for block in blocks {
+        for _ in line..block.line {
+            source.push('\n');
+        }
+        starts.push(source.len());
+        source.push_str(&block.code);
+        line = block.line + block.code.lines().count();
+    }
+    (source, starts)
+}
+
+/// A Markdown fence opening a code block: its marker, how many of them,
+/// its indentation and its info string.
+fn opening_fence(line: &str) -> Option<(char, usize, usize, &str)> {
+    let trimmed = line.trim_start_matches(' ');
+    let indent = line.len() - trimmed.len();
+    let marker = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let len = trimmed.len() - trimmed.trim_start_matches(marker).len();
+    let info = trimmed[len..].trim();
+    // Backticks can't appear in the info string of a backtick fence
+    let valid = indent <= 3 && len >= 3 && !(marker == '`' && info.contains('`'));
+    valid.then_some((marker, len, indent, info))
+}
+
+/// Whether `line` closes the open `block`.
+fn closes(block: &Open, line: &str) -> bool {
+    if let Some(exact) = &block.exact {
+        return line.trim_end() == exact;
+    }
+    let trimmed = line.trim_start_matches(' ');
+    let indent = line.len() - trimmed.len();
+    let len = trimmed.len() - trimmed.trim_start_matches(block.marker).len();
+    indent <= 3 && len >= block.len && trimmed[len..].trim().is_empty()
+}
+
+/// A line of code without up to `indent` spaces of the fence's
+/// indentation, and without trailing AsciiDoc callouts such as `<1>`.
+fn code_line(line: &str, indent: usize, asciidoc: bool) -> String {
+    let strip = line.len() - line.trim_start_matches(' ').len();
+    let line = &line[strip.min(indent)..];
+    if asciidoc {
+        return strip_callouts(line).to_string();
+    }
+    line.to_string()
+}
+
+/// `line` without the callouts at its end, unless they are in a `//`
+/// comment, where they are valid Java.
+fn strip_callouts(line: &str) -> &str {
+    let mut code = line.trim_end();
+    while let Some(before) = code
+        .strip_suffix('>')
+        .and_then(|rest| {
+            rest.trim_end_matches(|c: char| c.is_ascii_digit())
+                .strip_suffix('<')
+        })
+        .filter(|before| before.ends_with(char::is_whitespace))
+    {
+        code = before.trim_end();
+    }
+    if code.len() == line.trim_end().len() || code.ends_with("//") {
+        line
+    } else {
+        code
+    }
+}
+
+/// Whether `line` delimits an AsciiDoc listing block: four or more hyphens.
+fn is_listing_delimiter(line: &str) -> bool {
+    let line = line.trim_end();
+    line.len() >= 4 && line.bytes().all(|b| b == b'-')
+}
+
+/// The language of an AsciiDoc `[source,lang]` block attribute line, or
+/// `[,lang]` for short.
+fn source_language(line: &str) -> Option<&str> {
+    let attributes = line.trim().strip_prefix('[')?.strip_suffix(']')?;
+    let mut positional = attributes.split(',').map(str::trim);
+    let style = positional.next()?;
+    if style != "source" && !style.is_empty() {
+        return None;
+    }
+    positional.next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_blocks() {
+        let text = "# Usage\n\n```java\nint x=1;\n```\n\n```bash\nls\n```\n\n  ~~~~ Java title\n  if (a) {\n    b();\n  }\n  ~~~~\n";
+        let blocks = java_blocks(text, false);
+        assert_eq!(
+            blocks,
+            vec![
+                JavaBlock {
+                    line: 3,
+                    code: "int x=1;\n".to_string(),
+                },
+                JavaBlock {
+                    line: 11,
+                    code: "if (a) {\n  b();\n}\n".to_string(),
+                },
+            ]
+        );
+
+        let (source, starts) = blocks_source(&blocks);
+        assert_eq!(source.lines().nth(3), Some("int x=1;"));
+        assert_eq!(source.lines().nth(12), Some("  b();"));
+        assert_eq!(&source[starts[1]..starts[1] + 2], "if");
+    }
+
+    #[test]
+    fn test_asciidoc_blocks() {
+        let text = "= Guide\n\n[source,java]\n----\nint x = 1; <1>\nfoo(); // <2>\n----\n<1> One\n\n[source,xml]\n----\n<a/>\n----\n\n----\nnot java\n----\n";
+        let blocks = java_blocks(text, true);
+        assert_eq!(
+            blocks,
+            vec![JavaBlock {
+                line: 4,
+                code: "int x = 1;\nfoo(); // <2>\n".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_unclosed_block() {
+        let blocks = java_blocks("```java\nclass A {}\n", false);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].code, "class A {}\n");
+    }
+
+    #[test]
+    fn test_is_doc() {
+        assert!(is_doc(Path::new("README.md")));
+        assert!(is_doc(Path::new("docs/guide.ADOC")));
+        assert!(is_asciidoc(Path::new("docs/guide.adoc")));
+        assert!(!is_doc(Path::new("Foo.java")));
+    }
+}
@@ -0,0 +1,284 @@
+//! Fix application.
+//!
+//! Fixes are applied atomically: if any [`Edit`] of a [`Fix`] conflicts with an
+//! edit that has already been applied, the whole fix is skipped rather than
+//! applying half of it. Fixes sharing an [`IsolationLevel::Group`] are applied
+//! at most once per pass; skipped fixes are picked up by the next pass.
+//!
+//! Which fixes are applied at all is up to a [`FixPolicy`], which lets the
+//! configuration override how safe a rule's fixes are. A fix with an edit
+//! that would split a character is never applied; see [`fits_source`].
+
+use std::collections::{HashMap, HashSet};
+
+use lintal_checkstyle::FixOverride;
+use lintal_diagnostics::{Applicability, Edit, Fix, IsolationLevel};
+use lintal_java_cst::TextBlocks;
+use lintal_text_size::{Ranged, TextSize};
+
+/// Maximum number of fix passes before giving up on reaching a fixed point.
+pub const MAX_FIX_PASSES: usize = 10;
+
+/// Result of applying fixes to a source file.
+#[derive(Debug, Default)]
+pub struct FixResult {
+    /// The source code with fixes applied.
+    pub code: String,
+    /// Number of fixes that were applied.
+    pub applied: usize,
+    /// Number of fixes skipped because they overlapped an applied fix
+    /// or shared an isolation group with one.
+    pub skipped: usize,
+}
+
+/// Decides which fixes are applied, honouring per-rule overrides from
+/// lintal.toml's `[fixes]` table.
+#[derive(Debug, Clone, Default)]
+pub struct FixPolicy {
+    overrides: HashMap<String, FixOverride>,
+}
+
+impl FixPolicy {
+    /// A policy with `overrides` keyed by rule name.
+    pub fn new(overrides: HashMap<String, FixOverride>) -> Self {
+        Self { overrides }
+    }
+
+    /// How safe `fix`, reported by `rule`, is taken to be. `None` if the
+    /// rule's fixes are disabled.
+    ///
+    /// Display-only fixes stay display only: they may not even produce valid
+    /// code, so no override makes them applicable.
+    pub fn applicability(&self, rule: &str, fix: &Fix) -> Option<Applicability> {
+        match (self.overrides.get(rule), fix.applicability()) {
+            (Some(FixOverride::Disabled), _) => None,
+            (_, Applicability::DisplayOnly) => Some(Applicability::DisplayOnly),
+            (Some(FixOverride::Safe), _) => Some(Applicability::Safe),
+            (Some(FixOverride::Unsafe), _) => Some(Applicability::Unsafe),
+            (None, applicability) => Some(applicability),
+        }
+    }
+
+    /// Whether `fix`, reported by `rule`, is applied when fixes of at least
+    /// `applicability` are.
+    pub fn applies(&self, rule: &str, fix: &Fix, applicability: Applicability) -> bool {
+        self.applicability(rule, fix)
+            .is_some_and(|fix_applicability| fix_applicability >= applicability)
+    }
+}
+
+/// Whether every edit of `fix` starts and ends on a character boundary of
+/// `source`. Offsets computed by byte arithmetic next to non-ASCII text,
+/// such as an emoji in a string literal, can land inside a character.
+pub fn fits_source(fix: &Fix, source: &str) -> bool {
+    fix.edits().iter().all(|edit| {
+        source.is_char_boundary(edit.start().to_usize())
+            && source.is_char_boundary(edit.end().to_usize())
+    })
+}
+
+/// Whether `fix` edits the content of a text block without replacing the
+/// whole literal. Even re-indenting it can change the string: the closing
+/// delimiter's indentation decides how much of it is incidental.
+pub fn changes_text_block(fix: &Fix, text_blocks: &TextBlocks) -> bool {
+    fix.edits()
+        .iter()
+        .any(|edit| text_blocks.changes_content(edit.range()))
+}
+
+/// Apply a set of fixes to `source` in a single pass.
+///
+/// Fixes are applied in order of their first edit. A fix whose edits overlap
+/// an already-applied edit, or whose isolation group has already been used
+/// in this pass, is skipped in its entirety, as is one that doesn't
+/// [fit](fits_source) `source`.
+pub fn apply_fixes<'a>(source: &str, fixes: impl IntoIterator<Item = &'a Fix>) -> FixResult {
+    let mut fixes: Vec<&Fix> = fixes.into_iter().collect();
+    // Stable sort keeps diagnostic order for fixes starting at the same offset
+    fixes.sort_by_key(|fix| fix.min_start());
+
+    let mut output = String::with_capacity(source.len());
+    let mut last_pos: Option<TextSize> = None;
+    let mut applied_edits: HashSet<&Edit> = HashSet::new();
+    let mut isolated: HashSet<u32> = HashSet::new();
+    let mut applied = 0;
+    let mut skipped = 0;
+
+    for fix in fixes {
+        if !fits_source(fix, source) {
+            skipped += 1;
+            continue;
+        }
+
+        // Identical edits emitted by several diagnostics only need applying once
+        let edits: Vec<&Edit> = fix
+            .edits()
+            .iter()
+            .filter(|edit| !applied_edits.contains(edit))
+            .collect();
+
+        let Some(first) = edits.first() else {
+            // Every edit was already applied by another fix
+            applied += 1;
+            continue;
+        };
+
+        if last_pos.is_some_and(|last_pos| first.start() < last_pos) || !edits_are_disjoint(&edits)
+        {
+            skipped += 1;
+            continue;
+        }
+
+        if let IsolationLevel::Group(id) = fix.isolation()
+            && !isolated.insert(id)
+        {
+            skipped += 1;
+            continue;
+        }
+
+        for edit in edits {
+            let from = usize::from(last_pos.unwrap_or_default());
+            output.push_str(&source[from..usize::from(edit.start())]);
+            output.push_str(edit.content().unwrap_or_default());
+            last_pos = Some(edit.end());
+            applied_edits.insert(edit);
+        }
+        applied += 1;
+    }
+
+    output.push_str(&source[usize::from(last_pos.unwrap_or_default())..]);
+
+    FixResult {
+        code: output,
+        applied,
+        skipped,
+    }
+}
+
+/// Check that the (sorted) edits of a single fix don't overlap each other.
+fn edits_are_disjoint(edits: &[&Edit]) -> bool {
+    edits
+        .windows(2)
+        .all(|pair| pair[0].end() <= pair[1].start())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(offset: u32) -> TextSize {
+        TextSize::new(offset)
+    }
+
+    #[test]
+    fn test_apply_single_fix() {
+        let source = "int  x;";
+        let fix = Fix::safe_edit(Edit::deletion(at(3), at(4)));
+        let result = apply_fixes(source, [&fix]);
+        assert_eq!(result.code, "int x;");
+        assert_eq!(result.applied, 1);
+        assert_eq!(result.skipped, 0);
+    }
+
+    #[test]
+    fn test_non_overlapping_fixes_all_apply() {
+        let source = "a(b)c";
+        let first = Fix::safe_edit(Edit::insertion(" ".to_string(), at(2)));
+        let second = Fix::safe_edit(Edit::insertion(" ".to_string(), at(3)));
+        let result = apply_fixes(source, [&second, &first]);
+        assert_eq!(result.code, "a( b )c");
+        assert_eq!(result.applied, 2);
+    }
+
+    #[test]
+    fn test_conflicting_multi_edit_fix_is_skipped_entirely() {
+        let source = "if (x) foo();";
+        // Wraps the statement in braces using two edits
+        let braces = Fix::safe_edits(
+            Edit::insertion("{ ".to_string(), at(7)),
+            [Edit::insertion(" }".to_string(), at(13))],
+        );
+        // Conflicts with the closing edit only
+        let other = Fix::safe_edit(Edit::replacement("bar();".to_string(), at(7), at(13)));
+        let result = apply_fixes(source, [&other, &braces]);
+
+        // The first fix (by start offset, then input order) wins and the
+        // brace fix must not be half-applied
+        assert_eq!(result.code, "if (x) bar();");
+        assert_eq!(result.applied, 1);
+        assert_eq!(result.skipped, 1);
+    }
+
+    #[test]
+    fn test_isolation_group_applies_once_per_pass() {
+        let source = "abc";
+        let first = Fix::safe_edit(Edit::replacement("A".to_string(), at(0), at(1)))
+            .isolate(IsolationLevel::Group(1));
+        let second = Fix::safe_edit(Edit::replacement("C".to_string(), at(2), at(3)))
+            .isolate(IsolationLevel::Group(1));
+        let third = Fix::safe_edit(Edit::replacement("B".to_string(), at(1), at(2)))
+            .isolate(IsolationLevel::Group(2));
+        let result = apply_fixes(source, [&first, &second, &third]);
+        assert_eq!(result.code, "ABc");
+        assert_eq!(result.applied, 2);
+        assert_eq!(result.skipped, 1);
+    }
+
+    #[test]
+    fn test_identical_edits_applied_once() {
+        let source = "x=1;";
+        let first = Fix::safe_edit(Edit::insertion(" ".to_string(), at(1)));
+        let second = Fix::safe_edit(Edit::insertion(" ".to_string(), at(1)));
+        let result = apply_fixes(source, [&first, &second]);
+        assert_eq!(result.code, "x =1;");
+        assert_eq!(result.applied, 2);
+        assert_eq!(result.skipped, 0);
+    }
+
+    #[test]
+    fn test_fix_splitting_a_character_is_skipped() {
+        let source = "s = \"\u{1F600}\";";
+        // Offset 6 is inside the four bytes of the emoji
+        let inside = Fix::safe_edit(Edit::insertion(" ".to_string(), at(6)));
+        let after = Fix::safe_edit(Edit::insertion(" ".to_string(), at(10)));
+        assert!(!fits_source(&inside, source));
+        assert!(fits_source(&after, source));
+
+        let result = apply_fixes(source, [&inside, &after]);
+        assert_eq!(result.code, "s = \"\u{1F600} \";");
+        assert_eq!(result.applied, 1);
+        assert_eq!(result.skipped, 1);
+    }
+
+    #[test]
+    fn test_no_fixes_returns_source() {
+        let source = "class Foo {}";
+        let result = apply_fixes(source, std::iter::empty());
+        assert_eq!(result.code, source);
+        assert_eq!(result.applied, 0);
+    }
+
+    #[test]
+    fn test_fix_policy_overrides() {
+        let policy = FixPolicy::new(HashMap::from([
+            ("Risky".to_string(), FixOverride::Unsafe),
+            ("Trusted".to_string(), FixOverride::Safe),
+            ("Off".to_string(), FixOverride::Disabled),
+        ]));
+        let safe = Fix::safe_edit(Edit::deletion(at(0), at(1)));
+        let unsafe_fix = Fix::unsafe_edit(Edit::deletion(at(0), at(1)));
+        let display = Fix::display_only_edit(Edit::deletion(at(0), at(1)));
+
+        assert!(policy.applies("Other", &safe, Applicability::Safe));
+        assert!(!policy.applies("Other", &unsafe_fix, Applicability::Safe));
+
+        assert!(!policy.applies("Risky", &safe, Applicability::Safe));
+        assert!(policy.applies("Risky", &safe, Applicability::Unsafe));
+
+        assert!(policy.applies("Trusted", &unsafe_fix, Applicability::Safe));
+        assert!(!policy.applies("Trusted", &display, Applicability::Unsafe));
+
+        assert!(!policy.applies("Off", &safe, Applicability::Unsafe));
+        assert_eq!(policy.applicability("Off", &safe), None);
+    }
+}
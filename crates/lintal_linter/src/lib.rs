@@ -1,21 +1,65 @@
 //! Java linter with auto-fix support.
 
+#[cfg(not(target_family = "wasm"))]
+pub mod archive;
+pub mod audit;
+pub mod codes;
+pub mod diff;
+mod dispatch;
+pub mod docs;
+pub mod fix;
+pub mod lint;
+mod memo;
+pub mod metrics;
+pub mod organize_imports;
+#[cfg(not(target_family = "wasm"))]
+pub mod plugin;
+pub mod query;
 pub mod registry;
 pub mod rules;
 pub mod suppression;
+#[cfg(feature = "wasm-plugins")]
+pub mod wasm;
 
-pub use registry::{FromConfig, Properties, RuleRegistry};
-pub use suppression::{FileSuppressionsConfig, PlainTextCommentFilterConfig, SuppressionContext};
+pub use audit::{AuditEvent, AuditListener, AuditSummary};
+pub use fix::FixPolicy;
+pub use lint::{
+    FixedFile, FixedSource, LintedFile, Linter, RuleDiagnostic, SkipReason, SkippedFix,
+    SourceError, SuppressedBy, SuppressedDiagnostic, fix_source, lint_source, lint_source_at,
+};
+#[cfg(not(target_family = "wasm"))]
+pub use plugin::{PluginError, PluginLibrary};
+pub use query::QueryCaptures;
+pub use registry::{FromConfig, Properties, RuleError, RuleRegistry};
+pub use suppression::{
+    CommentFilterConfig, CommentFilterKind, FileSuppressionRule, FileSuppressionsConfig,
+    NumberRanges, PlainTextCommentFilterConfig, SuppressWarningsAliases, SuppressionContext,
+    SuppressionPolicy, SuppressionUsage, UnusedSuppressionViolation,
+};
+#[cfg(feature = "wasm-plugins")]
+pub use wasm::WasmPlugin;
+
+use std::any::Any;
+use std::path::Path;
+use std::sync::OnceLock;
 
 use lintal_diagnostics::Diagnostic;
-use lintal_java_cst::CstNode;
-use lintal_source_file::{LineIndex, SourceCode};
+use lintal_java_cst::{CommentMap, CstNode, TextBlocks, TokenStream};
+use lintal_java_parser::java_kind_ids;
+use lintal_java_semantic::{ProjectIndex, SemanticModel};
+use lintal_source_file::{LineIndex, PositionEncoding, SourceCode};
 use lintal_text_size::TextRange;
 
+use crate::memo::Memo;
+
 /// Context provided to rules during checking.
 pub struct CheckContext<'a> {
     source: &'a str,
     line_index: LineIndex,
+    semantic: OnceLock<SemanticModel>,
+    memo: Memo,
+    path: Option<&'a Path>,
+    project: Option<&'a ProjectIndex>,
 }
 
 impl<'a> CheckContext<'a> {
@@ -23,9 +67,37 @@ impl<'a> CheckContext<'a> {
         Self {
             source,
             line_index: LineIndex::from_source_text(source),
+            semantic: OnceLock::new(),
+            memo: Memo::default(),
+            path: None,
+            project: None,
         }
     }
 
+    /// Tell rules which file they are checking.
+    pub fn with_path(mut self, path: &'a Path) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    /// Get the path of the file being checked, `None` for sources that were
+    /// not read from a file.
+    pub fn path(&self) -> Option<&'a Path> {
+        self.path
+    }
+
+    /// Give rules access to the project-wide index.
+    pub fn with_project(mut self, project: &'a ProjectIndex) -> Self {
+        self.project = Some(project);
+        self
+    }
+
+    /// Get the project-wide index. Only built when a configured rule asks for
+    /// it with [`Rule::needs_project_index`].
+    pub fn project(&self) -> Option<&'a ProjectIndex> {
+        self.project
+    }
+
     /// Get the source text.
     pub fn source(&self) -> &'a str {
         self.source
@@ -36,6 +108,41 @@ impl<'a> CheckContext<'a> {
         &self.line_index
     }
 
+    /// Get the semantic model (scopes, symbols, types) of the file containing
+    /// `node`, building it on first use.
+    pub fn semantic(&self, node: &CstNode) -> &SemanticModel {
+        self.semantic
+            .get_or_init(|| SemanticModel::build(self.source, &root_of(node)))
+    }
+
+    /// Get the comments of the file containing `node` and the nodes they are
+    /// attached to, collecting them on first use.
+    pub fn comments(&self, node: &CstNode) -> &CommentMap {
+        self.memo(|| CommentMap::build(&root_of(node)))
+    }
+
+    /// Get the tokens of the file containing `node`, collecting them on first
+    /// use.
+    pub fn tokens(&self, node: &CstNode) -> &TokenStream {
+        self.memo(|| TokenStream::build(&root_of(node)))
+    }
+
+    /// Get the text blocks of the file containing `node`, collecting them on
+    /// first use.
+    pub fn text_blocks(&self, node: &CstNode) -> &TextBlocks {
+        self.memo(|| TextBlocks::build(&root_of(node)))
+    }
+
+    /// Get the file's value of type `T`, computing it with `compute` the first
+    /// time any rule asks for it.
+    ///
+    /// For analyses several rules need, such as the imports of the file:
+    /// wrap the result in a type of its own and every rule asking for that
+    /// type shares one computation per file.
+    pub fn memo<T: Any + Send + Sync>(&self, compute: impl FnOnce() -> T) -> &T {
+        self.memo.get_or_insert_with(compute)
+    }
+
     /// Get the source code helper for line/column info.
     pub fn source_code(&self) -> SourceCode<'a, '_> {
         SourceCode::new(self.source, &self.line_index)
@@ -57,18 +164,150 @@ impl<'a> CheckContext<'a> {
     }
 }
 
+/// The root of the tree containing `node`.
+fn root_of<'a>(node: &CstNode<'a>) -> CstNode<'a> {
+    std::iter::successors(Some(*node), CstNode::parent)
+        .last()
+        .unwrap_or(*node)
+}
+
 /// Trait for lint rules.
 pub trait Rule: Send + Sync {
     /// The rule's name (matching checkstyle module name).
     fn name(&self) -> &'static str;
 
+    /// The rule's short, stable code, e.g. `WS001`; see [`codes`]. Reported
+    /// on its diagnostics and accepted wherever the name is.
+    fn code(&self) -> Option<&'static str> {
+        codes::rule_code(self.name())
+    }
+
+    /// A one-line description of what the rule checks, for reports that
+    /// describe their rules.
+    fn description(&self) -> Option<&'static str> {
+        codes::description(self.name())
+    }
+
+    /// A URL documenting the rule, by default that of the checkstyle check
+    /// it implements; see [`codes::help_uri`].
+    fn help_uri(&self) -> Option<String> {
+        codes::help_uri(self.name())
+    }
+
     /// Node kinds this rule cares about. Empty means run on all nodes.
     fn relevant_kinds(&self) -> &'static [&'static str] {
         &[]
     }
 
-    /// Check a CST node for violations.
-    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic>;
+    /// Kind ids of the nodes this rule cares about, or `None` to run on all
+    /// nodes. Resolved once, when the rules are set up, so nodes are
+    /// dispatched by comparing ids rather than kind names.
+    ///
+    /// Defaults to the ids of [`Rule::relevant_kinds`].
+    fn relevant_kind_ids(&self) -> Option<Vec<u16>> {
+        let kinds = self.relevant_kinds();
+        (!kinds.is_empty()).then(|| java_kind_ids(kinds.iter().copied()))
+    }
+
+    /// How checkstyle counts columns in this rule's violations.
+    ///
+    /// AST checks report columns in code points; checks that scan raw line
+    /// text report them in UTF-16 code units.
+    fn column_encoding(&self) -> PositionEncoding {
+        PositionEncoding::Utf32
+    }
+
+    /// Whether this rule looks at other files through [`CheckContext::project`].
+    ///
+    /// Building the index takes an extra pass over every file, so it is only
+    /// done when a configured rule needs it.
+    fn needs_project_index(&self) -> bool {
+        false
+    }
+
+    /// This rule as a [`FileRule`], if it checks whole files rather than
+    /// nodes.
+    fn as_file_rule(&self) -> Option<&dyn FileRule> {
+        None
+    }
+
+    /// This rule as a [`QueryRule`], if it finds the nodes it checks with a
+    /// tree-sitter query rather than by kind.
+    fn as_query_rule(&self) -> Option<&dyn QueryRule> {
+        None
+    }
+
+    /// This rule as a [`StatefulRule`], if it accumulates state over a file
+    /// and reports once the file has been walked.
+    fn as_stateful_rule(&self) -> Option<&dyn StatefulRule> {
+        None
+    }
+
+    /// Check a CST node for violations. File, query and stateful rules don't
+    /// implement this.
+    fn check(&self, _ctx: &CheckContext, _node: &CstNode) -> Vec<Diagnostic> {
+        Vec::new()
+    }
+}
+
+/// Trait for rules that check a file as a whole, like checkstyle's
+/// Checker-level modules.
+///
+/// These run once per file, before the tree traversal, and are never
+/// dispatched nodes. They are configured like any other rule and return
+/// themselves from [`Rule::as_file_rule`].
+pub trait FileRule: Rule {
+    /// Check the source of a file for violations. `path` is `None` for
+    /// sources that were not read from a file.
+    fn check_file(
+        &self,
+        path: Option<&Path>,
+        source: &str,
+        line_index: &LineIndex,
+    ) -> Vec<Diagnostic>;
+}
+
+/// Trait for rules that find the nodes they check with a tree-sitter query,
+/// an S-expression pattern like
+/// `((identifier) @name (#match? @name "^[a-z]"))`.
+///
+/// The query is compiled once, when the rules are set up, and run over each
+/// file's tree; every match is handed to [`QueryRule::check_match`] with its
+/// captures. These rules are never dispatched nodes, and return themselves
+/// from [`Rule::as_query_rule`]. A query that doesn't compile against the
+/// grammar is logged, and the rule skipped.
+pub trait QueryRule: Rule {
+    /// The query, over the node kinds of the Java grammar.
+    fn query(&self) -> &str;
+
+    /// Check one match of the query for violations.
+    fn check_match(&self, ctx: &CheckContext, captures: &QueryCaptures) -> Vec<Diagnostic>;
+}
+
+/// Trait for rules that accumulate state over a file and report once it has
+/// been walked, like checks that count or order declarations across a file.
+///
+/// [`StatefulRule::begin_file`] is called before the traversal and returns
+/// the [`FileState`] for that file, which is then visited the rule's
+/// [`Rule::relevant_kinds`] in document order and finally asked for what is
+/// left with [`FileState::finish_file`]. Keeping the state out of the rule
+/// lets files still be linted in parallel. These rules return themselves from
+/// [`Rule::as_stateful_rule`].
+pub trait StatefulRule: Rule {
+    /// Start checking a file.
+    fn begin_file<'r>(&'r self, ctx: &CheckContext) -> Box<dyn FileState + 'r>;
+}
+
+/// The state of a [`StatefulRule`] for one file.
+pub trait FileState {
+    /// Visit a node the rule is interested in, returning any violations that
+    /// can already be reported.
+    fn visit(&mut self, _ctx: &CheckContext, _node: &CstNode) -> Vec<Diagnostic> {
+        Vec::new()
+    }
+
+    /// Finish the file, returning the violations that needed all of it.
+    fn finish_file(self: Box<Self>, ctx: &CheckContext) -> Vec<Diagnostic>;
 }
 
 /// Result of linting a file.
@@ -0,0 +1,1960 @@
+//! The check and fix pipeline: parsing, suppressions, rule dispatch and fix
+//! application.
+//!
+//! These are the entry points for embedding lintal. [`Linter`] runs a set of
+//! configured rules over sources or files the way `lintal check` and
+//! `lintal fix` do and returns structured results; the CLI only adds
+//! configuration discovery, parallelism and output. [`lint_source`] and
+//! [`fix_source`] are shorthands for a bare set of rules.
+
+use std::cell::RefCell;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use lintal_checkstyle::FixOverride;
+use lintal_diagnostics::{Applicability, Diagnostic, Fix, MessageBundles, Severity};
+use lintal_java_cst::{CstNode, TreeWalker};
+use lintal_java_parser::{JavaParser, ParseResult};
+use lintal_java_semantic::{FileSummary, ProjectIndex};
+use lintal_source_file::{Charset, DecodeError, DecodedSource, EncodeError, LineColumn, LineIndex};
+use lintal_text_size::TextSize;
+use thiserror::Error;
+
+use crate::dispatch::DispatchTable;
+use crate::docs;
+use crate::fix::{FixPolicy, MAX_FIX_PASSES, apply_fixes, changes_text_block, fits_source};
+use crate::metrics::FileMetrics;
+use crate::organize_imports::ImportLayout;
+use crate::query::check_matches;
+use crate::{
+    CheckContext, CommentFilterConfig, FileState, FileSuppressionRule, FileSuppressionsConfig,
+    PlainTextCommentFilterConfig, Rule, SuppressWarningsAliases, SuppressionContext,
+    SuppressionPolicy, SuppressionUsage, UnusedSuppressionViolation,
+};
+
+/// Size from which [`Linter::read_source`] maps a file instead of reading it.
+/// Below this, reading is as fast and avoids the cost of setting up a mapping.
+const MMAP_THRESHOLD: u64 = 256 * 1024;
+
+// Thread-local parser to avoid repeated initialization overhead
+thread_local! {
+    static PARSER: RefCell<JavaParser> = RefCell::new(JavaParser::new());
+}
+
+/// A diagnostic and the rule that reported it.
+#[derive(Debug, Clone)]
+pub struct RuleDiagnostic {
+    /// Module name of the rule.
+    pub rule: &'static str,
+    pub diagnostic: Diagnostic,
+}
+
+/// Result of fixing a source in memory.
+#[derive(Debug, Default)]
+pub struct FixedSource {
+    /// The source with fixes applied.
+    pub code: String,
+    /// Number of fixes that were applied.
+    pub applied: usize,
+    /// Diagnostics left in the fixed source.
+    pub remaining: Vec<RuleDiagnostic>,
+    /// Fixes that were left unapplied, with their diagnostics in the fixed
+    /// source. Display-only fixes are never applied, so are not listed.
+    pub skipped: Vec<SkippedFix>,
+}
+
+/// A fix that was left unapplied.
+#[derive(Debug, Clone)]
+pub struct SkippedFix {
+    pub diagnostic: RuleDiagnostic,
+    pub reason: SkipReason,
+}
+
+/// Why a fix was left unapplied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkipReason {
+    /// It still conflicted with other fixes when the passes ran out, or the
+    /// pass that would have applied it produced source that doesn't parse.
+    Overlapping,
+    /// It is unsafe and only safe fixes were applied.
+    Unsafe,
+    /// Fixes of its rule are disabled by the fix policy.
+    Disabled,
+    /// An edit of it would split a character, which is a bug in its rule.
+    Invalid,
+    /// Its diagnostic is suppressed.
+    Suppressed,
+}
+
+/// A file that was linted.
+#[derive(Debug)]
+pub struct LintedFile {
+    /// The decoded source text, shared with the parse it was linted from.
+    pub source: Arc<str>,
+    pub diagnostics: Vec<RuleDiagnostic>,
+    /// Diagnostics that were suppressed, if
+    /// [`Linter::with_suppressed_diagnostics`].
+    pub suppressed: Vec<SuppressedDiagnostic>,
+}
+
+/// A diagnostic that a suppression hid.
+#[derive(Debug, Clone)]
+pub struct SuppressedDiagnostic {
+    pub diagnostic: RuleDiagnostic,
+    pub by: SuppressedBy,
+}
+
+/// What suppressed a diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuppressedBy {
+    /// A comment or annotation in the source.
+    Directive {
+        /// One-based line it starts on.
+        line: usize,
+        /// Its text up to the end of that line, e.g.
+        /// `// CHECKSTYLE:OFF:MagicNumber`.
+        text: String,
+    },
+    /// A file suppression, such as a row of suppressions.xml.
+    File {
+        /// One-based line of the row, if read from XML.
+        line: Option<usize>,
+        checks: String,
+        files: String,
+    },
+}
+
+/// A file that was fixed in memory; see [`FixedFile::write`].
+#[derive(Debug)]
+pub struct FixedFile {
+    /// The file as it was read.
+    pub original: DecodedSource,
+    pub fixed: FixedSource,
+}
+
+impl FixedFile {
+    /// Whether any fix changed the source, or its imports were organized.
+    pub fn changed(&self) -> bool {
+        self.fixed.applied > 0 || *self.original.text != *self.fixed.code
+    }
+
+    /// Write the fixed source to `path` in the original file's charset.
+    /// Entries of archives can't be written.
+    pub fn write(&self, path: &Path) -> Result<(), SourceError> {
+        #[cfg(not(target_family = "wasm"))]
+        if crate::archive::split_entry_path(path).is_some() {
+            return Err(SourceError::InArchive {
+                path: path.to_path_buf(),
+            });
+        }
+        let bytes =
+            self.original
+                .encode(&self.fixed.code)
+                .map_err(|source| SourceError::Encode {
+                    path: path.to_path_buf(),
+                    source,
+                })?;
+        std::fs::write(path, bytes).map_err(|source| SourceError::Write {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+}
+
+/// Error reading, parsing or writing a source file.
+#[derive(Error, Debug)]
+pub enum SourceError {
+    #[error("Failed to read {}: {source}", .path.display())]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to decode as {}: {source}", .source.charset)]
+    Decode { path: PathBuf, source: DecodeError },
+    #[error("Failed to parse")]
+    Parse { path: PathBuf },
+    #[error("Skipped: {size} bytes is over the file size limit of {max} bytes")]
+    TooLarge { path: PathBuf, size: u64, max: u64 },
+    #[error("Skipped: parsing took longer than the limit of {}ms", .limit.as_millis())]
+    ParseTimeout { path: PathBuf, limit: Duration },
+    #[error("Failed to encode {}: {source}", .path.display())]
+    Encode { path: PathBuf, source: EncodeError },
+    #[error("Failed to write {}: {source}", .path.display())]
+    Write {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Can't write {}: it is inside an archive", .path.display())]
+    InArchive { path: PathBuf },
+}
+
+impl SourceError {
+    /// Whether the file was left alone for being over a limit set with
+    /// [`Linter::with_max_file_size`] or [`Linter::with_max_parse_time`],
+    /// rather than failing.
+    pub fn is_skipped(&self) -> bool {
+        matches!(self, Self::TooLarge { .. } | Self::ParseTimeout { .. })
+    }
+}
+
+/// Configured rules and the settings for running them over a set of files.
+///
+/// Built from the rules alone, a `Linter` honours suppression comments only
+/// through `@SuppressWarnings`; add the configuration's filters with the
+/// `with_*` methods.
+pub struct Linter {
+    rules: Vec<Box<dyn Rule>>,
+    dispatch: DispatchTable,
+    suppression_filters: Vec<PlainTextCommentFilterConfig>,
+    comment_filters: Vec<CommentFilterConfig>,
+    suppress_warnings_aliases: SuppressWarningsAliases,
+    suppression_policy: Option<SuppressionPolicy>,
+    file_suppressions: FileSuppressionsConfig,
+    /// Whether each file suppression has suppressed a diagnostic, if
+    /// [`Linter::with_unused_suppressions`].
+    file_suppressions_used: Vec<AtomicBool>,
+    report_unused_suppressions: bool,
+    collect_suppressed: bool,
+    charset: Charset,
+    tab_width: usize,
+    project: Option<ProjectIndex>,
+    messages: Option<MessageBundles>,
+    fix_policy: FixPolicy,
+    /// Nanoseconds spent in each rule, if [`Linter::with_rule_timings`].
+    rule_timings: Option<Vec<AtomicU64>>,
+    max_file_size: Option<u64>,
+    max_parse_time: Option<Duration>,
+    /// How imports are organized before fixing, if they are.
+    import_layout: Option<ImportLayout>,
+    /// Whether each rule runs on the Java blocks of documentation, if not
+    /// every rule does.
+    doc_rules: Option<Vec<bool>>,
+    /// The severity of each rule's diagnostics, if not all are errors.
+    severities: Option<Vec<Severity>>,
+}
+
+impl Linter {
+    pub fn new(rules: Vec<Box<dyn Rule>>) -> Self {
+        Self {
+            dispatch: DispatchTable::new(&rules),
+            rules,
+            suppression_filters: Vec::new(),
+            comment_filters: Vec::new(),
+            suppress_warnings_aliases: SuppressWarningsAliases::new(),
+            suppression_policy: None,
+            file_suppressions: FileSuppressionsConfig::new(),
+            file_suppressions_used: Vec::new(),
+            report_unused_suppressions: false,
+            collect_suppressed: false,
+            charset: Charset::default(),
+            tab_width: lintal_checkstyle::MergedConfig::DEFAULT_TAB_WIDTH,
+            project: None,
+            messages: None,
+            fix_policy: FixPolicy::default(),
+            rule_timings: None,
+            max_file_size: None,
+            max_parse_time: None,
+            import_layout: None,
+            doc_rules: None,
+            severities: None,
+        }
+    }
+
+    /// Honour suppression comments matching `filters`.
+    #[must_use]
+    pub fn with_suppression_filters(mut self, filters: Vec<PlainTextCommentFilterConfig>) -> Self {
+        self.suppression_filters = filters;
+        self
+    }
+
+    /// Honour the comments checkstyle's `SuppressionCommentFilter`s and
+    /// `SuppressWithNearbyCommentFilter`s in `filters` match.
+    #[must_use]
+    pub fn with_comment_filters(mut self, filters: Vec<CommentFilterConfig>) -> Self {
+        self.comment_filters = filters;
+        self
+    }
+
+    /// Accept `aliases` for rules in `@SuppressWarnings`.
+    #[must_use]
+    pub fn with_suppress_warnings_aliases(mut self, aliases: SuppressWarningsAliases) -> Self {
+        self.suppress_warnings_aliases = aliases;
+        self
+    }
+
+    /// Only run the rules `runs` accepts, by name, on the Java blocks of
+    /// documentation.
+    #[must_use]
+    pub fn with_doc_rules(mut self, runs: impl Fn(&str) -> bool) -> Self {
+        self.doc_rules = Some(self.rules.iter().map(|rule| runs(rule.name())).collect());
+        self
+    }
+
+    /// Report the diagnostics of each rule at the severity `severity_of`
+    /// gives its name.
+    #[must_use]
+    pub fn with_severities(mut self, severity_of: impl Fn(&str) -> Severity) -> Self {
+        self.severities = Some(
+            self.rules
+                .iter()
+                .map(|rule| severity_of(rule.name()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Report suppressions that don't meet `policy`, under
+    /// [`SuppressionPolicy::RULE_NAME`].
+    #[must_use]
+    pub fn with_suppression_policy(mut self, policy: SuppressionPolicy) -> Self {
+        self.suppression_policy = Some(policy);
+        self
+    }
+
+    /// Skip rules for the files `file_suppressions` matches.
+    #[must_use]
+    pub fn with_file_suppressions(mut self, file_suppressions: FileSuppressionsConfig) -> Self {
+        self.file_suppressions_used = file_suppressions
+            .rules()
+            .iter()
+            .map(|_| AtomicBool::new(false))
+            .collect();
+        self.file_suppressions = file_suppressions;
+        self
+    }
+
+    /// Report suppression comments and annotations that suppress nothing
+    /// under [`UnusedSuppressionViolation::RULE_NAME`], with fixes that
+    /// delete them, and track which file suppressions do; see
+    /// [`Linter::unused_file_suppressions`]. Rules that file suppressions
+    /// turn off then still run, to see whether they would report anything.
+    #[must_use]
+    pub fn with_unused_suppressions(mut self) -> Self {
+        self.report_unused_suppressions = true;
+        self
+    }
+
+    /// Keep the diagnostics suppressions hide, with what hid each, in
+    /// [`LintedFile::suppressed`]. Rules that file suppressions turn off then
+    /// still run, and files they turn off entirely are linted.
+    #[must_use]
+    pub fn with_suppressed_diagnostics(mut self) -> Self {
+        self.collect_suppressed = true;
+        self
+    }
+
+    /// Read and write files in `charset`.
+    #[must_use]
+    pub fn with_charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    /// Set the tab width columns are reported with.
+    #[must_use]
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
+    /// Give rules access to a project-wide index, built from
+    /// [`Linter::summarize_file`] over every file being linted.
+    #[must_use]
+    pub fn with_project(mut self, project: ProjectIndex) -> Self {
+        self.project = Some(project);
+        self
+    }
+
+    /// Report messages from checkstyle's message bundles where they have
+    /// them, in place of lintal's own.
+    #[must_use]
+    pub fn with_messages(mut self, messages: MessageBundles) -> Self {
+        self.messages = Some(messages);
+        self
+    }
+
+    /// Decide which fixes are applied with `policy` rather than by the
+    /// applicability rules give their fixes.
+    #[must_use]
+    pub fn with_fix_policy(mut self, policy: FixPolicy) -> Self {
+        self.fix_policy = policy;
+        self
+    }
+
+    /// Measure the time spent in each rule, summed over every source
+    /// linted; see [`Linter::rule_timings`]. Timing adds some overhead per
+    /// rule and node, so it is off by default.
+    #[must_use]
+    pub fn with_rule_timings(mut self) -> Self {
+        self.rule_timings = Some(self.rules.iter().map(|_| AtomicU64::new(0)).collect());
+        self
+    }
+
+    /// Skip files larger than `bytes` with [`SourceError::TooLarge`].
+    #[must_use]
+    pub fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = Some(bytes);
+        self
+    }
+
+    /// Skip files that take longer than `limit` to parse with
+    /// [`SourceError::ParseTimeout`].
+    #[must_use]
+    pub fn with_max_parse_time(mut self, limit: Duration) -> Self {
+        self.max_parse_time = Some(limit);
+        self
+    }
+
+    /// Organize the imports of each source with `layout` before fixing it;
+    /// see [`crate::organize_imports`].
+    #[must_use]
+    pub fn with_import_layout(mut self, layout: ImportLayout) -> Self {
+        self.import_layout = Some(layout);
+        self
+    }
+
+    /// Time spent in each rule so far, in rule order. Empty unless
+    /// [`Linter::with_rule_timings`].
+    pub fn rule_timings(&self) -> Vec<(&'static str, Duration)> {
+        self.rules
+            .iter()
+            .zip(self.rule_timings.iter().flatten())
+            .map(|(rule, nanos)| {
+                (
+                    rule.name(),
+                    Duration::from_nanos(nanos.load(Ordering::Relaxed)),
+                )
+            })
+            .collect()
+    }
+
+    pub fn fix_policy(&self) -> &FixPolicy {
+        &self.fix_policy
+    }
+
+    pub fn rules(&self) -> &[Box<dyn Rule>] {
+        &self.rules
+    }
+
+    /// Find a configured rule by module name.
+    pub fn rule(&self, name: &str) -> Option<&dyn Rule> {
+        self.rules
+            .iter()
+            .find(|rule| rule.name() == name)
+            .map(Box::as_ref)
+    }
+
+    pub fn charset(&self) -> Charset {
+        self.charset
+    }
+
+    pub fn tab_width(&self) -> usize {
+        self.tab_width
+    }
+
+    /// Whether any rule, or expanding star imports, needs
+    /// [`Linter::with_project`].
+    pub fn needs_project_index(&self) -> bool {
+        self.rules.iter().any(|rule| rule.needs_project_index())
+            || self
+                .import_layout
+                .as_ref()
+                .is_some_and(ImportLayout::expands_stars)
+    }
+
+    pub fn file_suppressions(&self) -> &FileSuppressionsConfig {
+        &self.file_suppressions
+    }
+
+    /// Whether file suppressions turn off every rule for `path`, so it
+    /// needn't be linted. Never while tracking unused suppressions or
+    /// collecting suppressed diagnostics.
+    pub fn is_file_suppressed(&self, path: &Path) -> bool {
+        !self.report_unused_suppressions
+            && !self.collect_suppressed
+            && self
+                .file_suppressions
+                .is_file_fully_suppressed(&path.to_string_lossy())
+    }
+
+    /// The file suppressions that haven't suppressed a diagnostic in the
+    /// files linted so far. Empty unless [`Linter::with_unused_suppressions`].
+    pub fn unused_file_suppressions(&self) -> Vec<&FileSuppressionRule> {
+        if !self.report_unused_suppressions {
+            return Vec::new();
+        }
+        self.file_suppressions
+            .rules()
+            .iter()
+            .zip(&self.file_suppressions_used)
+            .filter(|(_, used)| !used.load(Ordering::Relaxed))
+            .map(|(rule, _)| rule)
+            .collect()
+    }
+
+    /// Read a file and decode it with the configured charset, stripping any
+    /// byte order mark. Paths inside an archive, such as `a.jar!/A.java`, are
+    /// read from the archive; see [`crate::archive`].
+    ///
+    /// Files of [`MMAP_THRESHOLD`] bytes or more are memory-mapped rather than
+    /// read, so the only copy made is the decoded text.
+    pub fn read_source(&self, path: &Path) -> Result<DecodedSource, SourceError> {
+        let read_error = |source| SourceError::Read {
+            path: path.to_path_buf(),
+            source,
+        };
+        let decode = |bytes: &[u8]| {
+            lintal_source_file::decode(bytes, self.charset).map_err(|source| SourceError::Decode {
+                path: path.to_path_buf(),
+                source,
+            })
+        };
+
+        #[cfg(not(target_family = "wasm"))]
+        if let Some((archive, entry)) = crate::archive::split_entry_path(path) {
+            return decode(&crate::archive::read_entry(
+                archive,
+                entry,
+                path,
+                self.max_file_size,
+            )?);
+        }
+
+        let mut file = std::fs::File::open(path).map_err(read_error)?;
+        let len = file.metadata().map_err(read_error)?.len();
+        if let Some(max) = self.max_file_size
+            && len > max
+        {
+            return Err(SourceError::TooLarge {
+                path: path.to_path_buf(),
+                size: len,
+                max,
+            });
+        }
+        #[cfg(not(target_family = "wasm"))]
+        if len >= MMAP_THRESHOLD {
+            // SAFETY: the map is only read while decoding, before this function
+            // returns. A file truncated by another process in the meantime can
+            // fault, as with any tool that maps its inputs.
+            let map = unsafe { memmap2::Mmap::map(&file) }.map_err(read_error)?;
+            return decode(&map);
+        }
+        let mut bytes = Vec::with_capacity(usize::try_from(len).unwrap_or_default());
+        file.read_to_end(&mut bytes).map_err(read_error)?;
+        decode(&bytes)
+    }
+
+    /// Summarize a file for the project index. `None` if it can't be read or
+    /// parsed; linting it reports why.
+    pub fn summarize_file(&self, path: &Path) -> Option<FileSummary> {
+        let source = self.read_source(path).ok()?.text;
+        let result = parse(&source, self.max_parse_time)?;
+        let root = CstNode::new(result.tree.root_node(), &source);
+        Some(FileSummary::build(path, &root))
+    }
+
+    /// Read and parse a file and measure it; see [`crate::metrics`].
+    pub fn measure_file(&self, path: &Path) -> Result<FileMetrics, SourceError> {
+        let source = self.read_source(path)?.text;
+        let started = self.max_parse_time.map(|_| Instant::now());
+        let result =
+            parse(&source, self.max_parse_time).ok_or_else(|| self.parse_error(path, started))?;
+        let root = CstNode::new(result.tree.root_node(), &source);
+        Ok(crate::metrics::measure(&root))
+    }
+
+    /// Lint `source`, applying the file suppressions for `path` if given.
+    ///
+    /// Returns `None` if the source could not be parsed.
+    pub fn lint_source(&self, source: &str, path: Option<&Path>) -> Option<Vec<RuleDiagnostic>> {
+        self.lint_shared(&Arc::from(source), path, None)
+    }
+
+    /// Lint a source parsed beforehand, e.g. incrementally with
+    /// [`lintal_java_parser::Parser::parse_with_old_tree`] as an editor
+    /// changes it, applying the file suppressions for `path` if given.
+    pub fn lint_parsed(&self, parsed: &ParseResult, path: Option<&Path>) -> Vec<RuleDiagnostic> {
+        let mut diagnostics =
+            self.pipeline()
+                .lint_parsed(parsed, path, self.suppressed_rules(path).as_deref(), None);
+        self.localize(&mut diagnostics);
+        diagnostics
+    }
+
+    fn lint_shared(
+        &self,
+        source: &Arc<str>,
+        path: Option<&Path>,
+        mut suppressed: Option<&mut Vec<SuppressedDiagnostic>>,
+    ) -> Option<Vec<RuleDiagnostic>> {
+        let mut diagnostics = self.pipeline().lint(
+            source,
+            path,
+            self.suppressed_rules(path).as_deref(),
+            suppressed.as_deref_mut(),
+        )?;
+        self.localize(&mut diagnostics);
+        for found in suppressed.into_iter().flatten() {
+            self.localize(std::slice::from_mut(&mut found.diagnostic));
+        }
+        Some(diagnostics)
+    }
+
+    /// Read and lint a file, or the Java blocks of a Markdown or AsciiDoc
+    /// document; see [`Linter::lint_doc_file`].
+    pub fn lint_file(&self, path: &Path) -> Result<LintedFile, SourceError> {
+        if docs::is_doc(path) {
+            return self.lint_doc_file(path);
+        }
+        let _span = tracing::debug_span!("lint_file", path = %path.display()).entered();
+        let source = self.read_source(path)?.text;
+        // Only timed with a limit: `Instant` isn't available on every target
+        let started = self.max_parse_time.map(|_| Instant::now());
+        let mut suppressed = Vec::new();
+        let diagnostics = self
+            .lint_shared(
+                &source,
+                Some(path),
+                self.collect_suppressed.then_some(&mut suppressed),
+            )
+            .ok_or_else(|| self.parse_error(path, started))?;
+        Ok(LintedFile {
+            source,
+            diagnostics,
+            suppressed,
+        })
+    }
+
+    /// Read and lint a file again after it changed, parsing it incrementally
+    /// from `previous`, its parse from when it was last linted. The new parse
+    /// is returned with the result, to pass in after the next change;
+    /// documents are linted as [`Linter::lint_file`] lints them and have
+    /// none.
+    pub fn relint_file(
+        &self,
+        path: &Path,
+        previous: Option<ParseResult>,
+    ) -> Result<(LintedFile, Option<ParseResult>), SourceError> {
+        if docs::is_doc(path) {
+            return self.lint_doc_file(path).map(|file| (file, None));
+        }
+        let _span = tracing::debug_span!("relint_file", path = %path.display()).entered();
+        let source = self.read_source(path)?.text;
+        let started = self.max_parse_time.map(|_| Instant::now());
+        let parsed = match previous {
+            Some(mut previous) => {
+                previous.edit_to(&source);
+                reparse(&source, &previous, self.max_parse_time)
+            }
+            None => parse(&source, self.max_parse_time),
+        }
+        .ok_or_else(|| self.parse_error(path, started))?;
+
+        let mut suppressed = Vec::new();
+        let mut diagnostics = self.pipeline().lint_parsed(
+            &parsed,
+            Some(path),
+            self.suppressed_rules(Some(path)).as_deref(),
+            self.collect_suppressed.then_some(&mut suppressed),
+        );
+        self.localize(&mut diagnostics);
+        for found in &mut suppressed {
+            self.localize(std::slice::from_mut(&mut found.diagnostic));
+        }
+        let file = LintedFile {
+            source: Arc::clone(&parsed.source),
+            diagnostics,
+            suppressed,
+        };
+        Ok((file, Some(parsed)))
+    }
+
+    /// Read a Markdown or AsciiDoc document and lint each of its Java code
+    /// blocks with the rules of [`Linter::with_doc_rules`]. The diagnostics
+    /// are against the document with every line outside the blocks emptied;
+    /// see [`crate::docs`].
+    pub fn lint_doc_file(&self, path: &Path) -> Result<LintedFile, SourceError> {
+        let _span = tracing::debug_span!("lint_doc_file", path = %path.display()).entered();
+        let text = self.read_source(path)?.text;
+        let blocks = docs::java_blocks(&text, docs::is_asciidoc(path));
+        let (source, starts) = docs::blocks_source(&blocks);
+
+        let mut skipped = self
+            .suppressed_rules(Some(path))
+            .unwrap_or_else(|| vec![false; self.rules.len()]);
+        if let Some(doc_rules) = &self.doc_rules {
+            for (skip, runs) in skipped.iter_mut().zip(doc_rules) {
+                *skip |= !runs;
+            }
+        }
+        // Blocks are fragments, so only whole files are tracked for unused
+        // or explained suppressions
+        let pipeline = Pipeline {
+            report_unused: false,
+            collect_suppressed: false,
+            ..self.pipeline()
+        };
+
+        let mut diagnostics = Vec::new();
+        for (block, start) in blocks.iter().zip(starts) {
+            // Each block is linted on its own, on the lines it has in the
+            // document
+            let code: Arc<str> = Arc::from(format!("{}{}", "\n".repeat(block.line), block.code));
+            let started = self.max_parse_time.map(|_| Instant::now());
+            let found = pipeline
+                .lint(&code, Some(path), Some(&skipped), None)
+                .ok_or_else(|| self.parse_error(path, started))?;
+            let shift = TextSize::new((start - block.line) as u32);
+            diagnostics.extend(found.into_iter().map(|mut found| {
+                found.diagnostic.range = found.diagnostic.range + shift;
+                found.diagnostic.fix = None;
+                found
+            }));
+        }
+        self.localize(&mut diagnostics);
+        Ok(LintedFile {
+            source: Arc::from(source),
+            diagnostics,
+            suppressed: Vec::new(),
+        })
+    }
+
+    /// Organize the imports of `source` with `layout`; see
+    /// [`ImportLayout::organize`].
+    ///
+    /// Returns `None` if the source could not be parsed, or has code among
+    /// its imports.
+    pub fn organize_imports(&self, source: &str, layout: &ImportLayout) -> Option<String> {
+        let result = parse(&Arc::from(source), self.max_parse_time)?;
+        let root = CstNode::new(result.tree.root_node(), source);
+        layout.organize(&root, self.project.as_ref())
+    }
+
+    /// Read a file and organize its imports in memory with the layout of
+    /// [`Linter::with_import_layout`], or the default one. The source is
+    /// left as it is if its imports can't be organized.
+    pub fn organize_imports_file(&self, path: &Path) -> Result<FixedFile, SourceError> {
+        let original = self.read_source(path)?;
+        let started = self.max_parse_time.map(|_| Instant::now());
+        let layout = self.import_layout.clone().unwrap_or_default();
+        let code = match self.organize_imports(&original.text, &layout) {
+            Some(code) => code,
+            None if parse(&original.text, self.max_parse_time).is_none() => {
+                return Err(self.parse_error(path, started));
+            }
+            None => original.text.to_string(),
+        };
+        Ok(FixedFile {
+            original,
+            fixed: FixedSource {
+                code,
+                applied: 0,
+                remaining: Vec::new(),
+                skipped: Vec::new(),
+            },
+        })
+    }
+
+    /// Apply fixes to `source`, organizing its imports first if
+    /// [`Linter::with_import_layout`]; see [`fix_source`].
+    pub fn fix_source(
+        &self,
+        source: &str,
+        path: Option<&Path>,
+        applicability: Applicability,
+    ) -> Option<FixedSource> {
+        let organized = self
+            .import_layout
+            .as_ref()
+            .and_then(|layout| self.organize_imports(source, layout));
+        let source = organized.as_deref().unwrap_or(source);
+        let mut fixed = self.pipeline().fix(
+            source,
+            path,
+            self.suppressed_rules(path).as_deref(),
+            applicability,
+        )?;
+        self.localize(&mut fixed.remaining);
+        for skipped in &mut fixed.skipped {
+            self.localize(std::slice::from_mut(&mut skipped.diagnostic));
+        }
+        Some(fixed)
+    }
+
+    /// Read a file and delete the suppressions in it that suppress nothing,
+    /// in memory; see [`Linter::with_unused_suppressions`]. No other fixes
+    /// are applied.
+    pub fn remove_unused_suppressions_file(&self, path: &Path) -> Result<FixedFile, SourceError> {
+        let original = self.read_source(path)?;
+        let started = self.max_parse_time.map(|_| Instant::now());
+        let others_disabled = FixPolicy::new(
+            self.rules
+                .iter()
+                .map(|rule| (rule.name().to_string(), FixOverride::Disabled))
+                .collect(),
+        );
+        let pipeline = Pipeline {
+            fix_policy: Some(&others_disabled),
+            report_unused: true,
+            ..self.pipeline()
+        };
+        let fixed = pipeline
+            .fix(
+                &original.text,
+                Some(path),
+                self.suppressed_rules(path).as_deref(),
+                Applicability::Safe,
+            )
+            .ok_or_else(|| self.parse_error(path, started))?;
+        Ok(FixedFile { original, fixed })
+    }
+
+    /// Read a file and fix it in memory. Nothing is written until
+    /// [`FixedFile::write`].
+    pub fn fix_file(
+        &self,
+        path: &Path,
+        applicability: Applicability,
+    ) -> Result<FixedFile, SourceError> {
+        let _span = tracing::debug_span!("fix_file", path = %path.display()).entered();
+        let original = self.read_source(path)?;
+        let started = self.max_parse_time.map(|_| Instant::now());
+        let fixed = self
+            .fix_source(&original.text, Some(path), applicability)
+            .ok_or_else(|| self.parse_error(path, started))?;
+        Ok(FixedFile { original, fixed })
+    }
+
+    /// Why a source that parsing started on at `started` has no result.
+    fn parse_error(&self, path: &Path, started: Option<Instant>) -> SourceError {
+        match self.max_parse_time.zip(started) {
+            Some((limit, started)) if started.elapsed() >= limit => SourceError::ParseTimeout {
+                path: path.to_path_buf(),
+                limit,
+            },
+            _ => SourceError::Parse {
+                path: path.to_path_buf(),
+            },
+        }
+    }
+
+    fn pipeline(&self) -> Pipeline<'_> {
+        Pipeline {
+            rules: &self.rules,
+            dispatch: &self.dispatch,
+            suppression_filters: &self.suppression_filters,
+            comment_filters: &self.comment_filters,
+            suppress_warnings_aliases: Some(&self.suppress_warnings_aliases),
+            suppression_policy: self.suppression_policy.as_ref(),
+            report_unused: self.report_unused_suppressions,
+            collect_suppressed: self.collect_suppressed,
+            file_suppressions: Some((&self.file_suppressions, &self.file_suppressions_used)),
+            tab_width: self.tab_width,
+            project: self.project.as_ref(),
+            fix_policy: Some(&self.fix_policy),
+            rule_timings: self.rule_timings.as_deref(),
+            severities: self.severities.as_deref(),
+            max_parse_time: self.max_parse_time,
+        }
+    }
+
+    fn localize(&self, diagnostics: &mut [RuleDiagnostic]) {
+        let Some(messages) = &self.messages else {
+            return;
+        };
+        for RuleDiagnostic { diagnostic, .. } in diagnostics {
+            if let Some(body) = messages.localize(&diagnostic.kind) {
+                diagnostic.kind.body = body;
+            }
+        }
+    }
+
+    /// Which rules are suppressed for the whole of `path` (checked once per
+    /// file, not per node). `None` if there are no file suppressions.
+    fn suppressed_rules(&self, path: Option<&Path>) -> Option<Vec<bool>> {
+        let path = path?;
+        if self.file_suppressions.is_empty() {
+            return None;
+        }
+        let path_str = path.to_string_lossy();
+        Some(
+            self.rules
+                .iter()
+                .map(|rule| self.file_suppressions.is_suppressed(&path_str, rule.name()))
+                .collect(),
+        )
+    }
+}
+
+/// Parse `source` and run `rules` over it.
+///
+/// Returns `None` if the source could not be parsed.
+pub fn lint_source(source: &str, rules: &[Box<dyn Rule>]) -> Option<Vec<RuleDiagnostic>> {
+    Pipeline::bare(rules, &DispatchTable::new(rules)).lint(&Arc::from(source), None, None, None)
+}
+
+/// Parse `source`, read from `path`, and run `rules` over it. Unlike
+/// [`lint_source`], rules that look at the file's path, such as
+/// OuterTypeFilename, see it through [`CheckContext::path`].
+///
+/// Returns `None` if the source could not be parsed.
+pub fn lint_source_at(
+    source: &str,
+    path: &Path,
+    rules: &[Box<dyn Rule>],
+) -> Option<Vec<RuleDiagnostic>> {
+    Pipeline::bare(rules, &DispatchTable::new(rules)).lint(
+        &Arc::from(source),
+        Some(path),
+        None,
+        None,
+    )
+}
+
+/// Apply the fixes of `rules` to `source`.
+///
+/// Like `lintal fix`, fixes are applied in passes until none apply, so fixes
+/// skipped because they conflicted are retried against the updated source.
+/// Returns `None` if the source could not be parsed.
+pub fn fix_source(
+    source: &str,
+    rules: &[Box<dyn Rule>],
+    applicability: Applicability,
+) -> Option<FixedSource> {
+    Pipeline::bare(rules, &DispatchTable::new(rules)).fix(source, None, None, applicability)
+}
+
+/// Everything needed to lint one source.
+struct Pipeline<'a> {
+    rules: &'a [Box<dyn Rule>],
+    dispatch: &'a DispatchTable,
+    suppression_filters: &'a [PlainTextCommentFilterConfig],
+    comment_filters: &'a [CommentFilterConfig],
+    suppress_warnings_aliases: Option<&'a SuppressWarningsAliases>,
+    suppression_policy: Option<&'a SuppressionPolicy>,
+    /// Report suppressions that suppress nothing, running the rules file
+    /// suppressions turn off too.
+    report_unused: bool,
+    /// Keep every suppressed diagnostic, not just those with a fix, running
+    /// the rules file suppressions turn off too.
+    collect_suppressed: bool,
+    /// File suppressions, with whether each has suppressed a diagnostic.
+    file_suppressions: Option<(&'a FileSuppressionsConfig, &'a [AtomicBool])>,
+    /// For counting columns as checkstyle does, to match file suppressions
+    /// limited to some columns.
+    tab_width: usize,
+    project: Option<&'a ProjectIndex>,
+    /// `None` applies fixes by their own applicability.
+    fix_policy: Option<&'a FixPolicy>,
+    /// Totals to add the time spent in each rule to.
+    rule_timings: Option<&'a [AtomicU64]>,
+    /// `None` reports every diagnostic as an error.
+    severities: Option<&'a [Severity]>,
+    max_parse_time: Option<Duration>,
+}
+
+impl<'a> Pipeline<'a> {
+    fn bare(rules: &'a [Box<dyn Rule>], dispatch: &'a DispatchTable) -> Self {
+        Self {
+            rules,
+            dispatch,
+            suppression_filters: &[],
+            comment_filters: &[],
+            suppress_warnings_aliases: None,
+            suppression_policy: None,
+            report_unused: false,
+            collect_suppressed: false,
+            file_suppressions: None,
+            tab_width: lintal_checkstyle::MergedConfig::DEFAULT_TAB_WIDTH,
+            project: None,
+            fix_policy: None,
+            rule_timings: None,
+            severities: None,
+            max_parse_time: None,
+        }
+    }
+
+    /// Parse `source` and run all rules over it, returning unsuppressed
+    /// diagnostics and adding suppressed ones that have a fix, or all of
+    /// them if collecting them, to `suppressed`. Returns `None` if the source
+    /// could not be parsed.
+    fn lint(
+        &self,
+        source: &Arc<str>,
+        path: Option<&Path>,
+        suppressed_rules: Option<&[bool]>,
+        suppressed: Option<&mut Vec<SuppressedDiagnostic>>,
+    ) -> Option<Vec<RuleDiagnostic>> {
+        let parsed = {
+            let _span = tracing::debug_span!("parse", bytes = source.len()).entered();
+            parse(source, self.max_parse_time)
+        };
+        let Some(result) = parsed else {
+            tracing::debug!("source could not be parsed");
+            return None;
+        };
+        Some(self.lint_parsed(&result, path, suppressed_rules, suppressed))
+    }
+
+    /// Run all rules over a parsed source; see [`Pipeline::lint`].
+    fn lint_parsed(
+        &self,
+        result: &ParseResult,
+        path: Option<&Path>,
+        suppressed_rules: Option<&[bool]>,
+        mut suppressed: Option<&mut Vec<SuppressedDiagnostic>>,
+    ) -> Vec<RuleDiagnostic> {
+        let source: &str = &result.source;
+
+        let ctx = CheckContext::new(source);
+        let ctx = match path {
+            Some(path) => ctx.with_path(path),
+            None => ctx,
+        };
+        let ctx = match self.project {
+            Some(project) => ctx.with_project(project),
+            None => ctx,
+        };
+        let mut suppression_ctx = SuppressionContext::from_source(source, self.suppression_filters);
+        suppression_ctx.parse_comment_filters(source, self.comment_filters);
+
+        // Parse @SuppressWarnings annotations for additional suppressions
+        let root = CstNode::new(result.tree.root_node(), source);
+        match self.suppress_warnings_aliases {
+            Some(aliases) => suppression_ctx.parse_suppress_warnings_with(source, &root, aliases),
+            None => suppression_ctx.parse_suppress_warnings(source, &root),
+        }
+
+        // Rules run interleaved node by node, so their time is summed per rule
+        // and only measured when someone is listening
+        let mut timings = (self.rule_timings.is_some() || tracing::enabled!(tracing::Level::TRACE))
+            .then(|| vec![Duration::ZERO; self.rules.len()]);
+
+        // Collect all diagnostics, filtering out suppressed ones
+        let mut diagnostics = Vec::new();
+        let mut usage = SuppressionUsage::default();
+        let has_suppressions = suppression_ctx.has_suppressions();
+        // File suppressions limited to some lines, columns or messages are
+        // matched against each diagnostic rather than turning rules off
+        let path_str = path.map(Path::to_string_lossy);
+        let has_conditional = match (self.file_suppressions, &path_str) {
+            (Some((file_suppressions, _)), Some(path)) => file_suppressions.has_conditional(path),
+            _ => false,
+        };
+        let mut run = |rule_idx: usize, check: &mut dyn FnMut(&dyn Rule) -> Vec<Diagnostic>| {
+            let file_suppressed = suppressed_rules.is_some_and(|mask| mask[rule_idx]);
+            if file_suppressed && !self.report_unused && !self.collect_suppressed {
+                return;
+            }
+            let rule = self.rules[rule_idx].as_ref();
+            let found = match &mut timings {
+                Some(timings) => {
+                    let start = Instant::now();
+                    let found = check(rule);
+                    timings[rule_idx] += start.elapsed();
+                    found
+                }
+                None => check(rule),
+            };
+            for mut diagnostic in found {
+                if let Some(code) = rule.code() {
+                    diagnostic.kind.code = code.to_string();
+                }
+                if let Some(severities) = self.severities {
+                    diagnostic.severity = severities[rule_idx];
+                }
+                // Text block content belongs to the string, so the diagnostic
+                // stands but its fix can't be applied
+                if diagnostic
+                    .fix
+                    .as_ref()
+                    .is_some_and(|fix| changes_text_block(fix, ctx.text_blocks(&root)))
+                {
+                    tracing::debug!(rule = rule.name(), "dropped a fix inside a text block");
+                    diagnostic.fix = None;
+                }
+                let reported = RuleDiagnostic {
+                    rule: rule.name(),
+                    diagnostic,
+                };
+                let suppressed_inline = has_suppressions
+                    && suppression_ctx.record_suppressed(
+                        reported.rule,
+                        &reported.diagnostic.kind.body,
+                        reported.diagnostic.range.start(),
+                        &mut usage,
+                    );
+                let file_suppression = if file_suppressed || has_conditional {
+                    let location = ctx.line_index().checkstyle_column(
+                        reported.diagnostic.range.start(),
+                        source,
+                        self.tab_width,
+                        rule.column_encoding(),
+                    );
+                    self.file_suppression_of(path_str.as_deref(), &reported, location)
+                } else {
+                    None
+                };
+                if file_suppression.is_none() && !suppressed_inline {
+                    diagnostics.push(reported);
+                } else if let Some(suppressed) = suppressed.as_deref_mut()
+                    && (reported.diagnostic.fix.is_some() || self.collect_suppressed)
+                {
+                    let by = file_suppression.or_else(|| {
+                        suppression_ctx
+                            .suppressed_by(
+                                reported.rule,
+                                &reported.diagnostic.kind.body,
+                                reported.diagnostic.range.start(),
+                            )
+                            .map(|start| directive_at(source, ctx.line_index(), start))
+                    });
+                    if let Some(by) = by {
+                        suppressed.push(SuppressedDiagnostic {
+                            diagnostic: reported,
+                            by,
+                        });
+                    }
+                }
+            }
+        };
+
+        for &rule_idx in self.dispatch.file_rule_indices() {
+            run(rule_idx, &mut |rule| {
+                rule.as_file_rule()
+                    .map(|rule| rule.check_file(path, source, ctx.line_index()))
+                    .unwrap_or_default()
+            });
+        }
+
+        for (rule_idx, query) in self.dispatch.query_rules() {
+            run(*rule_idx, &mut |rule| {
+                rule.as_query_rule()
+                    .map(|rule| check_matches(rule, query, &ctx, root.inner(), source))
+                    .unwrap_or_default()
+            });
+        }
+
+        // Only stop at nodes some rule is interested in
+        let mut walker = TreeWalker::new(root.inner(), source);
+        if let Some(kinds) = self.dispatch.dispatched_kinds() {
+            walker = walker.only_kinds(kinds.iter().copied());
+        }
+        let mut states: Vec<Option<Box<dyn FileState + '_>>> =
+            self.rules.iter().map(|_| None).collect();
+        for &rule_idx in self.dispatch.stateful_rule_indices() {
+            states[rule_idx] = self.rules[rule_idx]
+                .as_stateful_rule()
+                .map(|rule| rule.begin_file(&ctx));
+        }
+        for node in walker {
+            for rule_idx in self.dispatch.rule_indices_for_kind(node.kind_id()) {
+                match &mut states[rule_idx] {
+                    Some(state) => run(rule_idx, &mut |_| state.visit(&ctx, &node)),
+                    None => run(rule_idx, &mut |rule| rule.check(&ctx, &node)),
+                }
+            }
+        }
+        for &rule_idx in self.dispatch.stateful_rule_indices() {
+            let mut state = states[rule_idx].take();
+            run(rule_idx, &mut |_| {
+                state
+                    .take()
+                    .map(|state| state.finish_file(&ctx))
+                    .unwrap_or_default()
+            });
+        }
+
+        for (rule_idx, elapsed) in timings.into_iter().flatten().enumerate() {
+            tracing::trace!(
+                rule = self.rules[rule_idx].name(),
+                ?elapsed,
+                "rule finished"
+            );
+            if let Some(totals) = self.rule_timings {
+                let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+                totals[rule_idx].fetch_add(nanos, Ordering::Relaxed);
+            }
+        }
+        // Suppressions can't suppress what is reported about them
+        if self.report_unused {
+            let is_tracked = |name: &str| {
+                name == "*"
+                    || self
+                        .rules
+                        .iter()
+                        .any(|rule| rule.name().eq_ignore_ascii_case(name))
+            };
+            diagnostics.extend(
+                suppression_ctx
+                    .unused(source, &usage, is_tracked)
+                    .into_iter()
+                    .map(|diagnostic| RuleDiagnostic {
+                        rule: UnusedSuppressionViolation::RULE_NAME,
+                        diagnostic,
+                    }),
+            );
+        }
+        if let Some(policy) = self.suppression_policy {
+            for directive in suppression_ctx.directives() {
+                diagnostics.extend(policy.check(directive).into_iter().map(|diagnostic| {
+                    RuleDiagnostic {
+                        rule: SuppressionPolicy::RULE_NAME,
+                        diagnostic,
+                    }
+                }));
+            }
+        }
+        sort_and_dedup(&mut diagnostics);
+        tracing::debug!(diagnostics = diagnostics.len(), "linted");
+
+        diagnostics
+    }
+
+    /// The first file suppression that hides `reported` in `path`, at
+    /// `location` as checkstyle counts it, marking every one that does as
+    /// used.
+    fn file_suppression_of(
+        &self,
+        path: Option<&str>,
+        reported: &RuleDiagnostic,
+        location: LineColumn,
+    ) -> Option<SuppressedBy> {
+        let (file_suppressions, used) = self.file_suppressions?;
+        let path = path?;
+        let message = &reported.diagnostic.kind.body;
+        let mut first = None;
+        for (suppression, used) in file_suppressions.rules().iter().zip(used) {
+            if suppression.suppresses(path, reported.rule, location, message) {
+                used.store(true, Ordering::Relaxed);
+                first.get_or_insert(suppression);
+            }
+        }
+        first.map(|suppression| SuppressedBy::File {
+            line: suppression.line,
+            checks: suppression.checks_pattern.to_string(),
+            files: suppression.files_pattern.to_string(),
+        })
+    }
+
+    /// Apply fixes in passes: a fix that conflicts with another fix is skipped
+    /// as a whole and retried against the re-linted output of the previous
+    /// pass, until no more fixes apply.
+    fn fix(
+        &self,
+        source: &str,
+        path: Option<&Path>,
+        suppressed_rules: Option<&[bool]>,
+        applicability: Applicability,
+    ) -> Option<FixedSource> {
+        let _span = tracing::debug_span!("fix").entered();
+        let mut suppressed = Vec::new();
+        let mut fixed = FixedSource {
+            code: source.to_string(),
+            applied: 0,
+            remaining: self.lint(
+                &Arc::from(source),
+                path,
+                suppressed_rules,
+                Some(&mut suppressed),
+            )?,
+            skipped: Vec::new(),
+        };
+
+        for pass in 1..=MAX_FIX_PASSES {
+            let fixes: Vec<&Fix> = fixed
+                .remaining
+                .iter()
+                .filter_map(|remaining| {
+                    let fix = remaining.diagnostic.fix.as_ref()?;
+                    let applies = self
+                        .fix_applicability(remaining.rule, fix)
+                        .is_some_and(|fix_applicability| fix_applicability >= applicability);
+                    (applies && fits_source(fix, &fixed.code)).then_some(fix)
+                })
+                .collect();
+            if fixes.is_empty() {
+                break;
+            }
+
+            let result = apply_fixes(&fixed.code, fixes);
+            if result.applied == 0 || result.code == fixed.code {
+                break;
+            }
+            tracing::debug!(pass, applied = result.applied, "applied fixes");
+            // Stop at the last source that still parses
+            let mut pass_suppressed = Vec::new();
+            let Some(remaining) = self.lint(
+                &Arc::from(result.code.as_str()),
+                path,
+                suppressed_rules,
+                Some(&mut pass_suppressed),
+            ) else {
+                tracing::warn!(
+                    pass,
+                    "fixed source no longer parses; keeping the previous pass"
+                );
+                break;
+            };
+            fixed.applied += result.applied;
+            fixed.code = result.code;
+            fixed.remaining = remaining;
+            suppressed = pass_suppressed;
+        }
+        let mut suppressed: Vec<RuleDiagnostic> = suppressed
+            .into_iter()
+            .map(|found| found.diagnostic)
+            .collect();
+        sort_and_dedup(&mut suppressed);
+
+        let skip_reason = |found: &RuleDiagnostic| {
+            let fix = found.diagnostic.fix.as_ref()?;
+            match self.fix_applicability(found.rule, fix) {
+                None => Some(SkipReason::Disabled),
+                Some(Applicability::DisplayOnly) => None,
+                Some(_) if !fits_source(fix, &fixed.code) => Some(SkipReason::Invalid),
+                Some(fix_applicability) if fix_applicability >= applicability => {
+                    Some(SkipReason::Overlapping)
+                }
+                Some(_) => Some(SkipReason::Unsafe),
+            }
+        };
+        fixed.skipped = fixed
+            .remaining
+            .iter()
+            .filter_map(|found| {
+                skip_reason(found).map(|reason| SkippedFix {
+                    diagnostic: found.clone(),
+                    reason,
+                })
+            })
+            .chain(
+                suppressed
+                    .into_iter()
+                    // Only fixes that would have been applied count
+                    .filter(|found| skip_reason(found) == Some(SkipReason::Overlapping))
+                    .map(|diagnostic| SkippedFix {
+                        diagnostic,
+                        reason: SkipReason::Suppressed,
+                    }),
+            )
+            .collect();
+        fixed
+            .skipped
+            .sort_by_key(|skipped| skipped.diagnostic.diagnostic.range.start());
+
+        Some(fixed)
+    }
+
+    /// How safe `fix`, reported by `rule`, is taken to be; `None` if the
+    /// rule's fixes are disabled.
+    fn fix_applicability(&self, rule: &str, fix: &Fix) -> Option<Applicability> {
+        match self.fix_policy {
+            Some(policy) => policy.applicability(rule, fix),
+            None => Some(fix.applicability()),
+        }
+    }
+}
+
+/// The suppression comment or annotation starting at `start`, by the line
+/// it starts on.
+fn directive_at(source: &str, line_index: &LineIndex, start: TextSize) -> SuppressedBy {
+    let text = &source[start.to_usize()..];
+    let text = text.find('\n').map_or(text, |end| &text[..end]);
+    SuppressedBy::Directive {
+        line: line_index.line_index(start).get(),
+        text: text.trim_end().to_string(),
+    }
+}
+
+/// Parse `source` with this thread's parser, giving up after
+/// `max_parse_time` if set.
+fn parse(source: &Arc<str>, max_parse_time: Option<Duration>) -> Option<ParseResult> {
+    // Use thread-local parser to avoid repeated initialization
+    PARSER.with(|parser| {
+        let mut parser = parser.borrow_mut();
+        match max_parse_time {
+            Some(limit) => parser.parse_shared_within(source, limit),
+            None => parser.parse_shared(source),
+        }
+    })
+}
+
+/// Like [`parse`], reusing what the edits since `previous` was parsed didn't
+/// touch.
+fn reparse(
+    source: &Arc<str>,
+    previous: &ParseResult,
+    max_parse_time: Option<Duration>,
+) -> Option<ParseResult> {
+    PARSER.with(|parser| {
+        let mut parser = parser.borrow_mut();
+        match max_parse_time {
+            Some(limit) => parser.reparse_shared_within(source, &previous.tree, limit),
+            None => parser.reparse_shared(source, &previous.tree),
+        }
+    })
+}
+
+/// Put diagnostics in source order, by rule at the same offset, and drop
+/// repeats of a finding that a rule reported from more than one node.
+///
+/// Rules run interleaved in tree order, so without this the order would
+/// depend on which node a rule happens to report from.
+fn sort_and_dedup(diagnostics: &mut Vec<RuleDiagnostic>) {
+    diagnostics.sort_by(|a, b| {
+        (a.diagnostic.range.start(), a.rule, a.diagnostic.range.end()).cmp(&(
+            b.diagnostic.range.start(),
+            b.rule,
+            b.diagnostic.range.end(),
+        ))
+    });
+    diagnostics.dedup_by(|later, kept| {
+        let same = later.rule == kept.rule
+            && later.diagnostic.range == kept.diagnostic.range
+            && later.diagnostic.kind.body == kept.diagnostic.kind.body;
+        if same && kept.diagnostic.fix.is_none() {
+            kept.diagnostic.fix = later.diagnostic.fix.take();
+        }
+        same
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StatefulRule;
+    use crate::rules::{OuterTypeFilename, UpperEll, WhitespaceAround};
+    use lintal_diagnostics::Edit;
+    use lintal_text_size::{TextRange, TextSize};
+
+    fn rules() -> Vec<Box<dyn Rule>> {
+        vec![
+            Box::new(WhitespaceAround::default()),
+            Box::new(UpperEll::default()),
+        ]
+    }
+
+    #[test]
+    fn test_lint_source() {
+        let source = "class A { long x=1l; }";
+        let diagnostics = lint_source(source, &rules()).unwrap();
+        let rules: Vec<&str> = diagnostics.iter().map(|d| d.rule).collect();
+        assert!(rules.contains(&"WhitespaceAround"));
+        assert!(rules.contains(&"UpperEll"));
+        assert!(
+            diagnostics
+                .iter()
+                .all(|d| d.diagnostic.kind.code == "WS001" || d.diagnostic.kind.code == "ST001")
+        );
+    }
+
+    #[test]
+    fn test_lint_source_at_passes_path() {
+        let rules: Vec<Box<dyn Rule>> = vec![Box::new(OuterTypeFilename)];
+        let source = "class A {}";
+        assert!(lint_source(source, &rules).unwrap().is_empty());
+        assert!(
+            lint_source_at(source, Path::new("A.java"), &rules)
+                .unwrap()
+                .is_empty()
+        );
+        let diagnostics = lint_source_at(source, Path::new("B.java"), &rules).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "OuterTypeFilename");
+    }
+
+    #[test]
+    fn test_lint_source_honours_suppress_warnings() {
+        let source = "@SuppressWarnings(\"checkstyle:UpperEll\")\nclass A { long x = 1l; }";
+        assert!(lint_source(source, &rules()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_fix_source() {
+        let source = "class A { long x=1L; }";
+        let fixed = fix_source(source, &rules(), Applicability::Safe).unwrap();
+        assert_eq!(fixed.code, "class A { long x = 1L; }");
+        assert!(fixed.applied > 0);
+        assert!(fixed.remaining.is_empty());
+    }
+
+    #[test]
+    fn test_linter_honours_comment_filters() {
+        let linter = Linter::new(rules())
+            .with_suppression_filters(vec![PlainTextCommentFilterConfig::checkstyle_default()]);
+        let source = "class A {\n// CHECKSTYLE:OFF:UpperEll\nlong x = 1l;\n}";
+        assert!(linter.lint_source(source, None).unwrap().is_empty());
+        assert_eq!(lint_source(source, &rules()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_lint_parsed() {
+        let source = "class A { long x=1l; }";
+        let parsed = JavaParser::new().parse(source).unwrap();
+        let linter = Linter::new(rules());
+        let rules_of = |diagnostics: Vec<RuleDiagnostic>| -> Vec<&str> {
+            diagnostics.iter().map(|d| d.rule).collect()
+        };
+        assert_eq!(
+            rules_of(linter.lint_parsed(&parsed, None)),
+            rules_of(linter.lint_source(source, None).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_linter_severities() {
+        let source = "class A { long x=1l; }";
+        let severity = |d: &RuleDiagnostic| (d.rule, d.diagnostic.severity);
+
+        let diagnostics = Linter::new(rules()).lint_source(source, None).unwrap();
+        assert!(
+            diagnostics
+                .iter()
+                .all(|d| d.diagnostic.severity == Severity::Error)
+        );
+
+        let linter = Linter::new(rules()).with_severities(|rule| match rule {
+            "UpperEll" => Severity::Info,
+            _ => Severity::Warning,
+        });
+        let diagnostics = linter.lint_source(source, None).unwrap();
+        assert!(
+            diagnostics
+                .iter()
+                .map(severity)
+                .any(|s| s == ("UpperEll", Severity::Info))
+        );
+        assert!(
+            diagnostics
+                .iter()
+                .map(severity)
+                .any(|s| s == ("WhitespaceAround", Severity::Warning))
+        );
+    }
+
+    #[test]
+    fn test_linter_file_suppressions() {
+        let suppressions = FileSuppressionsConfig::from_xml(
+            r#"<suppress files="Generated\.java" checks="UpperEll"/>"#,
+        );
+        let linter = Linter::new(rules()).with_file_suppressions(suppressions);
+        let source = "class A { long x = 1l; }";
+        let generated = Path::new("src/Generated.java");
+        assert!(
+            linter
+                .lint_source(source, Some(generated))
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(linter.lint_source(source, None).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_linter_file_suppressions_by_position() {
+        let suppressions = FileSuppressionsConfig::from_xml(
+            "<suppress checks=\"UpperEll\" files=\"A\\.java\" lines=\"2,4-5\"/>\n\
+             <suppress files=\"A\\.java\" message=\"^Should use uppercase\" columns=\"1-20\"/>",
+        );
+        let linter = Linter::new(rules())
+            .with_file_suppressions(suppressions)
+            .with_unused_suppressions();
+        let path = Path::new("src/A.java");
+        let source = "class A {\n    long a = 1l;\n    long b = 2l;\n    long c = 3l;\n    long d = 4l;\n    long eeeeeeeeeeee = 5l;\n}\n";
+        let index = LineIndex::from_source_text(source);
+        let lines: Vec<usize> = linter
+            .lint_source(source, Some(path))
+            .unwrap()
+            .iter()
+            .map(|d| index.line_index(d.diagnostic.range.start()).get())
+            .collect();
+        // Line 3 is at column 14, within the message suppression's columns;
+        // line 6 is past them
+        assert_eq!(lines, [6]);
+        assert!(linter.unused_file_suppressions().is_empty());
+    }
+
+    #[test]
+    fn test_linter_unused_suppressions() {
+        let suppressions = FileSuppressionsConfig::from_xml(
+            "<suppress files=\"A\\.java\" checks=\"UpperEll\"/>\n\
+             <suppress files=\"B\\.java\" checks=\"UpperEll\"/>",
+        );
+        let linter = Linter::new(rules())
+            .with_file_suppressions(suppressions)
+            .with_unused_suppressions();
+        let path = Path::new("src/A.java");
+        let source = "class A {\n    // lintal: disable-next-line UpperEll, WhitespaceAround\n    long x = 1l;\n}\n";
+        let diagnostics = linter.lint_source(source, Some(path)).unwrap();
+        let reported: Vec<(&str, &str)> = diagnostics
+            .iter()
+            .map(|d| (d.rule, d.diagnostic.kind.body.as_str()))
+            .collect();
+        assert_eq!(
+            reported,
+            [(
+                UnusedSuppressionViolation::RULE_NAME,
+                "Unused suppression of WhitespaceAround"
+            )]
+        );
+        // UpperEll is turned off for A.java, but still runs to use it up
+        let unused: Vec<Option<usize>> = linter
+            .unused_file_suppressions()
+            .iter()
+            .map(|suppression| suppression.line)
+            .collect();
+        assert_eq!(unused, [Some(2)]);
+
+        let stale =
+            "class A {\n    // lintal: disable-next-line WhitespaceAround\n    long x = 1L;\n}\n";
+        let fixed = linter
+            .fix_source(stale, Some(path), Applicability::Safe)
+            .unwrap();
+        assert_eq!(fixed.code, "class A {\n    long x = 1L;\n}\n");
+        assert!(fixed.remaining.is_empty());
+    }
+
+    #[test]
+    fn test_linter_messages() {
+        let mut messages = MessageBundles::default();
+        messages.insert_properties("", "upperEll=Verwende ein großes ''L''.");
+        let linter = Linter::new(rules()).with_messages(messages);
+        let diagnostics = linter.lint_source("class A { long x=1l; }", None).unwrap();
+        let body = |rule: &str| {
+            diagnostics
+                .iter()
+                .find(|d| d.rule == rule)
+                .map(|d| d.diagnostic.kind.body.as_str())
+        };
+        assert_eq!(body("UpperEll"), Some("Verwende ein großes 'L'."));
+        // Messages missing from the bundles keep lintal's wording
+        assert_eq!(
+            body("WhitespaceAround"),
+            Some("Missing whitespace before `=`")
+        );
+    }
+
+    #[test]
+    fn test_linter_fix_policy() {
+        use lintal_checkstyle::FixOverride;
+
+        let policy = FixPolicy::new(
+            [("UpperEll".to_string(), FixOverride::Unsafe)]
+                .into_iter()
+                .collect(),
+        );
+        let linter = Linter::new(rules()).with_fix_policy(policy);
+        let source = "class A { long x = 1l; }";
+
+        let fixed = linter
+            .fix_source(source, None, Applicability::Safe)
+            .unwrap();
+        assert_eq!(fixed.code, source);
+        assert_eq!(fixed.remaining.len(), 1);
+
+        let fixed = linter
+            .fix_source(source, None, Applicability::Unsafe)
+            .unwrap();
+        assert_eq!(fixed.code, "class A { long x = 1L; }");
+    }
+
+    #[test]
+    fn test_fix_source_organizes_imports() {
+        let linter = Linter::new(rules()).with_import_layout(ImportLayout::default());
+        let source =
+            "import java.util.Map;\nimport java.util.List;\n\nclass A { List<String> x=null; }";
+
+        let fixed = linter
+            .fix_source(source, None, Applicability::Safe)
+            .unwrap();
+        assert_eq!(
+            fixed.code,
+            "import java.util.List;\n\nclass A { List<String> x = null; }"
+        );
+    }
+
+    #[test]
+    fn test_fix_source_reports_skipped_fixes() {
+        use lintal_checkstyle::FixOverride;
+
+        let policy = FixPolicy::new(
+            [("UpperEll".to_string(), FixOverride::Unsafe)]
+                .into_iter()
+                .collect(),
+        );
+        let linter = Linter::new(rules()).with_fix_policy(policy);
+        let fixed = linter
+            .fix_source("class A { long x = 1l; }", None, Applicability::Safe)
+            .unwrap();
+        let skipped: Vec<_> = fixed
+            .skipped
+            .iter()
+            .map(|skipped| (skipped.diagnostic.rule, skipped.reason))
+            .collect();
+        assert_eq!(skipped, [("UpperEll", SkipReason::Unsafe)]);
+
+        let source = "@SuppressWarnings(\"checkstyle:UpperEll\")\nclass A { long x=1l; }";
+        let fixed = fix_source(source, &rules(), Applicability::Safe).unwrap();
+        assert!(fixed.remaining.is_empty());
+        let skipped: Vec<_> = fixed
+            .skipped
+            .iter()
+            .map(|skipped| (skipped.diagnostic.rule, skipped.reason))
+            .collect();
+        assert_eq!(skipped, [("UpperEll", SkipReason::Suppressed)]);
+    }
+
+    #[test]
+    fn test_file_rules_run_once_per_file() {
+        use crate::rules::LineLength;
+
+        let rules: Vec<Box<dyn Rule>> = vec![Box::new(LineLength {
+            max: 12,
+            ignore_pattern: None,
+        })];
+        // File rules are not dispatched nodes, so they don't force a walk
+        // over every node
+        assert!(DispatchTable::new(&rules).dispatched_kinds().is_some());
+
+        let source = "class A {\n    long value;\n}\n";
+        let diagnostics = lint_source(source, &rules).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "LineLength");
+    }
+
+    #[test]
+    fn test_linter_rule_timings() {
+        let source = "class A { long x = 1l; }";
+        assert!(Linter::new(rules()).rule_timings().is_empty());
+
+        let linter = Linter::new(rules()).with_rule_timings();
+        linter.lint_source(source, None).unwrap();
+        let names: Vec<&str> = linter
+            .rule_timings()
+            .iter()
+            .map(|(name, _)| *name)
+            .collect();
+        assert_eq!(names, ["WhitespaceAround", "UpperEll"]);
+    }
+
+    #[test]
+    fn test_linter_fix_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("A.java");
+        std::fs::write(&path, "class A { long x=1L; }").unwrap();
+
+        let linter = Linter::new(rules());
+        let fixed = linter.fix_file(&path, Applicability::Safe).unwrap();
+        assert!(fixed.changed());
+        fixed.write(&path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "class A { long x = 1L; }"
+        );
+
+        let missing = linter.lint_file(&dir.path().join("B.java")).unwrap_err();
+        assert!(matches!(missing, SourceError::Read { .. }));
+    }
+
+    #[test]
+    fn test_linter_lint_large_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("A.java");
+        let padding = "    // padding\n".repeat(usize::try_from(MMAP_THRESHOLD).unwrap() / 15 + 1);
+        let source = format!("\u{feff}class A {{\n{padding}    long x = 1l;\n}}\n");
+        std::fs::write(&path, &source).unwrap();
+
+        let file = Linter::new(rules()).lint_file(&path).unwrap();
+        assert_eq!(&*file.source, source.trim_start_matches('\u{feff}'));
+        assert_eq!(file.diagnostics.len(), 1);
+        assert_eq!(file.diagnostics[0].rule, "UpperEll");
+    }
+
+    #[test]
+    fn test_linter_max_parse_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("A.java");
+        let fields = "    long x = 1l;\n".repeat(10_000);
+        std::fs::write(&path, format!("class A {{\n{fields}}}\n")).unwrap();
+
+        let linter = Linter::new(rules()).with_max_parse_time(Duration::ZERO);
+        let error = linter.lint_file(&path).unwrap_err();
+        assert!(matches!(error, SourceError::ParseTimeout { .. }));
+        assert!(error.is_skipped());
+        assert!(matches!(
+            linter.fix_file(&path, Applicability::Safe),
+            Err(SourceError::ParseTimeout { .. })
+        ));
+    }
+
+    /// Reports the class name from both the declaration and the identifier.
+    struct ClassName;
+
+    struct ClassNameViolation;
+
+    impl lintal_diagnostics::Violation for ClassNameViolation {
+        fn message(&self) -> String {
+            "Class name.".to_string()
+        }
+    }
+
+    impl Rule for ClassName {
+        fn name(&self) -> &'static str {
+            "ClassName"
+        }
+
+        fn relevant_kinds(&self) -> &'static [&'static str] {
+            &["class_declaration", "identifier"]
+        }
+
+        fn check(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+            let in_class = node
+                .parent()
+                .is_some_and(|p| p.kind() == "class_declaration");
+            let name = match node.kind() {
+                "class_declaration" => node.child_by_field_name("name"),
+                _ if in_class => Some(*node),
+                _ => None,
+            };
+            name.map(|name| Diagnostic::new(ClassNameViolation, name.range()))
+                .into_iter()
+                .collect()
+        }
+    }
+
+    /// Indents the line of each text block delimiter by eight spaces.
+    struct IndentDelimiters;
+
+    impl Rule for IndentDelimiters {
+        fn name(&self) -> &'static str {
+            "IndentDelimiters"
+        }
+
+        fn relevant_kinds(&self) -> &'static [&'static str] {
+            &["\"\"\""]
+        }
+
+        fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+            let before = ctx.text_before(node.range().start());
+            let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+            let line = &ctx.source()[line_start..];
+            let indent = line.len() - line.trim_start_matches(' ').len();
+            if indent == 8 {
+                return vec![];
+            }
+            let edit = Edit::replacement(
+                " ".repeat(8),
+                TextSize::new(line_start as u32),
+                TextSize::new((line_start + indent) as u32),
+            );
+            vec![Diagnostic::new(ClassNameViolation, node.range()).with_fix(Fix::safe_edit(edit))]
+        }
+    }
+
+    #[test]
+    fn test_fixes_leave_text_blocks_alone() {
+        let rules: Vec<Box<dyn Rule>> = vec![Box::new(IndentDelimiters)];
+        let source = "class A {\n    String s = \"\"\"\n        a\n    \"\"\";\n}\n";
+
+        // Indenting the closing delimiter would strip more incidental
+        // whitespace from the string
+        let diagnostics = lint_source(source, &rules).unwrap();
+        let fixable: Vec<bool> = diagnostics
+            .iter()
+            .map(|d| d.diagnostic.fix.is_some())
+            .collect();
+        assert_eq!(fixable, [true, false]);
+
+        let fixed = fix_source(source, &rules, Applicability::Safe).unwrap();
+        assert_eq!(
+            fixed.code,
+            "class A {\n        String s = \"\"\"\n        a\n    \"\"\";\n}\n"
+        );
+        assert_eq!(fixed.remaining.len(), 1);
+    }
+
+    /// Reports every class after the first once the file has been walked.
+    struct ExtraClasses;
+
+    struct ExtraClassesState(Vec<TextRange>);
+
+    impl Rule for ExtraClasses {
+        fn name(&self) -> &'static str {
+            "ExtraClasses"
+        }
+
+        fn relevant_kinds(&self) -> &'static [&'static str] {
+            &["class_declaration"]
+        }
+
+        fn as_stateful_rule(&self) -> Option<&dyn StatefulRule> {
+            Some(self)
+        }
+    }
+
+    impl StatefulRule for ExtraClasses {
+        fn begin_file<'r>(&'r self, _ctx: &CheckContext) -> Box<dyn FileState + 'r> {
+            Box::new(ExtraClassesState(Vec::new()))
+        }
+    }
+
+    impl FileState for ExtraClassesState {
+        fn visit(&mut self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+            self.0.push(node.range());
+            vec![]
+        }
+
+        fn finish_file(self: Box<Self>, _ctx: &CheckContext) -> Vec<Diagnostic> {
+            self.0
+                .into_iter()
+                .skip(1)
+                .map(|range| Diagnostic::new(ClassNameViolation, range))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_stateful_rules_report_after_the_walk() {
+        let rules: Vec<Box<dyn Rule>> = vec![Box::new(ExtraClasses)];
+        let linter = Linter::new(rules);
+
+        let diagnostics = linter
+            .lint_source("class A {}\nclass B { class C {} }\n", None)
+            .unwrap();
+        let found: Vec<u32> = diagnostics
+            .iter()
+            .map(|d| d.diagnostic.range.start().into())
+            .collect();
+        assert_eq!(found, [11, 21]);
+
+        // Each file starts from fresh state
+        let diagnostics = linter.lint_source("class A {}\n", None).unwrap();
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_diagnostics_sorted_and_deduplicated() {
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(UpperEll::default()),
+            Box::new(ClassName),
+            Box::new(WhitespaceAround::default()),
+        ];
+        let diagnostics = lint_source("class A { long x=1l; }", &rules).unwrap();
+        let found: Vec<(u32, &str)> = diagnostics
+            .iter()
+            .map(|d| (d.diagnostic.range.start().into(), d.rule))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (6, "ClassName"),
+                (16, "WhitespaceAround"),
+                (16, "WhitespaceAround"),
+                (17, "UpperEll"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,100 @@
+//! Per-file results shared between rules.
+
+use std::any::Any;
+use std::sync::OnceLock;
+
+/// Values keyed by their type, each computed at most once.
+///
+/// Entries form an append-only list, so references to values stay valid while
+/// further values are added, including by the computation of another value.
+#[derive(Default)]
+pub(crate) struct Memo {
+    head: OnceLock<Box<MemoEntry>>,
+}
+
+struct MemoEntry {
+    value: Box<dyn Any + Send + Sync>,
+    next: OnceLock<Box<MemoEntry>>,
+}
+
+impl Memo {
+    /// Get the value of type `T`, computing it with `compute` if there is
+    /// none yet.
+    pub(crate) fn get_or_insert_with<T: Any + Send + Sync>(
+        &self,
+        compute: impl FnOnce() -> T,
+    ) -> &T {
+        if let Some(value) = self.get() {
+            return value;
+        }
+
+        let mut entry = Box::new(MemoEntry {
+            value: Box::new(compute()),
+            next: OnceLock::new(),
+        });
+        // `compute` may itself have added values, so append after whatever is
+        // there now, unless that already includes a `T`
+        let mut slot = &self.head;
+        loop {
+            match slot.get() {
+                Some(existing) => {
+                    if let Some(value) = existing.value.downcast_ref() {
+                        return value;
+                    }
+                    slot = &existing.next;
+                }
+                None => {
+                    if let Err(rejected) = slot.set(entry) {
+                        entry = rejected;
+                        continue;
+                    }
+                    let inserted = slot.get().expect("the entry was just set");
+                    return inserted
+                        .value
+                        .downcast_ref()
+                        .expect("the entry holds a `T`");
+                }
+            }
+        }
+    }
+
+    fn get<T: Any>(&self) -> Option<&T> {
+        std::iter::successors(self.head.get(), |entry| entry.next.get())
+            .find_map(|entry| entry.value.downcast_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Lines(usize);
+
+    #[derive(Debug, PartialEq)]
+    struct Words(usize);
+
+    #[test]
+    fn test_memo_computes_once() {
+        let memo = Memo::default();
+        let mut computed = 0;
+        let lines = memo.get_or_insert_with(|| {
+            computed += 1;
+            Lines(3)
+        });
+        assert_eq!(lines, &Lines(3));
+        assert_eq!(memo.get_or_insert_with(|| Lines(4)), &Lines(3));
+        assert_eq!(computed, 1);
+    }
+
+    #[test]
+    fn test_memo_nested() {
+        let memo = Memo::default();
+        let words = memo.get_or_insert_with(|| {
+            let lines = memo.get_or_insert_with(|| Lines(2));
+            Words(lines.0 * 5)
+        });
+        assert_eq!(words, &Words(10));
+        assert_eq!(memo.get_or_insert_with(|| Lines(0)), &Lines(2));
+    }
+}
@@ -0,0 +1,340 @@
+//! Size and complexity metrics of a file and of each of its methods, for
+//! `lintal metrics`.
+//!
+//! The counts follow checkstyle's metrics checks: NCSS as JavaNCSS counts
+//! it, cyclomatic complexity as CyclomaticComplexity does with its default
+//! tokens, and nesting as the depth of nested control statements, with an
+//! `else if` at the depth of its `if`. Lambdas count towards the method
+//! they are in; the methods of local and anonymous classes are measured on
+//! their own.
+
+use lintal_java_cst::CstNode;
+
+/// Metrics of a file and the methods declared in it, in source order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileMetrics {
+    /// Non-commenting source statements of the whole file.
+    pub ncss: usize,
+    pub methods: Vec<MethodMetrics>,
+}
+
+impl FileMetrics {
+    /// The highest cyclomatic complexity of a method, 0 without methods.
+    pub fn max_complexity(&self) -> usize {
+        self.methods.iter().map(|m| m.complexity).max().unwrap_or(0)
+    }
+
+    /// The deepest nesting in a method.
+    pub fn max_nesting(&self) -> usize {
+        self.methods
+            .iter()
+            .map(|m| m.max_nesting)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Metrics of a method, constructor or initializer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodMetrics {
+    /// The method's name qualified by the types it is declared in, e.g.
+    /// `Outer.Inner.run`. Initializers are named `<clinit>` and `<init>`.
+    pub name: String,
+    /// One-based line the declaration starts on.
+    pub line: usize,
+    /// Non-commenting source statements, the declaration included.
+    pub ncss: usize,
+    pub complexity: usize,
+    /// Depth of the most deeply nested control statement, 0 if there is none.
+    pub max_nesting: usize,
+}
+
+const TYPE_DECLARATIONS: &[&str] = &[
+    "class_declaration",
+    "interface_declaration",
+    "enum_declaration",
+    "record_declaration",
+    "annotation_type_declaration",
+];
+
+/// Declarations that count as a statement each.
+const DECLARATIONS: &[&str] = &[
+    "package_declaration",
+    "import_declaration",
+    "field_declaration",
+    "constant_declaration",
+    "annotation_type_element_declaration",
+    "method_declaration",
+    "constructor_declaration",
+    "compact_constructor_declaration",
+    "static_initializer",
+];
+
+/// Statements that count as one each, wherever they are.
+const STATEMENTS: &[&str] = &[
+    "expression_statement",
+    "local_variable_declaration",
+    "explicit_constructor_invocation",
+    "if_statement",
+    "while_statement",
+    "do_statement",
+    "for_statement",
+    "enhanced_for_statement",
+    "switch_expression",
+    "switch_label",
+    "try_statement",
+    "try_with_resources_statement",
+    "catch_clause",
+    "finally_clause",
+    "synchronized_statement",
+    "labeled_statement",
+    "return_statement",
+    "break_statement",
+    "continue_statement",
+    "throw_statement",
+    "yield_statement",
+    "assert_statement",
+];
+
+/// Statements that nest the statements inside them one level deeper.
+const NESTING: &[&str] = &[
+    "if_statement",
+    "while_statement",
+    "do_statement",
+    "for_statement",
+    "enhanced_for_statement",
+    "switch_expression",
+    "try_statement",
+    "try_with_resources_statement",
+    "synchronized_statement",
+];
+
+/// Measure the file whose tree is under `root`.
+pub fn measure(root: &CstNode) -> FileMetrics {
+    let mut metrics = FileMetrics::default();
+    let mut types = Vec::new();
+    visit(root, &mut types, &mut metrics);
+    metrics
+}
+
+fn visit(node: &CstNode, types: &mut Vec<String>, metrics: &mut FileMetrics) {
+    for child in node.children() {
+        metrics.ncss += ncss_of(&child);
+        if TYPE_DECLARATIONS.contains(&child.kind()) {
+            let name = child
+                .child_by_field_name("name")
+                .map_or("", |name| name.text());
+            types.push(name.to_string());
+            visit(&child, types, metrics);
+            types.pop();
+            continue;
+        }
+        if let Some(name) = method_name(&child) {
+            let mut qualified = types.join(".");
+            if !qualified.is_empty() {
+                qualified.push('.');
+            }
+            qualified.push_str(name);
+            let mut method = MethodMetrics {
+                name: qualified,
+                line: child.inner().start_position().row + 1,
+                ncss: 1,
+                complexity: 1,
+                max_nesting: 0,
+            };
+            measure_body(&child, 0, &mut method);
+            metrics.methods.push(method);
+        }
+        visit(&child, types, metrics);
+    }
+}
+
+/// The name of `node` if it is a method, constructor or initializer.
+fn method_name<'a>(node: &CstNode<'a>) -> Option<&'a str> {
+    match node.kind() {
+        "method_declaration" | "constructor_declaration" | "compact_constructor_declaration" => {
+            Some(node.child_by_field_name("name")?.text())
+        }
+        "static_initializer" => Some("<clinit>"),
+        "block" if is_instance_initializer(node) => Some("<init>"),
+        _ => None,
+    }
+}
+
+fn is_instance_initializer(block: &CstNode) -> bool {
+    block
+        .parent()
+        .is_some_and(|parent| matches!(parent.kind(), "class_body" | "enum_body_declarations"))
+}
+
+/// How many statements `node` counts for on its own.
+fn ncss_of(node: &CstNode) -> usize {
+    let kind = node.kind();
+    if TYPE_DECLARATIONS.contains(&kind) || DECLARATIONS.contains(&kind) {
+        return 1;
+    }
+    if kind == "block" && is_instance_initializer(node) {
+        return 1;
+    }
+    if STATEMENTS.contains(&kind) {
+        // An `else` counts as a statement of its own
+        let has_else = kind == "if_statement" && node.child_by_field_name("alternative").is_some();
+        return 1 + usize::from(has_else);
+    }
+    0
+}
+
+/// How many decision points `node` adds to the complexity of its method.
+fn decisions_of(node: &CstNode) -> usize {
+    match node.kind() {
+        "if_statement"
+        | "while_statement"
+        | "do_statement"
+        | "for_statement"
+        | "enhanced_for_statement"
+        | "catch_clause"
+        | "ternary_expression" => 1,
+        "switch_label" => usize::from(node.text().starts_with("case")),
+        "binary_expression" => usize::from(
+            node.child_by_field_name("operator")
+                .is_some_and(|operator| matches!(operator.kind(), "&&" | "||")),
+        ),
+        _ => 0,
+    }
+}
+
+/// Add the statements and decision points under `node`, at nesting `depth`,
+/// to `method`, leaving out the bodies of classes declared in it.
+fn measure_body(node: &CstNode, depth: usize, method: &mut MethodMetrics) {
+    for child in node.children() {
+        let kind = child.kind();
+        if kind == "class_body" || TYPE_DECLARATIONS.contains(&kind) {
+            continue;
+        }
+        method.ncss += ncss_of(&child);
+        method.complexity += decisions_of(&child);
+        let is_else_if = kind == "if_statement"
+            && node
+                .child_by_field_name("alternative")
+                .is_some_and(|alternative| alternative.range() == child.range());
+        let depth = if NESTING.contains(&kind) && !is_else_if {
+            depth + 1
+        } else {
+            depth
+        };
+        method.max_nesting = method.max_nesting.max(depth);
+        measure_body(&child, depth, method);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_parser::JavaParser;
+
+    fn measure_source(source: &str) -> FileMetrics {
+        let result = JavaParser::new().parse(source).unwrap();
+        measure(&CstNode::new(result.tree.root_node(), source))
+    }
+
+    #[test]
+    fn test_method_metrics() {
+        let metrics = measure_source(
+            r"package a;
+
+import java.util.List;
+
+class Foo {
+    int x;
+
+    Foo() {
+        x = 1;
+    }
+
+    int run(List<String> items, boolean flag) {
+        int count = 0;
+        for (String item : items) {
+            if (item.isEmpty() && flag) {
+                continue;
+            } else if (item.length() > 3) {
+                count++;
+            } else {
+                count += 2;
+            }
+        }
+        return flag ? count : -count;
+    }
+}
+",
+        );
+        assert_eq!(
+            metrics.methods,
+            [
+                MethodMetrics {
+                    name: "Foo.Foo".to_string(),
+                    line: 8,
+                    ncss: 2,
+                    complexity: 1,
+                    max_nesting: 0,
+                },
+                MethodMetrics {
+                    name: "Foo.run".to_string(),
+                    line: 12,
+                    // The declaration, `int count`, the for, two ifs, two
+                    // elses, continue, count++, count += 2 and return
+                    ncss: 11,
+                    // for, if, &&, else if, ?:
+                    complexity: 6,
+                    max_nesting: 2,
+                },
+            ]
+        );
+        // package, import, class, field, and the methods
+        assert_eq!(metrics.ncss, 4 + 2 + 11);
+        assert_eq!(metrics.max_complexity(), 6);
+        assert_eq!(metrics.max_nesting(), 2);
+    }
+
+    #[test]
+    fn test_nested_classes_are_measured_apart() {
+        let metrics = measure_source(
+            r"class Outer {
+    static {
+        init();
+    }
+
+    void start() {
+        Runnable r = new Runnable() {
+            public void run() {
+                while (true) {
+                    step();
+                }
+            }
+        };
+        r.run();
+    }
+
+    class Inner {
+        int get() {
+            return 0;
+        }
+    }
+}
+",
+        );
+        let summary: Vec<_> = metrics
+            .methods
+            .iter()
+            .map(|m| (m.name.as_str(), m.ncss, m.complexity, m.max_nesting))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("Outer.<clinit>", 2, 1, 0),
+                ("Outer.start", 3, 1, 0),
+                ("Outer.run", 3, 2, 1),
+                ("Outer.Inner.get", 2, 1, 0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,829 @@
+//! Rewriting the import block of a file as a whole, for
+//! `lintal organize-imports` and `lintal fix` with `organize_imports` set.
+//!
+//! Unused, duplicate and redundant imports are dropped, star imports are
+//! expanded (with AvoidStarImport) or collapsed (from `star_threshold`
+//! imports of a package), and what is left is sorted into the groups that
+//! the ImportOrder or CustomImportOrder module configures. Comments between
+//! imports move with the import they are above, or the one they end the
+//! line of.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use lintal_checkstyle::MergedConfig;
+use lintal_java_cst::CstNode;
+use lintal_java_semantic::ProjectIndex;
+use lintal_source_file::LineIndex;
+use lintal_text_size::{TextRange, TextSize};
+use regex::Regex;
+
+use crate::Properties;
+use crate::rules::imports::common::{
+    collect_imports, collect_javadoc_references, collect_type_usages, get_package_name,
+};
+
+/// How the imports of a file are grouped, ordered and abbreviated.
+#[derive(Debug, Clone)]
+pub struct ImportLayout {
+    groups: Vec<ImportGroup>,
+    /// Whether groups are separated by a blank line.
+    separated: bool,
+    /// Whether imports are sorted within their group, rather than kept in
+    /// the order they were written in.
+    sorted: bool,
+    /// Whether sorting tells upper and lower case apart, putting `Z` before
+    /// `a`.
+    case_sensitive: bool,
+    /// Collapse this many single-type imports of a package into a star
+    /// import.
+    star_threshold: Option<usize>,
+    /// Expand star imports, other than of these packages and types.
+    avoid_star: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone)]
+struct ImportGroup {
+    matcher: Matcher,
+    statics: Statics,
+}
+
+/// Which imports belong to a group.
+#[derive(Debug, Clone)]
+enum Matcher {
+    /// Any import, when no other group matches it.
+    All,
+    /// Imports starting with this, e.g. `java.`.
+    Prefix(String),
+    /// Imports this matches, the longest match winning.
+    Pattern(Regex),
+    /// Imports this matches that no [`Matcher::Prefix`] or
+    /// [`Matcher::Pattern`] group does, as CustomImportOrder's
+    /// THIRD_PARTY_PACKAGE.
+    Fallback(Regex),
+    /// Imports from the file's package, compared by its first `n` names.
+    SamePackage(usize),
+}
+
+/// Which imports of a group are static, and where those go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Statics {
+    Only,
+    Excluded,
+    First,
+    Last,
+    /// Sorted in with the others.
+    Mixed,
+}
+
+impl Default for ImportLayout {
+    /// Static imports, then the others, each sorted and with a blank line
+    /// between them.
+    fn default() -> Self {
+        Self {
+            groups: vec![
+                ImportGroup {
+                    matcher: Matcher::All,
+                    statics: Statics::Only,
+                },
+                ImportGroup {
+                    matcher: Matcher::All,
+                    statics: Statics::Excluded,
+                },
+            ],
+            separated: true,
+            sorted: true,
+            case_sensitive: true,
+            star_threshold: None,
+            avoid_star: None,
+        }
+    }
+}
+
+impl ImportLayout {
+    /// The layout the import modules of `config` ask for: CustomImportOrder
+    /// if it is enabled, else ImportOrder, with AvoidStarImport and the
+    /// `[imports]` section of lintal.toml.
+    pub fn from_config(config: &MergedConfig) -> Self {
+        let enabled = |name: &str| config.get_rule(name).filter(|rule| rule.is_enabled());
+
+        let mut layout = Self::default();
+        if let Some(rule) = enabled("CustomImportOrder") {
+            layout = layout.with_custom_import_order(&rule.properties_ref());
+        } else if let Some(rule) = enabled("ImportOrder") {
+            layout = layout.with_import_order(&rule.properties_ref());
+        }
+        if let Some(rule) = enabled("AvoidStarImport") {
+            layout = layout.with_avoid_star_import(&rule.properties_ref());
+        }
+        if let Some(threshold) = config.imports.star_threshold {
+            layout = layout.with_star_threshold(threshold);
+        }
+        layout
+    }
+
+    /// Group and order imports as ImportOrder with `properties` checks them.
+    #[must_use]
+    pub fn with_import_order(mut self, properties: &Properties) -> Self {
+        let mut matchers: Vec<Matcher> = properties
+            .get("groups")
+            .into_iter()
+            .flat_map(|groups| groups.split(','))
+            .map(str::trim)
+            .filter(|group| !group.is_empty())
+            .filter_map(|group| {
+                if group == "*" {
+                    Some(Matcher::All)
+                } else if let Some(pattern) =
+                    group.strip_prefix('/').and_then(|g| g.strip_suffix('/'))
+                {
+                    Regex::new(pattern).ok().map(Matcher::Pattern)
+                } else if group.ends_with('.') {
+                    Some(Matcher::Prefix(group.to_string()))
+                } else {
+                    // `java` is the group of `java.util`, not of `javax.swing`
+                    Some(Matcher::Prefix(format!("{group}.")))
+                }
+            })
+            .collect();
+        if !matchers
+            .iter()
+            .any(|matcher| matches!(matcher, Matcher::All))
+        {
+            matchers.push(Matcher::All);
+        }
+
+        let groups = |statics| {
+            matchers
+                .iter()
+                .cloned()
+                .map(move |matcher| ImportGroup { matcher, statics })
+        };
+        let statics_group = ImportGroup {
+            matcher: Matcher::All,
+            statics: Statics::Only,
+        };
+        self.groups = match properties.get("option").map(|option| option.trim()) {
+            Some("top") => std::iter::once(statics_group)
+                .chain(groups(Statics::Excluded))
+                .collect(),
+            Some("bottom") => groups(Statics::Excluded)
+                .chain(std::iter::once(statics_group))
+                .collect(),
+            Some("above") => groups(Statics::First).collect(),
+            Some("inflow") => groups(Statics::Mixed).collect(),
+            _ => groups(Statics::Last).collect(),
+        };
+        self.separated = properties.get("separated").is_some_and(|v| *v == "true");
+        self.sorted = properties.get("ordered").is_none_or(|v| *v != "false");
+        self.case_sensitive = properties
+            .get("caseSensitive")
+            .is_none_or(|v| *v != "false");
+        self
+    }
+
+    /// Group and order imports as CustomImportOrder with `properties` checks
+    /// them.
+    #[must_use]
+    pub fn with_custom_import_order(mut self, properties: &Properties) -> Self {
+        let pattern = |name, default| {
+            Regex::new(properties.get(name).copied().unwrap_or(default))
+                .or_else(|_| Regex::new(default))
+                .expect("default pattern is valid")
+        };
+        let standard = pattern("standardPackageRegExp", r"^(java|javax)\.");
+        let third_party = pattern("thirdPartyPackageRegExp", ".*");
+        let special = pattern("specialImportsRegExp", "^$");
+
+        let rules = custom_import_order_rules(properties);
+        // Without a STATIC group, static imports go with their packages
+        let others = if rules.contains(&"STATIC") {
+            Statics::Excluded
+        } else {
+            Statics::Mixed
+        };
+
+        self.groups = rules
+            .iter()
+            .filter_map(|&rule| {
+                let (matcher, statics) = match rule {
+                    "STATIC" => (Matcher::All, Statics::Only),
+                    "STANDARD_JAVA_PACKAGE" => (Matcher::Pattern(standard.clone()), others),
+                    "THIRD_PARTY_PACKAGE" => (Matcher::Fallback(third_party.clone()), others),
+                    "SPECIAL_IMPORTS" => (Matcher::Pattern(special.clone()), others),
+                    _ => (Matcher::SamePackage(same_package_depth(rule)?), others),
+                };
+                Some(ImportGroup { matcher, statics })
+            })
+            .collect();
+        // Imports no rule matches go last
+        self.groups.push(ImportGroup {
+            matcher: Matcher::All,
+            statics: others,
+        });
+        self.separated = properties
+            .get("separateLineBetweenGroups")
+            .is_none_or(|v| *v != "false");
+        self.sorted = properties
+            .get("sortImportsInGroupAlphabetically")
+            .is_some_and(|v| *v == "true");
+        self
+    }
+
+    /// Expand star imports as AvoidStarImport with `properties` asks.
+    #[must_use]
+    pub fn with_avoid_star_import(mut self, properties: &Properties) -> Self {
+        let excludes = properties
+            .get("excludes")
+            .into_iter()
+            .flat_map(|excludes| excludes.split(','))
+            .map(str::trim)
+            .filter(|exclude| !exclude.is_empty())
+            .map(str::to_string)
+            .collect();
+        self.avoid_star = Some(excludes);
+        self
+    }
+
+    /// Replace `threshold` or more single-type imports of a package with a
+    /// star import. Ignored if star imports are expanded.
+    #[must_use]
+    pub fn with_star_threshold(mut self, threshold: usize) -> Self {
+        self.star_threshold = Some(threshold.max(1));
+        self
+    }
+
+    /// Whether star imports are expanded, which needs the
+    /// [`ProjectIndex`] of the files to know what they import.
+    pub fn expands_stars(&self) -> bool {
+        self.avoid_star.is_some()
+    }
+
+    /// The file under `root` with its imports organized, or `None` if
+    /// anything other than imports and comments is among them.
+    ///
+    /// Star imports are only expanded into types `project` declares.
+    pub fn organize(&self, root: &CstNode, project: Option<&ProjectIndex>) -> Option<String> {
+        let source = root.source;
+        let Some(block) = ImportBlock::find(root)? else {
+            return Some(source.to_string());
+        };
+
+        let tree = root.inner();
+        let package = get_package_name(tree, source);
+        let mut usages = collect_type_usages(tree, source);
+        usages.extend(collect_javadoc_references(tree, source));
+
+        let mut entries = drop_redundant(block.entries, package.as_deref(), &usages);
+        match (&self.avoid_star, project) {
+            (Some(excludes), Some(project)) => {
+                entries = expand_stars(entries, excludes, project, &usages);
+            }
+            (Some(_), None) => {}
+            (None, _) => {
+                if let Some(threshold) = self.star_threshold {
+                    entries = collapse_stars(entries, threshold);
+                }
+            }
+        }
+        entries = drop_redundant(entries, package.as_deref(), &usages);
+
+        let lines = self.lay_out(entries, package.as_deref());
+        let mut end = block.end;
+        if lines.is_empty() {
+            // Take the blank lines after the block with it
+            end += source[end..].len() - source[end..].trim_start().len();
+        }
+        Some(format!(
+            "{}{}{}",
+            &source[..block.start],
+            lines.join(newline_of(source)),
+            &source[end..]
+        ))
+    }
+
+    /// The span of the imports under `root`, and those imports grouped and
+    /// sorted as this layout asks without dropping or rewriting any, for
+    /// ImportOrder's fix. `None` without imports or with code among them.
+    pub(crate) fn reorder(&self, root: &CstNode) -> Option<(TextRange, String)> {
+        let source = root.source;
+        let block = ImportBlock::find(root)??;
+        let package = get_package_name(root.inner(), source);
+        let range = TextRange::new(
+            TextSize::try_from(block.start).ok()?,
+            TextSize::try_from(block.end).ok()?,
+        );
+        let lines = self.lay_out(block.entries, package.as_deref());
+        Some((range, lines.join(newline_of(source))))
+    }
+
+    /// The lines of `entries` put in their groups and sorted, with the
+    /// comments that go with them.
+    fn lay_out(&self, entries: Vec<Entry>, package: Option<&str>) -> Vec<String> {
+        let mut grouped = vec![Vec::new(); self.groups.len()];
+        for entry in entries {
+            grouped[self.group_of(&entry.path, entry.is_static, package)].push(entry);
+        }
+        let mut lines = Vec::new();
+        for (group, members) in self.groups.iter().zip(&mut grouped) {
+            if members.is_empty() {
+                continue;
+            }
+            let rank = |entry: &Entry| match group.statics {
+                Statics::First => u8::from(!entry.is_static),
+                Statics::Last => u8::from(entry.is_static),
+                _ => 0,
+            };
+            if self.sorted {
+                members.sort_by(|a, b| {
+                    rank(a)
+                        .cmp(&rank(b))
+                        .then_with(|| self.compare(&a.path, &b.path))
+                });
+            } else {
+                members.sort_by_key(rank);
+            }
+            if self.separated && !lines.is_empty() {
+                lines.push(String::new());
+            }
+            for entry in members.iter() {
+                lines.extend(entry.leading.iter().cloned());
+                lines.push(entry.render());
+            }
+        }
+        lines
+    }
+
+    /// Whether groups are separated by a blank line.
+    pub(crate) fn separated(&self) -> bool {
+        self.separated
+    }
+
+    /// Whether imports are sorted within their group.
+    pub(crate) fn sorted(&self) -> bool {
+        self.sorted
+    }
+
+    /// The order of two imported names, as imports are sorted.
+    pub(crate) fn compare(&self, a: &str, b: &str) -> Ordering {
+        if self.case_sensitive {
+            a.cmp(b)
+        } else {
+            a.chars()
+                .flat_map(char::to_lowercase)
+                .cmp(b.chars().flat_map(char::to_lowercase))
+        }
+    }
+
+    /// The index of the group the import of `path` goes in: of those it can
+    /// be in, the one that matches it most closely, or the first of those
+    /// that match as closely.
+    pub(crate) fn group_of(&self, path: &str, is_static: bool, package: Option<&str>) -> usize {
+        let mut best: Option<(usize, (u8, usize))> = None;
+        for (index, group) in self.groups.iter().enumerate() {
+            let allowed = match group.statics {
+                Statics::Only => is_static,
+                Statics::Excluded => !is_static,
+                _ => true,
+            };
+            if !allowed {
+                continue;
+            }
+            let Some(score) = group.matcher.score(path, package) else {
+                continue;
+            };
+            if best.is_none_or(|(_, best)| score > best) {
+                best = Some((index, score));
+            }
+        }
+        best.map_or(self.groups.len() - 1, |(index, _)| index)
+    }
+}
+
+impl Matcher {
+    /// How closely `path` matches, higher being closer, or `None` if it
+    /// doesn't.
+    fn score(&self, path: &str, package: Option<&str>) -> Option<(u8, usize)> {
+        match self {
+            Matcher::All => Some((0, 0)),
+            Matcher::Fallback(pattern) => pattern.is_match(path).then_some((1, 0)),
+            Matcher::Prefix(prefix) => path
+                .starts_with(prefix.as_str())
+                .then_some((2, prefix.len())),
+            Matcher::Pattern(pattern) => pattern.find(path).map(|found| (2, found.len())),
+            Matcher::SamePackage(depth) => {
+                let prefix: Vec<&str> = package?.split('.').take(*depth).collect();
+                if prefix.len() < *depth {
+                    return None;
+                }
+                let prefix = prefix.join(".");
+                let matches = path.starts_with(&prefix) && path[prefix.len()..].starts_with('.');
+                matches.then_some((2, prefix.len()))
+            }
+        }
+    }
+}
+
+/// An import with the comments that go with it.
+#[derive(Debug, Clone)]
+struct Entry {
+    /// The imported name, e.g. `java.util.List` or `java.util.*`.
+    path: String,
+    is_static: bool,
+    /// Comments on the lines above it.
+    leading: Vec<String>,
+    /// A comment after it on its line.
+    trailing: Option<String>,
+}
+
+impl Entry {
+    fn new(path: String, is_static: bool) -> Self {
+        Self {
+            path,
+            is_static,
+            leading: Vec::new(),
+            trailing: None,
+        }
+    }
+
+    fn is_star(&self) -> bool {
+        self.path.ends_with(".*")
+    }
+
+    /// The package or type the import is from.
+    fn qualifier(&self) -> &str {
+        self.path
+            .rsplit_once('.')
+            .map_or("", |(qualifier, _)| qualifier)
+    }
+
+    fn render(&self) -> String {
+        let keyword = if self.is_static {
+            "import static"
+        } else {
+            "import"
+        };
+        match &self.trailing {
+            Some(comment) => format!("{keyword} {}; {comment}", self.path),
+            None => format!("{keyword} {};", self.path),
+        }
+    }
+}
+
+/// The imports of a file and the text they span.
+struct ImportBlock {
+    /// Byte offsets of the start of the first import and the end of the
+    /// last one, or of the comment after it on its line.
+    start: usize,
+    end: usize,
+    entries: Vec<Entry>,
+}
+
+impl ImportBlock {
+    /// The imports under `root`; `Some(None)` without imports, `None` if other
+    /// code is among them.
+    fn find(root: &CstNode) -> Option<Option<Self>> {
+        let source = root.source;
+        let imports = collect_imports(root.inner(), source, &LineIndex::from_source_text(source));
+        let (Some(first), Some(last)) = (imports.first(), imports.last()) else {
+            return Some(None);
+        };
+        let (start, last_end) = (first.range.start().to_usize(), last.range.end().to_usize());
+        let mut imports = imports.iter();
+
+        let mut block = Self {
+            start,
+            end: last_end,
+            entries: Vec::new(),
+        };
+        let mut pending = Vec::new();
+        for child in root.children() {
+            let range = child.range();
+            let (child_start, child_end) = (range.start().to_usize(), range.end().to_usize());
+            if child_start < start {
+                continue;
+            }
+            match child.kind() {
+                "import_declaration" => {
+                    // Imports that don't parse aren't collected
+                    let import = imports.next().filter(|import| import.range == range)?;
+                    let mut entry = Entry::new(import.path.clone(), import.is_static);
+                    entry.leading = std::mem::take(&mut pending);
+                    block.entries.push(entry);
+                    block.end = child_end;
+                }
+                "line_comment" | "block_comment" => {
+                    let text = child.text().trim_end().to_string();
+                    let trails = block
+                        .entries
+                        .last()
+                        .is_some_and(|entry| entry.trailing.is_none())
+                        && !source[block.end..child_start].contains('\n')
+                        && !text.contains('\n');
+                    if let Some(entry) = block.entries.last_mut().filter(|_| trails) {
+                        entry.trailing = Some(text);
+                        block.end = child_end;
+                    } else if child_start > last_end {
+                        break;
+                    } else {
+                        pending.push(text);
+                    }
+                }
+                _ if child_start > last_end => break,
+                _ => return None,
+            }
+        }
+        Some(Some(block))
+    }
+}
+
+/// The groups of CustomImportOrder's `customImportOrderRules` in
+/// `properties`, without those it doesn't know. Each is the group of that
+/// index of the layout [`ImportLayout::with_custom_import_order`] makes;
+/// the group after them is of the imports none of them match.
+pub(crate) fn custom_import_order_rules<'a>(properties: &Properties<'a>) -> Vec<&'a str> {
+    properties
+        .get("customImportOrderRules")
+        .copied()
+        .into_iter()
+        .flat_map(|rules| rules.split("###"))
+        .map(str::trim)
+        .filter(|&rule| {
+            matches!(
+                rule,
+                "STATIC" | "STANDARD_JAVA_PACKAGE" | "THIRD_PARTY_PACKAGE" | "SPECIAL_IMPORTS"
+            ) || same_package_depth(rule).is_some()
+        })
+        .collect()
+}
+
+/// The `n` of a `SAME_PACKAGE(n)` rule.
+fn same_package_depth(rule: &str) -> Option<usize> {
+    rule.strip_prefix("SAME_PACKAGE(")?
+        .strip_suffix(')')?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// The line ending `source` uses.
+fn newline_of(source: &str) -> &'static str {
+    if source.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+/// `entries` without duplicates, unused single-type imports, and imports
+/// of `java.lang` or of the file's own package. The comments of the imports
+/// dropped move to the next import.
+fn drop_redundant(
+    entries: Vec<Entry>,
+    package: Option<&str>,
+    usages: &HashSet<String>,
+) -> Vec<Entry> {
+    let mut seen = HashSet::new();
+    let mut kept: Vec<Entry> = Vec::new();
+    let mut orphaned = Vec::new();
+    for mut entry in entries {
+        let simple_name = entry.path.rsplit('.').next().unwrap_or_default();
+        let redundant = !entry.is_static
+            && (entry.qualifier() == "java.lang" || Some(entry.qualifier()) == package);
+        let keep = !redundant
+            && (entry.is_star() || usages.contains(simple_name))
+            && seen.insert((entry.path.clone(), entry.is_static));
+        if keep {
+            orphaned.append(&mut entry.leading);
+            entry.leading = std::mem::take(&mut orphaned);
+            kept.push(entry);
+        } else {
+            orphaned.append(&mut entry.leading);
+        }
+    }
+    // Comments after every remaining import stay at the end
+    if let Some(last) = kept.last_mut() {
+        last.leading.extend(orphaned);
+    }
+    kept
+}
+
+/// `entries` with star imports replaced by the types they import that the
+/// file uses, where `project` declares what they are.
+fn expand_stars(
+    entries: Vec<Entry>,
+    excludes: &[String],
+    project: &ProjectIndex,
+    usages: &HashSet<String>,
+) -> Vec<Entry> {
+    let mut expanded = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let qualifier = entry.qualifier();
+        let types =
+            if entry.is_star() && !entry.is_static && !excludes.iter().any(|e| e == qualifier) {
+                project.expand_star_import(qualifier)
+            } else {
+                Vec::new()
+            };
+        if types.is_empty() {
+            expanded.push(entry);
+            continue;
+        }
+        let mut names: Vec<String> = types
+            .iter()
+            .filter(|ty| usages.contains(&ty.name))
+            .map(|ty| format!("{qualifier}.{}", ty.name))
+            .collect();
+        names.sort();
+        names.dedup();
+        let mut comments = Some((entry.leading, entry.trailing));
+        for name in names {
+            let mut single = Entry::new(name, false);
+            if let Some((leading, trailing)) = comments.take() {
+                single.leading = leading;
+                single.trailing = trailing;
+            }
+            expanded.push(single);
+        }
+    }
+    expanded
+}
+
+/// `entries` with the single-type imports of each package that has at
+/// least `threshold` of them replaced by a star import, where the first of
+/// them was.
+fn collapse_stars(entries: Vec<Entry>, threshold: usize) -> Vec<Entry> {
+    let collapsible = |entry: &Entry| !entry.is_static && !entry.is_star();
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for entry in entries.iter().filter(|entry| collapsible(entry)) {
+        *counts.entry(entry.qualifier().to_string()).or_default() += 1;
+    }
+
+    let mut collapsed: Vec<Entry> = Vec::with_capacity(entries.len());
+    let mut stars: HashMap<String, usize> = HashMap::new();
+    for mut entry in entries {
+        let qualifier = entry.qualifier().to_string();
+        if !collapsible(&entry) || counts[&qualifier] < threshold {
+            collapsed.push(entry);
+            continue;
+        }
+        // A comment at the end of the line goes above the star import
+        entry.leading.extend(entry.trailing.take());
+        match stars.get(&qualifier) {
+            Some(&index) => {
+                let star = &mut collapsed[index];
+                star.leading.append(&mut entry.leading);
+            }
+            None => {
+                stars.insert(qualifier.clone(), collapsed.len());
+                let mut star = Entry::new(format!("{qualifier}.*"), false);
+                star.leading = entry.leading;
+                collapsed.push(star);
+            }
+        }
+    }
+    collapsed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_parser::JavaParser;
+    use lintal_java_semantic::FileSummary;
+
+    fn organize(layout: &ImportLayout, source: &str) -> String {
+        organize_in(layout, source, None)
+    }
+
+    fn organize_in(layout: &ImportLayout, source: &str, project: Option<&ProjectIndex>) -> String {
+        let result = JavaParser::new().parse(source).unwrap();
+        layout
+            .organize(&CstNode::new(result.tree.root_node(), source), project)
+            .unwrap()
+    }
+
+    const BODY: &str = "
+class Foo {
+    List<String> items = new ArrayList<>();
+    Map<String, Path> paths = emptyMap();
+    Test test;
+}
+";
+
+    #[test]
+    fn test_default_layout() {
+        let source = format!(
+            "package a;\n\nimport java.util.Map;\nimport org.junit.Test;\nimport java.util.List;\nimport java.util.Set;\nimport java.lang.String;\nimport a.Bar;\nimport static java.util.Collections.emptyMap;\nimport java.nio.file.Path;\nimport java.util.ArrayList;\nimport java.util.List;\n{BODY}"
+        );
+        assert_eq!(
+            organize(&ImportLayout::default(), &source),
+            format!(
+                "package a;\n\nimport static java.util.Collections.emptyMap;\n\nimport java.nio.file.Path;\nimport java.util.ArrayList;\nimport java.util.List;\nimport java.util.Map;\nimport org.junit.Test;\n{BODY}"
+            )
+        );
+    }
+
+    #[test]
+    fn test_import_order_groups() {
+        let properties: Properties = HashMap::from([
+            ("groups", "java,javax,/^org\\./"),
+            ("option", "bottom"),
+            ("separated", "true"),
+        ]);
+        let layout = ImportLayout::default().with_import_order(&properties);
+        let source = format!(
+            "import static java.util.Collections.emptyMap;\nimport com.acme.Test;\nimport org.slf4j.Path;\nimport javax.annotation.Map;\nimport java.util.List;\nimport java.util.ArrayList;\n{BODY}"
+        );
+        assert_eq!(
+            organize(&layout, &source),
+            format!(
+                "import java.util.ArrayList;\nimport java.util.List;\n\nimport javax.annotation.Map;\n\nimport org.slf4j.Path;\n\nimport com.acme.Test;\n\nimport static java.util.Collections.emptyMap;\n{BODY}"
+            )
+        );
+    }
+
+    #[test]
+    fn test_custom_import_order() {
+        let properties: Properties = HashMap::from([(
+            "customImportOrderRules",
+            "STATIC###SAME_PACKAGE(2)###STANDARD_JAVA_PACKAGE###THIRD_PARTY_PACKAGE",
+        )]);
+        let layout = ImportLayout::default().with_custom_import_order(&properties);
+        let source = format!(
+            "package com.acme.app;\n\nimport org.junit.Test;\nimport java.util.List;\nimport com.acme.util.Path;\nimport static java.util.Collections.emptyMap;\nimport java.util.Map;\nimport java.util.ArrayList;\n{BODY}"
+        );
+        // Not sorted by default
+        assert_eq!(
+            organize(&layout, &source),
+            format!(
+                "package com.acme.app;\n\nimport static java.util.Collections.emptyMap;\n\nimport com.acme.util.Path;\n\nimport java.util.List;\nimport java.util.Map;\nimport java.util.ArrayList;\n\nimport org.junit.Test;\n{BODY}"
+            )
+        );
+    }
+
+    #[test]
+    fn test_comments_move_with_their_import() {
+        let source = "import java.util.Set;\n// Lists\nimport java.util.List; // of strings\n/* Maps */\nimport java.util.Map;\n\nclass Foo {\n    Map<String, List<String>> map;\n}\n";
+        assert_eq!(
+            organize(&ImportLayout::default(), source),
+            "// Lists\nimport java.util.List; // of strings\n/* Maps */\nimport java.util.Map;\n\nclass Foo {\n    Map<String, List<String>> map;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_collapse_stars() {
+        let layout = ImportLayout::default().with_star_threshold(3);
+        let source = format!(
+            "import java.util.Map;\nimport java.util.List;\nimport java.util.ArrayList;\nimport java.nio.file.Path;\nimport org.junit.Test;\nimport static java.util.Collections.emptyMap;\n{BODY}"
+        );
+        assert_eq!(
+            organize(&layout, &source),
+            format!(
+                "import static java.util.Collections.emptyMap;\n\nimport java.nio.file.Path;\nimport java.util.*;\nimport org.junit.Test;\n{BODY}"
+            )
+        );
+    }
+
+    #[test]
+    fn test_expand_stars() {
+        let shapes =
+            "package com.acme.shapes;\n\npublic class Circle {}\nclass Square {}\nclass Line {}\n";
+        let source = "import com.acme.shapes.*;\nimport java.util.*;\n\nclass Foo {\n    Circle c;\n    Line l;\n    List<String> names;\n}\n";
+        let result = JavaParser::new().parse(shapes).unwrap();
+        let project = ProjectIndex::new([FileSummary::build(
+            "shapes/Circle.java",
+            &CstNode::new(result.tree.root_node(), shapes),
+        )]);
+
+        let layout = ImportLayout::default().with_avoid_star_import(&HashMap::new());
+        // java.util isn't in the project, so its star import stays
+        assert_eq!(
+            organize_in(&layout, source, Some(&project)),
+            "import com.acme.shapes.Circle;\nimport com.acme.shapes.Line;\nimport java.util.*;\n\nclass Foo {\n    Circle c;\n    Line l;\n    List<String> names;\n}\n"
+        );
+
+        let excluded = HashMap::from([("excludes", "com.acme.shapes")]);
+        let layout = ImportLayout::default().with_avoid_star_import(&excluded);
+        assert_eq!(organize_in(&layout, source, Some(&project)), source);
+    }
+
+    #[test]
+    fn test_unused_imports_only() {
+        let source =
+            "package a;\n\nimport java.util.List;\nimport java.util.Map;\n\nclass Foo {}\n";
+        assert_eq!(
+            organize(&ImportLayout::default(), source),
+            "package a;\n\nclass Foo {}\n"
+        );
+        let source = "class Foo {}\n";
+        assert_eq!(organize(&ImportLayout::default(), source), source);
+    }
+
+    #[test]
+    fn test_code_among_imports() {
+        let source = "import java.util.List;\n;\nimport java.util.Map;\n\nclass Foo {}\n";
+        let result = JavaParser::new().parse(source).unwrap();
+        let root = CstNode::new(result.tree.root_node(), source);
+        assert!(ImportLayout::default().organize(&root, None).is_none());
+    }
+}
@@ -0,0 +1,484 @@
+//! Rules loaded from plugin libraries.
+//!
+//! Plugins are `cdylib`s built against [`lintal_plugin_api`]. A loaded
+//! library stays loaded for the rest of the process, so the names and node
+//! kinds of its rules can be handed out as `&'static str`.
+
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use lintal_diagnostics::{Diagnostic, DiagnosticKind, Severity};
+use lintal_java_cst::CstNode;
+use lintal_plugin_api::abi::{
+    ABI_VERSION, ENTRY_POINT, EntryPoint, NO_NODE, NodeHandle, RawHost, RawPlugin, RawProperty,
+    RawRange, RawRule, RawStr,
+};
+use lintal_text_size::{TextRange, TextSize};
+use thiserror::Error;
+
+use crate::{CheckContext, Properties, Rule};
+
+#[derive(Error, Debug)]
+pub enum PluginError {
+    #[error("Failed to load plugin {}: {source}", .path.display())]
+    Load {
+        path: PathBuf,
+        source: libloading::Error,
+    },
+    #[error("{} is not a lintal plugin (no `lintal_plugin_v1` entry point)", .path.display())]
+    MissingEntryPoint { path: PathBuf },
+    #[error(
+        "Plugin {} was built for plugin ABI v{found}, expected v{expected}",
+        .path.display()
+    )]
+    AbiVersion {
+        path: PathBuf,
+        found: u32,
+        expected: u32,
+    },
+    #[error("Failed to load WebAssembly plugin {}: {message}", .path.display())]
+    Wasm { path: PathBuf, message: String },
+}
+
+/// A rule exported by a plugin.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PluginRuleInfo {
+    pub(crate) name: &'static str,
+    pub(crate) relevant_kinds: &'static [&'static str],
+    pub(crate) index: usize,
+}
+
+/// A loaded plugin library.
+pub struct PluginLibrary {
+    path: PathBuf,
+    name: &'static str,
+    plugin: *const RawPlugin,
+    rules: Vec<PluginRuleInfo>,
+    // Kept so the library is never unloaded while `plugin` is in use
+    _library: Option<libloading::Library>,
+}
+
+// SAFETY: the plugin descriptor is immutable, and plugin rules are required
+// to be thread-safe by the ABI
+unsafe impl Send for PluginLibrary {}
+// SAFETY: see above
+unsafe impl Sync for PluginLibrary {}
+
+impl PluginLibrary {
+    /// Load the plugin library at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Arc<Self>, PluginError> {
+        let path = path.as_ref().to_path_buf();
+        // SAFETY: loading a library runs its initializers; plugins are
+        // trusted code listed in the user's own configuration
+        let library =
+            unsafe { libloading::Library::new(&path) }.map_err(|source| PluginError::Load {
+                path: path.clone(),
+                source,
+            })?;
+        // SAFETY: the entry point has the signature the ABI prescribes
+        let plugin = unsafe {
+            let entry = library
+                .get::<EntryPoint>(ENTRY_POINT.as_bytes())
+                .map_err(|_| PluginError::MissingEntryPoint { path: path.clone() })?;
+            entry()
+        };
+        // SAFETY: the descriptor lives as long as the library
+        unsafe { Self::from_raw(path, plugin, Some(library)) }
+    }
+
+    /// Wrap a plugin descriptor, e.g. one linked into this binary.
+    ///
+    /// # Safety
+    ///
+    /// `plugin` must be null or point to a [`RawPlugin`] that stays valid for
+    /// the rest of the process (or as long as `library` is loaded).
+    pub unsafe fn from_raw(
+        path: PathBuf,
+        plugin: *const RawPlugin,
+        library: Option<libloading::Library>,
+    ) -> Result<Arc<Self>, PluginError> {
+        // SAFETY: guaranteed by the caller
+        let Some(raw) = (unsafe { plugin.as_ref() }) else {
+            return Err(PluginError::MissingEntryPoint { path });
+        };
+        if raw.abi_version != ABI_VERSION {
+            return Err(PluginError::AbiVersion {
+                path,
+                found: raw.abi_version,
+                expected: ABI_VERSION,
+            });
+        }
+
+        // SAFETY: the descriptor's arrays and strings live as long as it does
+        let raw_rules = unsafe { raw_slice(raw.rules, raw.rules_len) };
+        let rules = raw_rules
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| {
+                // SAFETY: as above
+                let kinds = unsafe { raw_slice(rule.relevant_kinds, rule.relevant_kinds_len) };
+                PluginRuleInfo {
+                    name: leak(rule.name),
+                    relevant_kinds: Box::leak(kinds.iter().map(|k| leak(*k)).collect::<Box<[_]>>()),
+                    index,
+                }
+            })
+            .collect();
+
+        Ok(Arc::new(Self {
+            path,
+            name: leak(raw.name),
+            plugin,
+            rules,
+            _library: library,
+        }))
+    }
+
+    /// The plugin's name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The path the plugin was loaded from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Module names of the rules the plugin provides.
+    pub fn rule_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.rules.iter().map(|rule| rule.name)
+    }
+
+    /// Create a configured instance of the rule at `index`, or `None` if the
+    /// plugin rejects the properties.
+    fn create_rule(self: &Arc<Self>, index: usize, properties: &Properties) -> Option<PluginRule> {
+        let info = self.rules[index];
+        let raw_properties: Vec<RawProperty> = properties
+            .iter()
+            .map(|(name, value)| RawProperty {
+                name: RawStr::new(name),
+                value: RawStr::new(value),
+            })
+            .collect();
+        let instance =
+            (self.raw_rule(info.index).create)(raw_properties.as_ptr(), raw_properties.len());
+        (!instance.is_null()).then(|| PluginRule {
+            library: Arc::clone(self),
+            info,
+            instance,
+        })
+    }
+
+    fn raw_rule(&self, index: usize) -> &RawRule {
+        // SAFETY: `plugin` was validated in `from_raw` and outlives `self`
+        unsafe { &raw_slice((*self.plugin).rules, (*self.plugin).rules_len)[index] }
+    }
+
+    /// Factories for every rule in the plugin, for registering with a
+    /// [`RuleRegistry`](crate::RuleRegistry).
+    pub(crate) fn factories(
+        self: &Arc<Self>,
+    ) -> impl Iterator<Item = (&'static str, crate::registry::RuleFactory)> + '_ {
+        self.rules.iter().map(|rule| {
+            let library = Arc::clone(self);
+            let index = rule.index;
+            let factory: crate::registry::RuleFactory = Box::new(move |properties| {
+                library
+                    .create_rule(index, properties)
+                    .map(|rule| Box::new(rule) as Box<dyn Rule>)
+            });
+            (rule.name, factory)
+        })
+    }
+}
+
+/// A configured instance of a plugin rule.
+struct PluginRule {
+    library: Arc<PluginLibrary>,
+    info: PluginRuleInfo,
+    instance: *mut c_void,
+}
+
+// SAFETY: plugin rule instances are required to be thread-safe by the ABI
+// (`PluginRule: Send + Sync` on the guest side)
+unsafe impl Send for PluginRule {}
+// SAFETY: see above
+unsafe impl Sync for PluginRule {}
+
+impl Drop for PluginRule {
+    fn drop(&mut self) {
+        (self.library.raw_rule(self.info.index).destroy)(self.instance);
+    }
+}
+
+impl Rule for PluginRule {
+    fn name(&self) -> &'static str {
+        self.info.name
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        self.info.relevant_kinds
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        let state = HostState::new(ctx.source(), *node, self.info.name);
+        let host = RawHost {
+            ctx: (&raw const state).cast_mut().cast(),
+            source: host_source,
+            kind: host_kind,
+            is_named: host_is_named,
+            range: host_range,
+            parent: host_parent,
+            child_count: host_child_count,
+            child: host_child,
+            child_by_field_name: host_child_by_field_name,
+            report: host_report,
+        };
+        (self.library.raw_rule(self.info.index).check)(self.instance, &raw const host, 1);
+        state.into_diagnostics()
+    }
+}
+
+/// Host-side state for one `check` call. Node handles are indices into
+/// `nodes`, offset by one so that zero means "no node"; the checked node is
+/// handle 1.
+pub(crate) struct HostState<'a> {
+    source: &'a str,
+    nodes: RefCell<Vec<CstNode<'a>>>,
+    diagnostics: RefCell<Vec<Diagnostic>>,
+    rule: &'static str,
+}
+
+impl<'a> HostState<'a> {
+    pub(crate) fn new(source: &'a str, node: CstNode<'a>, rule: &'static str) -> Self {
+        Self {
+            source,
+            nodes: RefCell::new(vec![node]),
+            diagnostics: RefCell::new(Vec::new()),
+            rule,
+        }
+    }
+
+    pub(crate) fn source(&self) -> &'a str {
+        self.source
+    }
+
+    pub(crate) fn into_diagnostics(self) -> Vec<Diagnostic> {
+        self.diagnostics.into_inner()
+    }
+
+    /// Recover the state from the `ctx` pointer of a [`RawHost`].
+    fn from_ctx<'s>(ctx: *mut c_void) -> &'s HostState<'a> {
+        // SAFETY: `ctx` always points at the `HostState` of the ongoing check
+        unsafe { &*ctx.cast::<HostState<'a>>() }
+    }
+
+    pub(crate) fn node(&self, handle: NodeHandle) -> Option<CstNode<'a>> {
+        let index = handle.checked_sub(1)?;
+        self.nodes.borrow().get(index).copied()
+    }
+
+    pub(crate) fn handle(&self, node: Option<CstNode<'a>>) -> NodeHandle {
+        let Some(node) = node else {
+            return NO_NODE;
+        };
+        let mut nodes = self.nodes.borrow_mut();
+        nodes.push(node);
+        nodes.len()
+    }
+
+    pub(crate) fn report(&self, start: u32, end: u32, body: String) {
+        // Clamp to the source so a buggy plugin can't produce out-of-bounds ranges
+        let len = TextSize::of(self.source);
+        let start = TextSize::from(start).min(len);
+        let end = TextSize::from(end).clamp(start, len);
+        self.diagnostics.borrow_mut().push(Diagnostic {
+            kind: DiagnosticKind {
+                code: self.rule.to_string(),
+                body,
+                message_key: None,
+                message_args: Vec::new(),
+            },
+            range: TextRange::new(start, end),
+            fix: None,
+            severity: Severity::default(),
+        });
+    }
+}
+
+extern "C" fn host_source(ctx: *mut c_void) -> RawStr {
+    RawStr::new(HostState::from_ctx(ctx).source)
+}
+
+extern "C" fn host_kind(ctx: *mut c_void, node: NodeHandle) -> RawStr {
+    RawStr::new(HostState::from_ctx(ctx).node(node).map_or("", |n| n.kind()))
+}
+
+extern "C" fn host_is_named(ctx: *mut c_void, node: NodeHandle) -> bool {
+    HostState::from_ctx(ctx)
+        .node(node)
+        .is_some_and(|n| n.inner().is_named())
+}
+
+extern "C" fn host_range(ctx: *mut c_void, node: NodeHandle) -> RawRange {
+    let range = HostState::from_ctx(ctx)
+        .node(node)
+        .map_or_else(TextRange::default, |n| n.range());
+    RawRange {
+        start: range.start().into(),
+        end: range.end().into(),
+    }
+}
+
+extern "C" fn host_parent(ctx: *mut c_void, node: NodeHandle) -> NodeHandle {
+    let state = HostState::from_ctx(ctx);
+    state.handle(state.node(node).and_then(|n| n.parent()))
+}
+
+extern "C" fn host_child_count(ctx: *mut c_void, node: NodeHandle) -> usize {
+    HostState::from_ctx(ctx)
+        .node(node)
+        .map_or(0, |n| n.children().count())
+}
+
+extern "C" fn host_child(ctx: *mut c_void, node: NodeHandle, index: usize) -> NodeHandle {
+    let state = HostState::from_ctx(ctx);
+    state.handle(state.node(node).and_then(|n| n.children().nth(index)))
+}
+
+extern "C" fn host_child_by_field_name(
+    ctx: *mut c_void,
+    node: NodeHandle,
+    field: RawStr,
+) -> NodeHandle {
+    let state = HostState::from_ctx(ctx);
+    // SAFETY: the field name outlives this call
+    let field = unsafe { field.as_str() };
+    state.handle(state.node(node).and_then(|n| n.child_by_field_name(field)))
+}
+
+extern "C" fn host_report(ctx: *mut c_void, range: RawRange, message: RawStr) {
+    // SAFETY: the message outlives this call
+    let body = unsafe { message.as_str() }.to_string();
+    HostState::from_ctx(ctx).report(range.start, range.end, body);
+}
+
+/// View a raw array from a plugin descriptor.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to `len` initialized values living for `'a`.
+unsafe fn raw_slice<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
+    if ptr.is_null() {
+        &[]
+    } else {
+        // SAFETY: guaranteed by the caller
+        unsafe { std::slice::from_raw_parts(ptr, len) }
+    }
+}
+
+/// Copy a plugin string into a `'static` one. Plugins are never unloaded,
+/// so this happens once per name.
+fn leak(raw: RawStr) -> &'static str {
+    // SAFETY: descriptor strings live as long as the plugin
+    let s = unsafe { raw.as_str() };
+    Box::leak(s.to_string().into_boxed_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+    use lintal_plugin_api::{Host, Node, PluginDescriptor, PluginRule as _, rule_descriptor};
+
+    use super::*;
+    use crate::RuleRegistry;
+
+    /// Flags calls to a configurable method.
+    struct BannedMethod {
+        method: String,
+    }
+
+    impl lintal_plugin_api::PluginRule for BannedMethod {
+        const NAME: &'static str = "BannedMethod";
+        const RELEVANT_KINDS: &'static [&'static str] = &["method_invocation"];
+
+        fn from_properties(properties: &HashMap<&str, &str>) -> Option<Self> {
+            Some(Self {
+                method: properties.get("method")?.to_string(),
+            })
+        }
+
+        fn check(&self, host: &Host<'_>, node: Node<'_>) {
+            if let Some(name) = node.child_by_field_name("name")
+                && name.text() == self.method
+                && node.parent().is_some()
+            {
+                host.report(name.range(), &format!("Do not call {}().", self.method));
+            }
+        }
+    }
+
+    fn library() -> Arc<PluginLibrary> {
+        static PLUGIN: std::sync::OnceLock<PluginDescriptor> = std::sync::OnceLock::new();
+        let plugin = PLUGIN
+            .get_or_init(|| PluginDescriptor::new("test", vec![rule_descriptor::<BannedMethod>()]));
+        // SAFETY: the descriptor is in a static
+        unsafe { PluginLibrary::from_raw(PathBuf::from("test"), plugin.as_raw(), None).unwrap() }
+    }
+
+    #[test]
+    fn test_plugin_rule_reports_violations() {
+        let mut registry = RuleRegistry::new();
+        registry.register_plugin(&library());
+        assert!(registry.has_rule(BannedMethod::NAME));
+
+        let properties: Properties = [("method", "exit")].into_iter().collect();
+        let rule = registry.create_rule("BannedMethod", &properties).unwrap();
+        assert_eq!(rule.relevant_kinds(), ["method_invocation"]);
+
+        let source = "class A { void m() { System.exit(1); run(); } }";
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let diagnostics: Vec<Diagnostic> = TreeWalker::new(result.tree.root_node(), source)
+            .filter(|node| node.kind() == "method_invocation")
+            .flat_map(|node| rule.check(&ctx, &node))
+            .collect();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind.body, "Do not call exit().");
+        assert_eq!(&source[diagnostics[0].range], "exit");
+    }
+
+    #[test]
+    fn test_plugin_rejects_invalid_properties() {
+        let mut registry = RuleRegistry::new();
+        registry.register_plugin(&library());
+        assert!(
+            registry
+                .create_rule("BannedMethod", &Properties::new())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_rejects_other_abi_versions() {
+        let plugin = RawPlugin {
+            abi_version: ABI_VERSION + 1,
+            name: RawStr::new("future"),
+            rules: std::ptr::null(),
+            rules_len: 0,
+        };
+        // SAFETY: `plugin` outlives the call, which fails before keeping it
+        let result =
+            unsafe { PluginLibrary::from_raw(PathBuf::from("future"), &raw const plugin, None) };
+        assert!(
+            matches!(result, Err(PluginError::AbiVersion { found, .. }) if found == ABI_VERSION + 1)
+        );
+    }
+}
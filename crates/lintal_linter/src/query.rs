@@ -0,0 +1,67 @@
+//! Running the queries of [`QueryRule`]s over a file's tree.
+
+use lintal_diagnostics::Diagnostic;
+use lintal_java_cst::CstNode;
+use tree_sitter::{Node, Query, QueryCursor, StreamingIterator};
+
+use crate::{CheckContext, QueryRule};
+
+/// The nodes captured by one match of a rule's query.
+#[derive(Debug)]
+pub struct QueryCaptures<'a> {
+    pattern: usize,
+    captures: Vec<(&'a str, CstNode<'a>)>,
+}
+
+impl<'a> QueryCaptures<'a> {
+    /// Index of the pattern that matched, for queries with several.
+    pub fn pattern(&self) -> usize {
+        self.pattern
+    }
+
+    /// The first node captured as `@name`.
+    pub fn get(&self, name: &str) -> Option<CstNode<'a>> {
+        self.all(name).next()
+    }
+
+    /// Every node captured as `@name`, for quantified captures.
+    pub fn all<'b>(&'b self, name: &'b str) -> impl Iterator<Item = CstNode<'a>> + 'b {
+        self.captures
+            .iter()
+            .filter(move |(capture, _)| *capture == name)
+            .map(|(_, node)| *node)
+    }
+}
+
+/// Run `query` over the tree under `root` and hand each match to `rule`.
+/// Predicates like `#match?` and `#eq?` are applied to the source text.
+pub(crate) fn check_matches(
+    rule: &dyn QueryRule,
+    query: &Query,
+    ctx: &CheckContext,
+    root: Node,
+    source: &str,
+) -> Vec<Diagnostic> {
+    let names = query.capture_names();
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(query, root, source.as_bytes());
+
+    let mut diagnostics = vec![];
+    while let Some(found) = matches.next() {
+        let captures = QueryCaptures {
+            pattern: found.pattern_index,
+            captures: found
+                .captures
+                .iter()
+                .map(|capture| {
+                    (
+                        names[capture.index as usize],
+                        CstNode::new(capture.node, source),
+                    )
+                })
+                .collect(),
+        };
+        diagnostics.extend(rule.check_match(ctx, &captures));
+    }
+    diagnostics
+}
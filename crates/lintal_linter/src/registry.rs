@@ -1,8 +1,15 @@
 //! Rule registry for mapping checkstyle module names to rule implementations.
 
 use std::collections::HashMap;
+#[cfg(not(target_family = "wasm"))]
+use std::sync::Arc;
+
+use lintal_checkstyle::MergedConfig;
+use thiserror::Error;
 
 use crate::Rule;
+#[cfg(not(target_family = "wasm"))]
+use crate::plugin::PluginLibrary;
 
 /// Properties from a checkstyle module configuration.
 pub type Properties<'a> = HashMap<&'a str, &'a str>;
@@ -17,8 +24,18 @@ pub trait FromConfig: Rule + Sized {
     fn from_config(properties: &Properties) -> Self;
 }
 
-/// A factory function that creates a boxed rule from properties.
-type RuleFactory = fn(&Properties) -> Box<dyn Rule>;
+/// Why a configured rule could not be created.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RuleError {
+    #[error("Unknown rule '{0}'")]
+    Unknown(String),
+    #[error("Rule '{0}' rejected its configuration")]
+    Rejected(String),
+}
+
+/// A factory that creates a boxed rule from properties, or `None` if the
+/// properties are rejected.
+pub(crate) type RuleFactory = Box<dyn Fn(&Properties) -> Option<Box<dyn Rule>> + Send + Sync>;
 
 /// Registry mapping checkstyle module names to rule factories.
 pub struct RuleRegistry {
@@ -42,26 +59,51 @@ impl RuleRegistry {
 
     /// Register a rule type that implements FromConfig.
     pub fn register<R: FromConfig + 'static>(&mut self) {
-        self.factories
-            .insert(R::MODULE_NAME, |props| Box::new(R::from_config(props)));
+        self.factories.insert(
+            R::MODULE_NAME,
+            Box::new(|props| Some(Box::new(R::from_config(props)))),
+        );
+    }
+
+    /// Register every rule provided by a plugin library. A plugin rule with
+    /// the same name as an existing rule replaces it.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn register_plugin(&mut self, library: &Arc<PluginLibrary>) {
+        self.factories.extend(library.factories());
+    }
+
+    /// Register every rule provided by a WebAssembly plugin, as
+    /// [`register_plugin`](Self::register_plugin) does for native ones.
+    #[cfg(feature = "wasm-plugins")]
+    pub fn register_wasm_plugin(&mut self, plugin: &Arc<crate::wasm::WasmPlugin>) {
+        self.factories.extend(plugin.factories());
     }
 
     /// Register all built-in rules.
     fn register_builtins(&mut self) {
         use crate::rules::{
-            ArrayTypeStyle, AvoidNestedBlocks, ConstantName, CovariantEquals, DeclarationOrder,
-            DefaultComesLast, DescendantToken, EmptyBlock, EmptyCatchBlock, EmptyForInitializerPad,
-            EmptyLineSeparator, EmptyStatement, EqualsHashCode, FallThrough, FileTabCharacter,
-            FinalClass, FinalLocalVariable, FinalParameters, HiddenField,
-            HideUtilityClassConstructor, IllegalType, Indentation, InnerAssignment, LeftCurly,
-            LineLength, LocalFinalVariableName, LocalVariableName, MemberName, MethodLength,
-            MethodName, MethodParamPad, MissingSwitchDefault, ModifierOrder,
-            MultipleVariableDeclarations, MutableException, NeedBraces, NestedTryDepth,
-            NoWhitespaceAfter, NoWhitespaceBefore, OneStatementPerLine, OperatorWrap,
-            PackageDeclaration, PackageName, ParameterName, ParenPad, RedundantImport,
-            RedundantModifier, RegexpSinglelineJava, RightCurly, SimplifyBooleanExpression,
-            SimplifyBooleanReturn, SingleSpaceSeparator, StaticVariableName, StringLiteralEquality,
-            TypeName, TypecastParenPad, UnusedImports, UpperEll, WhitespaceAfter, WhitespaceAround,
+            AnnotationLocation, AnnotationUseStyle, AnonInnerLength, ArrayTypeStyle,
+            AvoidNestedBlocks, AvoidStarImport, AvoidStaticImport, BooleanExpressionComplexity,
+            ClassTypeParameterName, ConstantName, CovariantEquals, CustomImportOrder,
+            DeclarationOrder, DefaultComesLast, DescendantToken, DesignForExtension, EmptyBlock,
+            EmptyCatchBlock, EmptyForInitializerPad, EmptyLineSeparator, EmptyStatement,
+            EqualsAvoidNull, EqualsHashCode, ExecutableStatementCount, FallThrough, FileLength,
+            FileTabCharacter, FinalClass, FinalLocalVariable, FinalParameters, GenericWhitespace,
+            Header, HiddenField, HideUtilityClassConstructor, IllegalType, ImportOrder,
+            Indentation, InnerAssignment, InterfaceTypeParameterName, JavadocMethod,
+            LambdaBodyLength, LeftCurly, LineLength, LocalFinalVariableName, LocalVariableName,
+            MagicNumber, MemberName, MethodCount, MethodLength, MethodName, MethodParamPad,
+            MethodTypeParameterName, MissingJavadocMethod, MissingOverride, MissingSwitchDefault,
+            ModifierOrder, MultipleVariableDeclarations, MutableException, NPathComplexity,
+            NeedBraces, NestedForDepth, NestedIfDepth, NestedTryDepth, NewlineAtEndOfFile,
+            NoWhitespaceAfter, NoWhitespaceBefore, OneStatementPerLine, OneTopLevelClass,
+            OperatorWrap, OuterTypeFilename, PackageDeclaration, PackageName, ParameterName,
+            ParameterNumber, ParenPad, RedundantImport, RedundantModifier, RegexpHeader,
+            RegexpMultiline, RegexpSingleline, RegexpSinglelineJava, RequireThis, ReturnCount,
+            RightCurly, SeparatorWrap, SimplifyBooleanExpression, SimplifyBooleanReturn,
+            SingleSpaceSeparator, StaticVariableName, StrictDuplicateCode, StringLiteralEquality,
+            TypeName, TypecastParenPad, UnusedImports, UpperEll, VisibilityModifier,
+            WhitespaceAfter, WhitespaceAround,
         };
         // Whitespace rules
         self.register::<WhitespaceAround>();
@@ -75,6 +117,8 @@ impl RuleRegistry {
         self.register::<TypecastParenPad>();
         self.register::<FileTabCharacter>();
         self.register::<OperatorWrap>();
+        self.register::<SeparatorWrap>();
+        self.register::<GenericWhitespace>();
         self.register::<EmptyLineSeparator>();
         self.register::<Indentation>();
         // Block rules
@@ -96,6 +140,10 @@ impl RuleRegistry {
         // Import rules
         self.register::<RedundantImport>();
         self.register::<UnusedImports>();
+        self.register::<ImportOrder>();
+        self.register::<CustomImportOrder>();
+        self.register::<AvoidStarImport>();
+        self.register::<AvoidStaticImport>();
         // Coding rules
         self.register::<OneStatementPerLine>();
         self.register::<MultipleVariableDeclarations>();
@@ -114,16 +162,50 @@ impl RuleRegistry {
         self.register::<HiddenField>();
         self.register::<FallThrough>();
         self.register::<IllegalType>();
+        self.register::<MagicNumber>();
+        self.register::<RequireThis>();
+        self.register::<EqualsAvoidNull>();
+        self.register::<ReturnCount>();
+        self.register::<NestedIfDepth>();
+        self.register::<NestedForDepth>();
         // Regexp rules
         self.register::<RegexpSinglelineJava>();
+        self.register::<RegexpSingleline>();
+        self.register::<RegexpMultiline>();
         // Misc rules
         self.register::<DescendantToken>();
+        self.register::<OuterTypeFilename>();
+        self.register::<NewlineAtEndOfFile>();
         // Design rules
+        self.register::<DesignForExtension>();
         self.register::<HideUtilityClassConstructor>();
         self.register::<MutableException>();
+        self.register::<OneTopLevelClass>();
+        self.register::<VisibilityModifier>();
+        // Header rules
+        self.register::<Header>();
+        self.register::<RegexpHeader>();
+        // Duplicate code rules
+        self.register::<StrictDuplicateCode>();
         // Size rules
         self.register::<LineLength>();
         self.register::<MethodLength>();
+        self.register::<FileLength>();
+        self.register::<ParameterNumber>();
+        self.register::<MethodCount>();
+        self.register::<AnonInnerLength>();
+        self.register::<ExecutableStatementCount>();
+        self.register::<LambdaBodyLength>();
+        // Metrics rules
+        self.register::<NPathComplexity>();
+        self.register::<BooleanExpressionComplexity>();
+        // Javadoc rules
+        self.register::<JavadocMethod>();
+        self.register::<MissingJavadocMethod>();
+        // Annotation rules
+        self.register::<MissingOverride>();
+        self.register::<AnnotationLocation>();
+        self.register::<AnnotationUseStyle>();
         // Naming rules
         self.register::<ConstantName>();
         self.register::<LocalFinalVariableName>();
@@ -134,14 +216,41 @@ impl RuleRegistry {
         self.register::<ParameterName>();
         self.register::<StaticVariableName>();
         self.register::<TypeName>();
+        self.register::<ClassTypeParameterName>();
+        self.register::<InterfaceTypeParameterName>();
+        self.register::<MethodTypeParameterName>();
     }
 
     /// Create a rule from a module name and properties.
-    /// Returns None if the module name is not recognized.
+    /// Returns None if the module name is not recognized, or if a plugin rule
+    /// rejects the properties.
     pub fn create_rule(&self, module_name: &str, properties: &Properties) -> Option<Box<dyn Rule>> {
         self.factories
             .get(module_name)
-            .map(|factory| factory(properties))
+            .and_then(|factory| factory(properties))
+    }
+
+    /// Create a rule from a module name and properties, telling unknown
+    /// rules apart from rejected properties.
+    pub fn try_create_rule(
+        &self,
+        module_name: &str,
+        properties: &Properties,
+    ) -> Result<Box<dyn Rule>, RuleError> {
+        let factory = self
+            .factories
+            .get(module_name)
+            .ok_or_else(|| RuleError::Unknown(module_name.to_string()))?;
+        factory(properties).ok_or_else(|| RuleError::Rejected(module_name.to_string()))
+    }
+
+    /// Create every enabled rule of a configuration, failing on the first one
+    /// that can't be created.
+    pub fn create_rules(&self, config: &MergedConfig) -> Result<Vec<Box<dyn Rule>>, RuleError> {
+        config
+            .enabled_rules()
+            .map(|rule| self.try_create_rule(&rule.name, &rule.properties_ref()))
+            .collect()
     }
 
     /// Check if a module name is registered.
@@ -196,4 +305,55 @@ mod tests {
 
         assert!(rule.is_none());
     }
+
+    #[test]
+    fn test_create_rules_from_config() {
+        let checkstyle = lintal_checkstyle::CheckstyleConfig::parse(
+            r#"<module name="Checker"><module name="TreeWalker">
+                <module name="UpperEll"/>
+                <module name="LineLength"><property name="max" value="100"/></module>
+            </module></module>"#,
+        )
+        .unwrap();
+        let registry = RuleRegistry::builtin();
+
+        let rules = registry
+            .create_rules(&MergedConfig::new(&checkstyle, None))
+            .unwrap();
+        let names: Vec<&str> = rules.iter().map(|rule| rule.name()).collect();
+        assert_eq!(names, ["UpperEll", "LineLength"]);
+
+        assert_eq!(
+            registry
+                .try_create_rule("UnknownRule", &HashMap::new())
+                .err(),
+            Some(RuleError::Unknown("UnknownRule".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_file_level_modules_are_file_rules() {
+        let registry = RuleRegistry::builtin();
+        let props = HashMap::new();
+
+        for name in [
+            "LineLength",
+            "FileLength",
+            "FileTabCharacter",
+            "NewlineAtEndOfFile",
+            "RegexpSingleline",
+            "RegexpMultiline",
+            "Header",
+            "RegexpHeader",
+        ] {
+            let rule = registry.create_rule(name, &props).unwrap();
+            assert!(
+                rule.as_file_rule().is_some(),
+                "{name} should be a file rule"
+            );
+        }
+
+        let rule = registry.create_rule("WhitespaceAround", &props).unwrap();
+        assert!(rule.as_file_rule().is_none());
+    }
 }
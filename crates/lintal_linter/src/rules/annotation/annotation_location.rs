@@ -0,0 +1,463 @@
+//! AnnotationLocation rule implementation.
+//!
+//! Checks that the annotations of a declaration are on their own lines,
+//! indented like the declaration.
+//!
+//! Checkstyle equivalent: AnnotationLocationCheck
+
+use std::collections::HashSet;
+
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+use lintal_source_file::find_newline;
+use lintal_text_size::{TextRange, TextSize};
+
+use super::common::{annotation_name, is_annotation};
+use crate::rules::javadoc::common::parse_list;
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: annotation shares its line with other code.
+#[derive(Debug, Clone)]
+pub struct AnnotationLocationAloneViolation {
+    pub name: String,
+}
+
+impl Violation for AnnotationLocationAloneViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
+
+    fn message(&self) -> String {
+        format!("Annotation '{}' should be alone on line.", self.name)
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("annotation", "annotation.location.alone"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
+}
+
+/// Violation: annotation not indented like its declaration.
+#[derive(Debug, Clone)]
+pub struct AnnotationLocationViolation {
+    pub name: String,
+    pub actual: usize,
+    pub expected: usize,
+}
+
+impl Violation for AnnotationLocationViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
+
+    fn message(&self) -> String {
+        format!(
+            "Annotation '{}' have incorrect indentation level {}, expected level should be {}.",
+            self.name, self.actual, self.expected
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("annotation", "annotation.location"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.actual.to_string(),
+            self.expected.to_string(),
+        ]
+    }
+}
+
+/// Configuration for AnnotationLocation rule.
+#[derive(Debug, Clone)]
+pub struct AnnotationLocation {
+    /// Allow several annotations on the line of the declaration (default:
+    /// false).
+    pub allow_sameline_multiple_annotations: bool,
+    /// Allow a single annotation without arguments on the line of the
+    /// declaration (default: true).
+    pub allow_sameline_single_parameterless_annotation: bool,
+    /// Allow a single annotation with arguments on the line of the
+    /// declaration (default: false).
+    pub allow_sameline_parameterized_annotation: bool,
+    /// Declarations to check: CLASS_DEF, INTERFACE_DEF, PACKAGE_DEF,
+    /// ENUM_CONSTANT_DEF, ENUM_DEF, METHOD_DEF, CTOR_DEF, VARIABLE_DEF,
+    /// RECORD_DEF, COMPACT_CTOR_DEF, PARAMETER_DEF, ANNOTATION_DEF,
+    /// ANNOTATION_FIELD_DEF.
+    pub tokens: HashSet<String>,
+}
+
+const DEFAULT_TOKENS: &[&str] = &[
+    "CLASS_DEF",
+    "INTERFACE_DEF",
+    "PACKAGE_DEF",
+    "ENUM_CONSTANT_DEF",
+    "ENUM_DEF",
+    "METHOD_DEF",
+    "CTOR_DEF",
+    "VARIABLE_DEF",
+    "RECORD_DEF",
+    "COMPACT_CTOR_DEF",
+];
+
+const RELEVANT_KINDS: &[&str] = &[
+    "class_declaration",
+    "interface_declaration",
+    "package_declaration",
+    "enum_constant",
+    "enum_declaration",
+    "method_declaration",
+    "constructor_declaration",
+    "field_declaration",
+    "constant_declaration",
+    "record_declaration",
+    "compact_constructor_declaration",
+    "formal_parameter",
+    "annotation_type_declaration",
+    "annotation_type_element_declaration",
+];
+
+impl Default for AnnotationLocation {
+    fn default() -> Self {
+        Self {
+            allow_sameline_multiple_annotations: false,
+            allow_sameline_single_parameterless_annotation: true,
+            allow_sameline_parameterized_annotation: false,
+            tokens: DEFAULT_TOKENS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl FromConfig for AnnotationLocation {
+    const MODULE_NAME: &'static str = "AnnotationLocation";
+
+    fn from_config(properties: &Properties) -> Self {
+        let default = Self::default();
+        let flag = |name: &str, default: bool| {
+            properties
+                .get(name)
+                .map_or(default, |v| v.trim().eq_ignore_ascii_case("true"))
+        };
+
+        Self {
+            allow_sameline_multiple_annotations: flag(
+                "allowSamelineMultipleAnnotations",
+                default.allow_sameline_multiple_annotations,
+            ),
+            allow_sameline_single_parameterless_annotation: flag(
+                "allowSamelineSingleParameterlessAnnotation",
+                default.allow_sameline_single_parameterless_annotation,
+            ),
+            allow_sameline_parameterized_annotation: flag(
+                "allowSamelineParameterizedAnnotation",
+                default.allow_sameline_parameterized_annotation,
+            ),
+            tokens: properties
+                .get("tokens")
+                .map(|v| parse_list(v).into_iter().collect())
+                .unwrap_or(default.tokens),
+        }
+    }
+}
+
+impl Rule for AnnotationLocation {
+    fn name(&self) -> &'static str {
+        "AnnotationLocation"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        let Some(token) = declaration_token(node.kind()) else {
+            return vec![];
+        };
+        if !self.tokens.contains(token) {
+            return vec![];
+        }
+
+        // A package's annotations are its own children, other declarations
+        // hold them in their modifiers
+        let list = if node.kind() == "package_declaration" {
+            *node
+        } else {
+            match node.children().find(|child| child.kind() == "modifiers") {
+                Some(modifiers) => modifiers,
+                None => return vec![],
+            }
+        };
+        let items: Vec<CstNode> = code_children(&list).collect();
+        let Some(first) = items.first() else {
+            return vec![];
+        };
+
+        let source_code = ctx.source_code();
+        let line = |offset: TextSize| source_code.line_column(offset).line;
+        let expected = source_code
+            .line_column(first.range().start())
+            .column
+            .to_zero_indexed();
+        let indent = indentation(ctx, first.range().start());
+        let newline = find_newline(ctx.source()).map_or("\n", |(_, ending)| ending.as_str());
+        let line_break = format!("{newline}{indent}");
+
+        let mut diagnostics = vec![];
+        // Like checkstyle, annotations after a keyword modifier are left alone
+        for (index, annotation) in items
+            .iter()
+            .enumerate()
+            .take_while(|(_, item)| is_annotation(item))
+        {
+            let start = annotation.range().start();
+            let before = index
+                .checked_sub(1)
+                .map(|previous| items[previous])
+                .filter(|previous| line(previous.range().start()) == line(start));
+            let after = items
+                .get(index + 1)
+                .copied()
+                .or_else(|| following(&list))
+                .filter(|next| line(next.range().start()) == line(start));
+
+            let allowed = if is_parameterized(annotation) {
+                self.allow_sameline_parameterized_annotation
+            } else {
+                self.allow_sameline_single_parameterless_annotation
+            };
+            let name = annotation_name(annotation).to_string();
+            if !(self.allow_sameline_multiple_annotations
+                || allowed && before.is_none()
+                || before.is_none() && after.is_none())
+            {
+                // Break the line on both sides, where only whitespace
+                // separates the annotation from its neighbors
+                let mut edits = vec![];
+                if let Some(previous) = before {
+                    edits.push(TextRange::new(previous.range().end(), start));
+                }
+                if let Some(next) = after {
+                    edits.push(TextRange::new(
+                        annotation.range().end(),
+                        next.range().start(),
+                    ));
+                }
+                let fix = edits
+                    .iter()
+                    .all(|&gap| ctx.source()[gap].trim().is_empty())
+                    .then(|| {
+                        let mut edits = edits
+                            .into_iter()
+                            .map(|gap| Edit::range_replacement(line_break.clone(), gap));
+                        Fix::safe_edits(edits.next().unwrap(), edits)
+                    });
+
+                let diagnostic = Diagnostic::new(
+                    AnnotationLocationAloneViolation { name },
+                    annotation.range(),
+                );
+                diagnostics.push(match fix {
+                    Some(fix) => diagnostic.with_fix(fix),
+                    None => diagnostic,
+                });
+                continue;
+            }
+
+            let actual = source_code.line_column(start).column.to_zero_indexed();
+            if actual != expected && before.is_none() {
+                let line_start = ctx.line_index().line_start(line(start), ctx.source());
+                let leading = TextRange::new(line_start, start);
+                let diagnostic = Diagnostic::new(
+                    AnnotationLocationViolation {
+                        name,
+                        actual,
+                        expected,
+                    },
+                    annotation.range(),
+                );
+                // Re-indent only an annotation that starts its line
+                diagnostics.push(if ctx.source()[leading].trim().is_empty() {
+                    diagnostic.with_fix(Fix::safe_edit(Edit::range_replacement(
+                        indent.clone(),
+                        leading,
+                    )))
+                } else {
+                    diagnostic
+                });
+            }
+        }
+        diagnostics
+    }
+}
+
+/// The checkstyle token of a declaration whose annotations are checked.
+fn declaration_token(kind: &str) -> Option<&'static str> {
+    match kind {
+        "class_declaration" => Some("CLASS_DEF"),
+        "interface_declaration" => Some("INTERFACE_DEF"),
+        "package_declaration" => Some("PACKAGE_DEF"),
+        "enum_constant" => Some("ENUM_CONSTANT_DEF"),
+        "enum_declaration" => Some("ENUM_DEF"),
+        "method_declaration" => Some("METHOD_DEF"),
+        "constructor_declaration" => Some("CTOR_DEF"),
+        // Fields only, as in checkstyle
+        "field_declaration" | "constant_declaration" => Some("VARIABLE_DEF"),
+        "record_declaration" => Some("RECORD_DEF"),
+        "compact_constructor_declaration" => Some("COMPACT_CTOR_DEF"),
+        "formal_parameter" => Some("PARAMETER_DEF"),
+        "annotation_type_declaration" => Some("ANNOTATION_DEF"),
+        "annotation_type_element_declaration" => Some("ANNOTATION_FIELD_DEF"),
+        _ => None,
+    }
+}
+
+/// The children of `node` other than comments.
+fn code_children<'a>(node: &CstNode<'a>) -> impl Iterator<Item = CstNode<'a>> + 'a {
+    node.children()
+        .filter(|child| !matches!(child.kind(), "line_comment" | "block_comment"))
+}
+
+/// The sibling after `node`, leaving out comments.
+fn following<'a>(node: &CstNode<'a>) -> Option<CstNode<'a>> {
+    let parent = node.parent()?;
+    code_children(&parent)
+        .skip_while(|sibling| sibling.range() != node.range())
+        .nth(1)
+}
+
+/// Whether the annotation has element values; `@Foo()` has none.
+fn is_parameterized(annotation: &CstNode) -> bool {
+    annotation
+        .child_by_field_name("arguments")
+        .is_some_and(|arguments| {
+            arguments
+                .named_children()
+                .any(|child| !matches!(child.kind(), "line_comment" | "block_comment"))
+        })
+}
+
+/// The whitespace that indents the line of `offset` up to it, or spaces as
+/// wide as that if something else comes first.
+fn indentation(ctx: &CheckContext, offset: TextSize) -> String {
+    let source_code = ctx.source_code();
+    let location = source_code.line_column(offset);
+    let line_start = ctx.line_index().line_start(location.line, ctx.source());
+    let leading = &ctx.source()[TextRange::new(line_start, offset)];
+    if leading.trim().is_empty() {
+        leading.to_string()
+    } else {
+        " ".repeat(location.column.to_zero_indexed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::apply_fixes;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, rule: &AnnotationLocation) -> Vec<Diagnostic> {
+        let result = JavaParser::new().parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        TreeWalker::new(result.tree.root_node(), source)
+            .flat_map(|node| rule.check(&ctx, &node))
+            .collect()
+    }
+
+    fn messages(source: &str, rule: &AnnotationLocation) -> Vec<String> {
+        check_source(source, rule)
+            .into_iter()
+            .map(|d| d.kind.body)
+            .collect()
+    }
+
+    #[test]
+    fn test_annotation_alone_on_line() {
+        let source = r#"
+class Foo {
+    @Override public String toString() {
+        return "";
+    }
+
+    @Deprecated @Override
+    public int hashCode() {
+        return 0;
+    }
+
+    @SuppressWarnings("unchecked") void run() {
+    }
+
+    @Deprecated
+    public @Nullable String name;
+}
+"#;
+        assert_eq!(
+            messages(source, &AnnotationLocation::default()),
+            [
+                "Annotation 'Override' should be alone on line.",
+                "Annotation 'SuppressWarnings' should be alone on line."
+            ]
+        );
+
+        let rule = AnnotationLocation {
+            allow_sameline_multiple_annotations: true,
+            ..AnnotationLocation::default()
+        };
+        assert!(messages(source, &rule).is_empty());
+    }
+
+    #[test]
+    fn test_fix_moves_annotations_onto_their_own_lines() {
+        let source = r#"
+class Foo {
+    @Deprecated @SuppressWarnings("all") void run() {
+    }
+}
+"#;
+        let diagnostics = check_source(source, &AnnotationLocation::default());
+        let result = apply_fixes(source, diagnostics.iter().filter_map(|d| d.fix.as_ref()));
+        assert_eq!(
+            result.code,
+            r#"
+class Foo {
+    @Deprecated
+    @SuppressWarnings("all")
+    void run() {
+    }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_fix_keeps_crlf() {
+        let source = "class Foo {\r\n    @Deprecated @SuppressWarnings(\"all\") void run() {\r\n    }\r\n}\r\n";
+        let diagnostics = check_source(source, &AnnotationLocation::default());
+        let result = apply_fixes(source, diagnostics.iter().filter_map(|d| d.fix.as_ref()));
+        assert_eq!(
+            result.code,
+            "class Foo {\r\n    @Deprecated\r\n    @SuppressWarnings(\"all\")\r\n    void run() {\r\n    }\r\n}\r\n"
+        );
+    }
+
+    #[test]
+    fn test_annotation_indentation() {
+        let source = r#"
+class Foo {
+    @Deprecated
+  @SuppressWarnings("all")
+    void run() {
+    }
+}
+"#;
+        let diagnostics = check_source(source, &AnnotationLocation::default());
+        assert_eq!(
+            diagnostics[0].kind.body,
+            "Annotation 'SuppressWarnings' have incorrect indentation level 2, expected level should be 4."
+        );
+        let result = apply_fixes(source, diagnostics.iter().filter_map(|d| d.fix.as_ref()));
+        assert_eq!(result.code, source.replace("\n  @", "\n    @"));
+    }
+}
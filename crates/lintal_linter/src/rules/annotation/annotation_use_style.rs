@@ -0,0 +1,469 @@
+//! AnnotationUseStyle rule implementation.
+//!
+//! Checks the style of annotation arguments: how element values are
+//! written, parentheses on annotations without arguments, and trailing
+//! commas in array values.
+//!
+//! Checkstyle equivalent: AnnotationUseStyleCheck
+
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+
+use super::common::is_annotation;
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// How element values are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ElementStyleOption {
+    /// Every value is named: `@Foo(value = "x")`.
+    Expanded,
+    /// A lone `value` element is unnamed: `@Foo("x")`.
+    Compact,
+    /// Like compact, with a lone array value unwrapped: `@Foo("x")`
+    /// rather than `@Foo({"x"})`.
+    #[default]
+    CompactNoArray,
+    /// Element values aren't checked.
+    Ignore,
+}
+
+impl ElementStyleOption {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Expanded => "EXPANDED",
+            Self::Compact => "COMPACT",
+            Self::CompactNoArray => "COMPACT_NO_ARRAY",
+            Self::Ignore => "IGNORE",
+        }
+    }
+}
+
+/// Whether annotations without arguments have parentheses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClosingParensOption {
+    /// `@Foo()`
+    Always,
+    /// `@Foo`
+    #[default]
+    Never,
+    /// Parentheses aren't checked.
+    Ignore,
+}
+
+/// Whether array values end with a comma.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrailingArrayCommaOption {
+    /// `{"a", "b",}`
+    Always,
+    /// `{"a", "b"}`
+    #[default]
+    Never,
+    /// Trailing commas aren't checked.
+    Ignore,
+}
+
+/// Violation: element values not written in the configured style.
+#[derive(Debug, Clone)]
+pub struct AnnotationIncorrectStyleViolation {
+    pub style: ElementStyleOption,
+}
+
+impl Violation for AnnotationIncorrectStyleViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
+
+    fn message(&self) -> String {
+        format!("Annotation style must be '{}'.", self.style.name())
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("annotation", "annotation.incorrect.style"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.style.name().to_string()]
+    }
+}
+
+/// Violation: parentheses missing or present on an annotation without
+/// arguments.
+#[derive(Debug, Clone)]
+pub struct AnnotationParensViolation {
+    /// Whether the parentheses are missing rather than present.
+    pub missing: bool,
+}
+
+impl Violation for AnnotationParensViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Always;
+
+    fn message(&self) -> String {
+        if self.missing {
+            "Annotation must have parentheses.".to_string()
+        } else {
+            "Annotation cannot have parentheses.".to_string()
+        }
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        let key = if self.missing {
+            "annotation.parens.missing"
+        } else {
+            "annotation.parens.present"
+        };
+        Some(MessageKey::new("annotation", key))
+    }
+}
+
+/// Violation: trailing comma missing or present in an array value.
+#[derive(Debug, Clone)]
+pub struct AnnotationTrailingCommaViolation {
+    /// Whether the comma is missing rather than present.
+    pub missing: bool,
+}
+
+impl Violation for AnnotationTrailingCommaViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Always;
+
+    fn message(&self) -> String {
+        if self.missing {
+            "Annotation array values must contain trailing comma.".to_string()
+        } else {
+            "Annotation array values cannot contain trailing comma.".to_string()
+        }
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        let key = if self.missing {
+            "annotation.trailing.comma.missing"
+        } else {
+            "annotation.trailing.comma.present"
+        };
+        Some(MessageKey::new("annotation", key))
+    }
+}
+
+/// Configuration for AnnotationUseStyle rule.
+#[derive(Debug, Clone, Default)]
+pub struct AnnotationUseStyle {
+    /// How element values are written (default: COMPACT_NO_ARRAY).
+    pub element_style: ElementStyleOption,
+    /// Parentheses on annotations without arguments (default: NEVER).
+    pub closing_parens: ClosingParensOption,
+    /// Trailing commas in array values (default: NEVER).
+    pub trailing_array_comma: TrailingArrayCommaOption,
+}
+
+const RELEVANT_KINDS: &[&str] = &["annotation", "marker_annotation"];
+
+impl FromConfig for AnnotationUseStyle {
+    const MODULE_NAME: &'static str = "AnnotationUseStyle";
+
+    fn from_config(properties: &Properties) -> Self {
+        let element_style = properties
+            .get("elementStyle")
+            .map(|v| match v.trim().to_uppercase().as_str() {
+                "EXPANDED" => ElementStyleOption::Expanded,
+                "COMPACT" => ElementStyleOption::Compact,
+                "IGNORE" => ElementStyleOption::Ignore,
+                _ => ElementStyleOption::CompactNoArray,
+            })
+            .unwrap_or_default();
+        let closing_parens = properties
+            .get("closingParens")
+            .map(|v| match v.trim().to_uppercase().as_str() {
+                "ALWAYS" => ClosingParensOption::Always,
+                "IGNORE" => ClosingParensOption::Ignore,
+                _ => ClosingParensOption::Never,
+            })
+            .unwrap_or_default();
+        let trailing_array_comma = properties
+            .get("trailingArrayComma")
+            .map(|v| match v.trim().to_uppercase().as_str() {
+                "ALWAYS" => TrailingArrayCommaOption::Always,
+                "IGNORE" => TrailingArrayCommaOption::Ignore,
+                _ => TrailingArrayCommaOption::Never,
+            })
+            .unwrap_or_default();
+
+        Self {
+            element_style,
+            closing_parens,
+            trailing_array_comma,
+        }
+    }
+}
+
+impl Rule for AnnotationUseStyle {
+    fn name(&self) -> &'static str {
+        "AnnotationUseStyle"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        if !is_annotation(node) {
+            return vec![];
+        }
+        let arguments = node.child_by_field_name("arguments");
+        let values: Vec<CstNode> = arguments.iter().flat_map(code_values).collect();
+
+        let mut diagnostics = vec![];
+        if let Some(diagnostic) = self.check_element_style(node, &values) {
+            diagnostics.push(diagnostic);
+        }
+
+        match (self.closing_parens, arguments) {
+            (ClosingParensOption::Never, Some(arguments)) if values.is_empty() => {
+                let fix = Fix::safe_edit(Edit::range_deletion(arguments.range()));
+                diagnostics.push(
+                    Diagnostic::new(AnnotationParensViolation { missing: false }, node.range())
+                        .with_fix(fix),
+                );
+            }
+            (ClosingParensOption::Always, None) => {
+                let fix = Fix::safe_edit(Edit::insertion("()".to_string(), node.range().end()));
+                diagnostics.push(
+                    Diagnostic::new(AnnotationParensViolation { missing: true }, node.range())
+                        .with_fix(fix),
+                );
+            }
+            _ => {}
+        }
+
+        if self.trailing_array_comma != TrailingArrayCommaOption::Ignore {
+            // Arrays in nested annotations are checked with those
+            let arrays = values.iter().filter_map(|value| match value.kind() {
+                "element_value_array_initializer" => Some(*value),
+                "element_value_pair" => value
+                    .child_by_field_name("value")
+                    .filter(|value| value.kind() == "element_value_array_initializer"),
+                _ => None,
+            });
+            for array in arrays {
+                diagnostics.extend(self.check_trailing_comma(&array));
+            }
+        }
+        diagnostics
+    }
+}
+
+impl AnnotationUseStyle {
+    fn check_element_style(&self, annotation: &CstNode, values: &[CstNode]) -> Option<Diagnostic> {
+        let style = self.element_style;
+        let pairs: Vec<&CstNode> = values
+            .iter()
+            .filter(|value| value.kind() == "element_value_pair")
+            .collect();
+        let lone_value_pair = match pairs[..] {
+            [pair]
+                if pair
+                    .child_by_field_name("key")
+                    .is_some_and(|key| key.text() == "value") =>
+            {
+                Some(pair)
+            }
+            _ => None,
+        };
+
+        let fix = match style {
+            // `@Foo("x")` names its value as `@Foo(value = "x")`
+            ElementStyleOption::Expanded => {
+                if !pairs.is_empty() || values.is_empty() {
+                    return None;
+                }
+                let value = values[0];
+                Some(Edit::insertion(
+                    "value = ".to_string(),
+                    value.range().start(),
+                ))
+            }
+            // `@Foo(value = "x")` drops the name as `@Foo("x")`
+            ElementStyleOption::Compact => {
+                let pair = lone_value_pair?;
+                let value = pair.child_by_field_name("value")?;
+                Some(Edit::range_replacement(
+                    value.text().to_string(),
+                    pair.range(),
+                ))
+            }
+            // `@Foo({"x"})` and `@Foo(value = {"x"})` unwrap the array
+            ElementStyleOption::CompactNoArray => {
+                let array = match values {
+                    [value] if value.kind() == "element_value_array_initializer" => *value,
+                    _ => lone_value_pair?
+                        .child_by_field_name("value")
+                        .filter(|value| value.kind() == "element_value_array_initializer")?,
+                };
+                let elements: Vec<CstNode> = code_values(&array).collect();
+                // Like checkstyle, only expressions count, not annotations
+                let expressions = elements.iter().filter(|e| !is_annotation(e)).count();
+                if expressions != 1 {
+                    return None;
+                }
+                match elements[..] {
+                    [element] => Some(Edit::range_replacement(
+                        element.text().to_string(),
+                        array.range(),
+                    )),
+                    _ => None,
+                }
+            }
+            ElementStyleOption::Ignore => return None,
+        };
+
+        let diagnostic = Diagnostic::new(
+            AnnotationIncorrectStyleViolation { style },
+            annotation.range(),
+        );
+        Some(match fix {
+            Some(edit) => diagnostic.with_fix(Fix::safe_edit(edit)),
+            None => diagnostic,
+        })
+    }
+
+    fn check_trailing_comma(&self, array: &CstNode) -> Option<Diagnostic> {
+        let tokens: Vec<CstNode> = array
+            .children()
+            .filter(|child| !matches!(child.kind(), "line_comment" | "block_comment"))
+            .collect();
+        let [.., last, closing] = tokens[..] else {
+            return None;
+        };
+
+        match self.trailing_array_comma {
+            TrailingArrayCommaOption::Always if last.kind() != "," => {
+                let fix = Fix::safe_edit(Edit::insertion(",".to_string(), last.range().end()));
+                Some(
+                    Diagnostic::new(
+                        AnnotationTrailingCommaViolation { missing: true },
+                        closing.range(),
+                    )
+                    .with_fix(fix),
+                )
+            }
+            TrailingArrayCommaOption::Never if last.kind() == "," => {
+                let fix = Fix::safe_edit(Edit::range_deletion(last.range()));
+                Some(
+                    Diagnostic::new(
+                        AnnotationTrailingCommaViolation { missing: false },
+                        last.range(),
+                    )
+                    .with_fix(fix),
+                )
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The element values or value pairs under `node`, leaving out comments.
+fn code_values<'a>(node: &CstNode<'a>) -> impl Iterator<Item = CstNode<'a>> + 'a {
+    node.named_children()
+        .filter(|child| !matches!(child.kind(), "line_comment" | "block_comment"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::apply_fixes;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, rule: &AnnotationUseStyle) -> Vec<Diagnostic> {
+        let result = JavaParser::new().parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        TreeWalker::new(result.tree.root_node(), source)
+            .flat_map(|node| rule.check(&ctx, &node))
+            .collect()
+    }
+
+    fn fixed(source: &str, rule: &AnnotationUseStyle) -> String {
+        let diagnostics = check_source(source, rule);
+        apply_fixes(source, diagnostics.iter().filter_map(|d| d.fix.as_ref())).code
+    }
+
+    #[test]
+    fn test_compact_no_array() {
+        let source = r#"
+@SuppressWarnings({"unchecked"})
+@Deprecated()
+class Foo {
+    @SuppressWarnings(value = {"rawtypes"})
+    @Retention(RetentionPolicy.RUNTIME)
+    void run() {
+    }
+}
+"#;
+        let rule = AnnotationUseStyle::default();
+        let messages: Vec<String> = check_source(source, &rule)
+            .into_iter()
+            .map(|d| d.kind.body)
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "Annotation style must be 'COMPACT_NO_ARRAY'.",
+                "Annotation cannot have parentheses.",
+                "Annotation style must be 'COMPACT_NO_ARRAY'."
+            ]
+        );
+        assert_eq!(
+            fixed(source, &rule),
+            r#"
+@SuppressWarnings("unchecked")
+@Deprecated
+class Foo {
+    @SuppressWarnings(value = "rawtypes")
+    @Retention(RetentionPolicy.RUNTIME)
+    void run() {
+    }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_compact_and_expanded() {
+        let source = r#"@Foo(value = "x") @Bar("y") class Foo {}"#;
+        let compact = AnnotationUseStyle {
+            element_style: ElementStyleOption::Compact,
+            ..AnnotationUseStyle::default()
+        };
+        assert_eq!(
+            fixed(source, &compact),
+            r#"@Foo("x") @Bar("y") class Foo {}"#
+        );
+
+        let expanded = AnnotationUseStyle {
+            element_style: ElementStyleOption::Expanded,
+            ..AnnotationUseStyle::default()
+        };
+        assert_eq!(
+            fixed(source, &expanded),
+            r#"@Foo(value = "x") @Bar(value = "y") class Foo {}"#
+        );
+    }
+
+    #[test]
+    fn test_closing_parens_and_trailing_comma() {
+        let source = r#"@Deprecated @Foo(names = {"a", "b",}) class Foo {}"#;
+        assert_eq!(
+            fixed(source, &AnnotationUseStyle::default()),
+            r#"@Deprecated @Foo(names = {"a", "b"}) class Foo {}"#
+        );
+
+        let rule = AnnotationUseStyle {
+            closing_parens: ClosingParensOption::Always,
+            trailing_array_comma: TrailingArrayCommaOption::Always,
+            ..AnnotationUseStyle::default()
+        };
+        let source = r#"@Deprecated @Foo(names = {"a", "b"}) class Foo {}"#;
+        let diagnostics = check_source(source, &rule);
+        assert_eq!(&source[diagnostics[1].range], "}");
+        assert_eq!(
+            fixed(source, &rule),
+            r#"@Deprecated() @Foo(names = {"a", "b",}) class Foo {}"#
+        );
+    }
+}
@@ -0,0 +1,21 @@
+//! Shared helpers for annotation rules.
+
+use lintal_java_cst::CstNode;
+
+/// Whether `node` is an annotation, with or without arguments.
+pub fn is_annotation(node: &CstNode) -> bool {
+    matches!(node.kind(), "annotation" | "marker_annotation")
+}
+
+/// The simple name of an annotation, e.g. `Override` for
+/// `@java.lang.Override`.
+pub fn annotation_name<'a>(annotation: &CstNode<'a>) -> &'a str {
+    let Some(name) = annotation.child_by_field_name("name") else {
+        return "";
+    };
+    match name.child_by_field_name("name") {
+        // A scoped identifier ends with the simple name
+        Some(last) => last.text(),
+        None => name.text(),
+    }
+}
@@ -0,0 +1,212 @@
+//! MissingOverride rule implementation.
+//!
+//! Checks that methods documented with `{@inheritDoc}` are annotated with
+//! `@Override`.
+//!
+//! Checkstyle equivalent: MissingOverrideCheck
+
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+use lintal_source_file::find_newline;
+
+use crate::rules::javadoc::common::{declaration_start, javadoc_before};
+use crate::rules::modifier::common::has_modifier;
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: `{@inheritDoc}` without `@Override`.
+#[derive(Debug, Clone)]
+pub struct MissingOverrideViolation;
+
+impl Violation for MissingOverrideViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Always;
+
+    fn message(&self) -> String {
+        "Must include @java.lang.Override annotation when '{@inheritDoc}' javadoc tag exists."
+            .to_string()
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("annotation", "annotation.missing.override"))
+    }
+}
+
+/// Violation: `{@inheritDoc}` on a method that can't override anything.
+#[derive(Debug, Clone)]
+pub struct InheritDocNotValidViolation;
+
+impl Violation for InheritDocNotValidViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        "The Javadoc {@inheritDoc} tag is not valid at this location.".to_string()
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("annotation", "tag.not.valid.on"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec!["{@inheritDoc}".to_string()]
+    }
+}
+
+/// Configuration for MissingOverride rule.
+#[derive(Debug, Clone, Default)]
+pub struct MissingOverride {
+    /// Only check methods that can override nothing but `Object`'s, in
+    /// types without `extends` or `implements` (default: false). Java 5
+    /// didn't allow `@Override` on interface methods.
+    pub java_five_compatibility: bool,
+}
+
+const RELEVANT_KINDS: &[&str] = &["method_declaration"];
+
+impl FromConfig for MissingOverride {
+    const MODULE_NAME: &'static str = "MissingOverride";
+
+    fn from_config(properties: &Properties) -> Self {
+        let java_five_compatibility = properties
+            .get("javaFiveCompatibility")
+            .is_some_and(|v| *v == "true");
+
+        Self {
+            java_five_compatibility,
+        }
+    }
+}
+
+impl Rule for MissingOverride {
+    fn name(&self) -> &'static str {
+        "MissingOverride"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        if node.kind() != "method_declaration" {
+            return vec![];
+        }
+        let inherits_doc = javadoc_before(ctx, node)
+            .is_some_and(|javadoc| javadoc.tags().iter().any(|tag| tag.is_inherit_doc()));
+        if !inherits_doc {
+            return vec![];
+        }
+
+        let modifiers = node.children().find(|child| child.kind() == "modifiers");
+        let is_modified = |modifier| {
+            modifiers
+                .as_ref()
+                .is_some_and(|modifiers| has_modifier(modifiers, modifier))
+        };
+        if is_modified("static") || is_modified("private") {
+            return vec![Diagnostic::new(InheritDocNotValidViolation, node.range())];
+        }
+
+        if self.java_five_compatibility && overrides_more_than_object(node) {
+            return vec![];
+        }
+        let has_override = modifiers
+            .iter()
+            .flat_map(|modifiers| modifiers.children())
+            .filter(|child| matches!(child.kind(), "annotation" | "marker_annotation"))
+            .filter_map(|annotation| annotation.child_by_field_name("name"))
+            .any(|name| matches!(name.text(), "Override" | "java.lang.Override"));
+        if has_override {
+            return vec![];
+        }
+
+        // Put the annotation on its own line above the method, indented
+        // like it, unless something else starts the line
+        let start = declaration_start(node);
+        let line = ctx.source_code().line_index(start);
+        let line_start = ctx.line_index().line_start(line, ctx.source());
+        let indent = &ctx.source()[usize::from(line_start)..usize::from(start)];
+        let annotation = if indent.trim().is_empty() {
+            let newline = find_newline(ctx.source()).map_or("\n", |(_, ending)| ending.as_str());
+            format!("@Override{newline}{indent}")
+        } else {
+            "@Override ".to_string()
+        };
+        let fix = Fix::safe_edit(Edit::insertion(annotation, start));
+        vec![Diagnostic::new(MissingOverrideViolation, node.range()).with_fix(fix)]
+    }
+}
+
+/// Whether the type declaring `method` may inherit methods from something
+/// other than `Object`: it extends or implements a type, or is anonymous.
+fn overrides_more_than_object(method: &CstNode) -> bool {
+    let Some(declaration) = method.parent().and_then(|body| body.parent()) else {
+        return false;
+    };
+    declaration.kind() == "object_creation_expression"
+        || declaration.children().any(|child| {
+            matches!(
+                child.kind(),
+                "superclass" | "super_interfaces" | "extends_interfaces"
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, rule: &MissingOverride) -> Vec<Diagnostic> {
+        let result = JavaParser::new().parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        TreeWalker::new(result.tree.root_node(), source)
+            .flat_map(|node| rule.check(&ctx, &node))
+            .collect()
+    }
+
+    #[test]
+    fn test_inherit_doc_without_override() {
+        let source = r#"
+class Foo extends Bar {
+    /** {@inheritDoc} */
+    public String toString() {
+        return "";
+    }
+
+    /** {@inheritDoc} */
+    @Override
+    public int hashCode() {
+        return 0;
+    }
+
+    /** {@inheritDoc} */
+    private static void helper() {
+    }
+}
+"#;
+        let diagnostics = check_source(source, &MissingOverride::default());
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.kind.body.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Must include @java.lang.Override annotation when '{@inheritDoc}' javadoc tag exists.",
+                "The Javadoc {@inheritDoc} tag is not valid at this location."
+            ]
+        );
+        let fix = diagnostics[0].fix.as_ref().unwrap();
+        assert_eq!(fix.edits()[0].content(), Some("@Override\n    "));
+
+        // Only toString could override Object, but Foo also extends Bar
+        let rule = MissingOverride {
+            java_five_compatibility: true,
+        };
+        assert_eq!(check_source(source, &rule).len(), 1);
+    }
+
+    #[test]
+    fn test_fix_keeps_crlf() {
+        let source = "class Foo {\r\n    /** {@inheritDoc} */\r\n    public String toString() {\r\n        return \"\";\r\n    }\r\n}\r\n";
+        let diagnostics = check_source(source, &MissingOverride::default());
+        let fix = diagnostics[0].fix.as_ref().unwrap();
+        assert_eq!(fix.edits()[0].content(), Some("@Override\r\n    "));
+    }
+}
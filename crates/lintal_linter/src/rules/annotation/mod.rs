@@ -0,0 +1,10 @@
+//! Annotation rules (MissingOverride, AnnotationLocation, etc.)
+
+mod annotation_location;
+mod annotation_use_style;
+mod common;
+mod missing_override;
+
+pub use annotation_location::AnnotationLocation;
+pub use annotation_use_style::AnnotationUseStyle;
+pub use missing_override::MissingOverride;
@@ -3,7 +3,7 @@
 //! Finds nested blocks (blocks that are used freely in the code).
 //! This is a port of the checkstyle AvoidNestedBlocksCheck for 100% compatibility.
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::TextRange;
 
@@ -43,6 +43,10 @@ impl Violation for NestedBlock {
     fn message(&self) -> String {
         "Avoid nested blocks.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("blocks", "block.nested"))
+    }
 }
 
 impl Rule for AvoidNestedBlocks {
@@ -3,7 +3,7 @@
 //! Checks for empty blocks.
 //! This is a port of the checkstyle EmptyBlockCheck for 100% compatibility.
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::TextRange;
 
@@ -71,6 +71,10 @@ impl Violation for EmptyBlockNoStatement {
     fn message(&self) -> String {
         "Must have at least one statement.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("blocks", "block.noStatement"))
+    }
 }
 
 /// Violation for empty block with no text.
@@ -85,6 +89,14 @@ impl Violation for EmptyBlockNoText {
     fn message(&self) -> String {
         format!("Empty {} block.", self.block_type)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("blocks", "block.empty"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.block_type.clone()]
+    }
 }
 
 impl Rule for EmptyBlock {
@@ -3,7 +3,7 @@
 //! Checks for empty catch blocks.
 //! This is a port of the checkstyle EmptyCatchBlockCheck for 100% compatibility.
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use regex::Regex;
 
@@ -60,6 +60,10 @@ impl Violation for EmptyCatchBlockViolation {
     fn message(&self) -> String {
         "Empty catch block.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("blocks", "catch.block.empty"))
+    }
 }
 
 impl Rule for EmptyCatchBlock {
@@ -3,7 +3,7 @@
 //! Checks the placement of left curly braces ('{') for code blocks.
 //! This is a port of the checkstyle LeftCurlyCheck for 100% compatibility.
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::{TextRange, TextSize};
 
@@ -112,6 +112,14 @@ impl Violation for LeftCurlyShouldBeOnNewLine {
     fn message(&self) -> String {
         format!("'{{' at column {} should be on a new line", self.column)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("blocks", "line.new"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec!["{".to_string(), self.column.to_string()]
+    }
 }
 
 /// Violation for left curly should be on the previous line.
@@ -129,6 +137,14 @@ impl Violation for LeftCurlyShouldBeOnPreviousLine {
             self.column
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("blocks", "line.previous"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec!["{".to_string(), self.column.to_string()]
+    }
 }
 
 /// Violation for left curly should have line break after.
@@ -146,6 +162,14 @@ impl Violation for LeftCurlyShouldHaveLineBreakAfter {
             self.column
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("blocks", "line.break.after"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec!["{".to_string(), self.column.to_string()]
+    }
 }
 
 impl Rule for LeftCurly {
@@ -214,8 +238,8 @@ impl LeftCurly {
     ///
     /// Note: We return None for patterns like `{ }` or `{  }` because:
     /// 1. These patterns often have SingleSpaceSeparator violations too
-    /// 2. Our fix would conflict with SingleSpaceSeparator's fix
-    /// 3. The fixer currently doesn't properly skip ALL edits from a fix when one overlaps
+    /// 2. Our fix would conflict with SingleSpaceSeparator's fix, and whichever
+    ///    fix is skipped only gets another chance on the next fix pass
     fn parse_trailing_content(after: &str) -> Option<String> {
         let trimmed = after.trim_end();
         if trimmed.is_empty() {
@@ -3,7 +3,7 @@
 //! Checks for braces around code blocks.
 //! This is a port of the checkstyle NeedBracesCheck for 100% compatibility.
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 
 use crate::{CheckContext, FromConfig, Properties, Rule};
@@ -56,6 +56,14 @@ impl Violation for NeedBracesViolation {
     fn message(&self) -> String {
         format!("'{}' construct must use '{{}}'s", self.construct)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("blocks", "needBraces"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.construct.clone()]
+    }
 }
 
 impl Rule for NeedBraces {
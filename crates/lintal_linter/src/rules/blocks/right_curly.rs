@@ -3,7 +3,7 @@
 //! Checks the placement of right curly braces ('}') for code blocks.
 //! This is a port of the checkstyle RightCurlyCheck for 100% compatibility.
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::{TextRange, TextSize};
 
@@ -181,6 +181,14 @@ impl Violation for RightCurlyShouldBeSameLine {
             self.column
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("blocks", "line.same"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec!["}".to_string(), self.column.to_string()]
+    }
 }
 
 /// Violation for right curly should be alone on line.
@@ -195,6 +203,14 @@ impl Violation for RightCurlyShouldBeAlone {
     fn message(&self) -> String {
         format!("'}}' at column {} should be alone on a line", self.column)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("blocks", "line.alone"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec!["}".to_string(), self.column.to_string()]
+    }
 }
 
 /// Violation for right curly should have line break before.
@@ -212,6 +228,14 @@ impl Violation for RightCurlyShouldHaveLineBreakBefore {
             self.column
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("blocks", "line.break.before"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec!["}".to_string(), self.column.to_string()]
+    }
 }
 
 impl Rule for RightCurly {
@@ -0,0 +1,17 @@
+//! Shared helpers for coding rules.
+
+use lintal_java_cst::CstNode;
+
+/// How many ancestors of `node` open a nesting level, as decided by
+/// `is_level`.
+pub fn nesting_depth(node: &CstNode, is_level: impl Fn(&CstNode) -> bool) -> usize {
+    let mut depth = 0;
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if is_level(&parent) {
+            depth += 1;
+        }
+        current = parent.parent();
+    }
+    depth
+}
@@ -5,7 +5,7 @@
 //!
 //! Checkstyle equivalent: CovariantEqualsCheck
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 
 use crate::{CheckContext, FromConfig, Properties, Rule};
@@ -20,6 +20,10 @@ impl Violation for CovariantEqualsViolation {
     fn message(&self) -> String {
         "Covariant equals without overriding equals(java.lang.Object).".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "covariant.equals"))
+    }
 }
 
 /// Configuration for CovariantEquals rule.
@@ -8,7 +8,7 @@
 //!
 //! Checkstyle equivalent: DeclarationOrderCheck
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 
 use crate::{CheckContext, FromConfig, Properties, Rule};
@@ -23,6 +23,10 @@ impl Violation for StaticVariableOrderViolation {
     fn message(&self) -> String {
         "Static variable definition in wrong order.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "declaration.order.static"))
+    }
 }
 
 /// Violation: instance variable in wrong order.
@@ -35,6 +39,10 @@ impl Violation for InstanceVariableOrderViolation {
     fn message(&self) -> String {
         "Instance variable definition in wrong order.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "declaration.order.instance"))
+    }
 }
 
 /// Violation: constructor in wrong order.
@@ -47,6 +55,10 @@ impl Violation for ConstructorOrderViolation {
     fn message(&self) -> String {
         "Constructor definition in wrong order.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "declaration.order.constructor"))
+    }
 }
 
 /// Violation: variable access modifier in wrong order.
@@ -59,6 +71,10 @@ impl Violation for VariableAccessOrderViolation {
     fn message(&self) -> String {
         "Variable access definition in wrong order.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "declaration.order.access"))
+    }
 }
 
 /// Configuration for DeclarationOrder rule.
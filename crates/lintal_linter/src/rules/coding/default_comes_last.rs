@@ -5,7 +5,7 @@
 //!
 //! Checkstyle equivalent: DefaultComesLastCheck
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 
 use crate::{CheckContext, FromConfig, Properties, Rule};
@@ -20,6 +20,10 @@ impl Violation for DefaultComesLastViolation {
     fn message(&self) -> String {
         "Default should be last label in the switch.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "default.comes.last"))
+    }
 }
 
 /// Violation: default should be last label in the case group (when skipIfLastAndSharedWithCase).
@@ -32,6 +36,10 @@ impl Violation for DefaultComesLastInGroupViolation {
     fn message(&self) -> String {
         "Default should be last label in the case group.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "default.comes.last.in.casegroup"))
+    }
 }
 
 /// Configuration for DefaultComesLast rule.
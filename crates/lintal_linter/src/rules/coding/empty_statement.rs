@@ -4,8 +4,11 @@
 //!
 //! Checkstyle equivalent: EmptyStatementCheck
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
+use lintal_java_cst::ast::{
+    DoStatement, EnhancedForStatement, ForStatement, IfStatement, WhileStatement,
+};
 
 use crate::{CheckContext, FromConfig, Properties, Rule};
 
@@ -19,6 +22,10 @@ impl Violation for EmptyStatementViolation {
     fn message(&self) -> String {
         "Empty statement.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "empty.statement"))
+    }
 }
 
 /// Configuration for EmptyStatement rule.
@@ -67,21 +74,20 @@ impl Rule for EmptyStatement {
 impl EmptyStatement {
     /// Check if statement for empty body.
     fn check_if_statement(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
-        // In tree-sitter Java, if_statement has:
-        // - "condition" field: the condition
-        // - "consequence" field: the then body
-        // - "alternative" field (optional): the else body
+        let Some(statement) = node.cast::<IfStatement>() else {
+            return vec![];
+        };
         let mut diagnostics = vec![];
 
         // Check the consequence (then body)
-        if let Some(consequence) = node.child_by_field_name("consequence")
+        if let Some(consequence) = statement.consequence()
             && consequence.kind() == ";"
         {
             diagnostics.push(self.create_diagnostic(&consequence));
         }
 
         // Check the alternative (else body) if present
-        if let Some(alternative) = node.child_by_field_name("alternative")
+        if let Some(alternative) = statement.alternative()
             && alternative.kind() == ";"
         {
             diagnostics.push(self.create_diagnostic(&alternative));
@@ -92,46 +98,34 @@ impl EmptyStatement {
 
     /// Check while statement for empty body.
     fn check_while_statement(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
-        // while_statement has "body" field
-        if let Some(body) = node.child_by_field_name("body")
-            && body.kind() == ";"
-        {
-            return vec![self.create_diagnostic(&body)];
-        }
-        vec![]
+        let body = node.cast::<WhileStatement>().and_then(|s| s.body());
+        self.check_body(body)
     }
 
     /// Check for statement for empty body.
     fn check_for_statement(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
-        // for_statement has "body" field
-        if let Some(body) = node.child_by_field_name("body")
-            && body.kind() == ";"
-        {
-            return vec![self.create_diagnostic(&body)];
-        }
-        vec![]
+        let body = node.cast::<ForStatement>().and_then(|s| s.body());
+        self.check_body(body)
     }
 
     /// Check enhanced for statement for empty body.
     fn check_enhanced_for_statement(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
-        // enhanced_for_statement has "body" field
-        if let Some(body) = node.child_by_field_name("body")
-            && body.kind() == ";"
-        {
-            return vec![self.create_diagnostic(&body)];
-        }
-        vec![]
+        let body = node.cast::<EnhancedForStatement>().and_then(|s| s.body());
+        self.check_body(body)
     }
 
     /// Check do statement for empty body.
     fn check_do_statement(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
-        // do_statement has "body" field
-        if let Some(body) = node.child_by_field_name("body")
-            && body.kind() == ";"
-        {
-            return vec![self.create_diagnostic(&body)];
+        let body = node.cast::<DoStatement>().and_then(|s| s.body());
+        self.check_body(body)
+    }
+
+    /// Flag a loop body that is just a semicolon.
+    fn check_body(&self, body: Option<CstNode>) -> Vec<Diagnostic> {
+        match body {
+            Some(body) if body.kind() == ";" => vec![self.create_diagnostic(&body)],
+            _ => vec![],
         }
-        vec![]
     }
 
     /// Check for standalone semicolons in blocks.
@@ -0,0 +1,200 @@
+//! EqualsAvoidNull rule implementation.
+//!
+//! Checks that string literals are on the left side of `equals()`
+//! comparisons, so a `null` on the other side can't throw.
+//!
+//! Checkstyle equivalent: EqualsAvoidNullCheck
+
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: string literal on the right side of `equals()`.
+#[derive(Debug, Clone)]
+pub struct EqualsAvoidNullViolation {
+    /// Whether the comparison is `equalsIgnoreCase()`.
+    pub ignore_case: bool,
+}
+
+impl Violation for EqualsAvoidNullViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Always;
+
+    fn message(&self) -> String {
+        let method = if self.ignore_case {
+            "equalsIgnoreCase"
+        } else {
+            "equals"
+        };
+        format!("String literal expressions should be on the left side of an {method} comparison.")
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        let key = if self.ignore_case {
+            "equalsIgnoreCase.avoid.null"
+        } else {
+            "equals.avoid.null"
+        };
+        Some(MessageKey::new("coding", key))
+    }
+}
+
+/// Configuration for EqualsAvoidNull rule.
+#[derive(Debug, Clone, Default)]
+pub struct EqualsAvoidNull {
+    /// Whether to leave `equalsIgnoreCase()` calls alone (default: false).
+    pub ignore_equals_ignore_case: bool,
+}
+
+const RELEVANT_KINDS: &[&str] = &["method_invocation"];
+
+impl FromConfig for EqualsAvoidNull {
+    const MODULE_NAME: &'static str = "EqualsAvoidNull";
+
+    fn from_config(properties: &Properties) -> Self {
+        let ignore_equals_ignore_case = properties
+            .get("ignoreEqualsIgnoreCase")
+            .is_some_and(|v| *v == "true");
+
+        Self {
+            ignore_equals_ignore_case,
+        }
+    }
+}
+
+impl Rule for EqualsAvoidNull {
+    fn name(&self) -> &'static str {
+        "EqualsAvoidNull"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        let Some(name) = node.child_by_field_name("name") else {
+            return vec![];
+        };
+        let ignore_case = match name.text() {
+            "equals" => false,
+            "equalsIgnoreCase" if !self.ignore_equals_ignore_case => true,
+            _ => return vec![],
+        };
+
+        let Some(receiver) = node.child_by_field_name("object") else {
+            return vec![];
+        };
+        let Some(arguments) = node.child_by_field_name("arguments") else {
+            return vec![];
+        };
+        let [argument] = arguments.named_children().collect::<Vec<_>>()[..] else {
+            return vec![];
+        };
+        if !is_string_constant(&argument) {
+            return vec![];
+        }
+
+        // Like checkstyle, only calls on a String variable or field are
+        // flagged, `this.name` included
+        let is_name = match receiver.kind() {
+            "identifier" => true,
+            "field_access" => receiver
+                .child_by_field_name("object")
+                .is_some_and(|object| object.kind() == "this"),
+            _ => false,
+        };
+        if !is_name
+            || !ctx
+                .semantic(node)
+                .expression_type(&receiver)
+                .is_some_and(|ty| ty.is_string())
+        {
+            return vec![];
+        }
+
+        // A concatenation needs parentheses to stay the receiver
+        let literal = if argument.kind() == "binary_expression" {
+            format!("({})", argument.text())
+        } else {
+            argument.text().to_string()
+        };
+        let swapped = format!("{literal}.{}({})", name.text(), receiver.text());
+        let fix = Fix::safe_edit(Edit::range_replacement(swapped, node.range()));
+        vec![Diagnostic::new(EqualsAvoidNullViolation { ignore_case }, node.range()).with_fix(fix)]
+    }
+}
+
+/// Whether `node` is a string literal, or literals joined with `+`.
+fn is_string_constant(node: &CstNode) -> bool {
+    match node.kind() {
+        "string_literal" => true,
+        "parenthesized_expression" => node
+            .named_children()
+            .next()
+            .is_some_and(|inner| is_string_constant(&inner)),
+        "binary_expression" => {
+            node.child_by_field_name("operator")
+                .is_some_and(|operator| operator.kind() == "+")
+                && node
+                    .child_by_field_name("left")
+                    .is_some_and(|left| is_string_constant(&left))
+                && node
+                    .child_by_field_name("right")
+                    .is_some_and(|right| is_string_constant(&right))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, rule: &EqualsAvoidNull) -> Vec<Diagnostic> {
+        let result = JavaParser::new().parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        TreeWalker::new(result.tree.root_node(), source)
+            .flat_map(|node| rule.check(&ctx, &node))
+            .collect()
+    }
+
+    #[test]
+    fn test_literal_on_the_right() {
+        let source = r#"
+class Test {
+    private String name;
+
+    boolean check(String value, Object other) {
+        return value.equals("yes")
+            || this.name.equalsIgnoreCase("a" + "b")
+            || "yes".equals(value)
+            || other.equals("yes")
+            || value.equals(name);
+    }
+}
+"#;
+        let diagnostics = check_source(source, &EqualsAvoidNull::default());
+        let fixed: Vec<Option<&str>> = diagnostics
+            .iter()
+            .map(|d| d.fix.as_ref().unwrap().edits()[0].content())
+            .collect();
+        assert_eq!(
+            fixed,
+            [
+                Some(r#""yes".equals(value)"#),
+                Some(r#"("a" + "b").equalsIgnoreCase(this.name)"#)
+            ]
+        );
+        assert_eq!(
+            diagnostics[1].kind.body,
+            "String literal expressions should be on the left side of an equalsIgnoreCase comparison."
+        );
+
+        let rule = EqualsAvoidNull {
+            ignore_equals_ignore_case: true,
+        };
+        assert_eq!(check_source(source, &rule).len(), 1);
+    }
+}
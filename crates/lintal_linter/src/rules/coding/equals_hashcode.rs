@@ -4,7 +4,7 @@
 //!
 //! Checkstyle equivalent: EqualsHashCodeCheck
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 
 use crate::{CheckContext, FromConfig, Properties, Rule};
@@ -19,6 +19,10 @@ impl Violation for EqualsWithoutHashCodeViolation {
     fn message(&self) -> String {
         "Definition of 'equals()' without corresponding definition of 'hashCode()'.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "equals.noHashCode"))
+    }
 }
 
 /// Violation: hashCode() without equals().
@@ -31,6 +35,10 @@ impl Violation for HashCodeWithoutEqualsViolation {
     fn message(&self) -> String {
         "Definition of 'hashCode()' without corresponding definition of 'equals()'.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "equals.noEquals"))
+    }
 }
 
 /// Configuration for EqualsHashCode rule.
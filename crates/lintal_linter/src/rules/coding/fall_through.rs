@@ -6,7 +6,7 @@
 //!
 //! Checkstyle equivalent: FallThroughCheck
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use regex::Regex;
 
@@ -22,6 +22,10 @@ impl Violation for FallThroughViolation {
     fn message(&self) -> String {
         "Fall through from previous branch of the switch statement.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "fall.through"))
+    }
 }
 
 /// Configuration for FallThrough rule.
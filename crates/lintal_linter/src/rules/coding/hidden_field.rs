@@ -5,7 +5,7 @@
 //!
 //! Checkstyle equivalent: HiddenFieldCheck
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use regex::Regex;
 
@@ -23,6 +23,14 @@ impl Violation for HiddenFieldViolation {
     fn message(&self) -> String {
         format!("'{}' hides a field.", self.name)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "hidden.field"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
 }
 
 /// Configuration for HiddenField rule.
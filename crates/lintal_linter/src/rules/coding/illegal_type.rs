@@ -7,7 +7,7 @@
 
 use std::collections::HashSet;
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use regex::Regex;
 use tree_sitter::Node;
@@ -26,6 +26,14 @@ impl Violation for IllegalTypeViolation {
     fn message(&self) -> String {
         format!("Usage of type '{}' is not allowed.", self.name)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "illegal.type"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
 }
 
 /// Violation: abstract class name does not match format.
@@ -5,7 +5,7 @@
 //!
 //! Checkstyle equivalent: InnerAssignmentCheck
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 
 use crate::{CheckContext, FromConfig, Properties, Rule};
@@ -20,6 +20,10 @@ impl Violation for InnerAssignmentViolation {
     fn message(&self) -> String {
         "Inner assignments should be avoided.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "assignment.inner.avoid"))
+    }
 }
 
 /// Configuration for InnerAssignment rule.
@@ -0,0 +1,778 @@
+//! MagicNumber rule implementation.
+//!
+//! Checks that numeric literals are not used outside constant definitions,
+//! e.g. `int timeout = 3600;` rather than `static final int TIMEOUT = 3600;`.
+//!
+//! Checkstyle equivalent: MagicNumberCheck
+
+use std::collections::HashSet;
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+
+use crate::rules::modifier::common::has_modifier;
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: numeric literal outside a constant definition.
+#[derive(Debug, Clone)]
+pub struct MagicNumberViolation {
+    pub number: String,
+}
+
+impl Violation for MagicNumberViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!("'{}' is a magic number.", self.number)
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "magic.number"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.number.clone()]
+    }
+}
+
+/// Configuration for MagicNumber rule.
+#[derive(Debug, Clone)]
+pub struct MagicNumber {
+    /// Values that are never magic, sorted by [`f64::total_cmp`].
+    pub ignore_numbers: Vec<f64>,
+    pub ignore_hash_code_method: bool,
+    pub ignore_annotation: bool,
+    pub ignore_field_declaration: bool,
+    pub ignore_annotation_element_defaults: bool,
+    /// Checkstyle tokens an expression in a constant definition may pass
+    /// through and still count as part of the constant.
+    pub constant_waiver_parent_token: HashSet<String>,
+    /// Literal tokens to check: NUM_DOUBLE, NUM_FLOAT, NUM_INT, NUM_LONG.
+    pub tokens: HashSet<String>,
+}
+
+const RELEVANT_KINDS: &[&str] = &[
+    "decimal_integer_literal",
+    "hex_integer_literal",
+    "octal_integer_literal",
+    "binary_integer_literal",
+    "decimal_floating_point_literal",
+    "hex_floating_point_literal",
+];
+
+const DEFAULT_IGNORE_NUMBERS: &[f64] = &[-1.0, 0.0, 1.0, 2.0];
+
+const DEFAULT_CONSTANT_WAIVER_PARENT_TOKEN: &[&str] = &[
+    "TYPECAST",
+    "METHOD_CALL",
+    "EXPR",
+    "ARRAY_INIT",
+    "UNARY_MINUS",
+    "UNARY_PLUS",
+    "ELIST",
+    "STAR",
+    "ASSIGN",
+    "PLUS",
+    "MINUS",
+    "DIV",
+    "LITERAL_NEW",
+    "SR",
+    "BSR",
+    "SL",
+    "BXOR",
+    "BOR",
+    "BAND",
+    "BNOT",
+    "QUESTION",
+    "COLON",
+    "EQUAL",
+    "NOT_EQUAL",
+    "GE",
+    "GT",
+    "LE",
+    "LT",
+    "MOD",
+];
+
+const DEFAULT_TOKENS: &[&str] = &["NUM_DOUBLE", "NUM_FLOAT", "NUM_INT", "NUM_LONG"];
+
+impl Default for MagicNumber {
+    fn default() -> Self {
+        Self {
+            ignore_numbers: DEFAULT_IGNORE_NUMBERS.to_vec(),
+            ignore_hash_code_method: false,
+            ignore_annotation: false,
+            ignore_field_declaration: false,
+            ignore_annotation_element_defaults: true,
+            constant_waiver_parent_token: DEFAULT_CONSTANT_WAIVER_PARENT_TOKEN
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            tokens: DEFAULT_TOKENS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl FromConfig for MagicNumber {
+    const MODULE_NAME: &'static str = "MagicNumber";
+
+    fn from_config(properties: &Properties) -> Self {
+        let default = Self::default();
+
+        let mut ignore_numbers = properties
+            .get("ignoreNumbers")
+            .map(|v| {
+                v.split(',')
+                    .filter_map(|s| s.trim().parse::<f64>().ok())
+                    .collect()
+            })
+            .unwrap_or(default.ignore_numbers);
+        ignore_numbers.sort_by(f64::total_cmp);
+
+        let flag = |name: &str, default: bool| {
+            properties
+                .get(name)
+                .map(|v| *v == "true")
+                .unwrap_or(default)
+        };
+
+        let names = |name: &str, default: HashSet<String>| {
+            properties
+                .get(name)
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or(default)
+        };
+
+        Self {
+            ignore_numbers,
+            ignore_hash_code_method: flag("ignoreHashCodeMethod", default.ignore_hash_code_method),
+            ignore_annotation: flag("ignoreAnnotation", default.ignore_annotation),
+            ignore_field_declaration: flag(
+                "ignoreFieldDeclaration",
+                default.ignore_field_declaration,
+            ),
+            ignore_annotation_element_defaults: flag(
+                "ignoreAnnotationElementDefaults",
+                default.ignore_annotation_element_defaults,
+            ),
+            constant_waiver_parent_token: names(
+                "constantWaiverParentToken",
+                default.constant_waiver_parent_token,
+            ),
+            tokens: names("tokens", default.tokens),
+        }
+    }
+}
+
+impl Rule for MagicNumber {
+    fn name(&self) -> &'static str {
+        "MagicNumber"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        let Some(token) = literal_token(node) else {
+            return vec![];
+        };
+        if !self.tokens.contains(token) {
+            return vec![];
+        }
+
+        if self.ignore_annotation && has_ancestor(node, &["annotation"]) {
+            return vec![];
+        }
+        if self.ignore_annotation_element_defaults && is_annotation_element_default(node) {
+            return vec![];
+        }
+        if self.is_in_ignore_list(node, token) {
+            return vec![];
+        }
+        if self.ignore_hash_code_method && is_in_hash_code_method(node) {
+            return vec![];
+        }
+
+        let magic = match containing_constant_def(node) {
+            Some(def) => !self.is_waived(node, &def),
+            None => !(self.ignore_field_declaration && is_field_declaration(node)),
+        };
+        if !magic {
+            return vec![];
+        }
+
+        let (number, range) = match sign_of(node) {
+            Some((sign, unary)) => (format!("{}{}", sign, node.text()), unary.range()),
+            None => (node.text().to_string(), node.range()),
+        };
+        vec![Diagnostic::new(MagicNumberViolation { number }, range)]
+    }
+}
+
+impl MagicNumber {
+    /// Whether the literal's value, negated under a unary minus, is one of
+    /// `ignoreNumbers`. Like checkstyle, `-0` and `0` are different values.
+    fn is_in_ignore_list(&self, node: &CstNode, token: &str) -> bool {
+        let Some(mut value) = parse_value(node.text(), token) else {
+            return false;
+        };
+        if matches!(sign_of(node), Some(("-", _))) {
+            value *= -1.0;
+        }
+        self.ignore_numbers
+            .binary_search_by(|n| n.total_cmp(&value))
+            .is_ok()
+    }
+
+    /// Whether every checkstyle token between the literal and the constant
+    /// definition `def` is in `constantWaiverParentToken`.
+    fn is_waived(&self, node: &CstNode, def: &CstNode) -> bool {
+        let mut child = *node;
+        while let Some(parent) = child.parent() {
+            let mut tokens = vec![];
+            if is_expression_root(&child, &parent) {
+                tokens.push("EXPR");
+            }
+            if parent.kind() == "variable_declarator" {
+                tokens.push("ASSIGN");
+            }
+            if parent.inner() == def.inner() {
+                return tokens
+                    .iter()
+                    .all(|t| self.constant_waiver_parent_token.contains(*t));
+            }
+            match token_of(&parent) {
+                Some(Some(token)) => tokens.push(token),
+                Some(None) => {}
+                None => return false,
+            }
+            if !tokens
+                .iter()
+                .all(|t| self.constant_waiver_parent_token.contains(*t))
+            {
+                return false;
+            }
+            child = parent;
+        }
+        false
+    }
+}
+
+/// The checkstyle token of a numeric literal.
+fn literal_token(node: &CstNode) -> Option<&'static str> {
+    let text = node.text();
+    match node.kind() {
+        "decimal_integer_literal"
+        | "hex_integer_literal"
+        | "octal_integer_literal"
+        | "binary_integer_literal" => Some(if text.ends_with(['l', 'L']) {
+            "NUM_LONG"
+        } else {
+            "NUM_INT"
+        }),
+        "decimal_floating_point_literal" | "hex_floating_point_literal" => {
+            Some(if text.ends_with(['f', 'F']) {
+                "NUM_FLOAT"
+            } else {
+                "NUM_DOUBLE"
+            })
+        }
+        _ => None,
+    }
+}
+
+/// The value of a literal as checkstyle computes it: integers keep Java's
+/// wrap-around, so `0xFFFFFFFF` is -1.
+fn parse_value(text: &str, token: &str) -> Option<f64> {
+    let text = text.replace('_', "");
+    match token {
+        "NUM_FLOAT" | "NUM_DOUBLE" => {
+            let digits = text.trim_end_matches(['f', 'F', 'd', 'D']);
+            match digits
+                .strip_prefix("0x")
+                .or_else(|| digits.strip_prefix("0X"))
+            {
+                Some(hex) => parse_hex_float(hex),
+                None => digits.parse().ok(),
+            }
+        }
+        _ => {
+            let digits = text.trim_end_matches(['l', 'L']);
+            let (digits, radix) = if let Some(hex) = digits
+                .strip_prefix("0x")
+                .or_else(|| digits.strip_prefix("0X"))
+            {
+                (hex, 16)
+            } else if let Some(bin) = digits
+                .strip_prefix("0b")
+                .or_else(|| digits.strip_prefix("0B"))
+            {
+                (bin, 2)
+            } else if digits.starts_with('0') {
+                (digits, 8)
+            } else {
+                (digits, 10)
+            };
+            let bits = u64::from_str_radix(digits, radix).ok()?;
+            Some(if token == "NUM_LONG" {
+                bits as i64 as f64
+            } else {
+                bits as u32 as i32 as f64
+            })
+        }
+    }
+}
+
+/// Parse the digits of a hex floating point literal after its `0x`, e.g.
+/// `1.8p1`.
+fn parse_hex_float(text: &str) -> Option<f64> {
+    let (mantissa, exponent) = text.split_once(['p', 'P'])?;
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+    let mut value = 0.0;
+    for c in whole.chars() {
+        value = value * 16.0 + f64::from(c.to_digit(16)?);
+    }
+    let mut scale = 1.0 / 16.0;
+    for c in fraction.chars() {
+        value += f64::from(c.to_digit(16)?) * scale;
+        scale /= 16.0;
+    }
+    Some(value * 2f64.powi(exponent.parse().ok()?))
+}
+
+/// The unary `-` or `+` applied to the literal, looking through
+/// parentheses, with the unary expression.
+fn sign_of<'a>(node: &CstNode<'a>) -> Option<(&'static str, CstNode<'a>)> {
+    let mut parent = node.parent()?;
+    while parent.kind() == "parenthesized_expression" {
+        parent = parent.parent()?;
+    }
+    if parent.kind() != "unary_expression" {
+        return None;
+    }
+    match parent.children().next()?.text() {
+        "-" => Some(("-", parent)),
+        "+" => Some(("+", parent)),
+        _ => None,
+    }
+}
+
+fn has_ancestor(node: &CstNode, kinds: &[&str]) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if kinds.contains(&parent.kind()) {
+            return true;
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// Whether the literal is in the `default` value of an annotation element.
+fn is_annotation_element_default(node: &CstNode) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.kind() == "annotation_type_element_declaration" {
+            return parent
+                .children()
+                .find(|c| c.kind() == "default")
+                .is_some_and(|default| default.range().end() <= node.range().start());
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// Whether the nearest enclosing method is `hashCode()`.
+fn is_in_hash_code_method(node: &CstNode) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.kind() == "method_declaration" {
+            return parent
+                .child_by_field_name("name")
+                .is_some_and(|name| name.text() == "hashCode")
+                && parent
+                    .child_by_field_name("parameters")
+                    .is_some_and(|params| params.named_children().next().is_none());
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// The constant the literal belongs to: the nearest enclosing variable
+/// declarator if it is final or in an interface or annotation, or the
+/// nearest enclosing enum constant.
+fn containing_constant_def<'a>(node: &CstNode<'a>) -> Option<CstNode<'a>> {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        match parent.kind() {
+            "enum_constant" => return Some(parent),
+            "variable_declarator" => {
+                let is_final = parent.parent().is_some_and(|decl| {
+                    decl.children()
+                        .find(|c| c.kind() == "modifiers")
+                        .is_some_and(|modifiers| has_modifier(&modifiers, "final"))
+                });
+                return (is_final || is_in_interface_or_annotation_block(&parent))
+                    .then_some(parent);
+            }
+            _ => {}
+        }
+        current = parent.parent();
+    }
+    None
+}
+
+/// Whether `node` is in an interface or annotation body, not counting a
+/// nested class, enum, record, or anonymous class.
+fn is_in_interface_or_annotation_block(node: &CstNode) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        match parent.kind() {
+            "interface_declaration" | "annotation_type_declaration" => return true,
+            "class_declaration"
+            | "enum_declaration"
+            | "record_declaration"
+            | "object_creation_expression"
+            | "array_creation_expression" => return false,
+            _ => {}
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// Whether the literal initializes a field of a class, record, or anonymous
+/// class.
+fn is_field_declaration(node: &CstNode) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        match parent.kind() {
+            "class_body" | "interface_body" | "enum_body" | "annotation_type_body" => {
+                return false;
+            }
+            "variable_declarator" => {
+                return parent
+                    .parent()
+                    .filter(|decl| decl.kind() == "field_declaration")
+                    .and_then(|decl| decl.parent())
+                    .filter(|body| body.kind() == "class_body")
+                    .and_then(|body| body.parent())
+                    .is_some_and(|owner| {
+                        matches!(
+                            owner.kind(),
+                            "class_declaration"
+                                | "record_declaration"
+                                | "object_creation_expression"
+                        )
+                    });
+            }
+            _ => {}
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// Whether checkstyle wraps `child` in an EXPR node under `parent`.
+fn is_expression_root(child: &CstNode, parent: &CstNode) -> bool {
+    match parent.kind() {
+        "argument_list" | "array_initializer" | "dimensions_expr" => child.kind() != "(",
+        "variable_declarator" => child.kind() != "array_initializer",
+        "array_access" => parent
+            .child_by_field_name("index")
+            .is_some_and(|index| index.range() == child.range()),
+        _ => false,
+    }
+}
+
+/// The checkstyle token for `node` as the parent of an expression:
+/// `Some(None)` if checkstyle has no node for it, such as parentheses, and
+/// `None` if it is not part of an expression at all.
+fn token_of(node: &CstNode) -> Option<Option<&'static str>> {
+    let operator = || {
+        node.child_by_field_name("operator")
+            .map(|op| op.text())
+            .unwrap_or_default()
+    };
+    let token = match node.kind() {
+        "parenthesized_expression" => return Some(None),
+        "binary_expression" => match operator() {
+            "+" => "PLUS",
+            "-" => "MINUS",
+            "*" => "STAR",
+            "/" => "DIV",
+            "%" => "MOD",
+            "<<" => "SL",
+            ">>" => "SR",
+            ">>>" => "BSR",
+            "&" => "BAND",
+            "|" => "BOR",
+            "^" => "BXOR",
+            "==" => "EQUAL",
+            "!=" => "NOT_EQUAL",
+            "<" => "LT",
+            "<=" => "LE",
+            ">" => "GT",
+            ">=" => "GE",
+            "&&" => "LAND",
+            "||" => "LOR",
+            _ => return None,
+        },
+        "unary_expression" => match node.children().next().map(|op| op.text()) {
+            Some("-") => "UNARY_MINUS",
+            Some("+") => "UNARY_PLUS",
+            Some("~") => "BNOT",
+            Some("!") => "LNOT",
+            _ => return None,
+        },
+        "assignment_expression" => match operator() {
+            "=" => "ASSIGN",
+            "+=" => "PLUS_ASSIGN",
+            "-=" => "MINUS_ASSIGN",
+            "*=" => "STAR_ASSIGN",
+            "/=" => "DIV_ASSIGN",
+            "%=" => "MOD_ASSIGN",
+            "&=" => "BAND_ASSIGN",
+            "|=" => "BOR_ASSIGN",
+            "^=" => "BXOR_ASSIGN",
+            "<<=" => "SL_ASSIGN",
+            ">>=" => "SR_ASSIGN",
+            ">>>=" => "BSR_ASSIGN",
+            _ => return None,
+        },
+        "cast_expression" => "TYPECAST",
+        "ternary_expression" => "QUESTION",
+        "argument_list" => "ELIST",
+        "method_invocation" => "METHOD_CALL",
+        "object_creation_expression" | "array_creation_expression" => "LITERAL_NEW",
+        "dimensions_expr" => "ARRAY_DECLARATOR",
+        "array_initializer" => "ARRAY_INIT",
+        "array_access" => "INDEX_OP",
+        "lambda_expression" => "LAMBDA",
+        "explicit_constructor_invocation" => {
+            if node
+                .child_by_field_name("constructor")
+                .is_some_and(|c| c.kind() == "super")
+            {
+                "SUPER_CTOR_CALL"
+            } else {
+                "CTOR_CALL"
+            }
+        }
+        _ => return None,
+    };
+    Some(Some(token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, properties: &[(&str, &str)]) -> Vec<String> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let properties: Properties = properties.iter().copied().collect();
+        let rule = MagicNumber::from_config(&properties);
+
+        let mut numbers = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            for diagnostic in rule.check(&ctx, &node) {
+                numbers.push(diagnostic.kind.body);
+            }
+        }
+        numbers
+    }
+
+    #[test]
+    fn test_magic_numbers() {
+        let source = r#"
+class A {
+    void f() {
+        int a = 3600;
+        int b = -5;
+        double c = 1.5;
+        long d = 100L;
+        int e = 0 + 1 + 2 - 1;
+    }
+}
+"#;
+        assert_eq!(
+            check_source(source, &[]),
+            [
+                "'3600' is a magic number.",
+                "'-5' is a magic number.",
+                "'1.5' is a magic number.",
+                "'100L' is a magic number.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_constants() {
+        let source = r#"
+interface I {
+    int LIMIT = 10;
+}
+enum E {
+    A(3), B(4);
+    E(int n) {}
+}
+class A {
+    static final int TIMEOUT = 3600;
+    static final int DOUBLED = 2 * 3600 + Math.max(4, 5);
+    static final int[] SIZES = {3, 5, (int) 7.5};
+    static final int[] VALUES = {1};
+    static final int HIDDEN = VALUES[10];
+}
+"#;
+        // Indexing is not a waived parent token
+        assert_eq!(check_source(source, &[]), ["'10' is a magic number."]);
+    }
+
+    #[test]
+    fn test_constant_waiver_parent_token() {
+        let source = r#"
+class A {
+    static final int PLAIN = 5;
+    static final int PRODUCT = 3 * 4;
+}
+"#;
+        assert_eq!(
+            check_source(source, &[("constantWaiverParentToken", "ASSIGN, EXPR")]),
+            ["'3' is a magic number.", "'4' is a magic number."]
+        );
+    }
+
+    #[test]
+    fn test_ignore_numbers() {
+        let source = r#"
+class A {
+    void f() {
+        int a = 100;
+        int b = -100;
+        double c = 0.5;
+        int d = 0xFFFFFFFF;
+    }
+}
+"#;
+        assert_eq!(
+            check_source(source, &[("ignoreNumbers", "100, 0.5")]),
+            [
+                "'-100' is a magic number.",
+                "'0xFFFFFFFF' is a magic number."
+            ]
+        );
+        // 0xFFFFFFFF wraps around to -1, which is ignored by default
+        assert_eq!(
+            check_source(source, &[]),
+            [
+                "'100' is a magic number.",
+                "'-100' is a magic number.",
+                "'0.5' is a magic number.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_negative_zero() {
+        let source = "class A { void f() { double a = -0.0; int b = -(1); } }";
+        assert_eq!(check_source(source, &[]), ["'-0.0' is a magic number."]);
+    }
+
+    #[test]
+    fn test_ignore_hash_code_method() {
+        let source = r#"
+class A {
+    public int hashCode() {
+        return 31 * 17;
+    }
+    public int hashCode(int seed) {
+        return 31 * seed;
+    }
+}
+"#;
+        assert_eq!(
+            check_source(source, &[("ignoreHashCodeMethod", "true")]),
+            ["'31' is a magic number."]
+        );
+        assert_eq!(check_source(source, &[]).len(), 3);
+    }
+
+    #[test]
+    fn test_ignore_annotation() {
+        let source = r#"
+class A {
+    @Size(max = 255)
+    String name;
+}
+"#;
+        assert_eq!(check_source(source, &[]), ["'255' is a magic number."]);
+        assert!(check_source(source, &[("ignoreAnnotation", "true")]).is_empty());
+    }
+
+    #[test]
+    fn test_ignore_annotation_element_defaults() {
+        let source = r#"
+@interface Retry {
+    int times() default 3;
+}
+"#;
+        assert!(check_source(source, &[]).is_empty());
+        assert_eq!(
+            check_source(source, &[("ignoreAnnotationElementDefaults", "false")]),
+            ["'3' is a magic number."]
+        );
+    }
+
+    #[test]
+    fn test_ignore_field_declaration() {
+        let source = r#"
+class A {
+    int size = 10;
+    void f() {
+        int local = 20;
+    }
+    Object o = new Object() {
+        int inner = 30;
+    };
+}
+"#;
+        assert_eq!(
+            check_source(source, &[("ignoreFieldDeclaration", "true")]),
+            ["'20' is a magic number."]
+        );
+        assert_eq!(check_source(source, &[]).len(), 3);
+    }
+
+    #[test]
+    fn test_tokens() {
+        let source = "class A { void f() { int a = 5; double b = 5.5; float c = 1.5f; } }";
+        assert_eq!(
+            check_source(source, &[("tokens", "NUM_FLOAT")]),
+            ["'1.5f' is a magic number."]
+        );
+    }
+
+    #[test]
+    fn test_parse_value() {
+        assert_eq!(parse_value("1_000", "NUM_INT"), Some(1000.0));
+        assert_eq!(parse_value("010", "NUM_INT"), Some(8.0));
+        assert_eq!(parse_value("0b101", "NUM_INT"), Some(5.0));
+        assert_eq!(parse_value("0xFFFFFFFF", "NUM_INT"), Some(-1.0));
+        assert_eq!(parse_value("0xFFFFFFFFL", "NUM_LONG"), Some(4294967295.0));
+        assert_eq!(parse_value("2.5f", "NUM_FLOAT"), Some(2.5));
+        assert_eq!(parse_value("0x1.8p1", "NUM_DOUBLE"), Some(3.0));
+    }
+}
@@ -4,7 +4,7 @@
 //!
 //! Checkstyle equivalent: MissingSwitchDefaultCheck
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 
 use crate::{CheckContext, FromConfig, Properties, Rule};
@@ -19,6 +19,10 @@ impl Violation for MissingSwitchDefaultViolation {
     fn message(&self) -> String {
         "switch without \"default\" clause.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "missing.switch.default"))
+    }
 }
 
 /// Configuration for MissingSwitchDefault rule.
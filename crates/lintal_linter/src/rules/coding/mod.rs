@@ -1,19 +1,26 @@
 //! Coding rules (OneStatementPerLine, MultipleVariableDeclarations, etc.)
 
+mod common;
 mod covariant_equals;
 mod declaration_order;
 mod default_comes_last;
 mod empty_statement;
+mod equals_avoid_null;
 mod equals_hashcode;
 mod fall_through;
 mod hidden_field;
 mod illegal_type;
 mod inner_assignment;
+mod magic_number;
 mod missing_switch_default;
 mod multiple_variable_declarations;
+mod nested_for_depth;
+mod nested_if_depth;
 mod nested_try_depth;
 mod one_statement_per_line;
 mod package_declaration;
+mod require_this;
+mod return_count;
 mod simplify_boolean_expression;
 mod simplify_boolean_return;
 mod string_literal_equality;
@@ -22,16 +29,22 @@ pub use covariant_equals::CovariantEquals;
 pub use declaration_order::DeclarationOrder;
 pub use default_comes_last::DefaultComesLast;
 pub use empty_statement::EmptyStatement;
+pub use equals_avoid_null::EqualsAvoidNull;
 pub use equals_hashcode::EqualsHashCode;
 pub use fall_through::FallThrough;
 pub use hidden_field::HiddenField;
 pub use illegal_type::IllegalType;
 pub use inner_assignment::InnerAssignment;
+pub use magic_number::MagicNumber;
 pub use missing_switch_default::MissingSwitchDefault;
 pub use multiple_variable_declarations::MultipleVariableDeclarations;
+pub use nested_for_depth::NestedForDepth;
+pub use nested_if_depth::NestedIfDepth;
 pub use nested_try_depth::NestedTryDepth;
 pub use one_statement_per_line::OneStatementPerLine;
 pub use package_declaration::PackageDeclaration;
+pub use require_this::RequireThis;
+pub use return_count::ReturnCount;
 pub use simplify_boolean_expression::SimplifyBooleanExpression;
 pub use simplify_boolean_return::SimplifyBooleanReturn;
 pub use string_literal_equality::StringLiteralEquality;
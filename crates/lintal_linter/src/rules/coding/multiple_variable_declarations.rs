@@ -4,7 +4,7 @@
 //!
 //! Checkstyle equivalent: MultipleVariableDeclarationsCheck
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 
 use crate::{CheckContext, FromConfig, Properties, Rule};
@@ -19,6 +19,13 @@ impl Violation for MultipleInStatementViolation {
     fn message(&self) -> String {
         "Each variable declaration must be in its own statement.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new(
+            "coding",
+            "multiple.variable.declarations.comma",
+        ))
+    }
 }
 
 /// Violation: multiple declarations on same line.
@@ -31,6 +38,10 @@ impl Violation for MultipleOnLineViolation {
     fn message(&self) -> String {
         "Only one variable definition per line allowed.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "multiple.variable.declarations"))
+    }
 }
 
 /// Configuration for MultipleVariableDeclarations rule.
@@ -0,0 +1,149 @@
+//! NestedForDepth rule implementation.
+//!
+//! Checks that for loops, enhanced for loops included, are not nested too
+//! deeply.
+//!
+//! Checkstyle equivalent: NestedForDepthCheck
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+
+use super::common::nesting_depth;
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: for nesting too deep.
+#[derive(Debug, Clone)]
+pub struct NestedForDepthViolation {
+    pub depth: usize,
+    pub max: usize,
+}
+
+impl Violation for NestedForDepthViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!(
+            "Nested for depth is {} (max allowed is {}).",
+            self.depth, self.max
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "nested.for.depth"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.depth.to_string(), self.max.to_string()]
+    }
+}
+
+/// Configuration for NestedForDepth rule.
+#[derive(Debug, Clone)]
+pub struct NestedForDepth {
+    /// Maximum allowed nesting depth (default: 1).
+    pub max: usize,
+}
+
+const RELEVANT_KINDS: &[&str] = &["for_statement", "enhanced_for_statement"];
+
+impl Default for NestedForDepth {
+    fn default() -> Self {
+        Self { max: 1 }
+    }
+}
+
+impl FromConfig for NestedForDepth {
+    const MODULE_NAME: &'static str = "NestedForDepth";
+
+    fn from_config(properties: &Properties) -> Self {
+        let max = properties
+            .get("max")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1);
+
+        Self { max }
+    }
+}
+
+impl Rule for NestedForDepth {
+    fn name(&self) -> &'static str {
+        "NestedForDepth"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        if !is_for(node) {
+            return vec![];
+        }
+
+        let depth = nesting_depth(node, is_for);
+
+        if depth > self.max {
+            return vec![Diagnostic::new(
+                NestedForDepthViolation {
+                    depth,
+                    max: self.max,
+                },
+                node.range(),
+            )];
+        }
+
+        vec![]
+    }
+}
+
+/// Whether `node` is a for loop of either kind.
+fn is_for(node: &CstNode) -> bool {
+    matches!(node.kind(), "for_statement" | "enhanced_for_statement")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+    use lintal_source_file::{LineIndex, SourceCode};
+
+    fn check_source(source: &str, max: usize) -> Vec<usize> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let rule = NestedForDepth { max };
+        let line_index = LineIndex::from_source_text(source);
+        let source_code = SourceCode::new(source, &line_index);
+
+        let mut violations = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            for d in rule.check(&ctx, &node) {
+                let loc = source_code.line_column(d.range.start());
+                violations.push(loc.line.get());
+            }
+        }
+        violations
+    }
+
+    #[test]
+    fn test_nested_for_exceeds_limit() {
+        let source = r#"
+class Foo {
+    void method(int[] values) {
+        for (int i = 0; i < 10; i++) {
+            for (int value : values) {
+                for (;;) {
+                    break;
+                }
+            }
+        }
+        for (int value : values) {
+        }
+    }
+}
+"#;
+        assert!(check_source(source, 2).is_empty());
+        assert_eq!(check_source(source, 1), [6]);
+        assert_eq!(check_source(source, 0), [5, 6]);
+    }
+}
@@ -0,0 +1,173 @@
+//! NestedIfDepth rule implementation.
+//!
+//! Checks that if-else statements are not nested too deeply. An `else if`
+//! continues its chain rather than opening a level.
+//!
+//! Checkstyle equivalent: NestedIfDepthCheck
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+
+use super::common::nesting_depth;
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: if-else nesting too deep.
+#[derive(Debug, Clone)]
+pub struct NestedIfDepthViolation {
+    pub depth: usize,
+    pub max: usize,
+}
+
+impl Violation for NestedIfDepthViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!(
+            "Nested if-else depth is {} (max allowed is {}).",
+            self.depth, self.max
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "nested.if.depth"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.depth.to_string(), self.max.to_string()]
+    }
+}
+
+/// Configuration for NestedIfDepth rule.
+#[derive(Debug, Clone)]
+pub struct NestedIfDepth {
+    /// Maximum allowed nesting depth (default: 1).
+    pub max: usize,
+}
+
+const RELEVANT_KINDS: &[&str] = &["if_statement"];
+
+impl Default for NestedIfDepth {
+    fn default() -> Self {
+        Self { max: 1 }
+    }
+}
+
+impl FromConfig for NestedIfDepth {
+    const MODULE_NAME: &'static str = "NestedIfDepth";
+
+    fn from_config(properties: &Properties) -> Self {
+        let max = properties
+            .get("max")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1);
+
+        Self { max }
+    }
+}
+
+impl Rule for NestedIfDepth {
+    fn name(&self) -> &'static str {
+        "NestedIfDepth"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        if node.kind() != "if_statement" || is_else_if(node) {
+            return vec![];
+        }
+
+        let depth = nesting_depth(node, |parent| {
+            parent.kind() == "if_statement" && !is_else_if(parent)
+        });
+
+        if depth > self.max {
+            return vec![Diagnostic::new(
+                NestedIfDepthViolation {
+                    depth,
+                    max: self.max,
+                },
+                node.range(),
+            )];
+        }
+
+        vec![]
+    }
+}
+
+/// Whether `node` is the `if` of an `else if`.
+fn is_else_if(node: &CstNode) -> bool {
+    node.parent()
+        .filter(|parent| parent.kind() == "if_statement")
+        .and_then(|parent| parent.child_by_field_name("alternative"))
+        .is_some_and(|alternative| alternative.range() == node.range())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+    use lintal_source_file::{LineIndex, SourceCode};
+
+    fn check_source(source: &str, max: usize) -> Vec<usize> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let rule = NestedIfDepth { max };
+        let line_index = LineIndex::from_source_text(source);
+        let source_code = SourceCode::new(source, &line_index);
+
+        let mut violations = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            for d in rule.check(&ctx, &node) {
+                let loc = source_code.line_column(d.range.start());
+                violations.push(loc.line.get());
+            }
+        }
+        violations
+    }
+
+    #[test]
+    fn test_nested_if_exceeds_limit() {
+        let source = r#"
+class Foo {
+    void method(boolean a, boolean b, boolean c) {
+        if (a) {
+            if (b) {
+                if (c) {
+                    doSomething();
+                }
+            }
+        }
+    }
+}
+"#;
+        assert!(check_source(source, 2).is_empty());
+        assert_eq!(check_source(source, 1), [6]);
+    }
+
+    #[test]
+    fn test_else_if_chain_is_one_level() {
+        let source = r#"
+class Foo {
+    void method(int a, boolean b) {
+        if (a == 1) {
+        } else if (a == 2) {
+        } else if (a == 3) {
+            if (b) {
+            } else if (!b) {
+                if (b) {
+                }
+            }
+        }
+    }
+}
+"#;
+        assert!(check_source(source, 2).is_empty());
+        assert_eq!(check_source(source, 1), [9]);
+        assert_eq!(check_source(source, 0), [7, 9]);
+    }
+}
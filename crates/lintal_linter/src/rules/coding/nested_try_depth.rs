@@ -4,9 +4,10 @@
 //!
 //! Checkstyle equivalent: NestedTryDepthCheck
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 
+use super::common::nesting_depth;
 use crate::{CheckContext, FromConfig, Properties, Rule};
 
 /// Violation: try nesting too deep.
@@ -25,6 +26,14 @@ impl Violation for NestedTryDepthViolation {
             self.depth, self.max
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "nested.try.depth"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.depth.to_string(), self.max.to_string()]
+    }
 }
 
 /// Configuration for NestedTryDepth rule.
@@ -70,16 +79,12 @@ impl Rule for NestedTryDepth {
             return vec![];
         }
 
-        // Count ancestor try statements
-        let mut depth = 0;
-        let mut current = node.parent();
-        while let Some(parent) = current {
-            let pk = parent.kind();
-            if pk == "try_statement" || pk == "try_with_resources_statement" {
-                depth += 1;
-            }
-            current = parent.parent();
-        }
+        let depth = nesting_depth(node, |parent| {
+            matches!(
+                parent.kind(),
+                "try_statement" | "try_with_resources_statement"
+            )
+        });
 
         if depth > self.max {
             return vec![Diagnostic::new(
@@ -4,7 +4,7 @@
 //!
 //! Checkstyle equivalent: OneStatementPerLineCheck
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 
 use crate::{CheckContext, FromConfig, Properties, Rule};
@@ -19,6 +19,10 @@ impl Violation for OneStatementPerLineViolation {
     fn message(&self) -> String {
         "Only one statement per line allowed.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "multiple.statements.line"))
+    }
 }
 
 /// Configuration for OneStatementPerLine rule.
@@ -0,0 +1,449 @@
+//! RequireThis rule implementation.
+//!
+//! Checks that references to instance fields and methods of the current
+//! object are qualified with `this.`.
+//!
+//! Checkstyle equivalent: RequireThisCheck
+
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::{CstNode, TreeWalker};
+use lintal_java_semantic::{
+    Reference, ReferenceKind, ScopeId, ScopeKind, SemanticModel, SymbolId, SymbolKind,
+};
+
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: reference to an instance field without `this.`.
+#[derive(Debug, Clone)]
+pub struct RequireThisVariableViolation {
+    pub name: String,
+    /// `Outer.` for a field of an enclosing class, otherwise empty.
+    pub prefix: String,
+}
+
+impl Violation for RequireThisVariableViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Always;
+
+    fn message(&self) -> String {
+        format!(
+            "Reference to instance variable '{}' needs \"{}this.\".",
+            self.name, self.prefix
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "require.this.variable"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.name.clone(), self.prefix.clone()]
+    }
+}
+
+/// Violation: call to an instance method without `this.`.
+#[derive(Debug, Clone)]
+pub struct RequireThisMethodViolation {
+    pub name: String,
+    /// `Outer.` for a method of an enclosing class, otherwise empty.
+    pub prefix: String,
+}
+
+impl Violation for RequireThisMethodViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Always;
+
+    fn message(&self) -> String {
+        format!(
+            "Method call to '{}' needs \"{}this.\".",
+            self.name, self.prefix
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "require.this.method"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.name.clone(), self.prefix.clone()]
+    }
+}
+
+/// Configuration for RequireThis rule.
+#[derive(Debug, Clone)]
+pub struct RequireThis {
+    /// Whether to check references to fields (default: true).
+    pub check_fields: bool,
+    /// Whether to check method calls (default: true).
+    pub check_methods: bool,
+    /// Whether to only flag assignments to a parameter or local that
+    /// overlaps a field, like `x = x` in a setter (default: true).
+    pub validate_only_overlapping: bool,
+}
+
+impl Default for RequireThis {
+    fn default() -> Self {
+        Self {
+            check_fields: true,
+            check_methods: true,
+            validate_only_overlapping: true,
+        }
+    }
+}
+
+const RELEVANT_KINDS: &[&str] = &["program"];
+
+impl FromConfig for RequireThis {
+    const MODULE_NAME: &'static str = "RequireThis";
+
+    fn from_config(properties: &Properties) -> Self {
+        let flag = |name: &str| properties.get(name).is_none_or(|v| *v != "false");
+        Self {
+            check_fields: flag("checkFields"),
+            check_methods: flag("checkMethods"),
+            validate_only_overlapping: flag("validateOnlyOverlapping"),
+        }
+    }
+}
+
+impl Rule for RequireThis {
+    fn name(&self) -> &'static str {
+        "RequireThis"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        let model = ctx.semantic(node);
+        model
+            .references()
+            .iter()
+            .filter(|reference| !reference.qualified_with_this)
+            .filter_map(|reference| self.check_reference(ctx, node, model, reference))
+            .collect()
+    }
+}
+
+impl RequireThis {
+    fn check_reference(
+        &self,
+        ctx: &CheckContext,
+        root: &CstNode,
+        model: &SemanticModel,
+        reference: &Reference,
+    ) -> Option<Diagnostic> {
+        let symbol = model.symbol(reference.resolved?);
+        match (reference.kind, symbol.kind) {
+            // A field used as is; `this.` changes nothing
+            (ReferenceKind::Variable, SymbolKind::Field)
+                if self.check_fields && !self.validate_only_overlapping && !symbol.is_static =>
+            {
+                let prefix = member_prefix(model, reference.scope, symbol.scope)?;
+                Some(
+                    Diagnostic::new(
+                        RequireThisVariableViolation {
+                            name: reference.name.clone(),
+                            prefix: prefix.clone(),
+                        },
+                        reference.range,
+                    )
+                    .with_fix(Fix::safe_edit(insert_this(&prefix, reference))),
+                )
+            }
+            // A parameter or local assigned where the field of the same name
+            // was likely meant; `this.` assigns the field instead
+            (ReferenceKind::Variable, SymbolKind::Parameter | SymbolKind::LocalVariable)
+                if self.check_fields && reference.access.is_write() =>
+            {
+                let field = overlapped_field(model, reference)?;
+                if !is_overlapping_assignment(ctx, root, reference)
+                    || assigns_with_this(model, reference, symbol.scope)
+                {
+                    return None;
+                }
+                let prefix = member_prefix(model, reference.scope, model.symbol(field).scope)?;
+                Some(
+                    Diagnostic::new(
+                        RequireThisVariableViolation {
+                            name: reference.name.clone(),
+                            prefix: prefix.clone(),
+                        },
+                        reference.range,
+                    )
+                    .with_fix(Fix::unsafe_edit(insert_this(&prefix, reference))),
+                )
+            }
+            (ReferenceKind::Method, SymbolKind::Method)
+                if self.check_methods && !self.validate_only_overlapping && !symbol.is_static =>
+            {
+                let prefix = member_prefix(model, reference.scope, symbol.scope)?;
+                Some(
+                    Diagnostic::new(
+                        RequireThisMethodViolation {
+                            name: reference.name.clone(),
+                            prefix: prefix.clone(),
+                        },
+                        reference.range,
+                    )
+                    .with_fix(Fix::safe_edit(insert_this(&prefix, reference))),
+                )
+            }
+            _ => None,
+        }
+    }
+}
+
+/// What qualifies `this` for a member of the type scope `owner` used in
+/// `scope`: nothing in the innermost type, `Outer.` in an enclosing one.
+/// `None` if there is no `this` there, or the type has no name to qualify
+/// it with.
+fn member_prefix(model: &SemanticModel, scope: ScopeId, owner: ScopeId) -> Option<String> {
+    let mut innermost = true;
+    for id in model.ancestors(scope) {
+        if id == owner {
+            if innermost {
+                return Some(String::new());
+            }
+            let range = model.scope(id).range;
+            let name = model
+                .symbols()
+                .iter()
+                .find(|symbol| symbol.kind == SymbolKind::Type && symbol.declaration == range)?;
+            return Some(format!("{}.", name.name));
+        }
+        let current = model.scope(id);
+        // Static methods, initializers and nested types have no `this` of
+        // the types around them
+        if current.is_static {
+            return None;
+        }
+        if current.kind == ScopeKind::Type {
+            innermost = false;
+        }
+    }
+    None
+}
+
+/// The instance field with the name of `reference`, in the nearest type
+/// around it that declares one.
+fn overlapped_field(model: &SemanticModel, reference: &Reference) -> Option<SymbolId> {
+    model
+        .ancestors(reference.scope)
+        .filter(|&id| model.scope(id).kind == ScopeKind::Type)
+        .find_map(|id| {
+            model
+                .symbols_in(id)
+                .find(|(_, symbol)| {
+                    symbol.kind == SymbolKind::Field
+                        && !symbol.is_static
+                        && symbol.name == reference.name
+                })
+                .map(|(id, _)| id)
+        })
+}
+
+/// Whether `reference` is the target of a compound assignment, or of a
+/// plain one whose value uses the same name, like `x = x`. Increments and
+/// decrements don't count, as in checkstyle.
+fn is_overlapping_assignment(ctx: &CheckContext, root: &CstNode, reference: &Reference) -> bool {
+    let Some(target) = root
+        .inner()
+        .named_descendant_for_byte_range(
+            reference.range.start().into(),
+            reference.range.end().into(),
+        )
+        .map(|node| CstNode::new(node, ctx.source()))
+    else {
+        return false;
+    };
+    let Some(assignment) = std::iter::successors(target.parent(), CstNode::parent)
+        .find(|node| node.kind() != "parenthesized_expression")
+        .filter(|node| node.kind() == "assignment_expression")
+    else {
+        return false;
+    };
+
+    let compound = assignment
+        .children()
+        .any(|child| child.kind() != "=" && child.kind().ends_with('='));
+    compound
+        || assignment
+            .child_by_field_name("right")
+            .is_some_and(|right| {
+                TreeWalker::new(right.inner(), ctx.source())
+                    .any(|node| node.kind() == "identifier" && node.text() == reference.name)
+            })
+}
+
+/// Whether the method or block declaring the assigned variable also assigns
+/// the field through `this.`, which shows the unqualified name is meant.
+fn assigns_with_this(model: &SemanticModel, reference: &Reference, frame: ScopeId) -> bool {
+    let frame = model.scope(frame).range;
+    model.references().iter().any(|other| {
+        other.qualified_with_this
+            && other.name == reference.name
+            && other.access.is_write()
+            && frame.contains_range(other.range)
+    })
+}
+
+/// Insert `this.` (or `Outer.this.`) before the name of `reference`.
+fn insert_this(prefix: &str, reference: &Reference) -> Edit {
+    Edit::insertion(format!("{prefix}this."), reference.range.start())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_diagnostics::Applicability;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, rule: &RequireThis) -> Vec<Diagnostic> {
+        let result = JavaParser::new().parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        TreeWalker::new(result.tree.root_node(), source)
+            .flat_map(|node| rule.check(&ctx, &node))
+            .collect()
+    }
+
+    fn messages(source: &str, rule: &RequireThis) -> Vec<String> {
+        check_source(source, rule)
+            .into_iter()
+            .map(|d| d.kind.body)
+            .collect()
+    }
+
+    fn all_references() -> RequireThis {
+        RequireThis {
+            validate_only_overlapping: false,
+            ..RequireThis::default()
+        }
+    }
+
+    #[test]
+    fn test_overlapping_assignment() {
+        let source = r#"
+class Point {
+    private int x;
+    private int y;
+
+    void setX(int x) {
+        x = x;
+    }
+
+    void setY(int y) {
+        this.y = y;
+    }
+
+    void move(int x) {
+        x += 1;
+        x++;
+    }
+}
+"#;
+        let diagnostics = check_source(source, &RequireThis::default());
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| d.kind.body.as_str())
+                .collect::<Vec<_>>(),
+            [
+                "Reference to instance variable 'x' needs \"this.\".",
+                "Reference to instance variable 'x' needs \"this.\"."
+            ]
+        );
+        // Assigning the field instead of the parameter changes behavior
+        let fix = diagnostics[0].fix.as_ref().unwrap();
+        assert_eq!(fix.edits()[0].content(), Some("this."));
+        assert_eq!(fix.applicability(), Applicability::Unsafe);
+    }
+
+    #[test]
+    fn test_unqualified_fields_and_methods() {
+        let source = r#"
+class Counter {
+    private static int instances;
+    private int count;
+
+    void increment() {
+        count++;
+        log();
+        instances++;
+        this.count++;
+    }
+
+    void log() {}
+
+    static void reset() {
+        instances = 0;
+    }
+}
+"#;
+        assert!(check_source(source, &RequireThis::default()).is_empty());
+
+        let diagnostics = check_source(source, &all_references());
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| d.kind.body.as_str())
+                .collect::<Vec<_>>(),
+            [
+                "Reference to instance variable 'count' needs \"this.\".",
+                "Method call to 'log' needs \"this.\"."
+            ]
+        );
+        assert!(
+            diagnostics
+                .iter()
+                .all(|d| d.fix.as_ref().unwrap().applicability() == Applicability::Safe)
+        );
+
+        let rule = RequireThis {
+            check_methods: false,
+            ..all_references()
+        };
+        assert_eq!(check_source(source, &rule).len(), 1);
+    }
+
+    #[test]
+    fn test_outer_class_members() {
+        let source = r#"
+class Outer {
+    int value;
+
+    class Inner {
+        int get() {
+            return value;
+        }
+    }
+
+    static class Nested {
+        void use(Outer outer) {
+            outer.value = 1;
+        }
+    }
+}
+"#;
+        let diagnostics = check_source(source, &all_references());
+        assert_eq!(
+            messages(source, &all_references()),
+            ["Reference to instance variable 'value' needs \"Outer.this.\"."]
+        );
+        let fix = diagnostics[0].fix.as_ref().unwrap();
+        assert_eq!(fix.edits()[0].content(), Some("Outer.this."));
+    }
+
+    #[test]
+    fn test_from_config() {
+        let rule = RequireThis::from_config(&Properties::new());
+        assert!(rule.check_fields && rule.check_methods && rule.validate_only_overlapping);
+
+        let mut props = Properties::new();
+        props.insert("checkMethods", "false");
+        props.insert("validateOnlyOverlapping", "false");
+        let rule = RequireThis::from_config(&props);
+        assert!(rule.check_fields);
+        assert!(!rule.check_methods);
+        assert!(!rule.validate_only_overlapping);
+    }
+}
@@ -0,0 +1,250 @@
+//! ReturnCount rule implementation.
+//!
+//! Checks that methods, constructors and lambdas don't have too many return
+//! statements.
+//!
+//! Checkstyle equivalent: ReturnCountCheck
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+use regex::Regex;
+
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: too many return statements.
+#[derive(Debug, Clone)]
+pub struct ReturnCountViolation {
+    pub count: usize,
+    pub max: usize,
+    /// Whether the returns have no value, so `maxForVoid` applies.
+    pub void: bool,
+}
+
+impl Violation for ReturnCountViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        let kind = if self.void {
+            "void methods/constructors/lambdas"
+        } else {
+            "non-void methods/lambdas"
+        };
+        format!(
+            "Return count is {} (max allowed for {} is {}).",
+            self.count, kind, self.max
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        let key = if self.void {
+            "return.countVoid"
+        } else {
+            "return.count"
+        };
+        Some(MessageKey::new("coding", key))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.count.to_string(), self.max.to_string()]
+    }
+}
+
+/// Configuration for ReturnCount rule.
+#[derive(Debug, Clone)]
+pub struct ReturnCount {
+    /// Maximum returns in a non-void method or lambda (default: 2).
+    pub max: usize,
+    /// Maximum returns in a void method, constructor or lambda (default: 1).
+    pub max_for_void: usize,
+    /// Names of methods to leave unchecked (default: `^equals$`).
+    pub format: Regex,
+}
+
+const RELEVANT_KINDS: &[&str] = &[
+    "method_declaration",
+    "constructor_declaration",
+    "lambda_expression",
+];
+
+impl Default for ReturnCount {
+    fn default() -> Self {
+        Self {
+            max: 2,
+            max_for_void: 1,
+            format: Regex::new("^equals$").unwrap(),
+        }
+    }
+}
+
+impl FromConfig for ReturnCount {
+    const MODULE_NAME: &'static str = "ReturnCount";
+
+    fn from_config(properties: &Properties) -> Self {
+        let default = Self::default();
+
+        let max = properties
+            .get("max")
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(default.max);
+        let max_for_void = properties
+            .get("maxForVoid")
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(default.max_for_void);
+        let format = properties
+            .get("format")
+            .and_then(|v| Regex::new(v).ok())
+            .unwrap_or(default.format);
+
+        Self {
+            max,
+            max_for_void,
+            format,
+        }
+    }
+}
+
+impl Rule for ReturnCount {
+    fn name(&self) -> &'static str {
+        "ReturnCount"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        if !is_container(node) {
+            return vec![];
+        }
+        if node.kind() == "method_declaration"
+            && node
+                .child_by_field_name("name")
+                .is_some_and(|name| self.format.is_match(name.text()))
+        {
+            return vec![];
+        }
+        let Some(body) = node.child_by_field_name("body") else {
+            return vec![];
+        };
+
+        let mut returns = vec![];
+        collect_returns(&body, &mut returns);
+        // Like checkstyle, the last return decides whether this is void
+        let Some(last) = returns.last() else {
+            return vec![];
+        };
+        let void = last.named_children().next().is_none();
+        let max = if void { self.max_for_void } else { self.max };
+        if returns.len() <= max {
+            return vec![];
+        }
+
+        // A lambda is reported on its `->`, where checkstyle places it
+        let at = match node.kind() {
+            "lambda_expression" => node.children().find(|c| c.kind() == "->").unwrap_or(*node),
+            _ => *node,
+        };
+        vec![Diagnostic::new(
+            ReturnCountViolation {
+                count: returns.len(),
+                max,
+                void,
+            },
+            at.range(),
+        )]
+    }
+}
+
+/// Whether `node` has return statements of its own.
+fn is_container(node: &CstNode) -> bool {
+    RELEVANT_KINDS.contains(&node.kind())
+}
+
+/// The return statements under `node`, leaving out those of nested lambdas
+/// and classes.
+fn collect_returns<'a>(node: &CstNode<'a>, returns: &mut Vec<CstNode<'a>>) {
+    for child in node.named_children() {
+        match child.kind() {
+            "return_statement" => returns.push(child),
+            "class_body" => {}
+            _ if is_container(&child) => {}
+            _ => collect_returns(&child, returns),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, rule: &ReturnCount) -> Vec<String> {
+        let result = JavaParser::new().parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        TreeWalker::new(result.tree.root_node(), source)
+            .flat_map(|node| rule.check(&ctx, &node))
+            .map(|d| d.kind.body)
+            .collect()
+    }
+
+    #[test]
+    fn test_return_count() {
+        let source = r#"
+class Foo {
+    int value(int a) {
+        if (a == 1) {
+            return 1;
+        }
+        Runnable r = () -> {
+            if (a == 2) {
+                return;
+            }
+            return;
+        };
+        if (a == 3) {
+            return 3;
+        }
+        return 0;
+    }
+
+    void run(boolean a) {
+        if (a) {
+            return;
+        }
+    }
+
+    public boolean equals(Object other) {
+        if (other == null) {
+            return false;
+        }
+        if (other == this) {
+            return true;
+        }
+        return false;
+    }
+}
+"#;
+        assert_eq!(
+            check_source(source, &ReturnCount::default()),
+            [
+                "Return count is 3 (max allowed for non-void methods/lambdas is 2).",
+                "Return count is 2 (max allowed for void methods/constructors/lambdas is 1)."
+            ]
+        );
+
+        // With no method names ignored, equals is checked too
+        let rule = ReturnCount {
+            max_for_void: 2,
+            format: Regex::new("^$").unwrap(),
+            ..ReturnCount::default()
+        };
+        assert_eq!(
+            check_source(source, &rule),
+            [
+                "Return count is 3 (max allowed for non-void methods/lambdas is 2).",
+                "Return count is 3 (max allowed for non-void methods/lambdas is 2)."
+            ]
+        );
+    }
+}
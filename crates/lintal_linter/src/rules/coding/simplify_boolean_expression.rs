@@ -4,7 +4,7 @@
 //!
 //! Checkstyle equivalent: SimplifyBooleanExpressionCheck
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 
 use crate::{CheckContext, FromConfig, Properties, Rule};
@@ -24,6 +24,10 @@ impl Violation for SimplifyBooleanExpressionViolation {
             self.suggestion
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "simplify.expression"))
+    }
 }
 
 /// Violation: expression is always true or false.
@@ -4,7 +4,7 @@
 //!
 //! Checkstyle equivalent: SimplifyBooleanReturnCheck
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 
 use crate::{CheckContext, FromConfig, Properties, Rule};
@@ -19,6 +19,10 @@ impl Violation for SimplifyBooleanReturnViolation {
     fn message(&self) -> String {
         "Conditional logic can be removed.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "simplify.boolReturn"))
+    }
 }
 
 /// Configuration for SimplifyBooleanReturn rule.
@@ -5,7 +5,7 @@
 //!
 //! Checkstyle equivalent: StringLiteralEqualityCheck
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use tree_sitter::Node;
 
@@ -13,7 +13,9 @@ use crate::{CheckContext, FromConfig, Properties, Rule};
 
 /// Violation: string literals should be compared with equals(), not ==.
 #[derive(Debug, Clone)]
-pub struct StringLiteralEqualityViolation;
+pub struct StringLiteralEqualityViolation {
+    pub operator: String,
+}
 
 impl Violation for StringLiteralEqualityViolation {
     const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
@@ -21,6 +23,14 @@ impl Violation for StringLiteralEqualityViolation {
     fn message(&self) -> String {
         "Literal Strings should be compared using equals(), not '=='.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "string.literal.equality"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.operator.clone()]
+    }
 }
 
 /// Configuration for StringLiteralEquality rule.
@@ -106,7 +116,12 @@ impl Rule for StringLiteralEquality {
         };
 
         // Report at operator position, but fix the whole expression
-        let diagnostic = Diagnostic::new(StringLiteralEqualityViolation, operator_range);
+        let diagnostic = Diagnostic::new(
+            StringLiteralEqualityViolation {
+                operator: op_text.to_string(),
+            },
+            operator_range,
+        );
 
         match fix {
             Some(replacement) => {
@@ -0,0 +1,408 @@
+//! DesignForExtension rule implementation.
+//!
+//! Checks that classes which can be subclassed document how to override
+//! their overridable methods, or make them unoverridable.
+//!
+//! Checkstyle equivalent: DesignForExtensionCheck
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+use regex::Regex;
+
+use crate::rules::javadoc::common::{Scope, javadoc_before, parse_list, surrounding_scope};
+use crate::rules::modifier::common::has_modifier;
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: overridable method without Javadoc explaining how to extend it.
+#[derive(Debug, Clone)]
+pub struct DesignForExtensionViolation {
+    pub class_name: String,
+    pub method_name: String,
+}
+
+impl Violation for DesignForExtensionViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!(
+            "Class '{0}' looks like designed for extension (can be subclassed), but the method \
+             '{1}' does not have javadoc that explains how to do that safely. If class is not \
+             designed for extension consider making the class '{0}' final or making the method \
+             '{1}' static/final/abstract/private.",
+            self.class_name, self.method_name
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("design", "design.forExtension"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.class_name.clone(), self.method_name.clone()]
+    }
+}
+
+/// Configuration for DesignForExtension rule.
+#[derive(Debug, Clone)]
+pub struct DesignForExtension {
+    /// Annotations exempting a method, by simple name (default: JUnit's
+    /// `After`, `AfterClass`, `Before`, `BeforeClass` and `Test`).
+    pub ignored_annotations: Vec<String>,
+    /// What the Javadoc of an overridable method must contain (default: `.*`).
+    pub required_javadoc_phrase: Regex,
+}
+
+const DEFAULT_IGNORED_ANNOTATIONS: &[&str] =
+    &["After", "AfterClass", "Before", "BeforeClass", "Test"];
+
+const RELEVANT_KINDS: &[&str] = &["method_declaration"];
+
+impl Default for DesignForExtension {
+    fn default() -> Self {
+        Self {
+            ignored_annotations: DEFAULT_IGNORED_ANNOTATIONS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            required_javadoc_phrase: Regex::new(".*").unwrap(),
+        }
+    }
+}
+
+impl FromConfig for DesignForExtension {
+    const MODULE_NAME: &'static str = "DesignForExtension";
+
+    fn from_config(properties: &Properties) -> Self {
+        let default = Self::default();
+
+        let ignored_annotations = properties
+            .get("ignoredAnnotations")
+            .map(|v| parse_list(v))
+            .unwrap_or(default.ignored_annotations);
+
+        let required_javadoc_phrase = properties
+            .get("requiredJavadocPhrase")
+            .and_then(|v| Regex::new(v).ok())
+            .unwrap_or(default.required_javadoc_phrase);
+
+        Self {
+            ignored_annotations,
+            required_javadoc_phrase,
+        }
+    }
+}
+
+impl Rule for DesignForExtension {
+    fn name(&self) -> &'static str {
+        "DesignForExtension"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        if node.kind() != "method_declaration" || !can_be_overridden(node) {
+            return vec![];
+        }
+
+        // Abstract methods aren't overridable here, so a method without
+        // body is native; an empty body is meant to be overridden
+        let is_native = modifiers_of(node).is_some_and(|m| has_modifier(&m, "native"));
+        if !is_native
+            && node
+                .child_by_field_name("body")
+                .is_none_or(|body| is_empty_body(&body))
+        {
+            return vec![];
+        }
+
+        if self.has_ignored_annotation(node) || self.has_javadoc(ctx, node) {
+            return vec![];
+        }
+
+        let Some(class) = std::iter::successors(node.parent(), CstNode::parent).find(|n| {
+            matches!(
+                n.kind(),
+                "class_declaration" | "enum_declaration" | "record_declaration"
+            )
+        }) else {
+            return vec![];
+        };
+        if !can_be_subclassed(&class) {
+            return vec![];
+        }
+
+        let (Some(class_name), Some(method_name)) = (
+            class.child_by_field_name("name"),
+            node.child_by_field_name("name"),
+        ) else {
+            return vec![];
+        };
+
+        vec![Diagnostic::new(
+            DesignForExtensionViolation {
+                class_name: class_name.text().to_string(),
+                method_name: method_name.text().to_string(),
+            },
+            node.range(),
+        )]
+    }
+}
+
+impl DesignForExtension {
+    /// Whether the method is annotated with one of the ignored annotations,
+    /// compared by simple name.
+    fn has_ignored_annotation(&self, node: &CstNode) -> bool {
+        modifiers_of(node)
+            .into_iter()
+            .flat_map(|modifiers| modifiers.children())
+            .filter(|child| matches!(child.kind(), "annotation" | "marker_annotation"))
+            .filter_map(|annotation| annotation.child_by_field_name("name"))
+            .any(|name| {
+                let simple_name = name.text().rsplit('.').next().unwrap_or_default().trim();
+                self.ignored_annotations.iter().any(|a| a == simple_name)
+            })
+    }
+
+    /// Whether the method has Javadoc containing the required phrase.
+    fn has_javadoc(&self, ctx: &CheckContext, node: &CstNode) -> bool {
+        javadoc_before(ctx, node).is_some_and(|javadoc| {
+            let content = javadoc
+                .text
+                .trim_start_matches("/**")
+                .trim_end_matches("*/");
+            self.required_javadoc_phrase.is_match(content)
+        })
+    }
+}
+
+fn modifiers_of<'a>(node: &CstNode<'a>) -> Option<CstNode<'a>> {
+    node.children().find(|c| c.kind() == "modifiers")
+}
+
+/// Whether a subclass outside the package could override the method: it
+/// is in a public or protected class, and not private, abstract, final or
+/// static. Methods of interfaces, records and anonymous classes can't be.
+fn can_be_overridden(node: &CstNode) -> bool {
+    let in_protected_scope =
+        surrounding_scope(node).is_some_and(|scope| scope.is_in(Scope::Protected));
+    let in_class_body = node
+        .parent()
+        .and_then(|body| match body.kind() {
+            "class_body" => body.parent(),
+            "enum_body_declarations" => body.parent().and_then(|b| b.parent()),
+            _ => None,
+        })
+        .is_some_and(|owner| matches!(owner.kind(), "class_declaration" | "enum_declaration"));
+
+    in_protected_scope
+        && in_class_body
+        && !modifiers_of(node).is_some_and(|modifiers| {
+            ["private", "abstract", "final", "static"]
+                .iter()
+                .any(|keyword| has_modifier(&modifiers, keyword))
+        })
+}
+
+/// Whether a method body holds nothing but comments.
+fn is_empty_body(body: &CstNode) -> bool {
+    body.children()
+        .all(|child| matches!(child.kind(), "{" | "}" | "line_comment" | "block_comment"))
+}
+
+/// Whether the class can be subclassed: not an enum, record or final class,
+/// and with a constructor a subclass can call.
+fn can_be_subclassed(class: &CstNode) -> bool {
+    if class.kind() != "class_declaration"
+        || modifiers_of(class).is_some_and(|m| has_modifier(&m, "final"))
+    {
+        return false;
+    }
+
+    let mut constructors = class
+        .child_by_field_name("body")
+        .into_iter()
+        .flat_map(|body| body.children())
+        .filter(|member| member.kind() == "constructor_declaration")
+        .peekable();
+
+    // Without constructors, the class has a default one
+    constructors.peek().is_none()
+        || constructors
+            .any(|ctor| !modifiers_of(&ctor).is_some_and(|m| has_modifier(&m, "private")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, rule: &DesignForExtension) -> Vec<String> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+
+        let mut methods = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            for d in rule.check(&ctx, &node) {
+                methods.push(d.kind.body.split('\'').nth(3).unwrap().to_string());
+            }
+        }
+        methods
+    }
+
+    #[test]
+    fn test_overridable_methods() {
+        let source = r#"
+public class Foo {
+    public int a() {
+        return 1;
+    }
+
+    protected void b() {
+        a();
+    }
+
+    void c() {
+        a();
+    }
+
+    public void empty() {
+        // nothing to do
+    }
+
+    public final void d() {
+        a();
+    }
+
+    private void e() {
+        a();
+    }
+
+    public static void f() {
+        System.out.println();
+    }
+
+    /**
+     * Subclasses may override this to change the result.
+     */
+    public int g() {
+        return 1;
+    }
+}
+"#;
+        assert_eq!(
+            check_source(source, &DesignForExtension::default()),
+            vec!["a", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn test_message() {
+        let source = "public class Foo { public int a() { return 1; } }";
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let rule = DesignForExtension::default();
+        let diagnostics: Vec<_> = TreeWalker::new(result.tree.root_node(), source)
+            .flat_map(|node| rule.check(&ctx, &node))
+            .collect();
+        assert_eq!(
+            diagnostics[0].kind.body,
+            "Class 'Foo' looks like designed for extension (can be subclassed), but the method \
+             'a' does not have javadoc that explains how to do that safely. If class is not \
+             designed for extension consider making the class 'Foo' final or making the method \
+             'a' static/final/abstract/private."
+        );
+    }
+
+    #[test]
+    fn test_classes_that_cannot_be_subclassed() {
+        let source = r#"
+public final class Foo {
+    public int a() { return 1; }
+}
+
+public class Bar {
+    private Bar() {}
+    public int a() { return 1; }
+}
+
+public enum Baz {
+    A;
+    public int a() { return 1; }
+}
+
+public interface Qux {
+    default int a() { return 1; }
+}
+
+public record Quux(int x) {
+    public int a() { return 1; }
+}
+"#;
+        assert!(check_source(source, &DesignForExtension::default()).is_empty());
+    }
+
+    #[test]
+    fn test_package_private_and_anonymous_classes_skipped() {
+        let source = r#"
+class Foo {
+    public int a() { return 1; }
+}
+
+public class Bar {
+    Object o = new Object() {
+        public String toString() { return ""; }
+    };
+}
+"#;
+        assert!(check_source(source, &DesignForExtension::default()).is_empty());
+    }
+
+    #[test]
+    fn test_ignored_annotations() {
+        let source = r#"
+public class Foo {
+    @Test
+    public void a() { run(); }
+
+    @org.junit.Before
+    public void b() { run(); }
+
+    @Override
+    public String toString() { return ""; }
+}
+"#;
+        assert_eq!(
+            check_source(source, &DesignForExtension::default()),
+            vec!["toString"]
+        );
+
+        let rule = DesignForExtension {
+            ignored_annotations: vec!["Override".to_string()],
+            ..DesignForExtension::default()
+        };
+        assert_eq!(check_source(source, &rule), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_required_javadoc_phrase() {
+        let source = r#"
+public class Foo {
+    /** Returns one. */
+    public int a() { return 1; }
+
+    /** This implementation returns two. */
+    public int b() { return 2; }
+}
+"#;
+        let rule = DesignForExtension {
+            required_javadoc_phrase: Regex::new("This implementation").unwrap(),
+            ..DesignForExtension::default()
+        };
+        assert_eq!(check_source(source, &rule), vec!["a"]);
+    }
+}
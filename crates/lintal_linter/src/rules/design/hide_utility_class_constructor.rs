@@ -5,7 +5,7 @@
 //!
 //! Checkstyle equivalent: HideUtilityClassConstructorCheck
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 
 use crate::{CheckContext, FromConfig, Properties, Rule};
@@ -20,6 +20,10 @@ impl Violation for HideUtilityClassConstructorViolation {
     fn message(&self) -> String {
         "Utility classes should not have a public or default constructor.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("design", "hide.utility.class"))
+    }
 }
 
 /// Configuration for HideUtilityClassConstructor rule.
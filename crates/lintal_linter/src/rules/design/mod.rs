@@ -1,7 +1,13 @@
 //! Design rules (HideUtilityClassConstructor, MutableException, etc.)
 
+mod design_for_extension;
 mod hide_utility_class_constructor;
 mod mutable_exception;
+mod one_top_level_class;
+mod visibility_modifier;
 
+pub use design_for_extension::DesignForExtension;
 pub use hide_utility_class_constructor::HideUtilityClassConstructor;
 pub use mutable_exception::MutableException;
+pub use one_top_level_class::OneTopLevelClass;
+pub use visibility_modifier::VisibilityModifier;
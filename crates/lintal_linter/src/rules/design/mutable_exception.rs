@@ -4,7 +4,7 @@
 //!
 //! Checkstyle equivalent: MutableExceptionCheck
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use regex::Regex;
 
@@ -22,6 +22,14 @@ impl Violation for MutableExceptionViolation {
     fn message(&self) -> String {
         format!("The field '{}' must be declared final.", self.name)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("design", "mutable.exception"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
 }
 
 /// Configuration for MutableException rule.
@@ -0,0 +1,172 @@
+//! OneTopLevelClass rule implementation.
+//!
+//! Checks that each source file declares only one top-level type.
+//!
+//! Checkstyle equivalent: OneTopLevelClassCheck
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+
+use crate::rules::modifier::common::has_modifier;
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: a top-level type that is not the file's main one.
+#[derive(Debug, Clone)]
+pub struct OneTopLevelClassViolation {
+    pub type_name: String,
+}
+
+impl Violation for OneTopLevelClassViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!(
+            "Top-level class {} has to reside in its own source file.",
+            self.type_name
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("design", "one.top.level.class"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.type_name.clone()]
+    }
+}
+
+/// Configuration for OneTopLevelClass rule.
+#[derive(Debug, Clone, Default)]
+pub struct OneTopLevelClass;
+
+const RELEVANT_KINDS: &[&str] = &["program"];
+
+const TYPE_DECLARATIONS: &[&str] = &[
+    "class_declaration",
+    "interface_declaration",
+    "enum_declaration",
+    "record_declaration",
+    "annotation_type_declaration",
+];
+
+impl FromConfig for OneTopLevelClass {
+    const MODULE_NAME: &'static str = "OneTopLevelClass";
+
+    fn from_config(_properties: &Properties) -> Self {
+        Self
+    }
+}
+
+impl Rule for OneTopLevelClass {
+    fn name(&self) -> &'static str {
+        "OneTopLevelClass"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        let types: Vec<CstNode> = node
+            .children()
+            .filter(|child| TYPE_DECLARATIONS.contains(&child.kind()))
+            .collect();
+
+        let is_public = |declaration: &CstNode| {
+            declaration
+                .children()
+                .find(|c| c.kind() == "modifiers")
+                .is_some_and(|modifiers| has_modifier(&modifiers, "public"))
+        };
+
+        // The public type is the file's main one; without one, the first
+        // type is
+        let main = types
+            .iter()
+            .position(is_public)
+            .or((!types.is_empty()).then_some(0));
+
+        types
+            .iter()
+            .enumerate()
+            .filter(|(i, declaration)| Some(*i) != main && !is_public(declaration))
+            .filter_map(|(_, declaration)| {
+                let name = declaration.child_by_field_name("name")?;
+                Some(Diagnostic::new(
+                    OneTopLevelClassViolation {
+                        type_name: name.text().to_string(),
+                    },
+                    declaration.range(),
+                ))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str) -> Vec<String> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let rule = OneTopLevelClass;
+
+        let mut messages = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            for d in rule.check(&ctx, &node) {
+                messages.push(d.kind.body);
+            }
+        }
+        messages
+    }
+
+    #[test]
+    fn test_single_type_no_violation() {
+        let source = r#"
+public class Foo {
+    class Inner {}
+}
+"#;
+        assert!(check_source(source).is_empty());
+    }
+
+    #[test]
+    fn test_types_besides_public_one() {
+        let source = r#"
+class Bar {}
+
+public class Foo {}
+
+interface Baz {}
+"#;
+        assert_eq!(
+            check_source(source),
+            vec![
+                "Top-level class Bar has to reside in its own source file.",
+                "Top-level class Baz has to reside in its own source file.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_first_type_is_main_without_public_one() {
+        let source = r#"
+class Foo {}
+
+enum Bar { A }
+
+record Baz(int x) {}
+"#;
+        assert_eq!(
+            check_source(source),
+            vec![
+                "Top-level class Bar has to reside in its own source file.",
+                "Top-level class Baz has to reside in its own source file.",
+            ]
+        );
+    }
+}
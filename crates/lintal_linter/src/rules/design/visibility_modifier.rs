@@ -0,0 +1,510 @@
+//! VisibilityModifier rule implementation.
+//!
+//! Checks that fields are private and accessed through methods, unless they
+//! are `static final` or allowed by the configuration.
+//!
+//! Checkstyle equivalent: VisibilityModifierCheck
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+use regex::Regex;
+
+use crate::rules::imports::common::{ImportInfo, imports};
+use crate::rules::javadoc::common::parse_list;
+use crate::rules::modifier::common::has_modifier;
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: field must be private.
+#[derive(Debug, Clone)]
+pub struct VisibilityModifierViolation {
+    pub name: String,
+}
+
+impl Violation for VisibilityModifierViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!(
+            "Variable '{}' must be private and have accessor methods.",
+            self.name
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("design", "variable.notPrivate"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
+}
+
+/// Configuration for VisibilityModifier rule.
+#[derive(Debug, Clone)]
+pub struct VisibilityModifier {
+    /// Whether package-private fields are allowed (default: false).
+    pub package_allowed: bool,
+    /// Whether protected fields are allowed (default: false).
+    pub protected_allowed: bool,
+    /// Names of public fields that are allowed (default: `^serialVersionUID$`).
+    pub public_member_pattern: Regex,
+    /// Whether final fields are allowed whatever their type (default: false).
+    pub allow_public_final_fields: bool,
+    /// Whether final fields of an immutable type are allowed in final classes
+    /// (default: false).
+    pub allow_public_immutable_fields: bool,
+    /// The immutable types, by canonical name.
+    pub immutable_class_canonical_names: Vec<String>,
+    /// Annotations exempting a field, by canonical name.
+    pub ignore_annotation_canonical_names: Vec<String>,
+}
+
+const DEFAULT_PUBLIC_MEMBER_PATTERN: &str = "^serialVersionUID$";
+
+const DEFAULT_IMMUTABLE_CLASS_CANONICAL_NAMES: &[&str] = &[
+    "java.io.File",
+    "java.lang.Boolean",
+    "java.lang.Byte",
+    "java.lang.Character",
+    "java.lang.Double",
+    "java.lang.Float",
+    "java.lang.Integer",
+    "java.lang.Long",
+    "java.lang.Short",
+    "java.lang.StackTraceElement",
+    "java.lang.String",
+    "java.math.BigDecimal",
+    "java.math.BigInteger",
+    "java.net.Inet4Address",
+    "java.net.Inet6Address",
+    "java.net.InetSocketAddress",
+    "java.net.URI",
+    "java.net.URL",
+    "java.util.Locale",
+    "java.util.UUID",
+];
+
+const DEFAULT_IGNORE_ANNOTATION_CANONICAL_NAMES: &[&str] = &[
+    "org.junit.Rule",
+    "org.junit.ClassRule",
+    "com.google.common.annotations.VisibleForTesting",
+];
+
+const RELEVANT_KINDS: &[&str] = &["field_declaration"];
+
+impl Default for VisibilityModifier {
+    fn default() -> Self {
+        Self {
+            package_allowed: false,
+            protected_allowed: false,
+            public_member_pattern: Regex::new(DEFAULT_PUBLIC_MEMBER_PATTERN).unwrap(),
+            allow_public_final_fields: false,
+            allow_public_immutable_fields: false,
+            immutable_class_canonical_names: DEFAULT_IMMUTABLE_CLASS_CANONICAL_NAMES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            ignore_annotation_canonical_names: DEFAULT_IGNORE_ANNOTATION_CANONICAL_NAMES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl FromConfig for VisibilityModifier {
+    const MODULE_NAME: &'static str = "VisibilityModifier";
+
+    fn from_config(properties: &Properties) -> Self {
+        let default = Self::default();
+        let flag = |name: &str, default: bool| {
+            properties
+                .get(name)
+                .map(|v| v.trim() == "true")
+                .unwrap_or(default)
+        };
+
+        let public_member_pattern = properties
+            .get("publicMemberPattern")
+            .and_then(|v| Regex::new(v).ok())
+            .unwrap_or(default.public_member_pattern);
+
+        let immutable_class_canonical_names = properties
+            .get("immutableClassCanonicalNames")
+            .map(|v| parse_list(v))
+            .unwrap_or(default.immutable_class_canonical_names);
+
+        let ignore_annotation_canonical_names = properties
+            .get("ignoreAnnotationCanonicalNames")
+            .map(|v| parse_list(v))
+            .unwrap_or(default.ignore_annotation_canonical_names);
+
+        Self {
+            package_allowed: flag("packageAllowed", default.package_allowed),
+            protected_allowed: flag("protectedAllowed", default.protected_allowed),
+            public_member_pattern,
+            allow_public_final_fields: flag(
+                "allowPublicFinalFields",
+                default.allow_public_final_fields,
+            ),
+            allow_public_immutable_fields: flag(
+                "allowPublicImmutableFields",
+                default.allow_public_immutable_fields,
+            ),
+            immutable_class_canonical_names,
+            ignore_annotation_canonical_names,
+        }
+    }
+}
+
+impl Rule for VisibilityModifier {
+    fn name(&self) -> &'static str {
+        "VisibilityModifier"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        // Fields of anonymous classes aren't checked; those of interfaces
+        // and annotations are constants, not field declarations
+        let Some(body) = node.parent() else {
+            return vec![];
+        };
+        let owner = match body.kind() {
+            "class_body" => body.parent(),
+            // The members of an enum follow its constants
+            "enum_body_declarations" => body.parent().and_then(|b| b.parent()),
+            _ => None,
+        };
+        let Some(owner) = owner.filter(|o| o.kind() != "object_creation_expression") else {
+            return vec![];
+        };
+
+        let root = std::iter::successors(Some(*node), CstNode::parent)
+            .last()
+            .unwrap_or(*node);
+        let imports = imports(ctx, &root);
+
+        let modifiers = node.children().find(|c| c.kind() == "modifiers");
+        let has = |keyword: &str| modifiers.is_some_and(|m| has_modifier(&m, keyword));
+
+        if self.has_ignore_annotation(modifiers.as_ref(), imports) {
+            return vec![];
+        }
+
+        let scope = if has("public") {
+            "public"
+        } else if has("protected") {
+            "protected"
+        } else if has("private") {
+            return vec![];
+        } else {
+            "package"
+        };
+
+        let is_final = has("final");
+        if (has("static") && is_final)
+            || (self.package_allowed && scope == "package")
+            || (self.protected_allowed && scope == "protected")
+            || (self.allow_public_final_fields && is_final)
+            || (self.allow_public_immutable_fields
+                && is_final
+                && is_final_class(&owner)
+                && node
+                    .child_by_field_name("type")
+                    .is_some_and(|ty| self.is_immutable_type(&ty, imports)))
+        {
+            return vec![];
+        }
+
+        node.children()
+            .filter(|c| c.kind() == "variable_declarator")
+            .filter_map(|declarator| declarator.child_by_field_name("name"))
+            .filter(|name| !(scope == "public" && self.public_member_pattern.is_match(name.text())))
+            .map(|name| {
+                Diagnostic::new(
+                    VisibilityModifierViolation {
+                        name: name.text().to_string(),
+                    },
+                    name.range(),
+                )
+            })
+            .collect()
+    }
+}
+
+impl VisibilityModifier {
+    /// Whether the field is annotated with one of the ignored annotations.
+    fn has_ignore_annotation(&self, modifiers: Option<&CstNode>, imports: &[ImportInfo]) -> bool {
+        let Some(modifiers) = modifiers else {
+            return false;
+        };
+        modifiers
+            .children()
+            .filter(|child| matches!(child.kind(), "annotation" | "marker_annotation"))
+            .filter_map(|annotation| annotation.child_by_field_name("name"))
+            .any(|name| {
+                is_listed(
+                    name.text(),
+                    &self.ignore_annotation_canonical_names,
+                    imports,
+                )
+            })
+    }
+
+    /// Whether a field type is immutable: a primitive, or one of the
+    /// immutable classes with immutable type arguments, if any.
+    fn is_immutable_type(&self, ty: &CstNode, imports: &[ImportInfo]) -> bool {
+        match ty.kind() {
+            "integral_type" | "floating_point_type" | "boolean_type" => true,
+            "type_identifier" | "scoped_type_identifier" => {
+                is_listed(ty.text(), &self.immutable_class_canonical_names, imports)
+            }
+            "generic_type" => {
+                let mut children = ty.children();
+                let Some(name) = children.next() else {
+                    return false;
+                };
+                is_listed(name.text(), &self.immutable_class_canonical_names, imports)
+                    && children
+                        .filter(|c| c.kind() == "type_arguments")
+                        .flat_map(|args| args.children())
+                        .filter(|arg| arg.inner().is_named())
+                        .all(|arg| self.is_immutable_type(&arg, imports))
+            }
+            // Arrays are mutable whatever their element type
+            _ => false,
+        }
+    }
+}
+
+/// Whether `name`, as written in the source, is one of the `canonical_names`.
+///
+/// A simple name stands for the listed class of that name, unless the file
+/// imports another class of that name.
+fn is_listed(name: &str, canonical_names: &[String], imports: &[ImportInfo]) -> bool {
+    let name: String = name.split_whitespace().collect();
+    if name.contains('.') {
+        return canonical_names.contains(&name);
+    }
+
+    let simple_name = |canonical: &str| {
+        canonical
+            .rsplit('.')
+            .next()
+            .unwrap_or(canonical)
+            .to_string()
+    };
+    canonical_names
+        .iter()
+        .any(|canonical| simple_name(canonical) == name)
+        && !imports.iter().any(|import| {
+            !import.is_static
+                && import.simple_name.as_deref() == Some(name.as_str())
+                && !canonical_names.contains(&import.path)
+        })
+}
+
+/// Whether the type declaring the field can't be subclassed: a final class,
+/// an enum or a record.
+fn is_final_class(owner: &CstNode) -> bool {
+    match owner.kind() {
+        "enum_declaration" | "record_declaration" => true,
+        _ => owner
+            .children()
+            .find(|c| c.kind() == "modifiers")
+            .is_some_and(|m| has_modifier(&m, "final")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, rule: &VisibilityModifier) -> Vec<String> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+
+        let mut names = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            for d in rule.check(&ctx, &node) {
+                names.push(d.kind.body);
+            }
+        }
+        names
+    }
+
+    fn names(source: &str, rule: &VisibilityModifier) -> Vec<String> {
+        check_source(source, rule)
+            .iter()
+            .map(|message| message.split('\'').nth(1).unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_default() {
+        let source = r#"
+class Foo {
+    public static final int CONSTANT = 1;
+    private static final long serialVersionUID = 1L;
+    public long serialVersionUID2;
+    private int a;
+    int b;
+    protected int c;
+    public int d, e;
+    public static int f;
+    public final int g = 1;
+}
+"#;
+        let rule = VisibilityModifier::default();
+        assert_eq!(
+            names(source, &rule),
+            vec!["serialVersionUID2", "b", "c", "d", "e", "f", "g"]
+        );
+        assert_eq!(
+            check_source(source, &rule)[0],
+            "Variable 'serialVersionUID2' must be private and have accessor methods."
+        );
+    }
+
+    #[test]
+    fn test_public_member_pattern() {
+        let source = r#"
+class Foo {
+    public long serialVersionUID;
+    long serialVersionUID2;
+}
+"#;
+        // The pattern only lets public fields through
+        assert_eq!(
+            names(source, &VisibilityModifier::default()),
+            vec!["serialVersionUID2"]
+        );
+    }
+
+    #[test]
+    fn test_package_and_protected_allowed() {
+        let source = r#"
+class Foo {
+    int a;
+    protected int b;
+    public int c;
+}
+"#;
+        let rule = VisibilityModifier {
+            package_allowed: true,
+            protected_allowed: true,
+            ..VisibilityModifier::default()
+        };
+        assert_eq!(names(source, &rule), vec!["c"]);
+    }
+
+    #[test]
+    fn test_allow_public_final_fields() {
+        let source = r#"
+class Foo {
+    public final java.util.List<String> a = null;
+    public java.util.List<String> b;
+}
+"#;
+        let rule = VisibilityModifier {
+            allow_public_final_fields: true,
+            ..VisibilityModifier::default()
+        };
+        assert_eq!(names(source, &rule), vec!["b"]);
+    }
+
+    #[test]
+    fn test_allow_public_immutable_fields() {
+        let source = r#"
+final class Foo {
+    public final int a = 1;
+    public final String b = "";
+    public final java.math.BigDecimal c = null;
+    public final java.util.List<String> d = null;
+    public final int[] e = null;
+}
+
+class Bar {
+    public final int a = 1;
+}
+"#;
+        let rule = VisibilityModifier {
+            allow_public_immutable_fields: true,
+            ..VisibilityModifier::default()
+        };
+        assert_eq!(names(source, &rule), vec!["d", "e", "a"]);
+    }
+
+    #[test]
+    fn test_immutable_short_name_shadowed_by_import() {
+        let source = r#"
+import com.example.String;
+
+final class Foo {
+    public final String a = null;
+}
+"#;
+        let rule = VisibilityModifier {
+            allow_public_immutable_fields: true,
+            ..VisibilityModifier::default()
+        };
+        assert_eq!(names(source, &rule), vec!["a"]);
+    }
+
+    #[test]
+    fn test_ignore_annotations() {
+        let source = r#"
+import org.junit.Rule;
+
+class Foo {
+    @Rule
+    public Object a;
+    @org.junit.ClassRule
+    public static Object b;
+    @VisibleForTesting
+    Object c;
+    @Deprecated
+    public Object d;
+}
+"#;
+        assert_eq!(names(source, &VisibilityModifier::default()), vec!["d"]);
+    }
+
+    #[test]
+    fn test_interface_and_anonymous_class_fields_skipped() {
+        let source = r#"
+interface Foo {
+    int A = 1;
+}
+
+class Bar {
+    Object o = new Object() {
+        public int x;
+    };
+}
+"#;
+        let rule = VisibilityModifier {
+            package_allowed: true,
+            ..VisibilityModifier::default()
+        };
+        assert!(check_source(source, &rule).is_empty());
+    }
+
+    #[test]
+    fn test_enum_fields() {
+        let source = r#"
+enum Foo {
+    A;
+
+    public int value;
+}
+"#;
+        assert_eq!(names(source, &VisibilityModifier::default()), vec!["value"]);
+    }
+}
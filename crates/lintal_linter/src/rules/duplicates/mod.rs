@@ -0,0 +1,5 @@
+//! Duplicate code rules (StrictDuplicateCode)
+
+mod strict_duplicate_code;
+
+pub use strict_duplicate_code::StrictDuplicateCode;
@@ -0,0 +1,198 @@
+//! StrictDuplicateCode rule implementation.
+//!
+//! Reports code that is duplicated in another file of the project, or
+//! elsewhere in the same file, once for each copy.
+//!
+//! Checkstyle equivalent: StrictDuplicateCodeCheck, removed in checkstyle 6.2.
+//! That check compared trimmed lines; this one compares tokens, so copies
+//! that differ only in formatting or comments are found too. Clones are
+//! found over the files of the run; see [`lintal_java_semantic::duplicates`].
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_java_cst::CstNode;
+use lintal_text_size::TextRange;
+
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: code is duplicated elsewhere.
+#[derive(Debug, Clone)]
+pub struct DuplicateCodeViolation {
+    pub lines: usize,
+    pub other_path: String,
+    pub other_line: usize,
+}
+
+impl Violation for DuplicateCodeViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!(
+            "Found duplicate of {} lines in {}, starting from line {}",
+            self.lines, self.other_path, self.other_line
+        )
+    }
+}
+
+/// Configuration for StrictDuplicateCode rule.
+#[derive(Debug, Clone)]
+pub struct StrictDuplicateCode {
+    /// Fewest tokens a duplicate must have (default: 100).
+    pub minimum_tokens: usize,
+    /// Fewest lines a duplicate must span (default: 12).
+    pub minimum_lines: usize,
+}
+
+impl Default for StrictDuplicateCode {
+    fn default() -> Self {
+        Self {
+            minimum_tokens: 100,
+            minimum_lines: 12,
+        }
+    }
+}
+
+const RELEVANT_KINDS: &[&str] = &["program"];
+
+impl FromConfig for StrictDuplicateCode {
+    const MODULE_NAME: &'static str = "StrictDuplicateCode";
+
+    fn from_config(properties: &Properties) -> Self {
+        let defaults = Self::default();
+        let minimum_tokens = properties
+            .get("minimumTokens")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(defaults.minimum_tokens);
+        // `min` is checkstyle's name for the number of lines
+        let minimum_lines = properties
+            .get("minimumLines")
+            .or_else(|| properties.get("min"))
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(defaults.minimum_lines);
+
+        Self {
+            minimum_tokens,
+            minimum_lines,
+        }
+    }
+}
+
+impl Rule for StrictDuplicateCode {
+    fn name(&self) -> &'static str {
+        "StrictDuplicateCode"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn needs_project_index(&self) -> bool {
+        true
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        let (Some(project), Some(path)) = (ctx.project(), ctx.path()) else {
+            return vec![];
+        };
+        if node.kind() != "program" {
+            return vec![];
+        }
+
+        project
+            .duplicates_of(path, self.minimum_tokens)
+            .into_iter()
+            .filter(|duplicate| duplicate.lines >= self.minimum_lines)
+            .map(|duplicate| {
+                Diagnostic::new(
+                    DuplicateCodeViolation {
+                        lines: duplicate.lines,
+                        other_path: project.files()[duplicate.other].path.display().to_string(),
+                        other_line: duplicate.other_line,
+                    },
+                    TextRange::empty(duplicate.range.start()),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::Linter;
+    use lintal_java_parser::JavaParser;
+    use lintal_java_semantic::{FileSummary, ProjectIndex};
+
+    const METHOD: &str = "
+    int sum(int[] values) {
+        int total = 0;
+        for (int value : values) {
+            if (value > 0) {
+                total += value * 2;
+            }
+        }
+        return total;
+    }
+";
+
+    fn summarize(path: &str, source: &str) -> FileSummary {
+        let result = JavaParser::new().parse(source).unwrap();
+        FileSummary::build(path, &CstNode::new(result.tree.root_node(), source))
+    }
+
+    #[test]
+    fn test_reports_each_copy() {
+        let a = format!("class A {{{METHOD}}}\n");
+        let b = format!("class B {{\n    int x;\n{METHOD}}}\n");
+        let linter = Linter::new(vec![Box::new(StrictDuplicateCode {
+            minimum_tokens: 20,
+            minimum_lines: 5,
+        })])
+        .with_project(ProjectIndex::new([
+            summarize("A.java", &a),
+            summarize("B.java", &b),
+        ]));
+
+        let in_a = linter.lint_source(&a, Some(Path::new("A.java"))).unwrap();
+        let messages: Vec<&str> = in_a
+            .iter()
+            .map(|found| found.diagnostic.kind.body.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            ["Found duplicate of 10 lines in B.java, starting from line 4"]
+        );
+
+        let in_b = linter.lint_source(&b, Some(Path::new("B.java"))).unwrap();
+        assert_eq!(in_b.len(), 1);
+        assert_eq!(
+            in_b[0].diagnostic.kind.body,
+            "Found duplicate of 10 lines in A.java, starting from line 2"
+        );
+
+        // Without a path there is no file to find in the project
+        assert!(linter.lint_source(&a, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_minimum_lines() {
+        // 11 lines, from the opening brace of the classes to their end
+        let a = format!("class A {{{METHOD}}}\n");
+        let b = format!("class B {{{METHOD}}}\n");
+        let linter = Linter::new(vec![Box::new(StrictDuplicateCode {
+            minimum_tokens: 20,
+            minimum_lines: 12,
+        })])
+        .with_project(ProjectIndex::new([
+            summarize("A.java", &a),
+            summarize("B.java", &b),
+        ]));
+        assert!(
+            linter
+                .lint_source(&a, Some(Path::new("A.java")))
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
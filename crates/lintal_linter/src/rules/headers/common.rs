@@ -0,0 +1,56 @@
+//! Shared utilities for header rules.
+
+use crate::Properties;
+use crate::rules::javadoc::common::parse_list;
+
+/// The lines of the expected header: the `header` property, where `\n`
+/// separates lines, or else the contents of `headerFile`.
+///
+/// A header file that can't be read gives no lines, so nothing is checked.
+pub fn header_lines(properties: &Properties) -> Vec<String> {
+    let text = match (properties.get("header"), properties.get("headerFile")) {
+        (Some(header), _) if !header.is_empty() => header.replace("\\n", "\n"),
+        (_, Some(file)) if !file.is_empty() => match std::fs::read_to_string(file) {
+            Ok(text) => text,
+            Err(err) => {
+                tracing::warn!("cannot read header file {file}: {err}");
+                return vec![];
+            }
+        },
+        _ => return vec![],
+    };
+    text.lines().map(str::to_string).collect()
+}
+
+/// One-based line numbers from a comma-separated list, such as
+/// `ignoreLines`. Entries that aren't numbers are skipped.
+pub fn line_numbers(properties: &Properties, name: &str) -> Vec<usize> {
+    properties
+        .get(name)
+        .map(|v| parse_list(v))
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|n| n.parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_lines() {
+        let mut props = Properties::new();
+        assert!(header_lines(&props).is_empty());
+        props.insert("header", "// Copyright\\n// License");
+        assert_eq!(header_lines(&props), vec!["// Copyright", "// License"]);
+    }
+
+    #[test]
+    fn test_line_numbers() {
+        let mut props = Properties::new();
+        assert!(line_numbers(&props, "ignoreLines").is_empty());
+        props.insert("ignoreLines", "2, 4,x");
+        assert_eq!(line_numbers(&props, "ignoreLines"), vec![2, 4]);
+    }
+}
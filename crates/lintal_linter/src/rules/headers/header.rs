@@ -0,0 +1,211 @@
+//! Header rule implementation.
+//!
+//! Checks that a source file begins with a specified header.
+//!
+//! Checkstyle equivalent: HeaderCheck
+
+use std::path::Path;
+
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
+use lintal_source_file::{LineIndex, OneIndexed, SourceCode, find_newline};
+use lintal_text_size::{TextRange, TextSize};
+
+use super::common::{header_lines, line_numbers};
+use crate::{FileRule, FromConfig, Properties, Rule};
+
+/// Violation: the file has fewer lines than the header.
+#[derive(Debug, Clone)]
+pub struct HeaderMissingViolation;
+
+impl Violation for HeaderMissingViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
+
+    fn message(&self) -> String {
+        "Missing a header - not enough lines in file.".to_string()
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("header", "header.missing"))
+    }
+}
+
+/// Violation: a line differs from the header.
+#[derive(Debug, Clone)]
+pub struct HeaderMismatchViolation {
+    pub expected: String,
+}
+
+impl Violation for HeaderMismatchViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
+
+    fn message(&self) -> String {
+        format!(
+            "Line does not match expected header line of '{}'.",
+            self.expected
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("header", "header.mismatch"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.expected.clone()]
+    }
+}
+
+/// Configuration for Header rule.
+#[derive(Debug, Clone, Default)]
+pub struct Header {
+    /// The expected header, from the `header` or `headerFile` property.
+    pub lines: Vec<String>,
+    /// One-based header lines that may differ, e.g. a copyright year.
+    pub ignore_lines: Vec<usize>,
+}
+
+impl FromConfig for Header {
+    const MODULE_NAME: &'static str = "Header";
+
+    fn from_config(properties: &Properties) -> Self {
+        Self {
+            lines: header_lines(properties),
+            ignore_lines: line_numbers(properties, "ignoreLines"),
+        }
+    }
+}
+
+impl Rule for Header {
+    fn name(&self) -> &'static str {
+        "Header"
+    }
+
+    fn as_file_rule(&self) -> Option<&dyn FileRule> {
+        Some(self)
+    }
+}
+
+impl FileRule for Header {
+    fn check_file(
+        &self,
+        _path: Option<&Path>,
+        source: &str,
+        line_index: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        if self.lines.is_empty() {
+            return vec![];
+        }
+
+        let file_lines: Vec<&str> = source.lines().collect();
+        let diagnostic =
+            if self.lines.len() > file_lines.len() {
+                Diagnostic::new(HeaderMissingViolation, TextRange::empty(TextSize::new(0)))
+            } else {
+                let Some(mismatch) = self.lines.iter().zip(&file_lines).enumerate().position(
+                    |(i, (expected, line))| {
+                        !self.ignore_lines.contains(&(i + 1)) && expected != line
+                    },
+                ) else {
+                    return vec![];
+                };
+                let source_code = SourceCode::new(source, line_index);
+                let line_start = source_code.line_start(OneIndexed::from_zero_indexed(mismatch));
+                Diagnostic::new(
+                    HeaderMismatchViolation {
+                        expected: self.lines[mismatch].clone(),
+                    },
+                    TextRange::at(line_start, TextSize::of(file_lines[mismatch])),
+                )
+            };
+
+        match self.insert_header_fix(source) {
+            Some(fix) => vec![diagnostic.with_fix(fix)],
+            None => vec![diagnostic],
+        }
+    }
+}
+
+impl Header {
+    /// Insert the header at the top of a file that has none: one that does
+    /// not begin with a comment. A file beginning with another header is
+    /// left for the user to update.
+    fn insert_header_fix(&self, source: &str) -> Option<Fix> {
+        let start = source.trim_start();
+        if start.starts_with("//") || start.starts_with("/*") {
+            return None;
+        }
+
+        let newline = find_newline(source).map_or("\n", |(_, ending)| ending.as_str());
+        let mut header = self.lines.join(newline);
+        header.push_str(newline);
+        Some(Fix::safe_edit(Edit::insertion(header, TextSize::new(0))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(header: &'static str, ignore_lines: &'static str) -> Header {
+        let mut props = Properties::new();
+        props.insert("header", header);
+        props.insert("ignoreLines", ignore_lines);
+        Header::from_config(&props)
+    }
+
+    fn check_source(source: &str, rule: &Header) -> Vec<Diagnostic> {
+        let line_index = LineIndex::from_source_text(source);
+        rule.check_file(None, source, &line_index)
+    }
+
+    const HEADER: &str = "// Copyright 2024 Example\\n// Licensed under MIT";
+
+    #[test]
+    fn test_matching_header() {
+        let source = "// Copyright 2024 Example\n// Licensed under MIT\nclass Foo {}\n";
+        assert!(check_source(source, &rule(HEADER, "")).is_empty());
+    }
+
+    #[test]
+    fn test_mismatched_line() {
+        let source = "// Copyright 2025 Example\n// Licensed under MIT\nclass Foo {}\n";
+        let diagnostics = check_source(source, &rule(HEADER, ""));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind.body,
+            "Line does not match expected header line of '// Copyright 2024 Example'."
+        );
+        // The file has a header of its own, so it isn't replaced
+        assert!(diagnostics[0].fix.is_none());
+
+        assert!(check_source(source, &rule(HEADER, "1")).is_empty());
+    }
+
+    #[test]
+    fn test_missing_header_fix() {
+        let source = "package a;\r\n\r\nclass Foo {}\r\n";
+        let diagnostics = check_source(source, &rule(HEADER, ""));
+        assert_eq!(diagnostics.len(), 1);
+        let fix = diagnostics[0].fix.as_ref().unwrap();
+        assert_eq!(
+            fix.edits()[0].content(),
+            Some("// Copyright 2024 Example\r\n// Licensed under MIT\r\n")
+        );
+    }
+
+    #[test]
+    fn test_short_file() {
+        let source = "class Foo {}\n";
+        let diagnostics = check_source(source, &rule(HEADER, ""));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind.body,
+            "Missing a header - not enough lines in file."
+        );
+        assert!(diagnostics[0].fix.is_some());
+    }
+
+    #[test]
+    fn test_no_header_configured() {
+        assert!(check_source("class Foo {}\n", &Header::default()).is_empty());
+    }
+}
@@ -0,0 +1,8 @@
+//! Header rules (Header, RegexpHeader)
+
+mod common;
+mod header;
+mod regexp_header;
+
+pub use header::Header;
+pub use regexp_header::RegexpHeader;
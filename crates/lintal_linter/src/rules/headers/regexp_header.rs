@@ -0,0 +1,232 @@
+//! RegexpHeader rule implementation.
+//!
+//! Checks that a source file begins with a header whose lines match
+//! specified patterns.
+//!
+//! Checkstyle equivalent: RegexpHeaderCheck
+
+use std::path::Path;
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_source_file::{LineIndex, OneIndexed, SourceCode};
+use lintal_text_size::{TextRange, TextSize};
+use regex::Regex;
+
+use super::common::{header_lines, line_numbers};
+use crate::{FileRule, FromConfig, Properties, Rule};
+
+/// Violation: the file ends before the header does.
+#[derive(Debug, Clone)]
+pub struct RegexpHeaderMissingViolation;
+
+impl Violation for RegexpHeaderMissingViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        "Missing a header - not enough lines in file.".to_string()
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("header", "header.missing"))
+    }
+}
+
+/// Violation: a line does not match its header pattern.
+#[derive(Debug, Clone)]
+pub struct RegexpHeaderMismatchViolation {
+    pub pattern: String,
+}
+
+impl Violation for RegexpHeaderMismatchViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!(
+            "Line does not match expected header line of '{}'.",
+            self.pattern
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("header", "header.mismatch"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.pattern.clone()]
+    }
+}
+
+/// Configuration for RegexpHeader rule.
+#[derive(Debug, Clone, Default)]
+pub struct RegexpHeader {
+    /// A pattern for each header line, from the `header` or `headerFile`
+    /// property. An empty line stands for `^$`.
+    pub patterns: Vec<Regex>,
+    /// One-based header lines that match any number of file lines, none
+    /// included.
+    pub multi_lines: Vec<usize>,
+}
+
+impl FromConfig for RegexpHeader {
+    const MODULE_NAME: &'static str = "RegexpHeader";
+
+    fn from_config(properties: &Properties) -> Self {
+        let patterns = header_lines(properties)
+            .iter()
+            .map(|line| if line.is_empty() { "^$" } else { line.as_str() })
+            .map(|line| {
+                Regex::new(line).unwrap_or_else(|_| Regex::new(&regex::escape(line)).unwrap())
+            })
+            .collect();
+
+        Self {
+            patterns,
+            multi_lines: line_numbers(properties, "multiLines"),
+        }
+    }
+}
+
+impl Rule for RegexpHeader {
+    fn name(&self) -> &'static str {
+        "RegexpHeader"
+    }
+
+    fn as_file_rule(&self) -> Option<&dyn FileRule> {
+        Some(self)
+    }
+}
+
+impl FileRule for RegexpHeader {
+    fn check_file(
+        &self,
+        _path: Option<&Path>,
+        source: &str,
+        line_index: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        if self.patterns.is_empty() {
+            return vec![];
+        }
+
+        let file_lines: Vec<&str> = source.lines().collect();
+        let header_size = self.patterns.len();
+        let file_start = TextRange::empty(TextSize::new(0));
+        let missing = || vec![Diagnostic::new(RegexpHeaderMissingViolation, file_start)];
+
+        if header_size.saturating_sub(self.multi_lines.len()) > file_lines.len() {
+            return missing();
+        }
+
+        // Walk the file and header together; a multi-line header line stays
+        // current while file lines match it, and is skipped once one doesn't
+        let mut header_line = 0;
+        for (index, line) in file_lines.iter().enumerate() {
+            if header_line >= header_size {
+                return vec![];
+            }
+
+            let mut is_match = self.patterns[header_line].is_match(line);
+            while !is_match && self.is_multi_line(header_line) {
+                header_line += 1;
+                is_match = header_line == header_size || self.patterns[header_line].is_match(line);
+            }
+
+            if !is_match {
+                let source_code = SourceCode::new(source, line_index);
+                let line_start = source_code.line_start(OneIndexed::from_zero_indexed(index));
+                return vec![Diagnostic::new(
+                    RegexpHeaderMismatchViolation {
+                        pattern: self.patterns[header_line].as_str().to_string(),
+                    },
+                    TextRange::at(line_start, TextSize::of(*line)),
+                )];
+            }
+
+            if !self.is_multi_line(header_line) {
+                header_line += 1;
+            }
+        }
+
+        // The file ended; the header is complete if only multi-line header
+        // lines are left
+        if (header_line..header_size).any(|line| !self.is_multi_line(line)) {
+            return missing();
+        }
+        vec![]
+    }
+}
+
+impl RegexpHeader {
+    /// Whether the zero-based header line may match any number of lines.
+    fn is_multi_line(&self, header_line: usize) -> bool {
+        self.multi_lines.contains(&(header_line + 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(header: &'static str, multi_lines: &'static str) -> RegexpHeader {
+        let mut props = Properties::new();
+        props.insert("header", header);
+        props.insert("multiLines", multi_lines);
+        RegexpHeader::from_config(&props)
+    }
+
+    fn check_source(source: &str, rule: &RegexpHeader) -> Vec<(usize, String)> {
+        let line_index = LineIndex::from_source_text(source);
+        let source_code = SourceCode::new(source, &line_index);
+        rule.check_file(None, source, &line_index)
+            .into_iter()
+            .map(|d| {
+                (
+                    source_code.line_column(d.range.start()).line.get(),
+                    d.kind.body,
+                )
+            })
+            .collect()
+    }
+
+    const HEADER: &str = r"^// Copyright \d{4}$\n^// Licensed under MIT$";
+
+    #[test]
+    fn test_matching_header() {
+        let source = "// Copyright 2025\n// Licensed under MIT\nclass Foo {}\n";
+        assert!(check_source(source, &rule(HEADER, "")).is_empty());
+    }
+
+    #[test]
+    fn test_mismatched_line() {
+        let source = "// Copyright 2025\n// Licensed under GPL\nclass Foo {}\n";
+        assert_eq!(
+            check_source(source, &rule(HEADER, "")),
+            vec![(
+                2,
+                "Line does not match expected header line of '^// Licensed under MIT$'."
+                    .to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_multi_lines() {
+        let header = r"^/\*$\n^ \* .*$\n^ \*/$";
+        let source = "/*\n * Copyright 2025\n * Example Inc.\n */\nclass Foo {}\n";
+        assert!(check_source(source, &rule(header, "2")).is_empty());
+        assert_eq!(check_source(source, &rule(header, "")).len(), 1);
+
+        // A multi-line header line may match no lines at all
+        assert!(check_source("/*\n */\nclass Foo {}\n", &rule(header, "2")).is_empty());
+    }
+
+    #[test]
+    fn test_missing_header() {
+        assert_eq!(
+            check_source("// Copyright 2025\n", &rule(HEADER, "")),
+            vec![(
+                1,
+                "Missing a header - not enough lines in file.".to_string()
+            )]
+        );
+    }
+}
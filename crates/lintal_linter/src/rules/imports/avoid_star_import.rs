@@ -0,0 +1,466 @@
+//! AvoidStarImport rule implementation.
+//!
+//! Detects imports of the form `import java.util.*;`.
+//!
+//! Checkstyle equivalent: AvoidStarImportCheck
+
+use std::collections::BTreeSet;
+
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+use tree_sitter::Node;
+
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+use super::common::{ImportInfo, imports};
+
+/// Violation: star import.
+#[derive(Debug, Clone)]
+pub struct StarImport {
+    pub import: String,
+}
+
+impl Violation for StarImport {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
+
+    fn message(&self) -> String {
+        format!(
+            "Using the '.*' form of import should be avoided - {}.",
+            self.import
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("imports", "import.avoidStar"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.import.clone()]
+    }
+}
+
+/// Configuration for AvoidStarImport rule.
+#[derive(Debug, Clone, Default)]
+pub struct AvoidStarImport {
+    /// Star imports allowed, each ending in `.*`.
+    pub excludes: Vec<String>,
+    /// Whether star imports of types are allowed.
+    pub allow_class_imports: bool,
+    /// Whether static star imports are allowed.
+    pub allow_static_member_imports: bool,
+}
+
+const RELEVANT_KINDS: &[&str] = &["program"];
+
+/// The public types of `java.lang`, which are in scope without an import.
+const JAVA_LANG_TYPES: &[&str] = &[
+    "AbstractMethodError",
+    "Appendable",
+    "ArithmeticException",
+    "ArrayIndexOutOfBoundsException",
+    "ArrayStoreException",
+    "AssertionError",
+    "AutoCloseable",
+    "Boolean",
+    "BootstrapMethodError",
+    "Byte",
+    "CharSequence",
+    "Character",
+    "Class",
+    "ClassCastException",
+    "ClassCircularityError",
+    "ClassFormatError",
+    "ClassLoader",
+    "ClassNotFoundException",
+    "ClassValue",
+    "CloneNotSupportedException",
+    "Cloneable",
+    "Comparable",
+    "Deprecated",
+    "Double",
+    "Enum",
+    "EnumConstantNotPresentException",
+    "Error",
+    "Exception",
+    "ExceptionInInitializerError",
+    "Float",
+    "FunctionalInterface",
+    "IllegalAccessError",
+    "IllegalAccessException",
+    "IllegalArgumentException",
+    "IllegalCallerException",
+    "IllegalMonitorStateException",
+    "IllegalStateException",
+    "IllegalThreadStateException",
+    "IncompatibleClassChangeError",
+    "IndexOutOfBoundsException",
+    "InheritableThreadLocal",
+    "InstantiationError",
+    "InstantiationException",
+    "Integer",
+    "InternalError",
+    "InterruptedException",
+    "Iterable",
+    "LayerInstantiationException",
+    "LinkageError",
+    "Long",
+    "MatchException",
+    "Math",
+    "Module",
+    "ModuleLayer",
+    "NegativeArraySizeException",
+    "NoClassDefFoundError",
+    "NoSuchFieldError",
+    "NoSuchFieldException",
+    "NoSuchMethodError",
+    "NoSuchMethodException",
+    "NullPointerException",
+    "Number",
+    "NumberFormatException",
+    "Object",
+    "OutOfMemoryError",
+    "Override",
+    "Package",
+    "Process",
+    "ProcessBuilder",
+    "ProcessHandle",
+    "Readable",
+    "Record",
+    "ReflectiveOperationException",
+    "Runnable",
+    "Runtime",
+    "RuntimeException",
+    "RuntimePermission",
+    "SafeVarargs",
+    "SecurityException",
+    "SecurityManager",
+    "Short",
+    "StackOverflowError",
+    "StackTraceElement",
+    "StackWalker",
+    "StrictMath",
+    "String",
+    "StringBuffer",
+    "StringBuilder",
+    "StringIndexOutOfBoundsException",
+    "SuppressWarnings",
+    "System",
+    "Thread",
+    "ThreadDeath",
+    "ThreadGroup",
+    "ThreadLocal",
+    "Throwable",
+    "TypeNotPresentException",
+    "UnknownError",
+    "UnsatisfiedLinkError",
+    "UnsupportedClassVersionError",
+    "UnsupportedOperationException",
+    "VerifyError",
+    "VirtualMachineError",
+    "Void",
+    "WrongThreadException",
+];
+
+impl FromConfig for AvoidStarImport {
+    const MODULE_NAME: &'static str = "AvoidStarImport";
+
+    fn from_config(properties: &Properties) -> Self {
+        let excludes = properties
+            .get("excludes")
+            .into_iter()
+            .flat_map(|v| v.split(','))
+            .map(str::trim)
+            .filter(|exclude| !exclude.is_empty())
+            .map(|exclude| {
+                if exclude.ends_with(".*") {
+                    exclude.to_string()
+                } else {
+                    format!("{exclude}.*")
+                }
+            })
+            .collect();
+        let allow_class_imports = properties
+            .get("allowClassImports")
+            .is_some_and(|v| *v == "true");
+        let allow_static_member_imports = properties
+            .get("allowStaticMemberImports")
+            .is_some_and(|v| *v == "true");
+
+        Self {
+            excludes,
+            allow_class_imports,
+            allow_static_member_imports,
+        }
+    }
+}
+
+impl Rule for AvoidStarImport {
+    fn name(&self) -> &'static str {
+        "AvoidStarImport"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        // Only check at program level (once per file)
+        if node.kind() != "program" {
+            return vec![];
+        }
+
+        let imports = imports(ctx, node);
+        let mut diagnostics = Vec::new();
+        for import in imports {
+            let allowed = if import.is_static {
+                self.allow_static_member_imports
+            } else {
+                self.allow_class_imports
+            };
+            if !import.is_wildcard || allowed || self.excludes.contains(&import.path) {
+                continue;
+            }
+            let diagnostic = Diagnostic::new(
+                StarImport {
+                    import: import.path.clone(),
+                },
+                import.range,
+            );
+            diagnostics.push(match expansion(ctx, node, imports, import) {
+                Some(fix) => diagnostic.with_fix(fix),
+                None => diagnostic,
+            });
+        }
+        diagnostics
+    }
+}
+
+/// A fix replacing the star import `import` with an import of each type
+/// the file uses that nothing else in the file accounts for: not declared
+/// in it, imported by name, or in `java.lang`. Only offered if `import` is
+/// the file's one star import of types, as otherwise which of them a type
+/// comes from can't be told.
+///
+/// Unsafe, as such a type can also come from the file's own package.
+fn expansion(
+    ctx: &CheckContext,
+    root: &CstNode,
+    imports: &[ImportInfo],
+    import: &ImportInfo,
+) -> Option<Fix> {
+    let type_stars = imports
+        .iter()
+        .filter(|other| other.is_wildcard && !other.is_static)
+        .count();
+    if import.is_static || type_stars != 1 {
+        return None;
+    }
+
+    let source = ctx.source();
+    let mut used = BTreeSet::new();
+    let mut declared = BTreeSet::new();
+    collect_types(root.inner(), source, &mut used, &mut declared);
+    let names: Vec<&str> = used
+        .iter()
+        .map(String::as_str)
+        .filter(|name| {
+            !declared.contains(*name)
+                && !JAVA_LANG_TYPES.contains(name)
+                && !imports
+                    .iter()
+                    .any(|other| other.simple_name.as_deref() == Some(*name))
+        })
+        .collect();
+    if names.is_empty() {
+        return None;
+    }
+
+    let package = import.path.strip_suffix(".*")?;
+    let newline = if source.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let expanded = names
+        .iter()
+        .map(|name| format!("import {package}.{name};"))
+        .collect::<Vec<_>>()
+        .join(newline);
+    Some(Fix::unsafe_edit(Edit::range_replacement(
+        expanded,
+        import.range,
+    )))
+}
+
+/// Collect the simple names of the types used under `node` into `used`,
+/// and of the types, type parameters and variables it declares into
+/// `declared`.
+fn collect_types(
+    node: Node,
+    source: &str,
+    used: &mut BTreeSet<String>,
+    declared: &mut BTreeSet<String>,
+) {
+    let text = |node: Node| node.utf8_text(source.as_bytes()).ok().map(String::from);
+    // Types are named in upper camel case, constants in upper case
+    let is_type_name = |name: &str| {
+        name.chars().next().is_some_and(char::is_uppercase) && name.chars().any(char::is_lowercase)
+    };
+
+    match node.kind() {
+        "import_declaration" | "package_declaration" => return,
+        "type_identifier" => used.extend(text(node)),
+        // Map.Entry uses Map
+        "scoped_type_identifier" => {
+            let mut first = node;
+            while first.kind() == "scoped_type_identifier" {
+                match first.child(0) {
+                    Some(child) => first = child,
+                    None => break,
+                }
+            }
+            used.extend(text(first));
+            return;
+        }
+        "marker_annotation" | "annotation" => {
+            if let Some(name) = node.child_by_field_name("name") {
+                let first = if name.kind() == "scoped_identifier" {
+                    name.child(0)
+                } else {
+                    Some(name)
+                };
+                used.extend(first.and_then(text));
+            }
+        }
+        // Arrays.asList(), System.out, String::valueOf
+        "method_invocation" | "field_access" | "method_reference" => {
+            let object = node
+                .child_by_field_name("object")
+                .or_else(|| node.child(0).filter(|_| node.kind() == "method_reference"));
+            if let Some(object) = object.filter(|object| object.kind() == "identifier")
+                && let Some(name) = text(object).filter(|name| is_type_name(name))
+            {
+                used.insert(name);
+            }
+        }
+        "class_declaration"
+        | "interface_declaration"
+        | "enum_declaration"
+        | "record_declaration"
+        | "annotation_type_declaration"
+        | "variable_declarator"
+        | "formal_parameter"
+        | "catch_formal_parameter"
+        | "enum_constant" => {
+            declared.extend(node.child_by_field_name("name").and_then(text));
+        }
+        "type_parameter" => {
+            let mut cursor = node.walk();
+            let name = node
+                .named_children(&mut cursor)
+                .find(|child| child.kind() == "type_identifier");
+            declared.extend(name.and_then(text));
+        }
+        _ => {}
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_types(child, source, used, declared);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::apply_fixes;
+    use lintal_diagnostics::Applicability;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, properties: &[(&str, &str)]) -> Vec<Diagnostic> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let properties: Properties = properties.iter().copied().collect();
+        let rule = AvoidStarImport::from_config(&properties);
+
+        let mut diagnostics = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            diagnostics.extend(rule.check(&ctx, &node));
+        }
+        diagnostics
+    }
+
+    fn fixed(source: &str, diagnostics: &[Diagnostic]) -> String {
+        apply_fixes(source, diagnostics.iter().filter_map(|d| d.fix.as_ref())).code
+    }
+
+    #[test]
+    fn test_star_import() {
+        let source = "import java.util.*;\nimport java.io.File;\n\nclass A {}\n";
+        let diagnostics = check_source(source, &[]);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind.body,
+            "Using the '.*' form of import should be avoided - java.util.*."
+        );
+    }
+
+    #[test]
+    fn test_static_star_import() {
+        let source = "import static java.lang.Math.*;\n\nclass A {}\n";
+        assert_eq!(check_source(source, &[]).len(), 1);
+        assert!(check_source(source, &[("allowStaticMemberImports", "true")]).is_empty());
+    }
+
+    #[test]
+    fn test_allow_class_imports() {
+        let source = "import java.util.*;\nimport static java.lang.Math.*;\n\nclass A {}\n";
+        let diagnostics = check_source(source, &[("allowClassImports", "true")]);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].kind.body.contains("java.lang.Math.*"));
+    }
+
+    #[test]
+    fn test_excludes() {
+        let source = "import java.util.*;\nimport java.io.*;\n\nclass A {}\n";
+        let diagnostics = check_source(source, &[("excludes", "java.io, java.net.*")]);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].kind.body.contains("java.util.*"));
+    }
+
+    #[test]
+    fn test_fix_expands_used_types() {
+        let source = r#"import java.util.*;
+
+public class A<T> {
+    private final List<T> items = new ArrayList<>();
+    private Map.Entry<String, Integer> entry;
+    private B nested;
+
+    @Override
+    public String toString() {
+        return Collections.emptyList().toString();
+    }
+
+    static class B {}
+}
+"#;
+        let diagnostics = check_source(source, &[]);
+        let fix = diagnostics[0].fix.as_ref().unwrap();
+        assert_eq!(fix.applicability(), Applicability::Unsafe);
+        assert!(fixed(source, &diagnostics).starts_with(
+            "import java.util.ArrayList;\nimport java.util.Collections;\nimport java.util.List;\nimport java.util.Map;\n\npublic class A<T> {"
+        ));
+    }
+
+    #[test]
+    fn test_no_fix_with_several_star_imports() {
+        let source =
+            "import java.util.*;\nimport java.io.*;\n\nclass A {\n    List<File> files;\n}\n";
+        let diagnostics = check_source(source, &[]);
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics.iter().all(|d| d.fix.is_none()));
+    }
+}
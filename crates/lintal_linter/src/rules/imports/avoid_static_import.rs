@@ -0,0 +1,173 @@
+//! AvoidStaticImport rule implementation.
+//!
+//! Detects static imports, such as `import static java.lang.Math.abs;`.
+//!
+//! Checkstyle equivalent: AvoidStaticImportCheck
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+use super::common::imports;
+
+/// Violation: static import.
+#[derive(Debug, Clone)]
+pub struct StaticImport {
+    pub import: String,
+}
+
+impl Violation for StaticImport {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!(
+            "Using a static member import should be avoided - {}.",
+            self.import
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("imports", "import.avoidStatic"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.import.clone()]
+    }
+}
+
+/// Configuration for AvoidStaticImport rule.
+#[derive(Debug, Clone, Default)]
+pub struct AvoidStaticImport {
+    /// Static imports allowed: a member, e.g. `java.lang.Math.abs`, or all
+    /// members of a type, e.g. `java.lang.Math.*`.
+    pub excludes: Vec<String>,
+}
+
+const RELEVANT_KINDS: &[&str] = &["program"];
+
+impl FromConfig for AvoidStaticImport {
+    const MODULE_NAME: &'static str = "AvoidStaticImport";
+
+    fn from_config(properties: &Properties) -> Self {
+        let excludes = properties
+            .get("excludes")
+            .into_iter()
+            .flat_map(|v| v.split(','))
+            .map(str::trim)
+            .filter(|exclude| !exclude.is_empty())
+            .map(String::from)
+            .collect();
+
+        Self { excludes }
+    }
+}
+
+impl Rule for AvoidStaticImport {
+    fn name(&self) -> &'static str {
+        "AvoidStaticImport"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        // Only check at program level (once per file)
+        if node.kind() != "program" {
+            return vec![];
+        }
+
+        imports(ctx, node)
+            .iter()
+            .filter(|import| import.is_static && !self.is_exempt(&import.path))
+            .map(|import| {
+                Diagnostic::new(
+                    StaticImport {
+                        import: import.path.clone(),
+                    },
+                    import.range,
+                )
+            })
+            .collect()
+    }
+}
+
+impl AvoidStaticImport {
+    /// Whether an exclude allows the static import of `path`: names it, or
+    /// is `T.*` where `path` is a member of `T` or `T.*` itself.
+    fn is_exempt(&self, path: &str) -> bool {
+        self.excludes.iter().any(|exclude| {
+            exclude == path
+                || exclude.strip_suffix(".*").is_some_and(|owner| {
+                    path.strip_prefix(owner)
+                        .and_then(|rest| rest.strip_prefix('.'))
+                        .is_some_and(|member| !member.contains('.'))
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, excludes: &str) -> Vec<Diagnostic> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let properties: Properties = [("excludes", excludes)].into_iter().collect();
+        let rule = AvoidStaticImport::from_config(&properties);
+
+        let mut diagnostics = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            diagnostics.extend(rule.check(&ctx, &node));
+        }
+        diagnostics
+    }
+
+    #[test]
+    fn test_static_import() {
+        let source = "import static java.lang.Math.abs;\nimport java.util.List;\n\nclass A {}\n";
+        let diagnostics = check_source(source, "");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind.body,
+            "Using a static member import should be avoided - java.lang.Math.abs."
+        );
+    }
+
+    #[test]
+    fn test_static_star_import() {
+        let source = "import static java.lang.Math.*;\n\nclass A {}\n";
+        assert_eq!(check_source(source, "").len(), 1);
+    }
+
+    #[test]
+    fn test_excludes() {
+        let source = r#"
+import static java.lang.Math.abs;
+import static java.lang.Math.*;
+import static java.lang.System.out;
+import static java.lang.System.err;
+import static java.util.Map.Entry.comparingByKey;
+"#;
+        let diagnostics = check_source(
+            source,
+            "java.lang.Math.*, java.lang.System.out, java.util.*",
+        );
+        let flagged: Vec<&str> = diagnostics
+            .iter()
+            .map(|d| d.kind.body.rsplit(' ').next().unwrap())
+            .collect();
+        assert_eq!(
+            flagged,
+            [
+                "java.lang.System.err.",
+                "java.util.Map.Entry.comparingByKey."
+            ]
+        );
+    }
+}
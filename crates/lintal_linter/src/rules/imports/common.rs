@@ -2,11 +2,16 @@
 
 use std::collections::HashSet;
 
+use lintal_diagnostics::{Diagnostic, Edit, Fix};
+use lintal_java_cst::CstNode;
 use lintal_source_file::{LineIndex, SourceCode};
 use lintal_text_size::{TextRange, TextSize};
 use regex::Regex;
 use tree_sitter::Node;
 
+use crate::CheckContext;
+use crate::organize_imports::ImportLayout;
+
 /// Represents a parsed import statement.
 #[derive(Debug, Clone)]
 pub struct ImportInfo {
@@ -37,6 +42,45 @@ impl ImportInfo {
     }
 }
 
+/// The import declarations of the file, collected once and shared by the
+/// import rules.
+pub fn imports<'c>(ctx: &'c CheckContext, root: &CstNode) -> &'c [ImportInfo] {
+    struct Imports(Vec<ImportInfo>);
+
+    let imports = ctx.memo(|| {
+        Imports(collect_imports(
+            root.inner(),
+            ctx.source(),
+            ctx.line_index(),
+        ))
+    });
+    &imports.0
+}
+
+/// `diagnostics` of the imports under `root`, each with a fix that lays
+/// the imports out again as `layout` asks, if that changes them.
+pub(crate) fn with_layout_fix(
+    ctx: &CheckContext,
+    root: &CstNode,
+    layout: &ImportLayout,
+    diagnostics: Vec<Diagnostic>,
+) -> Vec<Diagnostic> {
+    if diagnostics.is_empty() {
+        return diagnostics;
+    }
+    let fix = layout
+        .reorder(root)
+        .filter(|(range, text)| !text.is_empty() && ctx.source()[*range] != *text)
+        .map(|(range, text)| Fix::safe_edit(Edit::range_replacement(text, range)));
+    match fix {
+        Some(fix) => diagnostics
+            .into_iter()
+            .map(|diagnostic| diagnostic.with_fix(fix.clone()))
+            .collect(),
+        None => diagnostics,
+    }
+}
+
 /// Collect all import declarations from the source.
 pub fn collect_imports(root: Node, source: &str, line_index: &LineIndex) -> Vec<ImportInfo> {
     let mut imports = Vec::new();
@@ -0,0 +1,460 @@
+//! CustomImportOrder rule implementation.
+//!
+//! Checks that imports are in the groups `customImportOrderRules` lists, in
+//! that order, optionally sorted within each group and separated between
+//! groups.
+//!
+//! Checkstyle equivalent: CustomImportOrderCheck
+
+use std::cmp::Ordering;
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+
+use crate::organize_imports::{ImportLayout, custom_import_order_rules};
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+use super::common::{ImportInfo, get_package_name, imports, with_layout_fix};
+
+/// The group of imports no rule matches.
+const NON_GROUP: &str = "NON_GROUP";
+
+/// Violation: import is in a group other than the one expected there.
+#[derive(Debug, Clone)]
+pub struct WrongImportGroup {
+    pub import: String,
+    pub group: String,
+    pub expected: String,
+}
+
+impl Violation for WrongImportGroup {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
+
+    fn message(&self) -> String {
+        if self.group == NON_GROUP {
+            "Imports without groups should be placed at the end of the import list.".to_string()
+        } else if self.expected == NON_GROUP {
+            format!(
+                "Import statement for '{}' is in the wrong order. Should be in the '{}' group, expecting not assigned imports on this line.",
+                self.import, self.group
+            )
+        } else {
+            format!(
+                "Import statement for '{}' is in the wrong order. Should be in the '{}' group, expecting group '{}' on this line.",
+                self.import, self.group, self.expected
+            )
+        }
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        let key = if self.group == NON_GROUP {
+            "custom.import.order.nonGroup.import"
+        } else if self.expected == NON_GROUP {
+            "custom.import.order.nonGroup.expected"
+        } else {
+            "custom.import.order"
+        };
+        Some(MessageKey::new("imports", key))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        if self.group == NON_GROUP {
+            vec![self.import.clone()]
+        } else if self.expected == NON_GROUP {
+            vec![self.group.clone(), self.import.clone()]
+        } else {
+            vec![
+                self.group.clone(),
+                self.expected.clone(),
+                self.import.clone(),
+            ]
+        }
+    }
+}
+
+/// Violation: import is out of alphabetical order within its group.
+#[derive(Debug, Clone)]
+pub struct WrongLexicographicalOrder {
+    pub import: String,
+    pub before: String,
+}
+
+impl Violation for WrongLexicographicalOrder {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
+
+    fn message(&self) -> String {
+        format!(
+            "Wrong lexicographical order for '{}' import. Should be before '{}'.",
+            self.import, self.before
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("imports", "custom.import.order.lex"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.import.clone(), self.before.clone()]
+    }
+}
+
+/// Violation: group doesn't start after exactly one blank line.
+#[derive(Debug, Clone)]
+pub struct MissingGroupSeparator {
+    pub import: String,
+}
+
+impl Violation for MissingGroupSeparator {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
+
+    fn message(&self) -> String {
+        format!(
+            "'{}' should be separated from previous import group by one line.",
+            self.import
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new(
+            "imports",
+            "custom.import.order.line.separator",
+        ))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.import.clone()]
+    }
+}
+
+/// Violation: blank line between imports of the same group.
+#[derive(Debug, Clone)]
+pub struct SeparatedInGroup {
+    pub import: String,
+}
+
+impl Violation for SeparatedInGroup {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
+
+    fn message(&self) -> String {
+        format!("Extra separation in import group before '{}'", self.import)
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new(
+            "imports",
+            "custom.import.order.separated.internally",
+        ))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.import.clone()]
+    }
+}
+
+/// Configuration for CustomImportOrder rule.
+#[derive(Debug, Clone)]
+pub struct CustomImportOrder {
+    /// The groups, and how imports are ordered within them.
+    layout: ImportLayout,
+    /// The name of each group of `layout`, the last being [`NON_GROUP`].
+    groups: Vec<String>,
+}
+
+const RELEVANT_KINDS: &[&str] = &["program"];
+
+impl Default for CustomImportOrder {
+    fn default() -> Self {
+        Self::from_config(&Properties::new())
+    }
+}
+
+impl FromConfig for CustomImportOrder {
+    const MODULE_NAME: &'static str = "CustomImportOrder";
+
+    fn from_config(properties: &Properties) -> Self {
+        // `SAME_PACKAGE(n)` is the SAME_PACKAGE group
+        let groups = custom_import_order_rules(properties)
+            .into_iter()
+            .map(|rule| rule.split_once('(').map_or(rule, |(name, _)| name))
+            .chain(std::iter::once(NON_GROUP))
+            .map(str::to_string)
+            .collect();
+
+        Self {
+            layout: ImportLayout::default().with_custom_import_order(properties),
+            groups,
+        }
+    }
+}
+
+impl Rule for CustomImportOrder {
+    fn name(&self) -> &'static str {
+        "CustomImportOrder"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        // Only check at program level (once per file)
+        if node.kind() != "program" {
+            return vec![];
+        }
+
+        let imports = imports(ctx, node);
+        if imports.is_empty() {
+            return vec![];
+        }
+        let source = ctx.source();
+        let package = get_package_name(node.inner(), source);
+        let groups: Vec<usize> = imports
+            .iter()
+            .map(|import| {
+                self.layout
+                    .group_of(&import.path, import.is_static, package.as_deref())
+            })
+            .collect();
+        let non_group = self.groups.len() - 1;
+
+        let mut diagnostics = Vec::new();
+        let mut current = groups[0];
+        // The last import of the current group, and the last of it in order
+        let mut previous: Option<&ImportInfo> = None;
+        let mut previous_in_order: Option<&str> = None;
+
+        for (import, &group) in imports.iter().zip(&groups) {
+            let path = import.path.as_str();
+            if group == current {
+                if previous
+                    .is_some_and(|previous| blank_lines_between(source, previous, import) > 0)
+                {
+                    diagnostics.push(Diagnostic::new(
+                        SeparatedInGroup {
+                            import: import.path.clone(),
+                        },
+                        import.range,
+                    ));
+                }
+                match previous_in_order {
+                    Some(before)
+                        if self.layout.sorted()
+                            && self.layout.compare(path, before) == Ordering::Less =>
+                    {
+                        diagnostics.push(Diagnostic::new(
+                            WrongLexicographicalOrder {
+                                import: import.path.clone(),
+                                before: before.to_string(),
+                            },
+                            import.range,
+                        ));
+                    }
+                    _ => previous_in_order = Some(path),
+                }
+                previous = Some(import);
+            } else if current < non_group {
+                // The next group with imports, else the non-group
+                let next = (current + 1..non_group)
+                    .find(|index| groups.contains(index))
+                    .unwrap_or(non_group);
+                if group == next {
+                    if self.layout.separated()
+                        && previous.is_some_and(|previous| {
+                            blank_lines_between(source, previous, import) != 1
+                        })
+                    {
+                        diagnostics.push(Diagnostic::new(
+                            MissingGroupSeparator {
+                                import: import.path.clone(),
+                            },
+                            import.range,
+                        ));
+                    }
+                    current = next;
+                    previous_in_order = Some(path);
+                } else {
+                    diagnostics.push(self.wrong_group(import, group, next));
+                }
+                previous = Some(import);
+            } else {
+                diagnostics.push(self.wrong_group(import, group, current));
+            }
+        }
+
+        // Every violation is fixed by laying the whole block out again
+        with_layout_fix(ctx, node, &self.layout, diagnostics)
+    }
+}
+
+impl CustomImportOrder {
+    fn wrong_group(&self, import: &ImportInfo, group: usize, expected: usize) -> Diagnostic {
+        Diagnostic::new(
+            WrongImportGroup {
+                import: import.path.clone(),
+                group: self.groups[group].clone(),
+                expected: self.groups[expected].clone(),
+            },
+            import.range,
+        )
+    }
+}
+
+/// The number of blank lines between `previous` and `import`.
+fn blank_lines_between(source: &str, previous: &ImportInfo, import: &ImportInfo) -> usize {
+    let between = &source[previous.range.end().to_usize()..import.range.start().to_usize()];
+    // The first and last pieces are the ends of the imports' own lines
+    let mut lines: Vec<&str> = between.split('\n').skip(1).collect();
+    lines.pop();
+    lines.iter().filter(|line| line.trim().is_empty()).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::apply_fixes;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    const RULES: &str = "STATIC###STANDARD_JAVA_PACKAGE###THIRD_PARTY_PACKAGE";
+
+    fn check_source(source: &str, properties: &[(&str, &str)]) -> Vec<Diagnostic> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let properties: Properties = properties.iter().copied().collect();
+        let rule = CustomImportOrder::from_config(&properties);
+
+        let mut diagnostics = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            diagnostics.extend(rule.check(&ctx, &node));
+        }
+        diagnostics
+    }
+
+    fn messages(diagnostics: &[Diagnostic]) -> Vec<&str> {
+        diagnostics.iter().map(|d| d.kind.body.as_str()).collect()
+    }
+
+    fn fixed(source: &str, diagnostics: &[Diagnostic]) -> String {
+        apply_fixes(source, diagnostics.iter().filter_map(|d| d.fix.as_ref())).code
+    }
+
+    #[test]
+    fn test_groups_in_order() {
+        let source = "import static java.lang.Math.abs;\n\nimport java.util.List;\n\nimport org.junit.Test;\n\nclass A {}\n";
+        assert!(check_source(source, &[("customImportOrderRules", RULES)]).is_empty());
+    }
+
+    #[test]
+    fn test_wrong_group() {
+        let source = "import static java.lang.Math.abs;\n\nimport org.junit.Test;\n\nimport java.util.List;\n\nclass A {}\n";
+        let diagnostics = check_source(source, &[("customImportOrderRules", RULES)]);
+        assert_eq!(
+            messages(&diagnostics),
+            [
+                "Import statement for 'org.junit.Test' is in the wrong order. Should be in the 'THIRD_PARTY_PACKAGE' group, expecting group 'STANDARD_JAVA_PACKAGE' on this line."
+            ]
+        );
+        assert_eq!(
+            fixed(source, &diagnostics),
+            "import static java.lang.Math.abs;\n\nimport java.util.List;\n\nimport org.junit.Test;\n\nclass A {}\n"
+        );
+    }
+
+    #[test]
+    fn test_non_group() {
+        let source = "import static java.lang.Math.abs;\n\nimport org.junit.Test;\nimport java.util.List;\n\nclass A {}\n";
+        let properties = [("customImportOrderRules", "STATIC###STANDARD_JAVA_PACKAGE")];
+        assert_eq!(
+            messages(&check_source(source, &properties)),
+            [
+                "Imports without groups should be placed at the end of the import list.",
+                "'java.util.List' should be separated from previous import group by one line."
+            ]
+        );
+
+        let source = "import org.junit.Test;\nimport java.util.List;\n\nclass A {}\n";
+        let properties = [("customImportOrderRules", "STANDARD_JAVA_PACKAGE")];
+        assert_eq!(
+            messages(&check_source(source, &properties)),
+            [
+                "Import statement for 'java.util.List' is in the wrong order. Should be in the 'STANDARD_JAVA_PACKAGE' group, expecting not assigned imports on this line."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_separation() {
+        let properties = [("customImportOrderRules", RULES)];
+        let source = "import java.util.List;\nimport org.junit.Test;\n\nclass A {}\n";
+        let diagnostics = check_source(source, &properties);
+        assert_eq!(
+            messages(&diagnostics),
+            ["'org.junit.Test' should be separated from previous import group by one line."]
+        );
+        assert_eq!(
+            fixed(source, &diagnostics),
+            "import java.util.List;\n\nimport org.junit.Test;\n\nclass A {}\n"
+        );
+
+        let source = "import java.io.File;\n\nimport java.util.List;\n\nclass A {}\n";
+        assert_eq!(
+            messages(&check_source(source, &properties)),
+            ["Extra separation in import group before 'java.util.List'"]
+        );
+
+        let source = "import java.util.List;\nimport org.junit.Test;\n\nclass A {}\n";
+        let properties = [
+            ("customImportOrderRules", RULES),
+            ("separateLineBetweenGroups", "false"),
+        ];
+        assert!(check_source(source, &properties).is_empty());
+    }
+
+    #[test]
+    fn test_sort_alphabetically() {
+        let source = "import java.util.List;\nimport java.io.File;\n\nclass A {}\n";
+        assert!(check_source(source, &[("customImportOrderRules", RULES)]).is_empty());
+
+        let properties = [
+            ("customImportOrderRules", RULES),
+            ("sortImportsInGroupAlphabetically", "true"),
+        ];
+        let diagnostics = check_source(source, &properties);
+        assert_eq!(
+            messages(&diagnostics),
+            [
+                "Wrong lexicographical order for 'java.io.File' import. Should be before 'java.util.List'."
+            ]
+        );
+        assert_eq!(
+            fixed(source, &diagnostics),
+            "import java.io.File;\nimport java.util.List;\n\nclass A {}\n"
+        );
+    }
+
+    #[test]
+    fn test_same_package() {
+        let source = "package com.acme.app;\n\nimport java.util.List;\n\nimport com.acme.util.Strings;\n\nclass A {}\n";
+        let properties = [(
+            "customImportOrderRules",
+            "SAME_PACKAGE(2)###STANDARD_JAVA_PACKAGE",
+        )];
+        assert_eq!(
+            messages(&check_source(source, &properties)),
+            [
+                "Import statement for 'com.acme.util.Strings' is in the wrong order. Should be in the 'SAME_PACKAGE' group, expecting not assigned imports on this line."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_comments_kept_by_fix() {
+        let source = "import org.junit.Test; // Tests\n\n// Collections\nimport java.util.List;\n\nclass A {}\n";
+        let diagnostics = check_source(source, &[("customImportOrderRules", RULES)]);
+        assert_eq!(
+            fixed(source, &diagnostics),
+            "// Collections\nimport java.util.List;\n\nimport org.junit.Test; // Tests\n\nclass A {}\n"
+        );
+    }
+}
@@ -0,0 +1,415 @@
+//! ImportOrder rule implementation.
+//!
+//! Checks that imports are grouped, ordered within their groups, and
+//! separated between groups as configured.
+//!
+//! Checkstyle equivalent: ImportOrderCheck
+
+use std::cmp::Ordering;
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+
+use crate::organize_imports::ImportLayout;
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+use super::common::{imports, with_layout_fix};
+
+/// Violation: import is out of order, or in the wrong group.
+#[derive(Debug, Clone)]
+pub struct WrongImportOrder {
+    pub import: String,
+}
+
+impl Violation for WrongImportOrder {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
+
+    fn message(&self) -> String {
+        format!("Wrong order for '{}' import.", self.import)
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("imports", "import.ordering"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.import.clone()]
+    }
+}
+
+/// Violation: import starts a group without a blank line before it.
+#[derive(Debug, Clone)]
+pub struct MissingImportSeparation {
+    pub import: String,
+}
+
+impl Violation for MissingImportSeparation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
+
+    fn message(&self) -> String {
+        format!(
+            "'{}' should be separated from previous imports.",
+            self.import
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("imports", "import.separation"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.import.clone()]
+    }
+}
+
+/// Violation: blank line between imports of the same group.
+#[derive(Debug, Clone)]
+pub struct ExtraImportSeparation {
+    pub import: String,
+}
+
+impl Violation for ExtraImportSeparation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
+
+    fn message(&self) -> String {
+        format!("Extra separation in import group before '{}'", self.import)
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new(
+            "imports",
+            "import.groups.separated.internally",
+        ))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.import.clone()]
+    }
+}
+
+/// Where static imports go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportOrderOption {
+    /// In a group of their own, before the others.
+    Top,
+    /// Before the other imports of their group.
+    Above,
+    /// After the other imports of their group.
+    #[default]
+    Under,
+    /// In a group of their own, after the others.
+    Bottom,
+    /// Sorted in with the other imports of their group.
+    Inflow,
+}
+
+impl ImportOrderOption {
+    fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "top" => Some(Self::Top),
+            "above" => Some(Self::Above),
+            "under" => Some(Self::Under),
+            "bottom" => Some(Self::Bottom),
+            "inflow" => Some(Self::Inflow),
+            _ => None,
+        }
+    }
+
+    /// Whether static imports are a group of their own.
+    fn statics_apart(self) -> bool {
+        matches!(self, Self::Top | Self::Bottom)
+    }
+}
+
+/// Configuration for ImportOrder rule.
+#[derive(Debug, Clone)]
+pub struct ImportOrder {
+    /// The groups, and how imports are ordered within them.
+    layout: ImportLayout,
+    option: ImportOrderOption,
+}
+
+const RELEVANT_KINDS: &[&str] = &["program"];
+
+impl Default for ImportOrder {
+    fn default() -> Self {
+        Self::from_config(&Properties::new())
+    }
+}
+
+impl FromConfig for ImportOrder {
+    const MODULE_NAME: &'static str = "ImportOrder";
+
+    fn from_config(properties: &Properties) -> Self {
+        let option = properties
+            .get("option")
+            .and_then(|v| ImportOrderOption::parse(v))
+            .unwrap_or_default();
+
+        Self {
+            layout: ImportLayout::default().with_import_order(properties),
+            option,
+        }
+    }
+}
+
+/// The import before the one being checked.
+struct Previous<'a> {
+    path: &'a str,
+    group: usize,
+    is_static: bool,
+    /// Line the import ends on.
+    end_line: usize,
+}
+
+impl Rule for ImportOrder {
+    fn name(&self) -> &'static str {
+        "ImportOrder"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        // Only check at program level (once per file)
+        if node.kind() != "program" {
+            return vec![];
+        }
+
+        let source_code = ctx.source_code();
+        let mut diagnostics = Vec::new();
+        let mut previous: Option<Previous> = None;
+
+        for import in imports(ctx, node) {
+            // Groups are found as they are for the fix, so that it settles
+            let group = self.layout.group_of(&import.path, import.is_static, None);
+            let is_static = import.is_static;
+            let line = import.line;
+
+            if let Some(last) = &previous {
+                let wrong_order = match group.cmp(&last.group) {
+                    Ordering::Greater => {
+                        if line - last.end_line < 2
+                            && self.needs_separator(is_static, last.is_static)
+                        {
+                            diagnostics.push(Diagnostic::new(
+                                MissingImportSeparation {
+                                    import: import.path.clone(),
+                                },
+                                import.range,
+                            ));
+                        }
+                        false
+                    }
+                    Ordering::Equal => {
+                        self.layout.sorted() && self.is_out_of_order(last, &import.path, is_static)
+                    }
+                    Ordering::Less => true,
+                };
+                if wrong_order {
+                    diagnostics.push(Diagnostic::new(
+                        WrongImportOrder {
+                            import: import.path.clone(),
+                        },
+                        import.range,
+                    ));
+                }
+                if (group == last.group || !self.needs_separator(is_static, last.is_static))
+                    && line - last.end_line > 1
+                {
+                    diagnostics.push(Diagnostic::new(
+                        ExtraImportSeparation {
+                            import: import.path.clone(),
+                        },
+                        import.range,
+                    ));
+                }
+            }
+
+            previous = Some(Previous {
+                path: &import.path,
+                group,
+                is_static,
+                end_line: source_code.line_column(import.range.end()).line.get(),
+            });
+        }
+
+        // Every violation is fixed by laying the whole block out again
+        with_layout_fix(ctx, node, &self.layout, diagnostics)
+    }
+}
+
+impl ImportOrder {
+    /// Whether an import in the same group as `last` is out of order after
+    /// it.
+    fn is_out_of_order(&self, last: &Previous, path: &str, is_static: bool) -> bool {
+        let after = self.layout.compare(last.path, path) == Ordering::Greater;
+        match self.option {
+            ImportOrderOption::Inflow => after,
+            ImportOrderOption::Top | ImportOrderOption::Above => {
+                (is_static && !last.is_static)
+                    || (last.is_static == is_static && self.is_wrong_order(after, is_static))
+            }
+            ImportOrderOption::Under | ImportOrderOption::Bottom => {
+                (last.is_static && !is_static)
+                    || (last.is_static == is_static && self.is_wrong_order(after, is_static))
+            }
+        }
+    }
+
+    /// Whether two imports sorted as `after` says are in the wrong order.
+    /// Static imports in a group of their own are not sorted.
+    fn is_wrong_order(&self, after: bool, is_static: bool) -> bool {
+        after && !(is_static && self.option.statics_apart())
+    }
+
+    /// Whether an import in a later group than the one before it must be
+    /// separated from it by a blank line.
+    fn needs_separator(&self, is_static: bool, last_static: bool) -> bool {
+        let separated = self.layout.separated();
+        (!is_static && separated)
+            || (!self.option.statics_apart() && separated)
+            || (is_static != last_static && separated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::apply_fixes;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, properties: &[(&str, &str)]) -> Vec<Diagnostic> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let properties: Properties = properties.iter().copied().collect();
+        let rule = ImportOrder::from_config(&properties);
+
+        let mut diagnostics = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            diagnostics.extend(rule.check(&ctx, &node));
+        }
+        diagnostics
+    }
+
+    fn messages(diagnostics: &[Diagnostic]) -> Vec<&str> {
+        diagnostics.iter().map(|d| d.kind.body.as_str()).collect()
+    }
+
+    fn fixed(source: &str, diagnostics: &[Diagnostic]) -> String {
+        apply_fixes(source, diagnostics.iter().filter_map(|d| d.fix.as_ref())).code
+    }
+
+    #[test]
+    fn test_sorted_imports() {
+        let source = "import java.io.File;\nimport java.util.List;\nimport static java.lang.Math.abs;\n\nclass A {}\n";
+        assert!(check_source(source, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_wrong_order() {
+        let source = "import java.util.List;\nimport java.io.File;\n\nclass A {}\n";
+        let diagnostics = check_source(source, &[]);
+        assert_eq!(
+            messages(&diagnostics),
+            ["Wrong order for 'java.io.File' import."]
+        );
+        assert_eq!(
+            fixed(source, &diagnostics),
+            "import java.io.File;\nimport java.util.List;\n\nclass A {}\n"
+        );
+    }
+
+    #[test]
+    fn test_unordered() {
+        let source = "import java.util.List;\nimport java.io.File;\n\nclass A {}\n";
+        assert!(check_source(source, &[("ordered", "false")]).is_empty());
+    }
+
+    #[test]
+    fn test_case_sensitive() {
+        let source = "import java.util.Zebra;\nimport java.util.apple;\n\nclass A {}\n";
+        assert!(check_source(source, &[]).is_empty());
+        assert_eq!(
+            messages(&check_source(source, &[("caseSensitive", "false")])),
+            ["Wrong order for 'java.util.apple' import."]
+        );
+    }
+
+    #[test]
+    fn test_groups_and_separation() {
+        let properties = [("groups", "java,org"), ("separated", "true")];
+        let source = "import java.util.List;\nimport org.junit.Test;\n\nclass A {}\n";
+        let diagnostics = check_source(source, &properties);
+        assert_eq!(
+            messages(&diagnostics),
+            ["'org.junit.Test' should be separated from previous imports."]
+        );
+        assert_eq!(
+            fixed(source, &diagnostics),
+            "import java.util.List;\n\nimport org.junit.Test;\n\nclass A {}\n"
+        );
+
+        let source = "import org.junit.Test;\n\nimport java.util.List;\n\nclass A {}\n";
+        assert_eq!(
+            messages(&check_source(source, &properties)),
+            ["Wrong order for 'java.util.List' import."]
+        );
+
+        let source = "import java.io.File;\n\nimport java.util.List;\n\nclass A {}\n";
+        assert_eq!(
+            messages(&check_source(source, &properties)),
+            ["Extra separation in import group before 'java.util.List'"]
+        );
+    }
+
+    #[test]
+    fn test_option_top() {
+        let properties = [("option", "top"), ("separated", "true")];
+        let source = "import static java.lang.Math.abs;\n\nimport java.util.List;\n\nclass A {}\n";
+        assert!(check_source(source, &properties).is_empty());
+
+        let source = "import java.util.List;\n\nimport static java.lang.Math.abs;\n\nclass A {}\n";
+        let diagnostics = check_source(source, &properties);
+        assert_eq!(
+            messages(&diagnostics),
+            ["Wrong order for 'java.lang.Math.abs' import."]
+        );
+        assert_eq!(
+            fixed(source, &diagnostics),
+            "import static java.lang.Math.abs;\n\nimport java.util.List;\n\nclass A {}\n"
+        );
+    }
+
+    #[test]
+    fn test_option_under() {
+        let source = "import static java.lang.Math.abs;\nimport java.util.List;\n\nclass A {}\n";
+        assert_eq!(
+            messages(&check_source(source, &[])),
+            ["Wrong order for 'java.util.List' import."]
+        );
+    }
+
+    #[test]
+    fn test_option_inflow() {
+        let source = "import java.io.File;\nimport static java.lang.Math.abs;\nimport java.util.List;\n\nclass A {}\n";
+        assert!(check_source(source, &[("option", "inflow")]).is_empty());
+    }
+
+    #[test]
+    fn test_no_fix_when_settled() {
+        // Comments between groups can't be removed, so only the order is fixed
+        let properties = [("groups", "java,org")];
+        let source =
+            "import java.util.List;\n// Test support\nimport org.junit.Test;\n\nclass A {}\n";
+        let diagnostics = check_source(source, &properties);
+        assert_eq!(
+            messages(&diagnostics),
+            ["Extra separation in import group before 'org.junit.Test'"]
+        );
+        assert!(diagnostics[0].fix.is_none());
+    }
+}
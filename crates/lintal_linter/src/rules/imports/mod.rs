@@ -1,8 +1,16 @@
 //! Import-related lint rules.
 
+mod avoid_star_import;
+mod avoid_static_import;
 pub mod common;
+mod custom_import_order;
+mod import_order;
 mod redundant_import;
 mod unused_imports;
 
+pub use avoid_star_import::AvoidStarImport;
+pub use avoid_static_import::AvoidStaticImport;
+pub use custom_import_order::CustomImportOrder;
+pub use import_order::ImportOrder;
 pub use redundant_import::RedundantImport;
 pub use unused_imports::UnusedImports;
@@ -9,18 +9,19 @@
 
 use std::collections::HashMap;
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
-use lintal_source_file::LineIndex;
 use lintal_text_size::{TextRange, TextSize};
 
 use crate::{CheckContext, FromConfig, Properties, Rule};
 
-use super::common::{ImportInfo, collect_imports, get_package_name};
+use super::common::{ImportInfo, get_package_name, imports};
 
 /// Violation: import from same package.
 #[derive(Debug, Clone)]
-pub struct SamePackageImport;
+pub struct SamePackageImport {
+    pub import: String,
+}
 
 impl Violation for SamePackageImport {
     const FIX_AVAILABILITY: FixAvailability = FixAvailability::Always;
@@ -28,11 +29,21 @@ impl Violation for SamePackageImport {
     fn message(&self) -> String {
         "Redundant import from the same package.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("imports", "import.same"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.import.clone()]
+    }
 }
 
 /// Violation: import from java.lang package.
 #[derive(Debug, Clone)]
-pub struct JavaLangImport;
+pub struct JavaLangImport {
+    pub import: String,
+}
 
 impl Violation for JavaLangImport {
     const FIX_AVAILABILITY: FixAvailability = FixAvailability::Always;
@@ -40,11 +51,20 @@ impl Violation for JavaLangImport {
     fn message(&self) -> String {
         "Redundant import from the java.lang package.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("imports", "import.lang"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.import.clone()]
+    }
 }
 
 /// Violation: duplicate import.
 #[derive(Debug, Clone)]
 pub struct DuplicateImport {
+    pub import: String,
     pub first_line: usize,
 }
 
@@ -54,6 +74,14 @@ impl Violation for DuplicateImport {
     fn message(&self) -> String {
         format!("Duplicate import to line {}.", self.first_line)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("imports", "import.duplicate"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.first_line.to_string(), self.import.clone()]
+    }
 }
 
 /// Configuration for RedundantImport rule.
@@ -86,21 +114,26 @@ impl Rule for RedundantImport {
         }
 
         let source = ctx.source();
-        let line_index = LineIndex::from_source_text(source);
         let ts_node = node.inner();
 
-        let imports = collect_imports(ts_node, source, &line_index);
+        let imports = imports(ctx, node);
         let current_package = get_package_name(ts_node, source);
 
         let mut diagnostics = Vec::new();
         let mut seen: HashMap<&str, usize> = HashMap::new();
 
-        for import in &imports {
+        for import in imports {
             // Check for duplicate
             if let Some(&first_line) = seen.get(import.path.as_str()) {
                 diagnostics.push(
-                    Diagnostic::new(DuplicateImport { first_line }, import.range)
-                        .with_fix(self.create_delete_fix(import, source)),
+                    Diagnostic::new(
+                        DuplicateImport {
+                            import: import.path.clone(),
+                            first_line,
+                        },
+                        import.range,
+                    )
+                    .with_fix(self.create_delete_fix(import, source)),
                 );
                 continue;
             }
@@ -109,8 +142,13 @@ impl Rule for RedundantImport {
             // Check for java.lang import
             if self.is_java_lang_import(import) {
                 diagnostics.push(
-                    Diagnostic::new(JavaLangImport, import.range)
-                        .with_fix(self.create_delete_fix(import, source)),
+                    Diagnostic::new(
+                        JavaLangImport {
+                            import: import.path.clone(),
+                        },
+                        import.range,
+                    )
+                    .with_fix(self.create_delete_fix(import, source)),
                 );
                 continue;
             }
@@ -120,8 +158,13 @@ impl Rule for RedundantImport {
                 && self.is_same_package_import(import, pkg)
             {
                 diagnostics.push(
-                    Diagnostic::new(SamePackageImport, import.range)
-                        .with_fix(self.create_delete_fix(import, source)),
+                    Diagnostic::new(
+                        SamePackageImport {
+                            import: import.path.clone(),
+                        },
+                        import.range,
+                    )
+                    .with_fix(self.create_delete_fix(import, source)),
                 );
             }
         }
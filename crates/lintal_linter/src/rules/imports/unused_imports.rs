@@ -4,14 +4,13 @@
 //!
 //! Checkstyle equivalent: UnusedImportsCheck
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
-use lintal_source_file::LineIndex;
 use lintal_text_size::{TextRange, TextSize};
 
 use crate::{CheckContext, FromConfig, Properties, Rule};
 
-use super::common::{ImportInfo, collect_imports, collect_javadoc_references, collect_type_usages};
+use super::common::{ImportInfo, collect_javadoc_references, collect_type_usages, imports};
 
 /// Violation: import is unused.
 #[derive(Debug, Clone)]
@@ -25,6 +24,14 @@ impl Violation for UnusedImportViolation {
     fn message(&self) -> String {
         format!("Unused import - {}.", self.import_path)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("imports", "import.unused"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.import_path.clone()]
+    }
 }
 
 /// Configuration for UnusedImports rule.
@@ -73,10 +80,9 @@ impl Rule for UnusedImports {
         }
 
         let source = ctx.source();
-        let line_index = LineIndex::from_source_text(source);
         let ts_node = node.inner();
 
-        let imports = collect_imports(ts_node, source, &line_index);
+        let imports = imports(ctx, node);
 
         // Collect all type usages
         let mut usages = collect_type_usages(ts_node, source);
@@ -88,7 +94,7 @@ impl Rule for UnusedImports {
 
         let mut diagnostics = Vec::new();
 
-        for import in &imports {
+        for import in imports {
             // Skip wildcard imports - can't verify without type resolution
             if import.is_wildcard {
                 continue;
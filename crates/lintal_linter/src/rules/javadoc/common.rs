@@ -0,0 +1,438 @@
+//! Shared utilities for Javadoc rules: finding the Javadoc of a
+//! declaration, parsing its block tags, and the scope of declarations.
+//!
+//! Like checkstyle, a declaration's Javadoc is the Javadoc comment ending
+//! on the line above it, skipping blank lines and `//` comments.
+
+use std::collections::HashMap;
+
+use lintal_java_cst::{CommentKind, CstNode};
+use lintal_source_file::OneIndexed;
+use lintal_text_size::{TextRange, TextSize};
+use regex::Regex;
+
+use crate::CheckContext;
+use crate::rules::modifier::common::has_modifier;
+
+/// A Javadoc comment.
+#[derive(Debug, Clone, Copy)]
+pub struct Javadoc<'a> {
+    /// The comment, from `/**` to `*/`.
+    pub text: &'a str,
+    pub range: TextRange,
+}
+
+/// A tag of a Javadoc comment, such as `@param name` or `{@inheritDoc}`.
+#[derive(Debug, Clone)]
+pub struct JavadocTag<'a> {
+    /// The tag name without `@`, e.g. `param`.
+    pub name: &'a str,
+    /// The first argument, e.g. the parameter name of `@param`.
+    pub arg: Option<&'a str>,
+    /// The tag, from its `@`.
+    pub range: TextRange,
+}
+
+impl JavadocTag<'_> {
+    pub fn is_param(&self) -> bool {
+        self.name == "param"
+    }
+
+    pub fn is_return(&self) -> bool {
+        self.name == "return"
+    }
+
+    pub fn is_throws(&self) -> bool {
+        matches!(self.name, "throws" | "exception")
+    }
+
+    pub fn is_inherit_doc(&self) -> bool {
+        self.name == "inheritDoc"
+    }
+
+    pub fn is_see_or_inherit_doc(&self) -> bool {
+        matches!(self.name, "see" | "inheritDoc")
+    }
+}
+
+lazy_static::lazy_static! {
+    /// `@param`, `@throws` or `@exception` and its argument.
+    static ref ARG_TAG: Regex =
+        Regex::new(r"^\s*(?:\*|/\*\*)?\s*@(throws|exception|param)\s+(\S+)").unwrap();
+
+    /// `@return` or `@see` followed by text on the same line.
+    static ref NO_ARG_TAG: Regex = Regex::new(r"^\s*(?:\*|/\*\*)?\s*@(return|see)\s+\S").unwrap();
+
+    /// `@return` or `@see` ending its line, perhaps continued on the next.
+    static ref NO_ARG_TAG_LINE_END: Regex =
+        Regex::new(r"^\s*(?:\*|/\*\*)?\s*@(return|see)\s*$").unwrap();
+
+    static ref INHERIT_DOC: Regex = Regex::new(r"\{\s*@(inheritDoc)\s*\}").unwrap();
+
+    /// The first text on a line after a tag ending its line: the end of the
+    /// comment, the next tag, or a continuation of the tag.
+    static ref CONTINUATION: Regex = Regex::new(r"(\*/|@|[^\s*])").unwrap();
+}
+
+impl<'a> Javadoc<'a> {
+    /// The tags checkstyle's JavadocMethod validates, in source order:
+    /// `@param`, `@return`, `@throws`, `@exception`, `@see` and
+    /// `{@inheritDoc}`, at most one per line.
+    pub fn tags(&self) -> Vec<JavadocTag<'a>> {
+        let lines: Vec<(usize, &'a str)> = self
+            .text
+            .split('\n')
+            .scan(0, |offset, line| {
+                let start = *offset;
+                *offset += line.len() + 1;
+                Some((start, line.trim_end_matches('\r')))
+            })
+            .collect();
+        let mut tags = vec![];
+        for (index, &(line_start, line)) in lines.iter().enumerate() {
+            let captures = ARG_TAG
+                .captures(line)
+                .or_else(|| NO_ARG_TAG.captures(line))
+                .or_else(|| INHERIT_DOC.captures(line))
+                .or_else(|| {
+                    // A tag alone on its line counts if text follows before
+                    // the next tag or the end of the comment
+                    let next = lines[index + 1..]
+                        .iter()
+                        .find_map(|(_, line)| CONTINUATION.find(line));
+                    NO_ARG_TAG_LINE_END
+                        .captures(line)
+                        .filter(|_| next.is_some_and(|next| !matches!(next.as_str(), "*/" | "@")))
+                });
+            let Some(name) = captures.as_ref().and_then(|c| c.get(1)) else {
+                continue;
+            };
+
+            let start = self.range.start() + TextSize::from((line_start + name.start() - 1) as u32);
+            tags.push(JavadocTag {
+                name: name.as_str(),
+                arg: captures
+                    .as_ref()
+                    .and_then(|c| c.get(2))
+                    .map(|arg| arg.as_str()),
+                range: TextRange::at(start, TextSize::of(name.as_str()) + TextSize::from(1)),
+            });
+        }
+        tags
+    }
+}
+
+/// The Javadoc comments of the file by the line they end on, collected once
+/// and shared by the Javadoc rules.
+struct Javadocs(HashMap<OneIndexed, TextRange>);
+
+/// The Javadoc of the declaration `node`: the Javadoc comment ending on the
+/// line above the declaration, with only blank lines and `//` comments in
+/// between.
+pub fn javadoc_before<'a>(ctx: &CheckContext<'a>, node: &CstNode) -> Option<Javadoc<'a>> {
+    let source_code = ctx.source_code();
+    let javadocs = ctx.memo(|| {
+        Javadocs(
+            ctx.comments(node)
+                .comments()
+                .iter()
+                .filter(|comment| comment.kind == CommentKind::Javadoc)
+                .map(|comment| {
+                    let end = comment.range.end() - TextSize::from(1);
+                    (source_code.line_index(end), comment.range)
+                })
+                .collect(),
+        )
+    });
+
+    let start = declaration_start(node);
+    let mut line = source_code.line_index(start).checked_sub(OneIndexed::MIN)?;
+    while line > OneIndexed::MIN {
+        let text = source_code.line_text(line).trim();
+        if !text.is_empty() && !text.starts_with("//") {
+            break;
+        }
+        line = line.saturating_sub(1);
+    }
+
+    javadocs.0.get(&line).map(|&range| Javadoc {
+        text: ctx.text_at(range),
+        range,
+    })
+}
+
+/// Where a declaration starts: its first token, leaving out comments the
+/// parser placed in it.
+pub fn declaration_start(node: &CstNode) -> TextSize {
+    node.children()
+        .find(|child| !matches!(child.kind(), "line_comment" | "block_comment"))
+        .map_or(node.range().start(), |child| declaration_start(&child))
+}
+
+/// How visible a declaration is, from most to least visible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Scope {
+    Nothing,
+    Public,
+    Protected,
+    Package,
+    Private,
+    AnonInner,
+}
+
+impl Scope {
+    /// Parse a checkstyle scope name, such as `public` or `anoninner`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "nothing" => Some(Self::Nothing),
+            "public" => Some(Self::Public),
+            "protected" => Some(Self::Protected),
+            "package" => Some(Self::Package),
+            "private" => Some(Self::Private),
+            "anoninner" => Some(Self::AnonInner),
+            _ => None,
+        }
+    }
+
+    /// Whether this scope is at least as visible as `scope`.
+    pub fn is_in(self, scope: Scope) -> bool {
+        self <= scope
+    }
+}
+
+const TYPE_DECLARATIONS: &[&str] = &[
+    "class_declaration",
+    "interface_declaration",
+    "enum_declaration",
+    "record_declaration",
+    "annotation_type_declaration",
+];
+
+/// The scope of a declaration: its access modifier, or the implicit one of
+/// its enclosing body, public in interfaces and annotations, private for
+/// enum constructors, package otherwise.
+pub fn scope_of(node: &CstNode) -> Scope {
+    let modifiers = node.children().find(|child| child.kind() == "modifiers");
+    if let Some(modifiers) = modifiers {
+        for (modifier, scope) in [
+            ("public", Scope::Public),
+            ("protected", Scope::Protected),
+            ("private", Scope::Private),
+        ] {
+            if has_modifier(&modifiers, modifier) {
+                return scope;
+            }
+        }
+    }
+
+    match enclosing_type(node).map(|owner| owner.kind()) {
+        Some("interface_declaration" | "annotation_type_declaration") => Scope::Public,
+        Some("enum_declaration") if node.kind() == "constructor_declaration" => Scope::Private,
+        _ => Scope::Package,
+    }
+}
+
+/// The least visible scope of the types declaring `node`, anonymous classes
+/// being [`Scope::AnonInner`], or `None` outside any type.
+pub fn surrounding_scope(node: &CstNode) -> Option<Scope> {
+    let mut scope: Option<Scope> = None;
+    for ancestor in std::iter::successors(node.parent(), CstNode::parent) {
+        if TYPE_DECLARATIONS.contains(&ancestor.kind()) {
+            let declared = scope_of(&ancestor);
+            if scope.is_none_or(|scope| scope.is_in(declared)) {
+                scope = Some(declared);
+            }
+        } else if ancestor.kind() == "object_creation_expression" {
+            return Some(Scope::AnonInner);
+        }
+    }
+    scope
+}
+
+/// The nearest type declaration or anonymous class around `node`.
+fn enclosing_type<'a>(node: &CstNode<'a>) -> Option<CstNode<'a>> {
+    std::iter::successors(node.parent(), CstNode::parent).find(|ancestor| {
+        TYPE_DECLARATIONS.contains(&ancestor.kind())
+            || ancestor.kind() == "object_creation_expression"
+    })
+}
+
+/// Whether the declaration is annotated with one of `annotations`, by the
+/// name as written, e.g. `Override`.
+pub fn has_annotation(node: &CstNode, annotations: &[String]) -> bool {
+    node.children()
+        .filter(|child| child.kind() == "modifiers")
+        .flat_map(|modifiers| modifiers.children())
+        .filter(|child| matches!(child.kind(), "annotation" | "marker_annotation"))
+        .filter_map(|annotation| annotation.child_by_field_name("name"))
+        .any(|name| annotations.iter().any(|allowed| allowed == name.text()))
+}
+
+/// The checkstyle token of a declaration the Javadoc method rules check.
+pub fn declaration_token(kind: &str) -> Option<&'static str> {
+    match kind {
+        "method_declaration" => Some("METHOD_DEF"),
+        "constructor_declaration" => Some("CTOR_DEF"),
+        "compact_constructor_declaration" => Some("COMPACT_CTOR_DEF"),
+        "annotation_type_element_declaration" => Some("ANNOTATION_FIELD_DEF"),
+        _ => None,
+    }
+}
+
+/// The declarations the Javadoc method rules check.
+pub const METHOD_KINDS: &[&str] = &[
+    "method_declaration",
+    "constructor_declaration",
+    "compact_constructor_declaration",
+    "annotation_type_element_declaration",
+];
+
+/// Parse a comma-separated list property.
+pub fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn tags(text: &str) -> Vec<(String, Option<String>)> {
+        let javadoc = Javadoc {
+            text,
+            range: TextRange::up_to(TextSize::of(text)),
+        };
+        javadoc
+            .tags()
+            .iter()
+            .map(|tag| (tag.name.to_string(), tag.arg.map(String::from)))
+            .collect()
+    }
+
+    fn tag(name: &str, arg: Option<&str>) -> (String, Option<String>) {
+        (name.to_string(), arg.map(String::from))
+    }
+
+    #[test]
+    fn test_tags() {
+        let text = "/**
+     * Does things.
+     *
+     * @param a the first
+     * @param b
+     * @return
+     *     the result
+     * @throws IOException when it fails
+     * @see Other
+     * @author nobody
+     */";
+        assert_eq!(
+            tags(text),
+            [
+                tag("param", Some("a")),
+                tag("param", Some("b")),
+                tag("return", None),
+                tag("throws", Some("IOException")),
+                tag("see", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tag_without_text() {
+        assert_eq!(tags("/**\n * @return\n */"), []);
+        assert_eq!(tags("/**\n * @return\n * @param a x\n */").len(), 1);
+        assert_eq!(tags("/** {@inheritDoc} */"), [tag("inheritDoc", None)]);
+        assert_eq!(tags("/** @return the value */"), [tag("return", None)]);
+    }
+
+    #[test]
+    fn test_tag_range() {
+        let text = "/**\n * @param a x\n */";
+        let javadoc = Javadoc {
+            text,
+            range: TextRange::at(TextSize::from(10), TextSize::of(text)),
+        };
+        let range = javadoc.tags()[0].range;
+        assert_eq!(&text[range - TextSize::from(10)], "@param");
+    }
+
+    fn find_method<'a>(source: &'a str, tree: &'a tree_sitter::Tree, name: &str) -> CstNode<'a> {
+        TreeWalker::new(tree.root_node(), source)
+            .find(|node| {
+                node.kind() == "method_declaration"
+                    && node
+                        .child_by_field_name("name")
+                        .is_some_and(|n| n.text() == name)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_javadoc_before() {
+        let source = r#"
+class A {
+    /** Documented. */
+    void a() {}
+
+    /** Across a blank line and a comment. */
+
+    // note
+    @Deprecated
+    void b() {}
+
+    /** Not the Javadoc of c: a block comment is in between. */
+    /* other */
+    void c() {}
+
+    /** Same line. */ void d() {}
+}
+"#;
+        let result = JavaParser::new().parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let javadoc = |name| {
+            javadoc_before(&ctx, &find_method(source, &result.tree, name)).map(|doc| doc.text)
+        };
+        assert_eq!(javadoc("a"), Some("/** Documented. */"));
+        assert_eq!(
+            javadoc("b"),
+            Some("/** Across a blank line and a comment. */")
+        );
+        assert_eq!(javadoc("c"), None);
+        assert_eq!(javadoc("d"), None);
+    }
+
+    #[test]
+    fn test_scopes() {
+        let source = r#"
+public class A {
+    void a() {}
+    private class B {
+        public void b() {}
+    }
+    interface C {
+        void c();
+    }
+    Object o = new Object() {
+        public void d() {}
+    };
+}
+"#;
+        let result = JavaParser::new().parse(source).unwrap();
+        let method = |name| find_method(source, &result.tree, name);
+        assert_eq!(scope_of(&method("a")), Scope::Package);
+        assert_eq!(surrounding_scope(&method("a")), Some(Scope::Public));
+        assert_eq!(scope_of(&method("b")), Scope::Public);
+        assert_eq!(surrounding_scope(&method("b")), Some(Scope::Private));
+        assert_eq!(scope_of(&method("c")), Scope::Public);
+        assert_eq!(surrounding_scope(&method("c")), Some(Scope::Package));
+        assert_eq!(surrounding_scope(&method("d")), Some(Scope::AnonInner));
+        assert!(Scope::Public.is_in(Scope::Protected));
+        assert!(!Scope::Private.is_in(Scope::Package));
+    }
+}
@@ -0,0 +1,700 @@
+//! JavadocMethod rule implementation.
+//!
+//! Checks the `@param`, `@return` and `@throws` tags of method and
+//! constructor Javadoc against the signature.
+//!
+//! Checkstyle equivalent: JavadocMethodCheck
+
+use std::collections::HashSet;
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+use lintal_text_size::TextRange;
+
+use crate::rules::modifier::common::has_modifier;
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+use super::common::{
+    JavadocTag, METHOD_KINDS, Scope, declaration_token, has_annotation, javadoc_before, parse_list,
+    scope_of,
+};
+
+/// Violation: a parameter, type parameter or exception without its tag.
+#[derive(Debug, Clone)]
+pub struct ExpectedTagViolation {
+    pub tag: &'static str,
+    pub name: String,
+}
+
+impl Violation for ExpectedTagViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!("Expected {} tag for '{}'.", self.tag, self.name)
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("javadoc", "javadoc.expectedTag"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.tag.to_string(), self.name.clone()]
+    }
+}
+
+/// Violation: `@param` tag naming no parameter.
+#[derive(Debug, Clone)]
+pub struct UnusedTagViolation {
+    pub tag: &'static str,
+    pub name: String,
+}
+
+impl Violation for UnusedTagViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!("Unused {} tag for '{}'.", self.tag, self.name)
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("javadoc", "javadoc.unusedTag"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.tag.to_string(), self.name.clone()]
+    }
+}
+
+/// Violation: tag the declaration has no use for, such as `@return` on a
+/// void method.
+#[derive(Debug, Clone)]
+pub struct UnusedTagGeneralViolation;
+
+impl Violation for UnusedTagGeneralViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        "Unused Javadoc tag.".to_string()
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("javadoc", "javadoc.unusedTagGeneral"))
+    }
+}
+
+/// Violation: non-void method without `@return` tag.
+#[derive(Debug, Clone)]
+pub struct ReturnExpectedViolation;
+
+impl Violation for ReturnExpectedViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        "Expected an @return tag.".to_string()
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("javadoc", "javadoc.return.expected"))
+    }
+}
+
+/// Violation: `@return` tag given twice.
+#[derive(Debug, Clone)]
+pub struct DuplicateTagViolation {
+    pub tag: &'static str,
+}
+
+impl Violation for DuplicateTagViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!("Duplicate {} tag.", self.tag)
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("javadoc", "javadoc.duplicateTag"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.tag.to_string()]
+    }
+}
+
+/// Violation: `{@inheritDoc}` on a declaration that inherits nothing.
+#[derive(Debug, Clone)]
+pub struct InvalidInheritDocViolation;
+
+impl Violation for InvalidInheritDocViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        "Invalid use of the {@inheritDoc} tag.".to_string()
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("javadoc", "javadoc.invalidInheritDoc"))
+    }
+}
+
+/// Configuration for JavadocMethod rule.
+#[derive(Debug, Clone)]
+pub struct JavadocMethod {
+    /// Access modifiers of the declarations to check (default: all).
+    pub access_modifiers: Vec<Scope>,
+    /// Whether parameters may go without `@param` tag.
+    pub allow_missing_param_tags: bool,
+    /// Whether non-void methods may go without `@return` tag.
+    pub allow_missing_return_tag: bool,
+    /// Annotations exempting a method from expected tags, e.g. `Override`.
+    pub allowed_annotations: Vec<String>,
+    /// Whether exceptions declared or thrown need a `@throws` tag.
+    pub validate_throws: bool,
+    /// Declarations to check: METHOD_DEF, CTOR_DEF, ANNOTATION_FIELD_DEF,
+    /// COMPACT_CTOR_DEF.
+    pub tokens: HashSet<String>,
+}
+
+const DEFAULT_TOKENS: &[&str] = &[
+    "METHOD_DEF",
+    "CTOR_DEF",
+    "ANNOTATION_FIELD_DEF",
+    "COMPACT_CTOR_DEF",
+];
+
+impl Default for JavadocMethod {
+    fn default() -> Self {
+        Self {
+            access_modifiers: vec![
+                Scope::Public,
+                Scope::Protected,
+                Scope::Package,
+                Scope::Private,
+            ],
+            allow_missing_param_tags: false,
+            allow_missing_return_tag: false,
+            allowed_annotations: vec!["Override".to_string()],
+            validate_throws: false,
+            tokens: DEFAULT_TOKENS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl FromConfig for JavadocMethod {
+    const MODULE_NAME: &'static str = "JavadocMethod";
+
+    fn from_config(properties: &Properties) -> Self {
+        let default = Self::default();
+        let flag = |name: &str, default: bool| {
+            properties
+                .get(name)
+                .map(|v| v.trim() == "true")
+                .unwrap_or(default)
+        };
+
+        let access_modifiers = properties
+            .get("accessModifiers")
+            .map(|v| v.split(',').filter_map(Scope::parse).collect())
+            .unwrap_or(default.access_modifiers);
+
+        let allowed_annotations = properties
+            .get("allowedAnnotations")
+            .map(|v| parse_list(v))
+            .unwrap_or(default.allowed_annotations);
+
+        let tokens = properties
+            .get("tokens")
+            .map(|v| parse_list(v).into_iter().collect())
+            .unwrap_or(default.tokens);
+
+        Self {
+            access_modifiers,
+            allow_missing_param_tags: flag(
+                "allowMissingParamTags",
+                default.allow_missing_param_tags,
+            ),
+            allow_missing_return_tag: flag(
+                "allowMissingReturnTag",
+                default.allow_missing_return_tag,
+            ),
+            allowed_annotations,
+            validate_throws: flag("validateThrows", default.validate_throws),
+            tokens,
+        }
+    }
+}
+
+impl Rule for JavadocMethod {
+    fn name(&self) -> &'static str {
+        "JavadocMethod"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        METHOD_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        let Some(token) = declaration_token(node.kind()) else {
+            return vec![];
+        };
+        if !self.tokens.contains(token) || !self.access_modifiers.contains(&scope_of(node)) {
+            return vec![];
+        }
+        let Some(javadoc) = javadoc_before(ctx, node) else {
+            return vec![];
+        };
+
+        let mut tags = javadoc.tags();
+        let mut diagnostics = vec![];
+
+        // A lone {@inheritDoc} stands for the inherited documentation
+        if let [tag] = tags.as_slice()
+            && tag.is_inherit_doc()
+        {
+            if !is_inherit_doc_valid(node) {
+                diagnostics.push(Diagnostic::new(InvalidInheritDocViolation, node.range()));
+            }
+            return diagnostics;
+        }
+
+        if node.kind() == "annotation_type_element_declaration" {
+            self.check_return_tag(&mut tags, node, true, &mut diagnostics);
+        } else {
+            let report_expected = !tags.iter().any(JavadocTag::is_inherit_doc)
+                && !has_annotation(node, &self.allowed_annotations);
+
+            self.check_param_tags(&mut tags, node, report_expected, &mut diagnostics);
+            self.check_throws_tags(&mut tags, node, report_expected, &mut diagnostics);
+            if is_non_void_method(node) {
+                self.check_return_tag(&mut tags, node, report_expected, &mut diagnostics);
+            }
+        }
+
+        diagnostics.extend(
+            tags.iter()
+                .filter(|tag| !tag.is_see_or_inherit_doc())
+                .map(|tag| Diagnostic::new(UnusedTagGeneralViolation, tag.range)),
+        );
+        diagnostics
+    }
+}
+
+impl JavadocMethod {
+    /// Match the `@param` tags, taking them out of `tags`, with the
+    /// parameters and type parameters of the declaration.
+    fn check_param_tags(
+        &self,
+        tags: &mut Vec<JavadocTag>,
+        node: &CstNode,
+        report_expected: bool,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let mut params = parameter_names(node);
+        let mut type_params = type_parameter_names(node);
+
+        for tag in tags.extract_if(.., |tag| tag.is_param()) {
+            let arg = tag.arg.unwrap_or_default();
+            let found = match arg.strip_prefix('<').and_then(|a| a.strip_suffix('>')) {
+                Some(type_param) => take_named(&mut type_params, type_param),
+                None => take_named(&mut params, arg),
+            };
+            if !found {
+                diagnostics.push(Diagnostic::new(
+                    UnusedTagViolation {
+                        tag: "@param",
+                        name: arg.to_string(),
+                    },
+                    tag.range,
+                ));
+            }
+        }
+
+        if self.allow_missing_param_tags || !report_expected {
+            return;
+        }
+        for (name, range) in params {
+            diagnostics.push(Diagnostic::new(
+                ExpectedTagViolation {
+                    tag: "@param",
+                    name: name.to_string(),
+                },
+                range,
+            ));
+        }
+        for (name, range) in type_params {
+            diagnostics.push(Diagnostic::new(
+                ExpectedTagViolation {
+                    tag: "@param",
+                    name: format!("<{name}>"),
+                },
+                range,
+            ));
+        }
+    }
+
+    /// Match the `@throws` and `@exception` tags, taking them out of `tags`,
+    /// with the exceptions the declaration declares, and with
+    /// `validateThrows` those it throws.
+    ///
+    /// Documenting an exception the declaration does not declare is fine:
+    /// it may be unchecked.
+    fn check_throws_tags(
+        &self,
+        tags: &mut Vec<JavadocTag>,
+        node: &CstNode,
+        report_expected: bool,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let mut exceptions = declared_exceptions(node);
+        if self.validate_throws {
+            for (name, range) in thrown_exceptions(node) {
+                if !exceptions.iter().any(|(declared, _)| *declared == name) {
+                    exceptions.push((name, range));
+                }
+            }
+        }
+
+        let documented: Vec<&str> = tags
+            .extract_if(.., |tag| tag.is_throws())
+            .filter_map(|tag| tag.arg)
+            .collect();
+
+        if !self.validate_throws || !report_expected {
+            return;
+        }
+        for (name, range) in exceptions {
+            if !documented.iter().any(|doc| is_same_class(doc, &name)) {
+                diagnostics.push(Diagnostic::new(
+                    ExpectedTagViolation {
+                        tag: "@throws",
+                        name,
+                    },
+                    range,
+                ));
+            }
+        }
+    }
+
+    /// Take the `@return` tags out of `tags`, reporting all but the first,
+    /// or the lack of one.
+    fn check_return_tag(
+        &self,
+        tags: &mut Vec<JavadocTag>,
+        node: &CstNode,
+        report_expected: bool,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let returns: Vec<JavadocTag> = tags.extract_if(.., |tag| tag.is_return()).collect();
+
+        diagnostics.extend(
+            returns
+                .iter()
+                .skip(1)
+                .map(|tag| Diagnostic::new(DuplicateTagViolation { tag: "@return" }, tag.range)),
+        );
+        if returns.is_empty() && !self.allow_missing_return_tag && report_expected {
+            diagnostics.push(Diagnostic::new(ReturnExpectedViolation, node.range()));
+        }
+    }
+}
+
+/// Remove the first entry named `name`, returning whether there was one.
+fn take_named(names: &mut Vec<(&str, TextRange)>, name: &str) -> bool {
+    match names.iter().position(|(n, _)| *n == name) {
+        Some(index) => {
+            names.remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
+/// The names of the parameters of a declaration, for a compact constructor
+/// those of its record.
+fn parameter_names<'a>(node: &CstNode<'a>) -> Vec<(&'a str, TextRange)> {
+    let declaration = if node.kind() == "compact_constructor_declaration" {
+        std::iter::successors(node.parent(), CstNode::parent)
+            .find(|ancestor| ancestor.kind() == "record_declaration")
+    } else {
+        Some(*node)
+    };
+
+    declaration
+        .and_then(|declaration| declaration.child_by_field_name("parameters"))
+        .into_iter()
+        .flat_map(|params| params.named_children())
+        .filter_map(|param| match param.kind() {
+            "formal_parameter" => param.child_by_field_name("name"),
+            "spread_parameter" => param
+                .named_children()
+                .find(|child| child.kind() == "variable_declarator")
+                .and_then(|declarator| declarator.child_by_field_name("name")),
+            _ => None,
+        })
+        .map(|name| (name.text(), name.range()))
+        .collect()
+}
+
+/// The names of the type parameters of a declaration.
+fn type_parameter_names<'a>(node: &CstNode<'a>) -> Vec<(&'a str, TextRange)> {
+    node.child_by_field_name("type_parameters")
+        .into_iter()
+        .flat_map(|params| params.named_children())
+        .filter(|param| param.kind() == "type_parameter")
+        .filter_map(|param| {
+            param
+                .named_children()
+                .find(|child| child.kind() == "type_identifier")
+        })
+        .map(|name| (name.text(), name.range()))
+        .collect()
+}
+
+/// The exceptions in the `throws` clause of a declaration.
+fn declared_exceptions(node: &CstNode) -> Vec<(String, TextRange)> {
+    node.children()
+        .filter(|child| child.kind() == "throws")
+        .flat_map(|throws| throws.named_children())
+        .map(|exception| (exception.text().to_string(), exception.range()))
+        .collect()
+}
+
+/// The exceptions created by `throw new` statements in the body of a
+/// declaration, outside lambdas, nested classes, and `try` statements that
+/// catch exceptions.
+fn thrown_exceptions(node: &CstNode) -> Vec<(String, TextRange)> {
+    fn collect(node: &CstNode, thrown: &mut Vec<(String, TextRange)>) {
+        for child in node.children() {
+            match child.kind() {
+                "lambda_expression" | "class_body" => {}
+                "try_statement" | "try_with_resources_statement"
+                    if child.children().any(|c| c.kind() == "catch_clause") => {}
+                "throw_statement" => {
+                    let created = child
+                        .named_children()
+                        .find(|c| c.kind() == "object_creation_expression");
+                    if let Some(created) = created
+                        && let Some(exception) = created.child_by_field_name("type")
+                    {
+                        let exception = match exception.kind() {
+                            "generic_type" => exception.children().next().unwrap_or(exception),
+                            _ => exception,
+                        };
+                        thrown.push((exception.text().to_string(), created.range()));
+                    }
+                }
+                _ => collect(&child, thrown),
+            }
+        }
+    }
+
+    let mut thrown = vec![];
+    if let Some(body) = node.child_by_field_name("body") {
+        collect(&body, &mut thrown);
+    }
+    thrown
+}
+
+/// Whether two class names are the same, comparing only the simple names
+/// when either is qualified.
+fn is_same_class(a: &str, b: &str) -> bool {
+    let simple = |name: &str| name.rsplit('.').next().unwrap_or(name).to_string();
+    a == b || (a.contains('.') || b.contains('.')) && simple(a) == simple(b)
+}
+
+/// Whether `{@inheritDoc}` can apply: to a method neither static nor
+/// private.
+fn is_inherit_doc_valid(node: &CstNode) -> bool {
+    node.kind() == "method_declaration"
+        && !node
+            .children()
+            .find(|child| child.kind() == "modifiers")
+            .is_some_and(|modifiers| has_modifier(&modifiers, "static"))
+        && scope_of(node) != Scope::Private
+}
+
+/// Whether the declaration is a method returning a value.
+fn is_non_void_method(node: &CstNode) -> bool {
+    node.kind() == "method_declaration"
+        && node
+            .child_by_field_name("type")
+            .is_some_and(|ty| ty.kind() != "void_type")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+    use lintal_source_file::{LineIndex, SourceCode};
+
+    fn check_source(source: &str, properties: &[(&str, &str)]) -> Vec<(usize, String)> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let properties: Properties = properties.iter().copied().collect();
+        let rule = JavadocMethod::from_config(&properties);
+        let line_index = LineIndex::from_source_text(source);
+        let source_code = SourceCode::new(source, &line_index);
+
+        let mut violations = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            for diagnostic in rule.check(&ctx, &node) {
+                let line = source_code.line_column(diagnostic.range.start()).line.get();
+                violations.push((line, diagnostic.kind.body));
+            }
+        }
+        violations.sort();
+        violations
+    }
+
+    fn violation(line: usize, message: &str) -> (usize, String) {
+        (line, message.to_string())
+    }
+
+    #[test]
+    fn test_params() {
+        let source = r#"
+class A {
+    /**
+     * Does things.
+     *
+     * @param a the first
+     * @param c not a parameter
+     * @param <T> the type
+     */
+    <T, U> void f(int a, int b, U... rest) {}
+}
+"#;
+        assert_eq!(
+            check_source(source, &[]),
+            [
+                violation(7, "Unused @param tag for 'c'."),
+                violation(10, "Expected @param tag for '<U>'."),
+                violation(10, "Expected @param tag for 'b'."),
+                violation(10, "Expected @param tag for 'rest'."),
+            ]
+        );
+        assert_eq!(
+            check_source(source, &[("allowMissingParamTags", "true")]),
+            [violation(7, "Unused @param tag for 'c'.")]
+        );
+    }
+
+    #[test]
+    fn test_return() {
+        let source = r#"
+class A {
+    /** No return tag. */
+    int f() { return 1; }
+
+    /**
+     * Two return tags.
+     *
+     * @return one
+     * @return two
+     */
+    int g() { return 1; }
+
+    /** @return nothing */
+    void h() {}
+}
+"#;
+        assert_eq!(
+            check_source(source, &[]),
+            [
+                violation(4, "Expected an @return tag."),
+                violation(10, "Duplicate @return tag."),
+                violation(14, "Unused Javadoc tag."),
+            ]
+        );
+        assert_eq!(
+            check_source(source, &[("allowMissingReturnTag", "true")]).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_throws() {
+        let source = r#"
+import java.io.IOException;
+
+class A {
+    /**
+     * Documented.
+     *
+     * @throws java.io.IOException when reading fails
+     * @throws IllegalStateException never declared, but fine
+     */
+    void f() throws IOException, InterruptedException {
+        throw new UnsupportedOperationException();
+    }
+}
+"#;
+        assert!(check_source(source, &[]).is_empty());
+        assert_eq!(
+            check_source(source, &[("validateThrows", "true")]),
+            [
+                violation(11, "Expected @throws tag for 'InterruptedException'."),
+                violation(
+                    12,
+                    "Expected @throws tag for 'UnsupportedOperationException'."
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inherit_doc_and_allowed_annotations() {
+        let source = r#"
+class A {
+    /** {@inheritDoc} */
+    public String toString() { return ""; }
+
+    /** {@inheritDoc} */
+    private static int f() { return 1; }
+
+    /** Overridden. */
+    @Override
+    public boolean equals(Object o) { return false; }
+
+    /**
+     * Inherited, with more.
+     *
+     * {@inheritDoc}
+     * @return nothing
+     */
+    public int g(int a) { return a; }
+}
+"#;
+        assert_eq!(
+            check_source(source, &[]),
+            [violation(7, "Invalid use of the {@inheritDoc} tag.")]
+        );
+        assert_eq!(
+            check_source(source, &[("allowedAnnotations", "")]),
+            [
+                violation(7, "Invalid use of the {@inheritDoc} tag."),
+                violation(10, "Expected an @return tag."),
+                violation(11, "Expected @param tag for 'o'."),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_access_modifiers() {
+        let source = r#"
+class A {
+    /** Undocumented parameter. */
+    private void f(int a) {}
+
+    /** Undocumented parameter. */
+    public void g(int a) {}
+}
+"#;
+        assert_eq!(check_source(source, &[]).len(), 2);
+        assert_eq!(
+            check_source(source, &[("accessModifiers", "public")]),
+            [violation(7, "Expected @param tag for 'a'.")]
+        );
+    }
+}
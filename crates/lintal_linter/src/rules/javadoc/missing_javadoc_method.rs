@@ -0,0 +1,299 @@
+//! MissingJavadocMethod rule implementation.
+//!
+//! Checks that methods and constructors have a Javadoc comment.
+//!
+//! Checkstyle equivalent: MissingJavadocMethodCheck
+
+use std::collections::HashSet;
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+use regex::Regex;
+
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+use super::common::{
+    METHOD_KINDS, Scope, declaration_token, has_annotation, javadoc_before, parse_list, scope_of,
+    surrounding_scope,
+};
+
+/// Violation: method without a Javadoc comment.
+#[derive(Debug, Clone)]
+pub struct MissingJavadocViolation;
+
+impl Violation for MissingJavadocViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        "Missing a Javadoc comment.".to_string()
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("javadoc", "javadoc.missing"))
+    }
+}
+
+/// Configuration for MissingJavadocMethod rule.
+#[derive(Debug, Clone)]
+pub struct MissingJavadocMethod {
+    /// Least visible scope to check (default: public).
+    pub scope: Scope,
+    /// Scope not to check, if any.
+    pub exclude_scope: Option<Scope>,
+    /// Methods with at most this many lines in their body need no Javadoc
+    /// (default: -1).
+    pub min_line_count: i64,
+    /// Annotations exempting a method, e.g. `Override`.
+    pub allowed_annotations: Vec<String>,
+    /// Names of methods that need no Javadoc.
+    pub ignore_method_names_regex: Option<Regex>,
+    /// Declarations to check: METHOD_DEF, CTOR_DEF, ANNOTATION_FIELD_DEF,
+    /// COMPACT_CTOR_DEF.
+    pub tokens: HashSet<String>,
+}
+
+const DEFAULT_TOKENS: &[&str] = &[
+    "METHOD_DEF",
+    "CTOR_DEF",
+    "ANNOTATION_FIELD_DEF",
+    "COMPACT_CTOR_DEF",
+];
+
+impl Default for MissingJavadocMethod {
+    fn default() -> Self {
+        Self {
+            scope: Scope::Public,
+            exclude_scope: None,
+            min_line_count: -1,
+            allowed_annotations: vec!["Override".to_string()],
+            ignore_method_names_regex: None,
+            tokens: DEFAULT_TOKENS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl FromConfig for MissingJavadocMethod {
+    const MODULE_NAME: &'static str = "MissingJavadocMethod";
+
+    fn from_config(properties: &Properties) -> Self {
+        let default = Self::default();
+
+        let scope = properties
+            .get("scope")
+            .and_then(|v| Scope::parse(v))
+            .unwrap_or(default.scope);
+
+        let exclude_scope = properties
+            .get("excludeScope")
+            .and_then(|v| Scope::parse(v))
+            .or(default.exclude_scope);
+
+        let min_line_count = properties
+            .get("minLineCount")
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(default.min_line_count);
+
+        let allowed_annotations = properties
+            .get("allowedAnnotations")
+            .map(|v| parse_list(v))
+            .unwrap_or(default.allowed_annotations);
+
+        let ignore_method_names_regex = properties
+            .get("ignoreMethodNamesRegex")
+            .and_then(|v| Regex::new(&format!("^(?:{v})$")).ok());
+
+        let tokens = properties
+            .get("tokens")
+            .map(|v| parse_list(v).into_iter().collect())
+            .unwrap_or(default.tokens);
+
+        Self {
+            scope,
+            exclude_scope,
+            min_line_count,
+            allowed_annotations,
+            ignore_method_names_regex,
+            tokens,
+        }
+    }
+}
+
+impl Rule for MissingJavadocMethod {
+    fn name(&self) -> &'static str {
+        "MissingJavadocMethod"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        METHOD_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        let Some(token) = declaration_token(node.kind()) else {
+            return vec![];
+        };
+        if !self.tokens.contains(token)
+            || !self.should_check(node)
+            || self.is_missing_javadoc_allowed(ctx, node)
+            || javadoc_before(ctx, node).is_some()
+        {
+            return vec![];
+        }
+
+        vec![Diagnostic::new(MissingJavadocViolation, node.range())]
+    }
+}
+
+impl MissingJavadocMethod {
+    /// Whether the declaration is in the scopes checked.
+    fn should_check(&self, node: &CstNode) -> bool {
+        let scope = scope_of(node);
+        let surrounding = surrounding_scope(node);
+
+        scope.is_in(self.scope)
+            && surrounding.is_none_or(|s| s.is_in(self.scope))
+            && self.exclude_scope.is_none_or(|exclude| {
+                !scope.is_in(exclude) || surrounding.is_some_and(|s| !s.is_in(exclude))
+            })
+    }
+
+    /// Whether the declaration may go without Javadoc: a method named as
+    /// `ignoreMethodNamesRegex` allows, or a method or constructor short
+    /// enough or carrying an allowed annotation.
+    fn is_missing_javadoc_allowed(&self, ctx: &CheckContext, node: &CstNode) -> bool {
+        let ignored_name = node.kind() == "method_declaration"
+            && self
+                .ignore_method_names_regex
+                .as_ref()
+                .is_some_and(|regex| {
+                    node.child_by_field_name("name")
+                        .is_some_and(|name| regex.is_match(name.text()))
+                });
+
+        ignored_name
+            || node.kind() != "annotation_type_element_declaration"
+                && (body_line_count(ctx, node) <= self.min_line_count
+                    || has_annotation(node, &self.allowed_annotations))
+    }
+}
+
+/// The lines between the braces of a body, as checkstyle counts them: one
+/// for an empty body or none.
+fn body_line_count(ctx: &CheckContext, node: &CstNode) -> i64 {
+    let Some(body) = node.child_by_field_name("body") else {
+        return 1;
+    };
+    let is_empty = body
+        .named_children()
+        .all(|child| matches!(child.kind(), "line_comment" | "block_comment"));
+    match (body.children().next(), body.children().last()) {
+        (Some(open), Some(close)) if !is_empty => {
+            let line = |node: CstNode| ctx.source_code().line_index(node.range().start()).get();
+            line(close) as i64 - line(open) as i64 - 1
+        }
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+    use lintal_source_file::{LineIndex, SourceCode};
+
+    fn check_source(source: &str, properties: &[(&str, &str)]) -> Vec<usize> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let properties: Properties = properties.iter().copied().collect();
+        let rule = MissingJavadocMethod::from_config(&properties);
+        let line_index = LineIndex::from_source_text(source);
+        let source_code = SourceCode::new(source, &line_index);
+
+        let mut lines = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            for diagnostic in rule.check(&ctx, &node) {
+                lines.push(source_code.line_column(diagnostic.range.start()).line.get());
+            }
+        }
+        lines
+    }
+
+    #[test]
+    fn test_missing_javadoc() {
+        let source = r#"
+public class A {
+    /** Documented. */
+    public void a() {}
+
+    public void b() {}
+
+    public A() {}
+
+    void c() {}
+
+    @Override
+    public String toString() {
+        return "";
+    }
+}
+"#;
+        assert_eq!(check_source(source, &[]), [6, 8]);
+        assert_eq!(check_source(source, &[("scope", "package")]), [6, 8, 10]);
+        assert_eq!(
+            check_source(source, &[("allowedAnnotations", "Deprecated")]),
+            [6, 8, 12]
+        );
+        assert_eq!(check_source(source, &[("tokens", "CTOR_DEF")]), [8]);
+    }
+
+    #[test]
+    fn test_surrounding_scope() {
+        let source = r#"
+class A {
+    public void a() {}
+}
+public class B {
+    private class C {
+        public void c() {}
+    }
+    protected void d() {}
+}
+"#;
+        assert!(check_source(source, &[]).is_empty());
+        assert_eq!(check_source(source, &[("scope", "protected")]), [9]);
+        assert_eq!(check_source(source, &[("scope", "private")]), [3, 7, 9]);
+        assert_eq!(
+            check_source(
+                source,
+                &[("scope", "private"), ("excludeScope", "protected")]
+            ),
+            [3, 7]
+        );
+    }
+
+    #[test]
+    fn test_min_line_count_and_ignored_names() {
+        let source = r#"
+public class A {
+    public int getA() {
+        return 1;
+    }
+
+    public void longer() {
+        a();
+        b();
+    }
+
+    public void empty() {
+    }
+}
+"#;
+        assert_eq!(check_source(source, &[]), [3, 7, 12]);
+        assert_eq!(check_source(source, &[("minLineCount", "1")]), [7]);
+        assert_eq!(
+            check_source(source, &[("ignoreMethodNamesRegex", "get.*|empty")]),
+            [7]
+        );
+    }
+}
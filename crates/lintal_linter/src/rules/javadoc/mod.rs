@@ -0,0 +1,8 @@
+//! Javadoc rules (JavadocMethod, MissingJavadocMethod, etc.)
+
+pub mod common;
+mod javadoc_method;
+mod missing_javadoc_method;
+
+pub use javadoc_method::JavadocMethod;
+pub use missing_javadoc_method::MissingJavadocMethod;
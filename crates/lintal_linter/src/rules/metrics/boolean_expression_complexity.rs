@@ -0,0 +1,308 @@
+//! BooleanExpressionComplexity rule implementation.
+//!
+//! Restricts the number of boolean operators (`&&`, `||`, `&`, `|` and `^`)
+//! in an expression.
+//!
+//! Checkstyle equivalent: BooleanExpressionComplexityCheck
+
+use std::collections::HashSet;
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+
+use crate::rules::modifier::common::has_modifier;
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+use super::common::{binary_operator, expression_parent, is_expression_root, walk_expression};
+
+/// Violation: too many boolean operators in an expression.
+#[derive(Debug, Clone)]
+pub struct BooleanExpressionComplexityViolation {
+    pub count: usize,
+    pub max: usize,
+}
+
+impl Violation for BooleanExpressionComplexityViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!(
+            "Boolean expression complexity is {} (max allowed is {}).",
+            self.count, self.max
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("metrics", "booleanExpressionComplexity"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.count.to_string(), self.max.to_string()]
+    }
+}
+
+/// Configuration for BooleanExpressionComplexity rule.
+#[derive(Debug, Clone)]
+pub struct BooleanExpressionComplexity {
+    /// Maximum allowed boolean operators in one expression (default: 3).
+    pub max: usize,
+    /// Operators to count: LAND, BAND, LOR, BOR, BXOR.
+    pub tokens: HashSet<String>,
+}
+
+const RELEVANT_KINDS: &[&str] = &[
+    "method_declaration",
+    "constructor_declaration",
+    "compact_constructor_declaration",
+];
+
+const DEFAULT_TOKENS: &[&str] = &["LAND", "BAND", "LOR", "BOR", "BXOR"];
+
+impl Default for BooleanExpressionComplexity {
+    fn default() -> Self {
+        Self {
+            max: 3,
+            tokens: DEFAULT_TOKENS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl FromConfig for BooleanExpressionComplexity {
+    const MODULE_NAME: &'static str = "BooleanExpressionComplexity";
+
+    fn from_config(properties: &Properties) -> Self {
+        let default = Self::default();
+
+        let max = properties
+            .get("max")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(default.max);
+
+        let tokens = properties
+            .get("tokens")
+            .map(|v| {
+                v.split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or(default.tokens);
+
+        Self { max, tokens }
+    }
+}
+
+impl Rule for BooleanExpressionComplexity {
+    fn name(&self) -> &'static str {
+        "BooleanExpressionComplexity"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        // Like checkstyle, equals() is left alone, and so is everything
+        // outside methods and constructors
+        if is_equals_method(node) {
+            return vec![];
+        }
+
+        let mut roots = vec![];
+        collect_roots(node, &mut roots);
+
+        roots
+            .iter()
+            .filter_map(|root| {
+                let count = self.count_operators(root);
+                (count > self.max).then(|| {
+                    Diagnostic::new(
+                        BooleanExpressionComplexityViolation {
+                            count,
+                            max: self.max,
+                        },
+                        expression_parent(root).range(),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+impl BooleanExpressionComplexity {
+    /// The boolean operators of the expression at `root`, without those of
+    /// the expressions nested in it.
+    fn count_operators(&self, root: &CstNode) -> usize {
+        let mut count = 0;
+        walk_expression(root, &mut |node| {
+            let token = match binary_operator(node) {
+                Some("&&") => "LAND",
+                Some("||") => "LOR",
+                // A bitwise operator making up a whole argument is taken to
+                // be arithmetic
+                Some("&") if !is_whole_argument(node) => "BAND",
+                Some("|") if !is_whole_argument(node) => "BOR",
+                Some("^") if !is_whole_argument(node) => "BXOR",
+                _ => return,
+            };
+            if self.tokens.contains(token) {
+                count += 1;
+            }
+        });
+        count
+    }
+}
+
+/// Add the expression roots under `node` to `roots`, leaving out those of
+/// the methods declared in it, which are checked on their own.
+fn collect_roots<'a>(node: &CstNode<'a>, roots: &mut Vec<CstNode<'a>>) {
+    for child in node.children() {
+        if RELEVANT_KINDS.contains(&child.kind()) {
+            continue;
+        }
+        if is_expression_root(&child) {
+            roots.push(child);
+        }
+        collect_roots(&child, roots);
+    }
+}
+
+/// Whether the operator is a method argument of its own, ignoring
+/// parentheses.
+fn is_whole_argument(node: &CstNode) -> bool {
+    let mut parent = node.parent();
+    while let Some(p) = parent {
+        if p.kind() != "parenthesized_expression" {
+            return p.kind() == "argument_list";
+        }
+        parent = p.parent();
+    }
+    false
+}
+
+/// Whether `node` declares an instance `equals` method with one parameter.
+fn is_equals_method(node: &CstNode) -> bool {
+    if node.kind() != "method_declaration" {
+        return false;
+    }
+    let static_or_abstract = node
+        .children()
+        .find(|c| c.kind() == "modifiers")
+        .is_some_and(|m| has_modifier(&m, "static") || has_modifier(&m, "abstract"));
+    !static_or_abstract
+        && node
+            .child_by_field_name("name")
+            .is_some_and(|name| name.text() == "equals")
+        && node
+            .child_by_field_name("parameters")
+            .is_some_and(|params| {
+                params
+                    .named_children()
+                    .filter(|p| matches!(p.kind(), "formal_parameter" | "spread_parameter"))
+                    .count()
+                    == 1
+            })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+    use lintal_source_file::{LineIndex, SourceCode};
+
+    fn check_source(source: &str, properties: &[(&str, &str)]) -> Vec<(usize, String)> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let properties: Properties = properties.iter().copied().collect();
+        let rule = BooleanExpressionComplexity::from_config(&properties);
+        let line_index = LineIndex::from_source_text(source);
+        let source_code = SourceCode::new(source, &line_index);
+
+        let mut violations = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            for diagnostic in rule.check(&ctx, &node) {
+                let line = source_code.line_column(diagnostic.range.start()).line.get();
+                violations.push((line, diagnostic.kind.body));
+            }
+        }
+        violations
+    }
+
+    #[test]
+    fn test_complex_condition() {
+        let source = r#"
+class A {
+    void f(boolean a, boolean b, boolean c, boolean d, boolean e) {
+        if (a && b || c && d) {}
+        if (a && b || c && d || e) {}
+        boolean x = a & b | c ^ d & e;
+    }
+}
+"#;
+        assert_eq!(
+            check_source(source, &[]),
+            [
+                (
+                    5,
+                    "Boolean expression complexity is 4 (max allowed is 3).".to_string()
+                ),
+                (
+                    6,
+                    "Boolean expression complexity is 4 (max allowed is 3).".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_expressions_count_apart() {
+        let source = r#"
+class A {
+    void f(boolean a, boolean b, boolean c) {
+        if (a && b && check(a && b && c)) {}
+        if (a && b && check(a & b) && c) {}
+        if (a && b && check((a & b) | c)) {}
+    }
+}
+"#;
+        // The whole bitwise argument `a & b` is not counted, but `a & b`
+        // within `(a & b) | c` is
+        assert!(check_source(source, &[]).is_empty());
+        assert_eq!(check_source(source, &[("max", "1")]).len(), 4);
+    }
+
+    #[test]
+    fn test_equals_and_fields_ignored() {
+        let source = r#"
+class A {
+    boolean flag = a && b && c && d && e;
+    public boolean equals(Object o) {
+        return a && b && c && d && e;
+    }
+    boolean other(Object o) {
+        return a && b && c && d && e;
+    }
+}
+"#;
+        assert_eq!(check_source(source, &[]).len(), 1);
+        assert_eq!(check_source(source, &[]).first().map(|v| v.0), Some(8));
+    }
+
+    #[test]
+    fn test_tokens() {
+        let source = r#"
+class A {
+    void f(boolean a, boolean b) {
+        boolean x = a && b || a && b | a;
+    }
+}
+"#;
+        assert!(check_source(source, &[("max", "1"), ("tokens", "BAND, BOR")]).is_empty());
+        assert_eq!(
+            check_source(source, &[("max", "1"), ("tokens", "LAND, LOR")]).len(),
+            1
+        );
+    }
+}
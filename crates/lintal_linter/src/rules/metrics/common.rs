@@ -0,0 +1,202 @@
+//! Shared utilities for metrics rules: walking expressions the way
+//! checkstyle's tree divides them.
+//!
+//! Checkstyle wraps each whole expression, such as a condition, an
+//! initializer or a method argument, in an `EXPR` node. Rules that count per
+//! expression count between those boundaries, so a method argument is an
+//! expression of its own and not part of the call around it.
+
+use lintal_java_cst::CstNode;
+
+/// The operator of a binary expression, e.g. `&&`.
+pub fn binary_operator<'a>(node: &CstNode<'a>) -> Option<&'a str> {
+    if node.kind() != "binary_expression" {
+        return None;
+    }
+    node.child_by_field_name("operator").map(|op| op.text())
+}
+
+/// How many paths a node adds to an expression: one for `&&` and `||`, two
+/// for `?:`.
+pub fn operator_weight(node: &CstNode) -> usize {
+    match node.kind() {
+        "ternary_expression" => 2,
+        _ => usize::from(matches!(binary_operator(node), Some("&&" | "||"))),
+    }
+}
+
+/// The summed [`operator_weight`] of the descendants of `node`, as
+/// checkstyle's NPathComplexity counts the conditional operators under a
+/// token.
+pub fn conditional_operators(node: &CstNode) -> usize {
+    node.children()
+        .map(|child| operator_weight(&child) + conditional_operators(&child))
+        .sum()
+}
+
+/// The last token under `node`.
+pub fn last_leaf<'a>(node: &CstNode<'a>) -> CstNode<'a> {
+    let mut last = *node;
+    while let Some(child) = last.children().last() {
+        last = child;
+    }
+    last
+}
+
+/// Whether checkstyle wraps `node` in an `EXPR` node of its own.
+pub fn is_expression_root(node: &CstNode) -> bool {
+    if !node.inner().is_named() || matches!(node.kind(), "line_comment" | "block_comment") {
+        return false;
+    }
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    let is_field = |name: &str| {
+        parent
+            .child_by_field_name(name)
+            .is_some_and(|field| field.inner() == node.inner())
+    };
+
+    match parent.kind() {
+        "expression_statement"
+        | "return_statement"
+        | "throw_statement"
+        | "yield_statement"
+        | "assert_statement"
+        | "argument_list"
+        | "dimensions_expr"
+        | "guard"
+        | "element_value_array_initializer" => true,
+        "annotation_argument_list" => node.kind() != "element_value_pair",
+        "parenthesized_expression" => parent.parent().is_some_and(|statement| {
+            matches!(
+                statement.kind(),
+                "if_statement"
+                    | "while_statement"
+                    | "do_statement"
+                    | "switch_expression"
+                    | "synchronized_statement"
+            )
+        }),
+        "array_initializer" => node.kind() != "array_initializer",
+        "variable_declarator" => is_field("value") && node.kind() != "array_initializer",
+        "resource" | "element_value_pair" | "annotation_type_element_declaration" => {
+            is_field("value")
+        }
+        "array_access" => is_field("index"),
+        "for_statement" => {
+            node.kind() != "local_variable_declaration"
+                && (is_field("init") || is_field("condition") || is_field("update"))
+        }
+        "enhanced_for_statement" => is_field("value"),
+        "switch_label" => node.kind() != "guard" && !node.kind().ends_with("pattern"),
+        _ => false,
+    }
+}
+
+/// The node checkstyle reports a problem with the expression at `root` on:
+/// the parent of its `EXPR`, such as the `if` of a condition, the `=` of an
+/// initializer, or the `{` of the block holding an expression statement.
+pub fn expression_parent<'a>(root: &CstNode<'a>) -> CstNode<'a> {
+    let Some(parent) = root.parent() else {
+        return *root;
+    };
+    let token = |kind: &str| parent.children().find(|child| child.kind() == kind);
+
+    match parent.kind() {
+        "parenthesized_expression" => parent.parent().unwrap_or(parent),
+        "variable_declarator" | "resource" | "element_value_pair" => token("=").unwrap_or(parent),
+        "array_access" => token("[").unwrap_or(parent),
+        "for_statement" => *root,
+        "enhanced_for_statement" => parent.child_by_field_name("type").unwrap_or(*root),
+        "argument_list" => parent.named_children().next().unwrap_or(parent),
+        "expression_statement" => match parent.parent() {
+            // The statements of a case group start after its labels
+            Some(group) if group.kind() == "switch_block_statement_group" => group
+                .named_children()
+                .find(|child| child.kind() != "switch_label")
+                .unwrap_or(parent),
+            Some(owner) => owner,
+            None => parent,
+        },
+        _ => parent,
+    }
+}
+
+/// Visit the nodes of the expression at `root`, in source order, leaving
+/// out the expressions nested in it that checkstyle wraps on their own, such
+/// as method arguments.
+pub fn walk_expression<'a>(root: &CstNode<'a>, visit: &mut impl FnMut(&CstNode<'a>)) {
+    visit(root);
+    for child in root.children() {
+        if !is_expression_root(&child) {
+            walk_expression(&child, visit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    /// The text of each expression root in `source` with its operators.
+    fn expressions(source: &str) -> Vec<(String, Vec<String>)> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        TreeWalker::new(result.tree.root_node(), source)
+            .filter(is_expression_root)
+            .map(|root| {
+                let mut operators = vec![];
+                walk_expression(&root, &mut |node| {
+                    if let Some(op) = binary_operator(node) {
+                        operators.push(op.to_string());
+                    }
+                });
+                (root.text().to_string(), operators)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_expression_roots() {
+        let source = r#"
+class A {
+    boolean f(boolean a, boolean b, int[] c) {
+        boolean x = a && b;
+        if (a || check(b && a, c[1 + 2])) {
+            return x;
+        }
+        return a ? b : x;
+    }
+}
+"#;
+        let ops = |ops: &[&str]| ops.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            expressions(source),
+            [
+                ("a && b".to_string(), ops(&["&&"])),
+                ("a || check(b && a, c[1 + 2])".to_string(), ops(&["||"])),
+                ("b && a".to_string(), ops(&["&&"])),
+                ("c[1 + 2]".to_string(), ops(&[])),
+                ("1 + 2".to_string(), ops(&["+"])),
+                ("x".to_string(), ops(&[])),
+                ("a ? b : x".to_string(), ops(&[])),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_conditional_operators() {
+        let source = "class A { void f() { if (a && (b || c ? d : e)) {} } }";
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let condition = TreeWalker::new(result.tree.root_node(), source)
+            .find(|node| node.kind() == "if_statement")
+            .and_then(|node| node.child_by_field_name("condition"))
+            .unwrap();
+        // &&, || and ?:
+        assert_eq!(conditional_operators(&condition), 4);
+    }
+}
@@ -0,0 +1,8 @@
+//! Metrics rules (BooleanExpressionComplexity, NPathComplexity, etc.)
+
+mod boolean_expression_complexity;
+pub mod common;
+mod npath_complexity;
+
+pub use boolean_expression_complexity::BooleanExpressionComplexity;
+pub use npath_complexity::NPathComplexity;
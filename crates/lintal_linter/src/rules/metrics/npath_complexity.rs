@@ -0,0 +1,483 @@
+//! NPathComplexity rule implementation.
+//!
+//! Checks the number of possible execution paths through a method: branches
+//! add their paths together and consecutive statements multiply them.
+//!
+//! Checkstyle equivalent: NPathComplexityCheck
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+use super::common::{conditional_operators, last_leaf, operator_weight};
+
+/// Violation: method has too many execution paths.
+#[derive(Debug, Clone)]
+pub struct NPathComplexityViolation {
+    pub npath: u128,
+    pub max: u128,
+}
+
+impl Violation for NPathComplexityViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!(
+            "NPath Complexity is {} (max allowed is {}).",
+            self.npath, self.max
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("metrics", "npathComplexity"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.npath.to_string(), self.max.to_string()]
+    }
+}
+
+/// Configuration for NPathComplexity rule.
+#[derive(Debug, Clone)]
+pub struct NPathComplexity {
+    /// Maximum allowed number of execution paths (default: 200).
+    pub max: u128,
+}
+
+const RELEVANT_KINDS: &[&str] = &["program"];
+
+impl Default for NPathComplexity {
+    fn default() -> Self {
+        Self { max: 200 }
+    }
+}
+
+impl FromConfig for NPathComplexity {
+    const MODULE_NAME: &'static str = "NPathComplexity";
+
+    fn from_config(properties: &Properties) -> Self {
+        let max = properties
+            .get("max")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(200);
+
+        Self { max }
+    }
+}
+
+impl Rule for NPathComplexity {
+    fn name(&self) -> &'static str {
+        "NPathComplexity"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        // Only check at program level (once per file), as the count of a
+        // method depends on what came before it in the file
+        if node.kind() != "program" {
+            return vec![];
+        }
+
+        let mut counter = NPathCounter::new(self.max);
+        counter.walk(node);
+        counter.diagnostics
+    }
+}
+
+/// What leaving a node does to the count, decided when entering it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Exit {
+    None,
+    Method,
+    Conditional,
+    Unitary,
+    Branch,
+    Try,
+    Catch,
+}
+
+/// A port of checkstyle's count, which visits and leaves the tokens of a
+/// file in order, keeping the paths of each enclosing range on a stack.
+///
+/// `current` is the number of paths through the range so far, 0 while it
+/// has none of its own. Conditions, `return` values and `?:` expressions
+/// add their `&&`, `||` and `?:` operators to the paths of the construct
+/// they belong to; once one is counted, operators up to the end of it are
+/// not counted again.
+struct NPathCounter {
+    max: u128,
+    ranges: Vec<u128>,
+    expressions: Vec<u128>,
+    after: Vec<bool>,
+    /// Start of the last token of the expression counted last.
+    processed_end: Option<usize>,
+    current: u128,
+    branch_visited: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl NPathCounter {
+    fn new(max: u128) -> Self {
+        Self {
+            max,
+            ranges: vec![],
+            expressions: vec![],
+            after: vec![],
+            processed_end: None,
+            current: 0,
+            branch_visited: false,
+            diagnostics: vec![],
+        }
+    }
+
+    fn walk(&mut self, node: &CstNode) {
+        let exit = self.visit(node);
+
+        // The `else` of an `if` spans the keyword and the statement after it
+        let mut in_else = false;
+        for child in node.children() {
+            if node.kind() == "if_statement" && child.kind() == "else" {
+                self.visit_else();
+                in_else = true;
+                continue;
+            }
+            self.walk(&child);
+        }
+        if in_else {
+            self.leave_branch();
+        }
+
+        match exit {
+            Exit::None => {}
+            Exit::Method => self.leave_method(node),
+            Exit::Conditional => self.leave_conditional(),
+            Exit::Unitary => self.leave_unitary(),
+            Exit::Branch => self.leave_branch(),
+            Exit::Try => self.leave_try(),
+            Exit::Catch => self.leave_catch(),
+        }
+    }
+
+    fn visit(&mut self, node: &CstNode) -> Exit {
+        match node.kind() {
+            "method_declaration"
+            | "constructor_declaration"
+            | "compact_constructor_declaration"
+            | "static_initializer" => {
+                self.push(0);
+                Exit::Method
+            }
+            "block" if is_instance_initializer(node) => {
+                self.push(0);
+                Exit::Method
+            }
+            "if_statement" | "while_statement" | "do_statement" | "switch_expression" => {
+                match node.child_by_field_name("condition") {
+                    Some(condition) => {
+                        self.visit_conditional(1 + conditional_operators(&condition), &condition)
+                    }
+                    None => self.push(1),
+                }
+                Exit::Conditional
+            }
+            "for_statement" | "enhanced_for_statement" => {
+                // Everything between the parentheses counts
+                let operators: usize = node
+                    .children()
+                    .skip_while(|child| child.kind() != "(")
+                    .take_while(|child| child.kind() != ")")
+                    .map(|child| operator_weight(&child) + conditional_operators(&child))
+                    .sum();
+                self.push(1 + operators as u128);
+                self.processed_end = node
+                    .children()
+                    .find(|child| child.kind() == ")")
+                    .map(|paren| paren.inner().start_byte());
+                Exit::Conditional
+            }
+            "guard" => {
+                self.visit_conditional(1 + conditional_operators(node), node);
+                Exit::Conditional
+            }
+            "ternary_expression" => {
+                let question = node
+                    .children()
+                    .find(|child| child.kind() == "?")
+                    .unwrap_or(*node);
+                self.visit_unitary(2, node, &question);
+                Exit::Unitary
+            }
+            "return_statement" => {
+                self.visit_unitary(0, node, node);
+                Exit::Unitary
+            }
+            "switch_block_statement_group" => {
+                let cases = node
+                    .children()
+                    .filter(|child| {
+                        child.kind() == "switch_label" && child.text().starts_with("case")
+                    })
+                    .count();
+                self.branch_visited = true;
+                self.push(cases as u128);
+                Exit::Branch
+            }
+            "switch_rule" => {
+                let constants = node
+                    .children()
+                    .find(|child| child.kind() == "switch_label")
+                    .map_or(0, |label| {
+                        label
+                            .named_children()
+                            .filter(|c| {
+                                !matches!(c.kind(), "guard" | "line_comment" | "block_comment")
+                            })
+                            .count()
+                    });
+                self.branch_visited = true;
+                self.push(constants as u128);
+                Exit::Branch
+            }
+            // The keyword of a `default` label, method or annotation element
+            "default" => {
+                self.push(1);
+                Exit::Branch
+            }
+            "try_statement" | "try_with_resources_statement" => {
+                self.push(1);
+                Exit::Try
+            }
+            "catch_clause" => {
+                self.push(1);
+                Exit::Catch
+            }
+            _ => Exit::None,
+        }
+    }
+
+    /// Enter a construct whose condition ends with `condition`'s last token.
+    fn visit_conditional(&mut self, expression: usize, condition: &CstNode) {
+        self.processed_end = Some(last_leaf(condition).inner().start_byte());
+        self.push(expression as u128);
+    }
+
+    /// Enter a `return` or `?:` at `token`, unless it is within an
+    /// expression already counted.
+    fn visit_unitary(&mut self, basic: usize, node: &CstNode, token: &CstNode) {
+        let position = token.inner().start_byte();
+        let is_after = self.processed_end.is_some_and(|end| position <= end);
+        self.after.push(is_after);
+        if !is_after {
+            self.processed_end = Some(last_leaf(node).inner().start_byte());
+            self.push((basic + conditional_operators(node)) as u128);
+        }
+    }
+
+    fn visit_else(&mut self) {
+        self.branch_visited = true;
+        if self.current == 0 {
+            self.current = 1;
+        }
+        self.push(0);
+    }
+
+    fn leave_method(&mut self, node: &CstNode) {
+        if self.current > self.max {
+            self.diagnostics.push(Diagnostic::new(
+                NPathComplexityViolation {
+                    npath: self.current,
+                    max: self.max,
+                },
+                node.range(),
+            ));
+        }
+        let (range, _) = self.pop();
+        self.current = range;
+    }
+
+    fn leave_conditional(&mut self) {
+        let (range, expression) = self.pop();
+        if !self.branch_visited && self.current == 0 {
+            self.current = 1;
+        }
+        self.branch_visited = false;
+        self.current = self
+            .current
+            .saturating_add(expression)
+            .saturating_mul(range.max(1));
+    }
+
+    fn leave_unitary(&mut self) {
+        if self.after.pop() == Some(false) {
+            let (range, expression) = self.pop();
+            self.current = self
+                .current
+                .saturating_add(expression.max(1))
+                .saturating_mul(range.max(1));
+        }
+    }
+
+    fn leave_branch(&mut self) {
+        let (range, expression) = self.pop();
+        if self.branch_visited && self.current == 0 {
+            self.current = 1;
+        }
+        self.current = self
+            .current
+            .saturating_add(range)
+            .saturating_add(expression)
+            .saturating_sub(1);
+    }
+
+    fn leave_try(&mut self) {
+        let (range, _) = self.pop();
+        self.current = self
+            .current
+            .saturating_add(1)
+            .saturating_mul(range.saturating_add(1));
+    }
+
+    fn leave_catch(&mut self) {
+        let (range, _) = self.pop();
+        self.current = self.current.saturating_add(range).saturating_add(1);
+    }
+
+    /// Start a new range, saving the paths of the enclosing one.
+    fn push(&mut self, expression: u128) {
+        self.ranges.push(self.current);
+        self.expressions.push(expression);
+        self.current = 0;
+    }
+
+    /// The paths of the enclosing range and the expression of the construct
+    /// that started this one.
+    fn pop(&mut self) -> (u128, u128) {
+        (
+            self.ranges.pop().unwrap_or(0),
+            self.expressions.pop().unwrap_or(0),
+        )
+    }
+}
+
+fn is_instance_initializer(block: &CstNode) -> bool {
+    block
+        .parent()
+        .is_some_and(|parent| matches!(parent.kind(), "class_body" | "enum_body_declarations"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_parser::JavaParser;
+
+    /// The NPath of each method in `source`, with a max of 0 so that every
+    /// method with a path is reported.
+    fn npaths(source: &str) -> Vec<u128> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let properties: Properties = [("max", "0")].into_iter().collect();
+        let rule = NPathComplexity::from_config(&properties);
+        let root = CstNode::new(result.tree.root_node(), source);
+        rule.check(&ctx, &root)
+            .iter()
+            .map(|d| {
+                d.kind.body["NPath Complexity is ".len()..]
+                    .split(' ')
+                    .next()
+                    .unwrap()
+                    .parse()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    fn npath_of(body: &str) -> u128 {
+        let source = format!("class A {{\n    void f() {{\n{body}\n    }}\n}}\n");
+        let found = npaths(&source);
+        found.first().copied().unwrap_or(0)
+    }
+
+    #[test]
+    fn test_if() {
+        assert_eq!(npath_of("if (a) { x(); }"), 2);
+        assert_eq!(npath_of("if (a && b) { x(); }"), 3);
+        assert_eq!(npath_of("if (a) { x(); } else { y(); }"), 2);
+        assert_eq!(npath_of("if (a) {} else if (b) {} else {}"), 3);
+    }
+
+    #[test]
+    fn test_sequence_multiplies() {
+        assert_eq!(npath_of("if (a) {} if (b) {} if (c) {}"), 8);
+        assert_eq!(npath_of("if (a) { if (b) {} }"), 3);
+    }
+
+    #[test]
+    fn test_loops() {
+        assert_eq!(npath_of("while (a) {}"), 2);
+        assert_eq!(npath_of("do {} while (a || b);"), 3);
+        assert_eq!(npath_of("for (int i = 0; i < n && ok; i++) {}"), 3);
+        assert_eq!(npath_of("for (String s : list) {}"), 2);
+    }
+
+    #[test]
+    fn test_switch() {
+        let body = "switch (x) { case 1: a(); break; case 2: b(); break; default: c(); }";
+        assert_eq!(npath_of(body), 3);
+        // Each constant of a rule is a path
+        let body = "switch (x) { case 1, 2 -> a(); default -> b(); }";
+        assert_eq!(npath_of(body), 3);
+    }
+
+    #[test]
+    fn test_ternary_and_return() {
+        assert_eq!(npath_of("int x = a ? 1 : 2;"), 2);
+        assert_eq!(npath_of("int x = a ? 1 : 2; int y = b ? 1 : 2;"), 4);
+        // The ternary is counted with the return it is in
+        assert_eq!(npath_of("return a ? b : c;"), 2);
+        assert_eq!(npath_of("if (a ? b : c) {}"), 4);
+    }
+
+    #[test]
+    fn test_methods_counted_apart() {
+        let source = r#"
+class A {
+    void f() {
+        if (a) {}
+    }
+    void g() {
+        if (a) {} if (b) {}
+    }
+    void h() {
+        x();
+    }
+}
+"#;
+        assert_eq!(npaths(source), [2, 4]);
+    }
+
+    #[test]
+    fn test_max() {
+        let source = "class A { void f() { if (a) {} if (b) {} } }";
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let root = CstNode::new(result.tree.root_node(), source);
+
+        let properties: Properties = [("max", "4")].into_iter().collect();
+        let rule = NPathComplexity::from_config(&properties);
+        assert!(rule.check(&ctx, &root).is_empty());
+        let properties: Properties = [("max", "3")].into_iter().collect();
+        let rule = NPathComplexity::from_config(&properties);
+        let diagnostics = rule.check(&ctx, &root);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind.body,
+            "NPath Complexity is 4 (max allowed is 3)."
+        );
+    }
+}
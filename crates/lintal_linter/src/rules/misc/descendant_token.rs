@@ -5,7 +5,7 @@
 //!
 //! Checkstyle equivalent: DescendantTokenCheck
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::{TextRange, TextSize};
 use tree_sitter::Node;
@@ -15,28 +15,100 @@ use crate::{CheckContext, FromConfig, Properties, Rule};
 /// Violation: descendant token count exceeds maximum.
 #[derive(Debug, Clone)]
 pub struct DescendantTokenMaxViolation {
-    pub message: String,
+    pub count: usize,
+    pub parent: String,
+    pub descendant: String,
+    pub max: usize,
+    /// The configured `maximumMessage`, reported instead of the default.
+    pub custom_message: Option<String>,
 }
 
 impl Violation for DescendantTokenMaxViolation {
     const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
 
     fn message(&self) -> String {
-        self.message.clone()
+        if let Some(ref msg) = self.custom_message {
+            // Handle message template with {2} placeholder for token name
+            msg.replace("{2}", &self.descendant)
+        } else {
+            format!(
+                "Count of {} for '{}' descendant '{}' exceeds maximum count {}.",
+                self.count, self.parent, self.descendant, self.max
+            )
+        }
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        self.custom_message
+            .is_none()
+            .then_some(MessageKey::new("", "descendant.token.max"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![
+            self.count.to_string(),
+            self.parent.clone(),
+            self.descendant.clone(),
+            self.max.to_string(),
+        ]
     }
 }
 
 /// Violation: descendant token count below minimum.
 #[derive(Debug, Clone)]
 pub struct DescendantTokenMinViolation {
-    pub message: String,
+    pub count: usize,
+    pub parent: String,
+    /// The descendant counted, or `None` if `sumTokenCounts` totals them.
+    pub descendant: Option<String>,
+    pub min: usize,
+    /// The configured `minimumMessage`, reported instead of the default.
+    pub custom_message: Option<String>,
 }
 
 impl Violation for DescendantTokenMinViolation {
     const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
 
     fn message(&self) -> String {
-        self.message.clone()
+        if let Some(ref msg) = self.custom_message {
+            return msg.clone();
+        }
+        match &self.descendant {
+            Some(descendant) => format!(
+                "Count of {} for '{}' descendant '{}' is less than minimum count {}.",
+                self.count, self.parent, descendant, self.min
+            ),
+            None => format!(
+                "Total count of {} is less than minimum count {} under '{}'.",
+                self.count, self.min, self.parent
+            ),
+        }
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        if self.custom_message.is_some() {
+            return None;
+        }
+        Some(match self.descendant {
+            Some(_) => MessageKey::new("", "descendant.token.min"),
+            None => MessageKey::new("", "descendant.token.sum.min"),
+        })
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        match &self.descendant {
+            Some(descendant) => vec![
+                self.count.to_string(),
+                self.parent.clone(),
+                descendant.clone(),
+                self.min.to_string(),
+            ],
+            None => vec![
+                self.count.to_string(),
+                self.min.to_string(),
+                self.parent.clone(),
+            ],
+        }
     }
 }
 
@@ -225,22 +297,20 @@ impl DescendantToken {
 
             // Check maximum
             if total_count > self.maximum_number {
-                let message = self.format_maximum_message(
-                    parent_token,
-                    self.limited_tokens.first().map_or("", |t| &t.name),
-                    total_count,
-                );
                 diagnostics.push(Diagnostic::new(
-                    DescendantTokenMaxViolation { message },
+                    self.maximum_violation(
+                        parent_token,
+                        self.limited_tokens.first().map_or("", |t| &t.name),
+                        total_count,
+                    ),
                     range,
                 ));
             }
 
             // Check minimum
             if total_count < self.minimum_number {
-                let message = self.format_minimum_message_sum(parent_token, total_count);
                 diagnostics.push(Diagnostic::new(
-                    DescendantTokenMinViolation { message },
+                    self.minimum_violation(parent_token, None, total_count),
                     range,
                 ));
             }
@@ -251,19 +321,16 @@ impl DescendantToken {
 
                 // Check maximum
                 if count > self.maximum_number {
-                    let message =
-                        self.format_maximum_message(parent_token, &limited_token.name, count);
                     diagnostics.push(Diagnostic::new(
-                        DescendantTokenMaxViolation { message },
+                        self.maximum_violation(parent_token, &limited_token.name, count),
                         range,
                     ));
                 }
 
                 // Check minimum
                 if count < self.minimum_number {
-                    let message = self.format_minimum_message(parent_token, limited_token, count);
                     diagnostics.push(Diagnostic::new(
-                        DescendantTokenMinViolation { message },
+                        self.minimum_violation(parent_token, Some(limited_token), count),
                         range,
                     ));
                 }
@@ -311,47 +378,33 @@ impl DescendantToken {
         count
     }
 
-    fn format_maximum_message(
+    fn maximum_violation(
         &self,
         parent_token: &CheckstyleToken,
         limited_name: &str,
         count: usize,
-    ) -> String {
-        if let Some(ref msg) = self.maximum_message {
-            // Handle message template with {2} placeholder for token name
-            msg.replace("{2}", limited_name)
-        } else {
-            format!(
-                "Count of {} for '{}' descendant '{}' exceeds maximum count {}.",
-                count, parent_token.name, limited_name, self.maximum_number
-            )
+    ) -> DescendantTokenMaxViolation {
+        DescendantTokenMaxViolation {
+            count,
+            parent: parent_token.name.clone(),
+            descendant: limited_name.to_string(),
+            max: self.maximum_number,
+            custom_message: self.maximum_message.clone(),
         }
     }
 
-    fn format_minimum_message(
+    fn minimum_violation(
         &self,
         parent_token: &CheckstyleToken,
-        limited_token: &CheckstyleToken,
+        limited_token: Option<&CheckstyleToken>,
         count: usize,
-    ) -> String {
-        if let Some(ref msg) = self.minimum_message {
-            msg.clone()
-        } else {
-            format!(
-                "Count of {} for '{}' descendant '{}' is less than minimum count {}.",
-                count, parent_token.name, limited_token.name, self.minimum_number
-            )
-        }
-    }
-
-    fn format_minimum_message_sum(&self, parent_token: &CheckstyleToken, count: usize) -> String {
-        if let Some(ref msg) = self.minimum_message {
-            msg.clone()
-        } else {
-            format!(
-                "Total count of {} is less than minimum count {} under '{}'.",
-                count, self.minimum_number, parent_token.name
-            )
+    ) -> DescendantTokenMinViolation {
+        DescendantTokenMinViolation {
+            count,
+            parent: parent_token.name.clone(),
+            descendant: limited_token.map(|t| t.name.clone()),
+            min: self.minimum_number,
+            custom_message: self.minimum_message.clone(),
         }
     }
 }
@@ -1,5 +1,9 @@
-//! Miscellaneous rules (DescendantToken)
+//! Miscellaneous rules (DescendantToken, NewlineAtEndOfFile, OuterTypeFilename)
 
 mod descendant_token;
+mod newline_at_end_of_file;
+mod outer_type_filename;
 
 pub use descendant_token::DescendantToken;
+pub use newline_at_end_of_file::NewlineAtEndOfFile;
+pub use outer_type_filename::OuterTypeFilename;
@@ -0,0 +1,260 @@
+//! NewlineAtEndOfFile rule implementation.
+//!
+//! Checks that files end with a line separator.
+//!
+//! Checkstyle equivalent: NewlineAtEndOfFileCheck
+
+use std::path::Path;
+
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
+use lintal_source_file::{LineIndex, find_newline};
+use lintal_text_size::{TextRange, TextSize};
+
+use crate::{FileRule, FromConfig, Properties, Rule};
+
+/// Violation: file does not end with a line separator.
+#[derive(Debug, Clone)]
+pub struct NoNewlineAtEndOfFileViolation;
+
+impl Violation for NoNewlineAtEndOfFileViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Always;
+
+    fn message(&self) -> String {
+        "File does not end with a newline.".to_string()
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("misc", "noNewlineAtEOF"))
+    }
+}
+
+/// Violation: file ends with CRLF where LF is expected.
+#[derive(Debug, Clone)]
+pub struct WrongLineEndingViolation;
+
+impl Violation for WrongLineEndingViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        "Expected line ending for file is LF(\\n), but CRLF(\\r\\n) is detected.".to_string()
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("misc", "wrong.line.end"))
+    }
+}
+
+/// The line separator files must end with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineSeparator {
+    Lf,
+    Crlf,
+    Cr,
+    /// Any of LF, CR and CRLF.
+    #[default]
+    LfCrCrlf,
+    /// That of the platform lintal runs on.
+    System,
+}
+
+impl LineSeparator {
+    fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "lf" => Some(Self::Lf),
+            "crlf" => Some(Self::Crlf),
+            "cr" => Some(Self::Cr),
+            "lf_cr_crlf" => Some(Self::LfCrCrlf),
+            "system" => Some(Self::System),
+            _ => None,
+        }
+    }
+
+    /// The separator the platform stands for, if this is `System`.
+    fn resolve(self) -> Self {
+        match self {
+            Self::System if cfg!(windows) => Self::Crlf,
+            Self::System => Self::Lf,
+            other => other,
+        }
+    }
+
+    /// Whether `source` ends with this separator.
+    fn ends(self, source: &str) -> bool {
+        match self.resolve() {
+            Self::Lf => source.ends_with('\n'),
+            Self::Crlf => source.ends_with("\r\n"),
+            Self::Cr => source.ends_with('\r'),
+            _ => source.ends_with(['\n', '\r']),
+        }
+    }
+
+    /// The separator `source` should end with; any separator takes the one
+    /// the file already uses.
+    fn terminator(self, source: &str) -> &'static str {
+        match self.resolve() {
+            Self::Crlf => "\r\n",
+            Self::Cr => "\r",
+            Self::Lf => "\n",
+            _ => find_newline(source).map_or("\n", |(_, ending)| ending.as_str()),
+        }
+    }
+}
+
+/// Configuration for NewlineAtEndOfFile rule.
+#[derive(Debug, Clone, Default)]
+pub struct NewlineAtEndOfFile {
+    /// The line separator expected at the end of files (default: any).
+    pub line_separator: LineSeparator,
+}
+
+impl FromConfig for NewlineAtEndOfFile {
+    const MODULE_NAME: &'static str = "NewlineAtEndOfFile";
+
+    fn from_config(properties: &Properties) -> Self {
+        let line_separator = properties
+            .get("lineSeparator")
+            .and_then(|v| LineSeparator::from_str(v))
+            .unwrap_or_default();
+
+        Self { line_separator }
+    }
+}
+
+impl Rule for NewlineAtEndOfFile {
+    fn name(&self) -> &'static str {
+        "NewlineAtEndOfFile"
+    }
+
+    fn as_file_rule(&self) -> Option<&dyn FileRule> {
+        Some(self)
+    }
+}
+
+impl FileRule for NewlineAtEndOfFile {
+    fn check_file(
+        &self,
+        _path: Option<&Path>,
+        source: &str,
+        _line_index: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        if source.is_empty() {
+            return vec![];
+        }
+
+        // Reported on the first line of the file, as in checkstyle
+        let file_start = TextRange::empty(TextSize::new(0));
+        if self.line_separator.resolve() == LineSeparator::Lf && source.ends_with("\r\n") {
+            return vec![Diagnostic::new(WrongLineEndingViolation, file_start)];
+        }
+        if self.line_separator.ends(source) {
+            return vec![];
+        }
+
+        // A file ending in another separator has it replaced, so the fix
+        // doesn't leave a blank line or mixed line endings behind
+        let trailing = if source.ends_with("\r\n") {
+            "\r\n"
+        } else if source.ends_with(['\n', '\r']) {
+            &source[source.len() - 1..]
+        } else {
+            ""
+        };
+        let fix = Fix::safe_edit(Edit::range_replacement(
+            self.line_separator.terminator(source).to_string(),
+            TextRange::new(
+                TextSize::of(source) - TextSize::of(trailing),
+                TextSize::of(source),
+            ),
+        ));
+        vec![Diagnostic::new(NoNewlineAtEndOfFileViolation, file_start).with_fix(fix)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_source(source: &str, line_separator: &'static str) -> Vec<Diagnostic> {
+        let mut props = Properties::new();
+        props.insert("lineSeparator", line_separator);
+        let line_index = LineIndex::from_source_text(source);
+        NewlineAtEndOfFile::from_config(&props).check_file(None, source, &line_index)
+    }
+
+    fn appended(diagnostic: &Diagnostic) -> Option<&str> {
+        diagnostic.fix.as_ref()?.edits()[0].content()
+    }
+
+    fn fixed(source: &str, line_separator: &'static str) -> String {
+        let diagnostics = check_source(source, line_separator);
+        let fixes = diagnostics.iter().filter_map(|d| d.fix.as_ref());
+        crate::fix::apply_fixes(source, fixes).code
+    }
+
+    #[test]
+    fn test_any_separator() {
+        assert!(check_source("class Foo {}\n", "lf_cr_crlf").is_empty());
+        assert!(check_source("class Foo {}\r\n", "lf_cr_crlf").is_empty());
+        assert!(check_source("class Foo {}\r", "lf_cr_crlf").is_empty());
+        assert!(check_source("", "lf_cr_crlf").is_empty());
+
+        let diagnostics = check_source("class Foo {}", "lf_cr_crlf");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind.body,
+            "File does not end with a newline."
+        );
+        assert_eq!(appended(&diagnostics[0]), Some("\n"));
+
+        // The fix uses the separator of the file's other lines
+        let diagnostics = check_source("class Foo {\r\n}", "lf_cr_crlf");
+        assert_eq!(appended(&diagnostics[0]), Some("\r\n"));
+    }
+
+    #[test]
+    fn test_crlf() {
+        assert!(check_source("class Foo {}\r\n", "crlf").is_empty());
+        let diagnostics = check_source("class Foo {}", "crlf");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(appended(&diagnostics[0]), Some("\r\n"));
+    }
+
+    #[test]
+    fn test_fix_replaces_other_separator() {
+        assert_eq!(fixed("class Foo {}\n", "crlf"), "class Foo {}\r\n");
+        assert_eq!(fixed("class Foo {}\r", "crlf"), "class Foo {}\r\n");
+        assert_eq!(fixed("class Foo {}\n", "cr"), "class Foo {}\r");
+        assert_eq!(fixed("class Foo {}\r\n", "cr"), "class Foo {}\r");
+        assert_eq!(fixed("class Foo {}\r", "lf"), "class Foo {}\n");
+
+        // Fixed files are clean on the next pass
+        for (source, separator) in [
+            ("class Foo {}\n", "crlf"),
+            ("class Foo {}\n", "cr"),
+            ("class Foo {}\r", "lf"),
+        ] {
+            assert!(check_source(&fixed(source, separator), separator).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_lf_with_crlf_ending() {
+        let diagnostics = check_source("class Foo {}\r\n", "lf");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind.body,
+            "Expected line ending for file is LF(\\n), but CRLF(\\r\\n) is detected."
+        );
+        assert!(diagnostics[0].fix.is_none());
+    }
+
+    #[test]
+    fn test_from_config() {
+        let rule = NewlineAtEndOfFile::from_config(&Properties::new());
+        assert_eq!(rule.line_separator, LineSeparator::LfCrCrlf);
+        let mut props = Properties::new();
+        props.insert("lineSeparator", "CR");
+        let rule = NewlineAtEndOfFile::from_config(&props);
+        assert_eq!(rule.line_separator, LineSeparator::Cr);
+    }
+}
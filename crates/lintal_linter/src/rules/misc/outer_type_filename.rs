@@ -0,0 +1,149 @@
+//! OuterTypeFilename rule implementation.
+//!
+//! Checks that the outer type name and the file name match.
+//!
+//! Checkstyle equivalent: OuterTypeFilenameCheck
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+
+use crate::rules::modifier::common::has_modifier;
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: the outer type is not named after the file.
+#[derive(Debug, Clone)]
+pub struct OuterTypeFilenameViolation;
+
+impl Violation for OuterTypeFilenameViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        "The name of the outer type and the file do not match.".to_string()
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("misc", "type.file.mismatch"))
+    }
+}
+
+/// Configuration for OuterTypeFilename rule.
+#[derive(Debug, Clone, Default)]
+pub struct OuterTypeFilename;
+
+const RELEVANT_KINDS: &[&str] = &["program"];
+
+const TYPE_DECLARATIONS: &[&str] = &[
+    "class_declaration",
+    "interface_declaration",
+    "enum_declaration",
+    "record_declaration",
+    "annotation_type_declaration",
+];
+
+impl FromConfig for OuterTypeFilename {
+    const MODULE_NAME: &'static str = "OuterTypeFilename";
+
+    fn from_config(_properties: &Properties) -> Self {
+        Self
+    }
+}
+
+impl Rule for OuterTypeFilename {
+    fn name(&self) -> &'static str {
+        "OuterTypeFilename"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        // Sources that were not read from a file have no name to match
+        let Some(file_name) = ctx
+            .path()
+            .and_then(|path| path.file_stem())
+            .and_then(|stem| stem.to_str())
+        else {
+            return vec![];
+        };
+
+        let mut types = node
+            .children()
+            .filter(|child| TYPE_DECLARATIONS.contains(&child.kind()));
+        let Some(first) = types.next() else {
+            return vec![];
+        };
+        if first
+            .child_by_field_name("name")
+            .is_none_or(|name| name.text() == file_name)
+        {
+            return vec![];
+        }
+
+        // A later public type must be named after the file, which the
+        // compiler already enforces
+        let has_public = types.any(|declaration| {
+            declaration
+                .children()
+                .find(|c| c.kind() == "modifiers")
+                .is_some_and(|modifiers| has_modifier(&modifiers, "public"))
+        });
+        if has_public {
+            return vec![];
+        }
+
+        vec![Diagnostic::new(OuterTypeFilenameViolation, first.range())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, path: Option<&Path>) -> Vec<Diagnostic> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let ctx = match path {
+            Some(path) => ctx.with_path(path),
+            None => ctx,
+        };
+
+        let mut diagnostics = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            diagnostics.extend(OuterTypeFilename.check(&ctx, &node));
+        }
+        diagnostics
+    }
+
+    #[test]
+    fn test_matching_name_no_violation() {
+        let source = "public class Foo {}\nclass Bar {}\n";
+        assert!(check_source(source, Some(Path::new("src/Foo.java"))).is_empty());
+    }
+
+    #[test]
+    fn test_mismatched_name_violation() {
+        let source = "class Foo {}\n";
+        assert_eq!(
+            check_source(source, Some(Path::new("src/Bar.java"))).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_later_public_type_no_violation() {
+        let source = "class Foo {}\npublic class Bar {}\n";
+        assert!(check_source(source, Some(Path::new("Bar.java"))).is_empty());
+    }
+
+    #[test]
+    fn test_without_path_or_types_no_violation() {
+        assert!(check_source("class Foo {}\n", None).is_empty());
+        assert!(check_source("package a.b;\n", Some(Path::new("package-info.java"))).is_empty());
+    }
+}
@@ -1,9 +1,14 @@
 //! Lint rules organized by category.
 
+pub mod annotation;
 pub mod blocks;
 pub mod coding;
 pub mod design;
+pub mod duplicates;
+pub mod headers;
 pub mod imports;
+pub mod javadoc;
+pub mod metrics;
 pub mod misc;
 pub mod modifier;
 pub mod naming;
@@ -13,26 +18,42 @@ pub mod style;
 pub mod whitespace;
 
 // Re-export all rules
+pub use annotation::{AnnotationLocation, AnnotationUseStyle, MissingOverride};
 pub use blocks::{
     AvoidNestedBlocks, EmptyBlock, EmptyCatchBlock, LeftCurly, NeedBraces, RightCurly,
 };
 pub use coding::{
-    CovariantEquals, DeclarationOrder, DefaultComesLast, EmptyStatement, EqualsHashCode,
-    FallThrough, HiddenField, IllegalType, InnerAssignment, MissingSwitchDefault,
-    MultipleVariableDeclarations, NestedTryDepth, OneStatementPerLine, PackageDeclaration,
+    CovariantEquals, DeclarationOrder, DefaultComesLast, EmptyStatement, EqualsAvoidNull,
+    EqualsHashCode, FallThrough, HiddenField, IllegalType, InnerAssignment, MagicNumber,
+    MissingSwitchDefault, MultipleVariableDeclarations, NestedForDepth, NestedIfDepth,
+    NestedTryDepth, OneStatementPerLine, PackageDeclaration, RequireThis, ReturnCount,
     SimplifyBooleanExpression, SimplifyBooleanReturn, StringLiteralEquality,
 };
-pub use design::{HideUtilityClassConstructor, MutableException};
-pub use imports::{RedundantImport, UnusedImports};
-pub use misc::DescendantToken;
+pub use design::{
+    DesignForExtension, HideUtilityClassConstructor, MutableException, OneTopLevelClass,
+    VisibilityModifier,
+};
+pub use duplicates::StrictDuplicateCode;
+pub use headers::{Header, RegexpHeader};
+pub use imports::{
+    AvoidStarImport, AvoidStaticImport, CustomImportOrder, ImportOrder, RedundantImport,
+    UnusedImports,
+};
+pub use javadoc::{JavadocMethod, MissingJavadocMethod};
+pub use metrics::{BooleanExpressionComplexity, NPathComplexity};
+pub use misc::{DescendantToken, NewlineAtEndOfFile, OuterTypeFilename};
 pub use modifier::{
     FinalClass, FinalLocalVariable, FinalParameters, ModifierOrder, RedundantModifier,
 };
 pub use naming::{
-    ConstantName, LocalFinalVariableName, LocalVariableName, MemberName, MethodName, PackageName,
-    ParameterName, StaticVariableName, TypeName,
+    ClassTypeParameterName, ConstantName, InterfaceTypeParameterName, LocalFinalVariableName,
+    LocalVariableName, MemberName, MethodName, MethodTypeParameterName, PackageName, ParameterName,
+    StaticVariableName, TypeName,
+};
+pub use regexp::{RegexpMultiline, RegexpSingleline, RegexpSinglelineJava};
+pub use sizes::{
+    AnonInnerLength, ExecutableStatementCount, FileLength, LambdaBodyLength, LineLength,
+    MethodCount, MethodLength, ParameterNumber,
 };
-pub use regexp::RegexpSinglelineJava;
-pub use sizes::{LineLength, MethodLength};
 pub use style::{ArrayTypeStyle, UpperEll};
 pub use whitespace::*;
@@ -4,7 +4,7 @@
 //!
 //! Checkstyle equivalent: FinalClassCheck
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 
 use crate::{CheckContext, FromConfig, Properties, Rule};
@@ -23,6 +23,14 @@ impl Violation for ClassShouldBeFinalViolation {
     fn message(&self) -> String {
         format!("Class {} should be declared as final.", self.class_name)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("design", "final.class"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.class_name.clone()]
+    }
 }
 
 /// Configuration for FinalClass rule.
@@ -3,13 +3,15 @@
 //! This is a complex stateful rule that tracks variable declarations and assignments.
 
 use crate::{CheckContext, FromConfig, Rule};
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::TextRange;
 use std::collections::{HashMap, HashSet};
 
 /// Checks that local variables that are never reassigned are declared final.
 pub struct FinalLocalVariable {
+    /// Which tokens to check (VARIABLE_DEF, PARAMETER_DEF)
+    tokens: HashSet<FinalLocalVariableToken>,
     validate_enhanced_for_loop_variable: bool,
     validate_unnamed_variables: bool,
 }
@@ -22,6 +24,13 @@ const RELEVANT_KINDS: &[&str] = &[
     "lambda_expression",
 ];
 
+/// Token types that can be checked by FinalLocalVariable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FinalLocalVariableToken {
+    VariableDef,
+    ParameterDef,
+}
+
 /// Violation for a variable that should be final.
 #[derive(Debug, Clone)]
 pub struct VariableShouldBeFinal {
@@ -34,12 +43,26 @@ impl Violation for VariableShouldBeFinal {
     fn message(&self) -> String {
         format!("Variable '{}' should be declared final.", self.var_name)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("coding", "final.variable"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.var_name.clone()]
+    }
 }
 
 impl FromConfig for FinalLocalVariable {
     const MODULE_NAME: &'static str = "FinalLocalVariable";
 
     fn from_config(properties: &HashMap<&str, &str>) -> Self {
+        let tokens = if let Some(tokens_str) = properties.get("tokens") {
+            parse_tokens(tokens_str)
+        } else {
+            HashSet::from([FinalLocalVariableToken::VariableDef])
+        };
+
         let validate_enhanced_for_loop_variable = properties
             .get("validateEnhancedForLoopVariable")
             .map(|v| *v == "true")
@@ -51,12 +74,48 @@ impl FromConfig for FinalLocalVariable {
             .unwrap_or(false);
 
         Self {
+            tokens,
             validate_enhanced_for_loop_variable,
             validate_unnamed_variables,
         }
     }
 }
 
+/// Parse tokens from config string.
+fn parse_tokens(tokens_str: &str) -> HashSet<FinalLocalVariableToken> {
+    tokens_str
+        .split(',')
+        .filter_map(|token| match token.trim() {
+            "VARIABLE_DEF" => Some(FinalLocalVariableToken::VariableDef),
+            "PARAMETER_DEF" => Some(FinalLocalVariableToken::ParameterDef),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Position at which "final " is inserted for a declaration: before its type,
+/// so that it lands after any annotations (`@Ann final int x`).
+fn final_insert_position(node: &CstNode) -> lintal_text_size::TextSize {
+    node.child_by_field_name("type")
+        .or_else(|| {
+            node.children()
+                .find(|child| child.kind() != "modifiers" && child.kind() != "final")
+        })
+        .map(|type_node| type_node.range().start())
+        .unwrap_or_else(|| node.range().start())
+}
+
+/// Check whether a declaration node carries the `final` modifier.
+fn has_final(node: &CstNode) -> bool {
+    node.children().any(|child| {
+        if child.kind() == "modifiers" {
+            super::common::has_modifier(&child, "final")
+        } else {
+            super::common::resolve_modifier_kind(&child) == "final"
+        }
+    })
+}
+
 /// Candidate variable that might need to be final.
 #[derive(Debug, Clone)]
 struct VariableCandidate {
@@ -266,6 +325,9 @@ impl<'a> FinalLocalVariableVisitor<'a> {
             "lambda_expression" => {
                 self.process_lambda_expression(node);
             }
+            "catch_clause" => {
+                self.process_catch_clause(node);
+            }
             _ => {
                 self.visit_children(node);
             }
@@ -409,43 +471,16 @@ impl<'a> FinalLocalVariableVisitor<'a> {
             return;
         }
 
-        // Check if already has final modifier
-        // Note: modifiers might not be a field, check children
-        for child in node.children() {
-            if child.kind() == "modifiers" {
-                if super::common::has_modifier(&child, "final") {
-                    return; // Already final, skip
-                }
-            } else if super::common::resolve_modifier_kind(&child) == "final" {
-                // Sometimes final appears directly as a child (or wrapped in modifier node)
-                return;
-            }
+        if !self
+            .rule
+            .tokens
+            .contains(&FinalLocalVariableToken::VariableDef)
+            || has_final(node)
+        {
+            return;
         }
 
-        // Calculate insert position for "final "
-        // If there's a modifiers node, insert after it; otherwise before the type
-        let insert_position = node
-            .children()
-            .find(|child| child.kind() == "modifiers")
-            .map(|modifiers| modifiers.range().end())
-            .or_else(|| {
-                // Find the type node (various type kinds)
-                node.children()
-                    .find(|child| {
-                        matches!(
-                            child.kind(),
-                            "type_identifier"
-                                | "generic_type"
-                                | "array_type"
-                                | "integral_type"
-                                | "floating_point_type"
-                                | "boolean_type"
-                                | "void_type"
-                        )
-                    })
-                    .map(|type_node| type_node.range().start())
-            })
-            .unwrap_or_else(|| node.range().start());
+        let insert_position = final_insert_position(node);
 
         // Find all variable declarators
         for child in node.children() {
@@ -476,6 +511,71 @@ impl<'a> FinalLocalVariableVisitor<'a> {
         }
     }
 
+    /// Add a method, constructor or catch parameter to the current scope.
+    /// Parameters are initialized by the caller, so a single assignment in the
+    /// body is enough to rule them out.
+    fn add_parameter(&mut self, param: &CstNode) {
+        if param.kind() == "receiver_parameter" || has_final(param) {
+            return;
+        }
+
+        // Varargs parameters keep their name inside a variable_declarator
+        let name_node = param.child_by_field_name("name").or_else(|| {
+            param
+                .children()
+                .find(|child| child.kind() == "variable_declarator")
+                .and_then(|declarator| declarator.child_by_field_name("name"))
+        });
+        let Some(name_node) = name_node else {
+            return;
+        };
+
+        let var_name = &self.ctx.source()[name_node.range()];
+        if !self.rule.validate_unnamed_variables && var_name == "_" {
+            return;
+        }
+
+        let insert_position = final_insert_position(param);
+        if let Some(scope) = self.current_scope() {
+            scope.add_variable(
+                var_name.to_string(),
+                name_node.range(),
+                true,
+                insert_position,
+                param.range(),
+            );
+        }
+    }
+
+    /// Process a catch clause - the catch parameter lives in its own scope.
+    /// Multi-catch parameters are implicitly final and never reported.
+    fn process_catch_clause(&mut self, node: &CstNode) {
+        if !self
+            .rule
+            .tokens
+            .contains(&FinalLocalVariableToken::ParameterDef)
+        {
+            self.visit_children(node);
+            return;
+        }
+
+        self.push_scope();
+        if let Some(param) = node
+            .children()
+            .find(|child| child.kind() == "catch_formal_parameter")
+        {
+            let is_multi_catch = param
+                .children()
+                .find(|child| child.kind() == "catch_type")
+                .is_some_and(|catch_type| catch_type.children().any(|t| t.kind() == "|"));
+            if !is_multi_catch {
+                self.add_parameter(&param);
+            }
+        }
+        self.visit_children(node);
+        self.pop_scope();
+    }
+
     /// Process an assignment expression.
     fn process_assignment(&mut self, node: &CstNode) {
         if let Some(left) = node.child_by_field_name("left")
@@ -630,66 +730,25 @@ impl<'a> FinalLocalVariableVisitor<'a> {
         };
 
         // Handle the loop variable declaration if validateEnhancedForLoopVariable is enabled
-        if self.rule.validate_enhanced_for_loop_variable {
-            // Find the loop variable declaration
-            // enhanced_for_statement has: modifiers? type name ':' value body
-            // Check if the loop variable has 'final' modifier
-            let mut has_final = false;
-
-            // Check for modifiers as direct children of enhanced_for_statement
-            for child in node.children() {
-                if child.kind() == "modifiers" {
-                    has_final = super::common::has_modifier(&child, "final");
-                    break;
-                } else if super::common::resolve_modifier_kind(&child) == "final" {
-                    has_final = true;
-                    break;
-                }
-            }
-
-            // If no final modifier, check if the loop variable is reassigned in the body
-            if !has_final && let Some(name_node) = node.child_by_field_name("name") {
-                let var_name = &self.ctx.source()[name_node.range()];
+        if self.rule.validate_enhanced_for_loop_variable
+            && self
+                .rule
+                .tokens
+                .contains(&FinalLocalVariableToken::VariableDef)
+            && !has_final(node)
+            && let Some(name_node) = node.child_by_field_name("name")
+        {
+            let var_name = &self.ctx.source()[name_node.range()];
 
-                // Skip unnamed variables if configured
-                if !self.rule.validate_unnamed_variables && var_name == "_" {
-                    // Don't check this variable
-                } else {
-                    // Check if the variable is assigned in the loop body
-                    let mut assigned_in_body = false;
-                    if let Some(body) = node.child_by_field_name("body") {
-                        assigned_in_body = self.contains_assignment_to(&body, var_name);
-                    }
+            // Skip unnamed variables unless configured, and variables
+            // reassigned in the loop body
+            let skip_unnamed = !self.rule.validate_unnamed_variables && var_name == "_";
+            let assigned_in_body = node
+                .child_by_field_name("body")
+                .is_some_and(|body| self.contains_assignment_to(&body, var_name));
 
-                    // If not assigned in body, it should be final
-                    if !assigned_in_body {
-                        // Calculate insert position for "final "
-                        // If there's a modifiers node, insert after it; otherwise before the type
-                        let insert_position = node
-                            .children()
-                            .find(|child| child.kind() == "modifiers")
-                            .map(|modifiers| modifiers.range().end())
-                            .or_else(|| {
-                                // Find the type node
-                                node.children()
-                                    .find(|child| {
-                                        matches!(
-                                            child.kind(),
-                                            "type_identifier"
-                                                | "generic_type"
-                                                | "array_type"
-                                                | "integral_type"
-                                                | "floating_point_type"
-                                                | "boolean_type"
-                                        )
-                                    })
-                                    .map(|type_node| type_node.range().start())
-                            })
-                            .unwrap_or_else(|| node.range().start());
-
-                        self.report_violation(name_node.range(), var_name, insert_position);
-                    }
-                }
+            if !skip_unnamed && !assigned_in_body {
+                self.report_violation(name_node.range(), var_name, final_insert_position(node));
             }
         }
 
@@ -1073,6 +1132,16 @@ impl Rule for FinalLocalVariable {
                 if let Some(body) = node.child_by_field_name("body") {
                     let mut visitor = FinalLocalVariableVisitor::new(self, ctx);
                     visitor.push_scope();
+                    if self.tokens.contains(&FinalLocalVariableToken::ParameterDef)
+                        && !node
+                            .parent()
+                            .is_some_and(|parent| parent.kind() == "interface_body")
+                        && let Some(params) = node.child_by_field_name("parameters")
+                    {
+                        for param in params.named_children() {
+                            visitor.add_parameter(&param);
+                        }
+                    }
                     visitor.visit(&body);
                     visitor.pop_scope();
                     return visitor.diagnostics;
@@ -1123,11 +1192,38 @@ impl Rule for FinalLocalVariable {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, properties: HashMap<&str, &str>) -> Vec<Diagnostic> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let rule = FinalLocalVariable::from_config(&properties);
+
+        let mut diagnostics = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            diagnostics.extend(rule.check(&ctx, &node));
+        }
+        diagnostics
+    }
+
+    fn fixed_source(source: &str, properties: HashMap<&str, &str>) -> String {
+        let fixes: Vec<_> = check_source(source, properties)
+            .into_iter()
+            .filter_map(|d| d.fix)
+            .collect();
+        crate::fix::apply_fixes(source, &fixes).code
+    }
 
     #[test]
     fn test_from_config_defaults() {
         let properties = HashMap::new();
         let rule = FinalLocalVariable::from_config(&properties);
+        assert_eq!(
+            rule.tokens,
+            HashSet::from([FinalLocalVariableToken::VariableDef])
+        );
         assert!(!rule.validate_enhanced_for_loop_variable);
         assert!(!rule.validate_unnamed_variables);
     }
@@ -1141,4 +1237,78 @@ mod tests {
         assert!(rule.validate_enhanced_for_loop_variable);
         assert!(rule.validate_unnamed_variables);
     }
+
+    #[test]
+    fn test_parameters_not_checked_by_default() {
+        let source = r#"
+class Test {
+    void m(int a) {
+        int b = a;
+        System.out.println(b);
+    }
+}
+"#;
+        let diagnostics = check_source(source, HashMap::new());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind.body,
+            "Variable 'b' should be declared final."
+        );
+    }
+
+    #[test]
+    fn test_parameter_def() {
+        let source = r#"
+class Test {
+    Test(String a, int b) {
+        b = 1;
+    }
+    void m(final int c, int... d) {
+        try {
+        } catch (RuntimeException e) {
+        } catch (IllegalStateException | IllegalArgumentException f) {
+        }
+    }
+    abstract void n(int g);
+}
+interface I {
+    default void m(int h) {}
+}
+"#;
+        let properties = HashMap::from([("tokens", "PARAMETER_DEF")]);
+        let mut names: Vec<_> = check_source(source, properties)
+            .iter()
+            .map(|d| d.kind.body.clone())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "Variable 'a' should be declared final.",
+                "Variable 'd' should be declared final.",
+                "Variable 'e' should be declared final.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fix_inserts_final_after_annotations() {
+        let source = r#"
+class Test {
+    void m(@Deprecated int a) {
+        @SuppressWarnings("x") int b = a;
+        for (@Deprecated String s : list) {
+        }
+    }
+}
+"#;
+        let properties = HashMap::from([
+            ("tokens", "VARIABLE_DEF, PARAMETER_DEF"),
+            ("validateEnhancedForLoopVariable", "true"),
+        ]);
+        let fixed = fixed_source(source, properties);
+        assert!(fixed.contains("void m(@Deprecated final int a)"));
+        assert!(fixed.contains("@SuppressWarnings(\"x\") final int b = a;"));
+        assert!(fixed.contains("for (@Deprecated final String s : list)"));
+    }
 }
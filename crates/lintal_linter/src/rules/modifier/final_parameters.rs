@@ -3,7 +3,7 @@
 //! Checks that parameters for methods, constructors, catch and for-each blocks are final.
 //! This is a port of the checkstyle FinalParametersCheck for 100% compatibility.
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use std::collections::HashSet;
 
@@ -118,6 +118,14 @@ impl Violation for ParameterShouldBeFinal {
     fn message(&self) -> String {
         format!("Parameter {} should be final.", self.param_name)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("", "final.parameter"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.param_name.clone()]
+    }
 }
 
 impl Rule for FinalParameters {
@@ -3,7 +3,7 @@
 //! Checks that the order of modifiers conforms to the JLS suggestions.
 //! This is a port of the checkstyle ModifierOrderCheck for 100% compatibility.
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 
 use crate::{CheckContext, FromConfig, Properties, Rule};
@@ -44,6 +44,14 @@ impl Violation for ModifierOutOfOrder {
             self.modifier
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("modifier", "mod.order"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.modifier.clone()]
+    }
 }
 
 /// Violation for annotation must come before other modifiers.
@@ -62,6 +70,14 @@ impl Violation for AnnotationMustPrecedeModifiers {
             self.annotation
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("modifier", "annotation.order"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.annotation.clone()]
+    }
 }
 
 impl Rule for ModifierOrder {
@@ -3,10 +3,13 @@
 //! Checks for redundant modifiers in various contexts.
 //! This is a port of the checkstyle RedundantModifierCheck for 100% compatibility.
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use std::collections::HashSet;
+
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::TextRange;
 
+use crate::rules::javadoc::common::parse_list;
 use crate::{CheckContext, FromConfig, Properties, Rule};
 
 /// Configuration for RedundantModifier rule.
@@ -14,8 +17,29 @@ use crate::{CheckContext, FromConfig, Properties, Rule};
 pub struct RedundantModifier {
     /// JDK version for version-specific checks (e.g., strictfp in JDK 17+)
     jdk_version: u32,
+    /// Where to check modifiers: METHOD_DEF, VARIABLE_DEF,
+    /// ANNOTATION_FIELD_DEF, INTERFACE_DEF, CTOR_DEF, CLASS_DEF, ENUM_DEF,
+    /// RESOURCE, ANNOTATION_DEF, RECORD_DEF, PATTERN_VARIABLE_DEF,
+    /// LITERAL_CATCH, LAMBDA.
+    tokens: HashSet<String>,
 }
 
+const DEFAULT_TOKENS: &[&str] = &[
+    "METHOD_DEF",
+    "VARIABLE_DEF",
+    "ANNOTATION_FIELD_DEF",
+    "INTERFACE_DEF",
+    "CTOR_DEF",
+    "CLASS_DEF",
+    "ENUM_DEF",
+    "RESOURCE",
+    "ANNOTATION_DEF",
+    "RECORD_DEF",
+    "PATTERN_VARIABLE_DEF",
+    "LITERAL_CATCH",
+    "LAMBDA",
+];
+
 const RELEVANT_KINDS: &[&str] = &[
     "interface_declaration",
     "annotation_type_declaration",
@@ -37,7 +61,10 @@ const RELEVANT_KINDS: &[&str] = &[
 
 impl Default for RedundantModifier {
     fn default() -> Self {
-        Self { jdk_version: 22 }
+        Self {
+            jdk_version: 22,
+            tokens: DEFAULT_TOKENS.iter().map(|s| s.to_string()).collect(),
+        }
     }
 }
 
@@ -49,7 +76,14 @@ impl FromConfig for RedundantModifier {
             .get("jdkVersion")
             .and_then(|v| parse_jdk_version(v))
             .unwrap_or(22);
-        Self { jdk_version }
+        let tokens = properties
+            .get("tokens")
+            .map(|v| parse_list(v).into_iter().collect())
+            .unwrap_or_else(|| Self::default().tokens);
+        Self {
+            jdk_version,
+            tokens,
+        }
     }
 }
 
@@ -75,6 +109,14 @@ impl Violation for RedundantModifierViolation {
     fn message(&self) -> String {
         format!("Redundant '{}' modifier.", self.modifier)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("modifier", "redundantModifier"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.modifier.clone()]
+    }
 }
 
 impl Rule for RedundantModifier {
@@ -87,6 +129,13 @@ impl Rule for RedundantModifier {
     }
 
     fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        if !self
+            .token(node)
+            .is_some_and(|token| self.tokens.contains(token))
+        {
+            return vec![];
+        }
+
         // Check for redundant strictfp modifier (JDK 17+)
         let mut diagnostics = if self.jdk_version >= 17 {
             self.check_strictfp_modifier(ctx, node)
@@ -121,6 +170,30 @@ impl Rule for RedundantModifier {
 }
 
 impl RedundantModifier {
+    /// The checkstyle token under which the modifiers of `node` are checked.
+    fn token(&self, node: &CstNode) -> Option<&'static str> {
+        match node.kind() {
+            "interface_declaration" => Some("INTERFACE_DEF"),
+            "annotation_type_declaration" => Some("ANNOTATION_DEF"),
+            "field_declaration" | "constant_declaration" | "local_variable_declaration" => {
+                Some("VARIABLE_DEF")
+            }
+            "method_declaration" => Some("METHOD_DEF"),
+            "annotation_type_element_declaration" => Some("ANNOTATION_FIELD_DEF"),
+            "class_declaration" => Some("CLASS_DEF"),
+            "enum_declaration" => Some("ENUM_DEF"),
+            "record_declaration" => Some("RECORD_DEF"),
+            "constructor_declaration" => Some("CTOR_DEF"),
+            "try_with_resources_statement" => Some("RESOURCE"),
+            // Parameters of methods without a body are checked with the method
+            "formal_parameter" if self.is_in_lambda(node) => Some("LAMBDA"),
+            "formal_parameter" => Some("METHOD_DEF"),
+            "instanceof_expression" | "switch_rule" => Some("PATTERN_VARIABLE_DEF"),
+            "catch_formal_parameter" => Some("LITERAL_CATCH"),
+            _ => None,
+        }
+    }
+
     /// Check for redundant modifiers on interface/annotation declarations.
     fn check_interface_modifiers(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
         let mut diagnostics = vec![];
@@ -895,6 +968,7 @@ mod tests {
     use super::*;
     use lintal_java_cst::TreeWalker;
     use lintal_java_parser::JavaParser;
+    use std::collections::HashMap;
 
     fn check_source(source: &str, jdk_version: Option<u32>) -> Vec<Diagnostic> {
         let mut parser = JavaParser::new();
@@ -903,6 +977,7 @@ mod tests {
         let rule = if let Some(version) = jdk_version {
             RedundantModifier {
                 jdk_version: version,
+                ..RedundantModifier::default()
             }
         } else {
             RedundantModifier::default()
@@ -1184,4 +1259,30 @@ class Test {
             assert!(diagnostic.kind.body.contains("Redundant 'final' modifier"));
         }
     }
+
+    #[test]
+    fn test_tokens() {
+        let source = r#"
+interface Foo {
+    public static final int X = 1;
+    public abstract void run(final int value);
+}
+"#;
+        let properties = HashMap::from([("tokens", "VARIABLE_DEF")]);
+        let rule = RedundantModifier::from_config(&properties);
+        let result = JavaParser::new().parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let messages: Vec<String> = TreeWalker::new(result.tree.root_node(), source)
+            .flat_map(|node| rule.check(&ctx, &node))
+            .map(|d| d.kind.body)
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "Redundant 'public' modifier.",
+                "Redundant 'static' modifier.",
+                "Redundant 'final' modifier."
+            ]
+        );
+    }
 }
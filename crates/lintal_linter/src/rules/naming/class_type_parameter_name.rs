@@ -0,0 +1,173 @@
+//! ClassTypeParameterName rule implementation.
+//!
+//! Checks that class type parameter names conform to a specified pattern.
+//! Interface and method type parameters have rules of their own.
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+use regex::Regex;
+
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Default pattern for class type parameter names: a single uppercase letter
+const DEFAULT_FORMAT: &str = r"^[A-Z]$";
+
+/// Node kinds that represent type parameters
+const RELEVANT_KINDS: &[&str] = &["type_parameter"];
+
+/// Configuration for ClassTypeParameterName rule.
+#[derive(Debug, Clone)]
+pub struct ClassTypeParameterName {
+    /// Regex pattern for valid type parameter names
+    format: Regex,
+    /// Format string for error messages
+    format_str: String,
+}
+
+impl Default for ClassTypeParameterName {
+    fn default() -> Self {
+        Self {
+            format: Regex::new(DEFAULT_FORMAT).unwrap(),
+            format_str: DEFAULT_FORMAT.to_string(),
+        }
+    }
+}
+
+impl FromConfig for ClassTypeParameterName {
+    const MODULE_NAME: &'static str = "ClassTypeParameterName";
+
+    fn from_config(properties: &Properties) -> Self {
+        let format_str = properties
+            .get("format")
+            .copied()
+            .unwrap_or(DEFAULT_FORMAT)
+            .to_string();
+
+        let format =
+            Regex::new(&format_str).unwrap_or_else(|_| Regex::new(DEFAULT_FORMAT).unwrap());
+
+        Self { format, format_str }
+    }
+}
+
+/// Violation for class type parameter name not matching pattern.
+#[derive(Debug, Clone)]
+pub struct ClassTypeParameterNameInvalid {
+    pub name: String,
+    pub pattern: String,
+}
+
+impl Violation for ClassTypeParameterNameInvalid {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!(
+            "Name '{}' must match pattern '{}'.",
+            self.name, self.pattern
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("naming", "name.invalidPattern"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.name.clone(), self.pattern.clone()]
+    }
+}
+
+impl Rule for ClassTypeParameterName {
+    fn name(&self) -> &'static str {
+        "ClassTypeParameterName"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        // Only check type parameters of a class declaration
+        if node.kind() != "type_parameter" {
+            return vec![];
+        }
+
+        let declaration = node.parent().and_then(|parameters| parameters.parent());
+        if declaration.is_none_or(|d| d.kind() != "class_declaration") {
+            return vec![];
+        }
+
+        // The name is the type_identifier after any annotations
+        let Some(name_node) = node.children().find(|c| c.kind() == "type_identifier") else {
+            return vec![];
+        };
+        let parameter_name = &ctx.source()[name_node.range()];
+
+        if self.format.is_match(parameter_name) {
+            return vec![];
+        }
+
+        vec![Diagnostic::new(
+            ClassTypeParameterNameInvalid {
+                name: parameter_name.to_string(),
+                pattern: self.format_str.clone(),
+            },
+            name_node.range(),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, properties: Properties) -> Vec<Diagnostic> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let rule = ClassTypeParameterName::from_config(&properties);
+
+        let mut diagnostics = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            diagnostics.extend(rule.check(&ctx, &node));
+        }
+        diagnostics
+    }
+
+    #[test]
+    fn test_valid_type_parameter() {
+        let source = "class Foo<T, U extends Number> {}";
+        let diagnostics = check_source(source, Properties::new());
+        assert_eq!(diagnostics.len(), 0);
+    }
+
+    #[test]
+    fn test_invalid_type_parameter() {
+        let source = "class Foo<T, Key, @Ann foo> {}";
+        let diagnostics = check_source(source, Properties::new());
+        assert_eq!(diagnostics.len(), 2);
+    }
+
+    #[test]
+    fn test_other_type_parameters_not_checked() {
+        let source = r#"
+interface Bar<Key> {}
+class Foo<T> {
+    <Value> void foo() {}
+    <Value> Foo() {}
+}
+"#;
+        let diagnostics = check_source(source, Properties::new());
+        assert_eq!(diagnostics.len(), 0);
+    }
+
+    #[test]
+    fn test_custom_format() {
+        let source = "class Foo<TKey> {}";
+        let mut properties = Properties::new();
+        properties.insert("format", "^T[A-Z][a-zA-Z]*$");
+        let diagnostics = check_source(source, properties);
+        assert_eq!(diagnostics.len(), 0);
+    }
+}
@@ -4,7 +4,7 @@
 //! A constant is a static and final field or an interface/annotation field,
 //! except serialVersionUID and serialPersistentFields.
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use regex::Regex;
 
@@ -109,6 +109,14 @@ impl Violation for ConstantNameInvalid {
             self.name, self.pattern
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("naming", "name.invalidPattern"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.name.clone(), self.pattern.clone()]
+    }
 }
 
 impl Rule for ConstantName {
@@ -3,7 +3,7 @@
 //! Checks that final local variable names conform to a specified pattern.
 //! Also checks final parameters and try-with-resources variables.
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use regex::Regex;
 
@@ -115,6 +115,14 @@ impl Violation for LocalFinalVariableNameInvalid {
             self.name, self.pattern
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("naming", "name.invalidPattern"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.name.clone(), self.pattern.clone()]
+    }
 }
 
 impl Rule for LocalFinalVariableName {
@@ -3,7 +3,7 @@
 //! Checks that local variable names conform to a specified pattern.
 //! Does not check final local variables if they should be checked by LocalFinalVariableName.
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use regex::Regex;
 
@@ -78,6 +78,14 @@ impl Violation for LocalVariableNameInvalid {
             self.name, self.pattern
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("naming", "name.invalidPattern"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.name.clone(), self.pattern.clone()]
+    }
 }
 
 impl Rule for LocalVariableName {
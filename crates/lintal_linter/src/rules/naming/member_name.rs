@@ -3,7 +3,7 @@
 //! Checks that instance variable names (non-static fields) conform to a specified pattern.
 //! Does not check static fields - use StaticVariableName for those.
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use regex::Regex;
 
@@ -105,6 +105,14 @@ impl Violation for MemberNameInvalid {
             self.name, self.pattern
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("naming", "name.invalidPattern"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.name.clone(), self.pattern.clone()]
+    }
 }
 
 impl Rule for MemberName {
@@ -4,7 +4,7 @@
 //! Also checks if a method name has the same name as the enclosing class.
 //! Does not check the name of overridden methods (@Override annotation).
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use regex::Regex;
 
@@ -115,6 +115,14 @@ impl Violation for MethodNameInvalid {
             self.name, self.pattern
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("naming", "name.invalidPattern"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.name.clone(), self.pattern.clone()]
+    }
 }
 
 /// Violation for method name equaling class name.
@@ -132,6 +140,14 @@ impl Violation for MethodNameEqualsClassName {
             self.name
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("naming", "method.name.equals.class.name"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
 }
 
 impl Rule for MethodName {
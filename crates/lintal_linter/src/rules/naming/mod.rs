@@ -6,21 +6,27 @@
 // node kind before extracting optional fields
 #![allow(clippy::collapsible_if)]
 
+mod class_type_parameter_name;
 mod constant_name;
+mod interface_type_parameter_name;
 mod local_final_variable_name;
 mod local_variable_name;
 mod member_name;
 mod method_name;
+mod method_type_parameter_name;
 mod package_name;
 mod parameter_name;
 mod static_variable_name;
 mod type_name;
 
+pub use class_type_parameter_name::ClassTypeParameterName;
 pub use constant_name::ConstantName;
+pub use interface_type_parameter_name::InterfaceTypeParameterName;
 pub use local_final_variable_name::LocalFinalVariableName;
 pub use local_variable_name::LocalVariableName;
 pub use member_name::MemberName;
 pub use method_name::MethodName;
+pub use method_type_parameter_name::MethodTypeParameterName;
 pub use package_name::PackageName;
 pub use parameter_name::ParameterName;
 pub use static_variable_name::StaticVariableName;
@@ -1,5 +1,9 @@
-//! Regexp rules (RegexpSinglelineJava)
+//! Regexp rules (RegexpSingleline, RegexpMultiline, RegexpSinglelineJava)
 
+mod regexp_multiline;
+mod regexp_singleline;
 mod regexp_singleline_java;
 
+pub use regexp_multiline::RegexpMultiline;
+pub use regexp_singleline::RegexpSingleline;
 pub use regexp_singleline_java::RegexpSinglelineJava;
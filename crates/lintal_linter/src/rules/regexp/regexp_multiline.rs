@@ -0,0 +1,304 @@
+//! RegexpMultiline rule implementation.
+//!
+//! Checks that a specified pattern matches across multiple lines in any
+//! file.
+//!
+//! Checkstyle equivalent: RegexpMultilineCheck
+
+use std::path::Path;
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_source_file::{LineIndex, PositionEncoding};
+use lintal_text_size::{TextRange, TextSize};
+use regex::Regex;
+
+use crate::{FileRule, FromConfig, Properties, Rule};
+
+/// Violation: file matches illegal pattern.
+#[derive(Debug, Clone)]
+pub struct RegexpMultilineMatchViolation {
+    pub pattern: String,
+    /// The configured `message`, reported instead of the default.
+    pub custom_message: Option<String>,
+}
+
+impl Violation for RegexpMultilineMatchViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        match &self.custom_message {
+            Some(msg) => msg.clone(),
+            None => format!("Line matches the illegal pattern '{}'.", self.pattern),
+        }
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        self.custom_message
+            .is_none()
+            .then_some(MessageKey::new("regexp", "regexp.exceeded"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.pattern.clone()]
+    }
+}
+
+/// Violation: file does not meet minimum match count.
+#[derive(Debug, Clone)]
+pub struct RegexpMultilineMinimumViolation {
+    pub minimum: usize,
+    pub pattern: String,
+}
+
+impl Violation for RegexpMultilineMinimumViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!(
+            "File does not contain minimum {} match(es) for pattern '{}'.",
+            self.minimum, self.pattern
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("regexp", "regexp.minimum"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.minimum.to_string(), self.pattern.clone()]
+    }
+}
+
+/// Violation: the configured pattern is empty.
+#[derive(Debug, Clone)]
+pub struct RegexpMultilineEmptyViolation;
+
+impl Violation for RegexpMultilineEmptyViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        "Required pattern is empty.".to_string()
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("regexp", "regexp.empty"))
+    }
+}
+
+/// Configuration for RegexpMultiline rule.
+#[derive(Debug, Clone)]
+pub struct RegexpMultiline {
+    pub format: Regex,
+    pub format_str: String,
+    pub ignore_case: bool,
+    /// Whether `.` also matches line breaks (default: false).
+    pub match_across_lines: bool,
+    pub minimum: usize,
+    pub maximum: usize,
+    pub message: Option<String>,
+}
+
+impl Default for RegexpMultiline {
+    fn default() -> Self {
+        Self {
+            format: Regex::new("(?mR)$.").unwrap(),
+            format_str: "$.".to_string(),
+            ignore_case: false,
+            match_across_lines: false,
+            minimum: 0,
+            maximum: 0,
+            message: None,
+        }
+    }
+}
+
+impl FromConfig for RegexpMultiline {
+    const MODULE_NAME: &'static str = "RegexpMultiline";
+
+    fn from_config(properties: &Properties) -> Self {
+        let format_str = properties
+            .get("format")
+            .copied()
+            .unwrap_or("$.")
+            .to_string();
+        let ignore_case = properties.get("ignoreCase").is_some_and(|v| *v == "true");
+        let match_across_lines = properties
+            .get("matchAcrossLines")
+            .is_some_and(|v| *v == "true");
+        let minimum = properties
+            .get("minimum")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+        let maximum = properties
+            .get("maximum")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+        let message = properties
+            .get("message")
+            .filter(|v| !v.is_empty())
+            .map(|v| v.to_string());
+
+        // `^` and `$` match at line breaks, `\r\n` included, as in
+        // checkstyle's MULTILINE patterns
+        let flags = match (ignore_case, match_across_lines) {
+            (false, false) => "mR",
+            (true, false) => "imR",
+            (false, true) => "msR",
+            (true, true) => "imsR",
+        };
+        let pattern = format!("(?{flags}){format_str}");
+
+        let format = Regex::new(&pattern).unwrap_or_else(|_| Regex::new("$.").unwrap());
+
+        Self {
+            format,
+            format_str,
+            ignore_case,
+            match_across_lines,
+            minimum,
+            maximum,
+            message,
+        }
+    }
+}
+
+impl Rule for RegexpMultiline {
+    fn name(&self) -> &'static str {
+        "RegexpMultiline"
+    }
+
+    fn column_encoding(&self) -> PositionEncoding {
+        PositionEncoding::Utf16
+    }
+
+    fn as_file_rule(&self) -> Option<&dyn FileRule> {
+        Some(self)
+    }
+}
+
+impl FileRule for RegexpMultiline {
+    fn check_file(
+        &self,
+        _path: Option<&Path>,
+        source: &str,
+        _line_index: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        let file_start = TextRange::empty(TextSize::new(0));
+        if self.format_str.is_empty() {
+            return vec![Diagnostic::new(RegexpMultilineEmptyViolation, file_start)];
+        }
+
+        let mut match_count = 0usize;
+        let mut diagnostics = vec![];
+
+        // Each match is reported where it starts
+        for m in self.format.find_iter(source) {
+            match_count += 1;
+            if match_count > self.maximum {
+                let diag_range = TextRange::new(
+                    TextSize::new(m.start() as u32),
+                    TextSize::new(m.end() as u32),
+                );
+                diagnostics.push(Diagnostic::new(
+                    RegexpMultilineMatchViolation {
+                        pattern: self.format_str.clone(),
+                        custom_message: self.message.clone(),
+                    },
+                    diag_range,
+                ));
+            }
+        }
+
+        // Reported on the first line of the file
+        if match_count < self.minimum {
+            diagnostics.push(Diagnostic::new(
+                RegexpMultilineMinimumViolation {
+                    minimum: self.minimum,
+                    pattern: self.format_str.clone(),
+                },
+                file_start,
+            ));
+        }
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_source_file::SourceCode;
+
+    fn check_source(source: &str, rule: &RegexpMultiline) -> Vec<(usize, usize)> {
+        let line_index = LineIndex::from_source_text(source);
+        let source_code = SourceCode::new(source, &line_index);
+        rule.check_file(None, source, &line_index)
+            .iter()
+            .map(|d| {
+                let loc = source_code.line_column(d.range.start());
+                (loc.line.get(), loc.column.get())
+            })
+            .collect()
+    }
+
+    fn rule_with(properties: &[(&'static str, &'static str)]) -> RegexpMultiline {
+        RegexpMultiline::from_config(&properties.iter().copied().collect())
+    }
+
+    #[test]
+    fn test_match_spanning_lines() {
+        let source = "class Test {\n    int x;\n\n\n    int y;\n}\n";
+        assert_eq!(
+            check_source(source, &rule_with(&[("format", r"\n\n\n")])),
+            vec![(2, 11)]
+        );
+    }
+
+    #[test]
+    fn test_line_anchors_with_crlf() {
+        let source = "class Test { \r\n    int x;\r\n}\r\n";
+        assert_eq!(
+            check_source(source, &rule_with(&[("format", r"[ \t]+$")])),
+            vec![(1, 13)]
+        );
+    }
+
+    #[test]
+    fn test_match_across_lines() {
+        let source = "/* start\n end */\nclass Test {}\n";
+        assert!(check_source(source, &rule_with(&[("format", "start.*end")])).is_empty());
+        assert_eq!(
+            check_source(
+                source,
+                &rule_with(&[("format", "start.*end"), ("matchAcrossLines", "true")])
+            ),
+            vec![(1, 4)]
+        );
+    }
+
+    #[test]
+    fn test_minimum_and_maximum() {
+        let source = "// TODO one\n// todo two\n";
+        let rule = rule_with(&[("format", "todo"), ("ignoreCase", "true"), ("maximum", "1")]);
+        assert_eq!(check_source(source, &rule), vec![(2, 4)]);
+
+        let rule = rule_with(&[("format", "Copyright"), ("minimum", "1")]);
+        let line_index = LineIndex::from_source_text(source);
+        let diagnostics = rule.check_file(None, source, &line_index);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind.body,
+            "File does not contain minimum 1 match(es) for pattern 'Copyright'."
+        );
+    }
+
+    #[test]
+    fn test_empty_format() {
+        let source = "class Test {}\n";
+        let rule = rule_with(&[("format", "")]);
+        let line_index = LineIndex::from_source_text(source);
+        let diagnostics = rule.check_file(None, source, &line_index);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind.body, "Required pattern is empty.");
+    }
+}
@@ -0,0 +1,271 @@
+//! RegexpSingleline rule implementation.
+//!
+//! Checks that a specified pattern matches a single line in any file, not
+//! only Java sources.
+//!
+//! Checkstyle equivalent: RegexpSinglelineCheck
+
+use std::path::Path;
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_source_file::{LineIndex, OneIndexed, PositionEncoding, SourceCode};
+use lintal_text_size::{TextRange, TextSize};
+use regex::Regex;
+
+use crate::{FileRule, FromConfig, Properties, Rule};
+
+/// Violation: line matches illegal pattern.
+#[derive(Debug, Clone)]
+pub struct RegexpSinglelineMatchViolation {
+    pub pattern: String,
+    /// The configured `message`, reported instead of the default.
+    pub custom_message: Option<String>,
+}
+
+impl Violation for RegexpSinglelineMatchViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        match &self.custom_message {
+            Some(msg) => msg.clone(),
+            None => format!("Line matches the illegal pattern '{}'.", self.pattern),
+        }
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        self.custom_message
+            .is_none()
+            .then_some(MessageKey::new("regexp", "regexp.exceeded"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.pattern.clone()]
+    }
+}
+
+/// Violation: file does not meet minimum match count.
+#[derive(Debug, Clone)]
+pub struct RegexpSinglelineMinimumViolation {
+    pub minimum: usize,
+    pub pattern: String,
+}
+
+impl Violation for RegexpSinglelineMinimumViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!(
+            "File does not contain minimum {} match(es) for pattern '{}'.",
+            self.minimum, self.pattern
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("regexp", "regexp.minimum"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.minimum.to_string(), self.pattern.clone()]
+    }
+}
+
+/// Configuration for RegexpSingleline rule.
+#[derive(Debug, Clone)]
+pub struct RegexpSingleline {
+    pub format: Regex,
+    pub format_str: String,
+    pub ignore_case: bool,
+    pub minimum: usize,
+    pub maximum: usize,
+    pub message: Option<String>,
+}
+
+impl Default for RegexpSingleline {
+    fn default() -> Self {
+        Self {
+            format: Regex::new("$.").unwrap(),
+            format_str: "$.".to_string(),
+            ignore_case: false,
+            minimum: 0,
+            maximum: 0,
+            message: None,
+        }
+    }
+}
+
+impl FromConfig for RegexpSingleline {
+    const MODULE_NAME: &'static str = "RegexpSingleline";
+
+    fn from_config(properties: &Properties) -> Self {
+        let format_str = properties
+            .get("format")
+            .copied()
+            .unwrap_or("$.")
+            .to_string();
+        let ignore_case = properties.get("ignoreCase").is_some_and(|v| *v == "true");
+        let minimum = properties
+            .get("minimum")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+        let maximum = properties
+            .get("maximum")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+        let message = properties
+            .get("message")
+            .filter(|v| !v.is_empty())
+            .map(|v| v.to_string());
+
+        let pattern = if ignore_case {
+            format!("(?i){}", format_str)
+        } else {
+            format_str.clone()
+        };
+
+        let format = Regex::new(&pattern).unwrap_or_else(|_| Regex::new("$.").unwrap());
+
+        Self {
+            format,
+            format_str,
+            ignore_case,
+            minimum,
+            maximum,
+            message,
+        }
+    }
+}
+
+impl Rule for RegexpSingleline {
+    fn name(&self) -> &'static str {
+        "RegexpSingleline"
+    }
+
+    fn column_encoding(&self) -> PositionEncoding {
+        PositionEncoding::Utf16
+    }
+
+    fn as_file_rule(&self) -> Option<&dyn FileRule> {
+        Some(self)
+    }
+}
+
+impl FileRule for RegexpSingleline {
+    fn check_file(
+        &self,
+        _path: Option<&Path>,
+        source: &str,
+        line_index: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        let source_code = SourceCode::new(source, line_index);
+
+        let mut match_count = 0usize;
+        let mut diagnostics = vec![];
+
+        for (line_idx, line_text) in source.lines().enumerate() {
+            if !self.format.is_match(line_text) {
+                continue;
+            }
+
+            match_count += 1;
+            if match_count > self.maximum {
+                let line_start = source_code.line_start(OneIndexed::from_zero_indexed(line_idx));
+                let diag_range = TextRange::at(line_start, TextSize::of(line_text));
+
+                diagnostics.push(Diagnostic::new(
+                    RegexpSinglelineMatchViolation {
+                        pattern: self.format_str.clone(),
+                        custom_message: self.message.clone(),
+                    },
+                    diag_range,
+                ));
+            }
+        }
+
+        // Reported on the first line of the file
+        if match_count < self.minimum {
+            diagnostics.push(Diagnostic::new(
+                RegexpSinglelineMinimumViolation {
+                    minimum: self.minimum,
+                    pattern: self.format_str.clone(),
+                },
+                TextRange::empty(TextSize::new(0)),
+            ));
+        }
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_source(source: &str, rule: &RegexpSingleline) -> Vec<usize> {
+        let line_index = LineIndex::from_source_text(source);
+        let source_code = SourceCode::new(source, &line_index);
+        rule.check_file(None, source, &line_index)
+            .iter()
+            .map(|d| source_code.line_column(d.range.start()).line.get())
+            .collect()
+    }
+
+    fn rule_with_format(format: &str) -> RegexpSingleline {
+        let mut props = Properties::new();
+        props.insert("format", format);
+        RegexpSingleline::from_config(&props)
+    }
+
+    #[test]
+    fn test_trailing_spaces() {
+        let source = "class Test { \n    int x;\r\n}\t\n";
+        assert_eq!(check_source(source, &rule_with_format(r"\s+$")), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_maximum() {
+        let source = "// TODO one\n// TODO two\n// TODO three\n";
+        let rule = RegexpSingleline {
+            maximum: 2,
+            ..rule_with_format("TODO")
+        };
+        assert_eq!(check_source(source, &rule), vec![3]);
+    }
+
+    #[test]
+    fn test_minimum_not_met() {
+        let source = "class Test {}\n";
+        let rule = RegexpSingleline {
+            minimum: 1,
+            maximum: 1000,
+            ..rule_with_format("Copyright")
+        };
+        let line_index = LineIndex::from_source_text(source);
+        let diagnostics = rule.check_file(None, source, &line_index);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind.body,
+            "File does not contain minimum 1 match(es) for pattern 'Copyright'."
+        );
+    }
+
+    #[test]
+    fn test_ignore_case_and_message() {
+        let mut props = Properties::new();
+        props.insert("format", "system\\.out");
+        props.insert("ignoreCase", "true");
+        props.insert("message", "No printing to the console.");
+        let rule = RegexpSingleline::from_config(&props);
+
+        let source = "System.out.println();\n";
+        let line_index = LineIndex::from_source_text(source);
+        let diagnostics = rule.check_file(None, source, &line_index);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind.body, "No printing to the console.");
+    }
+
+    #[test]
+    fn test_default_pattern_no_violations() {
+        let source = "class Test {}\n";
+        assert!(check_source(source, &RegexpSingleline::default()).is_empty());
+    }
+}
@@ -5,8 +5,9 @@
 //!
 //! Checkstyle equivalent: RegexpSinglelineJavaCheck
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
+use lintal_source_file::PositionEncoding;
 use lintal_text_size::{TextRange, TextSize};
 use regex::Regex;
 use tree_sitter::Node;
@@ -16,28 +17,55 @@ use crate::{CheckContext, FromConfig, Properties, Rule};
 /// Violation: line matches illegal pattern.
 #[derive(Debug, Clone)]
 pub struct RegexpSinglelineJavaMatchViolation {
-    pub message: String,
+    pub pattern: String,
+    /// The configured `message`, reported instead of the default.
+    pub custom_message: Option<String>,
 }
 
 impl Violation for RegexpSinglelineJavaMatchViolation {
     const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
 
     fn message(&self) -> String {
-        self.message.clone()
+        match &self.custom_message {
+            Some(msg) => msg.clone(),
+            None => format!("Line matches the illegal pattern '{}'.", self.pattern),
+        }
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        self.custom_message
+            .is_none()
+            .then_some(MessageKey::new("regexp", "regexp.exceeded"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.pattern.clone()]
     }
 }
 
 /// Violation: file does not meet minimum match count.
 #[derive(Debug, Clone)]
 pub struct RegexpSinglelineJavaMinimumViolation {
-    pub message: String,
+    pub minimum: usize,
+    pub pattern: String,
 }
 
 impl Violation for RegexpSinglelineJavaMinimumViolation {
     const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
 
     fn message(&self) -> String {
-        self.message.clone()
+        format!(
+            "File does not contain minimum {} match(es) for pattern '{}'.",
+            self.minimum, self.pattern
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("regexp", "regexp.minimum"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.minimum.to_string(), self.pattern.clone()]
     }
 }
 
@@ -124,6 +152,10 @@ impl Rule for RegexpSinglelineJava {
         RELEVANT_KINDS
     }
 
+    fn column_encoding(&self) -> PositionEncoding {
+        PositionEncoding::Utf16
+    }
+
     fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
         // Only run at root node
         if node.parent().is_some() {
@@ -177,14 +209,11 @@ impl Rule for RegexpSinglelineJava {
                         TextSize::new((usize::from(line_start) + line_text.len()) as u32),
                     );
 
-                    let message = if let Some(ref msg) = self.message {
-                        msg.clone()
-                    } else {
-                        format!("Line matches the illegal pattern '{}'.", self.format_str)
-                    };
-
                     diagnostics.push(Diagnostic::new(
-                        RegexpSinglelineJavaMatchViolation { message },
+                        RegexpSinglelineJavaMatchViolation {
+                            pattern: self.format_str.clone(),
+                            custom_message: self.message.clone(),
+                        },
                         diag_range,
                     ));
                 }
@@ -194,12 +223,11 @@ impl Rule for RegexpSinglelineJava {
         // Check minimum requirement
         if self.minimum > 0 && match_count < self.minimum {
             let diag_range = TextRange::new(TextSize::new(0), TextSize::new(0));
-            let message = format!(
-                "File does not contain minimum {} match(es) for pattern '{}'.",
-                self.minimum, self.format_str
-            );
             diagnostics.push(Diagnostic::new(
-                RegexpSinglelineJavaMinimumViolation { message },
+                RegexpSinglelineJavaMinimumViolation {
+                    minimum: self.minimum,
+                    pattern: self.format_str.clone(),
+                },
                 diag_range,
             ));
         }
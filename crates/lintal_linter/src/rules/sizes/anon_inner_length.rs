@@ -0,0 +1,162 @@
+//! AnonInnerLength rule implementation.
+//!
+//! Checks that anonymous inner classes do not exceed a specified number of
+//! lines.
+//!
+//! Checkstyle equivalent: AnonInnerLengthCheck
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+
+use super::common::line_count;
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: anonymous inner class is too long.
+#[derive(Debug, Clone)]
+pub struct AnonInnerLengthViolation {
+    pub len: usize,
+    pub max: usize,
+}
+
+impl Violation for AnonInnerLengthViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!(
+            "Anonymous inner class length is {} lines (max allowed is {}).",
+            self.len, self.max
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("sizes", "maxLen.anonInner"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.len.to_string(), self.max.to_string()]
+    }
+}
+
+/// Configuration for AnonInnerLength rule.
+#[derive(Debug, Clone)]
+pub struct AnonInnerLength {
+    /// Maximum allowed anonymous class length in lines (default: 20).
+    pub max: usize,
+}
+
+const RELEVANT_KINDS: &[&str] = &["object_creation_expression"];
+
+impl Default for AnonInnerLength {
+    fn default() -> Self {
+        Self { max: 20 }
+    }
+}
+
+impl FromConfig for AnonInnerLength {
+    const MODULE_NAME: &'static str = "AnonInnerLength";
+
+    fn from_config(properties: &Properties) -> Self {
+        let max = properties
+            .get("max")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(20);
+
+        Self { max }
+    }
+}
+
+impl Rule for AnonInnerLength {
+    fn name(&self) -> &'static str {
+        "AnonInnerLength"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        let Some(body) = node.children().find(|c| c.kind() == "class_body") else {
+            return vec![];
+        };
+
+        // Counted from the `new`, which follows the outer instance of a
+        // qualified creation such as `outer.new Inner() {}`
+        let new = node.children().find(|c| c.kind() == "new").unwrap_or(*node);
+        let len = line_count(
+            ctx.source(),
+            new.inner().start_position().row,
+            body.inner().end_position().row,
+            true,
+        );
+
+        if len > self.max {
+            return vec![Diagnostic::new(
+                AnonInnerLengthViolation { len, max: self.max },
+                new.range(),
+            )];
+        }
+
+        vec![]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+    use lintal_source_file::{LineIndex, SourceCode};
+
+    fn check_source(source: &str, max: usize) -> Vec<usize> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let rule = AnonInnerLength { max };
+        let line_index = LineIndex::from_source_text(source);
+        let source_code = SourceCode::new(source, &line_index);
+
+        let mut violations = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            for d in rule.check(&ctx, &node) {
+                let loc = source_code.line_column(d.range.start());
+                violations.push(loc.line.get());
+            }
+        }
+        violations
+    }
+
+    const SOURCE: &str = r#"
+class Foo {
+    Runnable r = new Runnable() {
+        public void run() {
+            System.out.println();
+        }
+    };
+    Object o = new Object();
+}
+"#;
+
+    #[test]
+    fn test_short_anonymous_class_no_violation() {
+        assert!(check_source(SOURCE, 5).is_empty());
+    }
+
+    #[test]
+    fn test_long_anonymous_class_violation() {
+        assert_eq!(check_source(SOURCE, 4), vec![3]);
+    }
+
+    #[test]
+    fn test_qualified_creation() {
+        let source = r#"
+class Foo {
+    Object o = outer
+        .new Inner() {
+        };
+}
+"#;
+        // Two lines from the `new`, not three from `outer`
+        assert!(check_source(source, 2).is_empty());
+        assert_eq!(check_source(source, 1), vec![4]);
+    }
+}
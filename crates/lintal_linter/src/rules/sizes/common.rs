@@ -0,0 +1,30 @@
+//! Shared utilities for size rules.
+
+/// The number of lines from `start_line` to `end_line`, both zero-based and
+/// included. Lines that are empty or hold only whitespace are left out
+/// unless `count_empty`.
+pub fn line_count(source: &str, start_line: usize, end_line: usize, count_empty: bool) -> usize {
+    let total = end_line - start_line + 1;
+    if count_empty {
+        return total;
+    }
+    source
+        .lines()
+        .skip(start_line)
+        .take(total)
+        .filter(|line| !line.trim().is_empty())
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_count() {
+        let source = "a\n\n  \nb\nc\n";
+        assert_eq!(line_count(source, 0, 3, true), 4);
+        assert_eq!(line_count(source, 0, 3, false), 2);
+        assert_eq!(line_count(source, 4, 4, false), 1);
+    }
+}
@@ -0,0 +1,344 @@
+//! ExecutableStatementCount rule implementation.
+//!
+//! Restricts the number of executable statements in methods, constructors,
+//! initializers and lambdas.
+//!
+//! Checkstyle equivalent: ExecutableStatementCountCheck
+
+use std::collections::HashSet;
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+
+use crate::rules::javadoc::common::parse_list;
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: too many executable statements.
+#[derive(Debug, Clone)]
+pub struct ExecutableStatementCountViolation {
+    pub count: usize,
+    pub max: usize,
+}
+
+impl Violation for ExecutableStatementCountViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!(
+            "Executable statement count is {} (max allowed is {}).",
+            self.count, self.max
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("sizes", "executableStatementCount"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.count.to_string(), self.max.to_string()]
+    }
+}
+
+/// Configuration for ExecutableStatementCount rule.
+#[derive(Debug, Clone)]
+pub struct ExecutableStatementCount {
+    /// Maximum allowed number of executable statements (default: 30).
+    pub max: usize,
+    /// Declarations to check: CTOR_DEF, METHOD_DEF, INSTANCE_INIT,
+    /// STATIC_INIT, COMPACT_CTOR_DEF, LAMBDA.
+    pub tokens: HashSet<String>,
+}
+
+const DEFAULT_TOKENS: &[&str] = &[
+    "CTOR_DEF",
+    "METHOD_DEF",
+    "INSTANCE_INIT",
+    "STATIC_INIT",
+    "COMPACT_CTOR_DEF",
+    "LAMBDA",
+];
+
+const RELEVANT_KINDS: &[&str] = &[
+    "constructor_declaration",
+    "method_declaration",
+    "block",
+    "static_initializer",
+    "compact_constructor_declaration",
+    "lambda_expression",
+];
+
+impl Default for ExecutableStatementCount {
+    fn default() -> Self {
+        Self {
+            max: 30,
+            tokens: DEFAULT_TOKENS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl FromConfig for ExecutableStatementCount {
+    const MODULE_NAME: &'static str = "ExecutableStatementCount";
+
+    fn from_config(properties: &Properties) -> Self {
+        let default = Self::default();
+
+        let max = properties
+            .get("max")
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(default.max);
+
+        let tokens = properties
+            .get("tokens")
+            .map(|v| parse_list(v).into_iter().collect())
+            .unwrap_or(default.tokens);
+
+        Self { max, tokens }
+    }
+}
+
+impl Rule for ExecutableStatementCount {
+    fn name(&self) -> &'static str {
+        "ExecutableStatementCount"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        let Some(token) = container_token(node) else {
+            return vec![];
+        };
+        if !self.tokens.contains(token) {
+            return vec![];
+        }
+
+        let count = statement_count(node);
+        if count <= self.max {
+            return vec![];
+        }
+
+        // A lambda is reported on its `->`, where checkstyle places it
+        let at = match node.kind() {
+            "lambda_expression" => node.children().find(|c| c.kind() == "->").unwrap_or(*node),
+            _ => *node,
+        };
+        vec![Diagnostic::new(
+            ExecutableStatementCountViolation {
+                count,
+                max: self.max,
+            },
+            at.range(),
+        )]
+    }
+}
+
+/// The checkstyle token of a node whose statements are counted on their
+/// own, whether or not the rule checks that token.
+fn container_token(node: &CstNode) -> Option<&'static str> {
+    match node.kind() {
+        "constructor_declaration" => Some("CTOR_DEF"),
+        "method_declaration" => Some("METHOD_DEF"),
+        "static_initializer" => Some("STATIC_INIT"),
+        "compact_constructor_declaration" => Some("COMPACT_CTOR_DEF"),
+        "lambda_expression" => Some("LAMBDA"),
+        // An instance initializer is a block directly in a class body
+        "block" => node
+            .parent()
+            .filter(|p| matches!(p.kind(), "class_body" | "enum_body_declarations"))
+            .map(|_| "INSTANCE_INIT"),
+        _ => None,
+    }
+}
+
+/// The executable statements of a container, leaving out those of the
+/// containers nested in it, such as lambdas and the methods of anonymous
+/// classes.
+fn statement_count(container: &CstNode) -> usize {
+    fn walk(node: &CstNode, count: &mut usize) {
+        for child in node.children() {
+            if container_token(&child).is_some() {
+                continue;
+            }
+            if let Some(children) = statement_list_children(&child) {
+                *count += children / 2;
+            }
+            walk(&child, count);
+        }
+    }
+
+    let mut count = 0;
+    // The body of an instance initializer is the container itself
+    if let Some(children) = statement_list_children(container) {
+        count += children / 2;
+    }
+    walk(container, &mut count);
+    count
+}
+
+/// How many children checkstyle's `SLIST` for a statement list has, if
+/// `node` is one.
+///
+/// Checkstyle takes half of that as the number of statements in the list,
+/// since an expression statement or a variable declaration is followed by
+/// its own `SEMI`. Other statements hold their semicolon, so lists of them
+/// count fewer statements than they have; this is kept for compatibility.
+fn statement_list_children(node: &CstNode) -> Option<usize> {
+    // A block ends with an `RCURLY`, the statements of a case don't
+    let (closing, skipped): (usize, &[&str]) = match node.kind() {
+        "block" | "constructor_body" => (1, &["{", "}"]),
+        "switch_block_statement_group" => (0, &["switch_label", ":"]),
+        _ => return None,
+    };
+
+    let statements: usize = node
+        .children()
+        .filter(|child| {
+            !skipped.contains(&child.kind())
+                && !matches!(child.kind(), "line_comment" | "block_comment")
+        })
+        .map(|statement| match statement.kind() {
+            "expression_statement" => 2,
+            // `int a, b;` is VARIABLE_DEF COMMA VARIABLE_DEF SEMI
+            "local_variable_declaration" => {
+                2 * statement
+                    .children()
+                    .filter(|c| c.kind() == "variable_declarator")
+                    .count()
+            }
+            _ => 1,
+        })
+        .sum();
+
+    Some(statements + closing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, rule: &ExecutableStatementCount) -> Vec<String> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+
+        let mut messages = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            for d in rule.check(&ctx, &node) {
+                messages.push(d.kind.body);
+            }
+        }
+        messages
+    }
+
+    fn rule_with_max(max: usize) -> ExecutableStatementCount {
+        ExecutableStatementCount {
+            max,
+            ..ExecutableStatementCount::default()
+        }
+    }
+
+    #[test]
+    fn test_within_limit_no_violation() {
+        let source = r#"
+class Foo {
+    void method() {
+        int a = 1;
+        a++;
+    }
+}
+"#;
+        assert!(check_source(source, &rule_with_max(2)).is_empty());
+    }
+
+    #[test]
+    fn test_nested_blocks_counted() {
+        let source = r#"
+class Foo {
+    void method(boolean b) {
+        int a = 1;
+        if (b) {
+            a++;
+            a--;
+        }
+    }
+}
+"#;
+        assert_eq!(
+            check_source(source, &rule_with_max(3)),
+            vec!["Executable statement count is 4 (max allowed is 3)."]
+        );
+    }
+
+    #[test]
+    fn test_lambda_counted_on_its_own() {
+        let source = r#"
+class Foo {
+    void method() {
+        Runnable r = () -> {
+            foo();
+            bar();
+        };
+    }
+}
+"#;
+        assert_eq!(
+            check_source(source, &rule_with_max(1)),
+            vec!["Executable statement count is 2 (max allowed is 1)."]
+        );
+
+        let rule = ExecutableStatementCount {
+            max: 1,
+            tokens: ["METHOD_DEF".to_string()].into_iter().collect(),
+        };
+        assert!(check_source(source, &rule).is_empty());
+    }
+
+    #[test]
+    fn test_initializers_and_constructors() {
+        let source = r#"
+class Foo {
+    static {
+        foo();
+        bar();
+    }
+
+    {
+        foo();
+        bar();
+    }
+
+    Foo() {
+        super();
+        foo();
+        bar();
+    }
+}
+"#;
+        assert_eq!(check_source(source, &rule_with_max(1)).len(), 3);
+    }
+
+    #[test]
+    fn test_switch_cases() {
+        let source = r#"
+class Foo {
+    void method(int i) {
+        switch (i) {
+            case 1:
+                foo();
+                bar();
+                break;
+            default:
+                baz();
+        }
+    }
+}
+"#;
+        assert_eq!(
+            check_source(source, &rule_with_max(3)),
+            vec!["Executable statement count is 4 (max allowed is 3)."]
+        );
+    }
+}
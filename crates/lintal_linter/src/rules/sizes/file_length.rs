@@ -0,0 +1,132 @@
+//! FileLength rule implementation.
+//!
+//! Checks that files do not exceed a specified number of lines.
+//!
+//! Checkstyle equivalent: FileLengthCheck
+
+use std::path::Path;
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_source_file::LineIndex;
+use lintal_text_size::{TextRange, TextSize};
+
+use crate::{FileRule, FromConfig, Properties, Rule};
+
+/// Violation: file is too long.
+#[derive(Debug, Clone)]
+pub struct FileLengthViolation {
+    pub len: usize,
+    pub max: usize,
+}
+
+impl Violation for FileLengthViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!(
+            "File length is {} lines (max allowed is {}).",
+            self.len, self.max
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("sizes", "maxLen.file"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.len.to_string(), self.max.to_string()]
+    }
+}
+
+/// Configuration for FileLength rule.
+#[derive(Debug, Clone)]
+pub struct FileLength {
+    /// Maximum allowed file length in lines (default: 2000).
+    pub max: usize,
+}
+
+impl Default for FileLength {
+    fn default() -> Self {
+        Self { max: 2000 }
+    }
+}
+
+impl FromConfig for FileLength {
+    const MODULE_NAME: &'static str = "FileLength";
+
+    fn from_config(properties: &Properties) -> Self {
+        let max = properties
+            .get("max")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(2000);
+
+        Self { max }
+    }
+}
+
+impl Rule for FileLength {
+    fn name(&self) -> &'static str {
+        "FileLength"
+    }
+
+    fn as_file_rule(&self) -> Option<&dyn FileRule> {
+        Some(self)
+    }
+}
+
+impl FileRule for FileLength {
+    fn check_file(
+        &self,
+        _path: Option<&Path>,
+        source: &str,
+        _line_index: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        // A trailing line break doesn't start another line, as in checkstyle
+        let len = source.lines().count();
+        if len <= self.max {
+            return vec![];
+        }
+
+        // Reported on the first line of the file
+        vec![Diagnostic::new(
+            FileLengthViolation { len, max: self.max },
+            TextRange::empty(TextSize::new(0)),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_source(source: &str, max: usize) -> Vec<Diagnostic> {
+        let line_index = LineIndex::from_source_text(source);
+        FileLength { max }.check_file(None, source, &line_index)
+    }
+
+    #[test]
+    fn test_short_file_no_violation() {
+        let source = "class Foo {\n    int x;\n}\n";
+        assert!(check_source(source, 3).is_empty());
+    }
+
+    #[test]
+    fn test_long_file_violation() {
+        let source = "class Foo {\n    int x;\n    int y;\n}\n";
+        let diagnostics = check_source(source, 3);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind.body,
+            "File length is 4 lines (max allowed is 3)."
+        );
+        assert_eq!(diagnostics[0].range.start(), TextSize::new(0));
+    }
+
+    #[test]
+    fn test_from_config() {
+        let mut props = Properties::new();
+        assert_eq!(FileLength::from_config(&props).max, 2000);
+        props.insert("max", "500");
+        assert_eq!(FileLength::from_config(&props).max, 500);
+    }
+}
@@ -0,0 +1,163 @@
+//! LambdaBodyLength rule implementation.
+//!
+//! Checks that lambda bodies do not exceed a specified number of lines.
+//!
+//! Checkstyle equivalent: LambdaBodyLengthCheck
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+
+use super::common::line_count;
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: lambda body is too long.
+#[derive(Debug, Clone)]
+pub struct LambdaBodyLengthViolation {
+    pub len: usize,
+    pub max: usize,
+}
+
+impl Violation for LambdaBodyLengthViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!(
+            "Lambda body length is {} lines (max allowed is {}).",
+            self.len, self.max
+        )
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("sizes", "maxLen.lambdaBody"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.len.to_string(), self.max.to_string()]
+    }
+}
+
+/// Configuration for LambdaBodyLength rule.
+#[derive(Debug, Clone)]
+pub struct LambdaBodyLength {
+    /// Maximum allowed lambda body length in lines (default: 10).
+    pub max: usize,
+}
+
+const RELEVANT_KINDS: &[&str] = &["lambda_expression"];
+
+impl Default for LambdaBodyLength {
+    fn default() -> Self {
+        Self { max: 10 }
+    }
+}
+
+impl FromConfig for LambdaBodyLength {
+    const MODULE_NAME: &'static str = "LambdaBodyLength";
+
+    fn from_config(properties: &Properties) -> Self {
+        let max = properties
+            .get("max")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(10);
+
+        Self { max }
+    }
+}
+
+impl Rule for LambdaBodyLength {
+    fn name(&self) -> &'static str {
+        "LambdaBodyLength"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        let Some(body) = node.child_by_field_name("body") else {
+            return vec![];
+        };
+
+        // Only the body is counted, from its first line to its last, so the
+        // parameters don't add to the length
+        let ts_body = body.inner();
+        let len = line_count(
+            ctx.source(),
+            ts_body.start_position().row,
+            ts_body.end_position().row,
+            true,
+        );
+
+        if len > self.max {
+            // Reported on the `->`, where checkstyle places the lambda
+            let arrow = node.children().find(|c| c.kind() == "->").unwrap_or(*node);
+            return vec![Diagnostic::new(
+                LambdaBodyLengthViolation { len, max: self.max },
+                arrow.range(),
+            )];
+        }
+
+        vec![]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+    use lintal_source_file::{LineIndex, SourceCode};
+
+    fn check_source(source: &str, max: usize) -> Vec<usize> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let rule = LambdaBodyLength { max };
+        let line_index = LineIndex::from_source_text(source);
+        let source_code = SourceCode::new(source, &line_index);
+
+        let mut violations = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            for d in rule.check(&ctx, &node) {
+                let loc = source_code.line_column(d.range.start());
+                violations.push(loc.line.get());
+            }
+        }
+        violations
+    }
+
+    #[test]
+    fn test_block_body() {
+        let source = r#"
+class Foo {
+    Runnable r = () -> {
+        System.out.println();
+        System.out.println();
+    };
+}
+"#;
+        assert!(check_source(source, 4).is_empty());
+        assert_eq!(check_source(source, 3), vec![3]);
+    }
+
+    #[test]
+    fn test_expression_body() {
+        let source = r#"
+class Foo {
+    java.util.function.IntBinaryOperator op = (a,
+                                               b) ->
+        a
+            + b;
+}
+"#;
+        // The parameters are on lines of their own and aren't counted
+        assert!(check_source(source, 2).is_empty());
+        assert_eq!(check_source(source, 1), vec![4]);
+    }
+
+    #[test]
+    fn test_single_line_lambda() {
+        let source = "class Foo { Runnable r = () -> {}; }";
+        assert!(check_source(source, 1).is_empty());
+    }
+}
@@ -4,12 +4,14 @@
 //!
 //! Checkstyle equivalent: LineLengthCheck
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
-use lintal_java_cst::CstNode;
+use std::path::Path;
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_source_file::{LineIndex, PositionEncoding, SourceCode};
 use lintal_text_size::{TextRange, TextSize};
 use regex::Regex;
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{FileRule, FromConfig, Properties, Rule};
 
 /// Violation: line is too long.
 #[derive(Debug, Clone)]
@@ -27,6 +29,14 @@ impl Violation for LineLengthViolation {
             self.max, self.len
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("sizes", "maxLineLen"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.max.to_string(), self.len.to_string()]
+    }
 }
 
 /// Configuration for LineLength rule.
@@ -38,8 +48,6 @@ pub struct LineLength {
     pub ignore_pattern: Option<Regex>,
 }
 
-const RELEVANT_KINDS: &[&str] = &["program"];
-
 impl Default for LineLength {
     fn default() -> Self {
         Self {
@@ -74,18 +82,23 @@ impl Rule for LineLength {
         "LineLength"
     }
 
-    fn relevant_kinds(&self) -> &'static [&'static str] {
-        RELEVANT_KINDS
+    fn column_encoding(&self) -> PositionEncoding {
+        PositionEncoding::Utf16
     }
 
-    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
-        // Only check at the root node
-        if node.parent().is_some() {
-            return vec![];
-        }
+    fn as_file_rule(&self) -> Option<&dyn FileRule> {
+        Some(self)
+    }
+}
 
-        let source = ctx.source();
-        let source_code = ctx.source_code();
+impl FileRule for LineLength {
+    fn check_file(
+        &self,
+        _path: Option<&Path>,
+        source: &str,
+        line_index: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        let source_code = SourceCode::new(source, line_index);
         let mut diagnostics = vec![];
 
         for (line_no, line_text) in source.lines().enumerate() {
@@ -136,29 +149,23 @@ impl Rule for LineLength {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use lintal_java_cst::TreeWalker;
-    use lintal_java_parser::JavaParser;
-    use lintal_source_file::{LineIndex, SourceCode};
+
+    fn check_rule(rule: &LineLength, source: &str) -> Vec<usize> {
+        let line_index = LineIndex::from_source_text(source);
+        let source_code = SourceCode::new(source, &line_index);
+
+        rule.check_file(None, source, &line_index)
+            .iter()
+            .map(|d| source_code.line_column(d.range.start()).line.get())
+            .collect()
+    }
 
     fn check_source(source: &str, max: usize) -> Vec<usize> {
-        let mut parser = JavaParser::new();
-        let result = parser.parse(source).unwrap();
-        let ctx = CheckContext::new(source);
         let rule = LineLength {
             max,
             ignore_pattern: None,
         };
-        let line_index = LineIndex::from_source_text(source);
-        let source_code = SourceCode::new(source, &line_index);
-
-        let mut violations = vec![];
-        for node in TreeWalker::new(result.tree.root_node(), source) {
-            for d in rule.check(&ctx, &node) {
-                let loc = source_code.line_column(d.range.start());
-                violations.push(loc.line.get());
-            }
-        }
-        violations
+        check_rule(&rule, source)
     }
 
     #[test]
@@ -190,23 +197,11 @@ mod tests {
     #[test]
     fn test_ignore_pattern() {
         let source = "class Foo {\n    // This is a very long comment that exceeds the limit for sure by a lot of characters\n    int x;\n}\n";
-        let mut parser = JavaParser::new();
-        let result = parser.parse(source).unwrap();
-        let ctx = CheckContext::new(source);
         let rule = LineLength {
             max: 40,
             ignore_pattern: Some(Regex::new(r"^\s*//").unwrap()),
         };
-        let line_index = LineIndex::from_source_text(source);
-        let source_code = SourceCode::new(source, &line_index);
-
-        let mut violations = vec![];
-        for node in TreeWalker::new(result.tree.root_node(), source) {
-            for d in rule.check(&ctx, &node) {
-                let loc = source_code.line_column(d.range.start());
-                violations.push(loc.line.get());
-            }
-        }
+        let violations = check_rule(&rule, source);
 
         // The comment line should be ignored
         assert!(violations.is_empty(), "Comment lines should be ignored");
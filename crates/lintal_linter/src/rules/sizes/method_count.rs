@@ -0,0 +1,355 @@
+//! MethodCount rule implementation.
+//!
+//! Checks the number of methods declared in each type, in total and by
+//! access modifier.
+//!
+//! Checkstyle equivalent: MethodCountCheck
+
+use std::collections::HashSet;
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+
+use crate::rules::javadoc::common::{Scope, parse_list, scope_of};
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: type has too many methods, of a scope or in total.
+#[derive(Debug, Clone)]
+pub struct MethodCountViolation {
+    /// The access modifier counted, or `None` for all methods.
+    pub scope: Option<Scope>,
+    pub count: usize,
+    pub max: usize,
+}
+
+impl MethodCountViolation {
+    fn scope_name(&self) -> &'static str {
+        match self.scope {
+            Some(Scope::Private) => "private",
+            Some(Scope::Package) => "package",
+            Some(Scope::Protected) => "protected",
+            _ => "public",
+        }
+    }
+}
+
+impl Violation for MethodCountViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        match self.scope {
+            None => format!(
+                "Total number of methods is {} (max allowed is {}).",
+                self.count, self.max
+            ),
+            Some(_) => format!(
+                "Number of {} methods is {} (max allowed is {}).",
+                self.scope_name(),
+                self.count,
+                self.max
+            ),
+        }
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        let key = match self.scope {
+            None => "too.many.methods",
+            Some(Scope::Private) => "too.many.privateMethods",
+            Some(Scope::Package) => "too.many.packageMethods",
+            Some(Scope::Protected) => "too.many.protectedMethods",
+            Some(_) => "too.many.publicMethods",
+        };
+        Some(MessageKey::new("sizes", key))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.count.to_string(), self.max.to_string()]
+    }
+}
+
+/// Configuration for MethodCount rule.
+#[derive(Debug, Clone)]
+pub struct MethodCount {
+    /// Maximum allowed number of methods in a type (default: 100).
+    pub max_total: usize,
+    /// Maximum allowed number of private methods (default: 100).
+    pub max_private: usize,
+    /// Maximum allowed number of package-private methods (default: 100).
+    pub max_package: usize,
+    /// Maximum allowed number of protected methods (default: 100).
+    pub max_protected: usize,
+    /// Maximum allowed number of public methods (default: 100).
+    pub max_public: usize,
+    /// Types to check: CLASS_DEF, ENUM_CONSTANT_DEF, ENUM_DEF,
+    /// INTERFACE_DEF, ANNOTATION_DEF, RECORD_DEF, and METHOD_DEF for the
+    /// methods to count.
+    pub tokens: HashSet<String>,
+}
+
+const DEFAULT_TOKENS: &[&str] = &[
+    "CLASS_DEF",
+    "ENUM_CONSTANT_DEF",
+    "ENUM_DEF",
+    "INTERFACE_DEF",
+    "ANNOTATION_DEF",
+    "METHOD_DEF",
+    "RECORD_DEF",
+];
+
+const RELEVANT_KINDS: &[&str] = &[
+    "class_declaration",
+    "enum_constant",
+    "enum_declaration",
+    "interface_declaration",
+    "annotation_type_declaration",
+    "record_declaration",
+];
+
+impl Default for MethodCount {
+    fn default() -> Self {
+        Self {
+            max_total: 100,
+            max_private: 100,
+            max_package: 100,
+            max_protected: 100,
+            max_public: 100,
+            tokens: DEFAULT_TOKENS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl FromConfig for MethodCount {
+    const MODULE_NAME: &'static str = "MethodCount";
+
+    fn from_config(properties: &Properties) -> Self {
+        let default = Self::default();
+        let max = |name: &str, default: usize| {
+            properties
+                .get(name)
+                .and_then(|s| s.trim().parse().ok())
+                .unwrap_or(default)
+        };
+
+        let tokens = properties
+            .get("tokens")
+            .map(|v| parse_list(v).into_iter().collect())
+            .unwrap_or(default.tokens);
+
+        Self {
+            max_total: max("maxTotal", default.max_total),
+            max_private: max("maxPrivate", default.max_private),
+            max_package: max("maxPackage", default.max_package),
+            max_protected: max("maxProtected", default.max_protected),
+            max_public: max("maxPublic", default.max_public),
+            tokens,
+        }
+    }
+}
+
+impl Rule for MethodCount {
+    fn name(&self) -> &'static str {
+        "MethodCount"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        let Some(token) = type_token(node.kind()) else {
+            return vec![];
+        };
+        if !self.tokens.contains(token) || !self.tokens.contains("METHOD_DEF") {
+            return vec![];
+        }
+        // An enum constant without a body declares no methods
+        let Some(body) = node.child_by_field_name("body") else {
+            return vec![];
+        };
+
+        let mut private = 0;
+        let mut package = 0;
+        let mut protected = 0;
+        let mut public = 0;
+        for method in methods(&body) {
+            match scope_of(&method) {
+                Scope::Private => private += 1,
+                Scope::Package => package += 1,
+                Scope::Protected => protected += 1,
+                _ => public += 1,
+            }
+        }
+        let total = private + package + protected + public;
+
+        // Same order as checkstyle reports them
+        [
+            (Some(Scope::Private), private, self.max_private),
+            (Some(Scope::Package), package, self.max_package),
+            (Some(Scope::Protected), protected, self.max_protected),
+            (Some(Scope::Public), public, self.max_public),
+            (None, total, self.max_total),
+        ]
+        .into_iter()
+        .filter(|&(_, count, max)| count > max)
+        .map(|(scope, count, max)| {
+            Diagnostic::new(MethodCountViolation { scope, count, max }, node.range())
+        })
+        .collect()
+    }
+}
+
+/// The checkstyle token of a type the rule checks.
+fn type_token(kind: &str) -> Option<&'static str> {
+    match kind {
+        "class_declaration" => Some("CLASS_DEF"),
+        "enum_constant" => Some("ENUM_CONSTANT_DEF"),
+        "enum_declaration" => Some("ENUM_DEF"),
+        "interface_declaration" => Some("INTERFACE_DEF"),
+        "annotation_type_declaration" => Some("ANNOTATION_DEF"),
+        "record_declaration" => Some("RECORD_DEF"),
+        _ => None,
+    }
+}
+
+/// The methods declared directly in a type body. Constructors, and methods
+/// of nested and anonymous classes, aren't counted.
+fn methods<'a>(body: &CstNode<'a>) -> Vec<CstNode<'a>> {
+    body.children()
+        .flat_map(|child| {
+            // The members of an enum follow its constants
+            if child.kind() == "enum_body_declarations" {
+                child.children().collect()
+            } else {
+                vec![child]
+            }
+        })
+        .filter(|member| member.kind() == "method_declaration")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, rule: &MethodCount) -> Vec<String> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+
+        let mut messages = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            for d in rule.check(&ctx, &node) {
+                messages.push(d.kind.body);
+            }
+        }
+        messages
+    }
+
+    fn rule_from(properties: &[(&'static str, &'static str)]) -> MethodCount {
+        let mut props = Properties::new();
+        for &(name, value) in properties {
+            props.insert(name, value);
+        }
+        MethodCount::from_config(&props)
+    }
+
+    const SOURCE: &str = r#"
+class Foo {
+    Foo() {}
+    public void a() {}
+    public void b() {}
+    protected void c() {}
+    void d() {}
+    private void e() {}
+    private void f() {}
+
+    Runnable r = new Runnable() {
+        public void run() {}
+    };
+
+    class Inner {
+        void g() {}
+    }
+}
+"#;
+
+    #[test]
+    fn test_within_limits_no_violation() {
+        assert!(check_source(SOURCE, &MethodCount::default()).is_empty());
+    }
+
+    #[test]
+    fn test_total() {
+        let messages = check_source(SOURCE, &rule_from(&[("maxTotal", "5")]));
+        assert_eq!(
+            messages,
+            vec!["Total number of methods is 6 (max allowed is 5)."]
+        );
+    }
+
+    #[test]
+    fn test_by_scope() {
+        let rule = rule_from(&[
+            ("maxPrivate", "1"),
+            ("maxPackage", "0"),
+            ("maxProtected", "0"),
+            ("maxPublic", "1"),
+        ]);
+        let messages = check_source(SOURCE, &rule);
+        assert_eq!(
+            messages,
+            vec![
+                "Number of private methods is 2 (max allowed is 1).",
+                "Number of package methods is 1 (max allowed is 0).",
+                "Number of protected methods is 1 (max allowed is 0).",
+                "Number of public methods is 2 (max allowed is 1).",
+                "Number of package methods is 1 (max allowed is 0).",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interface_methods_are_public() {
+        let source = r#"
+interface Foo {
+    void a();
+    default void b() {}
+    static void c() {}
+}
+"#;
+        let messages = check_source(source, &rule_from(&[("maxPublic", "2")]));
+        assert_eq!(
+            messages,
+            vec!["Number of public methods is 3 (max allowed is 2)."]
+        );
+    }
+
+    #[test]
+    fn test_enum_and_constant_bodies() {
+        let source = r#"
+enum Foo {
+    A {
+        void a() {}
+        void b() {}
+    },
+    B;
+
+    void c() {}
+}
+"#;
+        // The constant's methods count towards the constant, not the enum
+        let messages = check_source(source, &rule_from(&[("maxTotal", "1")]));
+        assert_eq!(
+            messages,
+            vec!["Total number of methods is 2 (max allowed is 1)."]
+        );
+
+        let rule = rule_from(&[("maxTotal", "0"), ("tokens", "ENUM_DEF, METHOD_DEF")]);
+        assert_eq!(
+            check_source(source, &rule),
+            vec!["Total number of methods is 1 (max allowed is 0)."]
+        );
+    }
+}
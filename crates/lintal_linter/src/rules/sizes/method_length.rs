@@ -4,14 +4,16 @@
 //!
 //! Checkstyle equivalent: MethodLengthCheck
 
-use lintal_diagnostics::{Diagnostic, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 
+use super::common::line_count;
 use crate::{CheckContext, FromConfig, Properties, Rule};
 
 /// Violation: method is too long.
 #[derive(Debug, Clone)]
 pub struct MethodLengthViolation {
+    pub name: String,
     pub len: usize,
     pub max: usize,
 }
@@ -25,6 +27,18 @@ impl Violation for MethodLengthViolation {
             self.len, self.max
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("sizes", "maxLen.method"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![
+            self.len.to_string(),
+            self.max.to_string(),
+            self.name.clone(),
+        ]
+    }
 }
 
 /// Configuration for MethodLength rule.
@@ -107,33 +121,23 @@ impl Rule for MethodLength {
             return vec![];
         };
 
-        let ts_body = body.inner();
-        let start_line = ts_body.start_position().row;
-        let end_line = ts_body.end_position().row;
-
         // Line count includes opening and closing braces
-        let total_lines = end_line - start_line + 1;
-
-        let line_count = if self.count_empty {
-            total_lines
-        } else {
-            // Count non-empty lines within the body
-            let source = ctx.source();
-            let mut count = 0;
-            for line_no in start_line..=end_line {
-                if let Some(line) = source.lines().nth(line_no)
-                    && !line.trim().is_empty()
-                {
-                    count += 1;
-                }
-            }
-            count
-        };
+        let ts_body = body.inner();
+        let line_count = line_count(
+            ctx.source(),
+            ts_body.start_position().row,
+            ts_body.end_position().row,
+            self.count_empty,
+        );
 
         if line_count > self.max {
             let range = node.range();
             return vec![Diagnostic::new(
                 MethodLengthViolation {
+                    name: node
+                        .child_by_field_name("name")
+                        .map(|name| name.text().to_string())
+                        .unwrap_or_default(),
                     len: line_count,
                     max: self.max,
                 },
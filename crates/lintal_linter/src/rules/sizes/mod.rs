@@ -1,7 +1,20 @@
 //! Size-related rules (LineLength, MethodLength, etc.)
 
+mod anon_inner_length;
+mod common;
+mod executable_statement_count;
+mod file_length;
+mod lambda_body_length;
 mod line_length;
+mod method_count;
 mod method_length;
+mod parameter_number;
 
+pub use anon_inner_length::AnonInnerLength;
+pub use executable_statement_count::ExecutableStatementCount;
+pub use file_length::FileLength;
+pub use lambda_body_length::LambdaBodyLength;
 pub use line_length::LineLength;
+pub use method_count::MethodCount;
 pub use method_length::MethodLength;
+pub use parameter_number::ParameterNumber;
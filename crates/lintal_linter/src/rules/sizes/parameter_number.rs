@@ -0,0 +1,243 @@
+//! ParameterNumber rule implementation.
+//!
+//! Checks that methods and constructors do not declare too many parameters.
+//!
+//! Checkstyle equivalent: ParameterNumberCheck
+
+use lintal_diagnostics::{Diagnostic, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+
+use crate::rules::javadoc::common::has_annotation;
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: method has too many parameters.
+#[derive(Debug, Clone)]
+pub struct ParameterNumberViolation {
+    pub max: usize,
+    pub count: usize,
+}
+
+impl Violation for ParameterNumberViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        format!("More than {} parameters (found {}).", self.max, self.count)
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("sizes", "maxParam"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.max.to_string(), self.count.to_string()]
+    }
+}
+
+/// Configuration for ParameterNumber rule.
+#[derive(Debug, Clone)]
+pub struct ParameterNumber {
+    /// Maximum allowed number of parameters (default: 7).
+    pub max: usize,
+    /// Whether to skip methods annotated with `@Override` (default: false).
+    pub ignore_overridden_methods: bool,
+    /// Whether to check METHOD_DEF tokens.
+    pub check_methods: bool,
+    /// Whether to check CTOR_DEF tokens.
+    pub check_constructors: bool,
+}
+
+const RELEVANT_KINDS: &[&str] = &["method_declaration", "constructor_declaration"];
+
+impl Default for ParameterNumber {
+    fn default() -> Self {
+        Self {
+            max: 7,
+            ignore_overridden_methods: false,
+            check_methods: true,
+            check_constructors: true,
+        }
+    }
+}
+
+impl FromConfig for ParameterNumber {
+    const MODULE_NAME: &'static str = "ParameterNumber";
+
+    fn from_config(properties: &Properties) -> Self {
+        let max = properties
+            .get("max")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(7);
+
+        let ignore_overridden_methods = properties
+            .get("ignoreOverriddenMethods")
+            .map(|s| *s == "true")
+            .unwrap_or(false);
+
+        // Parse tokens property to determine what to check
+        let (check_methods, check_constructors) = if let Some(tokens) = properties.get("tokens") {
+            let has_method = tokens.contains("METHOD_DEF");
+            let has_ctor = tokens.contains("CTOR_DEF");
+            (has_method, has_ctor)
+        } else {
+            (true, true)
+        };
+
+        Self {
+            max,
+            ignore_overridden_methods,
+            check_methods,
+            check_constructors,
+        }
+    }
+}
+
+impl Rule for ParameterNumber {
+    fn name(&self) -> &'static str {
+        "ParameterNumber"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        match node.kind() {
+            "method_declaration" if self.check_methods => {}
+            "constructor_declaration" if self.check_constructors => {}
+            _ => return vec![],
+        }
+
+        let Some(parameters) = node.child_by_field_name("parameters") else {
+            return vec![];
+        };
+
+        // A receiver parameter such as `Foo this` isn't counted
+        let count = parameters
+            .children()
+            .filter(|p| matches!(p.kind(), "formal_parameter" | "spread_parameter"))
+            .count();
+        if count <= self.max {
+            return vec![];
+        }
+
+        if self.ignore_overridden_methods
+            && has_annotation(
+                node,
+                &["Override".to_string(), "java.lang.Override".to_string()],
+            )
+        {
+            return vec![];
+        }
+
+        let Some(name) = node.child_by_field_name("name") else {
+            return vec![];
+        };
+
+        vec![Diagnostic::new(
+            ParameterNumberViolation {
+                max: self.max,
+                count,
+            },
+            name.range(),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+    use lintal_source_file::{LineIndex, SourceCode};
+
+    fn check_source(source: &str, rule: &ParameterNumber) -> Vec<usize> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let line_index = LineIndex::from_source_text(source);
+        let source_code = SourceCode::new(source, &line_index);
+
+        let mut violations = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            for d in rule.check(&ctx, &node) {
+                let loc = source_code.line_column(d.range.start());
+                violations.push(loc.line.get());
+            }
+        }
+        violations
+    }
+
+    fn rule_with_max(max: usize) -> ParameterNumber {
+        ParameterNumber {
+            max,
+            ..ParameterNumber::default()
+        }
+    }
+
+    #[test]
+    fn test_within_limit_no_violation() {
+        let source = r#"
+class Foo {
+    Foo(int a, int b) {}
+    void method(int a, String... rest) {}
+}
+"#;
+        assert!(check_source(source, &rule_with_max(2)).is_empty());
+    }
+
+    #[test]
+    fn test_too_many_parameters() {
+        let source = r#"
+class Foo {
+    Foo(int a, int b, int c) {}
+    void method(int a, int b, String... rest) {}
+}
+"#;
+        assert_eq!(check_source(source, &rule_with_max(2)), vec![3, 4]);
+    }
+
+    #[test]
+    fn test_receiver_parameter_not_counted() {
+        let source = r#"
+class Foo {
+    void method(Foo this, int a, int b) {}
+}
+"#;
+        assert!(check_source(source, &rule_with_max(2)).is_empty());
+    }
+
+    #[test]
+    fn test_ignore_overridden_methods() {
+        let source = r#"
+class Foo extends Bar {
+    @Override
+    void method(int a, int b, int c) {}
+
+    @java.lang.Override
+    void other(int a, int b, int c) {}
+}
+"#;
+        assert_eq!(check_source(source, &rule_with_max(2)), vec![4, 7]);
+
+        let rule = ParameterNumber {
+            ignore_overridden_methods: true,
+            ..rule_with_max(2)
+        };
+        assert!(check_source(source, &rule).is_empty());
+    }
+
+    #[test]
+    fn test_tokens() {
+        let source = r#"
+class Foo {
+    Foo(int a, int b, int c) {}
+    void method(int a, int b, int c) {}
+}
+"#;
+        let mut props = Properties::new();
+        props.insert("max", "2");
+        props.insert("tokens", "METHOD_DEF");
+        let rule = ParameterNumber::from_config(&props);
+        assert_eq!(check_source(source, &rule), vec![4]);
+    }
+}
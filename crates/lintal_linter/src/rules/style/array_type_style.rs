@@ -21,7 +21,7 @@
 //! byte[] getData() { ... }  // ok
 //! ```
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::{TextRange, TextSize};
 
@@ -37,6 +37,10 @@ impl Violation for ArrayTypeStyleViolation {
     fn message(&self) -> String {
         "Array brackets at illegal position.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("", "array.type.style"))
+    }
 }
 
 /// Configuration for ArrayTypeStyle rule.
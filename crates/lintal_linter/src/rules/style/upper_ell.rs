@@ -12,11 +12,10 @@
 //! long good = 123L;  // ok
 //! ```
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
-use lintal_java_cst::CstNode;
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_text_size::{TextRange, TextSize};
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{CheckContext, FromConfig, Properties, QueryCaptures, QueryRule, Rule};
 
 /// Violation: long literal uses lowercase 'l' suffix.
 #[derive(Debug, Clone)]
@@ -28,6 +27,10 @@ impl Violation for UpperEllViolation {
     fn message(&self) -> String {
         "Should use uppercase 'L'.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("", "upperEll"))
+    }
 }
 
 /// Configuration for UpperEll rule.
@@ -36,12 +39,16 @@ impl Violation for UpperEllViolation {
 #[derive(Debug, Clone, Default)]
 pub struct UpperEll;
 
-const RELEVANT_KINDS: &[&str] = &[
-    "decimal_integer_literal",
-    "hex_integer_literal",
-    "octal_integer_literal",
-    "binary_integer_literal",
-];
+/// Integer literals ending in a lowercase `l`.
+const QUERY: &str = r#"
+[
+  (decimal_integer_literal)
+  (hex_integer_literal)
+  (octal_integer_literal)
+  (binary_integer_literal)
+] @literal
+(#match? @literal "l$")
+"#;
 
 impl FromConfig for UpperEll {
     const MODULE_NAME: &'static str = "UpperEll";
@@ -56,58 +63,43 @@ impl Rule for UpperEll {
         "UpperEll"
     }
 
-    fn relevant_kinds(&self) -> &'static [&'static str] {
-        RELEVANT_KINDS
+    fn as_query_rule(&self) -> Option<&dyn QueryRule> {
+        Some(self)
     }
+}
 
-    fn check(&self, _ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
-        // Look for integer literals (decimal, hex, octal, binary)
-        let kind = node.kind();
-        if kind != "decimal_integer_literal"
-            && kind != "hex_integer_literal"
-            && kind != "octal_integer_literal"
-            && kind != "binary_integer_literal"
-        {
-            return vec![];
-        }
-
-        let text = node.text();
-        let range = node.range();
-
-        // Check if the literal ends with lowercase 'l'
-        if text.ends_with('l') {
-            // Create a fix that replaces the 'l' with 'L'
-            let l_start = range.end() - TextSize::from(1u32);
-            let fix_range = TextRange::new(l_start, range.end());
+impl QueryRule for UpperEll {
+    fn query(&self) -> &str {
+        QUERY
+    }
 
-            let diagnostic = Diagnostic::new(UpperEllViolation, range).with_fix(Fix::safe_edit(
-                Edit::range_replacement("L".to_string(), fix_range),
-            ));
+    fn check_match(&self, _ctx: &CheckContext, captures: &QueryCaptures) -> Vec<Diagnostic> {
+        let Some(literal) = captures.get("literal") else {
+            return vec![];
+        };
+        let range = literal.range();
 
-            return vec![diagnostic];
-        }
+        // Replace the 'l' with 'L'
+        let l_start = range.end() - TextSize::from(1u32);
+        let fix_range = TextRange::new(l_start, range.end());
+        let fix = Fix::safe_edit(Edit::range_replacement("L".to_string(), fix_range));
 
-        vec![]
+        vec![Diagnostic::new(UpperEllViolation, range).with_fix(fix)]
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use lintal_java_cst::TreeWalker;
-    use lintal_java_parser::JavaParser;
+    use crate::lint_source;
 
     fn check_source(source: &str) -> Vec<Diagnostic> {
-        let mut parser = JavaParser::new();
-        let result = parser.parse(source).unwrap();
-        let ctx = CheckContext::new(source);
-        let rule = UpperEll;
-
-        let mut diagnostics = vec![];
-        for node in TreeWalker::new(result.tree.root_node(), source) {
-            diagnostics.extend(rule.check(&ctx, &node));
-        }
-        diagnostics
+        let rules: Vec<Box<dyn Rule>> = vec![Box::new(UpperEll)];
+        lint_source(source, &rules)
+            .unwrap()
+            .into_iter()
+            .map(|d| d.diagnostic)
+            .collect()
     }
 
     #[test]
@@ -1,6 +1,6 @@
 //! Shared helpers for whitespace rules.
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::{TextRange, TextSize};
 
@@ -109,6 +109,14 @@ impl Violation for NotFollowed {
     fn message(&self) -> String {
         format!("'{}' is not followed by whitespace", self.token)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "ws.notFollowed"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.token.clone()]
+    }
 }
 
 /// Violation: token is not preceded by whitespace.
@@ -123,6 +131,14 @@ impl Violation for NotPreceded {
     fn message(&self) -> String {
         format!("'{}' is not preceded by whitespace", self.token)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "ws.notPreceded"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.token.clone()]
+    }
 }
 
 /// Violation: token is followed by whitespace (when it shouldn't be).
@@ -137,6 +153,14 @@ impl Violation for Followed {
     fn message(&self) -> String {
         format!("'{}' is followed by whitespace", self.token)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "ws.followed"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.token.clone()]
+    }
 }
 
 /// Violation: token is preceded by whitespace (when it shouldn't be).
@@ -151,6 +175,14 @@ impl Violation for Preceded {
     fn message(&self) -> String {
         format!("'{}' is preceded by whitespace", self.token)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "ws.preceded"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.token.clone()]
+    }
 }
 
 // ============================================================================
@@ -6,7 +6,7 @@
 
 use std::collections::HashSet;
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::{TextRange, TextSize};
 
@@ -24,6 +24,14 @@ impl Violation for ShouldBeSeparated {
     fn message(&self) -> String {
         format!("'{}' should be separated from previous line.", self.element)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "empty.line.separator"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.element.clone()]
+    }
 }
 
 /// Violation: element has too many empty lines before it.
@@ -38,6 +46,17 @@ impl Violation for TooManyEmptyLines {
     fn message(&self) -> String {
         format!("'{}' has more than 1 empty lines before.", self.element)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new(
+            "whitespace",
+            "empty.line.separator.multiple.lines",
+        ))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.element.clone()]
+    }
 }
 
 /// Violation: closing brace has too many empty lines after it.
@@ -50,6 +69,17 @@ impl Violation for TooManyEmptyLinesAfter {
     fn message(&self) -> String {
         "'}' has more than 1 empty lines after.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new(
+            "whitespace",
+            "empty.line.separator.multiple.lines.after",
+        ))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec!["}".to_string()]
+    }
 }
 
 /// Violation: too many empty lines inside a class member.
@@ -62,6 +92,13 @@ impl Violation for TooManyEmptyLinesInside {
     fn message(&self) -> String {
         "There is more than 1 empty line after this line.".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new(
+            "whitespace",
+            "empty.line.separator.multiple.lines.inside",
+        ))
+    }
 }
 
 /// Violation: comment has too many empty lines before it.
@@ -79,6 +116,17 @@ impl Violation for CommentTooManyEmptyLines {
             self.comment_start
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new(
+            "whitespace",
+            "empty.line.separator.multiple.lines",
+        ))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.comment_start.clone()]
+    }
 }
 
 /// Token types that can be checked.
@@ -767,6 +815,18 @@ impl EmptyLineSeparator {
                 return; // Don't recurse - array initializers are checked separately
             }
 
+            // A string literal is one token: the blank lines inside a text
+            // block are part of the string, not empty lines of the block
+            if node.kind() == "string_literal" {
+                for row in node.start_position().row..=node.end_position().row {
+                    content_lines.insert(row);
+                    code_lines.insert(row);
+                    non_brace_code_lines.insert(row);
+                    brace_only_lines.remove(&row);
+                }
+                return;
+            }
+
             let is_comment =
                 node.kind() == "line_comment" || node.kind() == "block_comment" || node.is_extra();
             let is_brace = node.kind() == "{" || node.kind() == "}";
@@ -1798,6 +1858,26 @@ class Test {
         );
     }
 
+    #[test]
+    fn test_package_and_imports_need_blank_lines() {
+        let source = r#"package com.example;
+import java.util.List;
+import java.util.Map;
+class Test {}
+"#;
+        let diagnostics = check_source(source);
+        assert_eq!(
+            diagnostics.len(),
+            2,
+            "first import and class should need blank lines"
+        );
+        assert!(diagnostics[0].kind.body.contains("'IMPORT'"));
+        assert!(diagnostics[1].kind.body.contains("'CLASS_DEF'"));
+        for d in &diagnostics {
+            assert!(d.fix.is_some(), "separation violations should be fixable");
+        }
+    }
+
     #[test]
     fn test_constructor_needs_blank_line() {
         let source = r#"
@@ -2117,4 +2197,29 @@ class Test {
             "should detect multiple empty lines inside nested try block"
         );
     }
+
+    #[test]
+    fn test_empty_lines_inside_text_block() {
+        let source = r#"
+class Test {
+    void method() {
+        String s = """
+            a
+
+
+            b
+            """;
+    }
+}
+"#;
+        let rule = EmptyLineSeparator {
+            allow_multiple_empty_lines_inside_class_members: false,
+            ..Default::default()
+        };
+        let diagnostics = check_source_with_config(source, rule);
+        assert!(
+            diagnostics.is_empty(),
+            "blank lines of a text block are part of the string: {diagnostics:?}"
+        );
+    }
 }
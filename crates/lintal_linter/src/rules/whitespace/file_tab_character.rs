@@ -5,11 +5,13 @@
 //!
 //! Checkstyle equivalent: FileTabCharacterCheck
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
-use lintal_java_cst::CstNode;
+use std::path::Path;
+
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
+use lintal_source_file::{LineIndex, PositionEncoding, SourceCode};
 use lintal_text_size::{TextRange, TextSize};
 
-use crate::{CheckContext, FromConfig, Properties, Rule};
+use crate::{FileRule, FromConfig, Properties, Rule};
 
 /// Violation: file contains tab character.
 #[derive(Debug, Clone)]
@@ -21,6 +23,10 @@ impl Violation for FileContainsTabViolation {
     fn message(&self) -> String {
         "File contains tab characters (this is the first instance)".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "file.containsTab"))
+    }
 }
 
 /// Violation: line contains tab character (when eachLine=true).
@@ -33,6 +39,10 @@ impl Violation for LineContainsTabViolation {
     fn message(&self) -> String {
         "Line contains a tab character".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "containsTab"))
+    }
 }
 
 /// Configuration for FileTabCharacter rule.
@@ -44,8 +54,6 @@ pub struct FileTabCharacter {
     pub tab_width: usize,
 }
 
-const RELEVANT_KINDS: &[&str] = &["program"];
-
 impl Default for FileTabCharacter {
     fn default() -> Self {
         Self {
@@ -81,18 +89,23 @@ impl Rule for FileTabCharacter {
         "FileTabCharacter"
     }
 
-    fn relevant_kinds(&self) -> &'static [&'static str] {
-        RELEVANT_KINDS
+    fn column_encoding(&self) -> PositionEncoding {
+        PositionEncoding::Utf16
     }
 
-    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
-        // Only check at the root node to avoid scanning the file multiple times
-        if node.parent().is_some() {
-            return vec![];
-        }
+    fn as_file_rule(&self) -> Option<&dyn FileRule> {
+        Some(self)
+    }
+}
 
-        let source = ctx.source();
-        let source_code = ctx.source_code();
+impl FileRule for FileTabCharacter {
+    fn check_file(
+        &self,
+        _path: Option<&Path>,
+        source: &str,
+        line_index: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        let source_code = SourceCode::new(source, line_index);
 
         let mut diagnostics = vec![];
 
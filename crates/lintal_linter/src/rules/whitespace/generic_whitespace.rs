@@ -0,0 +1,385 @@
+//! GenericWhitespace rule implementation.
+//!
+//! Checks that the whitespace around the generic tokens `<` and `>` is correct
+//! to the typical convention, e.g. `List<String>` and `public <T> void foo()`.
+//!
+//! Checkstyle equivalent: GenericWhitespaceCheck
+
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+use lintal_text_size::{TextRange, TextSize};
+
+use crate::rules::whitespace::common::{
+    Followed, NotPreceded, diag_followed, diag_not_preceded, diag_preceded, whitespace_range_before,
+};
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: `>` is followed by something other than whitespace or a separator.
+#[derive(Debug, Clone)]
+pub struct IllegalFollow {
+    pub token: String,
+}
+
+impl Violation for IllegalFollow {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Always;
+
+    fn message(&self) -> String {
+        format!("'{}' should be followed by whitespace", self.token)
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "ws.illegalFollow"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.token.clone()]
+    }
+}
+
+/// Configuration for GenericWhitespace rule.
+#[derive(Debug, Clone, Default)]
+pub struct GenericWhitespace;
+
+const RELEVANT_KINDS: &[&str] = &["<", ">"];
+
+impl FromConfig for GenericWhitespace {
+    const MODULE_NAME: &'static str = "GenericWhitespace";
+
+    fn from_config(_properties: &Properties) -> Self {
+        Self
+    }
+}
+
+impl Rule for GenericWhitespace {
+    fn name(&self) -> &'static str {
+        "GenericWhitespace"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        // `<` and `>` are also comparison operators
+        let Some(generic) = node
+            .inner()
+            .parent()
+            .filter(|p| matches!(p.kind(), "type_arguments" | "type_parameters"))
+        else {
+            return vec![];
+        };
+
+        match node.kind() {
+            "<" => check_start(ctx.source(), node, generic),
+            ">" => check_end(ctx.source(), node, generic),
+            _ => vec![],
+        }
+    }
+}
+
+/// Check the whitespace around a `<`.
+fn check_start(source: &str, node: &CstNode, generic: tree_sitter::Node) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let range = node.range();
+    let (before, after) = line_around(source, range);
+
+    // A generic method or constructor declares its type parameters apart
+    // from the modifiers, as in `public <T> void foo()`, and so does a
+    // constructor call its type arguments, as in `new <T>Foo()`
+    if !before.is_empty() {
+        if requires_whitespace_before(generic) {
+            if !before.ends_with(char::is_whitespace) {
+                diagnostics.push(diag_not_preceded(node));
+            }
+        } else if let Some(ws) = whitespace_range_before_on_line(source, range.start(), before) {
+            diagnostics.push(diag_preceded(node, ws));
+        }
+    }
+
+    if let Some(ws) = whitespace_range_after_on_line(range.end(), after) {
+        diagnostics.push(diag_followed(node, ws));
+    }
+
+    diagnostics
+}
+
+/// Check the whitespace around a `>`.
+fn check_end(source: &str, node: &CstNode, generic: tree_sitter::Node) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let range = node.range();
+    let (before, after) = line_around(source, range);
+
+    if let Some(ws) = whitespace_range_before_on_line(source, range.start(), before) {
+        diagnostics.push(diag_preceded(node, ws));
+    }
+
+    let Some(char_after) = after.chars().next() else {
+        return diagnostics;
+    };
+
+    if generic_depth(generic) == 1 {
+        if is_before_invocation(generic) {
+            // `Collections.<String>emptyList()`, `new ArrayList<>()`
+            if let Some(ws) = whitespace_range_after_on_line(range.end(), after) {
+                diagnostics.push(diag_followed(node, ws));
+            }
+        } else if !char_after.is_whitespace()
+            && !matches!(char_after, ')' | ',' | '[' | '.' | ':' | ';')
+        {
+            diagnostics.push(
+                Diagnostic::new(
+                    IllegalFollow {
+                        token: node.text().to_string(),
+                    },
+                    range,
+                )
+                .with_fix(Fix::safe_edit(Edit::insertion(
+                    " ".to_string(),
+                    range.end(),
+                ))),
+            );
+        }
+    } else if let Some(amp) = after.find('&')
+        && after[..amp].chars().all(char::is_whitespace)
+    {
+        // Nested in a bound such as `<E extends Enum<E> & IntEnum>`, one
+        // space separates the `>` from the `&`
+        if amp == 0 {
+            diagnostics.push(
+                Diagnostic::new(
+                    NotPreceded {
+                        token: "&".to_string(),
+                    },
+                    range,
+                )
+                .with_fix(Fix::safe_edit(Edit::insertion(
+                    " ".to_string(),
+                    range.end(),
+                ))),
+            );
+        } else if after[..amp].chars().count() != 1 {
+            let end = range.end() + TextSize::from(amp as u32);
+            diagnostics.push(
+                Diagnostic::new(
+                    Followed {
+                        token: node.text().to_string(),
+                    },
+                    range,
+                )
+                .with_fix(Fix::safe_edit(Edit::replacement(
+                    " ".to_string(),
+                    range.end(),
+                    end,
+                ))),
+            );
+        }
+    } else if after.starts_with(' ')
+        && let Some(ws) = whitespace_range_after_on_line(range.end(), after)
+    {
+        // Nested, so only another `>` or a `,` may follow
+        diagnostics.push(diag_followed(node, ws));
+    }
+
+    diagnostics
+}
+
+/// The text of the token's line before and after it, without the line break.
+fn line_around(source: &str, range: TextRange) -> (&str, &str) {
+    let start = usize::from(range.start());
+    let end = usize::from(range.end());
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[end..].find('\n').map_or(source.len(), |i| end + i);
+    (
+        &source[line_start..start],
+        source[end..line_end].trim_end_matches('\r'),
+    )
+}
+
+/// The whitespace right before a token, unless the token starts its line.
+fn whitespace_range_before_on_line(source: &str, pos: TextSize, before: &str) -> Option<TextRange> {
+    if before.trim().is_empty() {
+        return None;
+    }
+    whitespace_range_before(source, pos)
+}
+
+/// The spaces and tabs right after a token, up to the end of its line.
+fn whitespace_range_after_on_line(pos: TextSize, after: &str) -> Option<TextRange> {
+    let len = after.len() - after.trim_start_matches([' ', '\t']).len();
+    (len > 0).then(|| TextRange::new(pos, pos + TextSize::from(len as u32)))
+}
+
+/// Whether the `<` must be preceded by whitespace: type parameters of a
+/// method or constructor, and type arguments of a constructor call.
+fn requires_whitespace_before(generic: tree_sitter::Node) -> bool {
+    let parent_kind = generic.parent().map(|p| p.kind());
+    match generic.kind() {
+        "type_parameters" => matches!(
+            parent_kind,
+            Some("method_declaration" | "constructor_declaration")
+        ),
+        _ => parent_kind == Some("object_creation_expression"),
+    }
+}
+
+/// Whether the generic is followed by the arguments of a method call,
+/// method reference, constructor call or record, where the `>` mustn't be
+/// followed by whitespace instead of needing it.
+fn is_before_invocation(generic: tree_sitter::Node) -> bool {
+    let parent = generic.parent();
+    match (generic.kind(), parent.map(|p| p.kind())) {
+        ("type_parameters", parent_kind) => parent_kind == Some("record_declaration"),
+        (_, Some("method_invocation" | "method_reference" | "object_creation_expression")) => true,
+        (_, Some("generic_type")) => parent.and_then(|p| p.parent()).is_some_and(|p| {
+            matches!(
+                p.kind(),
+                "object_creation_expression"
+                    | "array_creation_expression"
+                    | "method_reference"
+                    | "record_pattern"
+            )
+        }),
+        _ => false,
+    }
+}
+
+/// How many generics the generic is nested in, itself included.
+fn generic_depth(generic: tree_sitter::Node) -> usize {
+    std::iter::successors(Some(generic), |n| n.parent())
+        .filter(|n| matches!(n.kind(), "type_arguments" | "type_parameters"))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::apply_fixes;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str) -> Vec<Diagnostic> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        let rule = GenericWhitespace;
+
+        let mut diagnostics = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            diagnostics.extend(rule.check(&ctx, &node));
+        }
+        diagnostics
+    }
+
+    fn fix_source(source: &str) -> String {
+        let diagnostics = check_source(source);
+        apply_fixes(source, diagnostics.iter().filter_map(|d| d.fix.as_ref())).code
+    }
+
+    #[test]
+    fn test_valid_generics() {
+        let source = r#"
+class Foo<T extends Comparable<T>, E extends Enum<E> & Runnable> {
+    Map<String, List<Integer>> map = new HashMap<>();
+    List<String>[] lists;
+
+    public <K> Foo(K key) {}
+
+    public static <V> List<V> of(V value) {
+        List<V> list = Collections.<V>emptyList();
+        Function<String, Integer> f = Integer::valueOf;
+        return (List<V>) list;
+    }
+}
+"#;
+        let diagnostics = check_source(source);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    }
+
+    #[test]
+    fn test_comparison_not_checked() {
+        let source = "class Foo { boolean b = 1 < 2 && 3 > 4; }";
+        assert!(check_source(source).is_empty());
+    }
+
+    #[test]
+    fn test_preceded_and_followed() {
+        let diagnostics = check_source("class Foo { List < String > list; }");
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.kind.body.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "'<' is preceded by whitespace",
+                "'<' is followed by whitespace",
+                "'>' is preceded by whitespace",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_method_type_parameters_not_preceded() {
+        let diagnostics = check_source("class Foo { public<T> void foo() {} }");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind.body,
+            "'<' is not preceded by whitespace"
+        );
+    }
+
+    #[test]
+    fn test_illegal_follow() {
+        let diagnostics = check_source("class Foo { public <T>void foo() {} }");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind.body,
+            "'>' should be followed by whitespace"
+        );
+    }
+
+    #[test]
+    fn test_followed_before_invocation() {
+        let diagnostics = check_source("class Foo { Object o = new ArrayList<> (); }");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind.body, "'>' is followed by whitespace");
+    }
+
+    #[test]
+    fn test_nested_followed_by_ampersand() {
+        let diagnostics = check_source("class Foo<E extends Enum<E>& Runnable> {}");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind.body,
+            "'&' is not preceded by whitespace"
+        );
+    }
+
+    #[test]
+    fn test_line_wrapped_generics_ok() {
+        let source = r#"
+class Foo {
+    Map<
+        String,
+        Integer> map;
+}
+"#;
+        assert!(check_source(source).is_empty());
+    }
+
+    #[test]
+    fn test_fixes() {
+        let source = r#"
+class Foo<E extends Enum<E>   & Runnable> {
+    Map < String, List<Integer> > map = new HashMap<> ();
+
+    public<T>void foo() {}
+}
+"#;
+        let expected = r#"
+class Foo<E extends Enum<E> & Runnable> {
+    Map<String, List<Integer>> map = new HashMap<>();
+
+    public <T> void foo() {}
+}
+"#;
+        assert_eq!(fix_source(source), expected);
+    }
+}
@@ -3,7 +3,7 @@
 //! Provides the common functionality used by all indentation handlers,
 //! porting checkstyle's AbstractExpressionHandler.
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::{TextRange, TextSize};
 use std::cell::RefCell;
@@ -31,6 +31,22 @@ impl Violation for IndentationError {
             self.element, self.actual, self.expected
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(if is_multi_level(&self.expected) {
+            MessageKey::new("indentation", "indentation.error.multi")
+        } else {
+            MessageKey::new("indentation", "indentation.error")
+        })
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![
+            self.element.clone(),
+            self.actual.to_string(),
+            self.expected.clone(),
+        ]
+    }
 }
 
 /// Violation for incorrect child indentation.
@@ -53,6 +69,28 @@ impl Violation for IndentationChildError {
             self.parent, self.actual, self.expected
         )
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(if is_multi_level(&self.expected) {
+            MessageKey::new("indentation", "indentation.child.error.multi")
+        } else {
+            MessageKey::new("indentation", "indentation.child.error")
+        })
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![
+            self.parent.clone(),
+            self.actual.to_string(),
+            self.expected.clone(),
+        ]
+    }
+}
+
+/// Whether an expected level, as formatted by [`IndentLevel`], lists several
+/// acceptable levels; checkstyle words those messages differently.
+fn is_multi_level(expected: &str) -> bool {
+    expected.contains(',')
 }
 
 /// Context for indentation checking, shared across all handlers.
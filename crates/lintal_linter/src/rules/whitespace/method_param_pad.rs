@@ -5,7 +5,7 @@
 
 use std::collections::HashSet;
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::TextRange;
 
@@ -71,6 +71,14 @@ impl Violation for WsPreceded {
     fn message(&self) -> String {
         format!("'{}' is preceded by whitespace", self.token)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "ws.preceded"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.token.clone()]
+    }
 }
 
 /// Violation: '(' is not preceded by whitespace (when option=space).
@@ -85,6 +93,14 @@ impl Violation for WsNotPreceded {
     fn message(&self) -> String {
         format!("'{}' is not preceded by whitespace", self.token)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "ws.notPreceded"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.token.clone()]
+    }
 }
 
 /// Violation: '(' should be on the previous line (when allowLineBreaks=false).
@@ -99,6 +115,14 @@ impl Violation for LinePrevious {
     fn message(&self) -> String {
         format!("'{}' should be on the previous line", self.token)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "line.previous"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.token.clone()]
+    }
 }
 
 /// Configuration for MethodParamPad rule.
@@ -4,12 +4,14 @@ pub mod common;
 pub mod empty_for_initializer_pad;
 pub mod empty_line_separator;
 pub mod file_tab_character;
+pub mod generic_whitespace;
 pub mod indentation;
 pub mod method_param_pad;
 pub mod no_whitespace_after;
 pub mod no_whitespace_before;
 pub mod operator_wrap;
 pub mod paren_pad;
+pub mod separator_wrap;
 pub mod single_space_separator;
 pub mod typecast_paren_pad;
 pub mod whitespace_after;
@@ -18,12 +20,14 @@ mod whitespace_around;
 pub use empty_for_initializer_pad::EmptyForInitializerPad;
 pub use empty_line_separator::EmptyLineSeparator;
 pub use file_tab_character::FileTabCharacter;
+pub use generic_whitespace::GenericWhitespace;
 pub use indentation::Indentation;
 pub use method_param_pad::MethodParamPad;
 pub use no_whitespace_after::NoWhitespaceAfter;
 pub use no_whitespace_before::NoWhitespaceBefore;
 pub use operator_wrap::OperatorWrap;
 pub use paren_pad::ParenPad;
+pub use separator_wrap::SeparatorWrap;
 pub use single_space_separator::SingleSpaceSeparator;
 pub use typecast_paren_pad::TypecastParenPad;
 pub use whitespace_after::WhitespaceAfter;
@@ -4,7 +4,7 @@
 //!
 //! Checkstyle equivalent: OperatorWrapCheck
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::{TextRange, TextSize};
 use std::collections::HashSet;
@@ -23,6 +23,14 @@ impl Violation for OperatorShouldBeOnNewLine {
     fn message(&self) -> String {
         format!("'{}' should be on a new line.", self.operator)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "line.new"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.operator.clone()]
+    }
 }
 
 /// Violation: operator should be on the previous line.
@@ -37,6 +45,14 @@ impl Violation for OperatorShouldBeOnPrevLine {
     fn message(&self) -> String {
         format!("'{}' should be on the previous line.", self.operator)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "line.previous"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.operator.clone()]
+    }
 }
 
 /// Option for where operators should be placed.
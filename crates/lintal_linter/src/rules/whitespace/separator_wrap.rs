@@ -0,0 +1,529 @@
+//! SeparatorWrap rule implementation.
+//!
+//! Checks that separators such as `.` and `,` are on the correct line when a
+//! statement is wrapped.
+//!
+//! Checkstyle equivalent: SeparatorWrapCheck
+
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
+use lintal_java_cst::CstNode;
+use lintal_text_size::TextSize;
+use std::collections::HashSet;
+
+use crate::{CheckContext, FromConfig, Properties, Rule};
+
+/// Violation: separator should be on a new line.
+#[derive(Debug, Clone)]
+pub struct SeparatorShouldBeOnNewLine {
+    pub separator: String,
+}
+
+impl Violation for SeparatorShouldBeOnNewLine {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
+
+    fn message(&self) -> String {
+        format!("'{}' should be on a new line.", self.separator)
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "line.new"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.separator.clone()]
+    }
+}
+
+/// Violation: separator should be on the previous line.
+#[derive(Debug, Clone)]
+pub struct SeparatorShouldBeOnPrevLine {
+    pub separator: String,
+}
+
+impl Violation for SeparatorShouldBeOnPrevLine {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
+
+    fn message(&self) -> String {
+        format!("'{}' should be on the previous line.", self.separator)
+    }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "line.previous"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.separator.clone()]
+    }
+}
+
+/// Option for where separators should be placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SeparatorWrapOption {
+    /// Separator should be at end of line (default).
+    #[default]
+    Eol,
+    /// Separator should be on a new line.
+    Nl,
+}
+
+/// Tokens that SeparatorWrap can check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SeparatorWrapToken {
+    Dot,
+    Comma,
+    Semi,
+    Ellipsis,
+    At,
+    Lparen,
+    Rparen,
+    ArrayDeclarator,
+    Rbrack,
+    MethodRef,
+}
+
+impl SeparatorWrapToken {
+    /// Convert from checkstyle token name to our enum.
+    pub fn from_checkstyle_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "DOT" => Some(Self::Dot),
+            "COMMA" => Some(Self::Comma),
+            "SEMI" => Some(Self::Semi),
+            "ELLIPSIS" => Some(Self::Ellipsis),
+            "AT" => Some(Self::At),
+            "LPAREN" => Some(Self::Lparen),
+            "RPAREN" => Some(Self::Rparen),
+            "ARRAY_DECLARATOR" => Some(Self::ArrayDeclarator),
+            "RBRACK" => Some(Self::Rbrack),
+            "METHOD_REF" => Some(Self::MethodRef),
+            _ => None,
+        }
+    }
+
+    /// Get the token for a separator node.
+    ///
+    /// Returns `None` for separators checkstyle gives another token type:
+    /// the `(` of a method call (METHOD_CALL) or cast (TYPECAST), and the
+    /// `[` of an array access (INDEX_OP).
+    fn from_node(node: &tree_sitter::Node) -> Option<Self> {
+        let parent = node.parent();
+        let parent_kind = parent.map(|p| p.kind());
+
+        match node.kind() {
+            "." => Some(Self::Dot),
+            "," => Some(Self::Comma),
+            ";" => Some(Self::Semi),
+            "..." => Some(Self::Ellipsis),
+            "@" => Some(Self::At),
+            "::" => Some(Self::MethodRef),
+            ")" => Some(Self::Rparen),
+            "]" => Some(Self::Rbrack),
+            "(" => {
+                let is_method_call = parent_kind == Some("argument_list")
+                    && parent
+                        .and_then(|p| p.parent())
+                        .is_some_and(|gp| gp.kind() == "method_invocation");
+                if is_method_call || parent_kind == Some("cast_expression") {
+                    None
+                } else {
+                    Some(Self::Lparen)
+                }
+            }
+            "[" => matches!(parent_kind, Some("dimensions" | "dimensions_expr"))
+                .then_some(Self::ArrayDeclarator),
+            _ => None,
+        }
+    }
+
+    /// Whether a fix moving the separator onto a new line puts a space after it.
+    fn spaced(self) -> bool {
+        matches!(self, Self::Comma | Self::Semi)
+    }
+
+    /// Default tokens as per checkstyle documentation.
+    pub fn default_tokens() -> HashSet<Self> {
+        [Self::Dot, Self::Comma].into_iter().collect()
+    }
+}
+
+/// Configuration for SeparatorWrap rule.
+#[derive(Debug, Clone)]
+pub struct SeparatorWrap {
+    pub option: SeparatorWrapOption,
+    pub tokens: HashSet<SeparatorWrapToken>,
+}
+
+impl Default for SeparatorWrap {
+    fn default() -> Self {
+        Self {
+            option: SeparatorWrapOption::Eol,
+            tokens: SeparatorWrapToken::default_tokens(),
+        }
+    }
+}
+
+impl FromConfig for SeparatorWrap {
+    const MODULE_NAME: &'static str = "SeparatorWrap";
+
+    fn from_config(properties: &Properties) -> Self {
+        let option = properties
+            .get("option")
+            .map(|v| match v.trim().to_lowercase().as_str() {
+                "nl" => SeparatorWrapOption::Nl,
+                _ => SeparatorWrapOption::Eol,
+            })
+            .unwrap_or_default();
+
+        let tokens = if let Some(tokens_str) = properties.get("tokens") {
+            let tokens: HashSet<_> = tokens_str
+                .split(',')
+                .filter_map(SeparatorWrapToken::from_checkstyle_name)
+                .collect();
+            if tokens.is_empty() {
+                SeparatorWrapToken::default_tokens()
+            } else {
+                tokens
+            }
+        } else {
+            SeparatorWrapToken::default_tokens()
+        };
+
+        Self { option, tokens }
+    }
+}
+
+/// Node kinds that SeparatorWrap cares about.
+const RELEVANT_KINDS: &[&str] = &[".", ",", ";", "...", "@", "(", ")", "[", "]", "::"];
+
+impl Rule for SeparatorWrap {
+    fn name(&self) -> &'static str {
+        "SeparatorWrap"
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        RELEVANT_KINDS
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        let separator = node.inner();
+        let Some(token) = SeparatorWrapToken::from_node(&separator) else {
+            return vec![];
+        };
+        if !self.tokens.contains(&token) {
+            return vec![];
+        }
+
+        let source = ctx.source();
+        let start = separator.start_byte();
+        let end = separator.end_byte();
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[end..].find('\n').map_or(source.len(), |i| end + i);
+        let text = node.text();
+
+        // Like checkstyle, only the line the separator is on matters:
+        // nl wants nothing after it, eol nothing before it
+        match self.option {
+            SeparatorWrapOption::Nl if source[end..line_end].trim().is_empty() => {
+                let mut diagnostic = Diagnostic::new(
+                    SeparatorShouldBeOnNewLine {
+                        separator: text.to_string(),
+                    },
+                    node.range(),
+                );
+                if let Some(fix) = create_fix_nl(source, separator, token, text) {
+                    diagnostic = diagnostic.with_fix(fix);
+                }
+                vec![diagnostic]
+            }
+            SeparatorWrapOption::Eol if source[line_start..start].trim().is_empty() => {
+                let mut diagnostic = Diagnostic::new(
+                    SeparatorShouldBeOnPrevLine {
+                        separator: text.to_string(),
+                    },
+                    node.range(),
+                );
+                if let Some(fix) = create_fix_eol(source, separator, text, line_end) {
+                    diagnostic = diagnostic.with_fix(fix);
+                }
+                vec![diagnostic]
+            }
+            _ => vec![],
+        }
+    }
+}
+
+/// Create fix for NL option: move separator from end of line to start of next line.
+fn create_fix_nl(
+    source: &str,
+    separator: tree_sitter::Node,
+    token: SeparatorWrapToken,
+    text: &str,
+) -> Option<Fix> {
+    // Don't fix if a comment comes before the next token
+    let next = next_leaf(separator)?;
+    let between = &source[separator.end_byte()..next.start_byte()];
+    if is_in_comment(next) || !between.trim().is_empty() {
+        return None;
+    }
+
+    // Take the whitespace before the separator along, but leave a separator
+    // alone on its line to be fixed by hand
+    let start = source[..separator.start_byte()]
+        .trim_end_matches([' ', '\t'])
+        .len();
+    if start == 0 || source[..start].ends_with('\n') {
+        return None;
+    }
+
+    // Original: "foo.\n    bar" -> "foo\n    .bar"
+    let between = between.trim_start_matches([' ', '\t']);
+    let space = if token.spaced() { " " } else { "" };
+    let replacement = format!("{between}{text}{space}");
+
+    Some(Fix::safe_edit(Edit::replacement(
+        replacement,
+        TextSize::from(start as u32),
+        TextSize::from(next.start_byte() as u32),
+    )))
+}
+
+/// Create fix for EOL option: move separator from start of line to end of previous line.
+fn create_fix_eol(
+    source: &str,
+    separator: tree_sitter::Node,
+    text: &str,
+    line_end: usize,
+) -> Option<Fix> {
+    // Don't fix if a comment comes after the previous token
+    let prev = prev_leaf(separator)?;
+    let start = separator.start_byte();
+    let between = &source[prev.end_byte()..start];
+    if is_in_comment(prev) || !between.trim().is_empty() {
+        return None;
+    }
+
+    let end = separator.end_byte();
+    let rest = &source[end..line_end];
+
+    let (end, replacement) = if rest.trim().is_empty() {
+        // The separator is alone on its line, which goes away with it
+        (end + rest.trim_end_matches('\r').len(), text.to_string())
+    } else {
+        // Original: "foo\n    .bar" -> "foo.\n    bar"
+        let spaces = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+        (end + spaces, format!("{text}{between}"))
+    };
+
+    Some(Fix::safe_edit(Edit::replacement(
+        replacement,
+        TextSize::from(prev.end_byte() as u32),
+        TextSize::from(end as u32),
+    )))
+}
+
+/// The token before `node`, including comments.
+fn prev_leaf(node: tree_sitter::Node) -> Option<tree_sitter::Node> {
+    let mut current = node;
+    let mut leaf = loop {
+        if let Some(sibling) = current.prev_sibling() {
+            break sibling;
+        }
+        current = current.parent()?;
+    };
+    while leaf.child_count() > 0 {
+        leaf = leaf.child((leaf.child_count() - 1) as u32)?;
+    }
+    Some(leaf)
+}
+
+/// The token after `node`, including comments.
+fn next_leaf(node: tree_sitter::Node) -> Option<tree_sitter::Node> {
+    let mut current = node;
+    let mut leaf = loop {
+        if let Some(sibling) = current.next_sibling() {
+            break sibling;
+        }
+        current = current.parent()?;
+    };
+    while leaf.child_count() > 0 {
+        leaf = leaf.child(0)?;
+    }
+    Some(leaf)
+}
+
+/// Whether `node` is, or is part of, a comment.
+fn is_in_comment(node: tree_sitter::Node) -> bool {
+    std::iter::successors(Some(node), |n| n.parent())
+        .any(|n| matches!(n.kind(), "line_comment" | "block_comment"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::apply_fixes;
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    fn check_source(source: &str, rule: &SeparatorWrap) -> Vec<Diagnostic> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+
+        let mut diagnostics = vec![];
+        for node in TreeWalker::new(result.tree.root_node(), source) {
+            diagnostics.extend(rule.check(&ctx, &node));
+        }
+        diagnostics
+    }
+
+    fn fix_source(source: &str, rule: &SeparatorWrap) -> String {
+        let diagnostics = check_source(source, rule);
+        apply_fixes(source, diagnostics.iter().filter_map(|d| d.fix.as_ref())).code
+    }
+
+    fn nl_rule() -> SeparatorWrap {
+        SeparatorWrap {
+            option: SeparatorWrapOption::Nl,
+            tokens: SeparatorWrapToken::default_tokens(),
+        }
+    }
+
+    #[test]
+    fn test_eol_dot_on_new_line_violation() {
+        let source = r#"
+class Test {
+    String s = "a"
+        .trim();
+}
+"#;
+        let diagnostics = check_source(source, &SeparatorWrap::default());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind.body,
+            "'.' should be on the previous line."
+        );
+    }
+
+    #[test]
+    fn test_eol_separators_at_end_of_line_ok() {
+        let source = r#"
+class Test {
+    void method(int a,
+            int b) {
+        String s = "a".
+            trim();
+    }
+}
+"#;
+        let diagnostics = check_source(source, &SeparatorWrap::default());
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_nl_comma_at_end_of_line_violation() {
+        let source = r#"
+class Test {
+    void method(int a,
+            int b) {}
+}
+"#;
+        let diagnostics = check_source(source, &nl_rule());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind.body, "',' should be on a new line.");
+    }
+
+    #[test]
+    fn test_tokens_not_configured_ignored() {
+        let source = r#"
+class Test {
+    int[] a = new int[1]
+        ;
+}
+"#;
+        let diagnostics = check_source(source, &SeparatorWrap::default());
+        assert!(diagnostics.is_empty());
+
+        let mut properties = Properties::new();
+        properties.insert("tokens", "SEMI");
+        let rule = SeparatorWrap::from_config(&properties);
+        assert_eq!(check_source(source, &rule).len(), 1);
+    }
+
+    #[test]
+    fn test_method_call_paren_not_lparen() {
+        let source = r#"
+class Test {
+    void method() {
+        foo(
+            1);
+        int x = (
+            1);
+    }
+}
+"#;
+        let mut properties = Properties::new();
+        properties.insert("tokens", "LPAREN");
+        properties.insert("option", "nl");
+        let rule = SeparatorWrap::from_config(&properties);
+        let diagnostics = check_source(source, &rule);
+        assert_eq!(diagnostics.len(), 1, "only the parenthesized expression");
+    }
+
+    #[test]
+    fn test_fix_eol_moves_separator_to_previous_line() {
+        let source = r#"
+class Test {
+    void method(int a
+            , int b) {
+        String s = "a"
+            .trim();
+    }
+}
+"#;
+        let expected = r#"
+class Test {
+    void method(int a,
+            int b) {
+        String s = "a".
+            trim();
+    }
+}
+"#;
+        assert_eq!(fix_source(source, &SeparatorWrap::default()), expected);
+    }
+
+    #[test]
+    fn test_fix_nl_moves_separator_to_next_line() {
+        let source = r#"
+class Test {
+    void method(int a,
+            int b) {
+        String s = "a".
+            trim();
+    }
+}
+"#;
+        let expected = r#"
+class Test {
+    void method(int a
+            , int b) {
+        String s = "a"
+            .trim();
+    }
+}
+"#;
+        assert_eq!(fix_source(source, &nl_rule()), expected);
+    }
+
+    #[test]
+    fn test_no_fix_across_comment() {
+        let source = r#"
+class Test {
+    String s = "a" // comment
+        .trim();
+}
+"#;
+        let diagnostics = check_source(source, &SeparatorWrap::default());
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].fix.is_none());
+    }
+}
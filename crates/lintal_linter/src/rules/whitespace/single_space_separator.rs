@@ -6,7 +6,7 @@
 //!
 //! Checkstyle equivalent: SingleSpaceSeparatorCheck
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::{TextRange, TextSize};
 
@@ -22,6 +22,10 @@ impl Violation for SingleSpaceSeparatorViolation {
     fn message(&self) -> String {
         "'Use a single space to separate non-whitespace characters".to_string()
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "single.space.separator"))
+    }
 }
 
 /// Configuration for SingleSpaceSeparator rule.
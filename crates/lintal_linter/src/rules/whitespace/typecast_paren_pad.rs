@@ -7,8 +7,7 @@ use lintal_diagnostics::Diagnostic;
 use lintal_java_cst::CstNode;
 
 use crate::rules::whitespace::common::{
-    diag_followed, diag_not_followed, diag_not_preceded, diag_preceded, has_whitespace_after,
-    has_whitespace_before, whitespace_range_after, whitespace_range_before,
+    diag_followed, diag_not_followed, diag_not_preceded, diag_preceded,
 };
 use crate::{CheckContext, FromConfig, Properties, Rule};
 
@@ -101,68 +100,50 @@ impl TypecastParenPad {
 
     /// Check whitespace after opening paren.
     fn check_lparen(&self, ctx: &CheckContext, lparen: &CstNode) -> Vec<Diagnostic> {
-        let mut diagnostics = vec![];
-        let after_pos = lparen.range().end();
-
-        // Check if the next char is a closing paren (empty parens)
-        if let Some(next_char) = ctx.source()[usize::from(after_pos)..].chars().next()
-            && next_char == ')'
-        {
-            // Empty parens, don't check
-            return diagnostics;
-        }
+        let tokens = ctx.tokens(lparen);
+        let Some(index) = tokens.first_index(lparen) else {
+            return vec![];
+        };
+        let whitespace = tokens.whitespace_after(index);
 
-        let has_space = has_whitespace_after(ctx.source(), after_pos);
+        // Empty parens, don't check
+        if whitespace.is_empty() && tokens.next(index).is_some_and(|next| next.kind == ")") {
+            return vec![];
+        }
 
         match self.option {
-            TypecastParenPadOption::NoSpace => {
-                if has_space && let Some(ws_range) = whitespace_range_after(ctx.source(), after_pos)
-                {
-                    diagnostics.push(diag_followed(lparen, ws_range));
-                }
+            TypecastParenPadOption::NoSpace if !whitespace.is_empty() => {
+                vec![diag_followed(lparen, whitespace)]
             }
-            TypecastParenPadOption::Space => {
-                if !has_space {
-                    diagnostics.push(diag_not_followed(lparen));
-                }
+            TypecastParenPadOption::Space if whitespace.is_empty() => {
+                vec![diag_not_followed(lparen)]
             }
+            _ => vec![],
         }
-
-        diagnostics
     }
 
     /// Check whitespace before closing paren.
     fn check_rparen(&self, ctx: &CheckContext, rparen: &CstNode) -> Vec<Diagnostic> {
-        let mut diagnostics = vec![];
-        let before_pos = rparen.range().start();
-
-        // Check if the previous char is an opening paren (empty parens)
-        if before_pos > 0.into()
-            && let Some(prev_char) = ctx.source()[..usize::from(before_pos)].chars().last()
-            && prev_char == '('
-        {
-            // Empty parens, don't check
-            return diagnostics;
-        }
+        let tokens = ctx.tokens(rparen);
+        let Some(index) = tokens.first_index(rparen) else {
+            return vec![];
+        };
+        let whitespace = tokens.whitespace_before(index);
 
-        let has_space = has_whitespace_before(ctx.source(), before_pos);
+        // Empty parens, don't check
+        if whitespace.is_empty() && tokens.previous(index).is_some_and(|prev| prev.kind == "(") {
+            return vec![];
+        }
 
         match self.option {
-            TypecastParenPadOption::NoSpace => {
-                if has_space
-                    && let Some(ws_range) = whitespace_range_before(ctx.source(), before_pos)
-                {
-                    diagnostics.push(diag_preceded(rparen, ws_range));
-                }
+            TypecastParenPadOption::NoSpace if !whitespace.is_empty() => {
+                vec![diag_preceded(rparen, whitespace)]
             }
-            TypecastParenPadOption::Space => {
-                if !has_space {
-                    diagnostics.push(diag_not_preceded(rparen));
-                }
+            TypecastParenPadOption::Space if whitespace.is_empty() => {
+                vec![diag_not_preceded(rparen)]
             }
+            _ => vec![],
         }
-
-        diagnostics
     }
 }
 
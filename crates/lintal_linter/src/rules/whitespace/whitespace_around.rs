@@ -3,7 +3,7 @@
 //! Checks that tokens are surrounded by whitespace. This is a port of the
 //! checkstyle WhitespaceAround check for 100% compatibility.
 
-use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, MessageKey, Violation};
 use lintal_java_cst::CstNode;
 use lintal_text_size::TextSize;
 
@@ -142,6 +142,14 @@ impl Violation for MissingWhitespaceBefore {
     fn message(&self) -> String {
         format!("Missing whitespace before `{}`", self.token)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "ws.notPreceded"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.token.clone()]
+    }
 }
 
 /// Violation for missing whitespace after a token.
@@ -156,6 +164,14 @@ impl Violation for MissingWhitespaceAfter {
     fn message(&self) -> String {
         format!("Missing whitespace after `{}`", self.token)
     }
+
+    fn message_key(&self) -> Option<MessageKey> {
+        Some(MessageKey::new("whitespace", "ws.notFollowed"))
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        vec![self.token.clone()]
+    }
 }
 
 impl Rule for WhitespaceAround {
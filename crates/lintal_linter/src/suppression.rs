@@ -4,13 +4,29 @@
 //! - `// CHECKSTYLE:OFF:RuleName` / `// CHECKSTYLE:ON:RuleName` comments
 //! - `/* CHECKSTYLE:OFF:RuleName */` block comments
 //! - `@SuppressWarnings("checkstyle:RuleName")` annotations
+//! - checkstyle's `SuppressionCommentFilter` and
+//!   `SuppressWithNearbyCommentFilter`, which match checks and messages by
+//!   pattern; see [`CommentFilterConfig`]
+//! - `// lintal: disable-file RuleName, ...` and
+//!   `// lintal: disable-next-line RuleName, ...` comments
+//!
+//! Built-in rules can be named by their code instead, e.g. `CHECKSTYLE:OFF:WS001`.
 //!
 //! Suppressions work by tracking ranges where specific rules are disabled.
+//! Which of them suppressed a diagnostic can be recorded in a
+//! [`SuppressionUsage`], to report and delete those that suppress nothing.
 
+use lintal_checkstyle::{CheckstyleConfig, Module};
+use lintal_diagnostics::{Diagnostic, Edit, Fix, FixAvailability, Violation};
 use lintal_java_cst::CstNode;
-use lintal_text_size::TextSize;
-use regex::Regex;
-use std::collections::HashMap;
+use lintal_source_file::{LineColumn, LineIndex};
+use lintal_text_size::{TextRange, TextSize};
+use regex::{Captures, Regex};
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use crate::codes;
 
 /// A suppression region where a specific rule is disabled.
 #[derive(Debug, Clone)]
@@ -21,6 +37,8 @@ pub struct SuppressionRegion {
     pub start: TextSize,
     /// End offset in the source (None means until end of file).
     pub end: Option<TextSize>,
+    /// Start of the comment or annotation the region comes from.
+    pub directive: Option<TextSize>,
 }
 
 /// Configuration for a plain text comment filter.
@@ -66,16 +84,265 @@ impl PlainTextCommentFilterConfig {
         Self::new(r"CHECKSTYLE:OFF:(\w+)", r"CHECKSTYLE:ON:(\w+)", Some("$1"))
             .expect("Default patterns should be valid")
     }
+
+    /// The filters a checkstyle configuration sets up: the default filter,
+    /// plus one per `SuppressWithPlainTextCommentFilter` module.
+    pub fn from_checkstyle(config: &CheckstyleConfig) -> Vec<Self> {
+        let mut filters = vec![Self::checkstyle_default()];
+        for module in &config.modules {
+            if module.name == "SuppressWithPlainTextCommentFilter"
+                && let Some(filter) = Self::from_module(module)
+            {
+                filters.push(filter);
+            }
+        }
+        filters
+    }
+
+    /// Create a filter config from a checkstyle module.
+    fn from_module(module: &Module) -> Option<Self> {
+        let off_format = module.property("offCommentFormat")?;
+        let on_format = module.property("onCommentFormat")?;
+        let check_format = module.property("checkFormat");
+
+        Self::new(off_format, on_format, check_format)
+    }
+}
+
+/// Configuration for checkstyle's `SuppressionCommentFilter` or
+/// `SuppressWithNearbyCommentFilter`. Unlike the plain text filter, these
+/// only look at comments, and match the checks and messages of violations
+/// by pattern: `check_format` and `message_format` are regexes, in which
+/// `$1` and so on stand for the groups the comment matched.
+#[derive(Debug, Clone)]
+pub struct CommentFilterConfig {
+    pub kind: CommentFilterKind,
+    /// Regex of the rules the comment applies to, matched against names and
+    /// codes.
+    pub check_format: String,
+    /// Regex the messages of violations must match, if any.
+    pub message_format: Option<String>,
+    /// Whether `/* */` comments are looked at.
+    pub check_c: bool,
+    /// Whether `//` comments are looked at.
+    pub check_cpp: bool,
+}
+
+/// How a [`CommentFilterConfig`] suppresses violations.
+#[derive(Debug, Clone)]
+pub enum CommentFilterKind {
+    /// `SuppressionCommentFilter`: a violation is suppressed if the nearest
+    /// comment before it that matches its check and message is an "off"
+    /// comment.
+    Toggle {
+        off_pattern: Regex,
+        on_pattern: Regex,
+    },
+    /// `SuppressWithNearbyCommentFilter`: a comment suppresses the
+    /// violations it matches on its line and the `influence_format` lines
+    /// after it, or before it if negative.
+    Nearby {
+        comment_pattern: Regex,
+        influence_format: String,
+    },
+}
+
+impl CommentFilterConfig {
+    /// A `SuppressionCommentFilter` with checkstyle's defaults apart from
+    /// the comment formats.
+    pub fn toggle(off_comment_format: &str, on_comment_format: &str) -> Option<Self> {
+        Some(Self::with_kind(CommentFilterKind::Toggle {
+            off_pattern: Regex::new(off_comment_format).ok()?,
+            on_pattern: Regex::new(on_comment_format).ok()?,
+        }))
+    }
+
+    /// A `SuppressWithNearbyCommentFilter` with checkstyle's defaults apart
+    /// from the comment format and influence.
+    pub fn nearby(comment_format: &str, influence_format: &str) -> Option<Self> {
+        Some(Self::with_kind(CommentFilterKind::Nearby {
+            comment_pattern: Regex::new(comment_format).ok()?,
+            influence_format: influence_format.to_string(),
+        }))
+    }
+
+    fn with_kind(kind: CommentFilterKind) -> Self {
+        Self {
+            kind,
+            check_format: ".*".to_string(),
+            message_format: None,
+            check_c: true,
+            check_cpp: true,
+        }
+    }
+
+    /// The filters of every `SuppressionCommentFilter` and
+    /// `SuppressWithNearbyCommentFilter` module of a checkstyle
+    /// configuration.
+    pub fn from_checkstyle(config: &CheckstyleConfig) -> Vec<Self> {
+        config
+            .rules()
+            .into_iter()
+            .filter_map(Self::from_module)
+            .collect()
+    }
+
+    /// Create a filter from a checkstyle module, if it is one of the comment
+    /// filters. Filters with an `idFormat` are skipped: lintal doesn't track
+    /// module ids, so they would suppress too much.
+    fn from_module(module: &Module) -> Option<Self> {
+        if module.property("idFormat").is_some() {
+            return None;
+        }
+        let mut filter = match module.name.as_str() {
+            "SuppressionCommentFilter" => Self::toggle(
+                module
+                    .property("offCommentFormat")
+                    .unwrap_or("CHECKSTYLE:OFF"),
+                module
+                    .property("onCommentFormat")
+                    .unwrap_or("CHECKSTYLE:ON"),
+            )?,
+            "SuppressWithNearbyCommentFilter" => Self::nearby(
+                module
+                    .property("commentFormat")
+                    .unwrap_or(r"SUPPRESS CHECKSTYLE (\w+)"),
+                module.property("influenceFormat").unwrap_or("0"),
+            )?,
+            _ => return None,
+        };
+        if let Some(check_format) = module.property("checkFormat") {
+            filter.check_format = check_format.to_string();
+        }
+        filter.message_format = module.property("messageFormat").map(str::to_string);
+        filter.check_c = module.property("checkC") != Some("false");
+        filter.check_cpp = module.property("checkCPP") != Some("false");
+        Some(filter)
+    }
+
+    /// Whether the filter looks at `comment`.
+    fn looks_at(&self, comment: &str) -> bool {
+        if comment.starts_with("//") {
+            self.check_cpp
+        } else {
+            self.check_c
+        }
+    }
+
+    /// The check and message patterns of a comment the filter matched with
+    /// `captures`, or `None` if they aren't valid regexes.
+    fn patterns(&self, captures: &Captures) -> Option<(String, Regex, Option<Regex>)> {
+        let check_format = fill_template(&self.check_format, captures);
+        let check = Regex::new(&check_format).ok()?;
+        let message = match &self.message_format {
+            Some(format) => Some(Regex::new(&fill_template(format, captures)).ok()?),
+            None => None,
+        };
+        Some((check_format, check, message))
+    }
+}
+
+/// `template` with `$0`, `$1`, ... replaced by the groups `captures`
+/// matched, as checkstyle fills in the formats of its comment filters.
+fn fill_template(template: &str, captures: &Captures) -> String {
+    // From the last group, so `$1` doesn't replace the start of `$10`
+    (0..captures.len())
+        .rev()
+        .fold(template.to_string(), |filled, group| {
+            filled.replace(
+                &format!("${group}"),
+                captures.get(group).map_or("", |m| m.as_str()),
+            )
+        })
+}
+
+/// The rule a filled-in check format names, or `*` if it's a pattern.
+fn tag_rule(check_format: &str) -> String {
+    let is_name = !check_format.is_empty()
+        && check_format
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    let rule = if is_name { check_format } else { "*" };
+    codes::rule_name(rule).unwrap_or(rule).to_string()
+}
+
+/// The aliases `@SuppressWarnings` can name checks by, from the `aliasList`
+/// of checkstyle's `SuppressWarningsHolder`.
+#[derive(Debug, Clone, Default)]
+pub struct SuppressWarningsAliases {
+    /// Rule names by lowercase alias.
+    aliases: HashMap<String, String>,
+}
+
+impl SuppressWarningsAliases {
+    /// Create an empty set of aliases.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Let `alias` name `rule`, a rule name or a check class such as
+    /// `com.puppycrawl.tools.checkstyle.checks.sizes.MethodLengthCheck`.
+    #[must_use]
+    pub fn with_alias(mut self, rule: &str, alias: &str) -> Self {
+        self.add(rule, alias);
+        self
+    }
+
+    /// The aliases of every `SuppressWarningsHolder` module in a checkstyle
+    /// configuration, whose `aliasList` is written `check=alias,...`.
+    pub fn from_checkstyle(config: &CheckstyleConfig) -> Self {
+        let mut aliases = Self::new();
+        let modules = config.modules.iter().chain(config.rules());
+        for module in modules.filter(|m| m.name == "SuppressWarningsHolder") {
+            let Some(list) = module.property("aliasList") else {
+                continue;
+            };
+            for entry in list.split(',') {
+                if let Some((rule, alias)) = entry.split_once('=') {
+                    aliases.add(rule.trim(), alias.trim());
+                }
+            }
+        }
+        aliases
+    }
+
+    fn add(&mut self, rule: &str, alias: &str) {
+        let rule = rule.rsplit('.').next().unwrap_or(rule);
+        if rule.is_empty() || alias.is_empty() {
+            return;
+        }
+        self.aliases
+            .insert(alias.to_lowercase(), strip_check_suffix(rule).to_string());
+    }
+
+    /// The rule `alias` names, if it is an alias.
+    pub fn resolve(&self, alias: &str) -> Option<&str> {
+        self.aliases.get(&alias.to_lowercase()).map(String::as_str)
+    }
+
+    /// Whether there are no aliases.
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+    }
 }
 
 /// A file-based suppression rule from suppressions.xml.
-/// Matches file paths and rule names using regex patterns.
+/// Matches file paths and rule names using regex patterns, and optionally
+/// the lines, columns and messages of violations.
 #[derive(Debug, Clone)]
 pub struct FileSuppressionRule {
     /// Regex pattern to match file paths.
     pub files_pattern: Regex,
     /// Regex pattern to match rule/check names ("." matches all).
     pub checks_pattern: Regex,
+    /// The lines violations must be on, if limited.
+    pub lines: Option<NumberRanges>,
+    /// The columns violations must start at, if limited.
+    pub columns: Option<NumberRanges>,
+    /// Regex the message of violations must match, if limited.
+    pub message_pattern: Option<Regex>,
+    /// One-based line of the `<suppress>` element, if read from XML.
+    pub line: Option<usize>,
 }
 
 impl FileSuppressionRule {
@@ -86,45 +353,181 @@ impl FileSuppressionRule {
         Some(Self {
             files_pattern,
             checks_pattern,
+            lines: None,
+            columns: None,
+            message_pattern: None,
+            line: None,
         })
     }
 
-    /// Check if this rule suppresses the given check for the given file path.
+    /// Check if this rule suppresses the given check for the whole of the
+    /// given file path. Rules limited to some lines, columns or messages
+    /// never do; see [`Self::suppresses`].
     pub fn is_suppressed(&self, file_path: &str, rule_name: &str) -> bool {
+        self.is_unconditional() && self.matches(file_path, rule_name)
+    }
+
+    /// Whether this rule suppresses a violation of `rule_name` in
+    /// `file_path` with `message`, at `location` as checkstyle counts it.
+    pub fn suppresses(
+        &self,
+        file_path: &str,
+        rule_name: &str,
+        location: LineColumn,
+        message: &str,
+    ) -> bool {
+        self.matches(file_path, rule_name)
+            && self
+                .lines
+                .as_ref()
+                .is_none_or(|lines| lines.contains(location.line.get()))
+            && self
+                .columns
+                .as_ref()
+                .is_none_or(|columns| columns.contains(location.column.get()))
+            && self
+                .message_pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(message))
+    }
+
+    /// Whether this rule applies to whole files, rather than to some lines,
+    /// columns or messages of them.
+    pub fn is_unconditional(&self) -> bool {
+        self.lines.is_none() && self.columns.is_none() && self.message_pattern.is_none()
+    }
+
+    fn matches(&self, file_path: &str, rule_name: &str) -> bool {
         self.files_pattern.is_match(file_path) && self.checks_pattern.is_match(rule_name)
     }
+
+    /// Create a rule from the attributes of a `<suppress>` element, as
+    /// checkstyle reads them: every attribute given must match, `files`
+    /// defaulting to all files. `id` names a module by its checkstyle id,
+    /// which lintal doesn't track, so elements with one are ignored rather
+    /// than suppressing too much, as are elements with nothing to match
+    /// violations by.
+    fn from_attributes(attributes: &HashMap<&str, String>) -> Option<Self> {
+        if attributes.contains_key("id")
+            || !(attributes.contains_key("checks") || attributes.contains_key("message"))
+        {
+            return None;
+        }
+        let attribute = |name: &str| attributes.get(name).map(String::as_str);
+        let ranges = |name: &str| attribute(name).map(NumberRanges::parse);
+        let mut rule = Self::new(
+            attribute("files").unwrap_or(".*"),
+            attribute("checks").unwrap_or(".*"),
+        )?;
+        // Checkstyle rejects malformed lists; drop the element instead
+        rule.lines = match ranges("lines") {
+            Some(lines) => Some(lines?),
+            None => None,
+        };
+        rule.columns = match ranges("columns") {
+            Some(columns) => Some(columns?),
+            None => None,
+        };
+        rule.message_pattern = attribute("message").map(Regex::new).transpose().ok()?;
+        Some(rule)
+    }
+}
+
+/// A list of numbers and ranges of them, such as `10,20-30`, as the `lines`
+/// and `columns` of suppressions.xml are written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberRanges(Vec<RangeInclusive<usize>>);
+
+impl NumberRanges {
+    /// Parse a comma-separated list, or `None` if an item isn't a number or
+    /// a range.
+    pub fn parse(list: &str) -> Option<Self> {
+        list.split(',')
+            .map(|item| {
+                let item = item.trim();
+                let (start, end) = item.split_once('-').unwrap_or((item, item));
+                Some(start.trim().parse().ok()?..=end.trim().parse().ok()?)
+            })
+            .collect::<Option<_>>()
+            .map(Self)
+    }
+
+    /// Whether `number` is in the list.
+    pub fn contains(&self, number: usize) -> bool {
+        self.0.iter().any(|range| range.contains(&number))
+    }
 }
 
 /// Collection of file-based suppression rules.
 #[derive(Debug, Clone, Default)]
 pub struct FileSuppressionsConfig {
     rules: Vec<FileSuppressionRule>,
+    /// The suppressions.xml they were read from.
+    path: Option<PathBuf>,
 }
 
 impl FileSuppressionsConfig {
     /// Create a new empty config.
     pub fn new() -> Self {
-        Self { rules: vec![] }
+        Self {
+            rules: vec![],
+            path: None,
+        }
+    }
+
+    /// Note the suppressions.xml the rules were read from, for reports.
+    #[must_use]
+    pub fn with_path(mut self, path: PathBuf) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// The suppressions.xml files a checkstyle configuration's
+    /// `SuppressionFilter` modules refer to, with `${config_loc}` resolved to
+    /// `config_dir`.
+    pub fn files_from_checkstyle(config: &CheckstyleConfig, config_dir: &Path) -> Vec<PathBuf> {
+        config
+            .modules
+            .iter()
+            .filter(|module| module.name == "SuppressionFilter")
+            .filter_map(|module| module.property("file"))
+            .map(|file| PathBuf::from(file.replace("${config_loc}", &config_dir.to_string_lossy())))
+            .collect()
     }
 
     /// Parse suppressions from an XML file (suppressions.xml format).
+    ///
+    /// `<suppress>` elements may span lines and quote their attributes
+    /// either way; comments and `<suppress-xpath>` elements are skipped.
     pub fn from_xml(xml: &str) -> Self {
         let mut config = Self::new();
 
-        // Simple XML parsing for suppress elements
-        // Format: <suppress files="pattern" checks="pattern"/>
-        for line in xml.lines() {
-            let line = line.trim();
-            if !line.starts_with("<suppress ") {
+        let mut rest = xml;
+        while let Some(start) = rest.find('<') {
+            let offset = xml.len() - rest.len() + start;
+            rest = &rest[start..];
+            if let Some(comment) = rest.strip_prefix("<!--") {
+                rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
                 continue;
             }
-
-            let files = Self::extract_attr(line, "files");
-            let checks = Self::extract_attr(line, "checks");
-
-            if let (Some(files), Some(checks)) = (files, checks)
-                && let Some(rule) = FileSuppressionRule::new(files, checks)
+            let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+            let element = &rest[..end];
+            rest = &rest[end..];
+
+            let Some(attributes) = element
+                .strip_prefix("<suppress")
+                .filter(|after| after.starts_with(|c: char| c.is_whitespace() || c == '/'))
+            else {
+                continue;
+            };
+            if let Some(mut rule) =
+                FileSuppressionRule::from_attributes(&Self::parse_attributes(attributes))
             {
+                rule.line = Some(xml[..offset].matches('\n').count() + 1);
                 config.rules.push(rule);
             }
         }
@@ -132,13 +535,31 @@ impl FileSuppressionsConfig {
         config
     }
 
-    /// Extract an attribute value from an XML element.
-    fn extract_attr<'a>(line: &'a str, attr: &str) -> Option<&'a str> {
-        let pattern = format!("{}=\"", attr);
-        let start = line.find(&pattern)? + pattern.len();
-        let rest = &line[start..];
-        let end = rest.find('"')?;
-        Some(&rest[..end])
+    /// The attributes of an element, after its name, with entities decoded.
+    fn parse_attributes(mut text: &str) -> HashMap<&str, String> {
+        let mut attributes = HashMap::new();
+        while let Some((name, after)) = text.split_once('=') {
+            let name = name.trim();
+            let after = after.trim_start();
+            let Some(quote) = after.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+                break;
+            };
+            let Some(end) = after[1..].find(quote) else {
+                break;
+            };
+            attributes.insert(name, Self::decode_entities(&after[1..=end]));
+            text = &after[end + 2..];
+        }
+        attributes
+    }
+
+    fn decode_entities(value: &str) -> String {
+        value
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&")
     }
 
     /// Check if a rule is suppressed for the given file path.
@@ -151,11 +572,20 @@ impl FileSuppressionsConfig {
     /// Check if all rules are suppressed for the given file path.
     pub fn is_file_fully_suppressed(&self, file_path: &str) -> bool {
         self.rules.iter().any(|rule| {
-            rule.files_pattern.is_match(file_path)
+            rule.is_unconditional()
+                && rule.files_pattern.is_match(file_path)
                 && (rule.checks_pattern.as_str() == "." || rule.checks_pattern.as_str() == ".*")
         })
     }
 
+    /// Whether rules limited to some lines, columns or messages apply to
+    /// the given file path, so its violations must be matched one by one.
+    pub fn has_conditional(&self, file_path: &str) -> bool {
+        self.rules
+            .iter()
+            .any(|rule| !rule.is_unconditional() && rule.files_pattern.is_match(file_path))
+    }
+
     /// Returns true if there are no suppression rules.
     pub fn is_empty(&self) -> bool {
         self.rules.is_empty()
@@ -165,6 +595,66 @@ impl FileSuppressionsConfig {
     pub fn len(&self) -> usize {
         self.rules.len()
     }
+
+    /// The suppression rules, in the order they were read.
+    pub fn rules(&self) -> &[FileSuppressionRule] {
+        &self.rules
+    }
+}
+
+/// A comment or annotation that suppresses rules, with the text written
+/// after it, for [`SuppressionPolicy`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SuppressionDirective {
+    /// Where the comment or annotation starts.
+    pub start: TextSize,
+    /// What is written after the directive, e.g. `JIRA-123 until=2025-12-31`
+    /// after `// CHECKSTYLE:OFF:MagicNumber —`.
+    pub note: String,
+    /// The comment or annotation, then the `CHECKSTYLE:ON` comments that
+    /// close it.
+    pub ranges: Vec<TextRange>,
+    /// The rules it names, `*` for all of them.
+    pub rules: Vec<SuppressedRule>,
+}
+
+/// A rule named by a [`SuppressionDirective`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuppressedRule {
+    /// The rule's name, or `*`; codes are resolved to names.
+    pub name: String,
+    /// The string literal naming it in `@SuppressWarnings`.
+    pub range: Option<TextRange>,
+}
+
+impl SuppressedRule {
+    fn new(rule: &str, range: Option<TextRange>) -> Self {
+        Self {
+            name: codes::rule_name(rule).unwrap_or(rule).to_string(),
+            range,
+        }
+    }
+}
+
+/// Which suppressions covered a diagnostic while linting a source; see
+/// [`SuppressionContext::record_suppressed`].
+#[derive(Debug, Default)]
+pub struct SuppressionUsage {
+    /// Directive start and lowercase rule of each region used.
+    used: HashSet<(TextSize, String)>,
+}
+
+impl SuppressionUsage {
+    fn record(&mut self, region: &SuppressionRegion) {
+        if let Some(directive) = region.directive {
+            self.used.insert((directive, region.rule.to_lowercase()));
+        }
+    }
+
+    fn is_used(&self, directive: &SuppressionDirective, rule: &SuppressedRule) -> bool {
+        self.used
+            .contains(&(directive.start, rule.name.to_lowercase()))
+    }
 }
 
 /// Manages suppressions for a source file.
@@ -173,6 +663,46 @@ pub struct SuppressionContext {
     /// Suppression regions indexed by rule name.
     /// Key "*" matches all rules.
     regions: HashMap<String, Vec<SuppressionRegion>>,
+    /// The comments and annotations the regions come from, in the order
+    /// they were found.
+    directives: Vec<SuppressionDirective>,
+    /// Comments of [`CommentFilterConfig`]s, in the order they were found,
+    /// matched against the rule and message of each diagnostic.
+    comment_tags: Vec<CommentTag>,
+}
+
+/// Whether a comment of a [`CommentFilterConfig`] turns checks off or on,
+/// or off on the lines near it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TagKind {
+    Off,
+    On,
+    Nearby,
+}
+
+/// A comment a [`CommentFilterConfig`] matched.
+#[derive(Debug, Clone)]
+struct CommentTag {
+    /// Index of the filter; toggles of different filters don't interact.
+    filter: usize,
+    kind: TagKind,
+    check: Regex,
+    message: Option<Regex>,
+    /// Where the tag applies: from the comment on for toggles, over the
+    /// lines near it for nearby comments. Its rule is the one the check
+    /// format names, or `*` if it's a pattern.
+    region: SuppressionRegion,
+}
+
+impl CommentTag {
+    fn matches(&self, rule_name: &str, message: &str) -> bool {
+        (self.check.is_match(rule_name)
+            || codes::rule_code(rule_name).is_some_and(|code| self.check.is_match(code)))
+            && self
+                .message
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(message))
+    }
 }
 
 impl SuppressionContext {
@@ -180,16 +710,20 @@ impl SuppressionContext {
     pub fn new() -> Self {
         Self {
             regions: HashMap::new(),
+            directives: Vec::new(),
+            comment_tags: Vec::new(),
         }
     }
 
-    /// Parse suppressions from source code using the given filter configs.
+    /// Parse suppressions from source code using the given filter configs,
+    /// and lintal's own `// lintal:` directives.
     pub fn from_source(source: &str, filters: &[PlainTextCommentFilterConfig]) -> Self {
         let mut ctx = Self::new();
 
         for filter in filters {
             ctx.parse_with_filter(source, filter);
         }
+        ctx.parse_directives(source);
 
         ctx
     }
@@ -199,72 +733,208 @@ impl SuppressionContext {
         // Track open suppressions: rule -> start offset
         let mut open_suppressions: HashMap<String, TextSize> = HashMap::new();
 
-        // Use find() to skip to potential comment locations instead of byte-by-byte iteration
-        let bytes = source.as_bytes();
-        let mut pos = 0;
+        for_each_comment(source, |pos, comment| {
+            self.process_comment(
+                comment,
+                TextSize::new(pos as u32),
+                filter,
+                &mut open_suppressions,
+            );
+        });
 
-        while pos < bytes.len() {
-            // Skip to next '/' character - comments always start with '/'
-            let Some(slash_offset) = bytes[pos..].iter().position(|&b| b == b'/') else {
-                break;
-            };
-            pos += slash_offset;
+        // Close any remaining open suppressions at end of file
+        let end_pos = TextSize::new(source.len() as u32);
+        for (rule, start) in open_suppressions {
+            self.add_region(SuppressionRegion {
+                rule,
+                start,
+                end: Some(end_pos),
+                directive: Some(start),
+            });
+        }
+    }
 
-            // Check for line comment (//)
-            if pos + 1 < bytes.len() && bytes[pos + 1] == b'/' {
-                // Find end of line
-                let line_end = bytes[pos..]
-                    .iter()
-                    .position(|&b| b == b'\n')
-                    .map(|i| pos + i)
-                    .unwrap_or(bytes.len());
-                let comment = &source[pos..line_end];
-
-                self.process_comment(
-                    comment,
-                    TextSize::new(pos as u32),
-                    filter,
-                    &mut open_suppressions,
-                );
+    /// Parse the comments `filters` match, checkstyle's
+    /// `SuppressionCommentFilter`s and `SuppressWithNearbyCommentFilter`s.
+    pub fn parse_comment_filters(&mut self, source: &str, filters: &[CommentFilterConfig]) {
+        if filters.is_empty() {
+            return;
+        }
+        let needs_lines = filters
+            .iter()
+            .any(|filter| matches!(filter.kind, CommentFilterKind::Nearby { .. }));
+        let line_index = needs_lines.then(|| LineIndex::from_source_text(source));
 
-                pos = line_end + 1;
-                continue;
+        for_each_comment(source, |pos, comment| {
+            for (index, filter) in filters.iter().enumerate() {
+                if !filter.looks_at(comment) {
+                    continue;
+                }
+                let (kind, captures, end) = match &filter.kind {
+                    CommentFilterKind::Toggle {
+                        off_pattern,
+                        on_pattern,
+                    } => match off_pattern.captures(comment) {
+                        Some(captures) => (TagKind::Off, captures, None),
+                        None => match on_pattern.captures(comment) {
+                            Some(captures) => (TagKind::On, captures, None),
+                            None => continue,
+                        },
+                    },
+                    CommentFilterKind::Nearby {
+                        comment_pattern,
+                        influence_format,
+                    } => {
+                        let Some(captures) = comment_pattern.captures(comment) else {
+                            continue;
+                        };
+                        let Some(influence) = fill_template(influence_format, &captures)
+                            .trim()
+                            .parse::<isize>()
+                            .ok()
+                        else {
+                            continue;
+                        };
+                        let lines = line_index.as_ref().expect("line index of nearby filters");
+                        let tagged = pos + captures.get(0).map_or(0, |m| m.start());
+                        let line = lines
+                            .line_index(TextSize::new(tagged as u32))
+                            .to_zero_indexed();
+                        let (first, last) = if influence >= 0 {
+                            (line, line.saturating_add_signed(influence))
+                        } else {
+                            (line.saturating_add_signed(influence), line)
+                        };
+                        let starts = lines.line_starts();
+                        let start = starts[first];
+                        let end = starts
+                            .get(last + 1)
+                            .copied()
+                            .unwrap_or(TextSize::of(source));
+                        (TagKind::Nearby, captures, Some((start, end)))
+                    }
+                };
+                let Some((check_format, check, message)) = filter.patterns(&captures) else {
+                    continue;
+                };
+                let matched = captures.get(0).map_or(0..0, |m| m.range());
+                self.add_comment_tag(
+                    CommentTag {
+                        filter: index,
+                        kind,
+                        check,
+                        message,
+                        region: SuppressionRegion {
+                            rule: tag_rule(&check_format),
+                            start: end.map_or(
+                                TextSize::new((pos + matched.start) as u32),
+                                |(start, _)| start,
+                            ),
+                            end: end.map(|(_, end)| end),
+                            directive: Some(TextSize::new(pos as u32)),
+                        },
+                    },
+                    TextRange::at(TextSize::new(pos as u32), TextSize::of(comment)),
+                    &comment[matched.end..],
+                );
             }
+        });
+    }
 
-            // Check for block comment (/*)
-            if pos + 1 < bytes.len() && bytes[pos + 1] == b'*' {
-                // Find end of block comment - use find for */ instead of byte-by-byte
-                if let Some(end_offset) = source[pos + 2..].find("*/") {
-                    let comment_end = pos + 2 + end_offset + 2;
-                    let comment = &source[pos..comment_end];
-
-                    self.process_comment(
-                        comment,
-                        TextSize::new(pos as u32),
-                        filter,
-                        &mut open_suppressions,
-                    );
-
-                    pos = comment_end;
-                    continue;
-                } else {
-                    // Unclosed comment, skip to end
-                    break;
+    /// Record a comment filter's tag: "off" and nearby comments as
+    /// directives, "on" comments as closing the latest "off" comment of the
+    /// same filter and checks.
+    fn add_comment_tag(&mut self, tag: CommentTag, comment: TextRange, note: &str) {
+        match tag.kind {
+            TagKind::Off | TagKind::Nearby => {
+                self.add_directive(comment, [SuppressedRule::new(&tag.region.rule, None)], note)
+            }
+            TagKind::On => {
+                let opened = self
+                    .comment_tags
+                    .iter()
+                    .rev()
+                    .find(|open| {
+                        open.filter == tag.filter && open.check.as_str() == tag.check.as_str()
+                    })
+                    .filter(|open| open.kind == TagKind::Off)
+                    .and_then(|open| open.region.directive);
+                if let Some(start) = opened
+                    && let Some(directive) = self.directives.iter_mut().find(|d| d.start == start)
+                    && !directive.ranges.contains(&comment)
+                {
+                    directive.ranges.push(comment);
                 }
             }
+        }
+        self.comment_tags.push(tag);
+    }
 
-            // Not a comment start, move past this '/'
-            pos += 1;
+    /// Parse lintal's own directives: `// lintal: disable-file Rule1, Rule2`
+    /// suppresses the rules in the whole file, and
+    /// `// lintal: disable-next-line Rule` on the line after the comment.
+    /// Without rules, every rule is suppressed.
+    fn parse_directives(&mut self, source: &str) {
+        lazy_static::lazy_static! {
+            static ref DIRECTIVE_RE: Regex = Regex::new(
+                r"^(?://|/\*)\s*lintal:\s*(disable-file|disable-next-line)\b(?:[ \t]+(\w+(?:[ \t]*,[ \t]*\w+)*))?"
+            ).unwrap();
         }
 
-        // Close any remaining open suppressions at end of file
-        let end_pos = TextSize::new(source.len() as u32);
-        for (rule, start) in open_suppressions {
-            self.add_region(SuppressionRegion {
-                rule,
-                start,
-                end: Some(end_pos),
-            });
+        // Most files have none
+        if !source.contains("lintal:") {
+            return;
+        }
+        let mut regions = Vec::new();
+        let mut directives = Vec::new();
+        for_each_comment(source, |pos, comment| {
+            let Some(captures) = DIRECTIVE_RE.captures(comment) else {
+                return;
+            };
+            let (start, end) = if &captures[1] == "disable-file" {
+                (TextSize::new(0), None)
+            } else {
+                let comment_end = pos + comment.len();
+                let line_start = source[comment_end..]
+                    .find('\n')
+                    .map_or(source.len(), |i| comment_end + i + 1);
+                let line_end = source[line_start..]
+                    .find('\n')
+                    .map_or(source.len(), |i| line_start + i + 1);
+                (
+                    TextSize::new(line_start as u32),
+                    Some(TextSize::new(line_end as u32)),
+                )
+            };
+            let directive = TextSize::new(pos as u32);
+            let rules: Vec<&str> = captures
+                .get(2)
+                .map_or("*", |rules| rules.as_str())
+                .split(',')
+                .map(str::trim)
+                .collect();
+            for rule in &rules {
+                regions.push(SuppressionRegion {
+                    rule: (*rule).to_string(),
+                    start,
+                    end,
+                    directive: Some(directive),
+                });
+            }
+            directives.push((
+                TextRange::at(directive, TextSize::of(comment)),
+                rules
+                    .into_iter()
+                    .map(|rule| SuppressedRule::new(rule, None))
+                    .collect::<Vec<_>>(),
+                comment[captures[0].len()..].to_string(),
+            ));
+        });
+        for region in regions {
+            self.add_region(region);
+        }
+        for (range, rules, note) in directives {
+            self.add_directive(range, rules, &note);
         }
     }
 
@@ -288,6 +958,12 @@ impl SuppressionContext {
             };
 
             // Start a new suppression region
+            let end = captures.get(0).map_or(0, |m| m.end());
+            self.add_directive(
+                TextRange::at(comment_pos, TextSize::of(comment)),
+                [SuppressedRule::new(&rule, None)],
+                &comment[end..],
+            );
             open_suppressions.insert(rule, comment_pos);
         }
 
@@ -308,13 +984,64 @@ impl SuppressionContext {
                     rule,
                     start,
                     end: Some(comment_pos),
+                    directive: Some(start),
                 });
+                let range = TextRange::at(comment_pos, TextSize::of(comment));
+                if let Some(directive) = self.directives.iter_mut().find(|d| d.start == start)
+                    && !directive.ranges.contains(&range)
+                {
+                    directive.ranges.push(range);
+                }
             }
         }
     }
 
-    /// Add a suppression region.
-    fn add_region(&mut self, region: SuppressionRegion) {
+    /// Record the suppression comment or annotation at `range`, once even if
+    /// several filters match it, with the `rules` it names and the `note`
+    /// written after it.
+    fn add_directive(
+        &mut self,
+        range: TextRange,
+        rules: impl IntoIterator<Item = SuppressedRule>,
+        note: &str,
+    ) {
+        let start = range.start();
+        if let Some(directive) = self
+            .directives
+            .iter_mut()
+            .find(|directive| directive.start == start)
+        {
+            for rule in rules {
+                if !directive.rules.contains(&rule) {
+                    directive.rules.push(rule);
+                }
+            }
+            return;
+        }
+        let note = note
+            .trim_end()
+            .trim_end_matches("*/")
+            .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, '-' | '—' | '–' | ':'))
+            .trim_end();
+        self.directives.push(SuppressionDirective {
+            start,
+            note: note.to_string(),
+            ranges: vec![range],
+            rules: rules.into_iter().collect(),
+        });
+    }
+
+    /// The comments and annotations that suppress rules in the source.
+    pub fn directives(&self) -> &[SuppressionDirective] {
+        &self.directives
+    }
+
+    /// Add a suppression region, keyed by rule name even if it names the
+    /// rule by code.
+    fn add_region(&mut self, mut region: SuppressionRegion) {
+        if let Some(name) = codes::rule_name(&region.rule) {
+            region.rule = name.to_string();
+        }
         self.regions
             .entry(region.rule.clone())
             .or_default()
@@ -324,30 +1051,146 @@ impl SuppressionContext {
     /// Check if a diagnostic at the given position for the given rule is suppressed.
     /// Matching is case-insensitive to match checkstyle behavior where
     /// `@SuppressWarnings("methodlength")` suppresses `MethodLength`.
+    ///
+    /// Comment filters limited to some messages don't match here; see
+    /// [`Self::record_suppressed`].
     pub fn is_suppressed(&self, rule_name: &str, pos: TextSize) -> bool {
+        self.covering(rule_name, "", pos).next().is_some()
+    }
+
+    /// Whether a diagnostic of `rule_name` with `message` at `pos` is
+    /// suppressed, recording every suppression that covers it in `usage`.
+    pub fn record_suppressed(
+        &self,
+        rule_name: &str,
+        message: &str,
+        pos: TextSize,
+        usage: &mut SuppressionUsage,
+    ) -> bool {
+        let mut suppressed = false;
+        for region in self.covering(rule_name, message, pos) {
+            usage.record(region);
+            suppressed = true;
+        }
+        suppressed
+    }
+
+    /// Start of the comment or annotation that suppresses a diagnostic of
+    /// `rule_name` with `message` at `pos`, if one does.
+    pub fn suppressed_by(&self, rule_name: &str, message: &str, pos: TextSize) -> Option<TextSize> {
+        self.covering(rule_name, message, pos)
+            .filter_map(|region| region.directive)
+            .min()
+    }
+
+    /// The regions suppressing a diagnostic of `rule_name` with `message`
+    /// at `pos`.
+    fn covering<'a>(
+        &'a self,
+        rule_name: &str,
+        message: &str,
+        pos: TextSize,
+    ) -> impl Iterator<Item = &'a SuppressionRegion> {
         let rule_lower = rule_name.to_lowercase();
 
         // Check all regions — compare case-insensitively
-        for (key, regions) in &self.regions {
-            if key == "*" || key.to_lowercase() == rule_lower {
-                for region in regions {
-                    if pos >= region.start {
-                        match region.end {
-                            Some(end) if pos < end => return true,
-                            None => return true,
-                            _ => {}
-                        }
-                    }
+        self.regions
+            .iter()
+            .filter(move |(key, _)| *key == "*" || key.to_lowercase() == rule_lower)
+            .flat_map(|(_, regions)| regions)
+            .filter(move |region| pos >= region.start && region.end.is_none_or(|end| pos < end))
+            .chain(self.tags_covering(rule_name, message, pos))
+    }
+
+    /// The regions of the comment filter tags suppressing a diagnostic of
+    /// `rule_name` with `message` at `pos`. Of each filter's toggles, the
+    /// last one before the diagnostic that matches it decides, as in
+    /// checkstyle.
+    fn tags_covering(
+        &self,
+        rule_name: &str,
+        message: &str,
+        pos: TextSize,
+    ) -> Vec<&SuppressionRegion> {
+        let mut covering = Vec::new();
+        let mut toggles: HashMap<usize, &CommentTag> = HashMap::new();
+        for tag in &self.comment_tags {
+            if tag.region.start > pos || !tag.matches(rule_name, message) {
+                continue;
+            }
+            match tag.kind {
+                TagKind::Nearby if tag.region.end.is_none_or(|end| pos < end) => {
+                    covering.push(&tag.region);
+                }
+                TagKind::Nearby => {}
+                TagKind::Off | TagKind::On => {
+                    toggles.insert(tag.filter, tag);
                 }
             }
         }
+        covering.extend(
+            toggles
+                .into_values()
+                .filter(|tag| tag.kind == TagKind::Off)
+                .map(|tag| &tag.region),
+        );
+        covering
+    }
 
-        false
+    /// Report each rule of a directive that suppressed nothing according to
+    /// `usage`, with a fix that deletes it. Only rules `is_tracked` accepts
+    /// are reported: a rule that didn't run can't have been suppressed, and
+    /// `@SuppressWarnings` also names the warnings of other tools.
+    ///
+    /// A directive is deleted whole once none of its rules is used; from
+    /// `@SuppressWarnings` an unused rule can also be deleted on its own.
+    pub fn unused(
+        &self,
+        source: &str,
+        usage: &SuppressionUsage,
+        is_tracked: impl Fn(&str) -> bool,
+    ) -> Vec<Diagnostic> {
+        let mut found = Vec::new();
+        for directive in &self.directives {
+            let unused: Vec<&SuppressedRule> = directive
+                .rules
+                .iter()
+                .filter(|rule| is_tracked(&rule.name) && !usage.is_used(directive, rule))
+                .collect();
+            let Some(&first_range) = directive.ranges.first() else {
+                continue;
+            };
+            let stale = unused.len() == directive.rules.len();
+            for rule in unused {
+                let fix = if stale {
+                    let mut edits = directive
+                        .ranges
+                        .iter()
+                        .map(|&range| Edit::range_deletion(line_deletion(source, range)));
+                    edits.next().map(|first| Fix::safe_edits(first, edits))
+                } else {
+                    rule.range.map(|range| {
+                        Fix::safe_edit(Edit::range_deletion(element_deletion(source, range)))
+                    })
+                };
+                let diagnostic = Diagnostic::new(
+                    UnusedSuppressionViolation {
+                        rule: rule.name.clone(),
+                    },
+                    rule.range.unwrap_or(first_range),
+                );
+                found.push(match fix {
+                    Some(fix) => diagnostic.with_fix(fix),
+                    None => diagnostic,
+                });
+            }
+        }
+        found
     }
 
     /// Check if there are any suppressions.
     pub fn has_suppressions(&self) -> bool {
-        !self.regions.is_empty()
+        !self.regions.is_empty() || !self.comment_tags.is_empty()
     }
 
     /// Parse @SuppressWarnings annotations from a CST tree.
@@ -355,26 +1198,59 @@ impl SuppressionContext {
     /// - `@SuppressWarnings("checkstyle:RuleName")`
     /// - `@SuppressWarnings({"checkstyle:Rule1", "checkstyle:Rule2"})`
     pub fn parse_suppress_warnings(&mut self, source: &str, root: &CstNode) {
+        self.parse_suppress_warnings_with(source, root, &SuppressWarningsAliases::default());
+    }
+
+    /// Like [`Self::parse_suppress_warnings`], also accepting the `aliases`
+    /// configured for checks, as checkstyle's `SuppressWarningsHolder` does.
+    ///
+    /// Each annotation suppresses its rules over the whole element it
+    /// annotates, members nested in it included. Rules are named as
+    /// checkstyle accepts them: with or without the `checkstyle:` prefix and
+    /// `Check` suffix, in any case, by alias, or `all`. Values can be
+    /// concatenated, and name `static final String` constants of the file.
+    pub fn parse_suppress_warnings_with(
+        &mut self,
+        source: &str,
+        root: &CstNode,
+        aliases: &SuppressWarningsAliases,
+    ) {
         // Quick check: skip the tree walk if source doesn't contain @SuppressWarnings
-        if !source.contains("@SuppressWarnings") {
+        if !source.contains("SuppressWarnings") {
             return;
         }
-        self.visit_for_annotations(source, root);
+        let mut constants = HashMap::new();
+        collect_string_constants(source, root, &mut constants);
+        let values = AnnotationValues {
+            source,
+            aliases,
+            constants,
+        };
+        self.visit_for_annotations(&values, root);
     }
 
     /// Recursively visit nodes to find @SuppressWarnings annotations.
-    fn visit_for_annotations(&mut self, source: &str, node: &CstNode) {
+    fn visit_for_annotations(&mut self, values: &AnnotationValues, node: &CstNode) {
         // Check if this node has annotations (modifiers that contain annotations)
         if matches!(
             node.kind(),
             "class_declaration"
                 | "interface_declaration"
                 | "enum_declaration"
+                | "enum_constant"
                 | "method_declaration"
                 | "constructor_declaration"
+                | "compact_constructor_declaration"
                 | "field_declaration"
+                | "constant_declaration"
                 | "annotation_type_declaration"
+                | "annotation_type_element_declaration"
                 | "record_declaration"
+                | "local_variable_declaration"
+                | "formal_parameter"
+                | "spread_parameter"
+                | "catch_formal_parameter"
+                | "resource"
         ) {
             // Look for modifiers containing annotations
             // In tree-sitter-java, modifiers is a child node with kind "modifiers"
@@ -382,7 +1258,7 @@ impl SuppressionContext {
             if let Some(modifiers) = modifiers {
                 for child in modifiers.children() {
                     if child.kind() == "annotation" || child.kind() == "marker_annotation" {
-                        self.process_annotation(source, &child, node);
+                        self.process_annotation(values, &child, node);
                     }
                 }
             }
@@ -390,29 +1266,25 @@ impl SuppressionContext {
             // Also check direct children - annotations might be direct children
             for child in node.children() {
                 if child.kind() == "annotation" || child.kind() == "marker_annotation" {
-                    self.process_annotation(source, &child, node);
-                }
-            }
-        }
-
-        // Also check for annotations directly on local variables
-        if node.kind() == "local_variable_declaration" {
-            // Local variables can have annotations before the type
-            for child in node.children() {
-                if child.kind() == "annotation" || child.kind() == "marker_annotation" {
-                    self.process_annotation(source, &child, node);
+                    self.process_annotation(values, &child, node);
                 }
             }
         }
 
         // Recurse into children
         for child in node.named_children() {
-            self.visit_for_annotations(source, &child);
+            self.visit_for_annotations(values, &child);
         }
     }
 
     /// Process a single annotation to check if it's @SuppressWarnings.
-    fn process_annotation(&mut self, source: &str, annotation: &CstNode, target: &CstNode) {
+    fn process_annotation(
+        &mut self,
+        values: &AnnotationValues,
+        annotation: &CstNode,
+        target: &CstNode,
+    ) {
+        let source = values.source;
         // Get the annotation name - try both "name" field and looking for identifier
         let name = annotation
             .child_by_field_name("name")
@@ -423,106 +1295,471 @@ impl SuppressionContext {
             .map(|n| &source[n.range()])
             .unwrap_or("");
 
-        if name != "SuppressWarnings" {
+        if name != "SuppressWarnings" && name != "java.lang.SuppressWarnings" {
             return;
         }
 
         // Get the annotation arguments
         if let Some(args) = annotation.child_by_field_name("arguments") {
             // Extract the string values from the annotation
-            let rules = self.extract_suppress_warnings_rules(source, &args);
+            let rules = values.suppress_warnings_rules(&args);
+            // Others, such as "unchecked", are for javac
+            let suppresses_rules = rules.iter().any(|rule| {
+                rule.explicit
+                    || codes::rule_code(&rule.name).is_some()
+                    || codes::rule_name(&rule.name).is_some()
+            });
+            let start = annotation.range().start();
+            let named = rules
+                .iter()
+                .map(|rule| SuppressedRule::new(&rule.name, Some(rule.range)))
+                .collect::<Vec<_>>();
             for rule in rules {
                 self.add_region(SuppressionRegion {
-                    rule,
+                    rule: rule.name,
                     start: target.range().start(),
                     end: Some(target.range().end()),
+                    directive: Some(start),
                 });
             }
+            if suppresses_rules {
+                self.add_directive(
+                    annotation.range(),
+                    named,
+                    annotation_note(source, annotation),
+                );
+            }
         }
     }
+}
+
+impl Default for SuppressionContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
+/// The prefix naming a check in `@SuppressWarnings` for checkstyle alone.
+const CHECKSTYLE_PREFIX: &str = "checkstyle:";
+
+/// How many constants a `@SuppressWarnings` value is followed through.
+const MAX_CONSTANT_DEPTH: usize = 8;
+
+/// A rule named in `@SuppressWarnings`.
+struct AnnotatedRule {
+    /// The rule's name, or `*` for `all`.
+    name: String,
+    /// The value naming it.
+    range: TextRange,
+    /// Whether it's named for checkstyle alone, with the `checkstyle:`
+    /// prefix or by alias.
+    explicit: bool,
+}
+
+/// Reads the values of the `@SuppressWarnings` annotations in a source.
+struct AnnotationValues<'a, 'b> {
+    source: &'a str,
+    aliases: &'b SuppressWarningsAliases,
+    /// The `static final String` constants of the source, by name.
+    constants: HashMap<&'a str, CstNode<'a>>,
+}
+
+impl AnnotationValues<'_, '_> {
     /// Extract rule names from @SuppressWarnings annotation arguments.
-    /// Handles both single strings and arrays: "checkstyle:Rule" or {"checkstyle:Rule1", "rule2"}
-    fn extract_suppress_warnings_rules(&self, source: &str, args: &CstNode) -> Vec<String> {
+    /// Handles both single values and arrays: "checkstyle:Rule" or {"checkstyle:Rule1", "rule2"}
+    fn suppress_warnings_rules(&self, args: &CstNode) -> Vec<AnnotatedRule> {
         let mut rules = Vec::new();
 
-        // Look for string literals or array initializers
         // In tree-sitter-java, the structure can be:
-        // - annotation_argument_list > element_value_pair > string_literal
-        // - annotation_argument_list > element_value_pair > element_value_array_initializer > string_literal
-        // - annotation_argument_list > string_literal (direct value)
-        // - annotation_argument_list > element_value_array_initializer > string_literal (direct array)
+        // - annotation_argument_list > element_value_pair > value
+        // - annotation_argument_list > element_value_pair > element_value_array_initializer > value
+        // - annotation_argument_list > value (direct value)
+        // - annotation_argument_list > element_value_array_initializer > value (direct array)
 
-        self.extract_rules_recursive(source, args, &mut rules);
+        self.extract_rules_recursive(args, &mut rules);
 
         rules
     }
 
-    /// Recursively extract string values from annotation arguments.
-    fn extract_rules_recursive(&self, source: &str, node: &CstNode, rules: &mut Vec<String>) {
+    /// Recursively extract the rules named by annotation arguments.
+    fn extract_rules_recursive(&self, node: &CstNode, rules: &mut Vec<AnnotatedRule>) {
         match node.kind() {
-            "string_literal" => {
-                if let Some(rule) = self.parse_suppress_warning_value(source, node) {
-                    rules.push(rule);
-                }
-            }
-            "element_value_array_initializer" | "array_initializer" => {
-                // Array of values
+            "annotation_argument_list"
+            | "element_value_array_initializer"
+            | "array_initializer" => {
                 for child in node.named_children() {
-                    self.extract_rules_recursive(source, &child, rules);
+                    self.extract_rules_recursive(&child, rules);
                 }
             }
             "element_value_pair" => {
                 // key=value pair - extract the value
                 if let Some(value) = node.child_by_field_name("value") {
-                    self.extract_rules_recursive(source, &value, rules);
+                    self.extract_rules_recursive(&value, rules);
                 }
             }
-            "annotation_argument_list" => {
-                // Recurse into all children
-                for child in node.named_children() {
-                    self.extract_rules_recursive(source, &child, rules);
+            _ => {
+                if let Some(rule) = self
+                    .string_value(node, 0)
+                    .and_then(|value| self.rule(&value, node.range()))
+                {
+                    rules.push(rule);
                 }
             }
-            _ => {
-                // Try children for other node types
-                for child in node.named_children() {
-                    self.extract_rules_recursive(source, &child, rules);
+        }
+    }
+
+    /// The string `expr` evaluates to, if it's built from string literals
+    /// and constants of the source; other values are skipped, as checkstyle
+    /// can't resolve them either.
+    fn string_value(&self, expr: &CstNode, depth: usize) -> Option<String> {
+        match expr.kind() {
+            "string_literal" => Some(string_literal_value(&self.source[expr.range()]).to_string()),
+            "parenthesized_expression" => self.string_value(&expr.named_children().next()?, depth),
+            "binary_expression" => {
+                let operator = expr.child_by_field_name("operator")?;
+                if &self.source[operator.range()] != "+" {
+                    return None;
                 }
+                let left = self.string_value(&expr.child_by_field_name("left")?, depth)?;
+                let right = self.string_value(&expr.child_by_field_name("right")?, depth)?;
+                Some(left + &right)
+            }
+            "identifier" | "field_access" if depth < MAX_CONSTANT_DEPTH => {
+                let name = match expr.kind() {
+                    "field_access" => expr.child_by_field_name("field")?,
+                    _ => *expr,
+                };
+                let value = self.constants.get(&self.source[name.range()])?;
+                self.string_value(value, depth + 1)
             }
+            _ => None,
         }
     }
 
-    /// Parse a single @SuppressWarnings string value.
-    /// Returns the rule name from formats like:
+    /// The rule a single @SuppressWarnings value names, from formats like:
     /// - "checkstyle:RuleName" (checkstyle-specific prefix)
-    /// - "RuleName" (works in both javac and checkstyle)
-    fn parse_suppress_warning_value(&self, source: &str, string_lit: &CstNode) -> Option<String> {
-        let text = &source[string_lit.range()];
-        // Remove quotes
-        let content = text.trim_matches('"');
-
-        if content.is_empty() {
+    /// - "RuleName" or "RuleNameCheck" (works in both javac and checkstyle)
+    /// - an alias configured for the rule
+    /// - "all" for every rule
+    ///
+    /// All of them are matched case-insensitively, as checkstyle does.
+    fn rule(&self, value: &str, range: TextRange) -> Option<AnnotatedRule> {
+        let (value, prefixed) = match value.get(..CHECKSTYLE_PREFIX.len()) {
+            Some(prefix) if prefix.eq_ignore_ascii_case(CHECKSTYLE_PREFIX) => {
+                (&value[CHECKSTYLE_PREFIX.len()..], true)
+            }
+            _ => (value, false),
+        };
+        if value.is_empty() {
             return None;
         }
 
-        // Strip optional checkstyle: prefix, otherwise use the value as-is
-        // This matches checkstyle behavior where the prefix is optional
-        let rule = content.strip_prefix("checkstyle:").unwrap_or(content);
+        let (name, explicit) = if value.eq_ignore_ascii_case("all") {
+            ("*", prefixed)
+        } else if let Some(rule) = self.aliases.resolve(value) {
+            (rule, true)
+        } else {
+            (strip_check_suffix(value), prefixed)
+        };
+        Some(AnnotatedRule {
+            name: name.to_string(),
+            range,
+            explicit,
+        })
+    }
+}
 
-        Some(rule.to_string())
+/// Collect the `static final String` constants declared in the types under
+/// `node`, by name, with the expression each is initialized to.
+fn collect_string_constants<'a>(
+    source: &'a str,
+    node: &CstNode<'a>,
+    constants: &mut HashMap<&'a str, CstNode<'a>>,
+) {
+    let kind = node.kind();
+    let is_constant = match kind {
+        // Fields of interfaces are constants without saying so
+        "constant_declaration" => true,
+        "field_declaration" => node
+            .children()
+            .find(|c| c.kind() == "modifiers")
+            .is_some_and(|modifiers| {
+                modifiers.children().any(|c| c.kind() == "static")
+                    && modifiers.children().any(|c| c.kind() == "final")
+            }),
+        _ => false,
+    };
+    let is_string = node
+        .child_by_field_name("type")
+        .is_some_and(|ty| matches!(&source[ty.range()], "String" | "java.lang.String"));
+    if is_constant && is_string {
+        for declarator in node.children_by_field_name("declarator") {
+            if let (Some(name), Some(value)) = (
+                declarator.child_by_field_name("name"),
+                declarator.child_by_field_name("value"),
+            ) {
+                constants.insert(&source[name.range()], value);
+            }
+        }
+        return;
+    }
+
+    // Constants are members of types, so method bodies are skipped
+    if kind == "program" || kind.ends_with("_declaration") || kind.ends_with("_body") {
+        for child in node.named_children() {
+            collect_string_constants(source, &child, constants);
+        }
     }
 }
 
-impl Default for SuppressionContext {
-    fn default() -> Self {
-        Self::new()
+/// The content of a string literal or text block, escapes as written.
+fn string_literal_value(text: &str) -> &str {
+    match text
+        .strip_prefix("\"\"\"")
+        .and_then(|block| block.strip_suffix("\"\"\""))
+    {
+        Some(block) => block.trim(),
+        None => text.trim_matches('"'),
+    }
+}
+
+/// A check's name without the `Check` suffix of its checkstyle class.
+fn strip_check_suffix(name: &str) -> &str {
+    name.strip_suffix("Check")
+        .filter(|name| !name.is_empty())
+        .unwrap_or(name)
+}
+
+/// What is required of suppressions themselves: that each says why, and
+/// that none is kept past the `until=YYYY-MM-DD` date written after it.
+#[derive(Debug, Clone, Default)]
+pub struct SuppressionPolicy {
+    /// Report suppressions without a reason.
+    pub require_reason: bool,
+    /// Today as `YYYY-MM-DD`, to report suppressions that expired before
+    /// it. `None` leaves expiry dates unchecked.
+    pub today: Option<String>,
+}
+
+/// Violation: a suppression doesn't meet the [`SuppressionPolicy`].
+#[derive(Debug, Clone)]
+pub enum SuppressionPolicyViolation {
+    MissingReason,
+    Expired { until: String },
+    InvalidExpiry { until: String },
+}
+
+impl Violation for SuppressionPolicyViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::None;
+
+    fn message(&self) -> String {
+        match self {
+            Self::MissingReason => "Suppression has no reason".to_string(),
+            Self::Expired { until } => format!("Suppression expired on {until}"),
+            Self::InvalidExpiry { until } => {
+                format!("Suppression has an invalid expiry date '{until}', expected YYYY-MM-DD")
+            }
+        }
+    }
+}
+
+impl SuppressionPolicy {
+    /// The name its diagnostics are reported under.
+    pub const RULE_NAME: &'static str = "SuppressionPolicy";
+
+    /// How `directive` falls short of the policy.
+    pub fn check(&self, directive: &SuppressionDirective) -> Vec<Diagnostic> {
+        let mut until = None;
+        let mut reason = Vec::new();
+        for word in directive.note.split_whitespace() {
+            match word.strip_prefix("until=") {
+                Some(date) => until = Some(date),
+                None => reason.push(word),
+            }
+        }
+
+        let at = TextRange::empty(directive.start);
+        let mut found = Vec::new();
+        if self.require_reason && reason.is_empty() {
+            found.push(Diagnostic::new(
+                SuppressionPolicyViolation::MissingReason,
+                at,
+            ));
+        }
+        if let (Some(today), Some(until)) = (&self.today, until) {
+            let until = until.to_string();
+            if !is_date(&until) {
+                found.push(Diagnostic::new(
+                    SuppressionPolicyViolation::InvalidExpiry { until },
+                    at,
+                ));
+            } else if until < *today {
+                found.push(Diagnostic::new(
+                    SuppressionPolicyViolation::Expired { until },
+                    at,
+                ));
+            }
+        }
+        found
+    }
+}
+
+/// Violation: a suppression suppressed nothing; see
+/// [`SuppressionContext::unused`].
+#[derive(Debug, Clone)]
+pub struct UnusedSuppressionViolation {
+    /// The rule it names, `*` for all of them.
+    pub rule: String,
+}
+
+impl UnusedSuppressionViolation {
+    /// The name these diagnostics are reported under.
+    pub const RULE_NAME: &'static str = "UnusedSuppression";
+}
+
+impl Violation for UnusedSuppressionViolation {
+    const FIX_AVAILABILITY: FixAvailability = FixAvailability::Sometimes;
+
+    fn message(&self) -> String {
+        if self.rule == "*" {
+            "Unused suppression of all rules".to_string()
+        } else {
+            format!("Unused suppression of {}", self.rule)
+        }
+    }
+}
+
+/// What to delete to remove the comment or annotation at `range`: its whole
+/// line if nothing else is on it, otherwise it and the blanks beside it. A
+/// line comment after it, such as the reason for an annotation, goes too.
+fn line_deletion(source: &str, range: TextRange) -> TextRange {
+    let (start, end) = (range.start().to_usize(), range.end().to_usize());
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let next_line = source[end..]
+        .find('\n')
+        .map_or(source.len(), |i| end + i + 1);
+    let after = &source[end..next_line];
+    let before = &source[line_start..start];
+    let rest_is_blank = after.trim().is_empty() || after.trim_start().starts_with("//");
+
+    let (start, end) = if before.trim().is_empty() && rest_is_blank {
+        (line_start, next_line)
+    } else if rest_is_blank {
+        // Up to the line break
+        let line_end = next_line - (after.len() - after.trim_end().len());
+        (line_start + before.trim_end().len(), line_end)
+    } else {
+        (start, end + (after.len() - after.trim_start().len()))
+    };
+    TextRange::new(TextSize::new(start as u32), TextSize::new(end as u32))
+}
+
+/// What to delete to remove the element at `range` from a list: it and the
+/// comma after it, or before it if it is last.
+fn element_deletion(source: &str, range: TextRange) -> TextRange {
+    let (start, end) = (range.start().to_usize(), range.end().to_usize());
+    let after = source[end..].trim_start();
+    let (start, end) = if let Some(rest) = after.strip_prefix(',') {
+        (start, source.len() - rest.trim_start().len())
+    } else {
+        let before = source[..start].trim_end();
+        match before.strip_suffix(',') {
+            Some(before) => (before.len(), end),
+            None => (start, end),
+        }
+    };
+    TextRange::new(TextSize::new(start as u32), TextSize::new(end as u32))
+}
+
+/// Whether `text` is a date as `YYYY-MM-DD`, which compare in date order.
+fn is_date(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    bytes.len() == 10
+        && bytes.iter().enumerate().all(|(i, &b)| match i {
+            4 | 7 => b == b'-',
+            _ => b.is_ascii_digit(),
+        })
+}
+
+/// The comment explaining the annotation, after it on its line or on the
+/// line above on its own.
+fn annotation_note<'a>(source: &'a str, annotation: &CstNode) -> &'a str {
+    let (start, end) = (
+        annotation.range().start().to_usize(),
+        annotation.range().end().to_usize(),
+    );
+    let line_end = source[end..].find('\n').map_or(source.len(), |i| end + i);
+    if let Some(comment) = source[end..line_end].trim().strip_prefix("//") {
+        return comment;
+    }
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let above = source[..line_start.saturating_sub(1)]
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .trim();
+    if line_start > 0
+        && source[line_start..start].trim().is_empty()
+        && let Some(comment) = above.strip_prefix("//")
+    {
+        return comment;
+    }
+    ""
+}
+
+/// Call `f` with the offset and text of each `//` and `/* */` comment in
+/// `source`, a line comment without its line break.
+///
+/// Comment markers inside string literals are taken for comments too, as
+/// checkstyle's plain text filter does.
+fn for_each_comment(source: &str, mut f: impl FnMut(usize, &str)) {
+    // Use find() to skip to potential comment locations instead of byte-by-byte iteration
+    let bytes = source.as_bytes();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        // Skip to next '/' character - comments always start with '/'
+        let Some(slash_offset) = bytes[pos..].iter().position(|&b| b == b'/') else {
+            break;
+        };
+        pos += slash_offset;
+
+        // Check for line comment (//)
+        if pos + 1 < bytes.len() && bytes[pos + 1] == b'/' {
+            // Find end of line
+            let line_end = bytes[pos..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map(|i| pos + i)
+                .unwrap_or(bytes.len());
+            f(pos, &source[pos..line_end]);
+            pos = line_end + 1;
+            continue;
+        }
+
+        // Check for block comment (/*)
+        if pos + 1 < bytes.len() && bytes[pos + 1] == b'*' {
+            // Find end of block comment - use find for */ instead of byte-by-byte
+            if let Some(end_offset) = source[pos + 2..].find("*/") {
+                let comment_end = pos + 2 + end_offset + 2;
+                f(pos, &source[pos..comment_end]);
+                pos = comment_end;
+                continue;
+            } else {
+                // Unclosed comment, skip to end
+                break;
+            }
+        }
+
+        // Not a comment start, move past this '/'
+        pos += 1;
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use lintal_source_file::OneIndexed;
 
     #[test]
     fn test_parse_line_comment_suppression() {
@@ -590,6 +1827,217 @@ class Foo {
         assert!(ctx.is_suppressed("WhitespaceAround", end_pos));
     }
 
+    #[test]
+    fn test_disable_file_directive() {
+        let source = r#"
+// lintal: disable-file MagicNumber, WS001
+class Foo {
+    int x=42;
+}
+"#;
+
+        let ctx = SuppressionContext::from_source(source, &[]);
+        let pos = TextSize::new(source.find("42").unwrap() as u32);
+        assert!(ctx.is_suppressed("MagicNumber", pos));
+        assert!(ctx.is_suppressed("WhitespaceAround", pos));
+        assert!(ctx.is_suppressed("MagicNumber", TextSize::new(0)));
+        assert!(!ctx.is_suppressed("ParenPad", pos));
+
+        // Without rules every rule is disabled
+        let ctx = SuppressionContext::from_source("/* lintal: disable-file */\nclass A {}", &[]);
+        assert!(ctx.is_suppressed("ParenPad", TextSize::new(30)));
+    }
+
+    #[test]
+    fn test_disable_next_line_directive() {
+        let source = r#"
+class Foo {
+    // lintal: disable-next-line MagicNumber because it is the answer
+    int x = 42;
+    int y = 43;
+    /* lintal: disable-next-line */ int z = 44;
+    int w = 45;
+}
+"#;
+
+        let ctx = SuppressionContext::from_source(source, &[]);
+        let at = |text| TextSize::new(source.find(text).unwrap() as u32);
+        assert!(ctx.is_suppressed("MagicNumber", at("42")));
+        assert!(!ctx.is_suppressed("WhitespaceAround", at("42")));
+        assert!(!ctx.is_suppressed("MagicNumber", at("43")));
+        // The line after the comment, not the rest of its own line
+        assert!(!ctx.is_suppressed("MagicNumber", at("44")));
+        assert!(ctx.is_suppressed("ParenPad", at("45")));
+    }
+
+    #[test]
+    fn test_directive_must_start_comment() {
+        let source = "// see lintal: disable-file MagicNumber\nclass A { int x = 42; }";
+        let ctx = SuppressionContext::from_source(source, &[]);
+        assert!(!ctx.has_suppressions());
+    }
+
+    #[test]
+    fn test_suppression_directives() {
+        let source = r#"
+// lintal: disable-file MagicNumber -- JIRA-123
+class Foo {
+    // CHECKSTYLE:OFF:WhitespaceAround — generated until=2025-12-31
+    void method( int x ) { }
+    // CHECKSTYLE:ON:WhitespaceAround
+
+    @SuppressWarnings("checkstyle:MethodLength") // long table
+    void table() { }
+
+    // legacy API
+    @SuppressWarnings("FinalParameters")
+    void legacy(int x) { }
+
+    @SuppressWarnings("unchecked")
+    void raw() { }
+}
+"#;
+
+        let filter = PlainTextCommentFilterConfig::checkstyle_default();
+        let mut ctx = SuppressionContext::from_source(source, &[filter]);
+        let result = lintal_java_parser::JavaParser::new().parse(source).unwrap();
+        ctx.parse_suppress_warnings(source, &CstNode::new(result.tree.root_node(), source));
+
+        let notes: Vec<&str> = ctx
+            .directives()
+            .iter()
+            .map(|directive| directive.note.as_str())
+            .collect();
+        assert_eq!(
+            notes,
+            [
+                "generated until=2025-12-31",
+                "JIRA-123",
+                "long table",
+                "legacy API"
+            ]
+        );
+        assert_eq!(
+            ctx.directives()[0].start,
+            TextSize::new(source.find("// CHECKSTYLE:OFF").unwrap() as u32)
+        );
+    }
+
+    #[test]
+    fn test_suppression_policy() {
+        let policy = SuppressionPolicy {
+            require_reason: true,
+            today: Some("2026-01-15".to_string()),
+        };
+        let check = |note: &str| -> Vec<String> {
+            let directive = SuppressionDirective {
+                start: TextSize::new(0),
+                note: note.to_string(),
+                ..Default::default()
+            };
+            policy
+                .check(&directive)
+                .into_iter()
+                .map(|diagnostic| diagnostic.kind.body)
+                .collect()
+        };
+
+        assert!(check("JIRA-123").is_empty());
+        assert!(check("JIRA-123 until=2026-01-15").is_empty());
+        assert_eq!(check(""), ["Suppression has no reason"]);
+        assert_eq!(
+            check("until=2025-12-31"),
+            [
+                "Suppression has no reason",
+                "Suppression expired on 2025-12-31"
+            ]
+        );
+        assert_eq!(
+            check("JIRA-123 until=31/12/2025"),
+            ["Suppression has an invalid expiry date '31/12/2025', expected YYYY-MM-DD"]
+        );
+
+        // Expiry dates are only checked against a date
+        let lenient = SuppressionPolicy::default();
+        let directive = SuppressionDirective {
+            start: TextSize::new(0),
+            note: "until=2000-01-01".to_string(),
+            ..Default::default()
+        };
+        assert!(lenient.check(&directive).is_empty());
+    }
+
+    #[test]
+    fn test_unused_suppressions() {
+        let source = r#"class Foo {
+    // CHECKSTYLE:OFF:WhitespaceAround
+    void method( int x ) { }
+    // CHECKSTYLE:ON:WhitespaceAround
+
+    @SuppressWarnings({"unchecked", "checkstyle:MethodLength"})
+    void raw() { }
+
+    // lintal: disable-next-line UpperEll, MagicNumber
+    long x = 1l;
+
+    @SuppressWarnings({"checkstyle:FinalParameters", "checkstyle:ParameterNumber"}) // legacy
+    void legacy(int x) { }
+}
+"#;
+        let filter = PlainTextCommentFilterConfig::checkstyle_default();
+        let mut ctx = SuppressionContext::from_source(source, &[filter]);
+        let result = lintal_java_parser::JavaParser::new().parse(source).unwrap();
+        ctx.parse_suppress_warnings(source, &CstNode::new(result.tree.root_node(), source));
+        let at = |text: &str| TextSize::new(source.find(text).unwrap() as u32);
+        // "unchecked" is for javac
+        let is_tracked = |rule: &str| rule != "unchecked";
+        let fixed = |found: &[Diagnostic]| {
+            crate::fix::apply_fixes(source, found.iter().filter_map(|d| d.fix.as_ref())).code
+        };
+
+        let mut usage = SuppressionUsage::default();
+        assert!(ctx.record_suppressed("WhitespaceAround", "", at("( int"), &mut usage));
+        assert!(ctx.record_suppressed("UpperEll", "", at("1l"), &mut usage));
+        assert!(ctx.record_suppressed("FinalParameters", "", at("int x) {"), &mut usage));
+        assert!(!ctx.record_suppressed("UpperEll", "", at("void raw"), &mut usage));
+        let found = ctx.unused(source, &usage, is_tracked);
+        let messages: Vec<&str> = found.iter().map(|d| d.kind.body.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Unused suppression of MagicNumber",
+                "Unused suppression of MethodLength",
+                "Unused suppression of ParameterNumber"
+            ]
+        );
+        // Rules are only deleted from comments with the whole comment
+        assert!(found[0].fix.is_none());
+        assert_eq!(
+            fixed(&found),
+            source
+                .replace(r#", "checkstyle:MethodLength""#, "")
+                .replace(r#", "checkstyle:ParameterNumber""#, "")
+        );
+
+        // Directives that suppress nothing are deleted whole
+        let found = ctx.unused(source, &SuppressionUsage::default(), is_tracked);
+        assert_eq!(found.len(), 6);
+        assert_eq!(
+            fixed(&found),
+            r#"class Foo {
+    void method( int x ) { }
+
+    @SuppressWarnings({"unchecked"})
+    void raw() { }
+
+    long x = 1l;
+
+    void legacy(int x) { }
+}
+"#
+        );
+    }
+
     #[test]
     fn test_custom_pattern() {
         let source = r#"
@@ -610,6 +2058,17 @@ class Foo {
         assert!(ctx.is_suppressed("ParenPad", suppressed_pos));
     }
 
+    #[test]
+    fn test_suppression_by_rule_code() {
+        let source = "class Foo {\n    // CHECKSTYLE:OFF:WS001\n    void method( int x ) { }\n}\n";
+        let filter = PlainTextCommentFilterConfig::checkstyle_default();
+        let ctx = SuppressionContext::from_source(source, &[filter]);
+
+        let pos = TextSize::new(source.find("void method").unwrap() as u32);
+        assert!(ctx.is_suppressed("WhitespaceAround", pos));
+        assert!(!ctx.is_suppressed("WhitespaceAfter", pos));
+    }
+
     #[test]
     fn test_suppress_warnings_annotation() {
         use lintal_java_parser::JavaParser;
@@ -736,6 +2195,91 @@ class Foo {
         );
     }
 
+    #[test]
+    fn test_suppress_warnings_checkstyle_forms() {
+        use lintal_java_parser::JavaParser;
+
+        let source = r#"
+class Foo {
+    private static final String PREFIX = "checkstyle:";
+    private static final String LENGTH = PREFIX + "MethodLength";
+
+    @SuppressWarnings({LENGTH, Names.PARAMS, ("Checkstyle:" + "MagicNumberCheck")})
+    void method(@SuppressWarnings("FinalParameters") int a, int b) {
+        int x = 42;
+    }
+
+    @SuppressWarnings("long")
+    void other() {
+    }
+
+    @SuppressWarnings(value = "checkstyle:all")
+    class Inner {
+        void nested() {
+        }
+    }
+
+    interface Names {
+        String PARAMS = "ParameterNumber";
+    }
+}
+"#;
+
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).expect("Failed to parse");
+        let root = CstNode::new(result.tree.root_node(), source);
+
+        let aliases = SuppressWarningsAliases::new().with_alias(
+            "com.puppycrawl.tools.checkstyle.checks.sizes.MethodLengthCheck",
+            "long",
+        );
+        let mut ctx = SuppressionContext::new();
+        ctx.parse_suppress_warnings_with(source, &root, &aliases);
+
+        let at = |text: &str| TextSize::new(source.find(text).unwrap() as u32);
+        let body = at("int x");
+        assert!(ctx.is_suppressed("MethodLength", body));
+        assert!(ctx.is_suppressed("ParameterNumber", body));
+        assert!(ctx.is_suppressed("MagicNumber", body));
+        assert!(!ctx.is_suppressed("FinalParameters", body));
+        assert!(ctx.is_suppressed("FinalParameters", at("int a")));
+        assert!(!ctx.is_suppressed("FinalParameters", at("int b")));
+
+        assert!(ctx.is_suppressed("MethodLength", at("void other")));
+        assert!(!ctx.is_suppressed("MagicNumber", at("void other")));
+
+        assert!(ctx.is_suppressed("MethodLength", at("void nested")));
+        assert!(ctx.is_suppressed("JavadocMethod", at("void nested")));
+        assert!(!ctx.is_suppressed("JavadocMethod", at("interface Names")));
+
+        let rules: Vec<&str> = ctx.directives()[0]
+            .rules
+            .iter()
+            .map(|rule| rule.name.as_str())
+            .collect();
+        assert_eq!(rules, ["MethodLength", "ParameterNumber", "MagicNumber"]);
+    }
+
+    #[test]
+    fn test_suppress_warnings_aliases_from_checkstyle() {
+        let config = CheckstyleConfig::parse(
+            r#"<module name="Checker">
+    <module name="TreeWalker">
+        <module name="SuppressWarningsHolder">
+            <property name="aliasList"
+                value="com.puppycrawl.tools.checkstyle.checks.sizes.MethodLengthCheck=long, ParameterNumber=params"/>
+        </module>
+    </module>
+</module>"#,
+        )
+        .unwrap();
+
+        let aliases = SuppressWarningsAliases::from_checkstyle(&config);
+        assert_eq!(aliases.resolve("LONG"), Some("MethodLength"));
+        assert_eq!(aliases.resolve("params"), Some("ParameterNumber"));
+        assert_eq!(aliases.resolve("MethodLength"), None);
+    }
+
     // Tests for FileSuppressionsConfig (suppressions.xml parsing)
 
     #[test]
@@ -856,6 +2400,68 @@ class Foo {
         assert!(!config.is_suppressed("aeron-client/src/main/java/Foo.java", "FinalLocalVariable"));
     }
 
+    #[test]
+    fn test_file_suppressions_checkstyle_attributes() {
+        let xml = r#"<suppressions>
+    <!-- <suppress files="Commented" checks="."/> -->
+    <suppress
+        files='Foo\.java'
+        checks="LineLength|MagicNumber"
+        lines="10,20-30"
+        columns="5"/>
+    <suppress message="^Unused import" />
+    <suppress files="Bar" message="a &amp; b"></suppress>
+    <suppress id="lineLengthMain" files="."/>
+    <suppress files="Baz" checks="." lines="1-x"/>
+    <suppress-xpath files="Qux" checks="." query="//METHOD_DEF"/>
+</suppressions>"#;
+        let config = FileSuppressionsConfig::from_xml(xml);
+        assert_eq!(config.len(), 3);
+
+        let at = |line, column| LineColumn {
+            line: OneIndexed::new(line).unwrap(),
+            column: OneIndexed::new(column).unwrap(),
+        };
+        let foo = &config.rules()[0];
+        assert_eq!(foo.line, Some(3));
+        assert!(!foo.is_unconditional());
+        assert!(foo.suppresses("src/Foo.java", "LineLength", at(10, 5), "Line is longer"));
+        assert!(foo.suppresses(
+            "src/Foo.java",
+            "MagicNumber",
+            at(25, 5),
+            "'3' is a magic number"
+        ));
+        assert!(!foo.suppresses("src/Foo.java", "LineLength", at(11, 5), "Line is longer"));
+        assert!(!foo.suppresses("src/Foo.java", "LineLength", at(10, 6), "Line is longer"));
+        assert!(!foo.is_suppressed("src/Foo.java", "LineLength"));
+
+        // Without `files` a rule applies to every file
+        let unused = &config.rules()[1];
+        assert!(unused.suppresses("A.java", "UnusedImports", at(1, 1), "Unused import - a.B."));
+        assert!(!unused.suppresses("A.java", "UnusedImports", at(1, 1), "Duplicate import"));
+
+        assert_eq!(
+            config.rules()[2]
+                .message_pattern
+                .as_ref()
+                .map(Regex::as_str),
+            Some("a & b")
+        );
+        assert!(config.has_conditional("src/Bar.java"));
+        assert!(!config.is_file_fully_suppressed("src/Bar.java"));
+    }
+
+    #[test]
+    fn test_number_ranges() {
+        let ranges = NumberRanges::parse("3, 7-9").unwrap();
+        assert!(ranges.contains(3));
+        assert!(ranges.contains(8));
+        assert!(!ranges.contains(4));
+        assert!(NumberRanges::parse("1-").is_none());
+        assert!(NumberRanges::parse("").is_none());
+    }
+
     #[test]
     fn test_file_suppression_rule_new() {
         // Valid patterns
@@ -885,4 +2491,172 @@ class Foo {
         let config = FileSuppressionsConfig::from_xml(xml);
         assert!(config.is_empty());
     }
+
+    #[test]
+    fn test_filters_from_checkstyle() {
+        let config = CheckstyleConfig::parse(
+            r#"<module name="Checker">
+    <module name="SuppressWithPlainTextCommentFilter">
+        <property name="offCommentFormat" value="LINT-OFF"/>
+        <property name="onCommentFormat" value="LINT-ON"/>
+    </module>
+    <module name="SuppressionFilter">
+        <property name="file" value="${config_loc}/suppressions.xml"/>
+    </module>
+</module>"#,
+        )
+        .unwrap();
+
+        let filters = PlainTextCommentFilterConfig::from_checkstyle(&config);
+        assert_eq!(filters.len(), 2);
+        assert_eq!(filters[1].off_pattern.as_str(), "LINT-OFF");
+
+        let files = FileSuppressionsConfig::files_from_checkstyle(&config, Path::new("config"));
+        assert_eq!(files, vec![PathBuf::from("config/suppressions.xml")]);
+    }
+
+    #[test]
+    fn test_comment_filter_toggle_check_and_message_format() {
+        let source = r#"
+class Foo {
+    // CHECKSTYLE.OFF: MagicNumber - legacy constants
+    int a = 42;
+    // CHECKSTYLE.ON: MagicNumber
+    int b = 43;
+}
+"#;
+        let mut filter =
+            CommentFilterConfig::toggle(r"CHECKSTYLE.OFF: ([\w|]+)", r"CHECKSTYLE.ON: ([\w|]+)")
+                .unwrap();
+        filter.check_format = "$1".to_string();
+        filter.message_format = Some("'42'".to_string());
+        let mut ctx = SuppressionContext::new();
+        ctx.parse_comment_filters(source, &[filter]);
+        let at = |text: &str| TextSize::new(source.find(text).unwrap() as u32);
+        let mut usage = SuppressionUsage::default();
+
+        assert!(ctx.has_suppressions());
+        assert!(ctx.record_suppressed(
+            "MagicNumber",
+            "'42' is a magic number.",
+            at("42"),
+            &mut usage
+        ));
+        // Only the messages messageFormat matches
+        assert!(!ctx.record_suppressed(
+            "MagicNumber",
+            "'7' is a magic number.",
+            at("42"),
+            &mut usage
+        ));
+        // Only the rules checkFormat matches
+        assert!(!ctx.record_suppressed("UpperEll", "'42' ends in l", at("42"), &mut usage));
+        // Turned back on
+        assert!(!ctx.record_suppressed(
+            "MagicNumber",
+            "'42' is a magic number.",
+            at("43"),
+            &mut usage
+        ));
+        assert_eq!(
+            ctx.suppressed_by("MagicNumber", "'42' is a magic number.", at("42")),
+            Some(at("// CHECKSTYLE.OFF"))
+        );
+
+        let directive = &ctx.directives()[0];
+        assert_eq!(directive.note, "legacy constants");
+        assert_eq!(directive.rules, [SuppressedRule::new("MagicNumber", None)]);
+        assert_eq!(directive.ranges.len(), 2);
+    }
+
+    #[test]
+    fn test_comment_filter_toggle_last_matching_comment_decides() {
+        let source = r#"
+class Foo {
+    // CHECKSTYLE:OFF
+    int a = 42;
+    /* CHECKSTYLE:ON */
+    int b = 43;
+}
+"#;
+        let mut filter = CommentFilterConfig::toggle("CHECKSTYLE:OFF", "CHECKSTYLE:ON").unwrap();
+        let at = |text: &str| TextSize::new(source.find(text).unwrap() as u32);
+
+        let mut ctx = SuppressionContext::new();
+        ctx.parse_comment_filters(source, std::slice::from_ref(&filter));
+        assert!(ctx.is_suppressed("MagicNumber", at("42")));
+        assert!(!ctx.is_suppressed("MagicNumber", at("43")));
+
+        // Block comments are ignored without checkC
+        filter.check_c = false;
+        let mut ctx = SuppressionContext::new();
+        ctx.parse_comment_filters(source, &[filter]);
+        assert!(ctx.is_suppressed("MagicNumber", at("43")));
+    }
+
+    #[test]
+    fn test_comment_filter_nearby_influence() {
+        let source = r#"
+class Foo {
+    int a = 42; // SUPPRESS CHECKSTYLE MagicNumber
+    int b = 43;
+    // ALLOW MagicNumber FOR 2 LINES
+    int c = 44;
+    int d = 45;
+    int e = 46;
+}
+"#;
+        let default = CommentFilterConfig::nearby(r"SUPPRESS CHECKSTYLE (\w+)", "0").unwrap();
+        let mut lines = CommentFilterConfig::nearby(r"ALLOW (\w+) FOR (\d+) LINES", "$2").unwrap();
+        lines.check_format = "$1".to_string();
+        let mut ctx = SuppressionContext::new();
+        ctx.parse_comment_filters(source, &[default, lines]);
+        let at = |text: &str| TextSize::new(source.find(text).unwrap() as u32);
+
+        assert!(ctx.is_suppressed("MagicNumber", at("42")));
+        assert!(!ctx.is_suppressed("MagicNumber", at("43")));
+        assert!(ctx.is_suppressed("MagicNumber", at("44")));
+        assert!(ctx.is_suppressed("MagicNumber", at("45")));
+        assert!(!ctx.is_suppressed("MagicNumber", at("46")));
+        assert!(!ctx.is_suppressed("UpperEll", at("44")));
+    }
+
+    #[test]
+    fn test_comment_filters_from_checkstyle() {
+        let config = CheckstyleConfig::parse(
+            r#"<module name="Checker">
+    <module name="TreeWalker">
+        <module name="SuppressionCommentFilter">
+            <property name="offCommentFormat" value="LINT-OFF (\w+)"/>
+            <property name="onCommentFormat" value="LINT-ON (\w+)"/>
+            <property name="checkFormat" value="$1"/>
+            <property name="checkCPP" value="false"/>
+        </module>
+        <module name="SuppressWithNearbyCommentFilter">
+            <property name="messageFormat" value="magic"/>
+        </module>
+        <module name="SuppressionCommentFilter">
+            <property name="idFormat" value="strict"/>
+        </module>
+        <module name="MagicNumber"/>
+    </module>
+</module>"#,
+        )
+        .unwrap();
+
+        let filters = CommentFilterConfig::from_checkstyle(&config);
+        assert_eq!(filters.len(), 2);
+        assert!(matches!(
+            &filters[0].kind,
+            CommentFilterKind::Toggle { off_pattern, .. } if off_pattern.as_str() == r"LINT-OFF (\w+)"
+        ));
+        assert_eq!(filters[0].check_format, "$1");
+        assert!(filters[0].check_c && !filters[0].check_cpp);
+        assert!(matches!(
+            &filters[1].kind,
+            CommentFilterKind::Nearby { influence_format, .. } if influence_format == "0"
+        ));
+        assert_eq!(filters[1].check_format, ".*");
+        assert_eq!(filters[1].message_format.as_deref(), Some("magic"));
+    }
 }
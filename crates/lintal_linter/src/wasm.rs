@@ -0,0 +1,561 @@
+//! Rules loaded from sandboxed WebAssembly plugins.
+//!
+//! A `.wasm` plugin can only call the host functions described in
+//! [`lintal_plugin_api::abi::wasm`]: it sees the nodes and source of the file
+//! being checked and can report violations, but gets no WASI, so it has no
+//! filesystem, network or clock. Each check runs on a fuel budget and each
+//! instance under a memory limit, so a misbehaving rule loses its violations
+//! for that node rather than hanging or exhausting lintal.
+//!
+//! Wasmtime stores are single-threaded, so every configured rule keeps a pool
+//! of instances and each check borrows one.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use lintal_diagnostics::Diagnostic;
+use lintal_java_cst::CstNode;
+use lintal_plugin_api::abi::{ABI_VERSION, NodeHandle, wasm as abi};
+use lintal_text_size::TextRange;
+use wasmtime::{
+    Caller, Config, Engine, Extern, Instance, Linker, Memory, Module, Store, StoreLimits,
+    StoreLimitsBuilder, TypedFunc, WasmParams, WasmResults,
+};
+
+use crate::plugin::{HostState, PluginError, PluginRuleInfo};
+use crate::registry::RuleFactory;
+use crate::{CheckContext, Properties, Rule};
+
+/// Fuel for one call into a plugin, roughly a count of wasm instructions.
+const FUEL_PER_CALL: u64 = 10_000_000;
+
+/// Linear memory limit of a plugin instance.
+const MAX_MEMORY: usize = 64 << 20;
+
+/// A loaded WebAssembly plugin.
+pub struct WasmPlugin {
+    path: PathBuf,
+    name: String,
+    rules: Vec<PluginRuleInfo>,
+    engine: Engine,
+    module: Module,
+    linker: Linker<Sandbox>,
+}
+
+impl WasmPlugin {
+    /// Compile the plugin at `path` and read its rules.
+    pub fn load(path: impl AsRef<Path>) -> Result<Arc<Self>, PluginError> {
+        let path = path.as_ref().to_path_buf();
+        let error = |e: wasmtime::Error| PluginError::Wasm {
+            path: path.clone(),
+            message: format!("{e:#}"),
+        };
+
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(error)?;
+        let module = Module::from_file(&engine, &path).map_err(error)?;
+        // This is the sandbox: the lintal host functions are all a plugin gets
+        if let Some(import) = module
+            .imports()
+            .find(|import| import.module() != abi::HOST_MODULE)
+        {
+            return Err(PluginError::Wasm {
+                path: path.clone(),
+                message: format!(
+                    "imports `{}::{}`, which plugins have no access to",
+                    import.module(),
+                    import.name()
+                ),
+            });
+        }
+        let mut linker = Linker::new(&engine);
+        define_host_functions(&mut linker).map_err(error)?;
+
+        let mut plugin = Self {
+            path: path.clone(),
+            name: String::new(),
+            rules: Vec::new(),
+            engine,
+            module,
+            linker,
+        };
+        let mut instance = plugin.instantiate().map_err(error)?;
+        let found: u32 = instance.call(abi::EXPORT_ABI_VERSION, ()).map_err(error)?;
+        if found != ABI_VERSION {
+            return Err(PluginError::AbiVersion {
+                path: path.clone(),
+                found,
+                expected: ABI_VERSION,
+            });
+        }
+
+        let description = instance.describe().map_err(error)?;
+        let mut lines = description.lines();
+        plugin.name = lines.next().unwrap_or_default().to_string();
+        plugin.rules = lines
+            .enumerate()
+            .map(|(index, line)| {
+                let (name, kinds) = line.split_once('\t').unwrap_or((line, ""));
+                PluginRuleInfo {
+                    name: leak(name),
+                    relevant_kinds: Box::leak(
+                        kinds
+                            .split(',')
+                            .filter(|kind| !kind.is_empty())
+                            .map(leak)
+                            .collect::<Box<[_]>>(),
+                    ),
+                    index,
+                }
+            })
+            .collect();
+        Ok(Arc::new(plugin))
+    }
+
+    /// The plugin's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The path the plugin was loaded from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Module names of the rules the plugin provides.
+    pub fn rule_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.rules.iter().map(|rule| rule.name)
+    }
+
+    /// Factories for every rule in the plugin, for registering with a
+    /// [`RuleRegistry`](crate::RuleRegistry).
+    pub(crate) fn factories(
+        self: &Arc<Self>,
+    ) -> impl Iterator<Item = (&'static str, RuleFactory)> + '_ {
+        self.rules.iter().map(|rule| {
+            let plugin = Arc::clone(self);
+            let info = *rule;
+            let factory: RuleFactory = Box::new(move |properties: &Properties| {
+                let properties: Vec<(String, String)> = properties
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect();
+                // The first instance is created eagerly to validate the properties
+                let instance = plugin.configure(info.index, &properties)?;
+                Some(Box::new(WasmRule {
+                    plugin: Arc::clone(&plugin),
+                    info,
+                    properties,
+                    pool: Mutex::new(vec![instance]),
+                }) as Box<dyn Rule>)
+            });
+            (rule.name, factory)
+        })
+    }
+
+    fn instantiate(&self) -> wasmtime::Result<WasmInstance> {
+        let sandbox = Sandbox {
+            limits: StoreLimitsBuilder::new().memory_size(MAX_MEMORY).build(),
+            state: 0,
+            source: String::new(),
+            file_id: 0,
+        };
+        let mut store = Store::new(&self.engine, sandbox);
+        store.limiter(|sandbox| &mut sandbox.limits);
+        // Start functions run on the same budget as any other call
+        store.set_fuel(FUEL_PER_CALL)?;
+        let instance = self.linker.instantiate(&mut store, &self.module)?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| wasmtime::Error::msg("plugin does not export its memory"))?;
+        let check = instance.get_typed_func(&mut store, abi::EXPORT_CHECK)?;
+        Ok(WasmInstance {
+            store,
+            instance,
+            memory,
+            check,
+            rule: 0,
+            handle: 0,
+        })
+    }
+
+    /// Create an instance with rule `index` configured, or `None` if the
+    /// plugin rejects the properties or fails.
+    fn configure(&self, index: usize, properties: &[(String, String)]) -> Option<WasmInstance> {
+        let mut instance = self.instantiate().ok()?;
+        instance
+            .create(index, properties)
+            .is_ok_and(|created| created)
+            .then_some(instance)
+    }
+}
+
+/// Store data of a plugin instance.
+struct Sandbox {
+    limits: StoreLimits,
+    /// The [`HostState`] of the check in progress, or 0 between checks.
+    state: usize,
+    /// The file being checked, copied so that `file_id` can tell when it
+    /// changes.
+    source: String,
+    file_id: u32,
+}
+
+/// A plugin instance with one rule configured.
+struct WasmInstance {
+    store: Store<Sandbox>,
+    instance: Instance,
+    memory: Memory,
+    check: TypedFunc<(u32, u32, u32), ()>,
+    rule: u32,
+    handle: u32,
+}
+
+impl WasmInstance {
+    fn call<P: WasmParams, R: WasmResults>(
+        &mut self,
+        name: &str,
+        params: P,
+    ) -> wasmtime::Result<R> {
+        self.store.set_fuel(FUEL_PER_CALL)?;
+        let function = self
+            .instance
+            .get_typed_func::<P, R>(&mut self.store, name)?;
+        function.call(&mut self.store, params)
+    }
+
+    fn describe(&mut self) -> wasmtime::Result<String> {
+        let packed: u64 = self.call(abi::EXPORT_DESCRIBE, ())?;
+        let mut bytes = vec![0; (packed & 0xffff_ffff) as usize];
+        self.memory
+            .read(&self.store, (packed >> 32) as usize, &mut bytes)?;
+        Ok(String::from_utf8(bytes)?)
+    }
+
+    /// Configure rule `index`, returning whether the plugin accepted the
+    /// properties.
+    fn create(&mut self, index: usize, properties: &[(String, String)]) -> wasmtime::Result<bool> {
+        let mut buffer = Vec::new();
+        for (name, value) in properties {
+            for part in [name, value] {
+                buffer.extend_from_slice(part.as_bytes());
+                buffer.push(0);
+            }
+        }
+        let rule = u32::try_from(index)?;
+        let len = u32::try_from(buffer.len())?;
+        let ptr: u32 = self.call(abi::EXPORT_ALLOC, len)?;
+        self.memory.write(&mut self.store, ptr as usize, &buffer)?;
+        self.handle = self.call(abi::EXPORT_CREATE, (rule, ptr, len))?;
+        self.rule = rule;
+        Ok(self.handle != 0)
+    }
+
+    fn check(&mut self, state: &HostState<'_>) -> wasmtime::Result<()> {
+        let sandbox = self.store.data_mut();
+        if sandbox.source != state.source() {
+            sandbox.source = state.source().to_string();
+            sandbox.file_id = sandbox.file_id.wrapping_add(1);
+        }
+        sandbox.state = std::ptr::from_ref(state) as usize;
+        self.store.set_fuel(FUEL_PER_CALL)?;
+        let result = self
+            .check
+            .call(&mut self.store, (self.rule, self.handle, 1));
+        self.store.data_mut().state = 0;
+        result
+    }
+}
+
+/// A configured instance of a WebAssembly plugin rule.
+struct WasmRule {
+    plugin: Arc<WasmPlugin>,
+    info: PluginRuleInfo,
+    properties: Vec<(String, String)>,
+    pool: Mutex<Vec<WasmInstance>>,
+}
+
+impl Rule for WasmRule {
+    fn name(&self) -> &'static str {
+        self.info.name
+    }
+
+    fn relevant_kinds(&self) -> &'static [&'static str] {
+        self.info.relevant_kinds
+    }
+
+    fn check(&self, ctx: &CheckContext, node: &CstNode) -> Vec<Diagnostic> {
+        let pooled = self.pool.lock().ok().and_then(|mut pool| pool.pop());
+        let Some(mut instance) =
+            pooled.or_else(|| self.plugin.configure(self.info.index, &self.properties))
+        else {
+            return Vec::new();
+        };
+        let state = HostState::new(ctx.source(), *node, self.info.name);
+        // An instance that trapped may be in any state, so it is not reused
+        if instance.check(&state).is_ok()
+            && let Ok(mut pool) = self.pool.lock()
+        {
+            pool.push(instance);
+        }
+        state.into_diagnostics()
+    }
+}
+
+/// The state of the check in progress.
+fn state<'c>(caller: &'c Caller<'_, Sandbox>) -> wasmtime::Result<&'c HostState<'c>> {
+    match caller.data().state {
+        0 => Err(wasmtime::Error::msg(
+            "host function called outside of a check",
+        )),
+        // SAFETY: `state` points at the `HostState` of the ongoing
+        // `WasmInstance::check`, which outlives the call into the plugin
+        state => Ok(unsafe { &*(state as *const HostState<'c>) }),
+    }
+}
+
+fn handle(state: &HostState<'_>, node: Option<CstNode<'_>>) -> u32 {
+    // Handles count the nodes handed out during one check, which fits in u32
+    state.handle(node) as u32
+}
+
+fn memory(caller: &mut Caller<'_, Sandbox>) -> wasmtime::Result<Memory> {
+    caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| wasmtime::Error::msg("plugin does not export its memory"))
+}
+
+/// Copy as much of `text` as fits into the guest buffer at `ptr`, returning
+/// its full length.
+fn write_string(
+    caller: &mut Caller<'_, Sandbox>,
+    ptr: u32,
+    cap: u32,
+    text: &str,
+) -> wasmtime::Result<u32> {
+    let len = u32::try_from(text.len())?;
+    if len <= cap {
+        memory(caller)?.write(&mut *caller, ptr as usize, text.as_bytes())?;
+    }
+    Ok(len)
+}
+
+fn read_string(caller: &mut Caller<'_, Sandbox>, ptr: u32, len: u32) -> wasmtime::Result<String> {
+    let mut bytes = vec![0; len as usize];
+    memory(caller)?.read(&*caller, ptr as usize, &mut bytes)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn define_host_functions(linker: &mut Linker<Sandbox>) -> wasmtime::Result<()> {
+    let module = abi::HOST_MODULE;
+    linker.func_wrap(module, "file_id", |caller: Caller<'_, Sandbox>| {
+        caller.data().file_id
+    })?;
+    linker.func_wrap(
+        module,
+        "source",
+        |mut caller: Caller<'_, Sandbox>, ptr: u32, cap: u32| -> wasmtime::Result<u32> {
+            // Moved out while the guest memory is borrowed mutably
+            let source = std::mem::take(&mut caller.data_mut().source);
+            let result = write_string(&mut caller, ptr, cap, &source);
+            caller.data_mut().source = source;
+            result
+        },
+    )?;
+    linker.func_wrap(
+        module,
+        "kind",
+        |mut caller: Caller<'_, Sandbox>, node: u32, ptr: u32, cap: u32| -> wasmtime::Result<u32> {
+            let kind = state(&caller)?
+                .node(node as NodeHandle)
+                .map_or("", |n| n.kind());
+            write_string(&mut caller, ptr, cap, kind)
+        },
+    )?;
+    linker.func_wrap(
+        module,
+        "is_named",
+        |caller: Caller<'_, Sandbox>, node: u32| -> wasmtime::Result<u32> {
+            let node = state(&caller)?.node(node as NodeHandle);
+            Ok(node.is_some_and(|n| n.inner().is_named()).into())
+        },
+    )?;
+    linker.func_wrap(
+        module,
+        "range",
+        |caller: Caller<'_, Sandbox>, node: u32| -> wasmtime::Result<u64> {
+            let range = state(&caller)?
+                .node(node as NodeHandle)
+                .map_or_else(TextRange::default, |n| n.range());
+            Ok((u64::from(u32::from(range.start())) << 32) | u64::from(u32::from(range.end())))
+        },
+    )?;
+    linker.func_wrap(
+        module,
+        "parent",
+        |caller: Caller<'_, Sandbox>, node: u32| -> wasmtime::Result<u32> {
+            let state = state(&caller)?;
+            let parent = state.node(node as NodeHandle).and_then(|n| n.parent());
+            Ok(handle(state, parent))
+        },
+    )?;
+    linker.func_wrap(
+        module,
+        "child_count",
+        |caller: Caller<'_, Sandbox>, node: u32| -> wasmtime::Result<u32> {
+            let node = state(&caller)?.node(node as NodeHandle);
+            Ok(node.map_or(0, |n| n.children().count()) as u32)
+        },
+    )?;
+    linker.func_wrap(
+        module,
+        "child",
+        |caller: Caller<'_, Sandbox>, node: u32, index: u32| -> wasmtime::Result<u32> {
+            let state = state(&caller)?;
+            let child = state
+                .node(node as NodeHandle)
+                .and_then(|n| n.children().nth(index as usize));
+            Ok(handle(state, child))
+        },
+    )?;
+    linker.func_wrap(
+        module,
+        "child_by_field_name",
+        |mut caller: Caller<'_, Sandbox>, node: u32, ptr: u32, len: u32| -> wasmtime::Result<u32> {
+            let field = read_string(&mut caller, ptr, len)?;
+            let state = state(&caller)?;
+            let child = state
+                .node(node as NodeHandle)
+                .and_then(|n| n.child_by_field_name(&field));
+            Ok(handle(state, child))
+        },
+    )?;
+    linker.func_wrap(
+        module,
+        "report",
+        |mut caller: Caller<'_, Sandbox>,
+         start: u32,
+         end: u32,
+         ptr: u32,
+         len: u32|
+         -> wasmtime::Result<()> {
+            let body = read_string(&mut caller, ptr, len)?;
+            state(&caller)?.report(start, end, body);
+            Ok(())
+        },
+    )?;
+    Ok(())
+}
+
+/// Plugins are never unloaded, so their rule names and kinds are leaked once
+/// to be handed out as `&'static str`.
+fn leak(s: &str) -> &'static str {
+    Box::leak(s.to_string().into_boxed_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use lintal_java_cst::TreeWalker;
+    use lintal_java_parser::JavaParser;
+
+    use super::*;
+    use crate::RuleRegistry;
+
+    /// A plugin with one rule, `NoCalls`, flagging every method invocation,
+    /// and the given body for `lintal_check`.
+    fn plugin_source(check_body: &str) -> String {
+        format!(
+            r#"(module
+  (import "lintal" "range" (func $range (param i32) (result i64)))
+  (import "lintal" "report" (func $report (param i32 i32 i32 i32)))
+  (memory (export "memory") 1)
+  (data (i32.const 16) "wat\nNoCalls\tmethod_invocation\n")
+  (data (i32.const 64) "No calls.")
+  (global $heap (mut i32) (i32.const 1024))
+  (func (export "lintal_abi_version") (result i32) (i32.const 1))
+  (func (export "lintal_alloc") (param $len i32) (result i32)
+    (global.get $heap)
+    (global.set $heap (i32.add (global.get $heap) (local.get $len))))
+  (func (export "lintal_describe") (result i64)
+    (i64.or (i64.shl (i64.const 16) (i64.const 32)) (i64.const 30)))
+  (func (export "lintal_create") (param i32 i32 i32) (result i32)
+    (i32.const 1))
+  (func (export "lintal_check") (param i32 i32 i32)
+    (local $range i64)
+    {check_body}))"#
+        )
+    }
+
+    const REPORT_NODE: &str = r"(local.set $range (call $range (local.get 2)))
+    (call $report
+      (i32.wrap_i64 (i64.shr_u (local.get $range) (i64.const 32)))
+      (i32.wrap_i64 (local.get $range))
+      (i32.const 64)
+      (i32.const 9))";
+
+    fn load(source: &str) -> Result<Arc<WasmPlugin>, PluginError> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plugin.wat");
+        std::fs::write(&path, source).unwrap();
+        WasmPlugin::load(&path)
+    }
+
+    fn check(rule: &dyn Rule, source: &str) -> Vec<Diagnostic> {
+        let mut parser = JavaParser::new();
+        let result = parser.parse(source).unwrap();
+        let ctx = CheckContext::new(source);
+        TreeWalker::new(result.tree.root_node(), source)
+            .filter(|node| rule.relevant_kinds().contains(&node.kind()))
+            .flat_map(|node| rule.check(&ctx, &node))
+            .collect()
+    }
+
+    #[test]
+    fn test_wasm_rule_reports_violations() {
+        let plugin = load(&plugin_source(REPORT_NODE)).unwrap();
+        assert_eq!(plugin.name(), "wat");
+        assert_eq!(plugin.rule_names().collect::<Vec<_>>(), ["NoCalls"]);
+
+        let mut registry = RuleRegistry::new();
+        registry.register_wasm_plugin(&plugin);
+        let rule = registry.create_rule("NoCalls", &Properties::new()).unwrap();
+        assert_eq!(rule.relevant_kinds(), ["method_invocation"]);
+
+        let source = "class A { void m() { run(); } }";
+        let diagnostics = check(rule.as_ref(), source);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind.code, "NoCalls");
+        assert_eq!(diagnostics[0].kind.body, "No calls.");
+        assert_eq!(&source[diagnostics[0].range], "run()");
+    }
+
+    #[test]
+    fn test_runaway_rule_is_stopped() {
+        let plugin = load(&plugin_source("(loop $forever (br $forever))")).unwrap();
+        let mut registry = RuleRegistry::new();
+        registry.register_wasm_plugin(&plugin);
+        let rule = registry.create_rule("NoCalls", &Properties::new()).unwrap();
+
+        assert!(check(rule.as_ref(), "class A { void m() { run(); run(); } }").is_empty());
+    }
+
+    #[test]
+    fn test_rejects_imports_outside_the_sandbox() {
+        let source = plugin_source(REPORT_NODE).replacen(
+            "(module",
+            r#"(module
+  (import "wasi_snapshot_preview1" "fd_write"
+    (func (param i32 i32 i32 i32) (result i32)))"#,
+            1,
+        );
+        let Err(PluginError::Wasm { message, .. }) = load(&source) else {
+            panic!("expected the plugin to be rejected");
+        };
+        assert!(
+            message.contains("wasi_snapshot_preview1::fd_write"),
+            "{message}"
+        );
+    }
+}
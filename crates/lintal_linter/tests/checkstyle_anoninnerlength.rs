@@ -0,0 +1,63 @@
+//! AnonInnerLength checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::AnonInnerLength;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the AnonInnerLength rule on source code and return violation lines.
+fn check_anon_inner_length(source: &str, rule: AnonInnerLength) -> Vec<usize> {
+    check_source(&[Box::new(rule)], source)
+        .iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::sizes_test_input("anoninnerlength", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping {}: checkstyle repo not available", file_name);
+        return;
+    };
+
+    let rule = AnonInnerLength::from_config(&FixtureConfig::parse(&source).properties());
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(100.0);
+}
+
+#[test]
+fn test_input_anon_inner_length() {
+    run_fixture("InputAnonInnerLength.java");
+}
+
+#[test]
+fn test_from_config() {
+    let mut props = Properties::new();
+    assert_eq!(AnonInnerLength::from_config(&props).max, 20);
+    props.insert("max", "40");
+    assert_eq!(AnonInnerLength::from_config(&props).max, 40);
+}
+
+#[test]
+fn test_long_anonymous_class() {
+    let source = r#"
+class Foo {
+    Runnable r = new Runnable() {
+        public void run() {
+        }
+    };
+}
+"#;
+    assert_eq!(
+        check_anon_inner_length(source, AnonInnerLength { max: 3 }),
+        vec![3]
+    );
+}
@@ -0,0 +1,80 @@
+//! AvoidStarImport checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::AvoidStarImport;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the AvoidStarImport rule on source code and return violation lines.
+fn check_avoid_star_import(source: &str, properties: &Properties) -> Vec<usize> {
+    check_source(
+        &[Box::new(AvoidStarImport::from_config(properties))],
+        source,
+    )
+    .iter()
+    .map(|violation| violation.line)
+    .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::imports_test_input("avoidstarimport", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping test: checkstyle repo not available");
+        return;
+    };
+
+    let rule = AvoidStarImport::from_config(&FixtureConfig::parse(&source).properties());
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(80.0);
+}
+
+#[test]
+fn test_input_avoid_star_import_default() {
+    run_fixture("InputAvoidStarImportDefault.java");
+}
+
+#[test]
+fn test_input_avoid_star_import_excludes() {
+    run_fixture("InputAvoidStarImportExcludes.java");
+}
+
+#[test]
+fn test_input_avoid_star_import_allow_class_imports() {
+    run_fixture("InputAvoidStarImportAllowClassImports.java");
+}
+
+#[test]
+fn test_input_avoid_star_import_allow_static_member_imports() {
+    run_fixture("InputAvoidStarImportAllowStaticMemberImports.java");
+}
+
+#[test]
+fn test_single_type_import_no_violation() {
+    let source = r#"
+import java.util.List;
+
+class Foo {}
+"#;
+    let violations = check_avoid_star_import(source, &Properties::new());
+    assert!(violations.is_empty());
+}
+
+#[test]
+fn test_star_imports_violation() {
+    let source = r#"
+import java.util.*;
+import static java.lang.Math.*;
+
+class Foo {}
+"#;
+    let violations = check_avoid_star_import(source, &Properties::new());
+    assert_eq!(violations, vec![2, 3]);
+}
@@ -0,0 +1,70 @@
+//! AvoidStaticImport checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::AvoidStaticImport;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the AvoidStaticImport rule on source code and return violation lines.
+fn check_avoid_static_import(source: &str, properties: &Properties) -> Vec<usize> {
+    check_source(
+        &[Box::new(AvoidStaticImport::from_config(properties))],
+        source,
+    )
+    .iter()
+    .map(|violation| violation.line)
+    .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::imports_test_input("avoidstaticimport", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping test: checkstyle repo not available");
+        return;
+    };
+
+    let rule = AvoidStaticImport::from_config(&FixtureConfig::parse(&source).properties());
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(80.0);
+}
+
+#[test]
+fn test_input_avoid_static_import_default() {
+    run_fixture("InputAvoidStaticImportDefault.java");
+}
+
+#[test]
+fn test_input_avoid_static_import_excludes() {
+    run_fixture("InputAvoidStaticImportExcludes.java");
+}
+
+#[test]
+fn test_plain_import_no_violation() {
+    let source = r#"
+import java.util.List;
+
+class Foo {}
+"#;
+    let violations = check_avoid_static_import(source, &Properties::new());
+    assert!(violations.is_empty());
+}
+
+#[test]
+fn test_static_import_violation() {
+    let source = r#"
+import java.util.List;
+import static java.lang.Math.abs;
+
+class Foo {}
+"#;
+    let violations = check_avoid_static_import(source, &Properties::new());
+    assert_eq!(violations, vec![3]);
+}
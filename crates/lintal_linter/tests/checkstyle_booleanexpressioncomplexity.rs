@@ -0,0 +1,79 @@
+//! BooleanExpressionComplexity checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::BooleanExpressionComplexity;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the BooleanExpressionComplexity rule on source code and return violation lines.
+fn check_boolean_expression_complexity(
+    source: &str,
+    rule: BooleanExpressionComplexity,
+) -> Vec<usize> {
+    check_source(&[Box::new(rule)], source)
+        .iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::metrics_test_input("booleanexpressioncomplexity", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping test: checkstyle repo not available");
+        return;
+    };
+
+    let config = FixtureConfig::parse(&source);
+    let rule = BooleanExpressionComplexity::from_config(&config.properties());
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(80.0);
+}
+
+#[test]
+fn test_input_boolean_expression_complexity() {
+    run_fixture("InputBooleanExpressionComplexity.java");
+}
+
+#[test]
+fn test_input_boolean_expression_complexity_records() {
+    run_fixture("InputBooleanExpressionComplexityRecordsAndCompactCtors.java");
+}
+
+#[test]
+fn test_input_boolean_expression_complexity_lambdas() {
+    run_fixture("InputBooleanExpressionComplexityLeaves.java");
+}
+
+#[test]
+fn test_simple_condition_no_violation() {
+    let source = r#"
+class Foo {
+    boolean run(boolean a, boolean b, boolean c) {
+        return a && b || c;
+    }
+}
+"#;
+    let rule = BooleanExpressionComplexity::from_config(&Properties::new());
+    assert!(check_boolean_expression_complexity(source, rule).is_empty());
+}
+
+#[test]
+fn test_complex_condition_violation() {
+    let source = r#"
+class Foo {
+    boolean run(boolean a, boolean b, boolean c, boolean d) {
+        return a && b || c && d || a;
+    }
+}
+"#;
+    let rule = BooleanExpressionComplexity::from_config(&Properties::new());
+    assert_eq!(check_boolean_expression_complexity(source, rule), vec![4]);
+}
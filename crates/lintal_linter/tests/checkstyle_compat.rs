@@ -13,6 +13,7 @@ use lintal_java_parser::JavaParser;
 use lintal_linter::rules::WhitespaceAround;
 use lintal_linter::{CheckContext, Rule};
 use lintal_source_file::{LineIndex, SourceCode};
+use lintal_testkit::{CHECKSTYLE_TAB_WIDTH, diff_violations};
 use std::collections::HashMap;
 
 /// A violation at a specific location.
@@ -116,7 +117,11 @@ fn check_whitespace_around_with_config(
     for node in TreeWalker::new(result.tree.root_node(), source) {
         let diagnostics = rule.check(&ctx, &node);
         for diagnostic in diagnostics {
-            let loc = source_code.line_column(diagnostic.range.start());
+            let loc = source_code.checkstyle_column(
+                diagnostic.range.start(),
+                CHECKSTYLE_TAB_WIDTH,
+                rule.column_encoding(),
+            );
             let message = diagnostic.kind.body.clone();
 
             // Parse message to determine if it's "not preceded" or "not followed"
@@ -157,41 +162,6 @@ fn load_checkstyle_fixture(check_name: &str, file_name: &str) -> Option<String>
     std::fs::read_to_string(&path).ok()
 }
 
-/// Helper to verify violations match expected.
-/// Returns (found_count, missing_violations)
-fn verify_violations(
-    violations: &[Violation],
-    expected: &[Violation],
-    match_column: bool,
-) -> (usize, Vec<Violation>) {
-    let mut found = 0;
-    let mut missing = vec![];
-
-    for exp in expected {
-        let matched = if match_column {
-            violations.iter().any(|v| {
-                v.line == exp.line
-                    && v.column == exp.column
-                    && v.message_key == exp.message_key
-                    && v.token == exp.token
-            })
-        } else {
-            // Match by line and message_key and token only (columns may differ due to tab handling)
-            violations.iter().any(|v| {
-                v.line == exp.line && v.message_key == exp.message_key && v.token == exp.token
-            })
-        };
-
-        if matched {
-            found += 1;
-        } else {
-            missing.push(exp.clone());
-        }
-    }
-
-    (found, missing)
-}
-
 /// Print violation details for debugging.
 fn print_violations(label: &str, violations: &[Violation]) {
     println!("\n{}:", label);
@@ -319,7 +289,8 @@ fn test_whitespace_around_keywords_and_operators() {
     print_violations("Expected", &expected);
     print_violations("Actual", &violations);
 
-    let (found, missing) = verify_violations(&violations, &expected, true);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
 
     println!("\nFound {}/{} expected violations", found, expected.len());
     if !missing.is_empty() {
@@ -357,7 +328,8 @@ fn test_whitespace_around_start_of_line() {
     // Expected: line 25, '{' not preceded
     let expected = vec![Violation::not_preceded(25, 2, "{")];
 
-    let (found, missing) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     assert!(found == expected.len(), "Missing violations: {:?}", missing);
 }
 
@@ -392,7 +364,8 @@ fn test_whitespace_around_braces() {
         Violation::not_preceded(134, 18, "}"),
     ];
 
-    let (found, missing) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     println!("\nFound {}/{} expected violations", found, expected.len());
     if !missing.is_empty() {
         print_violations("Missing", &missing);
@@ -426,7 +399,8 @@ fn test_whitespace_around_switch() {
 
     let expected = vec![Violation::not_followed(26, 9, "switch")];
 
-    let (found, missing) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     assert!(found == expected.len(), "Missing violations: {:?}", missing);
 }
 
@@ -450,7 +424,8 @@ fn test_whitespace_around_do_while() {
 
     let expected = vec![Violation::not_followed(29, 11, "while")];
 
-    let (found, missing) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     assert!(found == expected.len(), "Missing violations: {:?}", missing);
 }
 
@@ -478,7 +453,8 @@ fn test_whitespace_around_lambda() {
         Violation::not_followed(28, 48, "->"),
     ];
 
-    let (found, missing) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     assert!(found == expected.len(), "Missing violations: {:?}", missing);
 }
 
@@ -511,7 +487,8 @@ fn test_ignore_enhanced_for_colon() {
     // Should detect colon violation on line 39
     let expected = vec![Violation::not_preceded(39, 20, ":")];
 
-    let (found, _) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     assert!(found >= 1, "Should detect colon violation on line 39");
 
     // Test with ignoreEnhancedForColon = true (default)
@@ -555,7 +532,8 @@ fn test_whitespace_around_generics() {
         Violation::not_followed(27, 16, "&"),
     ];
 
-    let (found, missing) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     println!("\nFound {}/{} expected violations", found, expected.len());
 
     // Note: Our parser may handle generics differently, so we check for at least some & violations
@@ -601,7 +579,8 @@ fn test_empty_types() {
         Violation::not_preceded(47, 21, "}"),
     ];
 
-    let (found, _missing) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     println!("\nFound {}/{} expected violations", found, expected.len());
 
     // Check that empty types (lines 56, 58, 60) don't have violations
@@ -650,7 +629,8 @@ fn test_empty_loops() {
         Violation::not_preceded(76, 19, "}"),
     ];
 
-    let (found, _missing) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     println!("\nFound {}/{} expected violations", found, expected.len());
 
     // With allowEmptyLoops = true, should have no empty loop violations
@@ -704,7 +684,8 @@ fn test_allow_empty_lambda_expressions() {
         Violation::not_preceded(33, 42, "}"),
     ];
 
-    let (found, _) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     println!("\nFound {}/{} expected violations", found, expected.len());
 
     // With allowEmptyLambdas = true
@@ -817,7 +798,8 @@ fn test_array_initialization() {
         Violation::not_preceded(43, 20, "{"),
     ];
 
-    let (found, missing) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     println!("\nFound {}/{} expected violations", found, expected.len());
     if !missing.is_empty() {
         print_violations("Missing", &missing);
@@ -852,7 +834,8 @@ fn test_double_brace_initialization() {
         Violation::not_preceded(37, 24, "}"),
     ];
 
-    let (found, _) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     println!("\nFound {}/{} expected violations", found, expected.len());
 }
 
@@ -1116,7 +1099,8 @@ fn test_whitespace_around_varargs() {
         Violation::not_followed(24, 39, "..."),
     ];
 
-    let (found, missing) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     println!("\nFound {}/{} expected violations", found, expected.len());
     if !missing.is_empty() {
         print_violations("Missing", &missing);
@@ -1165,7 +1149,8 @@ fn test_whitespace_around_records() {
         Violation::not_preceded(74, 29, "}"),
     ];
 
-    let (found, missing) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     println!("\nFound {}/{} expected violations", found, expected.len());
     assert!(
         found >= expected.len() * 50 / 100,
@@ -1208,7 +1193,8 @@ fn test_literal_when() {
         Violation::not_preceded(67, 38, "when"),
     ];
 
-    let (found, _) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     println!("\nFound {}/{} expected violations", found, expected.len());
 }
 
@@ -1241,7 +1227,8 @@ fn test_whitespace_around_after_emoji() {
         Violation::not_preceded(29, 19, "+"),
     ];
 
-    let (found, _) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     println!("\nFound {}/{} expected violations", found, expected.len());
 
     // Should detect at least some + violations near emoji
@@ -1316,7 +1303,8 @@ fn test_allow_empty_types_false_non_empty_classes() {
         Violation::not_preceded(56, 34, "{"),
     ];
 
-    let (found, missing) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     println!("\nFound {}/{} expected violations", found, expected.len());
 
     // Should detect at least some brace violations
@@ -1368,7 +1356,8 @@ fn test_whitespace_around_all_tokens() {
         Violation::not_preceded(27, 38, ">"),
     ];
 
-    let (found, missing) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     println!("\nFound {}/{} expected violations", found, expected.len());
     assert!(
         found >= expected.len() * 80 / 100,
@@ -1419,7 +1408,8 @@ fn test_allow_empty_types_true_non_empty_classes() {
         Violation::not_preceded(55, 35, "{"),
     ];
 
-    let (found, _) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     println!("\nFound {}/{} expected violations", found, expected.len());
 }
 
@@ -1536,7 +1526,8 @@ fn test_whitespace_around_after_permits_list() {
         Violation::not_preceded(27, 49, "}"),
     ];
 
-    let (found, _) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     println!("\nFound {}/{} expected violations", found, expected.len());
 }
 
@@ -1598,7 +1589,8 @@ fn test_switch_cases_parens() {
         Violation::not_preceded(51, 25, "}"),
     ];
 
-    let (found, _) = verify_violations(&violations, &expected, false);
+    let (missing, _) = diff_violations(&violations, &expected);
+    let found = expected.len() - missing.len();
     println!("\nFound {}/{} expected violations", found, expected.len());
 
     // Should detect some violations in switch cases
@@ -0,0 +1,99 @@
+//! CustomImportOrder checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::CustomImportOrder;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the CustomImportOrder rule on source code and return violation lines.
+fn check_custom_import_order(source: &str, properties: &Properties) -> Vec<usize> {
+    check_source(
+        &[Box::new(CustomImportOrder::from_config(properties))],
+        source,
+    )
+    .iter()
+    .map(|violation| violation.line)
+    .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::imports_test_input("customimportorder", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping test: checkstyle repo not available");
+        return;
+    };
+
+    let rule = CustomImportOrder::from_config(&FixtureConfig::parse(&source).properties());
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(80.0);
+}
+
+#[test]
+fn test_input_custom_import_order_default() {
+    run_fixture("InputCustomImportOrderDefault.java");
+}
+
+#[test]
+fn test_input_custom_import_order() {
+    run_fixture("InputCustomImportOrder.java");
+}
+
+#[test]
+fn test_input_custom_import_order_same_package() {
+    run_fixture("InputCustomImportOrderSamePackage.java");
+}
+
+#[test]
+fn test_input_custom_import_order_third_party_package() {
+    run_fixture("InputCustomImportOrderThirdPartyPackage.java");
+}
+
+#[test]
+fn test_grouped_imports_no_violation() {
+    let source = r#"
+import static java.lang.Math.abs;
+
+import java.util.List;
+
+import org.junit.Test;
+
+class Foo {}
+"#;
+    let properties: Properties = [(
+        "customImportOrderRules",
+        "STATIC###STANDARD_JAVA_PACKAGE###THIRD_PARTY_PACKAGE",
+    )]
+    .into_iter()
+    .collect();
+    let violations = check_custom_import_order(source, &properties);
+    assert!(violations.is_empty());
+}
+
+#[test]
+fn test_groups_out_of_order_violation() {
+    let source = r#"
+import static java.lang.Math.abs;
+
+import org.junit.Test;
+
+import java.util.List;
+
+class Foo {}
+"#;
+    let properties: Properties = [(
+        "customImportOrderRules",
+        "STATIC###STANDARD_JAVA_PACKAGE###THIRD_PARTY_PACKAGE",
+    )]
+    .into_iter()
+    .collect();
+    let violations = check_custom_import_order(source, &properties);
+    assert_eq!(violations, vec![4]);
+}
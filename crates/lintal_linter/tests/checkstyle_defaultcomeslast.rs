@@ -1,15 +1,13 @@
 //! DefaultComesLast checkstyle compatibility tests.
 
 mod checkstyle_repo;
-mod test_harness;
 
 use lintal_java_cst::TreeWalker;
 use lintal_java_parser::JavaParser;
 use lintal_linter::rules::DefaultComesLast;
 use lintal_linter::{CheckContext, Rule};
 use lintal_source_file::{LineIndex, SourceCode};
-use regex::Regex;
-use test_harness::TestResult;
+use lintal_testkit::{TestResult, expected_violation_lines};
 
 /// Run the DefaultComesLast rule on source code and return violation lines.
 fn check_default_comes_last(source: &str, skip_if_last_and_shared: bool) -> Vec<usize> {
@@ -38,48 +36,6 @@ fn check_default_comes_last(source: &str, skip_if_last_and_shared: bool) -> Vec<
     violations
 }
 
-/// Parse expected violations from checkstyle test file comments.
-fn parse_expected_violations(source: &str) -> Vec<usize> {
-    let mut violations = vec![];
-
-    // Match patterns like "// violation 'message'"
-    let inline_re = Regex::new(r"//\s*violation\s+'[^']*'").unwrap();
-    // Match "violation above" pattern
-    let above_re = Regex::new(r"//\s*violation\s+above").unwrap();
-    // Match "violation below" pattern
-    let below_re = Regex::new(r"//\s*violation\s+below").unwrap();
-    // Match "violation N lines above" pattern
-    let n_above_re = Regex::new(r"//\s*violation\s+(\d+)\s+lines?\s+above").unwrap();
-
-    for (i, line) in source.lines().enumerate() {
-        let line_num = i + 1;
-
-        // Skip lines that say "No violation" or similar
-        if line.contains("No violation") || line.contains("no violation") {
-            continue;
-        }
-
-        // Check for "N lines above" first
-        if let Some(caps) = n_above_re.captures(line) {
-            if let Some(n) = caps.get(1).and_then(|m| m.as_str().parse::<usize>().ok())
-                && line_num > n
-            {
-                violations.push(line_num - n);
-            }
-        } else if above_re.is_match(line) {
-            if line_num > 1 {
-                violations.push(line_num - 1);
-            }
-        } else if below_re.is_match(line) {
-            violations.push(line_num + 1);
-        } else if inline_re.is_match(line) {
-            violations.push(line_num);
-        }
-    }
-
-    violations
-}
-
 fn load_fixture(file_name: &str) -> Option<String> {
     let path = checkstyle_repo::coding_test_input("defaultcomeslast", file_name)?;
     std::fs::read_to_string(&path).ok()
@@ -92,7 +48,7 @@ fn test_input_default_comes_last_one() {
         return;
     };
 
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_default_comes_last(&source, false);
 
     let result = TestResult::compare(expected, actual);
@@ -109,7 +65,7 @@ fn test_input_default_comes_last_two() {
         return;
     };
 
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_default_comes_last(&source, false);
 
     let result = TestResult::compare(expected, actual);
@@ -127,7 +83,7 @@ fn test_input_skip_if_last_and_shared_one() {
         return;
     };
 
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_default_comes_last(&source, true);
 
     let result = TestResult::compare(expected, actual);
@@ -145,7 +101,7 @@ fn test_input_skip_if_last_and_shared_two() {
         return;
     };
 
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_default_comes_last(&source, true);
 
     let result = TestResult::compare(expected, actual);
@@ -162,7 +118,7 @@ fn test_input_switch_expressions() {
         return;
     };
 
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_default_comes_last(&source, false);
 
     let result = TestResult::compare(expected, actual);
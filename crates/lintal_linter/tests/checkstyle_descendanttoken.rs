@@ -1,16 +1,14 @@
 //! DescendantToken checkstyle compatibility tests.
 
 mod checkstyle_repo;
-mod test_harness;
 
 use lintal_java_cst::TreeWalker;
 use lintal_java_parser::JavaParser;
 use lintal_linter::rules::DescendantToken;
 use lintal_linter::{CheckContext, FromConfig, Properties, Rule};
 use lintal_source_file::{LineIndex, SourceCode};
-use regex::Regex;
+use lintal_testkit::{FixtureConfig, TestResult, expected_violation_lines};
 use std::collections::HashMap;
-use test_harness::TestResult;
 
 /// Run the DescendantToken rule on source code and return violation lines.
 fn check_descendant_token(source: &str, rule: &DescendantToken) -> Vec<usize> {
@@ -36,103 +34,13 @@ fn check_descendant_token(source: &str, rule: &DescendantToken) -> Vec<usize> {
     violations
 }
 
-/// Parse expected violations from checkstyle test file comments.
-fn parse_expected_violations(source: &str) -> Vec<usize> {
-    let mut violations = vec![];
-
-    let n_above_lines_re = Regex::new(r"//\s*violation\s+(\d+)\s+lines?\s+above").unwrap();
-    let n_violations_above_re = Regex::new(r"//\s*(\d+)\s+violations?\s+above").unwrap();
-    let n_violations_re = Regex::new(r"//\s*(\d+)\s+violations?\b").unwrap();
-    let above_re = Regex::new(r"//\s*violation\s+above").unwrap();
-    let below_re = Regex::new(r"//\s*violation\s+below").unwrap();
-    let inline_re = Regex::new(r"//\s*violation\b").unwrap();
-
-    for (i, line) in source.lines().enumerate() {
-        let line_num = i + 1;
-
-        if let Some(caps) = n_above_lines_re.captures(line) {
-            if let Some(n) = caps.get(1).and_then(|m| m.as_str().parse::<usize>().ok())
-                && line_num > n
-            {
-                violations.push(line_num - n);
-            }
-        } else if let Some(caps) = n_violations_above_re.captures(line) {
-            if let Some(n) = caps.get(1).and_then(|m| m.as_str().parse::<usize>().ok())
-                && line_num > 1
-            {
-                for _ in 0..n {
-                    violations.push(line_num - 1);
-                }
-            }
-        } else if above_re.is_match(line) {
-            if line_num > 1 {
-                violations.push(line_num - 1);
-            }
-        } else if below_re.is_match(line) {
-            violations.push(line_num + 1);
-        } else if let Some(caps) = n_violations_re.captures(line) {
-            if !line.contains("above")
-                && !line.contains("below")
-                && let Some(n) = caps.get(1).and_then(|m| m.as_str().parse::<usize>().ok())
-            {
-                for _ in 0..n {
-                    violations.push(line_num);
-                }
-            }
-        } else if inline_re.is_match(line) {
-            violations.push(line_num);
-        }
-    }
-
-    violations
-}
-
-/// Parse config from the header block of a checkstyle test file.
-fn parse_config_from_header(source: &str) -> HashMap<&str, &str> {
-    let mut props = HashMap::new();
-
-    // Find the first /* ... */ block
-    let Some(start) = source.find("/*") else {
-        return props;
-    };
-    let Some(end) = source[start..].find("*/") else {
-        return props;
-    };
-    let block = &source[start + 2..start + end];
-
-    for line in block.lines() {
-        let line = line.trim();
-        if line.is_empty() || line.starts_with("DescendantToken") {
-            continue;
-        }
-        if let Some(eq_pos) = line.find('=') {
-            let key = line[..eq_pos].trim();
-            let value = line[eq_pos + 1..].trim();
-
-            // Skip (default) values
-            if value.starts_with("(default)") {
-                continue;
-            }
-
-            props.insert(key, value);
-        }
-    }
-
-    props
-}
-
 fn load_fixture(file_name: &str) -> Option<String> {
     let path = checkstyle_repo::descendanttoken_test_input(file_name)?;
     std::fs::read_to_string(&path).ok()
 }
 
 fn build_rule_from_source(source: &str) -> DescendantToken {
-    let config = parse_config_from_header(source);
-    let mut props: Properties = HashMap::new();
-    for (k, v) in &config {
-        props.insert(k, v);
-    }
-    DescendantToken::from_config(&props)
+    DescendantToken::from_config(&FixtureConfig::parse(source).properties())
 }
 
 #[test]
@@ -143,7 +51,7 @@ fn test_return_from_finally() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_descendant_token(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -161,7 +69,7 @@ fn test_missing_switch_default() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_descendant_token(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -179,7 +87,7 @@ fn test_string_literal_equality() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_descendant_token(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -197,7 +105,7 @@ fn test_return_from_catch() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_descendant_token(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -215,7 +123,7 @@ fn test_empty_statement() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_descendant_token(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -250,7 +158,7 @@ fn test_illegal_tokens2_native() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_descendant_token(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -268,7 +176,7 @@ fn test_illegal_tokens3_native_message() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_descendant_token(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -286,7 +194,7 @@ fn test_illegal_tokens7_self_match() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_descendant_token(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -304,7 +212,7 @@ fn test_return_from_finally5_sum_minimum() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_descendant_token(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -322,7 +230,7 @@ fn test_return_from_finally6_custom_message() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_descendant_token(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -0,0 +1,98 @@
+//! DesignForExtension checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::DesignForExtension;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the DesignForExtension rule on source code and return violation lines.
+fn check_design_for_extension(source: &str, rule: DesignForExtension) -> Vec<usize> {
+    check_source(&[Box::new(rule)], source)
+        .iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::design_test_input("designforextension", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping {}: checkstyle repo not available", file_name);
+        return;
+    };
+
+    let rule = DesignForExtension::from_config(&FixtureConfig::parse(&source).properties());
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(100.0);
+}
+
+#[test]
+fn test_input_design_for_extension() {
+    run_fixture("InputDesignForExtension.java");
+}
+
+#[test]
+fn test_input_design_for_extension_ignored_annotations() {
+    run_fixture("InputDesignForExtensionIgnoredAnnotations.java");
+}
+
+#[test]
+fn test_input_design_for_extension_required_javadoc_phrase() {
+    run_fixture("InputDesignForExtensionRequiredJavadocPhrase.java");
+}
+
+#[test]
+fn test_input_design_for_extension_native_methods() {
+    run_fixture("InputDesignForExtensionNativeMethods.java");
+}
+
+#[test]
+fn test_from_config_default() {
+    let props = Properties::new();
+    let rule = DesignForExtension::from_config(&props);
+    assert_eq!(
+        rule.ignored_annotations,
+        vec!["After", "AfterClass", "Before", "BeforeClass", "Test"]
+    );
+    assert!(rule.required_javadoc_phrase.is_match("anything"));
+}
+
+#[test]
+fn test_from_config_custom() {
+    let mut props = Properties::new();
+    props.insert("ignoredAnnotations", "Override, Deprecated");
+    props.insert("requiredJavadocPhrase", "This implementation");
+    let rule = DesignForExtension::from_config(&props);
+    assert_eq!(rule.ignored_annotations, vec!["Override", "Deprecated"]);
+    assert!(
+        rule.required_javadoc_phrase
+            .is_match("This implementation does")
+    );
+    assert!(!rule.required_javadoc_phrase.is_match("Returns one."));
+}
+
+#[test]
+fn test_overridable_method_violation() {
+    let source = r#"
+public class Foo {
+    public int a() {
+        return 1;
+    }
+
+    public final int b() {
+        return 2;
+    }
+}
+"#;
+    assert_eq!(
+        check_design_for_extension(source, DesignForExtension::default()),
+        vec![3]
+    );
+}
@@ -10,7 +10,7 @@ use lintal_linter::rules::EmptyLineSeparator;
 use lintal_linter::rules::whitespace::empty_line_separator::EmptyLineSeparatorToken;
 use lintal_linter::{CheckContext, Rule};
 use lintal_source_file::{LineIndex, SourceCode};
-use regex::Regex;
+use lintal_testkit::{TestResult, parse_expected_violations};
 use std::collections::HashSet;
 
 /// Configuration parsed from fixture header.
@@ -137,13 +137,6 @@ fn token_from_str(s: &str) -> Option<EmptyLineSeparatorToken> {
     }
 }
 
-/// Expected violation from test file comments.
-#[derive(Debug, Clone)]
-struct ExpectedViolation {
-    line: usize,
-    message_pattern: String,
-}
-
 /// Actual violation from our implementation.
 #[derive(Debug, Clone)]
 struct ActualViolation {
@@ -151,156 +144,6 @@ struct ActualViolation {
     message: String,
 }
 
-/// Parse expected violations from checkstyle test file comments.
-fn parse_expected_violations(source: &str) -> Vec<ExpectedViolation> {
-    let mut violations = vec![];
-
-    // Patterns for violation comments
-    let inline_re = Regex::new(r"//\s*violation\s+'([^']*)'").unwrap();
-    let inline_re2 = Regex::new(r"//\s*violation\s+''([^']*)'").unwrap();
-    let below_re = Regex::new(r"//\s*violation\s+below").unwrap();
-    let above_re = Regex::new(r"//\s*violation\s+above").unwrap();
-    let n_lines_above_re = Regex::new(r"//\s*violation\s+(\d+)\s+lines?\s+above").unwrap();
-    // Double-quoted message pattern (e.g., "message")
-    let double_quote_msg_re = Regex::new(r#"//\s*violation.*"([^"]*)""#).unwrap();
-    // Multiple violations pattern (e.g., "// 2 violations")
-    let n_violations_re = Regex::new(r"//\s*(\d+)\s+violations?").unwrap();
-
-    for (i, line) in source.lines().enumerate() {
-        let line_num = i + 1;
-
-        // Check for "violation N lines above" pattern
-        if let Some(caps) = n_lines_above_re.captures(line) {
-            let offset: usize = caps.get(1).unwrap().as_str().parse().unwrap_or(1);
-            let msg = if let Some(msg_caps) = double_quote_msg_re.captures(line) {
-                msg_caps
-                    .get(1)
-                    .map(|m| m.as_str().to_string())
-                    .unwrap_or_default()
-            } else if let Some(msg_caps) = inline_re.captures(line) {
-                msg_caps
-                    .get(1)
-                    .map(|m| m.as_str().to_string())
-                    .unwrap_or_default()
-            } else {
-                String::new()
-            };
-            violations.push(ExpectedViolation {
-                line: line_num.saturating_sub(offset),
-                message_pattern: msg,
-            });
-            continue;
-        }
-
-        // Check for "violation above this line" pattern - different from "violation above"
-        // "violation above this line" means the violation is ON this line, describing that the empty
-        // lines are above it. "violation above" means the violation is 1 line above.
-        if line.contains("violation above this line") {
-            violations.push(ExpectedViolation {
-                line: line_num,
-                message_pattern: String::new(),
-            });
-            continue;
-        }
-
-        // Check for "violation above" pattern (simple, 1 line above)
-        if above_re.is_match(line) {
-            // Extract message if present
-            let msg = if let Some(msg_caps) = double_quote_msg_re.captures(line) {
-                msg_caps
-                    .get(1)
-                    .map(|m| m.as_str().to_string())
-                    .unwrap_or_default()
-            } else if let Some(caps) = inline_re.captures(line) {
-                caps.get(1)
-                    .map(|m| m.as_str().to_string())
-                    .unwrap_or_default()
-            } else if let Some(caps) = inline_re2.captures(line) {
-                caps.get(1)
-                    .map(|m| m.as_str().to_string())
-                    .unwrap_or_default()
-            } else {
-                String::new()
-            };
-            violations.push(ExpectedViolation {
-                line: line_num - 1,
-                message_pattern: msg,
-            });
-            continue;
-        }
-
-        // Check for "violation below" pattern
-        if below_re.is_match(line) {
-            // Extract message if present
-            let msg = if let Some(caps) = inline_re.captures(line) {
-                caps.get(1)
-                    .map(|m| m.as_str().to_string())
-                    .unwrap_or_default()
-            } else {
-                String::new()
-            };
-            violations.push(ExpectedViolation {
-                line: line_num + 1,
-                message_pattern: msg,
-            });
-            continue;
-        }
-
-        // Check for inline violation with double quote pattern ''X'
-        if let Some(caps) = inline_re2.captures(line) {
-            let msg = caps
-                .get(1)
-                .map(|m| m.as_str().to_string())
-                .unwrap_or_default();
-            violations.push(ExpectedViolation {
-                line: line_num,
-                message_pattern: msg,
-            });
-            continue;
-        }
-
-        // Check for inline violation with single quote pattern 'X'
-        if let Some(caps) = inline_re.captures(line) {
-            let msg = caps
-                .get(1)
-                .map(|m| m.as_str().to_string())
-                .unwrap_or_default();
-            violations.push(ExpectedViolation {
-                line: line_num,
-                message_pattern: msg,
-            });
-            continue;
-        }
-
-        // Check for inline violation with double quote pattern "X"
-        // This handles cases like: // violation "'CLASS_DEF' should be separated..."
-        if let Some(caps) = double_quote_msg_re.captures(line) {
-            let msg = caps
-                .get(1)
-                .map(|m| m.as_str().to_string())
-                .unwrap_or_default();
-            violations.push(ExpectedViolation {
-                line: line_num,
-                message_pattern: msg,
-            });
-            continue;
-        }
-
-        // Check for "N violations" pattern (e.g., "// 2 violations")
-        if let Some(caps) = n_violations_re.captures(line) {
-            let count: usize = caps.get(1).unwrap().as_str().parse().unwrap_or(1);
-            for _ in 0..count {
-                violations.push(ExpectedViolation {
-                    line: line_num,
-                    message_pattern: String::new(),
-                });
-            }
-        }
-    }
-
-    violations
-}
-
 /// Run EmptyLineSeparator rule on source and collect violations.
 fn check_empty_line_separator(source: &str, config: &FixtureConfig) -> Vec<ActualViolation> {
     let mut parser = JavaParser::new();
@@ -359,32 +202,17 @@ fn test_fixture(file_name: &str) -> Option<FixtureResult> {
     let expected = parse_expected_violations(&source);
     let actual = check_empty_line_separator(&source, &config);
 
-    // Match violations by line number, accounting for multiple violations on the same line.
-    let mut expected_counts = std::collections::HashMap::new();
-    for v in &expected {
-        *expected_counts.entry(v.line).or_insert(0usize) += 1;
-    }
-    let mut actual_counts = std::collections::HashMap::new();
-    for v in &actual {
-        *actual_counts.entry(v.line).or_insert(0usize) += 1;
-    }
-
-    let mut correct = 0usize;
-    for (line, expected_count) in &expected_counts {
-        let actual_count = actual_counts.get(line).copied().unwrap_or(0);
-        correct += (*expected_count).min(actual_count);
-    }
-    let expected_total: usize = expected_counts.values().sum();
-    let actual_total: usize = actual_counts.values().sum();
-    let missing: usize = expected_total.saturating_sub(correct);
-    let false_positives: usize = actual_total.saturating_sub(correct);
+    let result = TestResult::compare(
+        expected.iter().map(|v| v.line).collect(),
+        actual.iter().map(|v| v.line).collect(),
+    );
 
     Some(FixtureResult {
         expected: expected.len(),
         found: actual.len(),
-        correct,
-        missing,
-        false_positives,
+        correct: result.exact_matches.len(),
+        missing: result.missing.len(),
+        false_positives: result.false_positives.len(),
     })
 }
 
@@ -503,7 +331,11 @@ fn test_input_empty_line_separator_detailed() {
     let expected = parse_expected_violations(&source);
     println!("\nExpected violations ({}):", expected.len());
     for v in &expected {
-        println!("  Line {}: {}", v.line, v.message_pattern);
+        println!(
+            "  Line {}: {}",
+            v.line,
+            v.message.as_deref().unwrap_or_default()
+        );
     }
 
     let actual = check_empty_line_separator(&source, &config);
@@ -542,7 +374,11 @@ fn test_multiple_empty_lines_detailed() {
     let expected = parse_expected_violations(&source);
     println!("\nExpected violations ({}):", expected.len());
     for v in &expected {
-        println!("  Line {}: {}", v.line, v.message_pattern);
+        println!(
+            "  Line {}: {}",
+            v.line,
+            v.message.as_deref().unwrap_or_default()
+        );
     }
 
     let actual = check_empty_line_separator(&source, &config);
@@ -568,7 +404,11 @@ fn test_multiple_empty_lines_inside_detailed() {
     let expected = parse_expected_violations(&source);
     println!("\nExpected violations ({}):", expected.len());
     for v in &expected {
-        println!("  Line {}: {}", v.line, v.message_pattern);
+        println!(
+            "  Line {}: {}",
+            v.line,
+            v.message.as_deref().unwrap_or_default()
+        );
     }
 
     let actual = check_empty_line_separator(&source, &config);
@@ -594,7 +434,11 @@ fn test_multiple_inside_details() {
     let expected = parse_expected_violations(&source);
     println!("\nExpected violations ({}):", expected.len());
     for v in &expected {
-        println!("  Line {}: {}", v.line, v.message_pattern);
+        println!(
+            "  Line {}: {}",
+            v.line,
+            v.message.as_deref().unwrap_or_default()
+        );
     }
 
     let actual = check_empty_line_separator(&source, &config);
@@ -635,7 +479,11 @@ fn test_postfix_corner_cases_detailed() {
     let expected = parse_expected_violations(&source);
     println!("\nExpected violations ({}):", expected.len());
     for v in &expected {
-        println!("  Line {}: {}", v.line, v.message_pattern);
+        println!(
+            "  Line {}: {}",
+            v.line,
+            v.message.as_deref().unwrap_or_default()
+        );
     }
 
     let actual = check_empty_line_separator(&source, &config);
@@ -676,7 +524,11 @@ fn test_with_comments_detailed() {
     let expected = parse_expected_violations(&source);
     println!("\nExpected violations ({}):", expected.len());
     for v in &expected {
-        println!("  Line {}: {}", v.line, v.message_pattern);
+        println!(
+            "  Line {}: {}",
+            v.line,
+            v.message.as_deref().unwrap_or_default()
+        );
     }
 
     let actual = check_empty_line_separator(&source, &config);
@@ -717,7 +569,11 @@ fn test_with_javadoc_detailed() {
     let expected = parse_expected_violations(&source);
     println!("\nExpected violations ({}):", expected.len());
     for v in &expected {
-        println!("  Line {}: {}", v.line, v.message_pattern);
+        println!(
+            "  Line {}: {}",
+            v.line,
+            v.message.as_deref().unwrap_or_default()
+        );
     }
 
     let actual = check_empty_line_separator(&source, &config);
@@ -758,7 +614,11 @@ fn test_with_javadoc2_detailed() {
     let expected = parse_expected_violations(&source);
     println!("\nExpected violations ({}):", expected.len());
     for v in &expected {
-        println!("  Line {}: {}", v.line, v.message_pattern);
+        println!(
+            "  Line {}: {}",
+            v.line,
+            v.message.as_deref().unwrap_or_default()
+        );
     }
 
     let actual = check_empty_line_separator(&source, &config);
@@ -799,7 +659,11 @@ fn test_with_emoji_detailed() {
     let expected = parse_expected_violations(&source);
     println!("\nExpected violations ({}):", expected.len());
     for v in &expected {
-        println!("  Line {}: {}", v.line, v.message_pattern);
+        println!(
+            "  Line {}: {}",
+            v.line,
+            v.message.as_deref().unwrap_or_default()
+        );
     }
 
     let actual = check_empty_line_separator(&source, &config);
@@ -901,7 +765,11 @@ fn test_recursive_detailed() {
     let expected = parse_expected_violations(&source);
     println!("\nExpected violations ({}):", expected.len());
     for v in &expected {
-        println!("  Line {}: {}", v.line, v.message_pattern);
+        println!(
+            "  Line {}: {}",
+            v.line,
+            v.message.as_deref().unwrap_or_default()
+        );
     }
 
     let actual = check_empty_line_separator(&source, &config);
@@ -942,7 +810,11 @@ fn test_new_method_def_detailed() {
     let expected = parse_expected_violations(&source);
     println!("\nExpected violations ({}):", expected.len());
     for v in &expected {
-        println!("  Line {}: {}", v.line, v.message_pattern);
+        println!(
+            "  Line {}: {}",
+            v.line,
+            v.message.as_deref().unwrap_or_default()
+        );
     }
 
     let actual = check_empty_line_separator(&source, &config);
@@ -0,0 +1,85 @@
+//! ExecutableStatementCount checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::ExecutableStatementCount;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the ExecutableStatementCount rule on source code and return violation lines.
+fn check_executable_statement_count(source: &str, rule: ExecutableStatementCount) -> Vec<usize> {
+    check_source(&[Box::new(rule)], source)
+        .iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::sizes_test_input("executablestatementcount", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping {}: checkstyle repo not available", file_name);
+        return;
+    };
+
+    let rule = ExecutableStatementCount::from_config(&FixtureConfig::parse(&source).properties());
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(100.0);
+}
+
+#[test]
+fn test_input_executable_statement_count_max_zero() {
+    run_fixture("InputExecutableStatementCountMaxZero.java");
+}
+
+#[test]
+fn test_input_executable_statement_count_default_config() {
+    run_fixture("InputExecutableStatementCountDefaultConfig.java");
+}
+
+#[test]
+fn test_input_executable_statement_count_lambdas() {
+    run_fixture("InputExecutableStatementCountLambdas.java");
+}
+
+#[test]
+fn test_from_config_default() {
+    let props = Properties::new();
+    let rule = ExecutableStatementCount::from_config(&props);
+    assert_eq!(rule.max, 30);
+    assert_eq!(rule.tokens.len(), 6);
+}
+
+#[test]
+fn test_from_config_custom() {
+    let mut props = Properties::new();
+    props.insert("max", "0");
+    props.insert("tokens", "METHOD_DEF, LAMBDA");
+    let rule = ExecutableStatementCount::from_config(&props);
+    assert_eq!(rule.max, 0);
+    assert!(rule.tokens.contains("LAMBDA"));
+    assert!(!rule.tokens.contains("CTOR_DEF"));
+}
+
+#[test]
+fn test_too_many_statements() {
+    let source = r#"
+class Foo {
+    void method() {
+        foo();
+        bar();
+    }
+}
+"#;
+    let rule = ExecutableStatementCount {
+        max: 1,
+        ..ExecutableStatementCount::default()
+    };
+    assert_eq!(check_executable_statement_count(source, rule), vec![3]);
+}
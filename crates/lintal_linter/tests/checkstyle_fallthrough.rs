@@ -1,15 +1,13 @@
 //! FallThrough checkstyle compatibility tests.
 
 mod checkstyle_repo;
-mod test_harness;
 
 use lintal_java_cst::TreeWalker;
 use lintal_java_parser::JavaParser;
 use lintal_linter::rules::FallThrough;
 use lintal_linter::{CheckContext, Rule};
 use lintal_source_file::{LineIndex, SourceCode};
-use regex::Regex;
-use test_harness::TestResult;
+use lintal_testkit::{TestResult, expected_violation_lines};
 
 /// Run the FallThrough rule on source code and return violation lines.
 fn check_fall_through(source: &str) -> Vec<usize> {
@@ -39,64 +37,6 @@ fn check_fall_through(source: &str) -> Vec<usize> {
     violations
 }
 
-/// Parse expected violations from checkstyle test file comments.
-fn parse_expected_violations(source: &str) -> Vec<usize> {
-    let mut violations = vec![];
-
-    let n_above_lines_re = Regex::new(r"//\s*violation\s+(\d+)\s+lines?\s+above").unwrap();
-    let n_violations_above_re = Regex::new(r"//\s*(\d+)\s+violations?\s+above").unwrap();
-    let n_violations_below_re = Regex::new(r"//\s*(\d+)\s+violations?\s+below").unwrap();
-    let n_violations_re = Regex::new(r"//\s*(\d+)\s+violations?\b").unwrap();
-    let above_re = Regex::new(r"//\s*violation\s+above").unwrap();
-    let below_re = Regex::new(r"//\s*violation\s+below").unwrap();
-    let inline_re = Regex::new(r"//\s*violation\b").unwrap();
-
-    for (i, line) in source.lines().enumerate() {
-        let line_num = i + 1;
-
-        if let Some(caps) = n_above_lines_re.captures(line) {
-            if let Some(n) = caps.get(1).and_then(|m| m.as_str().parse::<usize>().ok())
-                && line_num > n
-            {
-                violations.push(line_num - n);
-            }
-        } else if let Some(caps) = n_violations_above_re.captures(line) {
-            if let Some(n) = caps.get(1).and_then(|m| m.as_str().parse::<usize>().ok())
-                && line_num > 1
-            {
-                for _ in 0..n {
-                    violations.push(line_num - 1);
-                }
-            }
-        } else if let Some(caps) = n_violations_below_re.captures(line) {
-            if let Some(n) = caps.get(1).and_then(|m| m.as_str().parse::<usize>().ok()) {
-                for _ in 0..n {
-                    violations.push(line_num + 1);
-                }
-            }
-        } else if above_re.is_match(line) {
-            if line_num > 1 {
-                violations.push(line_num - 1);
-            }
-        } else if below_re.is_match(line) {
-            violations.push(line_num + 1);
-        } else if let Some(caps) = n_violations_re.captures(line) {
-            if !line.contains("above")
-                && !line.contains("below")
-                && let Some(n) = caps.get(1).and_then(|m| m.as_str().parse::<usize>().ok())
-            {
-                for _ in 0..n {
-                    violations.push(line_num);
-                }
-            }
-        } else if inline_re.is_match(line) {
-            violations.push(line_num);
-        }
-    }
-
-    violations
-}
-
 fn load_fixture(file_name: &str) -> Option<String> {
     let path = checkstyle_repo::coding_test_input("fallthrough", file_name)?;
     std::fs::read_to_string(&path).ok()
@@ -109,7 +49,7 @@ fn test_input_fall_through() {
         return;
     };
 
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_fall_through(&source);
 
     let result = TestResult::compare(expected, actual);
@@ -2,10 +2,8 @@
 
 mod checkstyle_repo;
 
-use lintal_java_cst::TreeWalker;
-use lintal_java_parser::JavaParser;
+use lintal_linter::FileRule;
 use lintal_linter::rules::FileTabCharacter;
-use lintal_linter::{CheckContext, Rule};
 use lintal_source_file::{LineIndex, SourceCode};
 
 /// A violation at a specific location.
@@ -62,30 +60,20 @@ fn check_file_tab_character_with_config(
     source: &str,
     config: &FileTabCharacterConfig,
 ) -> Vec<Violation> {
-    let mut parser = JavaParser::new();
-    let Some(result) = parser.parse(source) else {
-        panic!("Failed to parse source");
-    };
-
     let rule = config.to_rule();
-    let ctx = CheckContext::new(source);
     let line_index = LineIndex::from_source_text(source);
     let source_code = SourceCode::new(source, &line_index);
 
-    let mut violations = vec![];
-
-    for node in TreeWalker::new(result.tree.root_node(), source) {
-        let diagnostics = rule.check(&ctx, &node);
-        for diagnostic in diagnostics {
+    rule.check_file(None, source, &line_index)
+        .iter()
+        .map(|diagnostic| {
             let loc = source_code.line_column(diagnostic.range.start());
-            violations.push(Violation {
+            Violation {
                 line: loc.line.get(),
                 column: loc.column.get(),
-            });
-        }
-    }
-
-    violations
+            }
+        })
+        .collect()
 }
 
 /// Load a checkstyle test input file.
@@ -346,20 +334,9 @@ fn test_empty_file() {
 fn test_tab_fix() {
     let source = "class\tFoo"; // Tab after "class" (column 5)
 
-    let mut parser = JavaParser::new();
-    let Some(result) = parser.parse(source) else {
-        panic!("Failed to parse source");
-    };
-
     let rule = FileTabCharacter::default();
-    let ctx = CheckContext::new(source);
-
-    // Get diagnostics from root node
-    let mut all_diagnostics = vec![];
-    for node in TreeWalker::new(result.tree.root_node(), source) {
-        let diagnostics = rule.check(&ctx, &node);
-        all_diagnostics.extend(diagnostics);
-    }
+    let line_index = LineIndex::from_source_text(source);
+    let all_diagnostics = rule.check_file(None, source, &line_index);
 
     assert_eq!(all_diagnostics.len(), 1, "Should have one diagnostic");
 
@@ -10,6 +10,7 @@ use lintal_java_parser::JavaParser;
 use lintal_linter::rules::FinalLocalVariable;
 use lintal_linter::{CheckContext, FromConfig, Rule};
 use lintal_source_file::{LineIndex, SourceCode};
+use lintal_testkit::{assert_violations, expected_violation_lines};
 use std::collections::HashMap;
 
 /// A violation at a specific location.
@@ -63,49 +64,6 @@ fn load_finallocalvariable_fixture(file_name: &str) -> Option<String> {
     std::fs::read_to_string(&path).ok()
 }
 
-/// Helper to verify violations match expected.
-fn verify_violations(violations: &[Violation], expected: &[Violation]) {
-    let mut missing = vec![];
-    let mut unexpected = vec![];
-
-    for exp in expected {
-        let matched = violations
-            .iter()
-            .any(|v| v.line == exp.line && v.column == exp.column);
-
-        if !matched {
-            missing.push(exp.clone());
-        }
-    }
-
-    for actual in violations {
-        let matched = expected
-            .iter()
-            .any(|v| v.line == actual.line && v.column == actual.column);
-
-        if !matched {
-            unexpected.push(actual.clone());
-        }
-    }
-
-    if !missing.is_empty() || !unexpected.is_empty() {
-        println!("\n=== Violations Report ===");
-        if !missing.is_empty() {
-            println!("\nMissing violations:");
-            for v in &missing {
-                println!("  {}:{}", v.line, v.column);
-            }
-        }
-        if !unexpected.is_empty() {
-            println!("\nUnexpected violations:");
-            for v in &unexpected {
-                println!("  {}:{}", v.line, v.column);
-            }
-        }
-        panic!("Violation mismatch detected");
-    }
-}
-
 // =============================================================================
 // Test: testInputFinalLocalVariableOne
 // File: InputFinalLocalVariableOne.java
@@ -156,7 +114,7 @@ fn test_input_final_local_variable_one() {
         Violation::new(85, 26),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Minimal test to debug assignment tracking
@@ -192,7 +150,7 @@ public class Test {
     // Should only report 'a' at line 5
     let expected = vec![Violation::new(5, 13)];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test all forms of assignment operators
@@ -269,7 +227,7 @@ public class Test {
     // Should only report 'a' at line 5
     let expected = vec![Violation::new(5, 13)];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test if/else control flow scenarios
@@ -329,7 +287,7 @@ public class Test {
         Violation::new(34, 13), // e
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test case from checkstyle: variable assigned in all branches then again later
@@ -373,7 +331,7 @@ public class Test {
         Violation::new(20, 13), // a
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test case from artio: variable assigned in else block, then conditionally reassigned
@@ -439,7 +397,7 @@ public class Test {
         Violation::new(39, 16), // y
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -474,7 +432,7 @@ fn test_final_local_variable_switch_assignment() {
         Violation::new(92, 16), // res
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -501,7 +459,7 @@ fn test_variable_is_assigned_inside_and_outside_switch() {
         Violation::new(39, 13), // b
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -526,7 +484,7 @@ fn test_final_local_variable_switch_statement() {
     // No violations expected - variable x is assigned in all cases but falls through
     let expected = vec![];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test switch with all branches assigning
@@ -558,7 +516,7 @@ public class Test {
     // Should report 'a' at line 5
     let expected = vec![Violation::new(5, 13)];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test switch with some branches assigning
@@ -591,7 +549,7 @@ public class Test {
     // because it's never reassigned after the first assignment in each path
     let expected = vec![Violation::new(5, 13)];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test switch with assignment after branches
@@ -624,7 +582,7 @@ public class Test {
     // Should not report 'a' - reassigned after switch
     let expected = vec![];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test switch expression (arrow syntax)
@@ -656,7 +614,7 @@ public class Test {
     // Should report 'a' at line 5
     let expected = vec![Violation::new(5, 13)];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test switch rule with assignment
@@ -682,7 +640,7 @@ public class Test {
     // Should report 'res' at line 5
     let expected = vec![Violation::new(5, 16)];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -719,7 +677,7 @@ public class Test {
     // Checkstyle skips all variables declared in for-loop initializers
     let expected: Vec<Violation> = vec![];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test while loop
@@ -751,7 +709,7 @@ public class Test {
     // Should report 'j' at line 11
     let expected = vec![Violation::new(11, 13)];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test do-while loop
@@ -781,7 +739,7 @@ public class Test {
     // Should report 'j' at line 11
     let expected = vec![Violation::new(11, 13)];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test enhanced for loop without validateEnhancedForLoopVariable
@@ -814,7 +772,7 @@ public class Test {
     // 'array' should be final (validateEnhancedForLoopVariable is false by default)
     let expected = vec![Violation::new(4, 15)];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test enhanced for loop with validateEnhancedForLoopVariable enabled
@@ -848,7 +806,7 @@ public class Test {
     // Should report 'array' at line 4 and 'x' at line 7
     let expected = vec![Violation::new(4, 15), Violation::new(7, 18)];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test variable declared outside loop, assigned inside
@@ -885,7 +843,7 @@ public class Test {
         Violation::new(12, 20), // temp
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test from checkstyle: InputFinalLocalVariableEnhancedForLoopVariable
@@ -913,7 +871,7 @@ fn test_input_final_local_variable_enhanced_for_loop_variable() {
         Violation::new(51, 16), // a (second declaration)
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test from checkstyle: InputFinalLocalVariableBreak
@@ -934,7 +892,7 @@ fn test_input_final_local_variable_break() {
         Violation::new(15, 19), // e
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -959,7 +917,7 @@ fn test_lambda_parameters_not_checked() {
         Violation::new(43, 16), // result
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test multi-catch parameters should NOT be checked
@@ -977,7 +935,7 @@ fn test_multi_catch_parameters_not_checked() {
     // Multi-catch parameter 'ex' should NOT be reported
     let expected = vec![];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test anonymous class creates separate scope
@@ -999,7 +957,7 @@ fn test_anonymous_class_separate_scope() {
         Violation::new(14, 16), // testSupport
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test constructor parameters should be checked
@@ -1011,18 +969,17 @@ fn test_constructor_parameters() {
         return;
     };
 
-    // Note: This test expects PARAMETER_DEF to be in tokens
-    // The comments say "tokens = PARAMETER_DEF"
-    // However, our current implementation only handles VARIABLE_DEF (local variables)
-    // Constructor parameters are not local variables, they're formal parameters
-    // We need to skip this test for now as it requires a different token type
-    let properties = HashMap::new();
+    // tokens = PARAMETER_DEF, as the fixture header says; the expected
+    // violations are taken from its comments
+    let properties = HashMap::from([("tokens", "PARAMETER_DEF")]);
     let violations = check_final_local_variable(&source, properties);
 
-    // Constructor parameters are NOT checked by default (tokens = VARIABLE_DEF by default)
-    let expected = vec![];
-
-    verify_violations(&violations, &expected);
+    let mut lines: Vec<usize> = violations.iter().map(|v| v.line).collect();
+    lines.sort_unstable();
+    let mut expected = expected_violation_lines(&source);
+    expected.sort_unstable();
+    assert!(!expected.is_empty());
+    assert_eq!(lines, expected);
 }
 
 // Test validateUnnamedVariables = false (default)
@@ -1052,7 +1009,7 @@ fn test_validate_unnamed_variables_false() {
         Violation::new(50, 18), // __
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Test validateUnnamedVariables = true
@@ -1091,5 +1048,5 @@ fn test_validate_unnamed_variables_true() {
         Violation::new(50, 18), // __
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
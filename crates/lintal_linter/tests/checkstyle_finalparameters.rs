@@ -10,6 +10,7 @@ use lintal_java_parser::JavaParser;
 use lintal_linter::rules::FinalParameters;
 use lintal_linter::{CheckContext, FromConfig, Rule};
 use lintal_source_file::{LineIndex, SourceCode};
+use lintal_testkit::assert_violations;
 use std::collections::HashMap;
 
 /// A violation at a specific location.
@@ -63,49 +64,6 @@ fn load_finalparameters_fixture(file_name: &str) -> Option<String> {
     std::fs::read_to_string(&path).ok()
 }
 
-/// Helper to verify violations match expected.
-fn verify_violations(violations: &[Violation], expected: &[Violation]) {
-    let mut missing = vec![];
-    let mut unexpected = vec![];
-
-    for exp in expected {
-        let matched = violations
-            .iter()
-            .any(|v| v.line == exp.line && v.column == exp.column);
-
-        if !matched {
-            missing.push(exp.clone());
-        }
-    }
-
-    for actual in violations {
-        let matched = expected
-            .iter()
-            .any(|v| v.line == actual.line && v.column == actual.column);
-
-        if !matched {
-            unexpected.push(actual.clone());
-        }
-    }
-
-    if !missing.is_empty() || !unexpected.is_empty() {
-        println!("\n=== Violations Report ===");
-        if !missing.is_empty() {
-            println!("\nMissing violations:");
-            for v in &missing {
-                println!("  {}:{}", v.line, v.column);
-            }
-        }
-        if !unexpected.is_empty() {
-            println!("\nUnexpected violations:");
-            for v in &unexpected {
-                println!("  {}:{}", v.line, v.column);
-            }
-        }
-        panic!("Violation mismatch detected");
-    }
-}
-
 // =============================================================================
 // Test: testDefaultTokens
 // File: InputFinalParameters.java
@@ -148,7 +106,7 @@ fn test_default_tokens() {
         Violation::new(121, 18),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -178,7 +136,7 @@ fn test_ctor_token() {
         Violation::new(49, 27),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -218,7 +176,7 @@ fn test_method_token() {
         Violation::new(122, 18),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -248,7 +206,7 @@ fn test_catch_token() {
         Violation::new(140, 16),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -273,7 +231,7 @@ fn test_for_each_clause_token() {
 
     let expected = vec![Violation::new(158, 13), Violation::new(166, 13)];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -312,7 +270,7 @@ fn test_ignore_primitive_types_parameters() {
         Violation::new(22, 32),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -364,7 +322,7 @@ fn test_primitive_types_parameters() {
         Violation::new(31, 32),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -386,7 +344,7 @@ fn test_receiver_parameters() {
 
     let expected = vec![];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -427,7 +385,7 @@ fn test_unnamed_parameters_property_true() {
         Violation::new(52, 14),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -473,7 +431,7 @@ fn test_unnamed_parameters_property_false() {
         Violation::new(52, 14),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -504,5 +462,5 @@ fn test_method_token_in_interface() {
         Violation::new(28, 27),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
@@ -0,0 +1,203 @@
+//! GenericWhitespace checkstyle compatibility tests.
+//!
+//! This test harness validates lintal's GenericWhitespace implementation
+//! against checkstyle's own test fixtures. It parses expected violations from
+//! comments in the test files and reports:
+//! - Missing matches: violations checkstyle expects but we missed
+//! - False positives: violations we report that checkstyle doesn't expect
+
+mod checkstyle_repo;
+
+use lintal_java_cst::TreeWalker;
+use lintal_java_parser::JavaParser;
+use lintal_linter::rules::whitespace::GenericWhitespace;
+use lintal_linter::{CheckContext, Rule};
+use lintal_testkit::expected_violation_lines;
+use std::collections::BTreeMap;
+
+/// Violation counts per line.
+type LineCounts = BTreeMap<usize, usize>;
+
+/// Results of running a single test fixture.
+#[derive(Debug)]
+struct TestResult {
+    file_name: String,
+    expected: usize,
+    missing: Vec<(usize, usize)>,
+    false_positives: Vec<(usize, usize)>,
+}
+
+impl TestResult {
+    fn print_summary(&self) {
+        println!("\n=== {} ===", self.file_name);
+        println!(
+            "Expected: {}, Missing: {}, False Positives: {}",
+            self.expected,
+            self.missing.len(),
+            self.false_positives.len()
+        );
+        for (line, count) in &self.missing {
+            println!("  Missing: line {} ({} violations)", line, count);
+        }
+        for (line, count) in &self.false_positives {
+            println!("  False positive: line {} ({} violations)", line, count);
+        }
+    }
+
+    fn is_perfect(&self) -> bool {
+        self.missing.is_empty() && self.false_positives.is_empty()
+    }
+}
+
+/// Count the violations a checkstyle test file expects per line.
+fn expected_counts(source: &str) -> LineCounts {
+    let mut counts = LineCounts::new();
+    for line in expected_violation_lines(source) {
+        *counts.entry(line).or_default() += 1;
+    }
+    counts
+}
+
+/// Run GenericWhitespace rule on source and count violations per line.
+fn check_generic_whitespace(source: &str) -> LineCounts {
+    let mut parser = JavaParser::new();
+    let Some(result) = parser.parse(source) else {
+        panic!("Failed to parse source");
+    };
+
+    let ctx = CheckContext::new(source);
+    let source_code = ctx.source_code();
+    let rule = GenericWhitespace;
+
+    let mut counts = LineCounts::new();
+    for node in TreeWalker::new(result.tree.root_node(), source) {
+        for diagnostic in rule.check(&ctx, &node) {
+            let loc = source_code.line_column(diagnostic.range.start());
+            *counts.entry(loc.line.get()).or_default() += 1;
+        }
+    }
+    counts
+}
+
+/// Lines where `actual` has fewer violations than `expected`, with the
+/// number of violations short.
+fn shortfall(expected: &LineCounts, actual: &LineCounts) -> Vec<(usize, usize)> {
+    expected
+        .iter()
+        .filter_map(|(&line, &count)| {
+            let found = actual.get(&line).copied().unwrap_or(0);
+            (found < count).then_some((line, count - found))
+        })
+        .collect()
+}
+
+/// Run a single test fixture and compare against its expected violations.
+fn run_fixture(file_name: &str) -> Option<TestResult> {
+    let path = checkstyle_repo::whitespace_test_input("genericwhitespace", file_name)?;
+    let source = std::fs::read_to_string(&path).ok()?;
+
+    let expected = expected_counts(&source);
+    let actual = check_generic_whitespace(&source);
+
+    Some(TestResult {
+        file_name: file_name.to_string(),
+        expected: expected.values().sum(),
+        missing: shortfall(&expected, &actual),
+        false_positives: shortfall(&actual, &expected),
+    })
+}
+
+/// All checkstyle GenericWhitespace test fixtures.
+const ALL_FIXTURES: &[&str] = &[
+    "InputGenericWhitespaceDefault.java",
+    "InputGenericWhitespaceList.java",
+    "InputGenericWhitespaceInnerClass.java",
+    "InputGenericWhitespaceNested.java",
+    "InputGenericWhitespaceEndsTheLine.java",
+    "InputGenericWhitespaceMethodRef1.java",
+    "InputGenericWhitespaceMethodRef2.java",
+    "InputGenericWhitespaceBeforeCtorInvocation.java",
+    "InputGenericWhitespaceBeforeRecordHeader.java",
+    "InputGenericWhitespaceRecordPattern.java",
+    "InputGenericWhitespaceWithEmoji.java",
+];
+
+#[test]
+fn test_all_fixtures_comprehensive() {
+    let mut results = vec![];
+
+    for fixture in ALL_FIXTURES {
+        match run_fixture(fixture) {
+            Some(result) => {
+                result.print_summary();
+                results.push(result);
+            }
+            None => {
+                println!("\n=== {} ===", fixture);
+                println!("SKIPPED: checkstyle repo not available or file not found");
+            }
+        }
+    }
+
+    let total_expected: usize = results.iter().map(|r| r.expected).sum();
+    let total_missing: usize = results.iter().map(|r| r.missing.len()).sum();
+    let total_false_positives: usize = results.iter().map(|r| r.false_positives.len()).sum();
+    let perfect_count = results.iter().filter(|r| r.is_perfect()).count();
+
+    println!("\nTotal expected violations: {}", total_expected);
+    println!("Total missing (false negatives): {}", total_missing);
+    println!("Total false positives: {}", total_false_positives);
+    println!("Perfect fixtures: {}/{}", perfect_count, results.len());
+}
+
+// =============================================================================
+// Basic Sanity Tests
+// =============================================================================
+
+#[test]
+fn test_well_formed_generics() {
+    let source = r#"
+class Test<T extends Comparable<T>> {
+    java.util.Map<String, java.util.List<T>> map = new java.util.HashMap<>();
+
+    public static <E> java.util.List<E> empty() {
+        return java.util.Collections.<E>emptyList();
+    }
+}
+"#;
+    assert!(check_generic_whitespace(source).is_empty());
+}
+
+#[test]
+fn test_whitespace_inside_brackets() {
+    let source = r#"
+class Test {
+    java.util.List< String > list;
+}
+"#;
+    let violations = check_generic_whitespace(source);
+    assert_eq!(violations, LineCounts::from([(3, 2)]));
+}
+
+#[test]
+fn test_method_type_parameters() {
+    let source = r#"
+class Test {
+    public<T>void foo() {}
+}
+"#;
+    let violations = check_generic_whitespace(source);
+    assert_eq!(violations, LineCounts::from([(3, 2)]));
+}
+
+#[test]
+fn test_parse_violation_comments() {
+    let source = r#"
+List< String> a; // violation ''<' is followed by whitespace.'
+List < String > b; // 3 violations
+// violation below ''>' is preceded with whitespace.'
+List<String > c;
+"#;
+    let expected = expected_counts(source);
+    assert_eq!(expected, LineCounts::from([(2, 1), (3, 3), (5, 1)]));
+}
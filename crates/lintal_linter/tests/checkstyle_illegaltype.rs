@@ -1,16 +1,13 @@
 //! IllegalType checkstyle compatibility tests.
 
 mod checkstyle_repo;
-mod test_harness;
 
 use lintal_java_cst::TreeWalker;
 use lintal_java_parser::JavaParser;
 use lintal_linter::rules::IllegalType;
-use lintal_linter::{CheckContext, FromConfig, Properties, Rule};
+use lintal_linter::{CheckContext, FromConfig, Rule};
 use lintal_source_file::{LineIndex, SourceCode};
-use regex::Regex;
-use std::collections::HashMap;
-use test_harness::TestResult;
+use lintal_testkit::{FixtureConfig, TestResult, expected_violation_lines};
 
 /// Run the IllegalType rule on source code and return violation lines.
 fn check_illegal_type(source: &str, rule: &IllegalType) -> Vec<usize> {
@@ -36,125 +33,16 @@ fn check_illegal_type(source: &str, rule: &IllegalType) -> Vec<usize> {
     violations
 }
 
-/// Parse expected violations from checkstyle test file comments.
-fn parse_expected_violations(source: &str) -> Vec<usize> {
-    let mut violations = vec![];
-
-    let n_above_lines_re = Regex::new(r"//\s*violation\s+(\d+)\s+lines?\s+above").unwrap();
-    let n_violations_above_re = Regex::new(r"//\s*(\d+)\s+violations?\s+above").unwrap();
-    let n_violations_below_re = Regex::new(r"//\s*(\d+)\s+violations?\s+below").unwrap();
-    let n_violations_re = Regex::new(r"//\s*(\d+)\s+violations?\b").unwrap();
-    let above_re = Regex::new(r"//\s*violation\s+above").unwrap();
-    let below_re = Regex::new(r"//\s*violation\s+below").unwrap();
-    let inline_re = Regex::new(r"//\s*violation\b").unwrap();
-
-    for (i, line) in source.lines().enumerate() {
-        let line_num = i + 1;
-
-        if let Some(caps) = n_above_lines_re.captures(line) {
-            if let Some(n) = caps.get(1).and_then(|m| m.as_str().parse::<usize>().ok())
-                && line_num > n
-            {
-                violations.push(line_num - n);
-            }
-        } else if let Some(caps) = n_violations_above_re.captures(line) {
-            if let Some(n) = caps.get(1).and_then(|m| m.as_str().parse::<usize>().ok())
-                && line_num > 1
-            {
-                for _ in 0..n {
-                    violations.push(line_num - 1);
-                }
-            }
-        } else if let Some(caps) = n_violations_below_re.captures(line) {
-            if let Some(n) = caps.get(1).and_then(|m| m.as_str().parse::<usize>().ok()) {
-                for _ in 0..n {
-                    violations.push(line_num + 1);
-                }
-            }
-        } else if above_re.is_match(line) {
-            if line_num > 1 {
-                violations.push(line_num - 1);
-            }
-        } else if below_re.is_match(line) {
-            violations.push(line_num + 1);
-        } else if let Some(caps) = n_violations_re.captures(line) {
-            if !line.contains("above")
-                && !line.contains("below")
-                && let Some(n) = caps.get(1).and_then(|m| m.as_str().parse::<usize>().ok())
-            {
-                for _ in 0..n {
-                    violations.push(line_num);
-                }
-            }
-        } else if inline_re.is_match(line) {
-            violations.push(line_num);
-        }
-    }
-
-    violations
-}
-
-/// Parse config from the header block of a checkstyle test file.
-fn parse_config_from_header(source: &str) -> HashMap<String, String> {
-    let mut props = HashMap::new();
-
-    // Find the first /* ... */ block
-    let Some(start) = source.find("/*") else {
-        return props;
-    };
-    let Some(end) = source[start..].find("*/") else {
-        return props;
-    };
-    let block = &source[start + 2..start + end];
-
-    // Handle multi-line values with backslash continuation
-    let mut full_block = String::new();
-    for line in block.lines() {
-        let trimmed = line.trim();
-        if let Some(stripped) = trimmed.strip_suffix('\\') {
-            full_block.push_str(stripped);
-        } else {
-            full_block.push_str(trimmed);
-            full_block.push('\n');
-        }
-    }
-
-    for line in full_block.lines() {
-        let line = line.trim();
-        if line.is_empty()
-            || line.starts_with("IllegalType")
-            || line.starts_with("Config:")
-            || line.starts_with("*")
-        {
-            continue;
-        }
-        if let Some(eq_pos) = line.find('=') {
-            let key = line[..eq_pos].trim().to_string();
-            let value = line[eq_pos + 1..].trim().to_string();
-
-            // Skip (default) values and tokens (we don't use them)
-            if value.starts_with("(default)") || key == "tokens" {
-                continue;
-            }
-
-            props.insert(key, value);
-        }
-    }
-
-    props
-}
-
 fn load_fixture(file_name: &str) -> Option<String> {
     let path = checkstyle_repo::coding_test_input("illegaltype", file_name)?;
     std::fs::read_to_string(&path).ok()
 }
 
 fn build_rule_from_source(source: &str) -> IllegalType {
-    let config = parse_config_from_header(source);
-    let mut props: Properties = HashMap::new();
-    for (k, v) in &config {
-        props.insert(k.as_str(), v.as_str());
-    }
+    let config = FixtureConfig::parse(source);
+    let mut props = config.properties();
+    // The rule checks every kind of type reference regardless
+    props.remove("tokens");
     IllegalType::from_config(&props)
 }
 
@@ -166,7 +54,7 @@ fn test_input_defaults() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_illegal_type(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -184,7 +72,7 @@ fn test_input_abstract_class_names_true() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_illegal_type(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -202,7 +90,7 @@ fn test_input_abstract_class_names_false() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_illegal_type(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -220,7 +108,7 @@ fn test_input_ignore_method_names() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_illegal_type(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -238,7 +126,7 @@ fn test_input_member_modifiers() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_illegal_type(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -256,7 +144,7 @@ fn test_input_extends_implements() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_illegal_type(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -274,7 +162,7 @@ fn test_input_generics() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_illegal_type(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -293,7 +181,7 @@ fn test_input_greg_cal() {
 
     // This is a helper class with no config header
     let rule = IllegalType::default();
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_illegal_type(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -310,7 +198,7 @@ fn test_input_similar_class_name() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_illegal_type(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -0,0 +1,83 @@
+//! ImportOrder checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::ImportOrder;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the ImportOrder rule on source code and return violation lines.
+fn check_import_order(source: &str, properties: &Properties) -> Vec<usize> {
+    check_source(&[Box::new(ImportOrder::from_config(properties))], source)
+        .iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::imports_test_input("importorder", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping test: checkstyle repo not available");
+        return;
+    };
+
+    let rule = ImportOrder::from_config(&FixtureConfig::parse(&source).properties());
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(80.0);
+}
+
+#[test]
+fn test_input_import_order() {
+    run_fixture("InputImportOrder.java");
+}
+
+#[test]
+fn test_input_import_order_above() {
+    run_fixture("InputImportOrder_Above.java");
+}
+
+#[test]
+fn test_input_import_order_bottom() {
+    run_fixture("InputImportOrder_Bottom.java");
+}
+
+#[test]
+fn test_input_import_order_in_flow() {
+    run_fixture("InputImportOrder_InFlow.java");
+}
+
+#[test]
+fn test_input_import_order_top() {
+    run_fixture("InputImportOrder_Top.java");
+}
+
+#[test]
+fn test_sorted_imports_no_violation() {
+    let source = r#"
+import java.io.File;
+import java.util.List;
+
+class Foo {}
+"#;
+    let violations = check_import_order(source, &Properties::new());
+    assert!(violations.is_empty());
+}
+
+#[test]
+fn test_unsorted_imports_violation() {
+    let source = r#"
+import java.util.List;
+import java.io.File;
+
+class Foo {}
+"#;
+    let violations = check_import_order(source, &Properties::new());
+    assert_eq!(violations, vec![3]);
+}
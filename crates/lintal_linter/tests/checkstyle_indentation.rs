@@ -232,11 +232,12 @@ fn check_indentation(source: &str) -> HashSet<usize> {
     check_indentation_with_config(source, &properties)
 }
 
-/// Parse expected violations from checkstyle test file comments.
+/// Parse expected violations from the `//indent:` comments of checkstyle's
+/// indentation inputs, which `lintal_testkit` doesn't read.
 /// Lines with `//indent:X exp:Y warn` are expected to have violations.
 /// Lines with `//indent:X exp:Y` where X != Y are expected to have violations.
 /// Lines with `//below indent:X exp:Y warn` indicate the NEXT line should have a violation.
-fn parse_expected_violations(source: &str) -> HashSet<usize> {
+fn parse_indent_comments(source: &str) -> HashSet<usize> {
     let mut expected = HashSet::new();
     let lines: Vec<&str> = source.lines().collect();
 
@@ -353,7 +354,7 @@ fn test_valid_block_indent() {
     };
 
     let violations = check_indentation(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = parse_indent_comments(&source);
 
     // Valid file should have no violations beyond what's marked
     // For now, just check we don't crash and produce reasonable results
@@ -1560,7 +1561,7 @@ fn get_config_overrides(file_name: &str) -> Option<HashMap<String, String>> {
 fn run_fixture_test(file_name: &str) -> Option<FixtureTestResult> {
     let source = load_indentation_fixture(file_name)?;
     let config = get_config_overrides(file_name).unwrap_or_else(|| parse_fixture_config(&source));
-    let expected = parse_expected_violations(&source);
+    let expected = parse_indent_comments(&source);
     let actual = check_indentation_with_config(&source, &config);
 
     let missing: Vec<usize> = expected.difference(&actual).copied().collect();
@@ -1584,7 +1585,7 @@ fn debug_fixture(file_name: &str) {
     let config = get_config_overrides(file_name).unwrap_or_else(|| parse_fixture_config(&source));
     eprintln!("Config: {:?}", config);
 
-    let expected = parse_expected_violations(&source);
+    let expected = parse_indent_comments(&source);
 
     eprintln!("\n=== Violation Details ===");
     let actual = check_indentation_with_config_debug(&source, &config, true);
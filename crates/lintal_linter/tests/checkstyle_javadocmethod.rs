@@ -0,0 +1,83 @@
+//! JavadocMethod checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::JavadocMethod;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the JavadocMethod rule on source code and return violation lines.
+fn check_javadoc_method(source: &str, rule: JavadocMethod) -> Vec<usize> {
+    check_source(&[Box::new(rule)], source)
+        .iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::javadoc_test_input("javadocmethod", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping test: checkstyle repo not available");
+        return;
+    };
+
+    let config = FixtureConfig::parse(&source);
+    let rule = JavadocMethod::from_config(&config.properties());
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(80.0);
+}
+
+#[test]
+fn test_input_javadoc_method_tags() {
+    run_fixture("InputJavadocMethodTags.java");
+}
+
+#[test]
+fn test_input_javadoc_method_inherit_doc() {
+    run_fixture("InputJavadocMethodInheritDoc.java");
+}
+
+#[test]
+fn test_documented_method_no_violation() {
+    let source = r#"
+class Foo {
+    /**
+     * Adds.
+     *
+     * @param a the first
+     * @param b the second
+     * @return the sum
+     */
+    int add(int a, int b) {
+        return a + b;
+    }
+}
+"#;
+    let rule = JavadocMethod::from_config(&Properties::new());
+    assert!(check_javadoc_method(source, rule).is_empty());
+}
+
+#[test]
+fn test_undocumented_param_and_return() {
+    let source = r#"
+class Foo {
+    /**
+     * Adds.
+     *
+     * @param a the first
+     */
+    int add(int a, int b) {
+        return a + b;
+    }
+}
+"#;
+    let rule = JavadocMethod::from_config(&Properties::new());
+    assert_eq!(check_javadoc_method(source, rule), vec![8, 8]);
+}
@@ -0,0 +1,67 @@
+//! LambdaBodyLength checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::LambdaBodyLength;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the LambdaBodyLength rule on source code and return violation lines.
+fn check_lambda_body_length(source: &str, rule: LambdaBodyLength) -> Vec<usize> {
+    check_source(&[Box::new(rule)], source)
+        .iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::sizes_test_input("lambdabodylength", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping {}: checkstyle repo not available", file_name);
+        return;
+    };
+
+    let rule = LambdaBodyLength::from_config(&FixtureConfig::parse(&source).properties());
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(100.0);
+}
+
+#[test]
+fn test_input_lambda_body_length_default() {
+    run_fixture("InputLambdaBodyLengthDefault.java");
+}
+
+#[test]
+fn test_input_lambda_body_length_max() {
+    run_fixture("InputLambdaBodyLengthMax.java");
+}
+
+#[test]
+fn test_from_config() {
+    let mut props = Properties::new();
+    assert_eq!(LambdaBodyLength::from_config(&props).max, 10);
+    props.insert("max", "3");
+    assert_eq!(LambdaBodyLength::from_config(&props).max, 3);
+}
+
+#[test]
+fn test_long_lambda_body() {
+    let source = r#"
+class Foo {
+    Runnable r = () -> {
+        System.out.println();
+    };
+}
+"#;
+    assert_eq!(
+        check_lambda_body_length(source, LambdaBodyLength { max: 2 }),
+        vec![3]
+    );
+}
@@ -0,0 +1,94 @@
+//! MagicNumber checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::MagicNumber;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the MagicNumber rule on source code and return violation lines.
+fn check_magic_number(source: &str, rule: MagicNumber) -> Vec<usize> {
+    check_source(&[Box::new(rule)], source)
+        .iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::coding_test_input("magicnumber", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping test: checkstyle repo not available");
+        return;
+    };
+
+    let config = FixtureConfig::parse(&source);
+    let rule = MagicNumber::from_config(&config.properties());
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(80.0);
+}
+
+#[test]
+fn test_input_magic_number_default() {
+    run_fixture("InputMagicNumberDefault.java");
+}
+
+#[test]
+fn test_input_magic_number_ignore_none() {
+    run_fixture("InputMagicNumberIgnoreNone.java");
+}
+
+#[test]
+fn test_input_magic_number_ignore_some() {
+    run_fixture("InputMagicNumberIgnoreSome.java");
+}
+
+#[test]
+fn test_input_magic_number_ignore_hash_code_method() {
+    run_fixture("InputMagicNumberIgnoreHashCodeMethod.java");
+}
+
+#[test]
+fn test_input_magic_number_ignore_field_declaration() {
+    run_fixture("InputMagicNumberIgnoreFieldDeclaration1.java");
+}
+
+#[test]
+fn test_input_magic_number_waiver_parent_token() {
+    run_fixture("InputMagicNumberWaiverParentToken.java");
+}
+
+#[test]
+fn test_input_magic_number_annotation_element_defaults() {
+    run_fixture("InputMagicNumberAnnotationElementDefaults.java");
+}
+
+#[test]
+fn test_constant_no_violation() {
+    let source = r#"
+class Foo {
+    private static final int TIMEOUT = 3600;
+}
+"#;
+    let rule = MagicNumber::from_config(&Properties::new());
+    assert!(check_magic_number(source, rule).is_empty());
+}
+
+#[test]
+fn test_local_variable_violation() {
+    let source = r#"
+class Foo {
+    void f() {
+        int timeout = 3600;
+    }
+}
+"#;
+    let rule = MagicNumber::from_config(&Properties::new());
+    assert_eq!(check_magic_number(source, rule), vec![4]);
+}
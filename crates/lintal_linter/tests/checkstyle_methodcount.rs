@@ -0,0 +1,95 @@
+//! MethodCount checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::MethodCount;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the MethodCount rule on source code and return violation lines.
+fn check_method_count(source: &str, rule: MethodCount) -> Vec<usize> {
+    check_source(&[Box::new(rule)], source)
+        .iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::sizes_test_input("methodcount", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping {}: checkstyle repo not available", file_name);
+        return;
+    };
+
+    let rule = MethodCount::from_config(&FixtureConfig::parse(&source).properties());
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(100.0);
+}
+
+#[test]
+fn test_input_method_count() {
+    run_fixture("InputMethodCount.java");
+}
+
+#[test]
+fn test_input_method_count_enum() {
+    run_fixture("InputMethodCount2.java");
+}
+
+#[test]
+fn test_from_config_default() {
+    let props = Properties::new();
+    let rule = MethodCount::from_config(&props);
+    assert_eq!(rule.max_total, 100);
+    assert_eq!(rule.max_public, 100);
+    assert!(rule.tokens.contains("ENUM_CONSTANT_DEF"));
+}
+
+#[test]
+fn test_from_config_custom() {
+    let mut props = Properties::new();
+    props.insert("maxTotal", "10");
+    props.insert("maxPrivate", "1");
+    props.insert("maxPackage", "2");
+    props.insert("maxProtected", "3");
+    props.insert("maxPublic", "4");
+    props.insert("tokens", "CLASS_DEF, METHOD_DEF");
+    let rule = MethodCount::from_config(&props);
+    assert_eq!(
+        (
+            rule.max_total,
+            rule.max_private,
+            rule.max_package,
+            rule.max_protected,
+            rule.max_public
+        ),
+        (10, 1, 2, 3, 4)
+    );
+    assert_eq!(rule.tokens.len(), 2);
+}
+
+#[test]
+fn test_nested_types_counted_separately() {
+    let source = r#"
+class Outer {
+    void a() {}
+    void b() {}
+
+    class Inner {
+        void c() {}
+    }
+}
+"#;
+    let rule = MethodCount {
+        max_total: 1,
+        ..MethodCount::default()
+    };
+    assert_eq!(check_method_count(source, rule), vec![2]);
+}
@@ -0,0 +1,70 @@
+//! MissingJavadocMethod checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::MissingJavadocMethod;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the MissingJavadocMethod rule on source code and return violation lines.
+fn check_missing_javadoc_method(source: &str, rule: MissingJavadocMethod) -> Vec<usize> {
+    check_source(&[Box::new(rule)], source)
+        .iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::javadoc_test_input("missingjavadocmethod", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping test: checkstyle repo not available");
+        return;
+    };
+
+    let config = FixtureConfig::parse(&source);
+    let rule = MissingJavadocMethod::from_config(&config.properties());
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(80.0);
+}
+
+#[test]
+fn test_input_missing_javadoc_method_scope_inner_interfaces() {
+    run_fixture("InputMissingJavadocMethodScopeInnerInterfaces.java");
+}
+
+#[test]
+fn test_input_missing_javadoc_method_min_line_count() {
+    run_fixture("InputMissingJavadocMethodMinLineCount.java");
+}
+
+#[test]
+fn test_documented_public_method_no_violation() {
+    let source = r#"
+public class Foo {
+    /** Runs. */
+    public void run() {}
+
+    void helper() {}
+}
+"#;
+    let rule = MissingJavadocMethod::from_config(&Properties::new());
+    assert!(check_missing_javadoc_method(source, rule).is_empty());
+}
+
+#[test]
+fn test_undocumented_public_method_violation() {
+    let source = r#"
+public class Foo {
+    public void run() {}
+}
+"#;
+    let rule = MissingJavadocMethod::from_config(&Properties::new());
+    assert_eq!(check_missing_javadoc_method(source, rule), vec![3]);
+}
@@ -10,6 +10,7 @@ use lintal_java_parser::JavaParser;
 use lintal_linter::rules::ModifierOrder;
 use lintal_linter::{CheckContext, FromConfig, Rule};
 use lintal_source_file::{LineIndex, SourceCode};
+use lintal_testkit::assert_violations;
 use std::collections::HashMap;
 
 /// A violation at a specific location.
@@ -87,51 +88,6 @@ fn load_modifierorder_fixture(file_name: &str) -> Option<String> {
     std::fs::read_to_string(&path).ok()
 }
 
-/// Helper to verify violations match expected.
-fn verify_violations(violations: &[Violation], expected: &[Violation]) {
-    let mut missing = vec![];
-    let mut unexpected = vec![];
-
-    for exp in expected {
-        let matched = violations.iter().any(|v| {
-            v.line == exp.line && v.column == exp.column && v.message_key == exp.message_key
-        });
-
-        if !matched {
-            missing.push(exp.clone());
-        }
-    }
-
-    for actual in violations {
-        let matched = expected.iter().any(|v| {
-            v.line == actual.line
-                && v.column == actual.column
-                && v.message_key == actual.message_key
-        });
-
-        if !matched {
-            unexpected.push(actual.clone());
-        }
-    }
-
-    if !missing.is_empty() || !unexpected.is_empty() {
-        println!("\n=== Violations Report ===");
-        if !missing.is_empty() {
-            println!("\nMissing violations:");
-            for v in &missing {
-                println!("  {}:{}: {}", v.line, v.column, v.message_key);
-            }
-        }
-        if !unexpected.is_empty() {
-            println!("\nUnexpected violations:");
-            for v in &unexpected {
-                println!("  {}:{}: {}", v.line, v.column, v.message_key);
-            }
-        }
-        panic!("Violation mismatch detected");
-    }
-}
-
 // =============================================================================
 // Test: testItOne
 // File: InputModifierOrderItOne.java
@@ -162,7 +118,7 @@ fn test_modifier_order_it_one() {
         Violation::annotation_order(50, 35),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -187,7 +143,7 @@ fn test_modifier_order_it_two() {
         Violation::modifier_order(57, 14),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -221,7 +177,7 @@ fn test_modifier_order_sealed_and_non_sealed() {
         Violation::modifier_order(59, 10),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -243,7 +199,7 @@ fn test_skip_type_annotations_one() {
 
     let expected = vec![Violation::annotation_order(101, 13)];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -265,5 +221,5 @@ fn test_annotation_on_annotation_declaration() {
 
     let expected = vec![Violation::annotation_order(9, 8)];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
@@ -1,15 +1,13 @@
 //! NestedTryDepth checkstyle compatibility tests.
 
 mod checkstyle_repo;
-mod test_harness;
 
 use lintal_java_cst::TreeWalker;
 use lintal_java_parser::JavaParser;
 use lintal_linter::rules::NestedTryDepth;
 use lintal_linter::{CheckContext, FromConfig, Properties, Rule};
 use lintal_source_file::{LineIndex, SourceCode};
-use regex::Regex;
-use test_harness::TestResult;
+use lintal_testkit::{TestResult, expected_violation_lines};
 
 /// Run the NestedTryDepth rule on source code and return violation lines.
 fn check_nested_try_depth(source: &str, max: usize) -> Vec<usize> {
@@ -34,38 +32,6 @@ fn check_nested_try_depth(source: &str, max: usize) -> Vec<usize> {
     violations
 }
 
-/// Parse expected violations from checkstyle test file comments.
-fn parse_expected_violations(source: &str) -> Vec<usize> {
-    let mut violations = vec![];
-    let inline_re = Regex::new(r"//\s*violation,?\s+'[^']*'").unwrap();
-    let above_re = Regex::new(r"//\s*violation\s+above").unwrap();
-    let below_re = Regex::new(r"//\s*violation\s+below").unwrap();
-    let n_above_re = Regex::new(r"//\s*violation\s+(\d+)\s+lines?\s+above").unwrap();
-
-    for (i, line) in source.lines().enumerate() {
-        let line_num = i + 1;
-        if line.contains("No violation") || line.contains("no violation") {
-            continue;
-        }
-        if let Some(caps) = n_above_re.captures(line) {
-            if let Some(n) = caps.get(1).and_then(|m| m.as_str().parse::<usize>().ok())
-                && line_num > n
-            {
-                violations.push(line_num - n);
-            }
-        } else if above_re.is_match(line) {
-            if line_num > 1 {
-                violations.push(line_num - 1);
-            }
-        } else if below_re.is_match(line) {
-            violations.push(line_num + 1);
-        } else if inline_re.is_match(line) {
-            violations.push(line_num);
-        }
-    }
-    violations
-}
-
 fn load_fixture(file_name: &str) -> Option<String> {
     let path = checkstyle_repo::coding_test_input("nestedtrydepth", file_name)?;
     std::fs::read_to_string(&path).ok()
@@ -78,7 +44,7 @@ fn test_input_nested_try_depth() {
         return;
     };
 
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_nested_try_depth(&source, 1);
 
     let result = TestResult::compare(expected, actual);
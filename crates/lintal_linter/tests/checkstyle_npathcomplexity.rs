@@ -0,0 +1,87 @@
+//! NPathComplexity checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::NPathComplexity;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the NPathComplexity rule on source code and return violation lines.
+fn check_npath_complexity(source: &str, rule: NPathComplexity) -> Vec<usize> {
+    check_source(&[Box::new(rule)], source)
+        .iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::metrics_test_input("npathcomplexity", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping test: checkstyle repo not available");
+        return;
+    };
+
+    let config = FixtureConfig::parse(&source);
+    let rule = NPathComplexity::from_config(&config.properties());
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(80.0);
+}
+
+#[test]
+fn test_input_npath_complexity_default() {
+    run_fixture("InputNPathComplexityDefault.java");
+}
+
+#[test]
+fn test_input_npath_complexity_check_1() {
+    run_fixture("InputNPathComplexityCheck1.java");
+}
+
+#[test]
+fn test_input_npath_complexity_check_2() {
+    run_fixture("InputNPathComplexityCheck2.java");
+}
+
+#[test]
+fn test_input_npath_complexity_records() {
+    run_fixture("InputNPathComplexityRecords.java");
+}
+
+#[test]
+fn test_simple_method_no_violation() {
+    let source = r#"
+class Foo {
+    void run(boolean a) {
+        if (a) {
+            work();
+        }
+    }
+}
+"#;
+    let rule = NPathComplexity::from_config(&Properties::new());
+    assert!(check_npath_complexity(source, rule).is_empty());
+}
+
+#[test]
+fn test_sequential_branches_multiply() {
+    let source = r#"
+class Foo {
+    void run(boolean a, boolean b, boolean c) {
+        if (a) {}
+        if (b) {}
+        if (c) {}
+    }
+}
+"#;
+    // 2 * 2 * 2 paths
+    let properties: Properties = [("max", "7")].into_iter().collect();
+    let rule = NPathComplexity::from_config(&properties);
+    assert_eq!(check_npath_complexity(source, rule), vec![3]);
+}
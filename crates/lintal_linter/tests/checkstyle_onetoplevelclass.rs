@@ -0,0 +1,67 @@
+//! OneTopLevelClass checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::FromConfig;
+use lintal_linter::rules::OneTopLevelClass;
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the OneTopLevelClass rule on source code and return violation lines.
+fn check_one_top_level_class(source: &str, rule: OneTopLevelClass) -> Vec<usize> {
+    check_source(&[Box::new(rule)], source)
+        .iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::design_test_input("onetoplevelclass", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping {}: checkstyle repo not available", file_name);
+        return;
+    };
+
+    let rule = OneTopLevelClass::from_config(&FixtureConfig::parse(&source).properties());
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(100.0);
+}
+
+#[test]
+fn test_input_one_top_level_class() {
+    run_fixture("InputOneTopLevelClass.java");
+}
+
+#[test]
+fn test_input_one_top_level_class_no_public() {
+    run_fixture("InputOneTopLevelClassNoPublic.java");
+}
+
+#[test]
+fn test_input_one_top_level_class_interface() {
+    run_fixture("InputOneTopLevelClassInterface.java");
+}
+
+#[test]
+fn test_input_one_top_level_class_records() {
+    run_fixture("InputOneTopLevelClassRecords.java");
+}
+
+#[test]
+fn test_nested_types_not_reported() {
+    let source = r#"
+public class Foo {
+    class Inner {}
+    interface Callback {}
+}
+
+class Bar {}
+"#;
+    assert_eq!(check_one_top_level_class(source, OneTopLevelClass), vec![7]);
+}
@@ -15,6 +15,7 @@ use lintal_linter::rules::whitespace::operator_wrap::{
     OperatorWrap, OperatorWrapToken, WrapOption,
 };
 use lintal_linter::{CheckContext, Rule};
+use lintal_testkit::parse_expected_violations;
 use regex::Regex;
 use std::collections::HashSet;
 
@@ -106,48 +107,22 @@ impl TestResult {
     }
 }
 
-/// Parse expected violations from checkstyle test file comments.
-/// Looks for patterns like:
-/// - `// violation ''&&' should be on a new line.'`
-/// - `// violation ''\+' should be on a new line.'`
-/// - `// violation below, ''instanceof' should be on a new line.'`
-fn parse_expected_violations(source: &str) -> Vec<ExpectedViolation> {
-    let mut violations = vec![];
-
-    // Pattern for inline violations: // violation '...'
-    let inline_re = Regex::new(r"//\s*violation\s+'[^']*'").unwrap();
-    // Pattern for "violation below" comments
-    let below_re = Regex::new(r"//\s*violation\s+below").unwrap();
-    // Pattern to extract operator from message like ''&&' should be'
-    let op_re = Regex::new(r"''([^']+)'").unwrap();
-
-    let lines: Vec<&str> = source.lines().collect();
-
-    for (i, line) in lines.iter().enumerate() {
-        let line_num = i + 1;
-
-        if below_re.is_match(line) {
-            // Violation is on the next line
-            let operator = op_re
-                .captures(line)
-                .map(|c| c.get(1).unwrap().as_str().to_string());
-            violations.push(ExpectedViolation {
-                line: line_num + 1,
-                operator,
-            });
-        } else if inline_re.is_match(line) {
-            // Violation is on this line
-            let operator = op_re
-                .captures(line)
-                .map(|c| c.get(1).unwrap().as_str().to_string());
-            violations.push(ExpectedViolation {
-                line: line_num,
-                operator,
-            });
-        }
-    }
-
-    violations
+/// Expected violations from the fixture's comments, with the operator each
+/// message names, e.g. `&&` from `// violation ''&&' should be on a new line.'`.
+fn expected_violations(source: &str) -> Vec<ExpectedViolation> {
+    let op_re = Regex::new(r"^'([^']+)'").unwrap();
+
+    parse_expected_violations(source)
+        .into_iter()
+        .map(|violation| ExpectedViolation {
+            line: violation.line,
+            operator: violation
+                .message
+                .as_deref()
+                .and_then(|message| op_re.captures(message))
+                .map(|c| c.get(1).unwrap().as_str().to_string()),
+        })
+        .collect()
 }
 
 /// Parse configuration from checkstyle test file header.
@@ -253,7 +228,7 @@ fn run_fixture(file_name: &str) -> Option<TestResult> {
     let source = std::fs::read_to_string(&path).ok()?;
 
     let config = parse_config(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violations(&source);
     let actual = check_operator_wrap(&source, &config);
 
     // Match expected to actual violations
@@ -0,0 +1,71 @@
+//! OuterTypeFilename checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use std::path::Path;
+
+use lintal_linter::FromConfig;
+use lintal_linter::rules::OuterTypeFilename;
+use lintal_testkit::{FixtureConfig, check_source_at, verify_violations};
+
+/// Run the OuterTypeFilename rule on source code read from `path` and return
+/// violation lines.
+fn check_outer_type_filename(source: &str, path: &Path, rule: OuterTypeFilename) -> Vec<usize> {
+    check_source_at(&[Box::new(rule)], source, path)
+        .iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(path) = checkstyle_repo::misc_test_input("outertypefilename", file_name) else {
+        eprintln!("Skipping {}: checkstyle repo not available", file_name);
+        return;
+    };
+    let Ok(source) = std::fs::read_to_string(&path) else {
+        eprintln!("Skipping {}: checkstyle repo not available", file_name);
+        return;
+    };
+
+    let rule = OuterTypeFilename::from_config(&FixtureConfig::parse(&source).properties());
+    let actual = check_source_at(&[Box::new(rule)], &source, &path);
+    let result = verify_violations(&source, &actual);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(100.0);
+}
+
+#[test]
+fn test_input_outer_type_filename_1() {
+    run_fixture("InputOuterTypeFilename1.java");
+}
+
+#[test]
+fn test_input_outer_type_filename_2() {
+    run_fixture("InputOuterTypeFilename2.java");
+}
+
+#[test]
+fn test_input_outer_type_filename_package_info() {
+    run_fixture("package-info.java");
+}
+
+#[test]
+fn test_input_outer_type_filename_records() {
+    run_fixture("InputOuterTypeFilenameRecord.java");
+}
+
+#[test]
+fn test_mismatched_outer_type() {
+    let source = r#"
+package com.example;
+
+class Foo {}
+"#;
+    assert_eq!(
+        check_outer_type_filename(source, Path::new("Bar.java"), OuterTypeFilename),
+        vec![4]
+    );
+    assert!(check_outer_type_filename(source, Path::new("Foo.java"), OuterTypeFilename).is_empty());
+}
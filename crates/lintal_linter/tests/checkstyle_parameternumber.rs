@@ -0,0 +1,83 @@
+//! ParameterNumber checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::ParameterNumber;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the ParameterNumber rule on source code and return violation lines.
+fn check_parameter_number(source: &str, rule: ParameterNumber) -> Vec<usize> {
+    check_source(&[Box::new(rule)], source)
+        .iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::sizes_test_input("parameternumber", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping {}: checkstyle repo not available", file_name);
+        return;
+    };
+
+    let rule = ParameterNumber::from_config(&FixtureConfig::parse(&source).properties());
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(100.0);
+}
+
+#[test]
+fn test_input_parameter_number_simple() {
+    run_fixture("InputParameterNumberSimple.java");
+}
+
+#[test]
+fn test_input_parameter_number_ignore_overridden_methods() {
+    run_fixture("InputParameterNumberIgnoreOverriddenMethods.java");
+}
+
+#[test]
+fn test_from_config_default() {
+    let props = Properties::new();
+    let rule = ParameterNumber::from_config(&props);
+    assert_eq!(rule.max, 7);
+    assert!(!rule.ignore_overridden_methods);
+    assert!(rule.check_methods);
+    assert!(rule.check_constructors);
+}
+
+#[test]
+fn test_from_config_custom() {
+    let mut props = Properties::new();
+    props.insert("max", "2");
+    props.insert("ignoreOverriddenMethods", "true");
+    props.insert("tokens", "CTOR_DEF");
+    let rule = ParameterNumber::from_config(&props);
+    assert_eq!(rule.max, 2);
+    assert!(rule.ignore_overridden_methods);
+    assert!(!rule.check_methods);
+    assert!(rule.check_constructors);
+}
+
+#[test]
+fn test_violation_on_method_name() {
+    let source = r#"
+class Foo {
+    void method(int a,
+                int b,
+                int c) {}
+}
+"#;
+    let rule = ParameterNumber {
+        max: 2,
+        ..ParameterNumber::default()
+    };
+    assert_eq!(check_parameter_number(source, rule), vec![3]);
+}
@@ -10,6 +10,7 @@ use lintal_java_parser::JavaParser;
 use lintal_linter::rules::RedundantModifier;
 use lintal_linter::{CheckContext, FromConfig, Rule};
 use lintal_source_file::{LineIndex, SourceCode};
+use lintal_testkit::assert_violations;
 use std::collections::HashMap;
 
 /// A violation at a specific location.
@@ -89,49 +90,6 @@ fn load_redundantmodifier_fixture(file_name: &str) -> Option<String> {
     std::fs::read_to_string(&path).ok()
 }
 
-/// Helper to verify violations match expected.
-fn verify_violations(violations: &[Violation], expected: &[Violation]) {
-    let mut missing = vec![];
-    let mut unexpected = vec![];
-
-    for exp in expected {
-        let matched = violations
-            .iter()
-            .any(|v| v.line == exp.line && v.column == exp.column && v.modifier == exp.modifier);
-
-        if !matched {
-            missing.push(exp.clone());
-        }
-    }
-
-    for v in violations {
-        let matched = expected
-            .iter()
-            .any(|exp| v.line == exp.line && v.column == exp.column && v.modifier == exp.modifier);
-
-        if !matched {
-            unexpected.push(v.clone());
-        }
-    }
-
-    if !missing.is_empty() || !unexpected.is_empty() {
-        eprintln!("\n=== Violation Mismatch ===");
-        if !missing.is_empty() {
-            eprintln!("\nMissing violations (expected but not found):");
-            for v in &missing {
-                eprintln!("  {}:{} - {}", v.line, v.column, v.modifier);
-            }
-        }
-        if !unexpected.is_empty() {
-            eprintln!("\nUnexpected violations (found but not expected):");
-            for v in &unexpected {
-                eprintln!("  {}:{} - {}", v.line, v.column, v.modifier);
-            }
-        }
-        panic!("Violations do not match expected");
-    }
-}
-
 #[test]
 fn test_it_one() {
     let Some(source) = load_redundantmodifier_fixture("InputRedundantModifierItOne.java") else {
@@ -154,7 +112,7 @@ fn test_it_one() {
         Violation::new(102, 1, "abstract"), // abstract interface definition
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 #[test]
@@ -174,7 +132,7 @@ fn test_it_two() {
         Violation::new(27, 5, "abstract"),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 #[test]
@@ -195,7 +153,7 @@ fn test_classes_inside_of_interfaces() {
         Violation::new(34, 5, "static"),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 #[test]
@@ -212,7 +170,7 @@ fn test_enum_constructor_is_implicitly_private() {
     // Enum constructors are implicitly private
     let expected = vec![Violation::new(14, 5, "private")];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 #[test]
@@ -229,7 +187,7 @@ fn test_annotation_on_enum_constructor() {
     // Private modifier is redundant even with annotation
     let expected = vec![Violation::new(22, 5, "private")];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 #[test]
@@ -246,7 +204,7 @@ fn test_not_public_class_constructor_has_not_public_modifier() {
     // Public modifier is redundant on constructor of non-public class
     let expected = vec![Violation::new(22, 5, "public")];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 #[test]
@@ -267,7 +225,7 @@ fn test_nested_static_enum() {
         Violation::new(20, 9, "static"), // nested in interface
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 #[test]
@@ -284,7 +242,7 @@ fn test_final_in_anonymous_class() {
     // Final on method in anonymous class is redundant
     let expected = vec![Violation::new(22, 20, "final")];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 #[test]
@@ -301,7 +259,7 @@ fn test_private_method_in_private_class() {
     // Final on private method is redundant
     let expected = vec![Violation::new(13, 17, "final")];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 #[test]
@@ -318,7 +276,7 @@ fn test_enum_static_methods() {
     // Final on static method in enum is redundant (static methods are not overridable)
     let expected = vec![Violation::new(20, 23, "final")];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 #[test]
@@ -338,7 +296,7 @@ fn test_enum_methods() {
         Violation::new(30, 16, "final"), // E1 constant body in second enum
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 #[test]
@@ -359,7 +317,7 @@ fn test_final_in_try_with_resource() {
         Violation::new(44, 17, "final"),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 #[test]
@@ -376,7 +334,7 @@ fn test_try_with_resources_block() {
     // Final on try-with-resources variables is redundant
     let expected = vec![Violation::new(18, 19, "final")];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 #[test]
@@ -399,7 +357,7 @@ fn test_final_in_abstract_methods() {
         Violation::new(33, 33, "final"), // abstract method in enum
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Task 9 tests: records, sealed classes, strictfp
@@ -433,7 +391,7 @@ fn test_records() {
                                           // Violation::new(47, 15, "static"),  // SKIPPED: record in annotation (parser limitation)
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 #[test]
@@ -463,7 +421,7 @@ fn test_strictfp_with_java17() {
         Violation::new(42, 15, "strictfp"), // strictfp method
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 #[test]
@@ -493,7 +451,7 @@ fn test_strictfp_with_default_version() {
         Violation::new(41, 15, "strictfp"), // strictfp method
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // Task 10 tests: unnamed variables (JDK 22+)
@@ -527,7 +485,7 @@ fn test_final_unnamed_variables_with_default_version() {
         Violation::new(69, 70, "final"), // lambda parameter (second underscore)
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 #[test]
@@ -548,5 +506,5 @@ fn test_final_unnamed_variables_with_old_version() {
         Violation::new(47, 14, "final"), // try-with-resources with underscore
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
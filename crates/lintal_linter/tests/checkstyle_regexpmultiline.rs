@@ -0,0 +1,84 @@
+//! RegexpMultiline checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::RegexpMultiline;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the RegexpMultiline rule on source code and return violation lines.
+fn check_regexp(source: &str, rule: RegexpMultiline) -> Vec<usize> {
+    check_source(&[Box::new(rule)], source)
+        .iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::regexp_test_input("regexpmultiline", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+/// Build the rule from the fixture header. Its formats use Java string
+/// escaping, so `\\` stands for a single backslash.
+fn build_rule_from_source(source: &str) -> RegexpMultiline {
+    let config = FixtureConfig::parse(source);
+    let unescaped: Vec<(&str, String)> = config
+        .properties
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.replace("\\\\", "\\")))
+        .collect();
+    let props: Properties = unescaped
+        .iter()
+        .map(|(key, value)| (*key, value.as_str()))
+        .collect();
+    RegexpMultiline::from_config(&props)
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping {}: checkstyle repo not available", file_name);
+        return;
+    };
+
+    let rule = build_rule_from_source(&source);
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(100.0);
+}
+
+#[test]
+fn test_input_regexp_multiline_semantic() {
+    run_fixture("InputRegexpMultilineSemantic.java");
+}
+
+#[test]
+fn test_input_regexp_multiline_semantic_2() {
+    run_fixture("InputRegexpMultilineSemantic2.java");
+}
+
+#[test]
+fn test_input_regexp_multiline_match_across_lines() {
+    run_fixture("InputRegexpMultilineMultilineSupport.java");
+}
+
+#[test]
+fn test_from_config_default() {
+    let rule = RegexpMultiline::from_config(&Properties::new());
+    assert_eq!(rule.format_str, "$.");
+    assert!(!rule.match_across_lines);
+    assert_eq!(rule.minimum, 0);
+    assert_eq!(rule.maximum, 0);
+}
+
+#[test]
+fn test_consecutive_blank_lines() {
+    let mut props = Properties::new();
+    props.insert("format", r"\n\s*\n\s*\n");
+    let rule = RegexpMultiline::from_config(&props);
+
+    let source = "class Foo {\n    int x;\n\n\n    int y;\n}\n";
+    assert_eq!(check_regexp(source, rule), vec![2]);
+}
@@ -0,0 +1,85 @@
+//! RegexpSingleline checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::RegexpSingleline;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the RegexpSingleline rule on source code and return violation lines.
+fn check_regexp(source: &str, rule: RegexpSingleline) -> Vec<usize> {
+    check_source(&[Box::new(rule)], source)
+        .iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::regexp_test_input("regexpsingleline", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+/// Build the rule from the fixture header. Its formats use Java string
+/// escaping, so `\\` stands for a single backslash.
+fn build_rule_from_source(source: &str) -> RegexpSingleline {
+    let config = FixtureConfig::parse(source);
+    let unescaped: Vec<(&str, String)> = config
+        .properties
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.replace("\\\\", "\\")))
+        .collect();
+    let props: Properties = unescaped
+        .iter()
+        .map(|(key, value)| (*key, value.as_str()))
+        .collect();
+    RegexpSingleline::from_config(&props)
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping {}: checkstyle repo not available", file_name);
+        return;
+    };
+
+    let rule = build_rule_from_source(&source);
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(100.0);
+}
+
+#[test]
+fn test_input_regexp_singleline_1() {
+    run_fixture("InputRegexpSingleline1.java");
+}
+
+#[test]
+fn test_input_regexp_singleline_2() {
+    run_fixture("InputRegexpSingleline2.java");
+}
+
+#[test]
+fn test_input_regexp_singleline_trailing_space() {
+    run_fixture("InputRegexpSinglelineTrailingSpace.java");
+}
+
+#[test]
+fn test_from_config_default() {
+    let rule = RegexpSingleline::from_config(&Properties::new());
+    assert_eq!(rule.format_str, "$.");
+    assert_eq!(rule.minimum, 0);
+    assert_eq!(rule.maximum, 0);
+    assert!(rule.message.is_none());
+}
+
+#[test]
+fn test_sun_trailing_spaces() {
+    let mut props = Properties::new();
+    props.insert("format", r"\s+$");
+    props.insert("message", "Line has trailing spaces.");
+    let rule = RegexpSingleline::from_config(&props);
+
+    let source = "class Foo {  \n    int x;\n}\n";
+    assert_eq!(check_regexp(source, rule), vec![1]);
+}
@@ -1,16 +1,15 @@
 //! RegexpSinglelineJava checkstyle compatibility tests.
 
 mod checkstyle_repo;
-mod test_harness;
 
 use lintal_java_cst::TreeWalker;
 use lintal_java_parser::JavaParser;
 use lintal_linter::rules::RegexpSinglelineJava;
 use lintal_linter::{CheckContext, FromConfig, Properties, Rule};
 use lintal_source_file::{LineIndex, SourceCode};
+use lintal_testkit::{TestResult, expected_violation_lines};
 use regex::Regex;
 use std::collections::HashMap;
-use test_harness::TestResult;
 
 /// Run the RegexpSinglelineJava rule on source code and return violation lines.
 fn check_regexp(source: &str, rule: &RegexpSinglelineJava) -> Vec<usize> {
@@ -36,38 +35,6 @@ fn check_regexp(source: &str, rule: &RegexpSinglelineJava) -> Vec<usize> {
     violations
 }
 
-/// Parse expected violations from checkstyle test file comments.
-fn parse_expected_violations(source: &str) -> Vec<usize> {
-    let mut violations = vec![];
-
-    let n_above_re = Regex::new(r"//\s*violation\s+(\d+)\s+lines?\s+above").unwrap();
-    let above_re = Regex::new(r"//\s*violation\s+above").unwrap();
-    let below_re = Regex::new(r"//\s*violation\s+below").unwrap();
-    let inline_re = Regex::new(r"//\s*violation\b").unwrap();
-
-    for (i, line) in source.lines().enumerate() {
-        let line_num = i + 1;
-
-        if let Some(caps) = n_above_re.captures(line) {
-            if let Some(n) = caps.get(1).and_then(|m| m.as_str().parse::<usize>().ok())
-                && line_num > n
-            {
-                violations.push(line_num - n);
-            }
-        } else if above_re.is_match(line) {
-            if line_num > 1 {
-                violations.push(line_num - 1);
-            }
-        } else if below_re.is_match(line) {
-            violations.push(line_num + 1);
-        } else if inline_re.is_match(line) {
-            violations.push(line_num);
-        }
-    }
-
-    violations
-}
-
 /// Parse config from the header block of a checkstyle test file.
 /// Returns owned strings because we need to unescape Java-style backslash sequences.
 fn parse_config_from_header(source: &str) -> HashMap<String, String> {
@@ -132,7 +99,7 @@ fn test_semantic1() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_regexp(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -150,7 +117,7 @@ fn test_semantic2() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_regexp(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -168,7 +135,7 @@ fn test_semantic3_ignore_case() {
     };
 
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_regexp(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -236,7 +203,7 @@ fn test_semantic7_minimum_not_met() {
     // Config: format="This\stext is not in the file", min=1, max=1000
     // Text not in file, so minimum not met - violation at line 1
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_regexp(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -289,7 +256,7 @@ fn test_trailing_comment2_no_ignore() {
 
     // Config: format="don't\suse trailing comments", ignoreComments=false
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_regexp(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -326,7 +293,7 @@ fn test_trailing_comment4_cstyle_no_ignore() {
 
     // Config: format="c-style\s1", ignoreComments=false
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_regexp(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -397,7 +364,7 @@ fn test_trailing_comment8_int_z() {
     // Config: format="int z", ignoreComments=true
     // "int z" appears in code (not in comments), should still match
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_regexp(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -416,7 +383,7 @@ fn test_trailing_comment9_int_y() {
 
     // Config: format="int y", ignoreComments=true
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_regexp(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -435,7 +402,7 @@ fn test_trailing_comment10_long_ms() {
 
     // Config: format="long ms  " (with trailing spaces), ignoreComments=true
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_regexp(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
@@ -454,7 +421,7 @@ fn test_trailing_comment11_trailing_whitespace() {
 
     // Config: format="\\s+$", ignoreComments=true
     let rule = build_rule_from_source(&source);
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_regexp(&source, &rule);
 
     let result = TestResult::compare(expected, actual);
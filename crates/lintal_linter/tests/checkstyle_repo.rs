@@ -108,6 +108,30 @@ pub fn sizes_test_input(check_name: &str, file_name: &str) -> Option<PathBuf> {
     if path.exists() { Some(path) } else { None }
 }
 
+/// Get path to a checkstyle test input file for javadoc checks.
+#[allow(dead_code)]
+pub fn javadoc_test_input(check_name: &str, file_name: &str) -> Option<PathBuf> {
+    let repo = checkstyle_repo()?;
+    let path = repo
+        .join("src/test/resources/com/puppycrawl/tools/checkstyle/checks/javadoc")
+        .join(check_name.to_lowercase())
+        .join(file_name);
+
+    if path.exists() { Some(path) } else { None }
+}
+
+/// Get path to a checkstyle test input file for metrics checks.
+#[allow(dead_code)]
+pub fn metrics_test_input(check_name: &str, file_name: &str) -> Option<PathBuf> {
+    let repo = checkstyle_repo()?;
+    let path = repo
+        .join("src/test/resources/com/puppycrawl/tools/checkstyle/checks/metrics")
+        .join(check_name.to_lowercase())
+        .join(file_name);
+
+    if path.exists() { Some(path) } else { None }
+}
+
 /// Get path to a checkstyle test input file for regexp checks.
 #[allow(dead_code)]
 pub fn regexp_test_input(check_name: &str, file_name: &str) -> Option<PathBuf> {
@@ -10,6 +10,7 @@ use lintal_java_parser::JavaParser;
 use lintal_linter::rules::RightCurly;
 use lintal_linter::{CheckContext, FromConfig, Rule};
 use lintal_source_file::{LineIndex, SourceCode};
+use lintal_testkit::assert_violations;
 use std::collections::HashMap;
 
 /// A violation at a specific location.
@@ -104,51 +105,6 @@ fn load_rightcurly_fixture(file_name: &str) -> Option<String> {
     std::fs::read_to_string(&path).ok()
 }
 
-/// Helper to verify violations match expected.
-fn verify_violations(violations: &[Violation], expected: &[Violation]) {
-    let mut missing = vec![];
-    let mut unexpected = vec![];
-
-    for exp in expected {
-        let matched = violations.iter().any(|v| {
-            v.line == exp.line && v.column == exp.column && v.message_key == exp.message_key
-        });
-
-        if !matched {
-            missing.push(exp.clone());
-        }
-    }
-
-    for actual in violations {
-        let matched = expected.iter().any(|v| {
-            v.line == actual.line
-                && v.column == actual.column
-                && v.message_key == actual.message_key
-        });
-
-        if !matched {
-            unexpected.push(actual.clone());
-        }
-    }
-
-    if !missing.is_empty() || !unexpected.is_empty() {
-        println!("\n=== Violations Report ===");
-        if !missing.is_empty() {
-            println!("\nMissing violations:");
-            for v in &missing {
-                println!("  {}:{}: {}", v.line, v.column, v.message_key);
-            }
-        }
-        if !unexpected.is_empty() {
-            println!("\nUnexpected violations:");
-            for v in &unexpected {
-                println!("  {}:{}: {}", v.line, v.column, v.message_key);
-            }
-        }
-        panic!("Violation mismatch detected");
-    }
-}
-
 // =============================================================================
 // Test: testDefault (SAME option with default tokens)
 // File: InputRightCurlyLeftTestDefault.java
@@ -178,7 +134,7 @@ fn test_right_curly_default() {
         Violation::line_break_before(93, 27),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -217,7 +173,7 @@ fn test_right_curly_same() {
         Violation::line_alone(190, 41),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -239,7 +195,7 @@ fn test_right_curly_catch_without_finally() {
 
     let expected = vec![Violation::line_same(19, 9)];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -282,7 +238,7 @@ fn test_right_curly_alone() {
         Violation::line_alone(190, 53),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
 
 // =============================================================================
@@ -331,5 +287,5 @@ fn test_right_curly_alone_or_singleline() {
         Violation::line_alone(243, 30),
     ];
 
-    verify_violations(&violations, &expected);
+    assert_violations(&violations, &expected);
 }
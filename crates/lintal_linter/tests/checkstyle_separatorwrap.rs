@@ -0,0 +1,251 @@
+//! SeparatorWrap checkstyle compatibility tests.
+//!
+//! This test harness validates lintal's SeparatorWrap implementation against
+//! checkstyle's own test fixtures. It parses expected violations from comments
+//! in the test files and reports:
+//! - Missing matches: violations checkstyle expects but we missed
+//! - False positives: violations we report that checkstyle doesn't expect
+
+mod checkstyle_repo;
+
+use lintal_java_cst::TreeWalker;
+use lintal_java_parser::JavaParser;
+use lintal_linter::rules::whitespace::separator_wrap::{
+    SeparatorWrap, SeparatorWrapOption, SeparatorWrapToken,
+};
+use lintal_linter::{CheckContext, FromConfig, Rule};
+use lintal_testkit::{FixtureConfig, parse_expected_violations};
+use regex::Regex;
+
+/// A violation at a specific line, with the separator it is about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Violation {
+    line: usize,
+    separator: String,
+}
+
+/// Results of running a single test fixture.
+#[derive(Debug)]
+struct TestResult {
+    file_name: String,
+    expected: Vec<Violation>,
+    missing: Vec<Violation>,
+    false_positives: Vec<Violation>,
+}
+
+impl TestResult {
+    fn print_summary(&self) {
+        println!("\n=== {} ===", self.file_name);
+        println!(
+            "Expected: {}, Missing: {}, False Positives: {}",
+            self.expected.len(),
+            self.missing.len(),
+            self.false_positives.len()
+        );
+        for v in &self.missing {
+            println!("  Missing: line {} '{}'", v.line, v.separator);
+        }
+        for v in &self.false_positives {
+            println!("  False positive: line {} '{}'", v.line, v.separator);
+        }
+    }
+
+    fn is_perfect(&self) -> bool {
+        self.missing.is_empty() && self.false_positives.is_empty()
+    }
+}
+
+/// The separator quoted at the start of a message, e.g. `.` from
+/// `'.' should be on a new line.`
+fn quoted_separator(message: &str) -> String {
+    let separator_re = Regex::new(r"'([^']+)'").unwrap();
+    separator_re
+        .captures(message)
+        .map(|c| c[1].to_string())
+        .unwrap_or_default()
+}
+
+/// The violations a checkstyle test file expects, from its comments.
+fn expected_violations(source: &str) -> Vec<Violation> {
+    parse_expected_violations(source)
+        .into_iter()
+        .map(|expected| Violation {
+            line: expected.line,
+            separator: expected
+                .message
+                .as_deref()
+                .map(quoted_separator)
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Run SeparatorWrap rule on source and collect violations.
+fn check_separator_wrap(source: &str, rule: &SeparatorWrap) -> Vec<Violation> {
+    let mut parser = JavaParser::new();
+    let Some(result) = parser.parse(source) else {
+        panic!("Failed to parse source");
+    };
+
+    let ctx = CheckContext::new(source);
+    let source_code = ctx.source_code();
+
+    let mut violations = vec![];
+    for node in TreeWalker::new(result.tree.root_node(), source) {
+        for diagnostic in rule.check(&ctx, &node) {
+            let loc = source_code.line_column(diagnostic.range.start());
+            violations.push(Violation {
+                line: loc.line.get(),
+                separator: quoted_separator(&diagnostic.kind.body),
+            });
+        }
+    }
+
+    violations
+}
+
+/// Run a single test fixture and compare against its expected violations.
+fn run_fixture(file_name: &str) -> Option<TestResult> {
+    let path = checkstyle_repo::whitespace_test_input("separatorwrap", file_name)?;
+    let source = std::fs::read_to_string(&path).ok()?;
+
+    let rule = SeparatorWrap::from_config(&FixtureConfig::parse(&source).properties());
+    let expected = expected_violations(&source);
+    let actual = check_separator_wrap(&source, &rule);
+
+    let missing = expected
+        .iter()
+        .filter(|v| !actual.contains(v))
+        .cloned()
+        .collect();
+    let false_positives = actual
+        .iter()
+        .filter(|v| !expected.contains(v))
+        .cloned()
+        .collect();
+
+    Some(TestResult {
+        file_name: file_name.to_string(),
+        expected,
+        missing,
+        false_positives,
+    })
+}
+
+/// All checkstyle SeparatorWrap test fixtures with a valid configuration.
+const ALL_FIXTURES: &[&str] = &[
+    "InputSeparatorWrapForTestTrailingWhitespace.java",
+    "InputSeparatorWrapComma.java",
+    "InputSeparatorWrapMethodRef.java",
+    "InputSeparatorWrapEllipsis.java",
+    "InputSeparatorWrapArrayDeclarator.java",
+    "InputSeparatorWrapWithEmoji.java",
+];
+
+#[test]
+fn test_all_fixtures_comprehensive() {
+    let mut results = vec![];
+
+    for fixture in ALL_FIXTURES {
+        match run_fixture(fixture) {
+            Some(result) => {
+                result.print_summary();
+                results.push(result);
+            }
+            None => {
+                println!("\n=== {} ===", fixture);
+                println!("SKIPPED: checkstyle repo not available or file not found");
+            }
+        }
+    }
+
+    let total_expected: usize = results.iter().map(|r| r.expected.len()).sum();
+    let total_missing: usize = results.iter().map(|r| r.missing.len()).sum();
+    let total_false_positives: usize = results.iter().map(|r| r.false_positives.len()).sum();
+    let perfect_count = results.iter().filter(|r| r.is_perfect()).count();
+
+    println!("\nTotal expected violations: {}", total_expected);
+    println!("Total missing (false negatives): {}", total_missing);
+    println!("Total false positives: {}", total_false_positives);
+    println!("Perfect fixtures: {}/{}", perfect_count, results.len());
+}
+
+// =============================================================================
+// Basic Sanity Tests
+// =============================================================================
+
+#[test]
+fn test_dot_eol_violation() {
+    let source = r#"
+class Test {
+    void test() {
+        String s = "a"
+            .trim();
+    }
+}
+"#;
+    let violations = check_separator_wrap(source, &SeparatorWrap::default());
+    assert_eq!(
+        violations,
+        vec![Violation {
+            line: 5,
+            separator: ".".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_comma_nl_violation() {
+    let source = r#"
+class Test {
+    void test(int a,
+            int b) {}
+}
+"#;
+    let rule = SeparatorWrap {
+        option: SeparatorWrapOption::Nl,
+        tokens: SeparatorWrapToken::default_tokens(),
+    };
+    let violations = check_separator_wrap(source, &rule);
+    assert_eq!(
+        violations,
+        vec![Violation {
+            line: 3,
+            separator: ",".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_ellipsis_and_method_ref() {
+    let source = r#"
+class Test {
+    void test(String
+            ... args) {
+        Runnable r = this
+            ::toString;
+    }
+}
+"#;
+    let rule = SeparatorWrap {
+        option: SeparatorWrapOption::Eol,
+        tokens: [SeparatorWrapToken::Ellipsis, SeparatorWrapToken::MethodRef]
+            .into_iter()
+            .collect(),
+    };
+    let violations = check_separator_wrap(source, &rule);
+    assert_eq!(violations.len(), 2, "{:?}", violations);
+}
+
+#[test]
+fn test_unwrapped_no_violation() {
+    let source = r#"
+class Test {
+    void test(int a, int b) {
+        String s = "a".trim().toUpperCase();
+    }
+}
+"#;
+    let violations = check_separator_wrap(source, &SeparatorWrap::default());
+    assert!(violations.is_empty());
+}
@@ -1,15 +1,13 @@
 //! StringLiteralEquality checkstyle compatibility tests.
 
 mod checkstyle_repo;
-mod test_harness;
 
 use lintal_java_cst::TreeWalker;
 use lintal_java_parser::JavaParser;
 use lintal_linter::rules::StringLiteralEquality;
 use lintal_linter::{CheckContext, Rule};
 use lintal_source_file::{LineIndex, SourceCode};
-use regex::Regex;
-use test_harness::TestResult;
+use lintal_testkit::{TestResult, expected_violation_lines};
 
 /// Run the StringLiteralEquality rule on source code and return violation lines.
 fn check_string_literal_equality(source: &str) -> Vec<usize> {
@@ -36,50 +34,6 @@ fn check_string_literal_equality(source: &str) -> Vec<usize> {
     violations
 }
 
-/// Parse expected violations from checkstyle test file comments.
-/// Supports formats like:
-/// - `// violation` (inline)
-/// - `// violation 'message'`
-/// - `// violation above` (violation is on previous line)
-/// - `// violation below` (violation is on next line)
-/// - `// violation 3 lines above` (violation N lines above)
-fn parse_expected_violations(source: &str) -> Vec<usize> {
-    let mut violations = vec![];
-
-    // Match patterns like "// violation" with optional message
-    let inline_re = Regex::new(r"//\s*violation\s*(?:'[^']*')?(?:\s|$)").unwrap();
-    // Match "violation above" pattern
-    let above_re = Regex::new(r"//\s*violation\s+above").unwrap();
-    // Match "violation below" pattern
-    let below_re = Regex::new(r"//\s*violation\s+below").unwrap();
-    // Match "violation N lines above" pattern
-    let n_above_re = Regex::new(r"//\s*violation\s+(\d+)\s+lines?\s+above").unwrap();
-
-    for (i, line) in source.lines().enumerate() {
-        let line_num = i + 1;
-
-        // Check for "N lines above" first (more specific pattern)
-        if let Some(caps) = n_above_re.captures(line) {
-            if let Some(n) = caps.get(1).and_then(|m| m.as_str().parse::<usize>().ok())
-                && line_num > n
-            {
-                violations.push(line_num - n);
-            }
-        } else if above_re.is_match(line) {
-            // Simple "above" means previous line
-            if line_num > 1 {
-                violations.push(line_num - 1);
-            }
-        } else if below_re.is_match(line) {
-            violations.push(line_num + 1);
-        } else if inline_re.is_match(line) {
-            violations.push(line_num);
-        }
-    }
-
-    violations
-}
-
 fn load_fixture(file_name: &str) -> Option<String> {
     let path = checkstyle_repo::coding_test_input("stringliteralequality", file_name)?;
     std::fs::read_to_string(&path).ok()
@@ -92,7 +46,7 @@ fn test_input_string_literal_equality() {
         return;
     };
 
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_string_literal_equality(&source);
 
     let result = TestResult::compare(expected, actual);
@@ -109,7 +63,7 @@ fn test_input_string_literal_equality_check() {
         return;
     };
 
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_string_literal_equality(&source);
 
     let result = TestResult::compare(expected, actual);
@@ -126,7 +80,7 @@ fn test_input_string_literal_equality_text_blocks() {
         return;
     };
 
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_string_literal_equality(&source);
 
     let result = TestResult::compare(expected, actual);
@@ -143,7 +97,7 @@ fn test_input_concatenated_string() {
         return;
     };
 
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_string_literal_equality(&source);
 
     let result = TestResult::compare(expected, actual);
@@ -160,7 +114,7 @@ fn test_input_concatenated_text_blocks() {
         return;
     };
 
-    let expected = parse_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     let actual = check_string_literal_equality(&source);
 
     let result = TestResult::compare(expected, actual);
@@ -2,10 +2,9 @@
 
 mod checkstyle_repo;
 
-use lintal_java_cst::TreeWalker;
-use lintal_java_parser::JavaParser;
+use lintal_diagnostics::Diagnostic;
 use lintal_linter::rules::UpperEll;
-use lintal_linter::{CheckContext, Rule};
+use lintal_linter::{Rule, lint_source};
 use lintal_source_file::{LineIndex, SourceCode};
 
 /// A violation at a specific location.
@@ -22,32 +21,30 @@ impl Violation {
     }
 }
 
-/// Run UpperEll rule on source and collect violations.
-fn check_upper_ell(source: &str) -> Vec<Violation> {
-    let mut parser = JavaParser::new();
-    let Some(result) = parser.parse(source) else {
+/// Run UpperEll rule on source and return its diagnostics.
+fn upper_ell_diagnostics(source: &str) -> Vec<Diagnostic> {
+    let rules: Vec<Box<dyn Rule>> = vec![Box::new(UpperEll)];
+    let Some(diagnostics) = lint_source(source, &rules) else {
         panic!("Failed to parse source");
     };
+    diagnostics.into_iter().map(|d| d.diagnostic).collect()
+}
 
-    let rule = UpperEll;
-    let ctx = CheckContext::new(source);
+/// Run UpperEll rule on source and collect violations.
+fn check_upper_ell(source: &str) -> Vec<Violation> {
     let line_index = LineIndex::from_source_text(source);
     let source_code = SourceCode::new(source, &line_index);
 
-    let mut violations = vec![];
-
-    for node in TreeWalker::new(result.tree.root_node(), source) {
-        let diagnostics = rule.check(&ctx, &node);
-        for diagnostic in diagnostics {
+    upper_ell_diagnostics(source)
+        .iter()
+        .map(|diagnostic| {
             let loc = source_code.line_column(diagnostic.range.start());
-            violations.push(Violation {
+            Violation {
                 line: loc.line.get(),
                 column: loc.column.get(),
-            });
-        }
-    }
-
-    violations
+            }
+        })
+        .collect()
 }
 
 /// Load a checkstyle test input file.
@@ -170,16 +167,7 @@ class Test {
     long a = 123l;
 }
 "#;
-    let mut parser = JavaParser::new();
-    let result = parser.parse(source).unwrap();
-    let rule = UpperEll;
-    let ctx = CheckContext::new(source);
-
-    let mut diagnostics = vec![];
-    for node in TreeWalker::new(result.tree.root_node(), source) {
-        diagnostics.extend(rule.check(&ctx, &node));
-    }
-
+    let diagnostics = upper_ell_diagnostics(source);
     assert_eq!(diagnostics.len(), 1);
     let fix = diagnostics[0].fix.as_ref().expect("Fix should be present");
     let edits = fix.edits();
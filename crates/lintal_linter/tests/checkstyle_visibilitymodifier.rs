@@ -0,0 +1,106 @@
+//! VisibilityModifier checkstyle compatibility tests.
+
+mod checkstyle_repo;
+
+use lintal_linter::rules::VisibilityModifier;
+use lintal_linter::{FromConfig, Properties};
+use lintal_testkit::{FixtureConfig, check_source, verify_fixture};
+
+/// Run the VisibilityModifier rule on source code and return violation lines.
+fn check_visibility_modifier(source: &str, rule: VisibilityModifier) -> Vec<usize> {
+    check_source(&[Box::new(rule)], source)
+        .iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+fn load_fixture(file_name: &str) -> Option<String> {
+    let path = checkstyle_repo::design_test_input("visibilitymodifier", file_name)?;
+    std::fs::read_to_string(&path).ok()
+}
+
+fn run_fixture(file_name: &str) {
+    let Some(source) = load_fixture(file_name) else {
+        eprintln!("Skipping {}: checkstyle repo not available", file_name);
+        return;
+    };
+
+    let rule = VisibilityModifier::from_config(&FixtureConfig::parse(&source).properties());
+    let result = verify_fixture(&[Box::new(rule)], &source);
+    result.print_report(file_name);
+
+    result.assert_no_false_positives();
+    result.assert_detection_rate(100.0);
+}
+
+#[test]
+fn test_input_visibility_modifier() {
+    run_fixture("InputVisibilityModifier.java");
+}
+
+#[test]
+fn test_input_visibility_modifier_public_immutable() {
+    run_fixture("InputVisibilityModifierIsStarImport.java");
+}
+
+#[test]
+fn test_input_visibility_modifier_annotations() {
+    run_fixture("InputVisibilityModifierAnnotation.java");
+}
+
+#[test]
+fn test_input_visibility_modifier_enum_and_interface() {
+    run_fixture("InputVisibilityModifierEnumAndInterface.java");
+}
+
+#[test]
+fn test_from_config_default() {
+    let props = Properties::new();
+    let rule = VisibilityModifier::from_config(&props);
+    assert!(!rule.package_allowed);
+    assert!(!rule.protected_allowed);
+    assert!(rule.public_member_pattern.is_match("serialVersionUID"));
+    assert_eq!(rule.immutable_class_canonical_names.len(), 20);
+    assert_eq!(rule.ignore_annotation_canonical_names.len(), 3);
+}
+
+#[test]
+fn test_from_config_custom() {
+    let mut props = Properties::new();
+    props.insert("packageAllowed", "true");
+    props.insert("protectedAllowed", "true");
+    props.insert("publicMemberPattern", "^f[A-Z]");
+    props.insert("allowPublicImmutableFields", "true");
+    props.insert(
+        "immutableClassCanonicalNames",
+        "java.lang.String, com.example.Money",
+    );
+    props.insert("ignoreAnnotationCanonicalNames", "com.example.Inject");
+    let rule = VisibilityModifier::from_config(&props);
+    assert!(rule.package_allowed);
+    assert!(rule.protected_allowed);
+    assert!(rule.public_member_pattern.is_match("fName"));
+    assert!(rule.allow_public_immutable_fields);
+    assert_eq!(
+        rule.immutable_class_canonical_names,
+        vec!["java.lang.String", "com.example.Money"]
+    );
+    assert_eq!(
+        rule.ignore_annotation_canonical_names,
+        vec!["com.example.Inject"]
+    );
+}
+
+#[test]
+fn test_public_field_violation() {
+    let source = r#"
+class Foo {
+    private int a;
+    public int b;
+}
+"#;
+    assert_eq!(
+        check_visibility_modifier(source, VisibilityModifier::default()),
+        vec![4]
+    );
+}
@@ -7,6 +7,7 @@ use lintal_java_parser::JavaParser;
 use lintal_linter::rules::WhitespaceAfter;
 use lintal_linter::{CheckContext, Rule};
 use lintal_source_file::{LineIndex, SourceCode};
+use lintal_testkit::expected_violation_lines;
 use std::collections::{HashMap, HashSet};
 
 /// A violation at a specific location.
@@ -138,40 +139,6 @@ fn load_fixture(file_name: &str) -> Option<String> {
     std::fs::read_to_string(&path).ok()
 }
 
-/// Extract expected violations from inline comments in test file.
-/// Format: // violation '',' is not followed by whitespace'
-fn extract_expected_violations(source: &str) -> Vec<(usize, String)> {
-    let mut violations = vec![];
-    for (line_num, line) in source.lines().enumerate() {
-        if let Some(comment_start) = line.find("// violation") {
-            let comment = &line[comment_start..];
-            // Extract token from pattern: ''X' is not followed'
-            if let Some(start) = comment.find("''") {
-                let after_quote = &comment[start + 2..];
-                if let Some(end) = after_quote.find("'") {
-                    let token = after_quote[..end].to_string();
-                    violations.push((line_num + 1, token)); // 1-indexed
-                }
-            }
-        }
-    }
-    violations
-}
-
-/// Helper to verify violations match expected.
-#[allow(dead_code)]
-fn verify_violations(violations: &[Violation], expected_lines: &[usize], token: &str) -> bool {
-    for line in expected_lines {
-        if !violations
-            .iter()
-            .any(|v| v.line == *line && v.token == token)
-        {
-            return false;
-        }
-    }
-    true
-}
-
 /// Print violation details for debugging.
 fn print_violations(label: &str, violations: &[Violation]) {
     println!("\n{}:", label);
@@ -203,7 +170,7 @@ fn test_whitespace_after_default_config() {
         return;
     };
 
-    let expected = extract_expected_violations(&source);
+    let expected = expected_violation_lines(&source);
     println!("Expected violations from comments: {:?}", expected);
 
     let violations = check_whitespace_after(&source);
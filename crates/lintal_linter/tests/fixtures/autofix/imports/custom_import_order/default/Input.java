@@ -0,0 +1,16 @@
+import javax.swing.JButton; // Buttons
+import java.util.Map;
+
+import static java.lang.Math.max;
+// Collections
+import java.util.ArrayList;
+import java.util.List;
+
+public class Input {
+    private List<String> items = new ArrayList<>();
+    private Map<String, JButton> buttons;
+
+    public int size() {
+        return max(items.size(), 0);
+    }
+}
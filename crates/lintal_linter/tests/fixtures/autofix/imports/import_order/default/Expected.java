@@ -0,0 +1,16 @@
+import static java.lang.Math.max;
+
+import java.util.ArrayList;
+import java.util.List;
+import java.util.Map;
+
+import javax.swing.JButton;
+
+public class Input {
+    private List<String> items = new ArrayList<>();
+    private Map<String, JButton> buttons;
+
+    public int size() {
+        return max(items.size(), 0);
+    }
+}
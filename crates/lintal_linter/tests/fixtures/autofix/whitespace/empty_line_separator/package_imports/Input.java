@@ -0,0 +1,10 @@
+package com.example;
+import java.util.ArrayList;
+import java.util.List;
+public class Input {
+    private final List<String> names = new ArrayList<>();
+
+    public List<String> getNames() {
+        return names;
+    }
+}
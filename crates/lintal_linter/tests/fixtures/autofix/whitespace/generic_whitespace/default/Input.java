@@ -0,0 +1,15 @@
+import java.util.ArrayList;
+import java.util.List;
+import java.util.Map;
+
+public class Input {
+    // Whitespace inside the angle brackets - should be removed
+    private Map < String, List<Integer> > map;
+
+    // Type parameters need whitespace around them
+    public<T>List<T> copy(List<T> source) {
+        // No whitespace between the diamond and the arguments
+        List<T> result = new ArrayList<> (source);
+        return result;
+    }
+}
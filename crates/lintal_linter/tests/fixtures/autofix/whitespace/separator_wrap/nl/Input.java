@@ -0,0 +1,14 @@
+import java.util.Arrays;
+import java.util.List;
+
+public class Input {
+    // Separators at end of line - should move to start of next line
+    public List<String> method(String first,
+            String second) {
+        String joined = first.
+            concat(second).
+            trim();
+        return Arrays.asList(joined,
+            first);
+    }
+}
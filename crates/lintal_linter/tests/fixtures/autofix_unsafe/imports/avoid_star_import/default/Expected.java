@@ -0,0 +1,16 @@
+import java.util.ArrayList;
+import java.util.Collections;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+
+public class Input {
+    private final List<String> items = new ArrayList<>();
+    private final Map<String, Integer> counts = new HashMap<>();
+
+    public List<String> sorted() {
+        List<String> copy = new ArrayList<>(items);
+        Collections.sort(copy);
+        return copy;
+    }
+}
@@ -0,0 +1,12 @@
+import java.util.*;
+
+public class Input {
+    private final List<String> items = new ArrayList<>();
+    private final Map<String, Integer> counts = new HashMap<>();
+
+    public List<String> sorted() {
+        List<String> copy = new ArrayList<>(items);
+        Collections.sort(copy);
+        return copy;
+    }
+}
@@ -0,0 +1,11 @@
+/*
+UpperEll
+
+*/
+class InputUpperEll {
+    long decimal = 1l;
+    long upper = 1L;
+	long hex = 0xFFl;
+    long binary = 0b1010l;
+    int notLong = 1;
+}
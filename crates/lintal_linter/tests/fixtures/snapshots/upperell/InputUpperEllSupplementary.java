@@ -0,0 +1,9 @@
+/*
+UpperEll
+
+*/
+class InputUpperEllSupplementary {
+    String s = "😀"; long a = 1l;
+    String e = "é"; long b = 2l;
+    String w = "👋🏽"; long c = 3l;
+}
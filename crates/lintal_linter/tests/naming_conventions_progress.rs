@@ -167,6 +167,18 @@ fn test_naming_conventions_progress() {
     let package_name = collect_package_name_metrics();
     progress.add(package_name);
 
+    // ClassTypeParameterName
+    let class_type_parameter_name = collect_class_type_parameter_name_metrics();
+    progress.add(class_type_parameter_name);
+
+    // InterfaceTypeParameterName
+    let interface_type_parameter_name = collect_interface_type_parameter_name_metrics();
+    progress.add(interface_type_parameter_name);
+
+    // MethodTypeParameterName
+    let method_type_parameter_name = collect_method_type_parameter_name_metrics();
+    progress.add(method_type_parameter_name);
+
     // Print the summary
     progress.print_summary();
 
@@ -268,3 +280,27 @@ fn collect_package_name_metrics() -> RuleMetrics {
 
     metrics
 }
+
+fn collect_class_type_parameter_name_metrics() -> RuleMetrics {
+    use lintal_linter::rules::ClassTypeParameterName;
+    naming_test_utils::run_all_tests_for_rule::<ClassTypeParameterName>(
+        "ClassTypeParameterName",
+        "classtypeparametername",
+    )
+}
+
+fn collect_interface_type_parameter_name_metrics() -> RuleMetrics {
+    use lintal_linter::rules::InterfaceTypeParameterName;
+    naming_test_utils::run_all_tests_for_rule::<InterfaceTypeParameterName>(
+        "InterfaceTypeParameterName",
+        "interfacetypeparametername",
+    )
+}
+
+fn collect_method_type_parameter_name_metrics() -> RuleMetrics {
+    use lintal_linter::rules::MethodTypeParameterName;
+    naming_test_utils::run_all_tests_for_rule::<MethodTypeParameterName>(
+        "MethodTypeParameterName",
+        "methodtypeparametername",
+    )
+}
@@ -14,6 +14,7 @@ use lintal_java_cst::TreeWalker;
 use lintal_java_parser::JavaParser;
 use lintal_linter::{CheckContext, FromConfig, Properties, Rule};
 use lintal_source_file::{LineIndex, SourceCode};
+use lintal_testkit::{FixtureConfig, parse_expected_violations};
 use regex::Regex;
 use std::path::PathBuf;
 
@@ -236,122 +237,23 @@ impl RuleMetrics {
     }
 }
 
-/// Parse expected violations from checkstyle test file comments.
-///
-/// Checkstyle marks expected violations with `// violation` comments.
-/// Format:
-/// - `// violation 'message'` - violation on this line
-/// - `// violation above 'message'` - violation on previous line
-/// - `// violation below 'message'` - violation on next line
-/// - `// N violations M lines below:` - N violations M lines below
-pub fn parse_expected_violations(source: &str) -> Vec<Violation> {
-    let mut violations = Vec::new();
-
-    // Pattern for single violation comments
-    // Matches: // violation 'Name 'foo' must match pattern'
-    // Also: // violation above|below 'message'
-    let single_violation_re =
-        Regex::new(r"//\s*violation(?:\s+(above|below))?(?:\s+'[^']*')?").unwrap();
-
-    // Pattern for multiple violations
-    // Matches: // 2 violations 3 lines below:
-    // Also: // 2 violations below
-    // Also: // 2 violations (on same line)
-    let multi_violation_re =
-        Regex::new(r"//\s*(\d+)\s+violations?(?:\s+(?:(\d+)\s+lines?\s+)?(above|below))?").unwrap();
-
+/// Expected violations from checkstyle test file comments, with the name
+/// each message quotes, e.g. `foo` from `// violation 'Name 'foo' must match pattern'`.
+pub fn expected_violations(source: &str) -> Vec<Violation> {
     let name_re = Regex::new(r"Name\s+'([^']+)'").unwrap();
 
-    for (line_idx, line) in source.lines().enumerate() {
-        let line_num = line_idx + 1;
-
-        // Check for multi-violation pattern first (e.g., "// 2 violations 3 lines below:")
-        if let Some(caps) = multi_violation_re.captures(line) {
-            let count: usize = caps
-                .get(1)
-                .map(|m| m.as_str().parse().unwrap_or(1))
-                .unwrap_or(1);
-            let line_offset: usize = caps
-                .get(2)
-                .map(|m| m.as_str().parse().unwrap_or(1))
-                .unwrap_or(1);
-            let direction = caps.get(3).map(|m| m.as_str());
-
-            let actual_line = match direction {
-                Some("above") => line_num.saturating_sub(line_offset),
-                Some("below") => line_num + line_offset,
-                _ => line_num,
-            };
-
-            // Add the specified number of violations
-            for _ in 0..count {
-                violations.push(Violation {
-                    line: actual_line,
-                    column: 1,
-                    name: None,
-                });
-            }
-        } else if let Some(caps) = single_violation_re.captures(line) {
-            // Single violation pattern
-            let direction = caps.get(1).map(|m| m.as_str());
-            let actual_line = match direction {
-                Some("above") => line_num.saturating_sub(1),
-                Some("below") => line_num + 1,
-                _ => line_num,
-            };
-
-            // Try to extract the name from the message
-            let name = name_re
-                .captures(line)
-                .map(|c| c.get(1).unwrap().as_str().to_string());
-
-            violations.push(Violation {
-                line: actual_line,
-                column: 1,
-                name,
-            });
-        }
-    }
-
-    violations
-}
-
-/// Parse config from checkstyle test file header comment.
-///
-/// Checkstyle test files have a header like:
-/// ```java
-/// /*
-/// ConstantName
-/// format = ^[A-Z][A-Z0-9]*(_[A-Z0-9]+)*$
-/// applyToPublic = true
-/// */
-/// ```
-pub fn parse_config_from_header(source: &str) -> Properties<'_> {
-    let mut properties = Properties::new();
-
-    // Find the header comment
-    if let Some(start) = source.find("/*") {
-        if let Some(end) = source[start..].find("*/") {
-            let header = &source[start + 2..start + end];
-
-            // Parse key = value lines
-            for line in header.lines() {
-                let line = line.trim();
-                if let Some(eq_pos) = line.find('=') {
-                    let key = line[..eq_pos].trim();
-                    let value = line[eq_pos + 1..].trim();
-                    // Skip (default) values - let the rule use its actual default
-                    // This avoids issues with Java-style escaping (e.g., \\. vs \.)
-                    if value.starts_with("(default)") {
-                        continue;
-                    }
-                    properties.insert(key, value);
-                }
-            }
-        }
-    }
-
-    properties
+    parse_expected_violations(source)
+        .into_iter()
+        .map(|expected| Violation {
+            line: expected.line,
+            column: 1,
+            name: expected
+                .message
+                .as_deref()
+                .and_then(|message| name_re.captures(message))
+                .map(|c| c.get(1).unwrap().as_str().to_string()),
+        })
+        .collect()
 }
 
 /// Run a naming rule on source and collect violations.
@@ -475,9 +377,9 @@ pub fn run_all_tests_for_rule<R: Rule + FromConfig>(
             continue;
         };
 
-        let properties = parse_config_from_header(&source);
-        let expected = parse_expected_violations(&source);
-        let actual = run_rule::<R>(&source, &properties);
+        let config = FixtureConfig::parse(&source);
+        let expected = expected_violations(&source);
+        let actual = run_rule::<R>(&source, &config.properties());
 
         let test_metrics = compute_metrics(&file_name, &actual, &expected);
         rule_metrics.add(test_metrics);
@@ -491,7 +393,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_expected_violations() {
+    fn test_expected_violations() {
         let source = r#"
 class Foo {
     public static final int badConstant = 2; // violation 'Name 'badConstant' must match pattern'
@@ -499,7 +401,7 @@ class Foo {
     public static final int BAD__NAME = 3; // violation 'Name 'BAD__NAME' must match pattern'
 }
 "#;
-        let violations = parse_expected_violations(source);
+        let violations = expected_violations(source);
         assert_eq!(violations.len(), 2);
         assert_eq!(violations[0].line, 3);
         assert_eq!(violations[0].name.as_deref(), Some("badConstant"));
@@ -507,23 +409,6 @@ class Foo {
         assert_eq!(violations[1].name.as_deref(), Some("BAD__NAME"));
     }
 
-    #[test]
-    fn test_parse_config_from_header() {
-        let source = r#"/*
-ConstantName
-format = ^[A-Z][A-Z0-9]*(_[A-Z0-9]+)*$
-applyToPublic = (default)true
-applyToPrivate = false
-*/
-package test;
-"#;
-        let config = parse_config_from_header(source);
-        assert_eq!(config.get("format"), Some(&"^[A-Z][A-Z0-9]*(_[A-Z0-9]+)*$"));
-        // (default) values are skipped so the rule uses its actual default
-        assert_eq!(config.get("applyToPublic"), None);
-        assert_eq!(config.get("applyToPrivate"), Some(&"false"));
-    }
-
     #[test]
     fn test_compute_metrics() {
         let actual = vec![Violation::new(10, 5), Violation::new(20, 10)];
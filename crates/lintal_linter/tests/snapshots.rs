@@ -0,0 +1,45 @@
+//! Snapshot tests of what rules report and how they fix a file.
+//!
+//! Each `.java` file under `tests/fixtures/snapshots` names the rule to run
+//! in a checkstyle-style header and has its expected output in a `.snap`
+//! file beside it. To add a regression test for a rule bug, add an input and
+//! run `LINTAL_UPDATE_SNAPSHOTS=1 cargo test --test snapshots`, then review
+//! the new snapshot.
+
+use std::path::PathBuf;
+
+use lintal_linter::RuleRegistry;
+use lintal_testkit::{FixtureConfig, assert_file_snapshot};
+use walkdir::WalkDir;
+
+#[test]
+fn test_snapshots() {
+    let fixtures_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/snapshots");
+    let registry = RuleRegistry::builtin();
+
+    let mut inputs: Vec<PathBuf> = WalkDir::new(&fixtures_dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .map(|entry| entry.into_path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "java"))
+        .collect();
+    inputs.sort();
+    assert!(
+        !inputs.is_empty(),
+        "no inputs in {}",
+        fixtures_dir.display()
+    );
+
+    for input in inputs {
+        let source = std::fs::read_to_string(&input).unwrap();
+        let config = FixtureConfig::parse(&source);
+        let module = config
+            .module
+            .as_deref()
+            .unwrap_or_else(|| panic!("{}: header names no module", input.display()));
+        let rule = registry
+            .create_rule(module, &config.properties())
+            .unwrap_or_else(|| panic!("{}: unknown module {module}", input.display()));
+        assert_file_snapshot(&[rule], &input);
+    }
+}
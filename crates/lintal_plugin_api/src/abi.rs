@@ -0,0 +1,168 @@
+//! The C ABI shared by lintal and its plugins.
+//!
+//! Only the types in this module cross the library boundary. They must not
+//! change within an [`ABI_VERSION`]; additions go into a new version with a
+//! new entry point.
+
+use std::ffi::c_void;
+
+/// Version of the plugin ABI described by this module.
+pub const ABI_VERSION: u32 = 1;
+
+/// Name of the function a plugin exports, of type [`EntryPoint`].
+pub const ENTRY_POINT: &str = "lintal_plugin_v1";
+
+/// The function a plugin exports under [`ENTRY_POINT`].
+///
+/// It returns a pointer to a [`RawPlugin`] that stays valid for as long as
+/// the library is loaded.
+pub type EntryPoint = unsafe extern "C" fn() -> *const RawPlugin;
+
+/// A borrowed UTF-8 string.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct RawStr {
+    pub ptr: *const u8,
+    pub len: usize,
+}
+
+impl RawStr {
+    pub fn new(s: &str) -> Self {
+        Self {
+            ptr: s.as_ptr(),
+            len: s.len(),
+        }
+    }
+
+    /// View the string.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to `len` bytes of valid UTF-8 that live for `'a`, or
+    /// be null (read as the empty string).
+    pub unsafe fn as_str<'a>(self) -> &'a str {
+        if self.ptr.is_null() {
+            return "";
+        }
+        // SAFETY: guaranteed by the caller
+        unsafe { std::str::from_utf8_unchecked(std::slice::from_raw_parts(self.ptr, self.len)) }
+    }
+}
+
+/// A range of byte offsets into the source being checked.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Handle to a syntax node, valid only during the `check` call it was
+/// handed out in.
+pub type NodeHandle = usize;
+
+/// The handle for "no node" (no parent, no such child).
+pub const NO_NODE: NodeHandle = 0;
+
+/// Functions lintal provides to a rule while it checks a node.
+///
+/// Every function takes `ctx` as its first argument. Strings returned by the
+/// host are valid until the `check` call returns.
+#[repr(C)]
+pub struct RawHost {
+    pub ctx: *mut c_void,
+    /// The full source text of the file.
+    pub source: extern "C" fn(ctx: *mut c_void) -> RawStr,
+    /// The grammar kind of a node (`method_invocation`, `identifier`, ...).
+    pub kind: extern "C" fn(ctx: *mut c_void, node: NodeHandle) -> RawStr,
+    /// Whether a node is named, as opposed to punctuation or a keyword.
+    pub is_named: extern "C" fn(ctx: *mut c_void, node: NodeHandle) -> bool,
+    pub range: extern "C" fn(ctx: *mut c_void, node: NodeHandle) -> RawRange,
+    pub parent: extern "C" fn(ctx: *mut c_void, node: NodeHandle) -> NodeHandle,
+    pub child_count: extern "C" fn(ctx: *mut c_void, node: NodeHandle) -> usize,
+    pub child: extern "C" fn(ctx: *mut c_void, node: NodeHandle, index: usize) -> NodeHandle,
+    pub child_by_field_name:
+        extern "C" fn(ctx: *mut c_void, node: NodeHandle, field: RawStr) -> NodeHandle,
+    /// Report a violation. The message is copied.
+    pub report: extern "C" fn(ctx: *mut c_void, range: RawRange, message: RawStr),
+}
+
+/// A configuration property (`<property name="..." value="..."/>`).
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct RawProperty {
+    pub name: RawStr,
+    pub value: RawStr,
+}
+
+/// A rule exported by a plugin.
+#[repr(C)]
+pub struct RawRule {
+    /// The checkstyle module name the rule is configured with.
+    pub name: RawStr,
+    /// Node kinds the rule is run on; empty means every node.
+    pub relevant_kinds: *const RawStr,
+    pub relevant_kinds_len: usize,
+    /// Create a configured instance of the rule. Returns null if the
+    /// properties are invalid.
+    pub create: extern "C" fn(properties: *const RawProperty, len: usize) -> *mut c_void,
+    /// Check one node, reporting violations through `host`.
+    pub check: extern "C" fn(rule: *const c_void, host: *const RawHost, node: NodeHandle),
+    /// Free an instance returned by `create`.
+    pub destroy: extern "C" fn(rule: *mut c_void),
+}
+
+/// What a plugin's entry point returns.
+#[repr(C)]
+pub struct RawPlugin {
+    /// Must be [`ABI_VERSION`].
+    pub abi_version: u32,
+    pub name: RawStr,
+    pub rules: *const RawRule,
+    pub rules_len: usize,
+}
+
+/// The WebAssembly flavour of the ABI.
+///
+/// A `.wasm` plugin runs in a sandbox: it may import only the functions of
+/// [`HOST_MODULE`] below, and nothing from WASI. It exports its linear memory
+/// as `memory`, plus:
+///
+/// - [`EXPORT_ABI_VERSION`]`() -> u32`, returning [`ABI_VERSION`](super::ABI_VERSION).
+/// - [`EXPORT_ALLOC`]`(len: u32) -> u32`, allocating a buffer for the host to
+///   fill in. Ownership of the buffer passes to whichever export it is handed to.
+/// - [`EXPORT_DESCRIBE`]`() -> u64`, the address (high half) and length (low
+///   half) of the plugin description: its name on the first line, then a line
+///   per rule holding the rule name and a tab followed by its comma-separated
+///   relevant kinds.
+/// - [`EXPORT_CREATE`]`(rule: u32, properties: u32, len: u32) -> u32`,
+///   configuring the rule at index `rule` from an allocated buffer of
+///   NUL-terminated property names and values. Returns an instance handle, or
+///   0 if the properties are invalid.
+/// - [`EXPORT_CHECK`]`(rule: u32, instance: u32, node: u32)`.
+///
+/// Host functions mirror [`RawHost`](super::RawHost), with `u32` node handles.
+/// Those returning strings take a guest buffer `(ptr, cap)`, copy as much of
+/// the string as fits and return its full length, so a guest retries with a
+/// larger buffer when the result exceeds `cap`:
+///
+/// - `file_id() -> u32`, changing whenever the file being checked does
+/// - `source(ptr, cap) -> u32`
+/// - `kind(node, ptr, cap) -> u32`
+/// - `is_named(node) -> u32`
+/// - `range(node) -> u64`, start in the high half and end in the low half
+/// - `parent(node) -> u32`
+/// - `child_count(node) -> u32`
+/// - `child(node, index) -> u32`
+/// - `child_by_field_name(node, field_ptr, field_len) -> u32`
+/// - `report(start, end, message_ptr, message_len)`
+pub mod wasm {
+    /// The module every host function is imported from.
+    pub const HOST_MODULE: &str = "lintal";
+
+    pub const EXPORT_ABI_VERSION: &str = "lintal_abi_version";
+    pub const EXPORT_ALLOC: &str = "lintal_alloc";
+    pub const EXPORT_DESCRIBE: &str = "lintal_describe";
+    pub const EXPORT_CREATE: &str = "lintal_create";
+    pub const EXPORT_CHECK: &str = "lintal_check";
+}
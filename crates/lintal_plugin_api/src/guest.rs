@@ -0,0 +1,208 @@
+//! Safe wrappers for writing a plugin in Rust.
+
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::ops::Range;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use crate::abi::{
+    ABI_VERSION, NO_NODE, NodeHandle, RawHost, RawPlugin, RawProperty, RawRange, RawRule, RawStr,
+};
+
+/// A rule implemented by a plugin.
+pub trait PluginRule: Send + Sync + Sized + 'static {
+    /// The checkstyle module name the rule is configured with.
+    const NAME: &'static str;
+
+    /// Node kinds the rule is run on. Empty means every node.
+    const RELEVANT_KINDS: &'static [&'static str] = &[];
+
+    /// Create the rule from its configuration properties, or `None` if they
+    /// are invalid.
+    fn from_properties(properties: &HashMap<&str, &str>) -> Option<Self>;
+
+    /// Check a node, reporting violations through `host`.
+    fn check(&self, host: &Host<'_>, node: Node<'_>);
+}
+
+/// The file being checked, and where violations go.
+#[derive(Clone, Copy)]
+pub struct Host<'h> {
+    raw: &'h RawHost,
+}
+
+impl<'h> Host<'h> {
+    /// The full source text of the file.
+    pub fn source(&self) -> &'h str {
+        // SAFETY: host strings are valid for the duration of the check call
+        unsafe { (self.raw.source)(self.raw.ctx).as_str() }
+    }
+
+    /// Report a violation at a byte range of the source.
+    pub fn report(&self, range: Range<u32>, message: &str) {
+        (self.raw.report)(
+            self.raw.ctx,
+            RawRange {
+                start: range.start,
+                end: range.end,
+            },
+            RawStr::new(message),
+        );
+    }
+}
+
+/// A syntax node of the file being checked.
+#[derive(Clone, Copy)]
+pub struct Node<'h> {
+    raw: &'h RawHost,
+    handle: NodeHandle,
+}
+
+impl<'h> Node<'h> {
+    fn from_handle(raw: &'h RawHost, handle: NodeHandle) -> Option<Self> {
+        (handle != NO_NODE).then_some(Self { raw, handle })
+    }
+
+    pub fn kind(&self) -> &'h str {
+        // SAFETY: host strings are valid for the duration of the check call
+        unsafe { (self.raw.kind)(self.raw.ctx, self.handle).as_str() }
+    }
+
+    pub fn is_named(&self) -> bool {
+        (self.raw.is_named)(self.raw.ctx, self.handle)
+    }
+
+    /// Byte range of the node in the source.
+    pub fn range(&self) -> Range<u32> {
+        let range = (self.raw.range)(self.raw.ctx, self.handle);
+        range.start..range.end
+    }
+
+    /// Source text of the node.
+    pub fn text(&self) -> &'h str {
+        let range = self.range();
+        Host { raw: self.raw }
+            .source()
+            .get(range.start as usize..range.end as usize)
+            .unwrap_or_default()
+    }
+
+    pub fn parent(&self) -> Option<Node<'h>> {
+        Node::from_handle(self.raw, (self.raw.parent)(self.raw.ctx, self.handle))
+    }
+
+    /// Iterate over all children, including punctuation and keywords.
+    pub fn children(&self) -> impl Iterator<Item = Node<'h>> + 'h {
+        let (raw, handle) = (self.raw, self.handle);
+        let count = (raw.child_count)(raw.ctx, handle);
+        (0..count)
+            .filter_map(move |index| Node::from_handle(raw, (raw.child)(raw.ctx, handle, index)))
+    }
+
+    pub fn named_children(&self) -> impl Iterator<Item = Node<'h>> + 'h {
+        self.children().filter(Node::is_named)
+    }
+
+    pub fn child_by_field_name(&self, field: &str) -> Option<Node<'h>> {
+        let handle = (self.raw.child_by_field_name)(self.raw.ctx, self.handle, RawStr::new(field));
+        Node::from_handle(self.raw, handle)
+    }
+}
+
+/// The [`RawRule`] describing `R`.
+pub fn rule_descriptor<R: PluginRule>() -> RawRule {
+    // Leaked once per rule when the plugin is first loaded
+    let kinds: &'static [RawStr] = Box::leak(
+        R::RELEVANT_KINDS
+            .iter()
+            .map(|kind| RawStr::new(kind))
+            .collect::<Box<[_]>>(),
+    );
+    RawRule {
+        name: RawStr::new(R::NAME),
+        relevant_kinds: kinds.as_ptr(),
+        relevant_kinds_len: kinds.len(),
+        create: create::<R>,
+        check: check::<R>,
+        destroy: destroy::<R>,
+    }
+}
+
+/// A plugin's rules, laid out for [`RawPlugin`].
+///
+/// Built once by [`export_plugin!`](crate::export_plugin) or
+/// [`export_wasm_plugin!`](crate::export_wasm_plugin) and kept in a static for
+/// as long as the plugin is loaded.
+pub struct PluginDescriptor {
+    name: &'static str,
+    rules: Vec<RawRule>,
+    raw: RawPlugin,
+}
+
+// SAFETY: the descriptor only holds pointers to `'static` strings and to its
+// own `rules`, and is never mutated after construction
+unsafe impl Send for PluginDescriptor {}
+// SAFETY: see above
+unsafe impl Sync for PluginDescriptor {}
+
+impl PluginDescriptor {
+    pub fn new(name: &'static str, rules: Vec<RawRule>) -> Self {
+        let raw = RawPlugin {
+            abi_version: ABI_VERSION,
+            name: RawStr::new(name),
+            rules: rules.as_ptr(),
+            rules_len: rules.len(),
+        };
+        Self { name, rules, raw }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn rules(&self) -> &[RawRule] {
+        &self.rules
+    }
+
+    pub fn as_raw(&self) -> *const RawPlugin {
+        debug_assert_eq!(self.raw.rules, self.rules.as_ptr());
+        &raw const self.raw
+    }
+}
+
+extern "C" fn create<R: PluginRule>(properties: *const RawProperty, len: usize) -> *mut c_void {
+    let properties: &[RawProperty] = if properties.is_null() {
+        &[]
+    } else {
+        // SAFETY: the host passes `len` properties that outlive this call
+        unsafe { std::slice::from_raw_parts(properties, len) }
+    };
+    let properties: HashMap<&str, &str> = properties
+        .iter()
+        // SAFETY: property strings outlive this call
+        .map(|property| unsafe { (property.name.as_str(), property.value.as_str()) })
+        .collect();
+
+    // Panics must not unwind into the host
+    match catch_unwind(|| R::from_properties(&properties)) {
+        Ok(Some(rule)) => Box::into_raw(Box::new(rule)).cast(),
+        Ok(None) | Err(_) => std::ptr::null_mut(),
+    }
+}
+
+extern "C" fn check<R: PluginRule>(rule: *const c_void, host: *const RawHost, node: NodeHandle) {
+    // SAFETY: `rule` was returned by `create::<R>` and `host` outlives this call
+    let (rule, raw) = unsafe { (&*rule.cast::<R>(), &*host) };
+    let Some(node) = Node::from_handle(raw, node) else {
+        return;
+    };
+    // A panicking rule loses its violations for this node, nothing more
+    let _ = catch_unwind(AssertUnwindSafe(|| rule.check(&Host { raw }, node)));
+}
+
+extern "C" fn destroy<R: PluginRule>(rule: *mut c_void) {
+    if !rule.is_null() {
+        // SAFETY: `rule` was returned by `create::<R>` and is destroyed once
+        drop(unsafe { Box::from_raw(rule.cast::<R>()) });
+    }
+}
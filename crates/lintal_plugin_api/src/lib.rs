@@ -0,0 +1,131 @@
+//! Stable ABI for lintal rule plugins.
+//!
+//! A plugin is a `cdylib` exporting a function named [`abi::ENTRY_POINT`]
+//! that describes its rules. Only `#[repr(C)]` types from [`abi`] cross the
+//! library boundary, so a plugin does not need to be built with the same Rust
+//! compiler (or in Rust at all) as the lintal that loads it, only against the
+//! same [`abi::ABI_VERSION`].
+//!
+//! In Rust, implement [`PluginRule`] and export the rules with
+//! [`export_plugin!`]:
+//!
+//! ```ignore
+//! use std::collections::HashMap;
+//! use lintal_plugin_api::{Host, Node, PluginRule, export_plugin};
+//!
+//! struct NoSystemExit;
+//!
+//! impl PluginRule for NoSystemExit {
+//!     const NAME: &'static str = "NoSystemExit";
+//!     const RELEVANT_KINDS: &'static [&'static str] = &["method_invocation"];
+//!
+//!     fn from_properties(_properties: &HashMap<&str, &str>) -> Option<Self> {
+//!         Some(Self)
+//!     }
+//!
+//!     fn check(&self, host: &Host<'_>, node: Node<'_>) {
+//!         if node.text().starts_with("System.exit(") {
+//!             host.report(node.range(), "Do not call System.exit().");
+//!         }
+//!     }
+//! }
+//!
+//! export_plugin!("acme", [NoSystemExit]);
+//! ```
+//!
+//! The same rules can instead be built for `wasm32-unknown-unknown` with
+//! [`export_wasm_plugin!`] and loaded as a `.wasm` file. Such a plugin runs
+//! sandboxed: it sees only the host functions of [`abi::wasm`], so it can't
+//! touch the filesystem or network, and is safe to load from third parties.
+
+pub mod abi;
+mod guest;
+#[cfg(target_arch = "wasm32")]
+#[doc(hidden)]
+pub mod wasm;
+
+pub use guest::{Host, Node, PluginDescriptor, PluginRule, rule_descriptor};
+
+/// Export the entry point of a plugin with the given name and rule types.
+#[macro_export]
+macro_rules! export_plugin {
+    ($name:expr, [$($rule:ty),* $(,)?]) => {
+        #[unsafe(no_mangle)]
+        pub extern "C" fn lintal_plugin_v1() -> *const $crate::abi::RawPlugin {
+            static PLUGIN: ::std::sync::OnceLock<$crate::PluginDescriptor> =
+                ::std::sync::OnceLock::new();
+            PLUGIN
+                .get_or_init(|| {
+                    $crate::PluginDescriptor::new(
+                        $name,
+                        vec![$($crate::rule_descriptor::<$rule>()),*],
+                    )
+                })
+                .as_raw()
+        }
+    };
+}
+
+/// Export the WebAssembly plugin interface for the given name and rule types.
+///
+/// Only available when building for `wasm32`.
+#[cfg(target_arch = "wasm32")]
+#[macro_export]
+macro_rules! export_wasm_plugin {
+    ($name:expr, [$($rule:ty),* $(,)?]) => {
+        fn __lintal_plugin() -> &'static $crate::PluginDescriptor {
+            static PLUGIN: ::std::sync::OnceLock<$crate::PluginDescriptor> =
+                ::std::sync::OnceLock::new();
+            PLUGIN.get_or_init(|| {
+                $crate::PluginDescriptor::new($name, vec![$($crate::rule_descriptor::<$rule>()),*])
+            })
+        }
+
+        #[unsafe(no_mangle)]
+        pub extern "C" fn lintal_abi_version() -> u32 {
+            $crate::abi::ABI_VERSION
+        }
+
+        #[unsafe(no_mangle)]
+        pub extern "C" fn lintal_alloc(len: u32) -> *mut u8 {
+            $crate::wasm::alloc(len)
+        }
+
+        #[unsafe(no_mangle)]
+        pub extern "C" fn lintal_describe() -> u64 {
+            $crate::wasm::describe(__lintal_plugin())
+        }
+
+        #[unsafe(no_mangle)]
+        pub extern "C" fn lintal_create(rule: u32, properties: *mut u8, len: u32) -> u32 {
+            $crate::wasm::create(__lintal_plugin(), rule, properties, len)
+        }
+
+        #[unsafe(no_mangle)]
+        pub extern "C" fn lintal_check(rule: u32, instance: u32, node: u32) {
+            $crate::wasm::check(__lintal_plugin(), rule, instance, node)
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::abi::RawStr;
+
+    #[test]
+    fn test_raw_str_round_trip() {
+        let text = "method_invocation";
+        // SAFETY: `text` outlives the view
+        assert_eq!(unsafe { RawStr::new(text).as_str() }, text);
+    }
+
+    #[test]
+    fn test_null_raw_str_is_empty() {
+        let raw = RawStr {
+            ptr: std::ptr::null(),
+            len: 3,
+        };
+        // SAFETY: null is read as the empty string
+        assert_eq!(unsafe { raw.as_str() }, "");
+    }
+}
@@ -0,0 +1,194 @@
+//! The guest side of the WebAssembly ABI.
+//!
+//! The host's imports are wrapped in a [`RawHost`] so rules written against
+//! [`PluginRule`](crate::PluginRule) run unchanged whether they are built as a
+//! `cdylib` or as a `.wasm` module. Only used through
+//! [`export_wasm_plugin!`](crate::export_wasm_plugin).
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::ffi::c_void;
+
+use crate::PluginDescriptor;
+use crate::abi::{NodeHandle, RawHost, RawProperty, RawRange, RawStr};
+
+mod imports {
+    #[link(wasm_import_module = "lintal")]
+    unsafe extern "C" {
+        pub(super) fn file_id() -> u32;
+        pub(super) fn source(ptr: *mut u8, cap: u32) -> u32;
+        pub(super) fn kind(node: u32, ptr: *mut u8, cap: u32) -> u32;
+        pub(super) fn is_named(node: u32) -> u32;
+        pub(super) fn range(node: u32) -> u64;
+        pub(super) fn parent(node: u32) -> u32;
+        pub(super) fn child_count(node: u32) -> u32;
+        pub(super) fn child(node: u32, index: u32) -> u32;
+        pub(super) fn child_by_field_name(node: u32, ptr: *const u8, len: u32) -> u32;
+        pub(super) fn report(start: u32, end: u32, ptr: *const u8, len: u32);
+    }
+}
+
+thread_local! {
+    /// The source of the file being checked, keyed by the host's file id.
+    static SOURCE: RefCell<(u32, String)> = const { RefCell::new((0, String::new())) };
+    /// Node kinds seen so far. There are only as many as the grammar has, so
+    /// they are leaked to hand out as `&'static str`.
+    static KINDS: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new());
+}
+
+/// Allocate a buffer of `len` bytes for the host to fill in.
+pub fn alloc(len: u32) -> *mut u8 {
+    Box::into_raw(vec![0u8; len as usize].into_boxed_slice()).cast()
+}
+
+/// The plugin description, packed as `(address << 32) | length`.
+pub fn describe(plugin: &PluginDescriptor) -> u64 {
+    static DESCRIPTION: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+    let description = DESCRIPTION.get_or_init(|| {
+        let mut description = format!("{}\n", plugin.name());
+        for rule in plugin.rules() {
+            // SAFETY: descriptors are built by `rule_descriptor`, whose kinds
+            // and names are `'static`
+            let (name, kinds) = unsafe {
+                let kinds =
+                    std::slice::from_raw_parts(rule.relevant_kinds, rule.relevant_kinds_len);
+                let kinds: Vec<&str> = kinds.iter().map(|kind| kind.as_str()).collect();
+                (rule.name.as_str(), kinds)
+            };
+            description.push_str(&format!("{name}\t{}\n", kinds.join(",")));
+        }
+        description
+    });
+    ((description.as_ptr() as u64) << 32) | description.len() as u64
+}
+
+/// Configure rule `rule` from a buffer of NUL-terminated names and values
+/// allocated by [`alloc`]. Returns 0 if the rule rejects them.
+pub fn create(plugin: &PluginDescriptor, rule: u32, ptr: *mut u8, len: u32) -> u32 {
+    // SAFETY: the host hands over a buffer from `alloc` of exactly `len` bytes
+    let buffer = unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len as usize)) };
+    let text = String::from_utf8_lossy(&buffer);
+    let parts: Vec<&str> = text.split('\0').collect();
+    let properties: Vec<RawProperty> = parts
+        .chunks_exact(2)
+        .map(|pair| RawProperty {
+            name: RawStr::new(pair[0]),
+            value: RawStr::new(pair[1]),
+        })
+        .collect();
+    let Some(raw) = plugin.rules().get(rule as usize) else {
+        return 0;
+    };
+    // Addresses are 32-bit in wasm32, and null is 0
+    (raw.create)(properties.as_ptr(), properties.len()) as u32
+}
+
+/// Check a node with a configured rule instance.
+pub fn check(plugin: &PluginDescriptor, rule: u32, instance: u32, node: u32) {
+    let Some(raw) = plugin.rules().get(rule as usize) else {
+        return;
+    };
+    let host = RawHost {
+        ctx: std::ptr::null_mut(),
+        source: host_source,
+        kind: host_kind,
+        is_named: host_is_named,
+        range: host_range,
+        parent: host_parent,
+        child_count: host_child_count,
+        child: host_child,
+        child_by_field_name: host_child_by_field_name,
+        report: host_report,
+    };
+    (raw.check)(
+        instance as usize as *const c_void,
+        &raw const host,
+        node as NodeHandle,
+    );
+}
+
+/// Read a string from a host function that copies into a guest buffer.
+fn read_string(read: impl Fn(*mut u8, u32) -> u32) -> String {
+    let mut buffer = vec![0u8; 64];
+    loop {
+        let len = read(buffer.as_mut_ptr(), buffer.len() as u32) as usize;
+        if len <= buffer.len() {
+            buffer.truncate(len);
+            return String::from_utf8(buffer).unwrap_or_default();
+        }
+        buffer.resize(len, 0);
+    }
+}
+
+extern "C" fn host_source(_ctx: *mut c_void) -> RawStr {
+    SOURCE.with_borrow_mut(|(file, text)| {
+        // SAFETY: host imports have no preconditions
+        let current = unsafe { imports::file_id() };
+        if *file != current {
+            // SAFETY: as above
+            *text = read_string(|ptr, cap| unsafe { imports::source(ptr, cap) });
+            *file = current;
+        }
+        // The text is only replaced when the next file is checked
+        RawStr::new(text)
+    })
+}
+
+extern "C" fn host_kind(_ctx: *mut c_void, node: NodeHandle) -> RawStr {
+    // SAFETY: host imports have no preconditions
+    let kind = read_string(|ptr, cap| unsafe { imports::kind(node as u32, ptr, cap) });
+    KINDS.with_borrow_mut(|kinds| {
+        let kind = match kinds.get(kind.as_str()) {
+            Some(kind) => *kind,
+            None => {
+                let kind: &'static str = Box::leak(kind.into_boxed_str());
+                kinds.insert(kind);
+                kind
+            }
+        };
+        RawStr::new(kind)
+    })
+}
+
+extern "C" fn host_is_named(_ctx: *mut c_void, node: NodeHandle) -> bool {
+    // SAFETY: host imports have no preconditions
+    unsafe { imports::is_named(node as u32) != 0 }
+}
+
+extern "C" fn host_range(_ctx: *mut c_void, node: NodeHandle) -> RawRange {
+    // SAFETY: host imports have no preconditions
+    let range = unsafe { imports::range(node as u32) };
+    RawRange {
+        start: (range >> 32) as u32,
+        end: range as u32,
+    }
+}
+
+extern "C" fn host_parent(_ctx: *mut c_void, node: NodeHandle) -> NodeHandle {
+    // SAFETY: host imports have no preconditions
+    unsafe { imports::parent(node as u32) as NodeHandle }
+}
+
+extern "C" fn host_child_count(_ctx: *mut c_void, node: NodeHandle) -> usize {
+    // SAFETY: host imports have no preconditions
+    unsafe { imports::child_count(node as u32) as usize }
+}
+
+extern "C" fn host_child(_ctx: *mut c_void, node: NodeHandle, index: usize) -> NodeHandle {
+    // SAFETY: host imports have no preconditions
+    unsafe { imports::child(node as u32, index as u32) as NodeHandle }
+}
+
+extern "C" fn host_child_by_field_name(
+    _ctx: *mut c_void,
+    node: NodeHandle,
+    field: RawStr,
+) -> NodeHandle {
+    // SAFETY: the host only reads `field.len` bytes at `field.ptr`
+    unsafe { imports::child_by_field_name(node as u32, field.ptr, field.len as u32) as NodeHandle }
+}
+
+extern "C" fn host_report(_ctx: *mut c_void, range: RawRange, message: RawStr) {
+    // SAFETY: the host copies the message before returning
+    unsafe { imports::report(range.start, range.end, message.ptr, message.len as u32) }
+}
@@ -0,0 +1,210 @@
+//! Python bindings for lintal.
+//!
+//! Built with `maturin build` into the `lintal` module:
+//!
+//! ```python
+//! import lintal
+//!
+//! config = "config/checkstyle/checkstyle.xml"
+//! for d in lintal.lint_path("src/main/java", config=config):
+//!     print(f"{d.path}:{d.line}:{d.column}: [{d.rule}] {d.message}")
+//!
+//! fixed = lintal.fix_source(source, config=config)
+//! ```
+//!
+//! Without `config`, the rules are those the CLI uses when it finds no
+//! checkstyle.xml. Lines and columns are reported as the CLI reports them.
+
+use std::path::{Path, PathBuf};
+
+use lintal_checkstyle::{CheckstyleConfig, MergedConfig};
+use lintal_diagnostics::Applicability;
+use lintal_linter::{Rule, RuleDiagnostic, RuleRegistry};
+use lintal_source_file::{Charset, LineIndex, PositionEncoding};
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use walkdir::WalkDir;
+
+create_exception!(
+    lintal,
+    LintalError,
+    PyException,
+    "Raised when the configuration can't be loaded or a source can't be read or parsed."
+);
+
+/// A violation found by lintal.
+#[pyclass(frozen, get_all, module = "lintal")]
+#[derive(Debug, Clone)]
+struct Diagnostic {
+    /// The file the violation is in, for diagnostics from `lint_path`.
+    path: Option<PathBuf>,
+    rule: String,
+    /// The rule's short code, e.g. `WS001`.
+    code: String,
+    message: String,
+    /// One-based line and column of the start of the violation.
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+    /// Whether `fix_source` can fix the violation.
+    fixable: bool,
+}
+
+#[pymethods]
+impl Diagnostic {
+    fn __repr__(&self) -> String {
+        format!(
+            "Diagnostic(rule={:?}, line={}, column={}, message={:?})",
+            self.rule, self.line, self.column, self.message
+        )
+    }
+}
+
+/// Rules and settings loaded from a configuration.
+struct Linter {
+    rules: Vec<Box<dyn Rule>>,
+    tab_width: usize,
+    charset: Charset,
+}
+
+impl Linter {
+    fn load(config: Option<&Path>) -> PyResult<Self> {
+        let Some(path) = config else {
+            return Ok(Self {
+                rules: vec![Box::new(lintal_linter::rules::WhitespaceAround::default())],
+                tab_width: MergedConfig::DEFAULT_TAB_WIDTH,
+                charset: Charset::default(),
+            });
+        };
+
+        let checkstyle = CheckstyleConfig::from_file(path)
+            .map_err(|e| LintalError::new_err(format!("{}: {e}", path.display())))?;
+        let config = MergedConfig::new(&checkstyle, None);
+        let rules = RuleRegistry::builtin()
+            .create_rules(&config)
+            .map_err(|e| LintalError::new_err(e.to_string()))?;
+        let charset = match config.charset.as_deref() {
+            None => Charset::default(),
+            Some(name) => Charset::from_name(name).ok_or_else(|| {
+                LintalError::new_err(format!(
+                    "Unsupported charset '{name}' in {}",
+                    path.display()
+                ))
+            })?,
+        };
+        Ok(Self {
+            rules,
+            tab_width: config.tab_width,
+            charset,
+        })
+    }
+
+    fn lint(&self, source: &str, path: Option<&Path>) -> PyResult<Vec<Diagnostic>> {
+        let diagnostics = match path {
+            Some(path) => lintal_linter::lint_source_at(source, path, &self.rules),
+            None => lintal_linter::lint_source(source, &self.rules),
+        }
+        .ok_or_else(|| parse_error(path))?;
+        let index = LineIndex::from_source_text(source);
+        Ok(diagnostics
+            .into_iter()
+            .map(|diagnostic| self.to_python(source, &index, path, diagnostic))
+            .collect())
+    }
+
+    fn to_python(
+        &self,
+        source: &str,
+        index: &LineIndex,
+        path: Option<&Path>,
+        rule_diagnostic: RuleDiagnostic,
+    ) -> Diagnostic {
+        let RuleDiagnostic { rule, diagnostic } = rule_diagnostic;
+        let encoding = self
+            .rules
+            .iter()
+            .find(|r| r.name() == rule)
+            .map_or(PositionEncoding::Utf32, |r| r.column_encoding());
+        let start =
+            index.checkstyle_column(diagnostic.range.start(), source, self.tab_width, encoding);
+        let end = index.checkstyle_column(diagnostic.range.end(), source, self.tab_width, encoding);
+        Diagnostic {
+            path: path.map(Path::to_path_buf),
+            rule: rule.to_string(),
+            code: diagnostic.kind.code,
+            message: diagnostic.kind.body,
+            line: start.line.get(),
+            column: start.column.get(),
+            end_line: end.line.get(),
+            end_column: end.column.get(),
+            fixable: diagnostic
+                .fix
+                .as_ref()
+                .is_some_and(|fix| fix.applies(Applicability::Safe)),
+        }
+    }
+}
+
+fn parse_error(path: Option<&Path>) -> PyErr {
+    match path {
+        Some(path) => LintalError::new_err(format!("{}: Failed to parse", path.display())),
+        None => LintalError::new_err("Failed to parse source"),
+    }
+}
+
+/// Lint Java source text.
+#[pyfunction]
+#[pyo3(signature = (source, config = None))]
+fn lint_source(source: &str, config: Option<PathBuf>) -> PyResult<Vec<Diagnostic>> {
+    Linter::load(config.as_deref())?.lint(source, None)
+}
+
+/// Lint a Java file, or every Java file under a directory.
+#[pyfunction]
+#[pyo3(signature = (path, config = None))]
+fn lint_path(path: PathBuf, config: Option<PathBuf>) -> PyResult<Vec<Diagnostic>> {
+    let linter = Linter::load(config.as_deref())?;
+    let mut diagnostics = Vec::new();
+    for entry in WalkDir::new(&path).sort_by_file_name() {
+        let entry = entry.map_err(|e| LintalError::new_err(e.to_string()))?;
+        let file = entry.path();
+        if !entry.file_type().is_file() || file.extension().is_none_or(|ext| ext != "java") {
+            continue;
+        }
+        let bytes = std::fs::read(file)
+            .map_err(|e| LintalError::new_err(format!("{}: {e}", file.display())))?;
+        let decoded = lintal_source_file::decode(&bytes, linter.charset)
+            .map_err(|e| LintalError::new_err(format!("{}: {e}", file.display())))?;
+        diagnostics.extend(linter.lint(&decoded.text, Some(file))?);
+    }
+    Ok(diagnostics)
+}
+
+/// Apply the configured rules' fixes to Java source text, returning the
+/// fixed source.
+#[pyfunction]
+#[pyo3(signature = (source, config = None, unsafe_fixes = false))]
+fn fix_source(source: &str, config: Option<PathBuf>, unsafe_fixes: bool) -> PyResult<String> {
+    let linter = Linter::load(config.as_deref())?;
+    let applicability = if unsafe_fixes {
+        Applicability::Unsafe
+    } else {
+        Applicability::Safe
+    };
+    lintal_linter::fix_source(source, &linter.rules, applicability)
+        .map(|fixed| fixed.code)
+        .ok_or_else(|| parse_error(None))
+}
+
+#[pymodule]
+#[pyo3(name = "lintal")]
+fn lintal_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("LintalError", m.py().get_type::<LintalError>())?;
+    m.add_class::<Diagnostic>()?;
+    m.add_function(wrap_pyfunction!(lint_source, m)?)?;
+    m.add_function(wrap_pyfunction!(lint_path, m)?)?;
+    m.add_function(wrap_pyfunction!(fix_source, m)?)?;
+    Ok(())
+}
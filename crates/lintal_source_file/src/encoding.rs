@@ -0,0 +1,410 @@
+//! Character encodings for reading and writing source files.
+//!
+//! Sources are always linted as UTF-8 text. Files stored in another charset
+//! (checkstyle's `charset` property) are decoded on read, with the byte order
+//! mark of a Unicode charset stripped, and re-encoded with the same charset and
+//! BOM on write.
+
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16_BE_BOM: &[u8] = &[0xFE, 0xFF];
+const UTF16_LE_BOM: &[u8] = &[0xFF, 0xFE];
+
+/// windows-1252 code points for bytes `0x80..=0x9F`. Bytes that are undefined
+/// in windows-1252 map to the C1 control of the same value, so they round-trip.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+/// A character encoding for Java source files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Charset {
+    /// UTF-8, with or without a byte order mark.
+    #[default]
+    Utf8,
+    /// UTF-16 with the byte order taken from the BOM (big-endian if absent).
+    Utf16,
+    /// UTF-16, big-endian.
+    Utf16Be,
+    /// UTF-16, little-endian.
+    Utf16Le,
+    /// ISO-8859-1 (Latin-1).
+    Latin1,
+    /// windows-1252 (the Windows superset of Latin-1).
+    Windows1252,
+    /// 7-bit US-ASCII.
+    Ascii,
+}
+
+impl Charset {
+    /// Look up a charset by its Java/IANA name or a common alias (case-insensitive).
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalized = name.trim().to_ascii_lowercase().replace('_', "-");
+        let charset = match normalized.as_str() {
+            "utf-8" | "utf8" => Self::Utf8,
+            "utf-16" | "utf16" => Self::Utf16,
+            "utf-16be" | "unicodebigunmarked" => Self::Utf16Be,
+            "utf-16le" | "unicodelittleunmarked" => Self::Utf16Le,
+            "iso-8859-1" | "iso8859-1" | "iso-latin-1" | "latin1" | "l1" | "cp819" => Self::Latin1,
+            "windows-1252" | "cp1252" => Self::Windows1252,
+            "us-ascii" | "ascii" | "iso646-us" => Self::Ascii,
+            _ => return None,
+        };
+        Some(charset)
+    }
+
+    /// The canonical (Java) name of this charset.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf16 => "UTF-16",
+            Self::Utf16Be => "UTF-16BE",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Latin1 => "ISO-8859-1",
+            Self::Windows1252 => "windows-1252",
+            Self::Ascii => "US-ASCII",
+        }
+    }
+}
+
+impl Display for Charset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error decoding a byte buffer in a given charset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    /// The charset that was used for decoding.
+    pub charset: Charset,
+    /// Byte offset of the first invalid sequence.
+    pub offset: usize,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid {} byte sequence at offset {}",
+            self.charset, self.offset
+        )
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Error encoding text in a given charset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodeError {
+    /// The charset that was used for encoding.
+    pub charset: Charset,
+    /// The character that cannot be represented.
+    pub character: char,
+}
+
+impl Display for EncodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "character {:?} (U+{:04X}) cannot be encoded as {}",
+            self.character, self.character as u32, self.charset
+        )
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+/// Source text decoded from a file, along with what is needed to write it back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedSource {
+    /// The decoded text, without any byte order mark. Shared, so the parser,
+    /// the rules and the diagnostics of a file all use the one copy.
+    pub text: Arc<str>,
+    /// The charset the bytes were decoded with. For [`Charset::Utf16`] this is
+    /// resolved to the concrete byte order.
+    pub charset: Charset,
+    /// Whether the original bytes started with a byte order mark.
+    pub bom: bool,
+}
+
+impl DecodedSource {
+    /// Encode `text` with the same charset and BOM as the original file.
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>, EncodeError> {
+        let mut bytes = Vec::with_capacity(text.len() + 3);
+        if self.bom {
+            bytes.extend_from_slice(match self.charset {
+                Charset::Utf16Le => UTF16_LE_BOM,
+                Charset::Utf16 | Charset::Utf16Be => UTF16_BE_BOM,
+                _ => UTF8_BOM,
+            });
+        }
+        encode_into(text, self.charset, &mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+/// Decode `bytes` in the given charset, stripping a leading byte order mark
+/// of UTF-8 or UTF-16. Like Java's decoders, the single-byte charsets keep the
+/// bytes of a UTF-8 BOM as characters.
+pub fn decode(bytes: &[u8], charset: Charset) -> Result<DecodedSource, DecodeError> {
+    match charset {
+        Charset::Utf8 | Charset::Ascii | Charset::Latin1 | Charset::Windows1252 => {
+            let (bom, body) = match bytes.strip_prefix(UTF8_BOM) {
+                Some(body) if charset == Charset::Utf8 => (true, body),
+                _ => (false, bytes),
+            };
+            let offset = bytes.len() - body.len();
+            let text = decode_single_byte(body, charset).map_err(|error| DecodeError {
+                offset: error.offset + offset,
+                ..error
+            })?;
+            Ok(DecodedSource { text, charset, bom })
+        }
+        Charset::Utf16 | Charset::Utf16Be | Charset::Utf16Le => {
+            let (bom, resolved) = if bytes.starts_with(UTF16_BE_BOM) {
+                (true, Charset::Utf16Be)
+            } else if bytes.starts_with(UTF16_LE_BOM) {
+                (true, Charset::Utf16Le)
+            } else if charset == Charset::Utf16 {
+                (false, Charset::Utf16Be)
+            } else {
+                (false, charset)
+            };
+            // An explicit byte order wins over a contradicting BOM
+            let resolved = if charset == Charset::Utf16 {
+                resolved
+            } else {
+                charset
+            };
+            let body = if bom { &bytes[2..] } else { bytes };
+            let text = decode_utf16(body, resolved).map_err(|error| DecodeError {
+                offset: error.offset + if bom { 2 } else { 0 },
+                ..error
+            })?;
+            Ok(DecodedSource {
+                text: text.into(),
+                charset: resolved,
+                bom,
+            })
+        }
+    }
+}
+
+/// Encode `text` in the given charset, without a byte order mark.
+pub fn encode(text: &str, charset: Charset) -> Result<Vec<u8>, EncodeError> {
+    let mut bytes = Vec::with_capacity(text.len());
+    encode_into(text, charset, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// UTF-8 and ASCII are copied straight from `bytes` into the shared text.
+fn decode_single_byte(bytes: &[u8], charset: Charset) -> Result<Arc<str>, DecodeError> {
+    match charset {
+        Charset::Utf8 => std::str::from_utf8(bytes)
+            .map(Arc::from)
+            .map_err(|error| DecodeError {
+                charset,
+                offset: error.valid_up_to(),
+            }),
+        Charset::Ascii => match bytes.iter().position(|byte| !byte.is_ascii()) {
+            Some(offset) => Err(DecodeError { charset, offset }),
+            None => Ok(Arc::from(
+                std::str::from_utf8(bytes).expect("ASCII is valid UTF-8"),
+            )),
+        },
+        Charset::Latin1 => Ok(bytes
+            .iter()
+            .map(|&byte| char::from(byte))
+            .collect::<String>()
+            .into()),
+        Charset::Windows1252 => Ok(bytes
+            .iter()
+            .map(|&byte| match byte {
+                0x80..=0x9F => WINDOWS_1252_HIGH[usize::from(byte - 0x80)],
+                _ => char::from(byte),
+            })
+            .collect::<String>()
+            .into()),
+        Charset::Utf16 | Charset::Utf16Be | Charset::Utf16Le => {
+            unreachable!("UTF-16 is not a single-byte charset")
+        }
+    }
+}
+
+fn decode_utf16(bytes: &[u8], charset: Charset) -> Result<String, DecodeError> {
+    if !bytes.len().is_multiple_of(2) {
+        return Err(DecodeError {
+            charset,
+            offset: bytes.len() - 1,
+        });
+    }
+
+    let units = bytes.chunks_exact(2).map(|pair| match charset {
+        Charset::Utf16Le => u16::from_le_bytes([pair[0], pair[1]]),
+        _ => u16::from_be_bytes([pair[0], pair[1]]),
+    });
+
+    let mut text = String::with_capacity(bytes.len() / 2);
+    let mut offset = 0;
+    for unit in char::decode_utf16(units) {
+        let Ok(character) = unit else {
+            return Err(DecodeError { charset, offset });
+        };
+        text.push(character);
+        offset += character.len_utf16() * 2;
+    }
+    Ok(text)
+}
+
+fn encode_into(text: &str, charset: Charset, bytes: &mut Vec<u8>) -> Result<(), EncodeError> {
+    match charset {
+        Charset::Utf8 => bytes.extend_from_slice(text.as_bytes()),
+        Charset::Utf16 | Charset::Utf16Be => {
+            for unit in text.encode_utf16() {
+                bytes.extend_from_slice(&unit.to_be_bytes());
+            }
+        }
+        Charset::Utf16Le => {
+            for unit in text.encode_utf16() {
+                bytes.extend_from_slice(&unit.to_le_bytes());
+            }
+        }
+        Charset::Ascii | Charset::Latin1 | Charset::Windows1252 => {
+            for character in text.chars() {
+                let byte = encode_single_byte(character, charset)
+                    .ok_or(EncodeError { charset, character })?;
+                bytes.push(byte);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn encode_single_byte(character: char, charset: Charset) -> Option<u8> {
+    let code = u32::from(character);
+    match charset {
+        Charset::Ascii if code < 0x80 => u8::try_from(code).ok(),
+        Charset::Latin1 => u8::try_from(code).ok(),
+        Charset::Windows1252 if code < 0x80 || (0xA0..=0xFF).contains(&code) => {
+            u8::try_from(code).ok()
+        }
+        Charset::Windows1252 => WINDOWS_1252_HIGH
+            .iter()
+            .position(|&mapped| mapped == character)
+            .and_then(|index| u8::try_from(index + 0x80).ok()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn charset_from_name() {
+        assert_eq!(Charset::from_name("UTF-8"), Some(Charset::Utf8));
+        assert_eq!(Charset::from_name("utf8"), Some(Charset::Utf8));
+        assert_eq!(Charset::from_name("ISO-8859-1"), Some(Charset::Latin1));
+        assert_eq!(Charset::from_name("ISO8859_1"), Some(Charset::Latin1));
+        assert_eq!(Charset::from_name("Cp1252"), Some(Charset::Windows1252));
+        assert_eq!(Charset::from_name("UTF-16LE"), Some(Charset::Utf16Le));
+        assert_eq!(Charset::from_name("EBCDIC"), None);
+    }
+
+    #[test]
+    fn decode_utf8_strips_bom() {
+        let decoded = decode(b"\xEF\xBB\xBFclass A {}", Charset::Utf8).unwrap();
+        assert_eq!(&*decoded.text, "class A {}");
+        assert!(decoded.bom);
+        assert_eq!(
+            decoded.encode(&decoded.text).unwrap(),
+            b"\xEF\xBB\xBFclass A {}"
+        );
+    }
+
+    #[test]
+    fn decode_single_byte_keeps_utf8_bom() {
+        let bytes = b"\xEF\xBB\xBFclass A {}";
+        let decoded = decode(bytes, Charset::Latin1).unwrap();
+        assert_eq!(&*decoded.text, "\u{EF}\u{BB}\u{BF}class A {}");
+        assert!(!decoded.bom);
+        assert_eq!(decoded.encode(&decoded.text).unwrap(), bytes);
+
+        let decoded = decode(bytes, Charset::Windows1252).unwrap();
+        assert_eq!(&*decoded.text, "\u{EF}\u{BB}\u{BF}class A {}");
+        assert_eq!(decoded.encode(&decoded.text).unwrap(), bytes);
+
+        let error = decode(bytes, Charset::Ascii).unwrap_err();
+        assert_eq!(error.offset, 0);
+    }
+
+    #[test]
+    fn decode_invalid_utf8_reports_offset() {
+        let error = decode(b"class \xE9 {}", Charset::Utf8).unwrap_err();
+        assert_eq!(error.offset, 6);
+        assert_eq!(error.charset, Charset::Utf8);
+    }
+
+    #[test]
+    fn decode_latin1_roundtrip() {
+        let bytes = b"// caf\xE9\nclass A {}";
+        let decoded = decode(bytes, Charset::Latin1).unwrap();
+        assert_eq!(&*decoded.text, "// café\nclass A {}");
+        assert_eq!(decoded.encode(&decoded.text).unwrap(), bytes);
+    }
+
+    #[test]
+    fn decode_windows_1252_high_range() {
+        let bytes = b"// \x93quoted\x94 \x80 \x81";
+        let decoded = decode(bytes, Charset::Windows1252).unwrap();
+        assert_eq!(
+            &*decoded.text,
+            "// \u{201C}quoted\u{201D} \u{20AC} \u{0081}"
+        );
+        assert_eq!(decoded.encode(&decoded.text).unwrap(), bytes);
+    }
+
+    #[test]
+    fn encode_unrepresentable_character_fails() {
+        let error = encode("snow \u{2603}", Charset::Latin1).unwrap_err();
+        assert_eq!(error.character, '\u{2603}');
+        assert!(encode("é", Charset::Ascii).is_err());
+    }
+
+    #[test]
+    fn decode_utf16_detects_byte_order_from_bom() {
+        let mut bytes = vec![0xFF, 0xFE];
+        for unit in "int x;".encode_utf16() {
+            bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+        let decoded = decode(&bytes, Charset::Utf16).unwrap();
+        assert_eq!(&*decoded.text, "int x;");
+        assert_eq!(decoded.charset, Charset::Utf16Le);
+        assert!(decoded.bom);
+        assert_eq!(
+            decoded.encode("int y;").unwrap()[..4],
+            [0xFF, 0xFE, b'i', 0]
+        );
+    }
+
+    #[test]
+    fn decode_utf16_defaults_to_big_endian() {
+        let decoded = decode(&[0, b'a', 0, b'b'], Charset::Utf16).unwrap();
+        assert_eq!(&*decoded.text, "ab");
+        assert_eq!(decoded.charset, Charset::Utf16Be);
+        assert!(!decoded.bom);
+    }
+
+    #[test]
+    fn decode_utf16_rejects_unpaired_surrogate() {
+        let error = decode(&[0, b'a', 0xD8, 0x00], Charset::Utf16Be).unwrap_err();
+        assert_eq!(error.offset, 2);
+        assert!(decode(&[0, b'a', 0], Charset::Utf16Be).is_err());
+    }
+}
@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 
 use lintal_text_size::{Ranged, TextRange, TextSize};
 
+pub use crate::encoding::{Charset, DecodeError, DecodedSource, EncodeError, decode, encode};
 pub use crate::line_index::{LineIndex, OneIndexed, PositionEncoding};
 pub use crate::line_ranges::LineRanges;
 pub use crate::newlines::{
@@ -20,6 +21,7 @@ pub use crate::newlines::{
     find_newline,
 };
 
+mod encoding;
 mod line_index;
 mod line_ranges;
 mod newlines;
@@ -55,6 +57,20 @@ impl<'src, 'index> SourceCode<'src, 'index> {
             .source_location(offset, self.text, position_encoding)
     }
 
+    /// Computes the line and column for `offset` the way checkstyle reports them.
+    ///
+    /// See [`LineIndex::checkstyle_column`].
+    #[inline]
+    pub fn checkstyle_column(
+        &self,
+        offset: TextSize,
+        tab_width: usize,
+        encoding: PositionEncoding,
+    ) -> LineColumn {
+        self.index
+            .checkstyle_column(offset, self.text, tab_width, encoding)
+    }
+
     #[inline]
     pub fn line_index(&self, offset: TextSize) -> OneIndexed {
         self.index.line_index(offset)
@@ -215,6 +215,66 @@ impl LineIndex {
         }
     }
 
+    /// Given a UTF-8 byte offset, returns the line and column as checkstyle reports them.
+    ///
+    /// Tabs expand to the next multiple of `tab_width`, and every other character counts
+    /// as its length in `encoding`. Checkstyle's `TreeWalker` checks count code points
+    /// ([`PositionEncoding::Utf32`]) while checks that match on raw line text count UTF-16
+    /// code units ([`PositionEncoding::Utf16`]), so an emoji outside the BMP is one column or
+    /// two. Neither groups combining sequences: a letter with a combining accent, or an
+    /// emoji with a skin tone modifier, counts as each of its code points. Like
+    /// [`Self::line_column`], a BOM at the start of the file is not counted.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use lintal_text_size::TextSize;
+    /// # use lintal_source_file::{LineIndex, OneIndexed, LineColumn, PositionEncoding};
+    /// let source = "\tint x;";
+    /// let index = LineIndex::from_source_text(source);
+    ///
+    /// assert_eq!(
+    ///     index.checkstyle_column(TextSize::from(5), source, 8, PositionEncoding::Utf32),
+    ///     LineColumn { line: OneIndexed::from_zero_indexed(0), column: OneIndexed::from_zero_indexed(12) }
+    /// );
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// If the byte offset isn't within the bounds of `content`.
+    pub fn checkstyle_column(
+        &self,
+        offset: TextSize,
+        content: &str,
+        tab_width: usize,
+        encoding: PositionEncoding,
+    ) -> LineColumn {
+        let line = self.line_index(offset);
+        let line_start = self.line_start(line, content);
+        let mut before = &content[TextRange::new(line_start, offset)];
+        if line.to_zero_indexed() == 0 {
+            before = before.strip_prefix('\u{feff}').unwrap_or(before);
+        }
+
+        let tab_width = tab_width.max(1);
+        let column = before.chars().fold(0, |column, c| match c {
+            '\t' => (column / tab_width + 1) * tab_width,
+            _ => {
+                column
+                    + match encoding {
+                        PositionEncoding::Utf8 => c.len_utf8(),
+                        PositionEncoding::Utf16 => c.len_utf16(),
+                        PositionEncoding::Utf32 => 1,
+                    }
+            }
+        });
+
+        LineColumn {
+            line,
+            column: OneIndexed::from_zero_indexed(column),
+        }
+    }
+
     /// Return the number of lines in the source code.
     pub fn line_count(&self) -> usize {
         self.line_starts().len()
@@ -691,7 +751,7 @@ mod tests {
     use lintal_text_size::TextSize;
 
     use crate::line_index::LineIndex;
-    use crate::{LineColumn, OneIndexed};
+    use crate::{LineColumn, OneIndexed, PositionEncoding};
 
     #[test]
     fn ascii_index() {
@@ -967,4 +1027,51 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn checkstyle_column_expands_tabs() {
+        let contents = "a\tb\n\t\tc";
+        let index = LineIndex::from_source_text(contents);
+
+        // `b` follows a tab that expands from column 1 to the next stop at 8
+        let loc = index.checkstyle_column(TextSize::from(2), contents, 8, PositionEncoding::Utf32);
+        assert_eq!(loc.column, OneIndexed::from_zero_indexed(8));
+
+        let loc = index.checkstyle_column(TextSize::from(6), contents, 4, PositionEncoding::Utf32);
+        assert_eq!(loc.line, OneIndexed::from_zero_indexed(1));
+        assert_eq!(loc.column, OneIndexed::from_zero_indexed(8));
+    }
+
+    #[test]
+    fn checkstyle_column_counts_supplementary_characters() {
+        let contents = "\u{feff}s = \"\u{1F600}\"; x";
+        let index = LineIndex::from_source_text(contents);
+        let x = TextSize::try_from(contents.find('x').unwrap()).unwrap();
+
+        let code_points = index.checkstyle_column(x, contents, 8, PositionEncoding::Utf32);
+        assert_eq!(code_points.column, OneIndexed::from_zero_indexed(9));
+
+        let code_units = index.checkstyle_column(x, contents, 8, PositionEncoding::Utf16);
+        assert_eq!(code_units.column, OneIndexed::from_zero_indexed(10));
+    }
+
+    #[test]
+    fn checkstyle_column_counts_combining_sequences_per_code_point() {
+        // `e` + combining acute accent, then a waving hand with a skin tone modifier
+        let contents = "\t\"e\u{301}\" + \"\u{1F44B}\u{1F3FD}\" + x";
+        let index = LineIndex::from_source_text(contents);
+        let x = TextSize::try_from(contents.find('x').unwrap()).unwrap();
+
+        let code_points = index.checkstyle_column(x, contents, 4, PositionEncoding::Utf32);
+        assert_eq!(
+            code_points.column,
+            OneIndexed::from_zero_indexed(4 + 4 + 3 + 4 + 3)
+        );
+
+        let code_units = index.checkstyle_column(x, contents, 4, PositionEncoding::Utf16);
+        assert_eq!(
+            code_units.column,
+            OneIndexed::from_zero_indexed(4 + 4 + 3 + 6 + 3)
+        );
+    }
 }
@@ -1,8 +1,7 @@
-//! Test harness for checkstyle compatibility testing.
-//!
-//! Provides structured comparison of expected vs actual violations with
-//! detailed reporting of exact matches, missing items (false negatives),
-//! and false positives.
+//! Comparison of expected and actual violations, with detailed reporting of
+//! exact matches, missing items (false negatives), and false positives.
+
+use std::fmt::Debug;
 
 /// Result of comparing expected vs actual violations.
 #[derive(Debug, Clone)]
@@ -120,7 +119,6 @@ impl TestResult {
     }
 
     /// Assert detection rate meets minimum threshold.
-    #[allow(dead_code)]
     pub fn assert_detection_rate(&self, min_percent: f64) {
         let rate = self.detection_rate();
         assert!(
@@ -138,6 +136,50 @@ impl TestResult {
     }
 }
 
+/// The entries of `expected` that `actual` lacks, and the entries of `actual`
+/// that weren't expected, in their original order.
+///
+/// For tests that pin what a rule reports to a hand-written list, such as
+/// the `line:column` positions checkstyle's own tests assert, rather than to
+/// a fixture's `// violation` comments.
+pub fn diff_violations<T: PartialEq + Clone>(actual: &[T], expected: &[T]) -> (Vec<T>, Vec<T>) {
+    let missing = expected
+        .iter()
+        .filter(|violation| !actual.contains(violation))
+        .cloned()
+        .collect();
+    let unexpected = actual
+        .iter()
+        .filter(|violation| !expected.contains(violation))
+        .cloned()
+        .collect();
+    (missing, unexpected)
+}
+
+/// Assert that `actual` has the entries of `expected` and no others, in any
+/// order, printing what's missing and what's unexpected if not.
+pub fn assert_violations<T: PartialEq + Clone + Debug>(actual: &[T], expected: &[T]) {
+    let (missing, unexpected) = diff_violations(actual, expected);
+    if missing.is_empty() && unexpected.is_empty() {
+        return;
+    }
+
+    println!("\n=== Violations Report ===");
+    if !missing.is_empty() {
+        println!("\nMissing violations:");
+        for violation in &missing {
+            println!("  {violation:?}");
+        }
+    }
+    if !unexpected.is_empty() {
+        println!("\nUnexpected violations:");
+        for violation in &unexpected {
+            println!("  {violation:?}");
+        }
+    }
+    panic!("Violation mismatch detected");
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -183,4 +225,12 @@ mod tests {
         assert_eq!(result.detection_rate(), 100.0);
         assert_eq!(result.false_positives, vec![1, 2]);
     }
+
+    #[test]
+    fn test_diff_violations() {
+        let (missing, unexpected) = diff_violations(&[(3, 5), (4, 9)], &[(4, 9), (7, 1)]);
+        assert_eq!(missing, vec![(7, 1)]);
+        assert_eq!(unexpected, vec![(3, 5)]);
+        assert_violations(&[(3, 5), (4, 9)], &[(4, 9), (3, 5)]);
+    }
 }
@@ -0,0 +1,173 @@
+//! Expected violations, from the comments of checkstyle's test inputs.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// A violation a fixture's comments expect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedViolation {
+    /// One-based line.
+    pub line: usize,
+    /// The quoted message, e.g. `Name 'foo' must match pattern` from
+    /// `// violation 'Name 'foo' must match pattern'`. Checkstyle's inputs
+    /// often abbreviate it.
+    pub message: Option<String>,
+}
+
+static N_LINES_ABOVE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"//\s*violation\s+(\d+)\s+lines?\s+above").unwrap());
+static N_VIOLATIONS_ABOVE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"//\s*(\d+)\s+violations?\s+(?:(\d+)\s+lines?\s+)?above").unwrap()
+});
+static N_VIOLATIONS_BELOW: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"//\s*(\d+)\s+violations?\s+(?:(\d+)\s+lines?\s+)?below").unwrap()
+});
+static N_VIOLATIONS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"//\s*(\d+)\s+violations?\b").unwrap());
+static ABOVE_THIS_LINE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"//\s*violation\s+above\s+this\s+line").unwrap());
+static ABOVE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"//\s*violation\s+above").unwrap());
+static BELOW: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"//\s*violation\s+below").unwrap());
+static INLINE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"//\s*violation\b").unwrap());
+
+/// Parse the violations a checkstyle test input expects from its comments:
+///
+/// - `// violation 'message'` on the line itself, as is
+///   `// violation above this line 'message'`
+/// - `// violation above 'message'` and `// violation below 'message'`
+/// - `// violation 3 lines above 'message'`
+/// - `// 2 violations`, `// 2 violations above` and `// 2 violations below`,
+///   with the messages on the following comment lines, and
+///   `// 2 violations 3 lines below`
+///
+/// A message may be in double quotes instead, `// violation "message"`.
+pub fn parse_expected_violations(source: &str) -> Vec<ExpectedViolation> {
+    let mut violations = vec![];
+
+    for (i, line) in source.lines().enumerate() {
+        let line_num = i + 1;
+        let number = |caps: &regex::Captures<'_>, group: usize| {
+            caps.get(group)
+                .and_then(|m| m.as_str().parse::<usize>().ok())
+        };
+        let count = |caps: &regex::Captures<'_>| number(caps, 1);
+        let offset = |caps: &regex::Captures<'_>| number(caps, 2).unwrap_or(1);
+        // Several violations of one line list their messages on the lines
+        // after, so only a single one is taken from the comment itself
+        let mut push = |at: usize, n: usize| {
+            let message = if n == 1 { quoted_message(line) } else { None };
+            for _ in 0..n {
+                violations.push(ExpectedViolation {
+                    line: at,
+                    message: message.clone(),
+                });
+            }
+        };
+
+        if let Some(caps) = N_LINES_ABOVE.captures(line) {
+            if let Some(n) = count(&caps)
+                && line_num > n
+            {
+                push(line_num - n, 1);
+            }
+        } else if let Some(caps) = N_VIOLATIONS_ABOVE.captures(line) {
+            if let Some(n) = count(&caps)
+                && line_num > offset(&caps)
+            {
+                push(line_num - offset(&caps), n);
+            }
+        } else if let Some(caps) = N_VIOLATIONS_BELOW.captures(line) {
+            if let Some(n) = count(&caps) {
+                push(line_num + offset(&caps), n);
+            }
+        } else if ABOVE_THIS_LINE.is_match(line) {
+            push(line_num, 1);
+        } else if ABOVE.is_match(line) {
+            if line_num > 1 {
+                push(line_num - 1, 1);
+            }
+        } else if BELOW.is_match(line) {
+            push(line_num + 1, 1);
+        } else if let Some(caps) = N_VIOLATIONS.captures(line) {
+            if !line.contains("above")
+                && !line.contains("below")
+                && let Some(n) = count(&caps)
+            {
+                push(line_num, n);
+            }
+        } else if INLINE.is_match(line) {
+            push(line_num, 1);
+        }
+    }
+
+    violations
+}
+
+/// The text between the first and last quote of the comment on `line`,
+/// single or double as the first one is.
+fn quoted_message(line: &str) -> Option<String> {
+    let comment = &line[line.find("//")?..];
+    let start = comment.find(['\'', '"'])?;
+    let end = comment.rfind(&comment[start..=start])?;
+    (start < end).then(|| comment[start + 1..end].to_string())
+}
+
+/// The lines of [`parse_expected_violations`], once per expected violation.
+pub fn expected_violation_lines(source: &str) -> Vec<usize> {
+    parse_expected_violations(source)
+        .into_iter()
+        .map(|violation| violation.line)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_expected_violations() {
+        let source = "\
+class Input {
+    int a; // violation 'Name 'a' must match pattern'
+    // violation below 'Missing a Javadoc comment.'
+    int b;
+    int c; // 2 violations
+    int d;
+    // violation 2 lines above 'Too many.'
+    int e; // no violation expected
+}
+";
+        let expected = parse_expected_violations(source);
+        let lines: Vec<usize> = expected.iter().map(|violation| violation.line).collect();
+        assert_eq!(lines, [2, 4, 5, 5, 5]);
+        assert_eq!(
+            expected[0].message.as_deref(),
+            Some("Name 'a' must match pattern")
+        );
+        assert_eq!(expected[2].message, None);
+        assert_eq!(expected[4].message.as_deref(), Some("Too many."));
+        assert_eq!(expected_violation_lines(source), lines);
+    }
+
+    #[test]
+    fn test_parse_expected_violations_other_forms() {
+        let source = r#"class Input {
+    // 2 violations 3 lines below:
+    //   'Name 'a' must match pattern'
+    //   'Name 'b' must match pattern'
+    int a, b;
+
+    int c; // violation above this line ''VARIABLE_DEF' has more than 1 empty lines before.'
+    int d; // violation "'VARIABLE_DEF' should be separated from previous line."
+}
+"#;
+        let expected = parse_expected_violations(source);
+        let lines: Vec<usize> = expected.iter().map(|violation| violation.line).collect();
+        assert_eq!(lines, [5, 5, 7, 8]);
+        assert_eq!(
+            expected[3].message.as_deref(),
+            Some("'VARIABLE_DEF' should be separated from previous line.")
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Check configuration, from the header comment of checkstyle's test inputs.
+
+use lintal_linter::Properties;
+
+/// The configuration a checkstyle test input declares in its first block
+/// comment:
+///
+/// ```java
+/// /*
+/// ConstantName
+/// format = ^[A-Z][A-Z0-9]*(_[A-Z0-9]+)*$
+/// applyToPublic = (default)true
+/// */
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FixtureConfig {
+    /// The module the header names, e.g. `ConstantName`.
+    pub module: Option<String>,
+    /// Properties in the order given, without those marked `(default)`, so
+    /// a rule falls back to its own default rather than checkstyle's
+    /// spelling of it.
+    pub properties: Vec<(String, String)>,
+}
+
+impl FixtureConfig {
+    /// Parse the header of `source`. Lines ending in `\` continue on the
+    /// next line. Empty if there is no block comment.
+    pub fn parse(source: &str) -> Self {
+        let mut config = Self::default();
+        let Some(start) = source.find("/*") else {
+            return config;
+        };
+        let Some(end) = source[start..].find("*/") else {
+            return config;
+        };
+        let block = &source[start + 2..start + end];
+
+        let mut joined = String::new();
+        for line in block.lines() {
+            let trimmed = line.trim();
+            if let Some(continued) = trimmed.strip_suffix('\\') {
+                joined.push_str(continued);
+            } else {
+                joined.push_str(trimmed);
+                joined.push('\n');
+            }
+        }
+
+        for line in joined.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("Config:") || line.starts_with('*') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                config.module.get_or_insert_with(|| line.to_string());
+                continue;
+            };
+            let value = value.trim();
+            if value.starts_with("(default)") {
+                continue;
+            }
+            config
+                .properties
+                .push((key.trim().to_string(), value.to_string()));
+        }
+
+        config
+    }
+
+    /// The value of property `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The properties, for [`FromConfig::from_config`] or
+    /// [`RuleRegistry::create_rule`].
+    ///
+    /// [`FromConfig::from_config`]: lintal_linter::FromConfig::from_config
+    /// [`RuleRegistry::create_rule`]: lintal_linter::RuleRegistry::create_rule
+    pub fn properties(&self) -> Properties<'_> {
+        self.properties
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fixture_config() {
+        let source = r"/*
+IllegalType
+validateAbstractClassNames = (default)false
+illegalClassNames = HashMap, \
+                    TreeSet
+format = ^[A-Z]$
+
+*/
+package test;
+";
+        let config = FixtureConfig::parse(source);
+        assert_eq!(config.module.as_deref(), Some("IllegalType"));
+        assert_eq!(config.get("validateAbstractClassNames"), None);
+        assert_eq!(config.get("illegalClassNames"), Some("HashMap, TreeSet"));
+        assert_eq!(config.properties().get("format"), Some(&"^[A-Z]$"));
+        assert_eq!(FixtureConfig::parse("class A {}"), FixtureConfig::default());
+    }
+}
@@ -0,0 +1,162 @@
+//! Checkstyle-style fixture tests for lintal rules.
+//!
+//! Checkstyle's test inputs are Java files that carry their own expectations:
+//! a header comment configures the check, and `// violation` comments mark
+//! the lines it should report. This crate reads both and compares them with
+//! what a rule reports, so built-in rules, out-of-tree rules and plugin rules
+//! can be tested against the same kind of fixture:
+//!
+//! ```ignore
+//! use lintal_testkit::{FixtureConfig, verify_fixture};
+//!
+//! let source = std::fs::read_to_string("InputNoSystemExit.java").unwrap();
+//! let config = FixtureConfig::parse(&source);
+//! let rule = NoSystemExit::from_config(&config.properties());
+//! let result = verify_fixture(&[Box::new(rule)], &source);
+//! result.print_report("InputNoSystemExit.java");
+//! assert!(result.is_perfect());
+//! ```
+//!
+//! A plugin's rules are created through a
+//! [`RuleRegistry`](lintal_linter::RuleRegistry) the plugin was registered
+//! with, as `lintal check` does.
+//!
+//! Tests that pin a rule to the `line:column` positions checkstyle's own
+//! tests list, rather than to a fixture's comments, compare with
+//! [`assert_violations`].
+//!
+//! For regression tests of what a rule reports and how it fixes a file,
+//! [`assert_file_snapshot`] compares both with a `.snap` file checked in next
+//! to the input; set `LINTAL_UPDATE_SNAPSHOTS=1` to write it.
+
+mod compare;
+mod expected;
+mod header;
+mod snapshot;
+
+use std::path::Path;
+
+use lintal_linter::{Rule, RuleDiagnostic, lint_source, lint_source_at};
+use lintal_source_file::{LineIndex, PositionEncoding, SourceCode};
+
+pub use compare::{TestResult, assert_violations, diff_violations};
+pub use expected::{ExpectedViolation, expected_violation_lines, parse_expected_violations};
+pub use header::FixtureConfig;
+pub use snapshot::{
+    UPDATE_SNAPSHOTS_ENV, assert_file_snapshot, assert_snapshot, render_snapshot, snapshot_path,
+};
+
+/// Checkstyle's default `tabWidth`, which the columns in its test inputs are
+/// counted with.
+pub const CHECKSTYLE_TAB_WIDTH: usize = 8;
+
+/// A violation a rule reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Module name of the rule.
+    pub rule: &'static str,
+    /// One-based line.
+    pub line: usize,
+    /// One-based column, counted the way checkstyle counts it.
+    pub column: usize,
+    pub message: String,
+}
+
+/// Run `rules` over `source` the way `lintal check` does, including
+/// `@SuppressWarnings`, and return what they report in source order.
+///
+/// # Panics
+///
+/// If `source` can't be parsed.
+pub fn check_source(rules: &[Box<dyn Rule>], source: &str) -> Vec<Violation> {
+    let diagnostics = lint_source(source, rules).expect("fixture should parse");
+    violations(rules, source, diagnostics)
+}
+
+/// Like [`check_source`], for `source` read from `path`, so rules that look
+/// at the file's path, such as OuterTypeFilename, see it.
+///
+/// # Panics
+///
+/// If `source` can't be parsed.
+pub fn check_source_at(rules: &[Box<dyn Rule>], source: &str, path: &Path) -> Vec<Violation> {
+    let diagnostics = lint_source_at(source, path, rules).expect("fixture should parse");
+    violations(rules, source, diagnostics)
+}
+
+/// What `rules` reported, with lines and columns counted as checkstyle does.
+fn violations(
+    rules: &[Box<dyn Rule>],
+    source: &str,
+    diagnostics: Vec<RuleDiagnostic>,
+) -> Vec<Violation> {
+    let line_index = LineIndex::from_source_text(source);
+    let source_code = SourceCode::new(source, &line_index);
+
+    diagnostics
+        .into_iter()
+        .map(|found| {
+            let encoding = rules
+                .iter()
+                .find(|rule| rule.name() == found.rule)
+                .map_or(PositionEncoding::Utf32, |rule| rule.column_encoding());
+            let location = source_code.checkstyle_column(
+                found.diagnostic.range.start(),
+                CHECKSTYLE_TAB_WIDTH,
+                encoding,
+            );
+            Violation {
+                rule: found.rule,
+                line: location.line.get(),
+                column: location.column.get(),
+                message: found.diagnostic.kind.body,
+            }
+        })
+        .collect()
+}
+
+/// Compare the lines `actual` reports with those `source`'s `// violation`
+/// comments expect.
+pub fn verify_violations(source: &str, actual: &[Violation]) -> TestResult {
+    TestResult::compare(
+        expected_violation_lines(source),
+        actual.iter().map(|violation| violation.line).collect(),
+    )
+}
+
+/// Run `rules` over the fixture `source` and compare what they report with
+/// what its comments expect.
+pub fn verify_fixture(rules: &[Box<dyn Rule>], source: &str) -> TestResult {
+    verify_violations(source, &check_source(rules, source))
+}
+
+#[cfg(test)]
+mod tests {
+    use lintal_linter::rules::UpperEll;
+
+    use super::*;
+
+    #[test]
+    fn test_verify_fixture() {
+        let source = "\
+/*
+UpperEll
+
+*/
+class Input {
+\tlong a = 1l; // violation 'Should use uppercase 'L'.'
+    long b = 1L;
+    // violation below 'Should use uppercase 'L'.'
+    long c = 2l;
+}
+";
+        let rules: Vec<Box<dyn Rule>> = vec![Box::new(UpperEll::default())];
+        let actual = check_source(&rules, source);
+        assert_eq!(actual.len(), 2);
+        assert_eq!(actual[0].rule, "UpperEll");
+        assert_eq!((actual[0].line, actual[0].column), (6, 18));
+
+        let result = verify_fixture(&rules, source);
+        assert!(result.is_perfect(), "{result:?}");
+    }
+}
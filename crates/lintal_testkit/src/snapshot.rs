@@ -0,0 +1,184 @@
+//! Snapshots of what rules report on a file and what fixing it produces.
+//!
+//! A snapshot is plain text, checked in next to its input, so a change in a
+//! rule's output shows up as a diff in review. Set `LINTAL_UPDATE_SNAPSHOTS=1`
+//! to write the current output instead of comparing against it.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use lintal_diagnostics::Applicability;
+use lintal_linter::diff::{DiffLine, diff_lines};
+use lintal_linter::{Rule, fix_source, lint_source};
+use lintal_source_file::{LineIndex, PositionEncoding, SourceCode};
+
+use crate::CHECKSTYLE_TAB_WIDTH;
+
+/// Environment variable that turns comparing snapshots into writing them.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "LINTAL_UPDATE_SNAPSHOTS";
+
+/// Render what `rules` report on `source` and the source with every fix
+/// applied, unsafe ones included:
+///
+/// ```text
+/// # diagnostics
+/// 6:14 UpperEll (ST001): Should use uppercase 'L'. [safe fix]
+///
+/// # fixed (1 applied)
+/// class Input {
+///     long a = 1L;
+/// }
+/// ```
+///
+/// # Panics
+///
+/// If `source` can't be parsed.
+pub fn render_snapshot(rules: &[Box<dyn Rule>], source: &str) -> String {
+    let diagnostics = lint_source(source, rules).expect("snapshot input should parse");
+    let line_index = LineIndex::from_source_text(source);
+    let source_code = SourceCode::new(source, &line_index);
+
+    let mut out = String::from("# diagnostics\n");
+    if diagnostics.is_empty() {
+        out.push_str("none\n");
+    }
+    for found in &diagnostics {
+        let diagnostic = &found.diagnostic;
+        let encoding = rules
+            .iter()
+            .find(|rule| rule.name() == found.rule)
+            .map_or(PositionEncoding::Utf32, |rule| rule.column_encoding());
+        let location =
+            source_code.checkstyle_column(diagnostic.range.start(), CHECKSTYLE_TAB_WIDTH, encoding);
+        write!(out, "{}:{} {}", location.line, location.column, found.rule).unwrap();
+        if !diagnostic.kind.code.is_empty() {
+            write!(out, " ({})", diagnostic.kind.code).unwrap();
+        }
+        write!(out, ": {}", diagnostic.kind.body).unwrap();
+        if let Some(fix) = &diagnostic.fix {
+            let applicability = match fix.applicability() {
+                Applicability::Safe => "safe",
+                Applicability::Unsafe => "unsafe",
+                Applicability::DisplayOnly => "display-only",
+            };
+            write!(out, " [{applicability} fix]").unwrap();
+        }
+        out.push('\n');
+    }
+
+    let fixed =
+        fix_source(source, rules, Applicability::Unsafe).expect("snapshot input should parse");
+    if fixed.applied == 0 {
+        out.push_str("\n# fixed: unchanged\n");
+    } else {
+        writeln!(out, "\n# fixed ({} applied)", fixed.applied).unwrap();
+        out.push_str(&fixed.code);
+        if !fixed.code.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Compare `actual` with the snapshot at `path`, or write it there if
+/// [`UPDATE_SNAPSHOTS_ENV`] is set.
+///
+/// # Panics
+///
+/// If they differ or there is no snapshot yet, with a diff and how to
+/// update it.
+pub fn assert_snapshot(path: &Path, actual: &str) {
+    if std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some_and(|value| value != "0") {
+        if std::fs::read_to_string(path).ok().as_deref() != Some(actual) {
+            std::fs::write(path, actual)
+                .unwrap_or_else(|err| panic!("failed to write {}: {err}", path.display()));
+        }
+        return;
+    }
+
+    let Ok(expected) = std::fs::read_to_string(path) else {
+        panic!(
+            "no snapshot at {}; run with {UPDATE_SNAPSHOTS_ENV}=1 to create it:\n{actual}",
+            path.display()
+        );
+    };
+    if expected == actual {
+        return;
+    }
+
+    let mut diff = String::new();
+    for hunk in diff_lines(&expected, actual) {
+        for line in &hunk.lines {
+            match line {
+                DiffLine::Context(line) => writeln!(diff, " {line}"),
+                DiffLine::Removed(line) => writeln!(diff, "-{line}"),
+                DiffLine::Added(line) => writeln!(diff, "+{line}"),
+            }
+            .unwrap();
+        }
+    }
+    panic!(
+        "snapshot {} does not match (- snapshot, + actual); run with \
+         {UPDATE_SNAPSHOTS_ENV}=1 to accept the change:\n{diff}",
+        path.display()
+    );
+}
+
+/// The snapshot file of `input`: `Input.java` has `Input.java.snap`.
+pub fn snapshot_path(input: &Path) -> PathBuf {
+    let mut path = input.as_os_str().to_owned();
+    path.push(".snap");
+    PathBuf::from(path)
+}
+
+/// Render `input` with [`render_snapshot`] and compare it with its
+/// [`snapshot_path`].
+///
+/// # Panics
+///
+/// If `input` can't be read or parsed, or the snapshot doesn't match.
+pub fn assert_file_snapshot(rules: &[Box<dyn Rule>], input: &Path) {
+    let source = std::fs::read_to_string(input)
+        .unwrap_or_else(|err| panic!("failed to read {}: {err}", input.display()));
+    assert_snapshot(&snapshot_path(input), &render_snapshot(rules, &source));
+}
+
+#[cfg(test)]
+mod tests {
+    use lintal_linter::rules::UpperEll;
+
+    use super::*;
+
+    #[test]
+    fn test_render_snapshot() {
+        let rules: Vec<Box<dyn Rule>> = vec![Box::new(UpperEll)];
+        assert_eq!(
+            render_snapshot(&rules, "class A {\n    long a = 1l;\n}"),
+            "# diagnostics\n\
+             2:14 UpperEll (ST001): Should use uppercase 'L'. [safe fix]\n\
+             \n\
+             # fixed (1 applied)\n\
+             class A {\n    long a = 1L;\n}\n"
+        );
+        assert_eq!(
+            render_snapshot(&rules, "class A {}\n"),
+            "# diagnostics\nnone\n\n# fixed: unchanged\n"
+        );
+    }
+
+    #[test]
+    fn test_assert_snapshot() {
+        let dir = std::env::temp_dir().join(format!("lintal-snapshot-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = snapshot_path(&dir.join("Input.java"));
+        assert!(path.ends_with("Input.java.snap"));
+
+        std::fs::write(&path, "a\nb\n").unwrap();
+        assert_snapshot(&path, "a\nb\n");
+        let mismatch = std::panic::catch_unwind(|| assert_snapshot(&path, "a\nc\n"));
+        std::fs::remove_dir_all(&dir).unwrap();
+        let message = mismatch.unwrap_err();
+        let message = message.downcast_ref::<String>().unwrap();
+        assert!(message.contains("-b\n+c\n"), "{message}");
+    }
+}
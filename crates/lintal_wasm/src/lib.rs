@@ -0,0 +1,222 @@
+//! lintal for the browser.
+//!
+//! Build with `wasm-pack build crates/lintal_wasm --target web` to get a
+//! JavaScript module exporting:
+//!
+//! - `lint(source, configJson?)`, returning an array of diagnostics
+//! - `applyFixes(source, configJson?)`, returning the fixed source
+//!
+//! The configuration is a JSON object naming the rules to run and their
+//! properties, an existing checkstyle.xml, or both:
+//!
+//! ```json
+//! {
+//!   "rules": { "LineLength": { "max": 100 }, "WhitespaceAround": {} },
+//!   "checkstyle": "<module name=\"Checker\">...</module>"
+//! }
+//! ```
+//!
+//! Without a configuration, the rules are those the CLI uses when it finds no
+//! checkstyle.xml. Positions are reported in UTF-16 code units, as JavaScript
+//! strings index them.
+
+use std::collections::BTreeMap;
+
+use lintal_checkstyle::{CheckstyleConfig, CheckstyleError, MergedConfig};
+use lintal_diagnostics::Applicability;
+use lintal_linter::{Rule, RuleDiagnostic, RuleError, RuleRegistry};
+use lintal_source_file::{LineIndex, PositionEncoding};
+use lintal_text_size::TextSize;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Invalid configuration: {0}")]
+    Config(#[from] serde_json::Error),
+    #[error("Invalid checkstyle configuration: {0}")]
+    Checkstyle(#[from] CheckstyleError),
+    #[error(transparent)]
+    Rule(#[from] RuleError),
+    #[error("Failed to parse source")]
+    Parse,
+}
+
+/// The configuration accepted by [`lint`] and [`apply_fixes`].
+#[derive(Debug, Default, Deserialize)]
+struct Config {
+    /// Rules to run, with their properties.
+    #[serde(default)]
+    rules: BTreeMap<String, BTreeMap<String, serde_json::Value>>,
+    /// The contents of a checkstyle.xml whose rules are run as well.
+    checkstyle: Option<String>,
+}
+
+/// A diagnostic as handed to JavaScript.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsDiagnostic {
+    pub rule: String,
+    /// The rule's short code, e.g. `WS001`.
+    pub code: String,
+    pub message: String,
+    /// Offset of the start of the violation in the source.
+    pub start: usize,
+    pub end: usize,
+    /// One-based line and column of the start of the violation.
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    /// Whether [`apply_fixes`] can fix the violation.
+    pub fixable: bool,
+}
+
+/// Lint `source`, returning its diagnostics.
+#[wasm_bindgen]
+pub fn lint(source: &str, config_json: Option<String>) -> Result<JsValue, JsError> {
+    let diagnostics = lint_source(source, config_json.as_deref())?;
+    Ok(serde_wasm_bindgen::to_value(&diagnostics)?)
+}
+
+/// Apply every safe fix to `source`, returning the fixed source.
+#[wasm_bindgen(js_name = applyFixes)]
+pub fn apply_fixes(source: &str, config_json: Option<String>) -> Result<String, JsError> {
+    Ok(fix_source(source, config_json.as_deref())?)
+}
+
+/// The implementation of [`lint`].
+pub fn lint_source(source: &str, config_json: Option<&str>) -> Result<Vec<JsDiagnostic>, Error> {
+    let rules = create_rules(config_json)?;
+    let diagnostics = lintal_linter::lint_source(source, &rules).ok_or(Error::Parse)?;
+    let index = LineIndex::from_source_text(source);
+    Ok(diagnostics
+        .into_iter()
+        .map(|diagnostic| to_js(source, &index, diagnostic))
+        .collect())
+}
+
+/// The implementation of [`apply_fixes`].
+pub fn fix_source(source: &str, config_json: Option<&str>) -> Result<String, Error> {
+    let rules = create_rules(config_json)?;
+    lintal_linter::fix_source(source, &rules, Applicability::Safe)
+        .map(|fixed| fixed.code)
+        .ok_or(Error::Parse)
+}
+
+fn create_rules(config_json: Option<&str>) -> Result<Vec<Box<dyn Rule>>, Error> {
+    let config: Config = match config_json {
+        Some(json) if !json.trim().is_empty() => serde_json::from_str(json)?,
+        _ => Config::default(),
+    };
+
+    let mut configured: Vec<(String, BTreeMap<String, String>)> = Vec::new();
+    if let Some(xml) = &config.checkstyle {
+        let checkstyle = CheckstyleConfig::parse(xml)?;
+        for rule in MergedConfig::new(&checkstyle, None).enabled_rules() {
+            configured.push((
+                rule.name.clone(),
+                rule.properties.clone().into_iter().collect(),
+            ));
+        }
+    }
+    for (name, properties) in config.rules {
+        let properties = properties
+            .into_iter()
+            .map(|(key, value)| match value {
+                // Checkstyle properties are strings; accept `"max": 100` too
+                serde_json::Value::String(value) => (key, value),
+                value => (key, value.to_string()),
+            })
+            .collect();
+        configured.push((name, properties));
+    }
+
+    if configured.is_empty() {
+        let defaults: Vec<Box<dyn Rule>> =
+            vec![Box::new(lintal_linter::rules::WhitespaceAround::default())];
+        return Ok(defaults);
+    }
+
+    let registry = RuleRegistry::builtin();
+    configured
+        .iter()
+        .map(|(name, properties)| {
+            let properties = properties
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str()))
+                .collect();
+            Ok(registry.try_create_rule(name, &properties)?)
+        })
+        .collect()
+}
+
+fn to_js(source: &str, index: &LineIndex, rule_diagnostic: RuleDiagnostic) -> JsDiagnostic {
+    let RuleDiagnostic { rule, diagnostic } = rule_diagnostic;
+    let start = index.source_location(diagnostic.range.start(), source, PositionEncoding::Utf16);
+    let end = index.source_location(diagnostic.range.end(), source, PositionEncoding::Utf16);
+    JsDiagnostic {
+        rule: rule.to_string(),
+        code: diagnostic.kind.code,
+        message: diagnostic.kind.body,
+        start: utf16_offset(source, diagnostic.range.start()),
+        end: utf16_offset(source, diagnostic.range.end()),
+        line: start.line.get(),
+        column: start.character_offset.get(),
+        end_line: end.line.get(),
+        end_column: end.character_offset.get(),
+        fixable: diagnostic
+            .fix
+            .as_ref()
+            .is_some_and(|fix| fix.applies(Applicability::Safe)),
+    }
+}
+
+fn utf16_offset(source: &str, offset: TextSize) -> usize {
+    source[..offset.to_usize()].encode_utf16().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_with_rules() {
+        let source = "class A {\n    // é\n    long x=1l;\n}\n";
+        let config = r#"{"rules": {"UpperEll": {}, "LineLength": {"max": 10}}}"#;
+        let diagnostics = lint_source(source, Some(config)).unwrap();
+
+        let upper_ell = diagnostics.iter().find(|d| d.rule == "UpperEll").unwrap();
+        assert_eq!((upper_ell.line, upper_ell.column), (3, 12));
+        assert_eq!(&source[upper_ell.start + 1..upper_ell.end + 1], "1l");
+        assert!(diagnostics.iter().any(|d| d.rule == "LineLength"));
+    }
+
+    #[test]
+    fn test_lint_with_checkstyle_xml() {
+        let config = serde_json::json!({
+            "checkstyle": r#"<module name="Checker"><module name="TreeWalker">
+                <module name="UpperEll"/>
+            </module></module>"#
+        });
+        let diagnostics =
+            lint_source("class A { long x = 1l; }", Some(&config.to_string())).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "UpperEll");
+    }
+
+    #[test]
+    fn test_unknown_rule_is_an_error() {
+        let result = lint_source("class A {}", Some(r#"{"rules": {"NoSuchRule": {}}}"#));
+        assert!(
+            matches!(result, Err(Error::Rule(RuleError::Unknown(name))) if name == "NoSuchRule")
+        );
+    }
+
+    #[test]
+    fn test_fix_source() {
+        let fixed = fix_source("class A { int x=1; }", None).unwrap();
+        assert_eq!(fixed, "class A { int x = 1; }");
+    }
+}
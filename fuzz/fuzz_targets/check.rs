@@ -0,0 +1,35 @@
+//! Every rule's checks must accept any input, however malformed, and report
+//! ranges and fix edits inside it that don't split a character.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use lintal_fuzz::builtin_rules;
+use lintal_linter::lint_source;
+use lintal_text_size::Ranged;
+
+fuzz_target!(|source: &str| {
+    let Some(diagnostics) = lint_source(source, builtin_rules()) else {
+        return;
+    };
+    for found in diagnostics {
+        let range = found.diagnostic.range;
+        assert!(
+            source
+                .get(range.start().to_usize()..range.end().to_usize())
+                .is_some(),
+            "{} reported {range:?}, which is not a range of the source",
+            found.rule
+        );
+        for edit in found.diagnostic.fix.iter().flat_map(|fix| fix.edits()) {
+            assert!(
+                source
+                    .get(edit.start().to_usize()..edit.end().to_usize())
+                    .is_some(),
+                "{} proposed an edit of {:?}, which is not a range of the source",
+                found.rule,
+                edit.range()
+            );
+        }
+    }
+});
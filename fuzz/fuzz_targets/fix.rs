@@ -0,0 +1,56 @@
+//! Fixing a source that parses must leave a source that still parses, with
+//! nothing left for the same rule to fix.
+//!
+//! The first byte picks the rule, so each rule's fixes are checked on their
+//! own; the rest is the source.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use lintal_diagnostics::Applicability;
+use lintal_fuzz::{builtin_rules, parses_cleanly};
+use lintal_linter::fix_source;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&selector, source)) = data.split_first() else {
+        return;
+    };
+    let Ok(source) = std::str::from_utf8(source) else {
+        return;
+    };
+    // A fix can't be expected to keep code valid that wasn't to begin with
+    if !parses_cleanly(source) {
+        return;
+    }
+
+    let rules = builtin_rules();
+    let rule = &rules[usize::from(selector) % rules.len()];
+    let Some(fixed) = fix_source(source, std::slice::from_ref(rule), Applicability::Unsafe) else {
+        return;
+    };
+    if fixed.applied == 0 {
+        return;
+    }
+
+    assert!(
+        parses_cleanly(&fixed.code),
+        "{} fixes broke the source:\n{}",
+        rule.name(),
+        fixed.code
+    );
+    // Fixes are applied in passes until none apply, so anything left means
+    // a fix re-triggered its own diagnostic or they never settled
+    for remaining in &fixed.remaining {
+        assert!(
+            !remaining
+                .diagnostic
+                .fix
+                .as_ref()
+                .is_some_and(|fix| fix.applies(Applicability::Unsafe)),
+            "{} still has a fix for {:?} after fixing:\n{}",
+            rule.name(),
+            remaining.diagnostic.kind.body,
+            fixed.code
+        );
+    }
+});
@@ -0,0 +1,14 @@
+//! The parser and the tree views built on it must accept any input.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use lintal_java_parser::JavaParser;
+
+fuzz_target!(|source: &str| {
+    let Some(result) = JavaParser::new().parse(source) else {
+        return;
+    };
+    let root = result.tree.root_node();
+    assert_eq!(root.end_byte(), source.len());
+});
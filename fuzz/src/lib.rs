@@ -0,0 +1,29 @@
+//! Shared setup of the fuzz targets.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use lintal_java_parser::JavaParser;
+use lintal_linter::{Rule, RuleRegistry};
+
+/// Every built-in rule with default properties, in module name order so a
+/// rule's index is stable across runs and crash reproductions.
+pub fn builtin_rules() -> &'static [Box<dyn Rule>] {
+    static RULES: OnceLock<Vec<Box<dyn Rule>>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        let registry = RuleRegistry::builtin();
+        let mut names: Vec<&str> = registry.module_names().collect();
+        names.sort_unstable();
+        names
+            .into_iter()
+            .filter_map(|name| registry.create_rule(name, &HashMap::new()))
+            .collect()
+    })
+}
+
+/// Whether `source` parses without syntax errors.
+pub fn parses_cleanly(source: &str) -> bool {
+    JavaParser::new()
+        .parse(source)
+        .is_some_and(|result| !result.tree.root_node().has_error())
+}